@@ -5,4 +5,10 @@ export interface CodeView {
 }
 
 // FIXME(nick): use this type in the CodeView interface once we want to dynamically check the code language type.
-export type CodeLanguage = "diff" | "json" | "unknown" | "yaml" | "javascript";
+export type CodeLanguage =
+  | "diff"
+  | "hcl"
+  | "json"
+  | "unknown"
+  | "yaml"
+  | "javascript";
@@ -95,6 +95,7 @@ export interface PropertyEditorProp {
   isReadonly: boolean;
   documentation?: string;
   validationFormat?: string;
+  isSocketDriven: boolean;
 }
 
 export interface PropertyEditorSchema {
@@ -109,6 +110,8 @@ export interface PropertyEditorValue {
   id: string;
   propId: string;
   key?: string;
+  // bumped every time the value changes, used to avoid re-running validations
+  generation: number;
   value: unknown;
   isFromExternalSource: boolean;
 }
@@ -166,6 +166,7 @@ export enum ComponentType {
   ConfigurationFrameDown = "configurationFrameDown",
   ConfigurationFrameUp = "configurationFrameUp",
   AggregationFrame = "aggregationFrame",
+  Subsystem = "subsystem",
 }
 
 export type DiagramNodeDef = {
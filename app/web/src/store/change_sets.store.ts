@@ -132,13 +132,15 @@ export function useChangeSetsStore() {
             },
           });
         },
-        async APPLY_CHANGE_SET() {
+        // applying fails with a 409 listing the conflicting values unless `force` is set
+        async APPLY_CHANGE_SET(force = false) {
           if (!this.selectedChangeSet) throw new Error("Select a change set");
           return new ApiRequest<{ changeSet: ChangeSet }>({
             method: "post",
             url: "change_set/apply_change_set",
             params: {
               changeSetPk: this.selectedChangeSet.pk,
+              force,
             },
             onSuccess: (response) => {
               this.changeSetsById[response.changeSet.pk] = response.changeSet;
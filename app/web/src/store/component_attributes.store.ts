@@ -64,9 +64,20 @@ export type AttributeTreeItem = {
   arrayIndex?: number;
 };
 
+type CachedValidation = {
+  epoch: number;
+  generation: number;
+  validationFormat?: string;
+  errorMessage?: string;
+};
+
 export const useComponentAttributesStore = (componentId: ComponentId) => {
   const featureFlagsStore = useFeatureFlagsStore();
 
+  // validation results keyed by value id, reused as long as neither the value (its generation)
+  // nor the prop's validation format changed. kept out of the state so it isn't made reactive
+  const validationCache = new Map<string, CachedValidation>();
+
   const changeSetsStore = useChangeSetsStore();
   const changeSetId = changeSetsStore.selectedChangeSetId;
 
@@ -87,6 +98,8 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
           // but we'll just move into a pinia store as the first step...
           schema: null as PropertyEditorSchema | null,
           values: null as PropertyEditorValues | null,
+          // bumped when the validation cache is cleared, so schemaValidation gets recomputed
+          validationEpoch: 0,
         }),
         getters: {
           // recombine the schema + values + validations into a single nested tree that can be used by the attributes panel
@@ -182,6 +195,7 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
               );
             }
             const tree = this.domainTree;
+            const epoch = this.validationEpoch;
 
             const output = [];
             let status: "success" | "failure" | "unknown" = tree
@@ -233,19 +247,39 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
                 continue;
               }
 
-              const validationFormat = (
-                prop.propDef.validationFormat
-                  ? Joi.build(JSON.parse(prop.propDef.validationFormat))
-                  : Joi.any()
-              ) as Schema;
-
-              // NOTE(victor): Joi treats null as a value, so even if .required()
-              // isn't set it fails validations for typed props
-              const valueNotNull = value === null ? undefined : value;
-
-              const { error } = validationFormat.validate(valueNotNull);
-
-              const errorMessage = error?.message;
+              const cached = validationCache.get(prop.valueId);
+              let errorMessage: string | undefined;
+              if (
+                cached &&
+                prop.value &&
+                cached.epoch === epoch &&
+                cached.generation === prop.value.generation &&
+                cached.validationFormat === prop.propDef.validationFormat
+              ) {
+                errorMessage = cached.errorMessage;
+              } else {
+                const validationFormat = (
+                  prop.propDef.validationFormat
+                    ? Joi.build(JSON.parse(prop.propDef.validationFormat))
+                    : Joi.any()
+                ) as Schema;
+
+                // NOTE(victor): Joi treats null as a value, so even if .required()
+                // isn't set it fails validations for typed props
+                const valueNotNull = value === null ? undefined : value;
+
+                const { error } = validationFormat.validate(valueNotNull);
+
+                errorMessage = error?.message;
+                if (prop.value) {
+                  validationCache.set(prop.valueId, {
+                    epoch,
+                    generation: prop.value.generation,
+                    validationFormat: prop.propDef.validationFormat,
+                    errorMessage,
+                  });
+                }
+              }
 
               if (errorMessage) {
                 status = "failure";
@@ -328,6 +362,37 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
             });
           },
 
+          clearValidationCache() {
+            validationCache.clear();
+            this.validationEpoch += 1;
+          },
+
+          // re-run every validation of the component, for this client and anyone else looking at it
+          async REVALIDATE() {
+            this.clearValidationCache();
+            return new ApiRequest<{ success: true }>({
+              method: "post",
+              url: "component/revalidate",
+              params: {
+                componentId: this.selectedComponentId,
+                ...visibilityParams,
+              },
+            });
+          },
+
+          // check a value that is still being edited against the validations of its prop
+          async VALIDATE_VALUE(propId: string, value: unknown) {
+            return new ApiRequest<{ valid: boolean; message?: string }>({
+              method: "post",
+              url: "component/validate_value",
+              params: {
+                propId,
+                value,
+                ...visibilityParams,
+              },
+            });
+          },
+
           reloadPropertyEditorData() {
             this.FETCH_PROPERTY_EDITOR_SCHEMA();
             this.FETCH_PROPERTY_EDITOR_VALUES();
@@ -442,6 +507,14 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
                 this.reloadPropertyEditorData();
               },
             },
+            {
+              eventType: "ComponentRevalidationRequested",
+              callback: (requested) => {
+                if (requested.changeSetPk !== changeSetId) return;
+                if (requested.componentId !== this.selectedComponentId) return;
+                this.clearValidationCache();
+              },
+            },
           ]);
 
           return () => {
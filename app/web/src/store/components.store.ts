@@ -93,6 +93,15 @@ type Edge = {
   createdInfo: ActorAndTimestamp;
   // updatedInfo?: ActorAndTimestamp; // currently we dont ever update an edge...
   deletedInfo?: ActorAndTimestamp;
+  /** whether the edge was drawn by a user, created when connecting to a frame or imported */
+  creationSource: "manual" | "frameAutoConnection" | "import";
+};
+
+export type ComponentNotes = {
+  // markdown, as written
+  notes: string | null;
+  // rendered and escaped server side
+  html: string | null;
 };
 
 export interface ActorAndTimestamp {
@@ -231,6 +240,7 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
           componentCodeViewsById: {} as Record<ComponentId, CodeView[]>,
           componentResourceById: {} as Record<ComponentId, Resource>,
           componentDiffsById: {} as Record<ComponentId, ComponentDiff>,
+          componentNotesById: {} as Record<ComponentId, ComponentNotes>,
 
           rawComponentsById: {} as Record<ComponentId, RawComponent>,
 
@@ -734,6 +744,8 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
                 width,
                 height,
                 diagramKind: "configuration",
+                // the diagram connects and disconnects frames itself
+                autoFrameMembership: false,
                 ...visibilityParams,
               },
               onSuccess: (response) => {
@@ -936,6 +948,41 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
             });
           },
 
+          async FETCH_COMPONENT_NOTES(componentId: ComponentId) {
+            return new ApiRequest<ComponentNotes>({
+              url: "component/get_notes",
+              keyRequestStatusBy: componentId,
+              params: {
+                componentId,
+                ...visibilityParams,
+              },
+              onSuccess: (response) => {
+                this.componentNotesById[componentId] = response;
+              },
+            });
+          },
+
+          async SET_COMPONENT_NOTES(componentId: ComponentId, notes: string) {
+            if (changeSetsStore.creatingChangeSet)
+              throw new Error("race, wait until the change set is created");
+            if (changeSetId === nilId())
+              changeSetsStore.creatingChangeSet = true;
+
+            return new ApiRequest<ComponentNotes>({
+              method: "post",
+              url: "component/set_notes",
+              keyRequestStatusBy: componentId,
+              params: {
+                componentId,
+                notes,
+                ...visibilityParams,
+              },
+              onSuccess: (response) => {
+                this.componentNotesById[componentId] = response;
+              },
+            });
+          },
+
           async FETCH_COMPONENT_RESOURCE(componentId: ComponentId) {
             return new ApiRequest<{ resource: Resource }>({
               url: "component/get_resource",
@@ -1359,6 +1406,18 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
             });
           },
 
+          async RUN_COMPONENT_CONFIRMATIONS(componentId: ComponentId) {
+            return new ApiRequest({
+              method: "post",
+              url: "component/run_confirmations",
+              keyRequestStatusBy: componentId,
+              params: {
+                componentId,
+                ...visibilityParams,
+              },
+            });
+          },
+
           async REFRESH_ALL_RESOURCE_INFO() {
             return new ApiRequest({
               method: "post",
@@ -1420,6 +1479,16 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
                 }
               },
             },
+            {
+              eventType: "SecretRotated",
+              callback: (data) => {
+                if (data.changeSetPk !== changeSetId) return;
+                // components using the secret get re-confirmed with its new contents
+                for (const componentId of data.componentIds) {
+                  this.RUN_COMPONENT_CONFIRMATIONS(componentId);
+                }
+              },
+            },
             {
               eventType: "ResourceRefreshed",
               callback: (resourceRefreshedEvent) => {
@@ -120,6 +120,12 @@ export const useFixesStore = () => {
               this.LOAD_FIX_BATCHES();
             },
           },
+          {
+            eventType: "FixStarted",
+            callback: () => {
+              this.LOAD_FIX_BATCHES();
+            },
+          },
           {
             eventType: "FixReturn",
             callback: (update) => {
@@ -14,7 +14,7 @@ import { useWorkspacesStore } from "@/store/workspaces.store";
 import { useAssetStore } from "@/store/asset.store";
 import { useChangeSetsStore } from "../change_sets.store";
 import { useRealtimeStore } from "../realtime/realtime.store";
-import { useComponentsStore } from "../components.store";
+import { ComponentId, useComponentsStore } from "../components.store";
 
 import {
   AttributePrototypeView,
@@ -69,6 +69,26 @@ export interface SaveFuncResponse {
   success: boolean;
 }
 
+export interface FuncCodeProblem {
+  kind: string;
+  message: string;
+}
+
+export interface FuncExecutionTrace {
+  funcExecutionPk: string;
+  funcId: FuncId;
+  componentId?: ComponentId;
+  attributeValueId?: string;
+  backendKind: string;
+  success: boolean;
+  failure?: string;
+  stdout: string[];
+  stderr: string[];
+  startedAt: string;
+  finishedAt: string;
+  durationMs: number;
+}
+
 export interface DeleteFuncResponse {
   success: boolean;
 }
@@ -108,6 +128,8 @@ export const useFuncStore = () => {
         outputSockets: {} as OutputSockets,
         openFuncIds: [] as FuncId[],
         lastFuncExecutionLogByFuncId: {} as Record<FuncId, FuncExecutionLog>,
+        codeProblemsByFuncId: {} as Record<FuncId, FuncCodeProblem[]>,
+        funcExecutionTraces: [] as FuncExecutionTrace[],
       }),
       getters: {
         urlSelectedFuncId: () => {
@@ -486,6 +508,20 @@ export const useFuncStore = () => {
           });
         },
 
+        async FETCH_FUNC_EXECUTIONS(filters: {
+          componentId?: ComponentId;
+          funcId?: FuncId;
+        }) {
+          return new ApiRequest<{ executions: FuncExecutionTrace[] }>({
+            method: "get",
+            url: "func/get_func_executions",
+            params: { ...filters, ...visibility },
+            onSuccess: (response) => {
+              this.funcExecutionTraces = response.executions;
+            },
+          });
+        },
+
         async EXECUTE(executeRequest: {
           id: FuncId;
           args: unknown;
@@ -519,6 +555,18 @@ export const useFuncStore = () => {
           });
         },
 
+        async CHECK_FUNC(funcId: FuncId, code: string) {
+          return new ApiRequest<{ problems: FuncCodeProblem[] }>({
+            method: "post",
+            url: "func/check_func",
+            params: { id: funcId, code, ...visibility },
+            keyRequestStatusBy: funcId,
+            onSuccess: (response) => {
+              this.codeProblemsByFuncId[funcId] = response.problems;
+            },
+          });
+        },
+
         async CREATE_FUNC(createFuncRequest: {
           variant: FuncVariant;
           name?: string;
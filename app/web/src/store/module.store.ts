@@ -40,8 +40,11 @@ export interface PkgExportRequest {
 }
 
 export interface LocalModuleSummary {
+  id: string;
   name: string;
+  version?: string;
   hash: ModuleHash;
+  assetCount: number;
   isBuiltin: boolean;
 }
 
@@ -55,6 +58,42 @@ export interface LocalModuleDetails {
   funcs: PkgFuncView[];
   hash: ModuleHash;
   kind: "module" | "workspaceExport";
+  installedAssets: InstalledModuleAsset[];
+}
+
+export interface InstalledModuleAsset {
+  kind: "func" | "schema" | "schemaVariant" | "schemaVariantDefinition";
+  installedPkgAssetId: string;
+  installedPkgId: string;
+  id: string;
+  hash: string;
+}
+
+export interface InstalledModuleAssets {
+  name: string;
+  version?: string;
+  hash: ModuleHash;
+  assets: InstalledModuleAsset[];
+}
+
+export interface ModuleUninstall {
+  installedPkgId: string;
+  name: string;
+  version?: string;
+  removedAssets: InstalledModuleAsset[];
+  sharedAssets: InstalledModuleAsset[];
+}
+
+export interface ModuleUpgrade {
+  installedPkgId: string;
+  previousInstalledPkgId: string;
+  name: string;
+  previousVersion?: string;
+  version: string;
+  unchangedSchemas: string[];
+  upgradedSchemas: string[];
+  addedSchemas: string[];
+  schemaVariantIds: string[];
 }
 
 export interface ModuleSpec {
@@ -219,6 +258,36 @@ export const useModuleStore = () => {
             });
           },
 
+          async LOAD_INSTALLED_MODULE_ASSETS(installedPkgId: string) {
+            return new ApiRequest<InstalledModuleAssets>({
+              method: "get",
+              url: "/pkg/list_installed_pkg_assets",
+              params: { installedPkgId, ...visibility },
+            });
+          },
+
+          async UNINSTALL_MODULE(installedPkgId: string) {
+            return new ApiRequest<ModuleUninstall>({
+              method: "post",
+              url: "/pkg/uninstall_pkg",
+              params: { installedPkgId, ...visibility },
+              onSuccess: (_response) => {
+                this.LOAD_LOCAL_MODULES();
+              },
+            });
+          },
+
+          async UPGRADE_MODULE(installedPkgId: string, moduleId: ModuleId) {
+            return new ApiRequest<ModuleUpgrade>({
+              method: "post",
+              url: "/pkg/upgrade_pkg",
+              params: { installedPkgId, id: moduleId, ...visibility },
+              onSuccess: (_response) => {
+                this.LOAD_LOCAL_MODULES();
+              },
+            });
+          },
+
           async LIST_WORKSPACE_EXPORTS() {
             return new ModuleIndexApiRequest<{
               modules: (RemoteModuleSummary & {
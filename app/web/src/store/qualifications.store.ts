@@ -235,11 +235,21 @@ export const useQualificationsStore = () => {
           const realtimeStore = useRealtimeStore();
           realtimeStore.subscribe(this.$id, `changeset/${changeSetId}`, [
             {
-              eventType: "ComponentUpdated",
+              eventType: "QualificationStatusChanged",
               callback: (data) => {
-                if (data.changeSetPk === changeSetId) {
-                  this.FETCH_QUALIFICATIONS_SUMMARY();
+                if (data.changeSetPk !== changeSetId) return;
+                if (data.deleted) {
+                  delete this.qualificationStatsByComponentIdRaw[data.componentId];
+                  return;
                 }
+                const { total, succeeded, warned, failed } = data;
+                this.qualificationStatsByComponentIdRaw[data.componentId] = {
+                  total,
+                  succeeded,
+                  warned,
+                  failed,
+                  running: total - succeeded - failed - warned,
+                };
               },
             },
             {
@@ -98,10 +98,27 @@ export type WsEventPayloadMap = {
     componentId: string;
   };
 
+  CodeArtifactGenerated: {
+    componentId: string;
+    funcName: string;
+    format: string | null;
+  };
+
   CodeGenerated: {
     componentId: string;
   };
 
+  QualificationStatusChanged: {
+    componentId: string;
+    componentName: string;
+    changeSetPk: string;
+    total: number;
+    warned: number;
+    succeeded: number;
+    failed: number;
+    deleted: boolean;
+  };
+
   LogLine: {
     stream: {
       stream: string;
@@ -145,6 +162,12 @@ export type WsEventPayloadMap = {
     output: string[];
     status: FixStatus;
   };
+  FixStarted: {
+    id: string;
+    batchId: string;
+    componentId: string;
+    action: string;
+  };
   FixBatchReturn: {
     id: string;
     status: FixStatus;
@@ -156,9 +179,20 @@ export type WsEventPayloadMap = {
     componentId: string;
     changeSetPk: string;
   };
+  ComponentRevalidationRequested: {
+    componentId: string;
+    changeSetPk: string;
+  };
   ModuleImported: {
     schemaVariantIds: string[];
   };
+  AttributeValueUpdated: {
+    componentId: ComponentId;
+    attributeValueId: AttributeValueId;
+    propPath: string;
+    value: unknown;
+    changeSetPk: ChangeSetId;
+  };
   WorkspaceImportBeginApprovalProcess: {
     workspacePk: WorkspacePk;
     userPk: UserId;
@@ -224,6 +258,12 @@ export type WsEventPayloadMap = {
     secretId: SecretId;
     changeSetPk: ChangeSetId;
   };
+  SecretRotated: {
+    secretId: SecretId;
+    revision: number;
+    componentIds: ComponentId[];
+    changeSetPk: ChangeSetId;
+  };
   SchemaVariantDefinitionCreated: {
     schemaVariantDefinitionId: string;
     changeSetPk: ChangeSetId;
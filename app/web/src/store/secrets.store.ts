@@ -61,6 +61,7 @@ export type Secret = {
   definition: SecretDefinitionId;
   name: string;
   description?: string;
+  revision: number;
   createdInfo: ActorAndTimestamp;
   updatedInfo?: ActorAndTimestamp;
   expiration?: string;
@@ -500,6 +501,13 @@ export function useSecretsStore() {
                 this.LOAD_SECRETS();
               },
             },
+            {
+              eventType: "SecretRotated",
+              callback: (data) => {
+                if (data.changeSetPk !== changeSetId) return;
+                this.LOAD_SECRETS();
+              },
+            },
           ]);
         },
       },
@@ -14,9 +14,13 @@ import resolver_function, {
 import schema_variant_definition, {
   SchemaVariantDefinitionFunc,
 } from "./function_kinds/schema_variant_definition";
-import action_run, { ActionRunFunc } from "./function_kinds/action_run";
+import action_run, {
+  ActionRunFunc,
+  ActionRunResultSuccess,
+} from "./function_kinds/action_run";
 import before from "./function_kinds/before";
 import { rawStorageRequest } from "./sandbox/requestStorage";
+import { DryRunPlan } from "./sandbox/dryRun";
 import { Debugger } from "./debug";
 
 export enum FunctionKind {
@@ -192,10 +196,17 @@ export async function executor<F extends Func, Result>(
 
   debug({ code });
 
-  const vm = createNodeVm(createSandbox(kind, ctx.executionId));
+  const isDryRun = kind === FunctionKind.ActionRun && !!(func as ActionRunFunc).dryRun;
+  const dryRunPlan: DryRunPlan | undefined = isDryRun ? [] : undefined;
+
+  const vm = createNodeVm(createSandbox(kind, ctx.executionId, dryRunPlan));
 
   const result = await execute(vm, ctx, func, code);
   debug({ result });
 
+  if (dryRunPlan && (result as ResultSuccess).status === "success") {
+    (result as ActionRunResultSuccess).plan = dryRunPlan;
+  }
+
   return result;
 }
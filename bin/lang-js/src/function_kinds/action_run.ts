@@ -8,11 +8,14 @@ import {
   ResultSuccess,
 } from "../function";
 import { RequestCtx } from "../request";
+import { DryRunPlan } from "../sandbox/dryRun";
 
 const debug = Debug("langJs:actionRun");
 
 export interface ActionRunFunc extends Func {
   args: unknown;
+  // Plan-only mode, see `DryRunPlan`
+  dryRun?: boolean;
 }
 
 export type ActionRunResult = ActionRunResultSuccess | ActionRunResultFailure;
@@ -21,6 +24,7 @@ export interface ActionRunResultSuccess extends ResultSuccess {
   payload: unknown;
   health: "ok" | "warning" | "error";
   message?: string;
+  plan?: DryRunPlan;
 }
 
 export type ActionRunResultFailure = ResultFailure;
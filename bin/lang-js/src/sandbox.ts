@@ -11,6 +11,11 @@ import Joi from "joi";
 import { FunctionKind } from "./function";
 import { makeConsole } from "./sandbox/console";
 import { makeExec } from "./sandbox/exec";
+import {
+  DryRunPlan,
+  makeDryRunExec,
+  makeDryRunFetch,
+} from "./sandbox/dryRun";
 import * as assetBuilder from "./asset_builder";
 import {
   makeBeforeRequestStorage,
@@ -51,6 +56,21 @@ function schemaVariantDefinitionSandbox(): Sandbox {
   };
 }
 
+// Validations check values against the Joi descriptions props are authored with
+function validationSandbox(): Sandbox {
+  return {
+    Joi,
+  };
+}
+
+// Commands and requests are recorded in the plan instead of being performed
+function dryRunSandbox(executionId: string, plan: DryRunPlan): Sandbox {
+  return {
+    siExec: makeDryRunExec(executionId, plan),
+    fetch: makeDryRunFetch(executionId, plan),
+  };
+}
+
 function beforeFunctionSandbox(executionId: string): Sandbox {
   return {
     requestStorage: makeBeforeRequestStorage(executionId),
@@ -60,6 +80,7 @@ function beforeFunctionSandbox(executionId: string): Sandbox {
 export function createSandbox(
   kind: FunctionKind,
   executionId: string,
+  dryRunPlan?: DryRunPlan,
 ): Sandbox {
   let sandbox = commonSandbox(executionId);
 
@@ -70,6 +91,12 @@ export function createSandbox(
         ...schemaVariantDefinitionSandbox(),
       };
       break;
+    case FunctionKind.Validation:
+      sandbox = {
+        ...sandbox,
+        ...validationSandbox(),
+      };
+      break;
     case FunctionKind.Before:
       sandbox = {
         ...sandbox,
@@ -80,5 +107,12 @@ export function createSandbox(
       break;
   }
 
+  if (dryRunPlan) {
+    sandbox = {
+      ...sandbox,
+      ...dryRunSandbox(executionId, dryRunPlan),
+    };
+  }
+
   return sandbox;
 }
@@ -0,0 +1,117 @@
+import * as _ from "lodash-es";
+import { ExecaReturnValue, Options } from "execa";
+import { RequestInfo, RequestInit, Response } from "node-fetch";
+import { Debug } from "../debug";
+import { SiExecResult, WatchArgs, WatchResult } from "./exec";
+
+const debug = Debug("langJs:dryRun");
+
+export type DryRunStep =
+  | {
+    kind: "command";
+    cmd: string;
+    args: readonly string[];
+    rendered: string;
+  }
+  | {
+    kind: "request";
+    url: string;
+    method: string;
+    body?: string;
+  };
+
+export type DryRunPlan = DryRunStep[];
+
+// Commands "succeed" with an empty JSON object so that functions parsing their output keep going
+const DRY_RUN_OUTPUT = "{}";
+
+const logStep = (executionId: string, message: string) => {
+  console.log(
+    JSON.stringify({
+      protocol: "output",
+      executionId,
+      stream: "stderr",
+      level: "debug",
+      group: "log",
+      message,
+    }),
+  );
+};
+
+// Records the CLI commands a function would run instead of running them, with the same interface
+// as `makeExec`
+export const makeDryRunExec = (executionId: string, plan: DryRunPlan) => {
+  async function waitUntilEnd(
+    execaFile: string,
+    execaArgs?: readonly string[],
+    _execaOptions?: Options<string>,
+  ): Promise<SiExecResult> {
+    const args = execaArgs ?? [];
+    const rendered = `${execaFile} ${args.map((a) => `'${a}'`).join(" ")}`;
+    debug(`recording command; executionId="${executionId}"; cmd="${rendered}"`);
+    logStep(executionId, `Dry run, not running CLI command: "${rendered}"`);
+    plan.push({
+      kind: "command",
+      cmd: execaFile,
+      args,
+      rendered,
+    });
+
+    const result: ExecaReturnValue<string> = {
+      command: rendered,
+      escapedCommand: rendered,
+      exitCode: 0,
+      stdout: DRY_RUN_OUTPUT,
+      stderr: "",
+      all: DRY_RUN_OUTPUT,
+      failed: false,
+      timedOut: false,
+      killed: false,
+      isCanceled: false,
+    };
+    return result;
+  }
+
+  // The callback is never evaluated: nothing ran, so there is nothing to wait for
+  async function watch(options: WatchArgs): Promise<WatchResult> {
+    const result = await waitUntilEnd(
+      options.cmd,
+      options.args,
+      options.execaOptions,
+    );
+    return { result };
+  }
+
+  return { waitUntilEnd, watch };
+};
+
+// Records the HTTP requests a function would send instead of sending them, with the same interface
+// as `fetch`
+export const makeDryRunFetch = (executionId: string, plan: DryRunPlan) =>
+  async function dryRunFetch(
+    url: RequestInfo,
+    init?: RequestInit,
+  ): Promise<Response> {
+    let target: string;
+    if (_.isString(url)) {
+      target = url;
+    } else if ("url" in url) {
+      target = url.url;
+    } else {
+      target = url.href;
+    }
+    const method = init?.method ?? "GET";
+    debug(`recording request; executionId="${executionId}"; request="${method} ${target}"`);
+    logStep(executionId, `Dry run, not sending request: "${method} ${target}"`);
+    plan.push({
+      kind: "request",
+      url: target,
+      method,
+      body: _.isString(init?.body) ? init?.body : undefined,
+    });
+
+    return new Response(DRY_RUN_OUTPUT, {
+      status: 200,
+      headers: { "Content-Type": "application/json" },
+    });
+  };
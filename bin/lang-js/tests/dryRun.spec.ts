@@ -0,0 +1,50 @@
+import { describe, expect, test } from "vitest";
+import { DryRunPlan, makeDryRunExec, makeDryRunFetch } from "../src/sandbox/dryRun";
+
+describe("dryRun", () => {
+  test("records commands instead of running them", async () => {
+    const plan: DryRunPlan = [];
+    const e = makeDryRunExec("p", plan);
+    const r = await e.waitUntilEnd("bigGunsBangBang", ["--poop", "canoe"]);
+    expect(r.failed).toBe(false);
+    expect(JSON.parse(r.stdout)).toEqual({});
+    expect(plan).toEqual([
+      {
+        kind: "command",
+        cmd: "bigGunsBangBang",
+        args: ["--poop", "canoe"],
+        rendered: "bigGunsBangBang '--poop' 'canoe'",
+      },
+    ]);
+  });
+
+  test("never waits on watched commands", async () => {
+    const plan: DryRunPlan = [];
+    const e = makeDryRunExec("p", plan);
+    const r = await e.watch({
+      cmd: "date",
+      args: ["+%s"],
+      callback: async () => false,
+    });
+    expect(r.failed).toBeUndefined();
+    expect(plan).toHaveLength(1);
+  });
+
+  test("records requests instead of sending them", async () => {
+    const plan: DryRunPlan = [];
+    const f = makeDryRunFetch("p", plan);
+    const r = await f("https://example.com/poop", {
+      method: "POST",
+      body: "{\"canoe\":true}",
+    });
+    expect(r.status).toBe(200);
+    expect(plan).toEqual([
+      {
+        kind: "request",
+        url: "https://example.com/poop",
+        method: "POST",
+        body: "{\"canoe\":true}",
+      },
+    ]);
+  });
+});
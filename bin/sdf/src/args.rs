@@ -67,6 +67,11 @@ pub(crate) struct Args {
     #[arg(long, value_parser = PossibleValuesParser::new(MigrationMode::variants()))]
     pub(crate) migration_mode: Option<String>,
 
+    /// Builtin schema families to migrate on startup: "all", "none" or a comma separated list
+    /// (e.g. "aws,generic-frame")
+    #[arg(long)]
+    pub(crate) builtin_schema_migration_mode: Option<String>,
+
     /// Disable OpenTelemetry on startup
     #[arg(long)]
     pub(crate) disable_opentelemetry: bool,
@@ -105,6 +110,14 @@ pub(crate) struct Args {
     /// The base URL for the module-index API server
     #[arg(long, env = "SI_MODULE_INDEX_URL")]
     pub(crate) module_index_url: Option<String>,
+
+    /// The maximum size of request bodies, in bytes
+    #[arg(long)]
+    pub(crate) body_limit_bytes: Option<u32>,
+
+    /// The maximum size of request bodies for routes receiving large payloads, in bytes
+    #[arg(long)]
+    pub(crate) large_body_limit_bytes: Option<u32>,
 }
 
 impl TryFrom<Args> for Config {
@@ -136,6 +149,12 @@ impl TryFrom<Args> for Config {
             if let Some(migration_mode) = args.migration_mode {
                 config_map.set("migration_mode", migration_mode);
             }
+            if let Some(builtin_schema_migration_mode) = args.builtin_schema_migration_mode {
+                config_map.set(
+                    "builtin_schema_migration_mode",
+                    builtin_schema_migration_mode,
+                );
+            }
             if let Some(url) = args.nats_url {
                 config_map.set("nats.url", url);
             }
@@ -166,6 +185,12 @@ impl TryFrom<Args> for Config {
             if let Some(module_index_url) = args.module_index_url {
                 config_map.set("module_index_url", module_index_url);
             }
+            if let Some(body_limit_bytes) = args.body_limit_bytes {
+                config_map.set("body_limits.default_bytes", i64::from(body_limit_bytes));
+            }
+            if let Some(large_body_limit_bytes) = args.large_body_limit_bytes {
+                config_map.set("body_limits.large_bytes", i64::from(large_body_limit_bytes));
+            }
 
             config_map.set("nats.connection_name", NAME);
             config_map.set("pg.application_name", NAME);
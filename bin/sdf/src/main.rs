@@ -96,7 +96,7 @@ async fn async_main() -> Result<()> {
     );
 
     if let MigrationMode::Run | MigrationMode::RunAndQuit = config.migration_mode() {
-        Server::migrate_database(&services_context).await?;
+        Server::migrate_database(&services_context, config.builtin_schema_migration_mode()).await?;
         if let MigrationMode::RunAndQuit = config.migration_mode() {
             info!(
                 "migration mode is {}, shutting down",
@@ -121,6 +121,10 @@ async fn async_main() -> Result<()> {
                 posthog_client,
             )?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let fourth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let fifth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let sixth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
 
             Server::start_resource_refresh_scheduler(
                 services_context.clone(),
@@ -128,6 +132,21 @@ async fn async_main() -> Result<()> {
             )
             .await;
 
+            Server::start_digest_scheduler(services_context.clone(), third_shutdown_broadcast_rx)
+                .await;
+
+            Server::start_retention_scheduler(
+                services_context.clone(),
+                fourth_shutdown_broadcast_rx,
+            )
+            .await;
+
+            Server::start_garbage_collector(services_context.clone(), sixth_shutdown_broadcast_rx)
+                .await;
+
+            Server::start_webhook_dispatcher(services_context.clone(), fifth_shutdown_broadcast_rx)
+                .await;
+
             Server::start_status_updater(services_context, second_shutdown_broadcast_rx).await?;
 
             server.run().await?;
@@ -141,6 +160,10 @@ async fn async_main() -> Result<()> {
             )
             .await?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let fourth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let fifth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let sixth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
 
             Server::start_resource_refresh_scheduler(
                 services_context.clone(),
@@ -148,6 +171,21 @@ async fn async_main() -> Result<()> {
             )
             .await;
 
+            Server::start_digest_scheduler(services_context.clone(), third_shutdown_broadcast_rx)
+                .await;
+
+            Server::start_retention_scheduler(
+                services_context.clone(),
+                fourth_shutdown_broadcast_rx,
+            )
+            .await;
+
+            Server::start_garbage_collector(services_context.clone(), sixth_shutdown_broadcast_rx)
+                .await;
+
+            Server::start_webhook_dispatcher(services_context.clone(), fifth_shutdown_broadcast_rx)
+                .await;
+
             Server::start_status_updater(services_context, second_shutdown_broadcast_rx).await?;
 
             server.run().await?;
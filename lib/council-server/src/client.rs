@@ -2,6 +2,7 @@ use futures::StreamExt;
 use si_data_nats::{NatsClient, Subject, Subscriber};
 use std::time::Duration;
 use telemetry::prelude::*;
+use telemetry_nats::empty_injected_headers;
 
 use crate::SubjectGenerator;
 use crate::{Graph, Id, Request, Response};
@@ -37,59 +38,58 @@ pub struct PubClient {
 }
 
 impl PubClient {
+    #[instrument(
+        name = "council_client.register_dependency_graph",
+        skip_all,
+        level = "info"
+    )]
     pub async fn register_dependency_graph(&self, dependency_graph: Graph) -> ClientResult<()> {
-        let message = serde_json::to_vec(&Request::ValueDependencyGraph {
+        self.publish(&Request::ValueDependencyGraph {
             change_set_id: self.change_set_id,
             dependency_graph,
-        })?;
-        self.nats
-            .publish_with_reply(
-                self.pub_channel.clone(),
-                self.reply_channel.clone(),
-                message.into(),
-            )
-            .await?;
-        Ok(())
+        })
+        .await
     }
 
+    #[instrument(name = "council_client.processed_value", skip(self), level = "info")]
     pub async fn processed_value(&self, node_id: Id) -> ClientResult<()> {
-        let message = serde_json::to_vec(&Request::ProcessedValue {
+        self.publish(&Request::ProcessedValue {
             change_set_id: self.change_set_id,
             node_id,
-        })?;
-        self.nats
-            .publish_with_reply(
-                self.pub_channel.clone(),
-                self.reply_channel.clone(),
-                message.into(),
-            )
-            .await?;
-        Ok(())
+        })
+        .await
     }
 
+    #[instrument(
+        name = "council_client.failed_processing_value",
+        skip(self),
+        level = "info"
+    )]
     pub async fn failed_processing_value(&self, node_id: Id) -> ClientResult<()> {
-        let message = serde_json::to_vec(&Request::ValueProcessingFailed {
+        self.publish(&Request::ValueProcessingFailed {
             change_set_id: self.change_set_id,
             node_id,
-        })?;
-        self.nats
-            .publish_with_reply(
-                self.pub_channel.clone(),
-                self.reply_channel.clone(),
-                message.into(),
-            )
-            .await?;
-        Ok(())
+        })
+        .await
     }
 
+    #[instrument(name = "council_client.bye", skip_all, level = "info")]
     pub async fn bye(self) -> ClientResult<()> {
-        let message = serde_json::to_vec(&Request::Bye {
+        self.publish(&Request::Bye {
             change_set_id: self.change_set_id,
-        })?;
+        })
+        .await
+    }
+
+    /// Publishes a request to council, propagating the current trace so that council's
+    /// coordination shows up in the same distributed trace as the job.
+    async fn publish(&self, request: &Request) -> ClientResult<()> {
+        let message = serde_json::to_vec(request)?;
         self.nats
-            .publish_with_reply(
+            .publish_with_reply_and_headers(
                 self.pub_channel.clone(),
                 self.reply_channel.clone(),
+                empty_injected_headers(),
                 message.into(),
             )
             .await?;
@@ -3,6 +3,7 @@ use graph::ChangeSetGraph;
 use si_data_nats::{NatsClient, Subject, Subscriber};
 use std::time::Duration;
 use telemetry::prelude::*;
+use telemetry_nats::NatsMakeSpan;
 use tokio::{signal, sync::watch};
 
 use crate::subject_generator::{ManagementChannel, ManagementReplyChannel};
@@ -142,7 +143,7 @@ impl Server {
             let sleep = tokio::time::sleep(Duration::from_secs(60));
             tokio::pin!(sleep);
             // FIXME: handle timeouts
-            let (reply_channel, request) = tokio::select! {
+            let (reply_channel, request, process_span) = tokio::select! {
                 _ = &mut sleep => {
                     if !complete_graph.is_empty() {
                         warn!(?complete_graph, "Council has values in graph but has been waiting for messages for 60 seconds");
@@ -151,7 +152,11 @@ impl Server {
                 }
                 req = subscriber.next() => match req {
                     Some(msg) => match (serde_json::from_slice::<Request>(msg.payload()), msg.reply()) {
-                        (Ok(req), Some(reply)) => (reply.to_owned(), req),
+                        (Ok(req), Some(reply)) => {
+                            // Continue the trace of the job that sent the request, if any
+                            let process_span = NatsMakeSpan::new().make_span(&msg, msg.subject());
+                            (reply.to_owned(), req, process_span)
+                        }
                         (Err(err), _) => {
                             error!("Unable to deserialize request: {err}");
                             continue;
@@ -181,59 +186,63 @@ impl Server {
             // Cache the reply channel in case we are missing dependency data and need to restart.
             let cached_reply_channel = reply_channel.clone();
 
-            let (result, discrim) = match request {
-                Request::ValueDependencyGraph {
-                    change_set_id,
-                    dependency_graph,
-                } => (
-                    register_graph_from_job(
-                        complete_graph,
-                        reply_channel,
+            let (result, discrim) = async {
+                match request {
+                    Request::ValueDependencyGraph {
                         change_set_id,
                         dependency_graph,
-                    )
-                    .await,
-                    RequestDiscriminants::ValueDependencyGraph,
-                ),
-                Request::ProcessedValue {
-                    change_set_id,
-                    node_id,
-                } => (
-                    job_processed_a_value(
-                        &self.nats,
-                        complete_graph,
-                        reply_channel,
+                    } => (
+                        register_graph_from_job(
+                            complete_graph,
+                            reply_channel,
+                            change_set_id,
+                            dependency_graph,
+                        )
+                        .await,
+                        RequestDiscriminants::ValueDependencyGraph,
+                    ),
+                    Request::ProcessedValue {
                         change_set_id,
                         node_id,
-                    )
-                    .await,
-                    RequestDiscriminants::ProcessedValue,
-                ),
-                Request::Bye { change_set_id } => (
-                    job_is_going_away(complete_graph, reply_channel, change_set_id).await,
-                    RequestDiscriminants::Bye,
-                ),
-                Request::ValueProcessingFailed {
-                    change_set_id,
-                    node_id,
-                } => (
-                    job_failed_processing_a_value(
-                        &self.nats,
-                        complete_graph,
-                        reply_channel,
+                    } => (
+                        job_processed_a_value(
+                            &self.nats,
+                            complete_graph,
+                            reply_channel,
+                            change_set_id,
+                            node_id,
+                        )
+                        .await,
+                        RequestDiscriminants::ProcessedValue,
+                    ),
+                    Request::Bye { change_set_id } => (
+                        job_is_going_away(complete_graph, reply_channel, change_set_id).await,
+                        RequestDiscriminants::Bye,
+                    ),
+                    Request::ValueProcessingFailed {
                         change_set_id,
                         node_id,
-                    )
-                    .await,
-                    RequestDiscriminants::ValueProcessingFailed,
-                ),
-                Request::Restart => {
-                    debug!(
-                        %management_channel, %management_reply_channel, "found restart request sent to everyone subscribing to management channel: no-op"
-                    );
-                    (Ok(()), RequestDiscriminants::Restart)
+                    } => (
+                        job_failed_processing_a_value(
+                            &self.nats,
+                            complete_graph,
+                            reply_channel,
+                            change_set_id,
+                            node_id,
+                        )
+                        .await,
+                        RequestDiscriminants::ValueProcessingFailed,
+                    ),
+                    Request::Restart => {
+                        debug!(
+                            %management_channel, %management_reply_channel, "found restart request sent to everyone subscribing to management channel: no-op"
+                        );
+                        (Ok(()), RequestDiscriminants::Restart)
+                    }
                 }
-            };
+            }
+            .instrument(process_span)
+            .await;
 
             match result {
                 Ok(()) => match discrim {
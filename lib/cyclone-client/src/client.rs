@@ -1139,6 +1139,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            dry_run: false,
         };
 
         // Start the protocol
@@ -1226,6 +1227,7 @@ mod tests {
                 }"#,
             ),
             before: vec![],
+            dry_run: false,
         };
 
         // Start the protocol
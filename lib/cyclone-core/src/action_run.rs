@@ -9,6 +9,10 @@ pub struct ActionRunRequest {
     pub code_base64: String,
     pub args: serde_json::Value,
     pub before: Vec<BeforeFunction>,
+    /// Plan-only mode: CLI commands and HTTP requests are recorded in the result's `plan` rather
+    /// than performed.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[remain::sorted]
@@ -29,4 +33,7 @@ pub struct ActionRunResultSuccess {
     pub message: Option<String>,
     // Collects the error if the function throws
     pub error: Option<String>,
+    /// What a dry run would have done (rendered CLI commands and HTTP requests), in order.
+    #[serde(default)]
+    pub plan: Vec<serde_json::Value>,
 }
@@ -15,6 +15,8 @@ pub struct LangServerActionRunResultSuccess {
     // Collects the error if the function throws
     #[serde(default)]
     pub error: Option<String>,
+    #[serde(default)]
+    pub plan: Vec<serde_json::Value>,
 }
 
 impl From<LangServerActionRunResultSuccess> for ActionRunResultSuccess {
@@ -25,6 +27,7 @@ impl From<LangServerActionRunResultSuccess> for ActionRunResultSuccess {
             status: value.health,
             message: value.message,
             payload: value.payload,
+            plan: value.plan,
         }
     }
 }
@@ -0,0 +1,379 @@
+//! This module provides [`FaultInjector`], which lets integration tests exercise resilience
+//! behaviors (job retries, transaction rollback, council recovery) deterministically.
+//!
+//! A test built with faults talks to PostgreSQL and NATS through local TCP proxies. The proxies
+//! understand just enough of each wire protocol to count PostgreSQL queries and NATS publishes,
+//! and to fail, drop or delay them as the [`FaultInjector`] is configured. To keep the PostgreSQL
+//! conversation readable, the proxy refuses SSL, which the connection pool then falls back from.
+
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use telemetry::prelude::*;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+};
+
+/// The NATS port used when the configured URL does not have one.
+const NATS_DEFAULT_PORT: u16 = 4222;
+
+/// The codes of the PostgreSQL startup messages asking to encrypt the connection.
+const PG_SSL_REQUEST_CODE: i32 = 80877103;
+const PG_GSSENC_REQUEST_CODE: i32 = 80877104;
+
+/// The faults to inject once a test's setup is done, usually set through the options of the test
+/// macro:
+///
+/// ```ignore
+/// #[test(pg_fail_every = 3, nats_latency_ms = 50)]
+/// async fn survives_a_flaky_database(ctx: &DalContext) {
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FaultConfig {
+    /// The number of messages published to NATS to drop before delivering the rest.
+    pub nats_drop_messages: u64,
+    /// How long to hold every message published to NATS.
+    pub nats_latency: Duration,
+    /// Fail every kth PostgreSQL query by severing its connection.
+    pub pg_fail_every: Option<u64>,
+    /// How long to hold every PostgreSQL query.
+    pub pg_latency: Duration,
+}
+
+#[derive(Debug, Default)]
+struct FaultState {
+    nats_drop_remaining: AtomicU64,
+    nats_dropped: AtomicU64,
+    nats_latency_ms: AtomicU64,
+    /// Zero means never.
+    pg_fail_every: AtomicU64,
+    pg_queries: AtomicU64,
+    pg_failed: AtomicU64,
+    pg_latency_ms: AtomicU64,
+}
+
+/// Controls the faults injected into a test's PostgreSQL and NATS connections.
+///
+/// Tests can take this as an argument to change the faults as they go, e.g. to only start failing
+/// queries once some objects were created.
+#[derive(Clone, Debug, Default)]
+pub struct FaultInjector {
+    state: Arc<FaultState>,
+}
+
+impl FaultInjector {
+    /// Replaces the faults being injected and resets the PostgreSQL query count.
+    pub fn apply(&self, config: FaultConfig) {
+        self.drop_nats_messages(config.nats_drop_messages);
+        self.set_nats_latency(config.nats_latency);
+        self.fail_every_pg_query(config.pg_fail_every);
+        self.set_pg_latency(config.pg_latency);
+    }
+
+    /// Stops injecting faults.
+    pub fn clear(&self) {
+        self.apply(FaultConfig::default());
+    }
+
+    /// Drops the next `count` messages published to NATS.
+    pub fn drop_nats_messages(&self, count: u64) {
+        self.state
+            .nats_drop_remaining
+            .store(count, Ordering::SeqCst);
+    }
+
+    /// The number of NATS messages dropped so far.
+    pub fn nats_messages_dropped(&self) -> u64 {
+        self.state.nats_dropped.load(Ordering::SeqCst)
+    }
+
+    pub fn set_nats_latency(&self, latency: Duration) {
+        self.state
+            .nats_latency_ms
+            .store(duration_as_millis(latency), Ordering::SeqCst);
+    }
+
+    /// Fails every kth PostgreSQL query from now on, counting from one. `None` stops failing
+    /// queries.
+    pub fn fail_every_pg_query(&self, every: Option<u64>) {
+        self.state.pg_queries.store(0, Ordering::SeqCst);
+        self.state
+            .pg_fail_every
+            .store(every.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    /// The number of PostgreSQL queries failed so far.
+    pub fn pg_queries_failed(&self) -> u64 {
+        self.state.pg_failed.load(Ordering::SeqCst)
+    }
+
+    pub fn set_pg_latency(&self, latency: Duration) {
+        self.state
+            .pg_latency_ms
+            .store(duration_as_millis(latency), Ordering::SeqCst);
+    }
+
+    fn should_drop_nats_message(&self) -> bool {
+        let dropped = self
+            .state
+            .nats_drop_remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .is_ok();
+        if dropped {
+            self.state.nats_dropped.fetch_add(1, Ordering::SeqCst);
+        }
+        dropped
+    }
+
+    fn should_fail_pg_query(&self) -> bool {
+        let every = self.state.pg_fail_every.load(Ordering::SeqCst);
+        if every == 0 {
+            return false;
+        }
+        let count = self.state.pg_queries.fetch_add(1, Ordering::SeqCst) + 1;
+        let fail = count % every == 0;
+        if fail {
+            self.state.pg_failed.fetch_add(1, Ordering::SeqCst);
+        }
+        fail
+    }
+
+    fn nats_latency(&self) -> Duration {
+        Duration::from_millis(self.state.nats_latency_ms.load(Ordering::SeqCst))
+    }
+
+    fn pg_latency(&self) -> Duration {
+        Duration::from_millis(self.state.pg_latency_ms.load(Ordering::SeqCst))
+    }
+
+    /// Starts a proxy to the PostgreSQL server at `hostname:port`, returning its address.
+    pub(crate) async fn proxy_pg(&self, hostname: &str, port: u16) -> io::Result<SocketAddr> {
+        self.proxy(Protocol::Postgres, format!("{hostname}:{port}"))
+            .await
+    }
+
+    /// Starts a proxy to the NATS server at `url`, returning its address.
+    pub(crate) async fn proxy_nats(&self, url: &str) -> io::Result<SocketAddr> {
+        let address = url.rsplit("://").next().unwrap_or(url);
+        let upstream = if address.contains(':') {
+            address.to_owned()
+        } else {
+            format!("{address}:{NATS_DEFAULT_PORT}")
+        };
+        self.proxy(Protocol::Nats, upstream).await
+    }
+
+    async fn proxy(&self, protocol: Protocol, upstream: String) -> io::Result<SocketAddr> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+
+        let injector = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let client = match listener.accept().await {
+                    Ok((client, _)) => client,
+                    Err(err) => {
+                        warn!(error = ?err, ?protocol, "fault proxy failed to accept connection");
+                        continue;
+                    }
+                };
+                let upstream = upstream.clone();
+                let injector = injector.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = injector.proxy_connection(protocol, client, &upstream).await {
+                        debug!(error = ?err, ?protocol, "fault proxy connection closed");
+                    }
+                });
+            }
+        });
+
+        Ok(address)
+    }
+
+    async fn proxy_connection(
+        &self,
+        protocol: Protocol,
+        client: TcpStream,
+        upstream: &str,
+    ) -> io::Result<()> {
+        let server = TcpStream::connect(upstream).await?;
+        let (mut client_read, mut client_write) = client.into_split();
+        let (server_read, mut server_write) = server.into_split();
+
+        // Whichever direction finishes first closes both connections
+        match protocol {
+            Protocol::Nats => {
+                tokio::select! {
+                    result = self.proxy_nats_publishes(client_read, server_write) => result,
+                    result = forward(server_read, client_write) => result,
+                }
+            }
+            Protocol::Postgres => {
+                let startup = refuse_pg_encryption(&mut client_read, &mut client_write).await?;
+                server_write.write_all(&startup).await?;
+                tokio::select! {
+                    result = self.proxy_pg_queries(client_read, server_write) => result,
+                    result = forward(server_read, client_write) => result,
+                }
+            }
+        }
+    }
+
+    /// Forwards the client side of a NATS connection, dropping or delaying `PUB` and `HPUB`
+    /// messages.
+    async fn proxy_nats_publishes(
+        &self,
+        client_read: OwnedReadHalf,
+        mut server_write: OwnedWriteHalf,
+    ) -> io::Result<()> {
+        let mut client_read = BufReader::new(client_read);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if client_read.read_until(b'\n', &mut line).await? == 0 {
+                return Ok(());
+            }
+
+            match nats_payload_len(&line) {
+                Some(payload_len) => {
+                    // The payload is followed by its own CRLF
+                    let mut payload = vec![0; payload_len + 2];
+                    client_read.read_exact(&mut payload).await?;
+                    if self.should_drop_nats_message() {
+                        debug!("fault injector dropped a nats message");
+                        continue;
+                    }
+                    let latency = self.nats_latency();
+                    if !latency.is_zero() {
+                        tokio::time::sleep(latency).await;
+                    }
+                    server_write.write_all(&line).await?;
+                    server_write.write_all(&payload).await?;
+                }
+                None => server_write.write_all(&line).await?,
+            }
+        }
+    }
+
+    /// Forwards the frontend side of a PostgreSQL connection once started, failing or delaying
+    /// simple queries and executions of extended queries.
+    async fn proxy_pg_queries(
+        &self,
+        mut client_read: OwnedReadHalf,
+        mut server_write: OwnedWriteHalf,
+    ) -> io::Result<()> {
+        loop {
+            let tag = match client_read.read_u8().await {
+                Ok(tag) => tag,
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                Err(err) => return Err(err),
+            };
+            let len = read_pg_len(&mut client_read).await?;
+            let mut message = Vec::with_capacity(len + 1);
+            message.push(tag);
+            message.extend_from_slice(&(len as i32).to_be_bytes());
+            message.resize(len + 1, 0);
+            client_read.read_exact(&mut message[5..]).await?;
+
+            if tag == b'Q' || tag == b'E' {
+                if self.should_fail_pg_query() {
+                    debug!("fault injector failed a postgres query");
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        "injected postgres query failure",
+                    ));
+                }
+                let latency = self.pg_latency();
+                if !latency.is_zero() {
+                    tokio::time::sleep(latency).await;
+                }
+            }
+            server_write.write_all(&message).await?;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Protocol {
+    Nats,
+    Postgres,
+}
+
+/// Forwards everything the server sends, as is.
+async fn forward(
+    mut server_read: OwnedReadHalf,
+    mut client_write: OwnedWriteHalf,
+) -> io::Result<()> {
+    tokio::io::copy(&mut server_read, &mut client_write).await?;
+    Ok(())
+}
+
+/// Answers any request to encrypt the connection with a refusal and returns the startup message
+/// that follows, which is to be forwarded to the server.
+async fn refuse_pg_encryption(
+    client_read: &mut OwnedReadHalf,
+    client_write: &mut OwnedWriteHalf,
+) -> io::Result<Vec<u8>> {
+    loop {
+        let len = read_pg_len(client_read).await?;
+        let mut message = Vec::with_capacity(len);
+        message.extend_from_slice(&(len as i32).to_be_bytes());
+        message.resize(len, 0);
+        client_read.read_exact(&mut message[4..]).await?;
+
+        let code = message
+            .get(4..8)
+            .map(|code| i32::from_be_bytes([code[0], code[1], code[2], code[3]]));
+        match code {
+            Some(PG_SSL_REQUEST_CODE) | Some(PG_GSSENC_REQUEST_CODE) => {
+                client_write.write_all(b"N").await?
+            }
+            _ => return Ok(message),
+        }
+    }
+}
+
+/// Reads the length of a PostgreSQL message, which counts itself.
+async fn read_pg_len(client_read: &mut OwnedReadHalf) -> io::Result<usize> {
+    let len = client_read.read_i32().await?;
+    if len < 4 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid postgres message length: {len}"),
+        ));
+    }
+    Ok(len as usize)
+}
+
+/// Returns the size of the payload following a NATS `PUB` or `HPUB` control line, or `None` for
+/// any other line.
+fn nats_payload_len(line: &[u8]) -> Option<usize> {
+    let line = std::str::from_utf8(line).ok()?;
+    let mut parts = line.split_whitespace();
+    let op = parts.next()?;
+    if op.eq_ignore_ascii_case("PUB") || op.eq_ignore_ascii_case("HPUB") {
+        // For `HPUB` the last field is the size of the headers and payload together
+        parts.last()?.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn duration_as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
@@ -1,12 +1,11 @@
 use color_eyre::Result;
 use dal::{
     func::{
-        argument::{FuncArgument, FuncArgumentId},
-        binding::FuncBindingId,
+        argument::FuncArgumentId, binding::FuncBindingId,
         binding_return_value::FuncBindingReturnValueId,
     },
-    ChangeSet, DalContext, Func, FuncBinding, FuncId, HistoryActor, StandardModel, User, UserClaim,
-    UserPk, Visibility, Workspace, WorkspaceSignup,
+    ChangeSet, DalContext, Func, FuncId, HistoryActor, StandardModel, User, UserClaim, UserPk,
+    Visibility, Workspace, WorkspaceSignup,
 };
 use jwt_simple::algorithms::RSAKeyPairLike;
 use jwt_simple::{claims::Claims, reexports::coarsetime::Duration};
@@ -98,31 +97,13 @@ pub async fn setup_identity_func(
     FuncBindingReturnValueId,
     FuncArgumentId,
 ) {
-    let identity_func: Func = Func::find_by_attr(ctx, "name", &"si:identity".to_string())
-        .await
-        .expect("could not find identity func by name attr")
-        .pop()
-        .expect("identity func not found");
-
-    let identity_func_identity_arg = FuncArgument::list_for_func(ctx, *identity_func.id())
-        .await
-        .expect("cannot list identity func args")
-        .pop()
-        .expect("cannot find identity func identity arg");
-
-    let (identity_func_binding, identity_func_binding_return_value) =
-        FuncBinding::create_and_execute(
-            ctx,
-            serde_json::json![{ "identity": null }],
-            *identity_func.id(),
-            vec![],
-        )
+    let identity = Func::identity(ctx)
         .await
         .expect("could not find or create identity func binding");
     (
-        *identity_func.id(),
-        *identity_func_binding.id(),
-        *identity_func_binding_return_value.id(),
-        *identity_func_identity_arg.id(),
+        *identity.func.id(),
+        *identity.func_binding.id(),
+        *identity.func_binding_return_value.id(),
+        *identity.func_argument.id(),
     )
 }
@@ -37,6 +37,9 @@ pub use si_test_macros::{dal_test as test, sdf_test};
 pub use telemetry;
 pub use tracing_subscriber;
 
+pub use fault::{FaultConfig, FaultInjector};
+
+mod fault;
 pub mod helpers;
 pub mod test_harness;
 
@@ -171,6 +174,8 @@ pub struct TestContext {
     encryption_key: Arc<CycloneEncryptionKey>,
     /// A service that can encrypt values based on the loaded donkeys
     symmetric_crypto_service: SymmetricCryptoService,
+    /// Controls the faults injected into the connections, if built with faults.
+    fault_injector: Option<FaultInjector>,
 }
 
 impl TestContext {
@@ -182,6 +187,17 @@ impl TestContext {
     /// This functions wraps over a mutex which ensures that only the first caller will run global
     /// database creation, migrations, and other preparations.
     pub async fn global(pg_dbname: &'static str) -> Result<Self> {
+        Self::global_inner(pg_dbname, false).await
+    }
+
+    /// Like [`Self::global`], except that the PostgreSQL and NATS connections go through fault
+    /// injecting proxies, controlled by the returned context's [`FaultInjector`]. No faults are
+    /// injected until some are applied.
+    pub async fn global_with_faults(pg_dbname: &'static str) -> Result<Self> {
+        Self::global_inner(pg_dbname, true).await
+    }
+
+    async fn global_inner(pg_dbname: &'static str, with_faults: bool) -> Result<Self> {
         let mut mutex_guard = TEST_CONTEXT_BUILDER.lock().await;
 
         match &*mutex_guard {
@@ -205,7 +221,7 @@ impl TestContext {
                     Ok(Ok(())) => {
                         debug!("task global_setup was successful");
                         *mutex_guard = ContextBuilderState::created(test_context_builder.clone());
-                        test_context_builder.build_for_test().await
+                        test_context_builder.build_for_test(with_faults).await
                     }
                     // Global setup errored
                     Ok(Err(err)) => {
@@ -224,7 +240,7 @@ impl TestContext {
                     }
                 }
             }
-            ContextBuilderState::Created(builder) => builder.build_for_test().await,
+            ContextBuilderState::Created(builder) => builder.build_for_test(with_faults).await,
             ContextBuilderState::Errored(message) => {
                 error!(error = %message, "global setup failed, aborting test");
                 Err(eyre!("global setup failed: {}", message))
@@ -252,6 +268,14 @@ impl TestContext {
     pub fn nats_config(&self) -> &NatsConfig {
         &self.config.nats
     }
+
+    /// Gets the [`FaultInjector`] controlling the faults injected into the connections. Fails if
+    /// the context was not built with [`Self::global_with_faults`].
+    pub fn fault_injector(&self) -> Result<FaultInjector> {
+        self.fault_injector
+            .clone()
+            .ok_or_else(|| eyre!("test context was not built with fault injection"))
+    }
 }
 
 /// A builder for a [`TestContext`].
@@ -289,23 +313,50 @@ impl TestContextBuilder {
             .await
             .wrap_err("failed to create global setup PgPool")?;
 
-        self.build_inner(pg_pool).await
+        self.build_inner(pg_pool, None).await
     }
 
     /// Builds and returns a new [`TestContext`] with its own connection pooling for each test.
-    async fn build_for_test(&self) -> Result<TestContext> {
-        let pg_pool = self.create_test_specific_db_with_pg_pool().await?;
+    /// With faults, the connections go through fault injecting proxies.
+    async fn build_for_test(&self, with_faults: bool) -> Result<TestContext> {
+        let mut pg_pool_config = self.create_test_specific_db().await?;
+
+        let fault_injector = with_faults.then(FaultInjector::default);
+        if let Some(fault_injector) = &fault_injector {
+            let proxy_address = fault_injector
+                .proxy_pg(&pg_pool_config.hostname, pg_pool_config.port)
+                .await
+                .wrap_err("failed to start postgres fault proxy")?;
+            pg_pool_config.hostname = proxy_address.ip().to_string();
+            pg_pool_config.port = proxy_address.port();
+        }
 
-        self.build_inner(pg_pool).await
+        let pg_pool = PgPool::new(&pg_pool_config)
+            .await
+            .wrap_err("failed to create PgPool to test specific db")?;
+
+        self.build_inner(pg_pool, fault_injector).await
     }
 
-    async fn build_inner(&self, pg_pool: PgPool) -> Result<TestContext> {
+    async fn build_inner(
+        &self,
+        pg_pool: PgPool,
+        fault_injector: Option<FaultInjector>,
+    ) -> Result<TestContext> {
+        let mut config = self.config.clone();
+        if let Some(fault_injector) = &fault_injector {
+            let proxy_address = fault_injector
+                .proxy_nats(&config.nats.url)
+                .await
+                .wrap_err("failed to start nats fault proxy")?;
+            config.nats.url = proxy_address.to_string();
+        }
+
         // Need to make a new NatsConfig so that we can add the test-specific subject prefix
         // without leaking it to other tests.
-        let mut nats_config = self.config.nats.clone();
+        let mut nats_config = config.nats.clone();
         let nats_subject_prefix = random_identifier_string();
         nats_config.subject_prefix = Some(nats_subject_prefix.clone());
-        let mut config = self.config.clone();
         config.nats.subject_prefix = Some(nats_subject_prefix);
 
         let nats_conn = NatsClient::new(&nats_config)
@@ -325,10 +376,13 @@ impl TestContextBuilder {
             job_processor,
             encryption_key: self.encryption_key.clone(),
             symmetric_crypto_service,
+            fault_injector,
         })
     }
 
-    async fn create_test_specific_db_with_pg_pool(&self) -> Result<PgPool> {
+    /// Creates a database for a test from the migrated template database, returning the
+    /// configuration to connect to it.
+    async fn create_test_specific_db(&self) -> Result<PgPoolConfig> {
         // Connect to the 'postgres' database so we can copy our migrated template test database
         let mut new_pg_pool_config = self.config.pg.clone();
         new_pg_pool_config.dbname = "postgres".to_string();
@@ -370,11 +424,8 @@ impl TestContextBuilder {
         // failing tests.
         println!("Test database: {}", &dbname);
 
-        // Return new PG pool that uess the new datatbase
         new_pg_pool_config.dbname = dbname;
-        PgPool::new(&new_pg_pool_config)
-            .await
-            .wrap_err("failed to create PgPool to db 'postgres'")
+        Ok(new_pg_pool_config)
     }
 }
 
@@ -5,12 +5,13 @@ use thiserror::Error;
 use si_data_pg::PgError;
 use telemetry::prelude::*;
 
+use crate::confirmation::resolver::ConfirmationResolverError;
 use crate::{
     impl_standard_model, pk, standard_model, standard_model_accessor_ro, ActionKind,
-    ActionPrototype, ActionPrototypeError, ActionPrototypeId, ChangeSetPk, Component,
-    ComponentError, ComponentId, DalContext, HistoryActor, HistoryEventError, Node, NodeError,
-    StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError, UserPk, Visibility,
-    WsEvent, WsEventError, WsEventResult, WsPayload,
+    ActionPrototype, ActionPrototypeContext, ActionPrototypeError, ActionPrototypeId, ChangeSetPk,
+    Component, ComponentError, ComponentId, ConfirmationResolver, DalContext, HistoryActor,
+    HistoryEventError, Node, NodeError, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, UserPk, Visibility, WsEvent, WsEventError, WsEventResult, WsPayload,
 };
 
 const FIND_FOR_CHANGE_SET: &str = include_str!("./queries/action/find_for_change_set.sql");
@@ -24,6 +25,8 @@ pub enum ActionError {
     Component(#[from] ComponentError),
     #[error("component not found: {0}")]
     ComponentNotFound(ComponentId),
+    #[error("confirmation resolver error: {0}")]
+    ConfirmationResolver(#[from] ConfirmationResolverError),
     #[error("history event: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("in head")]
@@ -156,6 +159,55 @@ impl Action {
             .ok_or(ActionError::ComponentNotFound(*self.component_id()))
     }
 
+    /// Queue an [`Action`] for every action recommended by the latest confirmations of the
+    /// [`Components`](Component) in the current change set, skipping the ones already queued.
+    /// Recommendations without a matching [`ActionPrototype`] for the
+    /// [`SchemaVariant`](crate::SchemaVariant) of the [`Component`] are ignored.
+    ///
+    /// Queued actions are ordered by [`Self::order()`] and run when the change set is applied.
+    pub async fn queue_recommended(ctx: &DalContext) -> ActionResult<Vec<Self>> {
+        if ctx.visibility().change_set_pk.is_none() {
+            return Err(ActionError::InHead);
+        }
+
+        let mut queued: Vec<(ActionPrototypeId, ComponentId)> = Self::find_for_change_set(ctx)
+            .await?
+            .into_iter()
+            .map(|action| (action.action_prototype_id, action.component_id))
+            .collect();
+
+        let mut actions = Vec::new();
+        for component in Component::list(ctx).await? {
+            let schema_variant_id = Component::schema_variant_id(ctx, *component.id()).await?;
+
+            for resolver in ConfirmationResolver::list_for_component(ctx, *component.id()).await? {
+                for recommended_action in resolver.recommended_actions() {
+                    let prototype = match ActionPrototype::find_for_context_and_kind(
+                        ctx,
+                        (*recommended_action).into(),
+                        ActionPrototypeContext { schema_variant_id },
+                    )
+                    .await?
+                    .pop()
+                    {
+                        Some(prototype) => prototype,
+                        None => continue,
+                    };
+
+                    let key = (*prototype.id(), *component.id());
+                    if queued.contains(&key) {
+                        continue;
+                    }
+                    queued.push(key);
+
+                    actions.push(Self::new(ctx, *prototype.id(), *component.id()).await?);
+                }
+            }
+        }
+
+        Ok(actions)
+    }
+
     pub async fn order(ctx: &DalContext) -> ActionResult<HashMap<ActionId, ActionBag>> {
         let actions_by_id: HashMap<ActionId, Action> = Self::find_for_change_set(ctx)
             .await?
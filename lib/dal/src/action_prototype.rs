@@ -1,5 +1,6 @@
 use std::default::Default;
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, Display};
 use thiserror::Error;
@@ -11,11 +12,13 @@ use telemetry::prelude::*;
 
 use crate::func::before::before_funcs_for_component;
 use crate::{
-    component::view::ComponentViewError, func::backend::js_action::ActionRunResult,
-    impl_standard_model, pk, standard_model, standard_model_accessor, Component, ComponentId,
-    ComponentView, DalContext, Func, FuncBinding, FuncBindingError, FuncBindingReturnValueError,
-    FuncError, FuncId, HistoryEventError, SchemaVariantId, StandardModel, StandardModelError,
-    Tenancy, Timestamp, TransactionsError, Visibility, WsEvent, WsEventError,
+    component::view::ComponentViewError,
+    func::backend::js_action::{ActionRunResult, DRY_RUN_ARG},
+    impl_standard_model, pk, standard_model, standard_model_accessor, AttributeValueId, Component,
+    ComponentId, ComponentView, DalContext, Func, FuncBinding, FuncBindingError,
+    FuncBindingReturnValueError, FuncError, FuncId, HistoryEventError, SchemaVariantId,
+    StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError, Visibility, WsEvent,
+    WsEventError,
 };
 
 const FIND_FOR_CONTEXT: &str = include_str!("./queries/action_prototype/find_for_context.sql");
@@ -374,16 +377,77 @@ impl ActionPrototype {
         &self,
         ctx: &DalContext,
         component_id: ComponentId,
+    ) -> ActionPrototypeResult<Option<ActionRunResult>> {
+        Ok(match self.execute(ctx, component_id, false).await? {
+            Some(mut run_result) => {
+                run_result
+                    .last_synced
+                    .get_or_insert_with(|| Utc::now().to_rfc3339());
+
+                let deleted_ctx = &ctx.clone_with_delete_visibility();
+                let mut component = Component::get_by_id(deleted_ctx, &component_id)
+                    .await?
+                    .ok_or(ActionPrototypeError::ComponentNotFound(component_id))?;
+
+                if component.needs_destroy() && run_result.payload.is_none() {
+                    component
+                        .set_needs_destroy(deleted_ctx, false)
+                        .await
+                        .map_err(|e| ActionPrototypeError::Component(e.to_string()))?;
+                }
+
+                if component
+                    .set_resource(ctx, run_result.clone())
+                    .await
+                    .map_err(|e| ActionPrototypeError::Component(e.to_string()))?
+                {
+                    WsEvent::resource_refreshed(ctx, *component.id())
+                        .await?
+                        .publish_on_commit(ctx)
+                        .await?;
+                }
+
+                Some(run_result)
+            }
+            None => None,
+        })
+    }
+
+    /// Run the func of [`self`](Self) in plan-only mode: the CLI commands and HTTP requests it
+    /// would perform are returned in the result's `plan` instead of being performed, and the
+    /// resource of the [`Component`] is left untouched.
+    pub async fn preview(
+        &self,
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ActionPrototypeResult<Option<ActionRunResult>> {
+        self.execute(ctx, component_id, true).await
+    }
+
+    async fn execute(
+        &self,
+        ctx: &DalContext,
+        component_id: ComponentId,
+        dry_run: bool,
     ) -> ActionPrototypeResult<Option<ActionRunResult>> {
         let component_view = ComponentView::new(ctx, component_id).await?;
+        let mut args = serde_json::to_value(component_view)?;
+        if dry_run {
+            if let Some(args) = args.as_object_mut() {
+                args.insert(DRY_RUN_ARG.to_owned(), serde_json::Value::Bool(true));
+            }
+        }
+
         let deleted_ctx = ctx.clone_with_delete_visibility();
         let before = before_funcs_for_component(&deleted_ctx, &component_id).await?;
 
-        let (_, return_value) = FuncBinding::create_and_execute(
+        let (_, return_value) = FuncBinding::create_and_execute_for_component(
             ctx,
-            serde_json::to_value(component_view)?,
+            args,
             self.func_id(),
             before,
+            component_id,
+            AttributeValueId::NONE,
         )
         .await?;
 
@@ -402,30 +466,6 @@ impl ActionPrototype {
             Some(value) => {
                 let mut run_result: ActionRunResult = serde_json::from_value(value.clone())?;
                 run_result.logs = logs.iter().map(|l| l.message.clone()).collect();
-
-                let deleted_ctx = &ctx.clone_with_delete_visibility();
-                let mut component = Component::get_by_id(deleted_ctx, &component_id)
-                    .await?
-                    .ok_or(ActionPrototypeError::ComponentNotFound(component_id))?;
-
-                if component.needs_destroy() && run_result.payload.is_none() {
-                    component
-                        .set_needs_destroy(deleted_ctx, false)
-                        .await
-                        .map_err(|e| ActionPrototypeError::Component(e.to_string()))?;
-                }
-
-                if component
-                    .set_resource(ctx, run_result.clone())
-                    .await
-                    .map_err(|e| ActionPrototypeError::Component(e.to_string()))?
-                {
-                    WsEvent::resource_refreshed(ctx, *component.id())
-                        .await?
-                        .publish_on_commit(ctx)
-                        .await?;
-                }
-
                 Some(run_result)
             }
             None => None,
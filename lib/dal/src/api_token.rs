@@ -0,0 +1,242 @@
+//! This module contains [`ApiToken`], a long-lived credential scoped to a single
+//! [`Workspace`](crate::Workspace) that automation (e.g. CI systems) can use instead of a user's
+//! session.
+//!
+//! Only a hash of the token is stored. The token itself is returned once, when it is created.
+
+use base64::{engine::general_purpose, Engine};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use si_hash::Hash;
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    pk, standard_model, DalContext, HistoryActor, HistoryEvent, HistoryEventError,
+    StandardModelError, Timestamp, TransactionsError, UserPk, WorkspacePk,
+};
+
+const CREATE: &str = include_str!("queries/api_token/create.sql");
+const LIST: &str = include_str!("queries/api_token/list.sql");
+const REVOKE: &str = include_str!("queries/api_token/revoke.sql");
+const AUTHENTICATE: &str = include_str!("queries/api_token/authenticate.sql");
+
+/// Every [`ApiToken`] starts with this, which tells it apart from a user's JWT.
+pub const API_TOKEN_PREFIX: &str = "si_api_";
+
+/// The number of random bytes in an [`ApiToken`].
+const TOKEN_BYTES: usize = 32;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ApiTokenError {
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("api tokens can only be created by a user")]
+    NoUserInContext,
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error("api token not found: {0}")]
+    NotFound(ApiTokenPk),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ApiTokenResult<T> = Result<T, ApiTokenError>;
+
+pk!(ApiTokenPk);
+
+/// What an [`ApiToken`] is allowed to do in its [`Workspace`](crate::Workspace).
+#[remain::sorted]
+#[derive(
+    AsRefStr, Deserialize, Serialize, Debug, Display, EnumString, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ApiTokenScope {
+    /// Can only read.
+    ReadOnly,
+    /// Can read and make changes, e.g. update components.
+    ReadWrite,
+}
+
+impl ApiTokenScope {
+    pub fn allows_writes(&self) -> bool {
+        matches!(self, Self::ReadWrite)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct ApiToken {
+    pk: ApiTokenPk,
+    workspace_pk: WorkspacePk,
+    name: String,
+    scope: ApiTokenScope,
+    created_by_user_pk: UserPk,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl ApiToken {
+    pub fn pk(&self) -> ApiTokenPk {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scope(&self) -> ApiTokenScope {
+        self.scope
+    }
+
+    /// The [`User`](crate::User) who created the token. Changes made with the token are
+    /// attributed to them.
+    pub fn created_by_user_pk(&self) -> UserPk {
+        self.created_by_user_pk
+    }
+
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    pub fn last_used_at(&self) -> Option<DateTime<Utc>> {
+        self.last_used_at
+    }
+
+    pub fn revoked_at(&self) -> Option<DateTime<Utc>> {
+        self.revoked_at
+    }
+
+    /// Whether `raw_token` looks like an [`ApiToken`] rather than a JWT.
+    pub fn is_api_token(raw_token: &str) -> bool {
+        raw_token.starts_with(API_TOKEN_PREFIX)
+    }
+
+    /// Create a token for the current [`Workspace`](crate::Workspace) on behalf of the
+    /// [`User`](crate::User) in the context. Returns the token along with its secret, which
+    /// can't be retrieved again.
+    #[instrument(skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl AsRef<str> + std::fmt::Debug,
+        scope: ApiTokenScope,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> ApiTokenResult<(Self, String)> {
+        let name = name.as_ref();
+        let created_by_user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => *user_pk,
+            HistoryActor::SystemInit => return Err(ApiTokenError::NoUserInContext),
+        };
+
+        let mut secret = [0u8; TOKEN_BYTES];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let raw_token = format!(
+            "{API_TOKEN_PREFIX}{}",
+            general_purpose::URL_SAFE_NO_PAD.encode(secret)
+        );
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                CREATE,
+                &[
+                    &workspace_pk(ctx)?,
+                    &name,
+                    &scope.as_ref(),
+                    &hash_token(&raw_token),
+                    &created_by_user_pk,
+                    &expires_at,
+                ],
+            )
+            .await?;
+        let token: Self = standard_model::object_from_row(row)?;
+
+        HistoryEvent::new(
+            ctx,
+            "api_token.create",
+            "API token created",
+            &serde_json::json!({ "pk": token.pk, "name": name, "scope": scope }),
+        )
+        .await?;
+
+        Ok((token, raw_token))
+    }
+
+    /// List every token of the current [`Workspace`](crate::Workspace), including revoked and
+    /// expired ones, most recent first.
+    pub async fn list(ctx: &DalContext) -> ApiTokenResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST, &[&workspace_pk(ctx)?])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Revoke a token of the current [`Workspace`](crate::Workspace). It is rejected from then on.
+    #[instrument(skip(ctx))]
+    pub async fn revoke(ctx: &DalContext, pk: ApiTokenPk) -> ApiTokenResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(REVOKE, &[&workspace_pk(ctx)?, &pk])
+            .await?
+            .ok_or(ApiTokenError::NotFound(pk))?;
+        let token: Self = standard_model::object_from_row(row)?;
+
+        HistoryEvent::new(
+            ctx,
+            "api_token.revoke",
+            "API token revoked",
+            &serde_json::json!({ "pk": token.pk, "name": token.name }),
+        )
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Find the live token matching `raw_token`, recording that it was used. Returns `None` if
+    /// there is no such token or it was revoked or has expired.
+    pub async fn authenticate(ctx: &DalContext, raw_token: &str) -> ApiTokenResult<Option<Self>> {
+        if !Self::is_api_token(raw_token) {
+            return Ok(None);
+        }
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(AUTHENTICATE, &[&hash_token(raw_token)])
+            .await?;
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+}
+
+fn hash_token(raw_token: &str) -> String {
+    Hash::new(raw_token.as_bytes()).to_string()
+}
+
+fn workspace_pk(ctx: &DalContext) -> ApiTokenResult<WorkspacePk> {
+    ctx.tenancy()
+        .workspace_pk()
+        .ok_or(ApiTokenError::NoWorkspaceInTenancy)
+}
@@ -29,6 +29,7 @@ use crate::{
     StandardModelError,
 };
 
+pub mod cache;
 pub mod read;
 
 use crate::attribute::context::AttributeContextLeastSpecificFieldKind::{
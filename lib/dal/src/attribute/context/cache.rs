@@ -0,0 +1,119 @@
+//! This module contains [`AttributeContextCache`], an opt-in read-through cache for the entities
+//! that attribute context flows (e.g. connecting a component to a frame) fetch over and over
+//! within a single request: [`Props`](crate::Prop) by id and
+//! [`InternalProviders`](crate::InternalProvider) by [`Prop`](crate::Prop) or socket. Identity
+//! [`Func`](crate::Func) lookups are already covered by the
+//! [`FuncCache`](crate::func::cache::FuncCache).
+//!
+//! Like the [`FuncCache`](crate::func::cache::FuncCache), the cache lives on the
+//! [`Transactions`](crate::Transactions) of a [`DalContext`], is keyed by [`Tenancy`] and
+//! [`Visibility`] and is cleared whenever one of its tables is mutated through the standard model
+//! (or a change set is applied). Unlike it, the cache is only used by contexts that enabled it
+//! with [`DalContext::enable_attribute_context_cache()`].
+
+use std::collections::HashMap;
+
+use crate::socket::SocketId;
+use crate::{DalContext, InternalProvider, PropId, Tenancy, TransactionsError, Visibility};
+
+/// The tables whose mutations invalidate the [`AttributeContextCache`].
+const CACHED_TABLES: &[&str] = &["props", "internal_providers"];
+
+type Scope = (Tenancy, Visibility);
+
+#[derive(Clone, Debug, Default)]
+pub struct AttributeContextCache {
+    /// Standard model objects of the [`CACHED_TABLES`], by table and id.
+    objects_by_id: HashMap<(Scope, String, String), serde_json::Value>,
+    internal_providers_by_prop: HashMap<(Scope, PropId), InternalProvider>,
+    explicit_internal_providers_by_socket: HashMap<(Scope, SocketId), InternalProvider>,
+}
+
+impl AttributeContextCache {
+    /// Returns `true` if writes to the given table must invalidate the cache.
+    pub fn caches_table(table: &str) -> bool {
+        CACHED_TABLES.contains(&table)
+    }
+
+    pub fn clear(&mut self) {
+        self.objects_by_id.clear();
+        self.internal_providers_by_prop.clear();
+        self.explicit_internal_providers_by_socket.clear();
+    }
+
+    /// Clears the cache of the current transactions, if any have been started.
+    pub async fn invalidate(ctx: &DalContext) -> Result<(), TransactionsError> {
+        ctx.txns().await?.attribute_context_cache_mut().clear();
+        Ok(())
+    }
+
+    pub(crate) fn object_by_id(
+        &self,
+        ctx: &DalContext,
+        table: &str,
+        id: &str,
+    ) -> Option<serde_json::Value> {
+        self.objects_by_id
+            .get(&(scope(ctx), table.to_owned(), id.to_owned()))
+            .cloned()
+    }
+
+    /// Only found objects are cached, for the same reasons as in the
+    /// [`FuncCache`](crate::func::cache::FuncCache).
+    pub(crate) fn insert_object_by_id(
+        &mut self,
+        ctx: &DalContext,
+        table: &str,
+        id: &str,
+        object: &serde_json::Value,
+    ) {
+        self.objects_by_id.insert(
+            (scope(ctx), table.to_owned(), id.to_owned()),
+            object.clone(),
+        );
+    }
+
+    pub(crate) fn internal_provider_by_prop(
+        &self,
+        ctx: &DalContext,
+        prop_id: PropId,
+    ) -> Option<InternalProvider> {
+        self.internal_providers_by_prop
+            .get(&(scope(ctx), prop_id))
+            .cloned()
+    }
+
+    pub(crate) fn insert_internal_provider_by_prop(
+        &mut self,
+        ctx: &DalContext,
+        prop_id: PropId,
+        internal_provider: &InternalProvider,
+    ) {
+        self.internal_providers_by_prop
+            .insert((scope(ctx), prop_id), internal_provider.clone());
+    }
+
+    pub(crate) fn explicit_internal_provider_by_socket(
+        &self,
+        ctx: &DalContext,
+        socket_id: SocketId,
+    ) -> Option<InternalProvider> {
+        self.explicit_internal_providers_by_socket
+            .get(&(scope(ctx), socket_id))
+            .cloned()
+    }
+
+    pub(crate) fn insert_explicit_internal_provider_by_socket(
+        &mut self,
+        ctx: &DalContext,
+        socket_id: SocketId,
+        internal_provider: &InternalProvider,
+    ) {
+        self.explicit_internal_providers_by_socket
+            .insert((scope(ctx), socket_id), internal_provider.clone());
+    }
+}
+
+fn scope(ctx: &DalContext) -> Scope {
+    (*ctx.tenancy(), *ctx.visibility())
+}
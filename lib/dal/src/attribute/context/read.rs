@@ -24,7 +24,7 @@ use crate::{AttributeContext, ComponentId, ExternalProviderId, InternalProviderI
 /// The above `AttributeReadContext` would be used for finding all
 /// attributes, across all [`Props`](crate::Prop) that have been set
 /// for a given [`ComponentId`].
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AttributeReadContext {
     #[serde(rename = "attribute_context_prop_id")]
     pub prop_id: Option<PropId>,
@@ -961,4 +961,8 @@ pub struct AttributePrototypeArgumentValues {
     pub attribute_prototype_id: AttributePrototypeId,
     pub argument_name: String,
     pub values: Vec<serde_json::Value>,
+    /// The [`ComponentId`] each value of [`values`](Self::values) comes from, in the same order
+    /// (nil when it does not come from another [`Component`](crate::Component)).
+    #[serde(default)]
+    pub tail_component_ids: Vec<ComponentId>,
 }
@@ -320,7 +320,7 @@ impl AttributePrototypeArgument {
     /// Wraps the standard model accessor for "external_provider_id" to ensure that a set value
     /// cannot become unset and vice versa.
     pub async fn set_external_provider_id_safe(
-        mut self,
+        &mut self,
         ctx: &DalContext,
         external_provider_id: ExternalProviderId,
     ) -> AttributePrototypeArgumentResult<()> {
@@ -346,7 +346,7 @@ impl AttributePrototypeArgument {
     /// Wraps the standard model accessor for "tail_component_id" to ensure that a set value
     /// cannot become unset and vice versa.
     pub async fn set_tail_component_id_safe(
-        mut self,
+        &mut self,
         ctx: &DalContext,
         tail_component_id: ComponentId,
     ) -> AttributePrototypeArgumentResult<()> {
@@ -367,7 +367,7 @@ impl AttributePrototypeArgument {
     /// Wraps the standard model accessor for "head_component_id" to ensure that a set value
     /// cannot become unset and vice versa.
     pub async fn set_head_component_id_safe(
-        mut self,
+        &mut self,
         ctx: &DalContext,
         head_component_id: ComponentId,
     ) -> AttributePrototypeArgumentResult<()> {
@@ -385,6 +385,32 @@ impl AttributePrototypeArgument {
         Ok(())
     }
 
+    /// Rebind [`Self`] to a different source for its value. Only the provided fields are
+    /// changed and, since this goes through the "safe" setters, an _intra_
+    /// [`Component`](crate::Component) argument cannot become an _inter_
+    /// [`Component`](crate::Component) argument (or vice versa).
+    pub async fn rebind(
+        &mut self,
+        ctx: &DalContext,
+        internal_provider_id: Option<InternalProviderId>,
+        external_provider_id: Option<ExternalProviderId>,
+        tail_component_id: Option<ComponentId>,
+    ) -> AttributePrototypeArgumentResult<()> {
+        if let Some(internal_provider_id) = internal_provider_id {
+            self.set_internal_provider_id_safe(ctx, internal_provider_id)
+                .await?;
+        }
+        if let Some(external_provider_id) = external_provider_id {
+            self.set_external_provider_id_safe(ctx, external_provider_id)
+                .await?;
+        }
+        if let Some(tail_component_id) = tail_component_id {
+            self.set_tail_component_id_safe(ctx, tail_component_id)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Determines if the [`InternalProviderId`](crate::InternalProvider) is unset. This function
     /// can be useful for determining how to build [`FuncBinding`](crate::FuncBinding) arguments.
     pub fn is_internal_provider_unset(&self) -> bool {
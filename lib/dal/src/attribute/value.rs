@@ -45,6 +45,7 @@ use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::func::before::before_funcs_for_component;
+use crate::schema::variant::leaves::LeafInputLocation;
 use crate::{
     attribute::{
         context::{
@@ -54,6 +55,7 @@ use crate::{
         prototype::{AttributePrototype, AttributePrototypeId},
     },
     func::{
+        backend::FuncBackendResponseType,
         binding::{FuncBindingError, FuncBindingId},
         binding_return_value::{
             FuncBindingReturnValue, FuncBindingReturnValueError, FuncBindingReturnValueId,
@@ -64,14 +66,21 @@ use crate::{
     pk,
     standard_model::{self, TypeHint},
     standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
-    AttributeContextError, AttributePrototypeArgumentError, Component, ComponentId, DalContext,
-    Func, FuncBinding, FuncError, HistoryEventError, IndexMap, InternalProvider,
-    InternalProviderId, Prop, PropError, PropId, PropKind, StandardModel, StandardModelError,
-    Tenancy, Timestamp, TransactionsError, Visibility, WsEventError,
+    AttributeContextError, AttributePrototypeArgumentError, ChangeSetPk, Component, ComponentId,
+    DalContext, Func, FuncBinding, FuncError, HistoryEventError, IndexMap, InternalProvider,
+    InternalProviderId, Prop, PropError, PropId, PropKind, SocketMergeStrategy, StandardModel,
+    StandardModelError, Tenancy, Timestamp, TransactionsError, Visibility, WsEvent, WsEventError,
+    WsEventResult, WsPayload,
 };
 
+pub mod array;
+pub mod audit;
+pub mod stale;
 pub mod view;
 
+use self::audit::AttributeValueAuditPk;
+use self::view::AttributeView;
+
 const CHILD_ATTRIBUTE_VALUES_FOR_CONTEXT: &str =
     include_str!("../queries/attribute_value/child_attribute_values_for_context.sql");
 const FETCH_UPDATE_GRAPH_DATA: &str =
@@ -83,6 +92,7 @@ const FIND_WITH_PARENT_AND_KEY_FOR_CONTEXT: &str =
 const FIND_WITH_PARENT_AND_PROTOTYPE_FOR_CONTEXT: &str =
     include_str!("../queries/attribute_value/find_with_parent_and_prototype_for_context.sql");
 const LIST_FOR_CONTEXT: &str = include_str!("../queries/attribute_value/list_for_context.sql");
+const LIST_FOR_CONTEXTS: &str = include_str!("../queries/attribute_value/list_for_contexts.sql");
 const LIST_PAYLOAD_FOR_READ_CONTEXT: &str =
     include_str!("../queries/attribute_value/list_payload_for_read_context.sql");
 const LIST_PAYLOAD_FOR_READ_CONTEXT_AND_ROOT: &str =
@@ -91,6 +101,10 @@ const LIST_PAYLOAD_FOR_READ_CONTEXT_AND_ROOT: &str =
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum AttributeValueError {
+    #[error("attribute value {1} is not an element of array attribute value {0}")]
+    ArrayElementNotFound(AttributeValueId, AttributeValueId),
+    #[error("new order for array attribute value {0} must contain exactly its current elements")]
+    ArrayOrderMismatch(AttributeValueId),
     #[error("AttributeContext error: {0}")]
     AttributeContext(#[from] AttributeContextError),
     #[error("AttributeContextBuilder error: {0}")]
@@ -101,6 +115,8 @@ pub enum AttributeValueError {
     AttributePrototypeArgument(#[from] AttributePrototypeArgumentError),
     #[error("AttributePrototype not found for AttributeValue: {0} ({1:?})")]
     AttributePrototypeNotFound(AttributeValueId, Visibility),
+    #[error("attribute value audit not found: {0}")]
+    AuditNotFound(AttributeValueAuditPk),
     #[error("invalid json pointer: {0} for {1}")]
     BadJsonPointer(String, String),
     #[error("component error: {0}")]
@@ -109,6 +125,8 @@ pub enum AttributeValueError {
     ComponentNotFound(ComponentId),
     #[error("component not found by id: {0}")]
     ComponentNotFoundById(ComponentId),
+    #[error("row index out of range of the requested contexts: {0}")]
+    ContextIndexOutOfRange(i64),
     #[error(transparent)]
     Council(#[from] council_server::client::ClientError),
     #[error("empty attribute prototype arguments for group name: {0}")]
@@ -203,6 +221,8 @@ pub enum AttributeValueError {
     SchemaVariantNotFoundForComponent(ComponentId),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("socket merge strategy error: {0}")]
+    SocketMergeStrategy(String),
     #[error("standard model error: {0}")]
     StandardModelError(#[from] StandardModelError),
     #[error(transparent)]
@@ -241,6 +261,9 @@ pub struct AttributeValue {
     /// If this is a `sealed_proxy`, then it should **not** update its [`FuncBindingReturnValueId`] from the
     /// [`AttributeValue`] referenced to in `proxy_for_attribute_value_id`.
     sealed_proxy: bool,
+    /// Bumped every time [`Self::func_binding_return_value_id`] changes.
+    #[serde(default)]
+    content_generation: i64,
     pub index_map: Option<IndexMap>,
     pub key: Option<String>,
     #[serde(flatten)]
@@ -300,6 +323,12 @@ impl AttributeValue {
         AttributeValueResult
     );
     standard_model_accessor!(sealed_proxy, bool, AttributeValueResult);
+
+    /// A counter that changes whenever the value changes. Anything derived from the value alone,
+    /// like the result of validating it, can be cached until the generation moves on.
+    pub fn content_generation(&self) -> i64 {
+        self.content_generation
+    }
     standard_model_accessor!(func_binding_id, Pk(FuncBindingId), AttributeValueResult);
     standard_model_accessor!(
         func_binding_return_value_id,
@@ -522,6 +551,52 @@ impl AttributeValue {
         Ok(standard_model::option_object_from_row(maybe_row)?)
     }
 
+    /// Batch version of [`Self::find_for_context()`]: finds the most specific [`AttributeValue`]
+    /// for each of the provided [`AttributeReadContexts`](crate::AttributeReadContext) in a single
+    /// query. Contexts without an [`AttributeValue`] are absent from the returned map.
+    ///
+    /// The same restrictions as [`Self::find_for_context()`] apply to maps and arrays.
+    pub async fn find_many_for_contexts(
+        ctx: &DalContext,
+        contexts: &[AttributeReadContext],
+    ) -> AttributeValueResult<HashMap<AttributeReadContext, Self>> {
+        for context in contexts {
+            AttributeContextBuilder::from(*context).to_context()?;
+        }
+        if contexts.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_CONTEXTS,
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &serde_json::to_value(contexts)?,
+                ],
+            )
+            .await?;
+
+        // Rows come sorted per context from most to least specific, and, like
+        // Self::find_for_context(), the last one of each context wins.
+        let mut values = HashMap::new();
+        for row in rows {
+            // Ordinalities start at one.
+            let index: i64 = row.try_get("index")?;
+            let context = contexts
+                .get(index as usize - 1)
+                .copied()
+                .ok_or(AttributeValueError::ContextIndexOutOfRange(index))?;
+            values.insert(context, standard_model::object_from_row(row)?);
+        }
+
+        Ok(values)
+    }
+
     /// Return the [`Prop`] that the [`AttributeValueId`] belongs to,
     /// following the relationship through [`AttributePrototype`].
     pub async fn find_prop_for_value(
@@ -688,7 +763,7 @@ impl AttributeValue {
         // TODO: Allow updating the key
         key: Option<String>,
     ) -> AttributeValueResult<(Option<serde_json::Value>, AttributeValueId)> {
-        Self::update_for_context_raw(
+        let (value, new_attribute_value_id) = Self::update_for_context_raw(
             ctx,
             attribute_value_id,
             parent_attribute_value_id,
@@ -698,7 +773,26 @@ impl AttributeValue {
             true,
             true,
         )
-        .await
+        .await?;
+
+        // Only values set on a component's props are interesting to anyone watching a field.
+        if !context.is_component_unset() && context.prop_id() != PropId::NONE {
+            let prop = Prop::get_by_id(ctx, &context.prop_id())
+                .await?
+                .ok_or(AttributeValueError::PropNotFound(context.prop_id()))?;
+            WsEvent::attribute_value_updated(
+                ctx,
+                context.component_id(),
+                new_attribute_value_id,
+                format!("/{}", prop.path().with_replaced_sep("/")),
+                value.clone(),
+            )
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+        }
+
+        Ok((value, new_attribute_value_id))
     }
 
     pub async fn update_for_context_without_propagating_dependent_values(
@@ -760,6 +854,10 @@ impl AttributeValue {
         // TODO(nick,paulo,zack,jacob): ensure we do not _have_ to do this in the future.
         let ctx = &ctx.clone_without_deleted_visibility();
 
+        let previous_func_binding_return_value_id = Self::get_by_id(ctx, &attribute_value_id)
+            .await?
+            .map(|attribute_value| attribute_value.func_binding_return_value_id);
+
         let row = ctx.txns()
             .await?
             .pg()
@@ -778,6 +876,12 @@ impl AttributeValue {
             ).await?;
 
         let new_attribute_value_id: AttributeValueId = row.try_get("new_attribute_value_id")?;
+        Self::record_audit(
+            ctx,
+            new_attribute_value_id,
+            previous_func_binding_return_value_id,
+        )
+        .await?;
 
         // TODO(fnichol): we might want to fire off a status even at this point, however we've
         // already updated the initial attribute value, so is there much value?
@@ -986,6 +1090,33 @@ impl AttributeValue {
         Ok(row.try_get("new_attribute_value_id")?)
     }
 
+    /// Returns true if the current [`FuncBinding`] of this [`AttributeValue`] was bound to the same
+    /// code generation [`Func`], with the same code and with the same args, and has a value.
+    async fn code_generation_inputs_unchanged(
+        &self,
+        ctx: &DalContext,
+        func: &Func,
+        func_binding_args: &HashMap<String, Option<serde_json::Value>>,
+    ) -> AttributeValueResult<bool> {
+        let func_binding = match FuncBinding::get_by_id(ctx, &self.func_binding_id).await? {
+            Some(func_binding) => func_binding,
+            None => return Ok(false),
+        };
+        if func_binding.code_sha256() != func.code_sha256() {
+            return Ok(false);
+        }
+        match func_binding.func(ctx).await? {
+            Some(bound_func) if bound_func.id() == func.id() => {}
+            _ => return Ok(false),
+        }
+        match FuncBindingReturnValue::get_by_id(ctx, &self.func_binding_return_value_id).await? {
+            Some(func_binding_return_value) if func_binding_return_value.value().is_some() => {}
+            _ => return Ok(false),
+        }
+
+        Ok(*func_binding.args() == serde_json::to_value(func_binding_args)?)
+    }
+
     /// Re-evaluates the current `AttributeValue`'s `AttributePrototype` to update the
     /// `FuncBinding`, and `FuncBindingReturnValue`, reflecting the current inputs to
     /// the function.
@@ -1052,11 +1183,45 @@ impl AttributeValue {
         // This is so secret values still trigger the dependent values system,
         // and before functions are only called when necessary
         let mut func_binding_args: HashMap<String, Option<serde_json::Value>> = HashMap::new();
+
+        // Aggregation frames merge the values of their children the way that was chosen for the
+        // socket, if any (see SocketMergeStrategy).
+        let merge_strategy = if self.context.component_id() != ComponentId::NONE
+            && self
+                .context
+                .is_least_specific_field_kind_external_provider()?
+        {
+            SocketMergeStrategy::find_for_external_provider(
+                ctx,
+                self.context.component_id(),
+                self.context.external_provider_id(),
+            )
+            .await
+            .map_err(|e| AttributeValueError::SocketMergeStrategy(e.to_string()))?
+        } else {
+            None
+        };
+
         for mut argument_data in attribute_prototype
             .argument_values(ctx, self.context)
             .await
             .map_err(|e| AttributeValueError::AttributePrototype(e.to_string()))?
         {
+            if let Some(merge_strategy) = merge_strategy {
+                // Only the values of the children are merged.
+                let values = argument_data
+                    .tail_component_ids
+                    .into_iter()
+                    .zip(argument_data.values)
+                    .filter(|(tail_component_id, _)| *tail_component_id != ComponentId::NONE)
+                    .collect();
+                func_binding_args.insert(
+                    argument_data.argument_name,
+                    Some(merge_strategy.merge(values)),
+                );
+                continue;
+            }
+
             match argument_data.values.len() {
                 1 => {
                     let argument = argument_data.values.pop().ok_or_else(|| {
@@ -1091,35 +1256,99 @@ impl AttributeValue {
         // We need the associated [`ComponentId`] for this function--this is how we resolve and
         // prepare before functions
         let associated_component_id = self.context.component_id();
+
+        // Code generation functions also receive a summary of the components connected to this
+        // one, so that generated code can reference values resolved on the other end of an edge.
+        if associated_component_id != ComponentId::NONE {
+            let func = Func::get_by_id(ctx, &func_id)
+                .await?
+                .ok_or_else(|| AttributeValueError::MissingFunc(func_id.to_string()))?;
+            if *func.backend_response_type() == FuncBackendResponseType::CodeGeneration {
+                let connections =
+                    Component::connections_for_code_generation(ctx, associated_component_id)
+                        .await
+                        .map_err(|e| AttributeValueError::Component(e.to_string()))?;
+                func_binding_args.insert(
+                    "connections".to_owned(),
+                    Some(serde_json::to_value(connections)?),
+                );
+
+                // Generated code must not churn between runs, so the inputs are given the order
+                // of their assembled views (see MapKeyOrder) rather than the one they were
+                // stored with.
+                for (argument_name, argument_value) in func_binding_args.iter_mut() {
+                    let (Some(location), Some(argument_value)) = (
+                        LeafInputLocation::maybe_from_arg_name(argument_name),
+                        argument_value.as_mut(),
+                    ) else {
+                        continue;
+                    };
+                    let input_attribute_value =
+                        Component::root_prop_child_attribute_value_for_component(
+                            ctx,
+                            associated_component_id,
+                            location.into(),
+                        )
+                        .await
+                        .map_err(|e| AttributeValueError::Component(e.to_string()))?;
+                    let input_view = AttributeView::new(
+                        ctx,
+                        AttributeReadContext {
+                            prop_id: None,
+                            ..AttributeReadContext::from(input_attribute_value.context)
+                        },
+                        Some(*input_attribute_value.id()),
+                    )
+                    .await?;
+                    input_view.apply_order_to(argument_value);
+                }
+
+                // Every value a code generation reads is in its args, so identical args mean
+                // identical code: keep the existing value rather than asking veritech again.
+                if self
+                    .code_generation_inputs_unchanged(ctx, &func, &func_binding_args)
+                    .await?
+                {
+                    debug!(
+                        "code generation inputs unchanged, skipping execution of {}",
+                        func.name()
+                    );
+                    return Ok(());
+                }
+            }
+        }
         let before = before_funcs_for_component(ctx, &associated_component_id).await?;
 
-        let (func_binding, mut func_binding_return_value) = match FuncBinding::create_and_execute(
-            ctx,
-            serde_json::to_value(func_binding_args.clone())?,
-            attribute_prototype.func_id(),
-            before,
-        )
-        .instrument(debug_span!(
-            "Func execution",
-            "func.id" = %func_id,
-            ?func_binding_args,
-        ))
-        .await
-        {
-            Ok(function_return_value) => function_return_value,
-            Err(FuncBindingError::FuncBackendResultFailure {
-                kind,
-                message,
-                backend,
-            }) => {
-                return Err(AttributeValueError::FuncBackendResultFailure {
+        let (func_binding, mut func_binding_return_value) =
+            match FuncBinding::create_and_execute_for_component(
+                ctx,
+                serde_json::to_value(func_binding_args.clone())?,
+                attribute_prototype.func_id(),
+                before,
+                associated_component_id,
+                self.id,
+            )
+            .instrument(debug_span!(
+                "Func execution",
+                "func.id" = %func_id,
+                ?func_binding_args,
+            ))
+            .await
+            {
+                Ok(function_return_value) => function_return_value,
+                Err(FuncBindingError::FuncBackendResultFailure {
                     kind,
                     message,
                     backend,
-                });
-            }
-            Err(err) => Err(err)?,
-        };
+                }) => {
+                    return Err(AttributeValueError::FuncBackendResultFailure {
+                        kind,
+                        message,
+                        backend,
+                    });
+                }
+                Err(err) => Err(err)?,
+            };
 
         self.set_func_binding_id(ctx, *func_binding.id()).await?;
         self.set_func_binding_return_value_id(ctx, *func_binding_return_value.id())
@@ -1169,9 +1398,8 @@ impl AttributeValue {
                     _ => unreachable!(),
                 };
 
-                let func = Func::find_by_attr(ctx, "name", &func_name)
+                let func = Func::find_by_name(ctx, func_name)
                     .await?
-                    .pop()
                     .ok_or_else(|| AttributeValueError::MissingFunc(func_name.to_owned()))?;
 
                 if attribute_prototype.func_id() != *func.id() {
@@ -1249,3 +1477,35 @@ impl AttributeValuePayload {
         }
     }
 }
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeValueUpdatedPayload {
+    component_id: ComponentId,
+    attribute_value_id: AttributeValueId,
+    prop_path: String,
+    value: Option<serde_json::Value>,
+    change_set_pk: ChangeSetPk,
+}
+
+impl WsEvent {
+    pub async fn attribute_value_updated(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        attribute_value_id: AttributeValueId,
+        prop_path: String,
+        value: Option<serde_json::Value>,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::AttributeValueUpdated(AttributeValueUpdatedPayload {
+                component_id,
+                attribute_value_id,
+                prop_path,
+                value,
+                change_set_pk: ctx.visibility().change_set_pk,
+            }),
+        )
+        .await
+    }
+}
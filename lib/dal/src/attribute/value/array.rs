@@ -0,0 +1,159 @@
+//! This module contains helpers for editing the elements of an [`AttributeValue`] whose
+//! [`Prop`](crate::Prop) is an [`array`](crate::PropKind::Array): inserting an element at a
+//! given position, removing one and reordering all of them. The position of every element is
+//! tracked by the [`IndexMap`](crate::index_map::IndexMap) of the array's [`AttributeValue`].
+
+use serde_json::Value;
+use telemetry::prelude::*;
+
+use crate::attribute::value::{AttributeValueError, AttributeValueResult};
+use crate::job::definition::DependentValuesUpdate;
+use crate::{
+    AttributeContextBuilder, AttributePrototype, AttributeValue, AttributeValueId, DalContext,
+    Prop, PropKind, StandardModel,
+};
+
+impl AttributeValue {
+    /// Insert a new element with the provided value into the array [`AttributeValue`]
+    /// corresponding to the provided [`AttributeValueId`]. The element is placed at `index`, or
+    /// at the end of the array if `index` is `None` or past the end.
+    #[instrument(skip(ctx, value), level = "debug")]
+    pub async fn insert_into_array(
+        ctx: &DalContext,
+        array_attribute_value_id: AttributeValueId,
+        value: Option<Value>,
+        index: Option<usize>,
+    ) -> AttributeValueResult<AttributeValueId> {
+        let array_attribute_value = Self::get_array(ctx, array_attribute_value_id).await?;
+        let array_prop_id = array_attribute_value.context.prop_id();
+        let array_prop = Prop::get_by_id(ctx, &array_prop_id)
+            .await?
+            .ok_or(AttributeValueError::PropNotFound(array_prop_id))?;
+        let element_prop = array_prop
+            .child_props(ctx)
+            .await
+            .map_err(Box::new)?
+            .pop()
+            .ok_or(AttributeValueError::MissingChildProp(array_prop_id))?;
+
+        let element_context = AttributeContextBuilder::from(array_attribute_value.context)
+            .set_prop_id(*element_prop.id())
+            .to_context()?;
+        let element_attribute_value_id =
+            Self::insert_for_context(ctx, element_context, array_attribute_value_id, value, None)
+                .await?;
+
+        if let Some(index) = index {
+            // Inserting always appends, so the index map has to be reloaded before moving.
+            let mut array_attribute_value = Self::get_array(ctx, array_attribute_value_id).await?;
+            if let Some(index_map) = array_attribute_value.index_map_mut() {
+                index_map.move_to(element_attribute_value_id, index);
+                index_map.reindex();
+            }
+            array_attribute_value.finish_array_update(ctx).await?;
+        }
+
+        Ok(element_attribute_value_id)
+    }
+
+    /// Remove the element corresponding to `element_attribute_value_id` (and its children) from
+    /// the array [`AttributeValue`] corresponding to `array_attribute_value_id`.
+    #[instrument(skip(ctx), level = "debug")]
+    pub async fn remove_from_array(
+        ctx: &DalContext,
+        array_attribute_value_id: AttributeValueId,
+        element_attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<()> {
+        let mut array_attribute_value = Self::get_array(ctx, array_attribute_value_id).await?;
+        let index_map = array_attribute_value.index_map_mut().ok_or(
+            AttributeValueError::ArrayElementNotFound(
+                array_attribute_value_id,
+                element_attribute_value_id,
+            ),
+        )?;
+        if !index_map.order().contains(&element_attribute_value_id) {
+            return Err(AttributeValueError::ArrayElementNotFound(
+                array_attribute_value_id,
+                element_attribute_value_id,
+            ));
+        }
+        index_map.delete(element_attribute_value_id);
+        index_map.reindex();
+
+        let mut element_attribute_value = Self::get_by_id(ctx, &element_attribute_value_id)
+            .await?
+            .ok_or(AttributeValueError::MissingForId(
+                element_attribute_value_id,
+            ))?;
+        match element_attribute_value.attribute_prototype(ctx).await? {
+            // Removing the prototype takes the element and all of its children with it.
+            Some(attribute_prototype) => {
+                AttributePrototype::remove(ctx, attribute_prototype.id(), false)
+                    .await
+                    .map_err(|e| AttributeValueError::AttributePrototype(e.to_string()))?
+            }
+            None => element_attribute_value.delete_by_id(ctx).await?,
+        }
+
+        array_attribute_value.finish_array_update(ctx).await
+    }
+
+    /// Reorder the elements of the array [`AttributeValue`] corresponding to
+    /// `array_attribute_value_id`. The new order must contain exactly the current elements.
+    #[instrument(skip(ctx), level = "debug")]
+    pub async fn reorder_array(
+        ctx: &DalContext,
+        array_attribute_value_id: AttributeValueId,
+        order: Vec<AttributeValueId>,
+    ) -> AttributeValueResult<()> {
+        let mut array_attribute_value = Self::get_array(ctx, array_attribute_value_id).await?;
+        let reordered = match array_attribute_value.index_map_mut() {
+            Some(index_map) => {
+                let reordered = index_map.reorder(order.clone());
+                index_map.reindex();
+                reordered
+            }
+            None => order.is_empty(),
+        };
+        if !reordered {
+            return Err(AttributeValueError::ArrayOrderMismatch(
+                array_attribute_value_id,
+            ));
+        }
+
+        array_attribute_value.finish_array_update(ctx).await
+    }
+
+    async fn get_array(
+        ctx: &DalContext,
+        array_attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<Self> {
+        let array_attribute_value = Self::get_by_id(ctx, &array_attribute_value_id)
+            .await?
+            .ok_or(AttributeValueError::MissingForId(array_attribute_value_id))?;
+        let prop_id = array_attribute_value.context.prop_id();
+        let prop = Prop::get_by_id(ctx, &prop_id)
+            .await?
+            .ok_or(AttributeValueError::PropNotFound(prop_id))?;
+        if *prop.kind() != PropKind::Array {
+            return Err(AttributeValueError::UnexpectedPropKind(*prop.kind()));
+        }
+        Ok(array_attribute_value)
+    }
+
+    /// Store the index map of the array and recompute everything that depends on it.
+    async fn finish_array_update(&self, ctx: &DalContext) -> AttributeValueResult<()> {
+        self.update_stored_index_map(ctx).await?;
+
+        if !ctx.no_dependent_values() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                vec![self.id],
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
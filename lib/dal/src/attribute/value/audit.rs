@@ -0,0 +1,221 @@
+//! This module contains [`AttributeValueAudit`], a record of a value being set on a
+//! [`Component`](crate::Component)'s [`Prop`](crate::Prop), and the means to roll the
+//! [`Prop`](crate::Prop) back to a previously audited value.
+//!
+//! Only values set through [`AttributeValue::update_for_context()`] are audited. Values computed
+//! by functions are derived from other values and would be recomputed right after a rollback.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::attribute::value::{AttributeValueError, AttributeValueResult};
+use crate::func::binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueId};
+use crate::{
+    pk, standard_model, AttributeContextBuilder, AttributeReadContext, AttributeValue,
+    AttributeValueId, ChangeSetPk, ComponentId, DalContext, HistoryActor, PropId, StandardModel,
+    WorkspacePk,
+};
+
+const AUDIT_INSERT: &str = include_str!("../../queries/attribute_value/audit_insert.sql");
+const AUDIT_GET: &str = include_str!("../../queries/attribute_value/audit_get.sql");
+const AUDIT_LIST_FOR_COMPONENT_AND_PROP: &str =
+    include_str!("../../queries/attribute_value/audit_list_for_component_and_prop.sql");
+
+pk!(AttributeValueAuditPk);
+
+/// A change of the [`FuncBindingReturnValue`] of an [`AttributeValue`], who made it and when.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttributeValueAudit {
+    pk: AttributeValueAuditPk,
+    #[serde(rename = "tenancy_workspace_pk")]
+    workspace_pk: WorkspacePk,
+    #[serde(rename = "visibility_change_set_pk")]
+    change_set_pk: ChangeSetPk,
+    attribute_value_id: AttributeValueId,
+    component_id: ComponentId,
+    prop_id: PropId,
+    previous_func_binding_return_value_id: Option<FuncBindingReturnValueId>,
+    func_binding_return_value_id: FuncBindingReturnValueId,
+    actor: HistoryActor,
+    created_at: DateTime<Utc>,
+}
+
+impl AttributeValueAudit {
+    pub fn pk(&self) -> AttributeValueAuditPk {
+        self.pk
+    }
+
+    pub fn change_set_pk(&self) -> ChangeSetPk {
+        self.change_set_pk
+    }
+
+    pub fn attribute_value_id(&self) -> AttributeValueId {
+        self.attribute_value_id
+    }
+
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    pub fn prop_id(&self) -> PropId {
+        self.prop_id
+    }
+
+    pub fn previous_func_binding_return_value_id(&self) -> Option<FuncBindingReturnValueId> {
+        self.previous_func_binding_return_value_id
+    }
+
+    pub fn func_binding_return_value_id(&self) -> FuncBindingReturnValueId {
+        self.func_binding_return_value_id
+    }
+
+    pub fn actor(&self) -> HistoryActor {
+        self.actor
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Returns the value that was set.
+    pub async fn value(&self, ctx: &DalContext) -> AttributeValueResult<Option<serde_json::Value>> {
+        match FuncBindingReturnValue::get_by_id(ctx, &self.func_binding_return_value_id).await? {
+            Some(func_binding_return_value) => {
+                Ok(func_binding_return_value.unprocessed_value().cloned())
+            }
+            None => Err(AttributeValueError::MissingFuncBindingReturnValue),
+        }
+    }
+
+    pub async fn get(
+        ctx: &DalContext,
+        pk: AttributeValueAuditPk,
+    ) -> AttributeValueResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                AUDIT_GET,
+                &[
+                    &ctx.tenancy().workspace_pk(),
+                    &ctx.visibility().change_set_pk,
+                    &pk,
+                ],
+            )
+            .await?;
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+
+    /// List the audits of a [`Prop`](crate::Prop) on a [`Component`](crate::Component), most
+    /// recent first. Audits made on head are included when in a change set.
+    pub async fn list_for_component_and_prop(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        prop_id: PropId,
+    ) -> AttributeValueResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                AUDIT_LIST_FOR_COMPONENT_AND_PROP,
+                &[
+                    &ctx.tenancy().workspace_pk(),
+                    &ctx.visibility().change_set_pk,
+                    &component_id,
+                    &prop_id,
+                ],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+}
+
+impl AttributeValue {
+    /// Record that the [`AttributeValue`] corresponding to `attribute_value_id` was set, if it
+    /// belongs to a [`Component`](crate::Component)'s [`Prop`](crate::Prop).
+    pub(crate) async fn record_audit(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        previous_func_binding_return_value_id: Option<FuncBindingReturnValueId>,
+    ) -> AttributeValueResult<()> {
+        let workspace_pk = match ctx.tenancy().workspace_pk() {
+            Some(workspace_pk) => workspace_pk,
+            None => return Ok(()),
+        };
+        let attribute_value = Self::get_by_id(ctx, &attribute_value_id)
+            .await?
+            .ok_or(AttributeValueError::MissingForId(attribute_value_id))?;
+        let context = attribute_value.context;
+        if context.is_component_unset() || context.prop_id() == PropId::NONE {
+            return Ok(());
+        }
+        if previous_func_binding_return_value_id
+            == Some(attribute_value.func_binding_return_value_id())
+        {
+            return Ok(());
+        }
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_one(
+                AUDIT_INSERT,
+                &[
+                    &workspace_pk,
+                    &ctx.visibility().change_set_pk,
+                    &attribute_value_id,
+                    &context.component_id(),
+                    &context.prop_id(),
+                    &previous_func_binding_return_value_id,
+                    &attribute_value.func_binding_return_value_id(),
+                    &serde_json::to_value(ctx.history_actor())?,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Roll the [`Prop`](crate::Prop) of an [`AttributeValueAudit`] back to the value it
+    /// recorded. The rollback is itself audited.
+    #[instrument(skip(ctx), level = "debug")]
+    pub async fn revert_to(
+        ctx: &DalContext,
+        audit_pk: AttributeValueAuditPk,
+    ) -> AttributeValueResult<AttributeValueId> {
+        let audit = AttributeValueAudit::get(ctx, audit_pk)
+            .await?
+            .ok_or(AttributeValueError::AuditNotFound(audit_pk))?;
+        let value = audit.value(ctx).await?;
+
+        let read_context = AttributeReadContext {
+            prop_id: Some(audit.prop_id),
+            component_id: Some(audit.component_id),
+            ..AttributeReadContext::default()
+        };
+        let attribute_value = Self::find_for_context(ctx, read_context)
+            .await?
+            .ok_or(AttributeValueError::NotFoundForReadContext(read_context))?;
+        let parent_attribute_value_id = attribute_value
+            .parent_attribute_value(ctx)
+            .await?
+            .map(|parent| *parent.id());
+        let context = AttributeContextBuilder::new()
+            .set_prop_id(audit.prop_id)
+            .set_component_id(audit.component_id)
+            .to_context()?;
+
+        let (_, attribute_value_id) = Self::update_for_context(
+            ctx,
+            *attribute_value.id(),
+            parent_attribute_value_id,
+            context,
+            value,
+            attribute_value.key,
+        )
+        .await?;
+        Ok(attribute_value_id)
+    }
+}
@@ -0,0 +1,90 @@
+//! This module contains [`StaleAttributeValue`], which describes an [`AttributeValue`] whose
+//! inputs changed after it was last computed. This object does not exist in the database.
+//!
+//! Every recomputation of an [`AttributeValue`] records a new
+//! [`FuncBindingReturnValue`](crate::FuncBindingReturnValue) and therefore bumps its
+//! `updated_at`. A value that is older than one of the provider values feeding its
+//! [`AttributePrototype`](crate::AttributePrototype) was missed by the dependent values update
+//! that should have recomputed it (or that update has not run yet).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::job::definition::DependentValuesUpdate;
+use crate::{AttributeValue, AttributeValueId, AttributeValueResult, ComponentId, DalContext};
+
+const LIST_STALE: &str = include_str!("../../queries/attribute_value/list_stale.sql");
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleAttributeValue {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub updated_at: DateTime<Utc>,
+    /// The most recently updated input that the value has not caught up with.
+    pub upstream_attribute_value_id: AttributeValueId,
+    pub upstream_updated_at: DateTime<Utc>,
+}
+
+impl AttributeValue {
+    /// Find every [`AttributeValue`] that is older than one of its inputs, optionally limited to
+    /// a single [`Component`](crate::Component).
+    ///
+    /// Values with a [`DependentValuesUpdate`] still in flight will show up here until the job
+    /// finishes.
+    #[instrument(skip(ctx), level = "debug")]
+    pub async fn list_stale(
+        ctx: &DalContext,
+        component_id: Option<ComponentId>,
+    ) -> AttributeValueResult<Vec<StaleAttributeValue>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_STALE,
+                &[ctx.tenancy(), ctx.visibility(), &component_id],
+            )
+            .await?;
+
+        let mut stale = Vec::with_capacity(rows.len());
+        for row in rows {
+            stale.push(StaleAttributeValue {
+                attribute_value_id: row.try_get("attribute_value_id")?,
+                component_id: row.try_get("component_id")?,
+                updated_at: row.try_get("updated_at")?,
+                upstream_attribute_value_id: row.try_get("upstream_attribute_value_id")?,
+                upstream_updated_at: row.try_get("upstream_updated_at")?,
+            });
+        }
+
+        Ok(stale)
+    }
+
+    /// Enqueue a [`DependentValuesUpdate`] seeded with the inputs of the given
+    /// [`StaleAttributeValues`](StaleAttributeValue), which recomputes them (and everything
+    /// downstream of them). Returns the seeded [`AttributeValueIds`](AttributeValueId).
+    pub async fn repair_stale(
+        ctx: &DalContext,
+        stale: &[StaleAttributeValue],
+    ) -> AttributeValueResult<Vec<AttributeValueId>> {
+        let mut upstream_ids: Vec<AttributeValueId> = stale
+            .iter()
+            .map(|stale| stale.upstream_attribute_value_id)
+            .collect();
+        upstream_ids.sort();
+        upstream_ids.dedup();
+
+        if !upstream_ids.is_empty() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                upstream_ids.clone(),
+            ))
+            .await?;
+        }
+
+        Ok(upstream_ids)
+    }
+}
@@ -7,8 +7,8 @@ use telemetry::prelude::*;
 
 use crate::{
     AttributeReadContext, AttributeValue, AttributeValueError, AttributeValueId,
-    AttributeValuePayload, AttributeValueResult, DalContext, Prop, PropError, PropKind,
-    StandardModel,
+    AttributeValuePayload, AttributeValueResult, DalContext, MapKeyOrder, Prop, PropError,
+    PropKind, StandardModel,
 };
 
 /// A generated view for an [`AttributeReadContext`](crate::AttributeReadContext) and an optional
@@ -79,18 +79,30 @@ impl AttributeView {
 
         // We sort the work queue according to the order of every nested IndexMap. This ensures that
         // when we reconstruct the final shape, we don't have to worry about the order that things
-        // appear in.
+        // appear in. Map entries follow the MapKeyOrder of their Prop and object fields, which are
+        // not in any IndexMap, follow the order their Props were created in. This keeps the
+        // generated value (and anything serialized from it) stable across runs.
         let attribute_value_order: Vec<AttributeValueId> = initial_work
             .iter()
-            .filter_map(|avp| avp.attribute_value.index_map())
-            .flat_map(|index_map| index_map.order())
-            .copied()
+            .filter_map(|avp| {
+                avp.attribute_value.index_map().map(|index_map| {
+                    let map_key_order = match avp.prop.kind() {
+                        PropKind::Map => *avp.prop.map_key_order(),
+                        _ => MapKeyOrder::Insertion,
+                    };
+                    index_map.order_by(map_key_order)
+                })
+            })
+            .flatten()
             .collect();
         initial_work.sort_by_cached_key(|avp| {
-            attribute_value_order
-                .iter()
-                .position(|attribute_value_id| attribute_value_id == avp.attribute_value.id())
-                .unwrap_or(0)
+            (
+                attribute_value_order
+                    .iter()
+                    .position(|attribute_value_id| attribute_value_id == avp.attribute_value.id())
+                    .unwrap_or(0),
+                *avp.prop.id(),
+            )
         });
 
         // We need the work queue to be a VecDeque so we can pop elements off of the front
@@ -251,4 +263,41 @@ impl AttributeView {
     pub fn json_pointers_for_attribute_value_id(&self) -> &HashMap<AttributeValueId, String> {
         &self.json_pointer_for_attribute_value_id
     }
+
+    /// Reorder the keys of every object in `value` to match the order of [`self`](Self). Values
+    /// read back from the database lose that order, so this is used to restore it before they are
+    /// handed to functions. Keys missing from the view keep their relative order after the others.
+    pub fn apply_order_to(&self, value: &mut Value) {
+        order_value_like(value, &self.value);
+    }
+}
+
+fn order_value_like(value: &mut Value, ordered: &Value) {
+    match (value, ordered) {
+        (Value::Object(object), Value::Object(ordered_object)) => {
+            let mut reordered = serde_json::Map::with_capacity(object.len());
+            for key in ordered_object.keys() {
+                if let Some(child) = object.get(key) {
+                    reordered.insert(key.clone(), child.clone());
+                }
+            }
+            for (key, child) in object.iter() {
+                if !reordered.contains_key(key) {
+                    reordered.insert(key.clone(), child.clone());
+                }
+            }
+            for (key, child) in reordered.iter_mut() {
+                if let Some(ordered_child) = ordered_object.get(key) {
+                    order_value_like(child, ordered_child);
+                }
+            }
+            *object = reordered;
+        }
+        (Value::Array(array), Value::Array(ordered_array)) => {
+            for (child, ordered_child) in array.iter_mut().zip(ordered_array) {
+                order_value_like(child, ordered_child);
+            }
+        }
+        _ => {}
+    }
 }
@@ -3,12 +3,17 @@
 //! [migrate()](crate::builtins::migrate_local()) function. However, they may have some functionality
 //! exposed for "dev mode" use cases.
 
-use std::collections::HashSet;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use strum::{AsRefStr, Display, EnumIter, EnumString, IntoEnumIterator};
 use telemetry::prelude::*;
 use thiserror::Error;
 
 use si_pkg::{SiPkgError, SpecError};
 
+use crate::builtins::checkpoint::BuiltinMigrationCheckpointError;
 use crate::func::argument::FuncArgumentError;
 use crate::func::binding::FuncBindingError;
 use crate::func::binding_return_value::FuncBindingReturnValueError;
@@ -21,12 +26,13 @@ use crate::schema::variant::SchemaVariantError;
 use crate::socket::SocketError;
 use crate::{
     AttributeContextBuilderError, AttributePrototypeArgumentError, AttributePrototypeError,
-    AttributeReadContext, AttributeValueError, AttributeValueId, DalContext, ExternalProviderId,
-    FuncError, InternalProviderId, PropError, PropId, SchemaError, SchemaVariantId,
-    StandardModelError, TransactionsError,
+    AttributeReadContext, AttributeValueError, AttributeValueId, ConfirmationPrototypeError,
+    DalContext, ExternalProviderId, FuncError, InternalProviderId, PropError, PropId, PropKind,
+    SchemaError, SchemaVariantId, StandardModelError, TransactionsError,
 };
 
 // Private builtins modules.
+pub mod checkpoint;
 pub mod func;
 pub mod schema;
 
@@ -55,8 +61,12 @@ pub enum BuiltinsError {
     AttributeValueNotFound(AttributeValueId),
     #[error("attribute value not found for attribute read context: {0:?}")]
     AttributeValueNotFoundForContext(AttributeReadContext),
+    #[error("builtin migration checkpoint error: {0}")]
+    BuiltinMigrationCheckpoint(#[from] BuiltinMigrationCheckpointError),
     #[error("builtin {0} missing func argument {1}")]
     BuiltinMissingFuncArgument(String, String),
+    #[error("confirmation prototype error: {0}")]
+    ConfirmationPrototype(#[from] ConfirmationPrototypeError),
     #[error("explicit internal provider not found by name: {0}")]
     ExplicitInternalProviderNotFound(String),
     #[error("external provider error: {0}")]
@@ -85,12 +95,18 @@ pub enum BuiltinsError {
     InstalledPkg(#[from] InstalledPkgError),
     #[error("internal provider error: {0}")]
     InternalProvider(#[from] InternalProviderError),
+    #[error("builtin migration task join error: {0}")]
+    MigrationTaskJoin(tokio::task::JoinError),
+    #[error("builtin migration tasks closed before completing")]
+    MigrationTasksClosed,
     #[error("missing attribute prototype for attribute value")]
     MissingAttributePrototypeForAttributeValue,
     #[error("missing attribute prototype for explicit internal provider: {0}")]
     MissingAttributePrototypeForExplicitInternalProvider(InternalProviderId),
     #[error("missing attribute prototype for external provider: {0}")]
     MissingAttributePrototypeForExternalProvider(ExternalProviderId),
+    #[error("item prop not found for map prop: {0}")]
+    MissingItemPropForMapProp(PropId),
     #[error("no packages path configured")]
     MissingPkgsPath,
     #[error(transparent)]
@@ -101,6 +117,8 @@ pub enum BuiltinsError {
     PropCacheNotFound(SchemaVariantId),
     #[error("prop not bound by id: {0}")]
     PropNotFound(PropId),
+    #[error("prop {0} is a {1}, not a map")]
+    PropNotMap(PropId, PropKind),
     #[error("Regex parsing error: {0}")]
     Regex(#[from] regex::Error),
     #[error("schema error: {0}")]
@@ -143,6 +161,105 @@ pub enum SelectedTestBuiltinSchemas {
     Test,
 }
 
+/// The families of builtin [`Schemas`](crate::Schema), named after the packages they are migrated
+/// from (e.g. "si-aws-ec2-2023-09-26.sipkg" belongs to [`Aws`](Self::Aws)).
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BuiltinSchemaFamily {
+    Aws,
+    Coreos,
+    Docker,
+    GenericFrame,
+}
+
+impl BuiltinSchemaFamily {
+    /// The family of a builtin package, found by its name or file name.
+    pub fn for_pkg(pkg_name: impl AsRef<str>) -> Option<Self> {
+        let pkg_name = pkg_name.as_ref();
+        Self::iter().find(|family| pkg_name.starts_with(&format!("si-{family}-")))
+    }
+}
+
+/// Which families of builtin [`Schemas`](crate::Schema) to migrate, so that test environments
+/// and trimmed deployments only pay for the [`Schemas`](crate::Schema) they use.
+///
+/// It is written as "all", "none" or a comma separated list of
+/// [`BuiltinSchemaFamilies`](BuiltinSchemaFamily), e.g. "aws,generic-frame".
+#[derive(Clone, Debug, Default, DeserializeFromStr, Eq, PartialEq, SerializeDisplay)]
+pub enum BuiltinSchemaMigrationMode {
+    /// Migrate every family (default behavior).
+    #[default]
+    All,
+    /// Migrate no family.
+    None,
+    /// Migrate the listed families only.
+    Only(BTreeSet<BuiltinSchemaFamily>),
+}
+
+impl BuiltinSchemaMigrationMode {
+    /// Whether the given family is migrated.
+    pub fn includes(&self, family: BuiltinSchemaFamily) -> bool {
+        match self {
+            Self::All => true,
+            Self::None => false,
+            Self::Only(families) => families.contains(&family),
+        }
+    }
+
+    /// Whether the builtin package with the given name (or file name) is migrated. Packages that
+    /// belong to no family are migrated unless nothing is.
+    pub fn includes_pkg(&self, pkg_name: impl AsRef<str>) -> bool {
+        match BuiltinSchemaFamily::for_pkg(pkg_name) {
+            Some(family) => self.includes(family),
+            None => *self != Self::None,
+        }
+    }
+}
+
+impl fmt::Display for BuiltinSchemaMigrationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => f.write_str("all"),
+            Self::None => f.write_str("none"),
+            Self::Only(families) => {
+                let families: Vec<&str> = families.iter().map(AsRef::as_ref).collect();
+                f.write_str(&families.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for BuiltinSchemaMigrationMode {
+    type Err = strum::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "all" => Ok(Self::All),
+            "none" => Ok(Self::None),
+            families => Ok(Self::Only(
+                families
+                    .split(',')
+                    .map(|family| family.trim().parse())
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
 /// Migrate all local "builtins" in a definitive order.
 pub async fn migrate_local(
     ctx: &DalContext,
@@ -166,3 +283,49 @@ pub async fn migrate_local(
     info!("completed migrating functions, workflows and schemas");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_schema_family_for_pkg() {
+        assert_eq!(
+            Some(BuiltinSchemaFamily::Aws),
+            BuiltinSchemaFamily::for_pkg(SI_AWS_EC2_PKG)
+        );
+        assert_eq!(
+            Some(BuiltinSchemaFamily::GenericFrame),
+            BuiltinSchemaFamily::for_pkg(SI_GENERIC_FRAME_PKG)
+        );
+        assert_eq!(
+            Some(BuiltinSchemaFamily::Docker),
+            BuiltinSchemaFamily::for_pkg(SI_DOCKER_IMAGE_PKG)
+        );
+        assert_eq!(
+            None,
+            BuiltinSchemaFamily::for_pkg("si-kubernetes-2023-09-13.sipkg")
+        );
+    }
+
+    #[test]
+    fn builtin_schema_migration_mode_from_str() {
+        assert_eq!(
+            BuiltinSchemaMigrationMode::All,
+            "all".parse().expect("failed to parse")
+        );
+        assert_eq!(
+            BuiltinSchemaMigrationMode::None,
+            "none".parse().expect("failed to parse")
+        );
+        let mode: BuiltinSchemaMigrationMode =
+            "aws, generic-frame".parse().expect("failed to parse");
+        assert_eq!("aws,generic-frame", mode.to_string());
+        assert!(mode.includes_pkg(SI_AWS_PKG));
+        assert!(mode.includes_pkg(SI_GENERIC_FRAME_PKG));
+        assert!(!mode.includes_pkg(SI_COREOS_PKG));
+        assert!("aws,kubernetes"
+            .parse::<BuiltinSchemaMigrationMode>()
+            .is_err());
+    }
+}
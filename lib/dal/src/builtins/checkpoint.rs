@@ -0,0 +1,96 @@
+//! This module contains [`BuiltinMigrationCheckpoint`], a record of a builtin migration that ran
+//! to completion.
+//!
+//! Builtin migrations that patch [`Schemas`](crate::Schema) are not safe to run twice, since they
+//! may add [`Props`](crate::Prop) and sockets again. A checkpoint is recorded (and committed) as
+//! each of them completes, so that a migration that failed partway resumes from the first
+//! migration that did not complete. Checkpoints are keyed by the hash of the migration's source as
+//! well as its name, so a migration runs again once it is changed.
+
+use si_data_pg::PgError;
+use si_hash::Hash;
+use std::future::Future;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{BuiltinsResult, DalContext, TransactionsError};
+
+const EXISTS: &str = include_str!("../queries/builtin_migration_checkpoint/exists.sql");
+const INSERT: &str = include_str!("../queries/builtin_migration_checkpoint/insert.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum BuiltinMigrationCheckpointError {
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type BuiltinMigrationCheckpointResult<T> = Result<T, BuiltinMigrationCheckpointError>;
+
+/// Identifies a version of a builtin migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuiltinMigrationCheckpoint {
+    name: &'static str,
+    content_hash: String,
+}
+
+impl BuiltinMigrationCheckpoint {
+    /// Identify the migration `name` by its `source`, usually the `include_str!()` of the module
+    /// implementing it.
+    pub fn new(name: &'static str, source: &str) -> Self {
+        Self {
+            name,
+            content_hash: Hash::new(source.as_bytes()).to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn content_hash(&self) -> &str {
+        &self.content_hash
+    }
+
+    /// Whether this version of the migration has already completed.
+    pub async fn is_completed(&self, ctx: &DalContext) -> BuiltinMigrationCheckpointResult<bool> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(EXISTS, &[&self.name, &self.content_hash])
+            .await?;
+        Ok(row.try_get("completed")?)
+    }
+
+    /// Record that this version of the migration has completed. Recording it again is a no-op.
+    pub async fn complete(&self, ctx: &DalContext) -> BuiltinMigrationCheckpointResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(INSERT, &[&self.name, &self.content_hash])
+            .await?;
+        Ok(())
+    }
+
+    /// Run `migration` unless this version of it has already completed, then commit its work
+    /// along with its checkpoint.
+    pub async fn run(
+        &self,
+        ctx: &DalContext,
+        migration: impl Future<Output = BuiltinsResult<()>>,
+    ) -> BuiltinsResult<()> {
+        if self.is_completed(ctx).await? {
+            debug!(name = self.name, "skipping completed builtin migration");
+            return Ok(());
+        }
+
+        info!(name = self.name, "running builtin migration");
+        migration.await?;
+        self.complete(ctx).await?;
+        ctx.blocking_commit().await?;
+        Ok(())
+    }
+}
@@ -101,8 +101,7 @@ pub async fn migrate(ctx: &DalContext) -> BuiltinsResult<()> {
                 .to_string_lossy()
         );
 
-        let mut existing_func = Func::find_by_attr(ctx, "name", &func_name).await?;
-        if let Some(mut existing_func) = existing_func.pop() {
+        if let Some(mut existing_func) = Func::find_by_name(ctx, &func_name).await? {
             if *existing_func.backend_kind() != func_metadata.kind {
                 info!(
                     "updating backend kind for {:?} from {:?} to {:?}",
@@ -1,30 +1,121 @@
 use si_pkg::SiPkg;
 use std::collections::HashSet;
+use std::sync::Arc;
 use strum::{AsRefStr, Display, EnumIter, EnumString};
 use telemetry::prelude::*;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
+use crate::builtins::checkpoint::BuiltinMigrationCheckpoint;
+use crate::builtins::schema::aws_credential::migrate_aws_credential;
+use crate::builtins::schema::code_generation::migrate_code_generations;
+use crate::builtins::schema::confirmation::migrate_confirmations;
+use crate::builtins::schema::container_image_tag::migrate_container_image_tag_qualification;
+use crate::builtins::schema::discovery::migrate_aws_discoveries;
+use crate::builtins::schema::docker_registry_credential::migrate_docker_registry_credential;
+use crate::builtins::schema::security_group_rule::migrate_security_group_rule_qualifications;
+use crate::builtins::schema::tags::migrate_tags;
 use crate::builtins::schema::test_exclusive_schema_fallout::migrate_test_exclusive_schema_fallout;
 use crate::builtins::schema::test_exclusive_schema_starfield::migrate_test_exclusive_schema_starfield;
 use crate::installed_pkg::InstalledPkg;
 use crate::pkg::{import_pkg_from_pkg, ImportOptions};
-use crate::{BuiltinsError, BuiltinsResult, DalContext, SelectedTestBuiltinSchemas};
+use crate::{
+    BuiltinSchemaFamily, BuiltinSchemaMigrationMode, BuiltinsError, BuiltinsResult, DalContext,
+    SelectedTestBuiltinSchemas,
+};
 
+pub mod aws_credential;
+mod code_generation;
+mod confirmation;
+mod container_image_tag;
+pub mod discovery;
+pub mod docker_registry_credential;
+pub mod helpers;
+mod security_group_rule;
+mod tags;
 mod test_exclusive_schema_fallout;
 mod test_exclusive_schema_starfield;
 
+/// The number of builtin packages imported at the same time by [`migrate_local_schemas`].
+const MIGRATION_CONCURRENCY: usize = 4;
+
 /// Migrate [`Schemas`](crate::Schema) for production use.
 pub async fn migrate_local_all_schemas(ctx: &DalContext) -> BuiltinsResult<()> {
-    info!("migrating schemas");
-
-    migrate_pkg(ctx, super::SI_AWS_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_AWS_EC2_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_DOCKER_IMAGE_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_COREOS_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_GENERIC_FRAME_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_AWS_IAM_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_AWS_ECS_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_AWS_CLOUDWATCH_PKG, None).await?;
-    migrate_pkg(ctx, super::SI_AWS_LB_TARGET_GROUP_PKG, None).await?;
+    migrate_local_schemas(ctx, &BuiltinSchemaMigrationMode::All).await
+}
+
+/// Migrate the families of [`Schemas`](crate::Schema) selected by a
+/// [`BuiltinSchemaMigrationMode`]. Families that are already migrated are left untouched, so this
+/// can be called again later to bring in more of them, or to resume a migration that failed
+/// partway.
+pub async fn migrate_local_schemas(
+    ctx: &DalContext,
+    mode: &BuiltinSchemaMigrationMode,
+) -> BuiltinsResult<()> {
+    info!(%mode, "migrating schemas");
+
+    // Other packages bind "si:resourcePayloadToValue" from the generic frame package, so it has to
+    // be in place before they are imported.
+    if mode.includes_pkg(super::SI_GENERIC_FRAME_PKG) {
+        migrate_pkg(ctx, super::SI_GENERIC_FRAME_PKG, None).await?;
+    }
+    let pkg_filenames: Vec<&'static str> = [
+        super::SI_AWS_PKG,
+        super::SI_AWS_EC2_PKG,
+        super::SI_DOCKER_IMAGE_PKG,
+        super::SI_COREOS_PKG,
+        super::SI_AWS_IAM_PKG,
+        super::SI_AWS_ECS_PKG,
+        super::SI_AWS_CLOUDWATCH_PKG,
+        super::SI_AWS_LB_TARGET_GROUP_PKG,
+    ]
+    .into_iter()
+    .filter(|pkg_filename| mode.includes_pkg(pkg_filename))
+    .collect();
+    migrate_pkgs_concurrently(ctx, pkg_filenames).await?;
+
+    // The schemas below are patched after their packages are imported, so they run serially.
+    // They are also checkpointed, since running them twice would patch the schemas twice.
+    if mode.includes(BuiltinSchemaFamily::Docker) {
+        BuiltinMigrationCheckpoint::new(
+            "docker_registry_credential",
+            include_str!("schema/docker_registry_credential.rs"),
+        )
+        .run(ctx, migrate_docker_registry_credential(ctx))
+        .await?;
+        BuiltinMigrationCheckpoint::new(
+            "container_image_tag",
+            include_str!("schema/container_image_tag.rs"),
+        )
+        .run(ctx, migrate_container_image_tag_qualification(ctx))
+        .await?;
+    }
+    if mode.includes(BuiltinSchemaFamily::Aws) {
+        BuiltinMigrationCheckpoint::new(
+            "security_group_rule",
+            include_str!("schema/security_group_rule.rs"),
+        )
+        .run(ctx, migrate_security_group_rule_qualifications(ctx))
+        .await?;
+        BuiltinMigrationCheckpoint::new("aws_credential", include_str!("schema/aws_credential.rs"))
+            .run(ctx, migrate_aws_credential(ctx))
+            .await?;
+        BuiltinMigrationCheckpoint::new("tags", include_str!("schema/tags.rs"))
+            .run(ctx, migrate_tags(ctx))
+            .await?;
+        BuiltinMigrationCheckpoint::new("confirmation", include_str!("schema/confirmation.rs"))
+            .run(ctx, migrate_confirmations(ctx))
+            .await?;
+        BuiltinMigrationCheckpoint::new("discovery", include_str!("schema/discovery.rs"))
+            .run(ctx, migrate_aws_discoveries(ctx))
+            .await?;
+        BuiltinMigrationCheckpoint::new(
+            "code_generation",
+            include_str!("schema/code_generation.rs"),
+        )
+        .run(ctx, migrate_code_generations(ctx))
+        .await?;
+    }
 
     Ok(())
 }
@@ -63,6 +154,14 @@ pub async fn migrate_local_only_test_schemas(
         migrate_pkg(ctx, super::SI_DOCKER_IMAGE_PKG, None).await?;
         migrate_pkg(ctx, super::SI_GENERIC_FRAME_PKG, None).await?;
         migrate_pkg(ctx, super::SI_AWS_LB_TARGET_GROUP_PKG, None).await?;
+        migrate_docker_registry_credential(ctx).await?;
+        migrate_container_image_tag_qualification(ctx).await?;
+        migrate_security_group_rule_qualifications(ctx).await?;
+        migrate_aws_credential(ctx).await?;
+        migrate_tags(ctx).await?;
+        migrate_confirmations(ctx).await?;
+        migrate_aws_discoveries(ctx).await?;
+        migrate_code_generations(ctx).await?;
 
         migrate_pkg_test_exclusive(ctx, TestExclusiveSchema::Fallout).await?;
         migrate_pkg_test_exclusive(ctx, TestExclusiveSchema::Starfield).await?;
@@ -128,6 +227,51 @@ async fn migrate_pkg(
     Ok(())
 }
 
+/// Import independent packages in parallel tasks, at most [`MIGRATION_CONCURRENCY`] at a time.
+///
+/// Every task works in its own [`DalContext`] and commits it when its package is imported, so the
+/// work pending in the given [`DalContext`] is committed first for the tasks to build upon.
+async fn migrate_pkgs_concurrently(
+    ctx: &DalContext,
+    pkg_filenames: Vec<&'static str>,
+) -> BuiltinsResult<()> {
+    ctx.blocking_commit().await?;
+
+    let semaphore = Arc::new(Semaphore::new(MIGRATION_CONCURRENCY));
+    let mut migrate_tasks = JoinSet::new();
+    for pkg_filename in pkg_filenames {
+        let builder = ctx.to_builder();
+        let request_context = ctx.access_builder().build(*ctx.visibility());
+        let semaphore = semaphore.clone();
+        migrate_tasks.spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .map_err(|_| BuiltinsError::MigrationTasksClosed)?;
+            let ctx = builder.build(request_context).await?;
+            migrate_pkg(&ctx, pkg_filename, None).await?;
+            ctx.blocking_commit().await?;
+            Ok::<(), BuiltinsError>(())
+        });
+    }
+
+    // Wait for every task, rather than bailing at the first failure, so no import is left
+    // running in the background. The first error is the one reported.
+    let mut result = Ok(());
+    while let Some(joined) = migrate_tasks.join_next().await {
+        let migrated = joined
+            .map_err(BuiltinsError::MigrationTaskJoin)
+            .and_then(|r| r);
+        if let Err(err) = migrated {
+            error!(error = ?err, "builtin package migration failed");
+            if result.is_ok() {
+                result = Err(err);
+            }
+        }
+    }
+    result
+}
+
 async fn migrate_pkg_test_exclusive(
     ctx: &DalContext,
     schema: TestExclusiveSchema,
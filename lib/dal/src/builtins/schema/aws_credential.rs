@@ -0,0 +1,277 @@
+use si_pkg::{
+    AttrFuncInputSpec, AttrFuncInputSpecKind, AuthenticationFuncSpec, FuncSpec,
+    FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, PropSpec,
+    PropSpecWidgetKind, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecData,
+    SiPkg, SocketSpec, SocketSpecArity, SocketSpecData, SocketSpecKind,
+};
+
+use crate::builtins::schema::helpers::BuiltinSchemaHelpers;
+use crate::func::intrinsics::IntrinsicFunc;
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::property_editor::schema::WidgetKind;
+use crate::socket::SocketArity;
+use crate::{prop::PropPath, PropKind};
+use crate::{
+    AttributePrototypeArgument, AttributeReadContext, AttributeValue, AttributeValueError,
+    BuiltinsResult, DalContext, Func, InternalProvider, Prop, Schema, SchemaVariant, StandardModel,
+};
+
+/// The name of the secret kind (and [`Schema`]) holding AWS access keys.
+pub const AWS_CREDENTIAL: &str = "AWS Credential";
+
+/// The name of the output socket of the "AWS Credential" [`Schema`].
+const AWS_CREDENTIAL_OUTPUT_SOCKET: &str = "Credential";
+
+/// The name of the authentication func exposing an "AWS Credential" to the AWS CLI.
+pub const AWS_CREDENTIAL_AUTH_FUNC: &str = "si:awsCredentialAuth";
+
+/// The AWS [`Schemas`](Schema) whose actions and generated code can use an "AWS Credential".
+const AWS_CREDENTIAL_CONSUMERS: &[&str] = &["Security Group", "Ingress", "Egress"];
+
+const AWS_CREDENTIAL_AUTH_CODE: &str = "async function auth(secret: Input): Promise<Output> {
+    requestStorage.setEnv(\"AWS_ACCESS_KEY_ID\", secret.AccessKeyId);
+    requestStorage.setEnv(\"AWS_SECRET_ACCESS_KEY\", secret.SecretAccessKey);
+    if (secret.SessionToken) {
+        requestStorage.setEnv(\"AWS_SESSION_TOKEN\", secret.SessionToken);
+    }
+}";
+
+/// Migrate the "AWS Credential" secret-defining [`Schema`] and give the AWS [`Schemas`](Schema)
+/// listed in [`AWS_CREDENTIAL_CONSUMERS`] an "AWS Credential" input socket feeding their
+/// "/root/secrets/AWS Credential" prop.
+///
+/// Connecting a credential to one of those [`Components`](crate::Component) runs the
+/// authentication func of the credential before its actions and attribute funcs, which exposes the
+/// keys to the AWS CLI through the environment.
+pub async fn migrate_aws_credential(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-aws-credential")
+        .version("2023-12-20")
+        .created_by("System Initiative");
+
+    let identity_func_spec = IntrinsicFunc::Identity.to_spec()?;
+
+    let fn_name = AWS_CREDENTIAL_AUTH_FUNC;
+    let auth_func = FuncSpec::builder()
+        .name(fn_name)
+        .unique_id(fn_name)
+        .data(
+            FuncSpecData::builder()
+                .name(fn_name)
+                .display_name("AWS Credential authentication")
+                .code_plaintext(AWS_CREDENTIAL_AUTH_CODE)
+                .handler("auth")
+                .backend_kind(FuncSpecBackendKind::JsAuthentication)
+                .response_type(FuncSpecBackendResponseType::Void)
+                .build()?,
+        )
+        .build()?;
+
+    let scaffold_func_code = "function createAsset() {\
+                return new AssetBuilder().build();
+            }";
+    let fn_name = "si:scaffoldAwsCredentialAsset";
+    let scaffold_func = FuncSpec::builder()
+        .name(fn_name)
+        .unique_id(fn_name)
+        .data(
+            FuncSpecData::builder()
+                .name(fn_name)
+                .code_plaintext(scaffold_func_code)
+                .handler("createAsset")
+                .backend_kind(FuncSpecBackendKind::JsSchemaVariantDefinition)
+                .response_type(FuncSpecBackendResponseType::SchemaVariantDefinition)
+                .build()?,
+        )
+        .build()?;
+
+    let credential_schema = SchemaSpec::builder()
+        .name(AWS_CREDENTIAL)
+        .data(
+            SchemaSpecData::builder()
+                .name(AWS_CREDENTIAL)
+                .category("AWS")
+                .category_name(AWS_CREDENTIAL)
+                .build()?,
+        )
+        .variant(
+            SchemaVariantSpec::builder()
+                .name("v0")
+                .unique_id("aws_credential_sv")
+                .data(
+                    SchemaVariantSpecData::builder()
+                        .name("v0")
+                        .color("#FF9900")
+                        .func_unique_id(&scaffold_func.unique_id)
+                        .build()?,
+                )
+                .secret_definition_prop(
+                    PropSpec::builder()
+                        .name("AccessKeyId")
+                        .kind(PropKind::String)
+                        .build()?,
+                )
+                .secret_definition_prop(
+                    PropSpec::builder()
+                        .name("SecretAccessKey")
+                        .kind(PropKind::String)
+                        .widget_kind(PropSpecWidgetKind::Password)
+                        .build()?,
+                )
+                .secret_definition_prop(
+                    PropSpec::builder()
+                        .name("SessionToken")
+                        .kind(PropKind::String)
+                        .widget_kind(PropSpecWidgetKind::Password)
+                        .documentation("Only needed for temporary credentials")
+                        .build()?,
+                )
+                .secret_prop(
+                    PropSpec::builder()
+                        .name(AWS_CREDENTIAL)
+                        .kind(PropKind::String)
+                        .widget_kind(PropSpecWidgetKind::Secret)
+                        .widget_options(secret_widget_options())
+                        .build()?,
+                )
+                .socket(
+                    SocketSpec::builder()
+                        .name(AWS_CREDENTIAL_OUTPUT_SOCKET)
+                        .data(
+                            SocketSpecData::builder()
+                                .name(AWS_CREDENTIAL_OUTPUT_SOCKET)
+                                .connection_annotations(connection_annotations()?)
+                                .kind(SocketSpecKind::Output)
+                                .arity(SocketSpecArity::One)
+                                .func_unique_id(&identity_func_spec.unique_id)
+                                .build()?,
+                        )
+                        .input(
+                            AttrFuncInputSpec::builder()
+                                .name("identity")
+                                .kind(AttrFuncInputSpecKind::Prop)
+                                .prop_path(PropPath::new(["root", "secrets", AWS_CREDENTIAL]))
+                                .build()?,
+                        )
+                        .build()?,
+                )
+                .auth_func(
+                    AuthenticationFuncSpec::builder()
+                        .func_unique_id(&auth_func.unique_id)
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+
+    let spec = builder
+        .func(identity_func_spec)
+        .func(auth_func)
+        .func(scaffold_func)
+        .schema(credential_schema)
+        .build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    for schema_name in AWS_CREDENTIAL_CONSUMERS {
+        for schema in Schema::find_by_attr(ctx, "name", schema_name).await? {
+            for mut variant in schema.variants(ctx).await? {
+                add_aws_credential_input(ctx, &mut variant).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn secret_widget_options() -> serde_json::Value {
+    serde_json::json!([{
+        "label": "secretKind",
+        "value": AWS_CREDENTIAL,
+    }])
+}
+
+fn connection_annotations() -> BuiltinsResult<String> {
+    Ok(serde_json::to_string(&vec![AWS_CREDENTIAL.to_lowercase()])?)
+}
+
+/// Add the "/root/secrets/AWS Credential" prop to a [`SchemaVariant`], set from a new
+/// "AWS Credential" input socket. Variants that already have the prop are left untouched.
+async fn add_aws_credential_input(
+    ctx: &DalContext,
+    schema_variant: &mut SchemaVariant,
+) -> BuiltinsResult<()> {
+    let schema_variant_id = *schema_variant.id();
+    let secret_prop_path = PropPath::new(["root", "secrets", AWS_CREDENTIAL]);
+    if Prop::find_prop_by_path_opt(ctx, schema_variant_id, &secret_prop_path)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    let secrets_prop =
+        Prop::find_prop_by_path(ctx, schema_variant_id, &PropPath::new(["root", "secrets"]))
+            .await?;
+    let secret_prop = Prop::new(
+        ctx,
+        AWS_CREDENTIAL,
+        PropKind::String,
+        schema_variant_id,
+        Some(*secrets_prop.id()),
+        Some((WidgetKind::Secret, Some(secret_widget_options()))),
+        None,
+        None,
+    )
+    .await?;
+
+    // Finalizing again creates the values of the new prop. It also resets the default component
+    // type, so hand the current one back.
+    let component_type =
+        BuiltinSchemaHelpers::default_component_type(ctx, schema_variant_id).await?;
+    schema_variant.finalize(ctx, Some(component_type)).await?;
+
+    let (identity_func, identity_func_binding, identity_fbrv) =
+        Func::identity_with_binding_and_return_value(ctx).await?;
+    let (explicit_internal_provider, _input_socket) = InternalProvider::new_explicit_with_socket(
+        ctx,
+        schema_variant_id,
+        AWS_CREDENTIAL,
+        *identity_func.id(),
+        *identity_func_binding.id(),
+        *identity_fbrv.id(),
+        connection_annotations()?,
+        SocketArity::One,
+        false,
+    )
+    .await?;
+
+    let (_, identity_func_argument) = Func::identity_with_argument(ctx).await?;
+    let mut prototype = AttributeValue::find_for_context(
+        ctx,
+        AttributeReadContext::default_with_prop(*secret_prop.id()),
+    )
+    .await?
+    .ok_or(AttributeValueError::Missing)?
+    .attribute_prototype(ctx)
+    .await?
+    .ok_or(AttributeValueError::MissingAttributePrototype)?;
+    prototype.set_func_id(ctx, *identity_func.id()).await?;
+    AttributePrototypeArgument::new_for_intra_component(
+        ctx,
+        *prototype.id(),
+        *identity_func_argument.id(),
+        *explicit_internal_provider.id(),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use si_pkg::{
+    FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, SiPkg,
+};
+
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
+use crate::{
+    BuiltinsError, BuiltinsResult, DalContext, Func, Schema, SchemaVariant, StandardModel,
+};
+
+/// Additional code generations for [`Schemas`](Schema) that already generate their AWS JSON, as
+/// `(schema name, func name, artifact name, code)`. Each one becomes its own entry of
+/// "/root/code", named after the display name of its func.
+const CODE_GENERATIONS: &[(&str, &str, &str, &str)] = &[(
+    "VPC",
+    "si:generateTerraformVpc",
+    "Terraform",
+    "async function generateTerraformVpc(component: Input): Promise<Output> {
+    const domain = component.domain ?? {};
+    const attributes = [
+        [\"cidr_block\", domain.CidrBlock],
+        [\"instance_tenancy\", domain.InstanceTenancy],
+        [\"enable_dns_hostnames\", domain.EnableDnsHostnames],
+        [\"enable_dns_support\", domain.EnableDnsSupport],
+    ].filter(([_, value]) => value !== undefined && value !== null && value !== \"\");
+
+    const tags = (domain.Tags ?? []).filter((tag) => tag?.Key);
+    const nameTag = tags.find((tag) => tag.Key === \"Name\");
+
+    const lines = [`resource \"aws_vpc\" \"${terraformName(nameTag?.Value)}\" {`];
+    for (const [name, value] of attributes) {
+        lines.push(`  ${name} = ${JSON.stringify(value)}`);
+    }
+    if (tags.length > 0) {
+        lines.push(\"\", \"  tags = {\");
+        for (const tag of tags) {
+            lines.push(`    ${JSON.stringify(tag.Key)} = ${JSON.stringify(tag.Value ?? \"\")}`);
+        }
+        lines.push(\"  }\");
+    }
+    lines.push(\"}\");
+
+    return {
+        format: \"hcl\",
+        code: lines.join(\"\\n\") + \"\\n\",
+    };
+}
+
+function terraformName(name) {
+    const sanitized = `${name || \"this\"}`.toLowerCase().replace(/[^a-z0-9_]/g, \"_\");
+    return /^[a-z_]/.test(sanitized) ? sanitized : `_${sanitized}`;
+}",
+)];
+
+/// Migrate the code generations in [`CODE_GENERATIONS`], attaching each of them to every variant
+/// of its [`Schema`] next to the code generations the variant already has.
+pub async fn migrate_code_generations(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-code-generations")
+        .version("2023-12-22")
+        .created_by("System Initiative");
+
+    for (schema_name, fn_name, artifact_name, code) in CODE_GENERATIONS {
+        let code_generation_func = FuncSpec::builder()
+            .name(*fn_name)
+            .unique_id(*fn_name)
+            .data(
+                FuncSpecData::builder()
+                    .name(*fn_name)
+                    .display_name(*artifact_name)
+                    .description(format!(
+                        "Generates the {artifact_name} code of a {schema_name}"
+                    ))
+                    .code_plaintext(*code)
+                    .handler(fn_name.trim_start_matches("si:"))
+                    .backend_kind(FuncSpecBackendKind::JsAttribute)
+                    .response_type(FuncSpecBackendResponseType::CodeGeneration)
+                    .build()?,
+            )
+            .build()?;
+        builder.func(code_generation_func);
+    }
+
+    let spec = builder.build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    for (schema_name, fn_name, _, _) in CODE_GENERATIONS {
+        let func = Func::find_by_name(ctx, fn_name)
+            .await?
+            .ok_or_else(|| BuiltinsError::FuncMetadata(format!("{fn_name} was not installed")))?;
+
+        for schema in Schema::find_by_attr(ctx, "name", schema_name).await? {
+            for variant in schema.variants(ctx).await? {
+                SchemaVariant::upsert_leaf_function(
+                    ctx,
+                    *variant.id(),
+                    None,
+                    LeafKind::CodeGeneration,
+                    &[LeafInputLocation::Domain],
+                    &func,
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
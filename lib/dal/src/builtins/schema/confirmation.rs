@@ -0,0 +1,150 @@
+use si_pkg::{
+    FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, SiPkg,
+};
+
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::{
+    BuiltinsError, BuiltinsResult, ConfirmationPrototype, ConfirmationPrototypeContext, DalContext,
+    Func, Schema, StandardModel,
+};
+
+/// The [`Schemas`](Schema) with confirmations, along with the confirmation func name and the
+/// "findDrift" implementation comparing the domain with the resource payload for each of them.
+const CONFIRMATIONS: &[(&str, &str, &str)] = &[
+    (
+        "Security Group",
+        "si:awsSecurityGroupConfirmation",
+        "function findDrift(domain, payload) {
+    return [\"GroupName\", \"Description\", \"VpcId\"]
+        .filter((key) => payload[key] !== undefined && domain[key] !== undefined)
+        .filter((key) => payload[key] !== domain[key])
+        .map((key) => `${key} is \"${payload[key]}\" but should be \"${domain[key]}\"`);
+}",
+    ),
+    (
+        "Ingress",
+        "si:awsIngressConfirmation",
+        "function findDrift(domain, payload) {
+    const rules = payload.SecurityGroupRules;
+    if (!Array.isArray(rules)) {
+        return [];
+    }
+    const permissions = domain.IpPermissions ?? [];
+    const matches = (permission, rule) =>
+        `${rule.IpProtocol}` === `${permission.IpProtocol}`
+        && Number(rule.FromPort) === Number(permission.FromPort)
+        && Number(rule.ToPort) === Number(permission.ToPort)
+        && (permission.CidrIp === undefined || rule.CidrIpv4 === permission.CidrIp);
+
+    const drift = [];
+    permissions.forEach((permission, index) => {
+        if (!rules.some((rule) => matches(permission, rule))) {
+            drift.push(`rule ${index + 1} is missing from the resource`);
+        }
+    });
+    rules.forEach((rule) => {
+        if (!permissions.some((permission) => matches(permission, rule))) {
+            drift.push(`rule ${rule.SecurityGroupRuleId} is not in the desired rules`);
+        }
+    });
+    return drift;
+}",
+    ),
+];
+
+/// The body shared by every confirmation, run after "findDrift" is declared.
+const CONFIRMATION_CODE: &str = "async function confirm(component: Input): Promise<Output> {
+    const payload = component.resource?.payload;
+    const deleted = !!component.deleted_at;
+
+    if (!payload) {
+        if (deleted) {
+            return { success: true, recommendedActions: [] };
+        }
+        return {
+            success: false,
+            recommendedActions: [\"create\"],
+            message: \"Resource does not exist\",
+        };
+    }
+    if (deleted) {
+        return {
+            success: false,
+            recommendedActions: [\"delete\"],
+            message: \"Component was deleted but its resource still exists\",
+        };
+    }
+
+    const drift = findDrift(component.domain ?? {}, payload);
+    if (drift.length > 0) {
+        return {
+            success: false,
+            recommendedActions: [\"update\"],
+            message: drift.join(\"\\n\"),
+        };
+    }
+    return { success: true, recommendedActions: [] };
+}";
+
+/// Migrate confirmations comparing the desired attribute values of the [`Schemas`](Schema) in
+/// [`CONFIRMATIONS`] with their last known resource state.
+pub async fn migrate_confirmations(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-confirmations")
+        .version("2023-12-19")
+        .created_by("System Initiative");
+
+    for (schema_name, fn_name, find_drift) in CONFIRMATIONS {
+        let confirmation_func = FuncSpec::builder()
+            .name(*fn_name)
+            .unique_id(*fn_name)
+            .data(
+                FuncSpecData::builder()
+                    .name(*fn_name)
+                    .display_name(format!("Confirm {schema_name} resource"))
+                    .description(format!(
+                        "Recommends actions when a {schema_name} resource has drifted from the \
+                        desired attribute values"
+                    ))
+                    .code_plaintext(format!("{find_drift}\n\n{CONFIRMATION_CODE}"))
+                    .handler("confirm")
+                    .backend_kind(FuncSpecBackendKind::JsAttribute)
+                    .response_type(FuncSpecBackendResponseType::Json)
+                    .build()?,
+            )
+            .build()?;
+        builder.func(confirmation_func);
+    }
+
+    let spec = builder.build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    for (schema_name, fn_name, _) in CONFIRMATIONS {
+        let func = Func::find_by_name(ctx, fn_name)
+            .await?
+            .ok_or_else(|| BuiltinsError::FuncMetadata(format!("{fn_name} was not installed")))?;
+
+        for schema in Schema::find_by_attr(ctx, "name", schema_name).await? {
+            for variant in schema.variants(ctx).await? {
+                ConfirmationPrototype::upsert(
+                    ctx,
+                    *func.id(),
+                    fn_name,
+                    ConfirmationPrototypeContext::new_for_schema_variant(*variant.id()),
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
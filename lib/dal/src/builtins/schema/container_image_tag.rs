@@ -0,0 +1,163 @@
+use si_pkg::{
+    FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, SiPkg,
+};
+
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
+use crate::{
+    BuiltinsError, BuiltinsResult, DalContext, Func, Schema, SchemaVariant, StandardModel,
+};
+
+/// The names of the [`Schemas`](Schema) whose components reference container images, either
+/// directly at "/root/domain/image" or through "/root/domain/containerDefinitions/*/image".
+const CONTAINER_IMAGE_SCHEMA_NAMES: &[&str] = &["Docker Image", "Task Definition"];
+const CONTAINER_IMAGE_TAG_QUALIFICATION: &str = "si:qualificationContainerImageTagResolves";
+
+const CONTAINER_IMAGE_TAG_QUALIFICATION_CODE: &str =
+    "async function qualificationContainerImageTagResolves(component: Input): Promise<Output> {
+    const domain = component.domain ?? {};
+    const images = [];
+    if (domain.image) {
+        images.push(domain.image);
+    }
+    for (const container of domain.containerDefinitions ?? []) {
+        if (container?.image) {
+            images.push(container.image);
+        }
+    }
+    if (images.length === 0) {
+        return { result: \"warning\", message: \"no image specified\" };
+    }
+
+    // Lookups are cached on disk for a short while so that recomputing the qualification for
+    // every component sharing an image in a change set does not hammer the registries.
+    const cacheTtlMs = 5 * 60 * 1000;
+    const cacheDir = path.join(os.tmpdir(), \"si-image-digests\");
+    fs.mkdirSync(cacheDir, { recursive: true });
+
+    const username = requestStorage.getItem(\"dockerRegistryUsername\");
+    const password = requestStorage.getItem(\"dockerRegistryPassword\");
+    const registry = requestStorage.getItem(\"dockerRegistry\");
+
+    const resolve = async (reference) => {
+        const cacheKey = Buffer.from(`${username ?? \"\"}@${reference}`).toString(\"hex\");
+        const cachePath = path.join(cacheDir, cacheKey);
+        if (fs.existsSync(cachePath)) {
+            const cached = JSON.parse(fs.readFileSync(cachePath, \"utf8\"));
+            if (Date.now() - cached.resolvedAt < cacheTtlMs) {
+                return cached;
+            }
+        }
+
+        const args = [\"inspect\", \"--no-tags\", \"--format\", \"{{.Digest}}\"];
+        if (username && password) {
+            args.push(\"--creds\", `${username}:${password}`);
+        }
+        args.push(`docker://${reference}`);
+
+        const child = await siExec.waitUntilEnd(\"skopeo\", args);
+        const entry = child.exitCode === 0
+            ? { digest: child.stdout.trim(), resolvedAt: Date.now() }
+            : { error: child.stderr.trim(), resolvedAt: Date.now() };
+        fs.writeFileSync(cachePath, JSON.stringify(entry));
+        return entry;
+    };
+
+    const failures = [];
+    const warnings = [];
+    const digests = [];
+    for (const image of images) {
+        const reference = registry && !image.startsWith(registry) ? `${registry}/${image}` : image;
+        const lastSegment = image.split(\"/\").pop();
+        const pinned = lastSegment.includes(\"@\");
+        const tag = pinned ? undefined : (lastSegment.split(\":\")[1] ?? \"latest\");
+        if (tag === \"latest\") {
+            warnings.push(`${image} uses the mutable \"latest\" tag; pin a version or digest`);
+        }
+
+        const resolved = await resolve(reference);
+        if (resolved.error) {
+            failures.push(`${image} could not be resolved: ${resolved.error}`);
+        } else {
+            digests.push(`${image} resolves to ${resolved.digest}`);
+        }
+    }
+
+    if (failures.length > 0) {
+        return { result: \"failure\", message: [...failures, ...warnings].join(\"\\n\") };
+    }
+    if (warnings.length > 0) {
+        return { result: \"warning\", message: [...warnings, ...digests].join(\"\\n\") };
+    }
+    return { result: \"success\", message: digests.join(\"\\n\") };
+}";
+
+/// Migrate a qualification that resolves the digest of every container image referenced by the
+/// container-oriented [`Schemas`](Schema) (see [`CONTAINER_IMAGE_SCHEMA_NAMES`]).
+///
+/// The qualification fails when a tag cannot be resolved in its registry and warns when an image
+/// relies on the mutable "latest" tag. It reuses the registry credentials placed in request
+/// storage by the "Docker Registry Credential" authentication func, if one is attached.
+pub async fn migrate_container_image_tag_qualification(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-container-image-tag")
+        .version("2023-12-15")
+        .created_by("System Initiative");
+
+    let fn_name = CONTAINER_IMAGE_TAG_QUALIFICATION;
+    let qualification_func = FuncSpec::builder()
+        .name(fn_name)
+        .unique_id(fn_name)
+        .data(
+            FuncSpecData::builder()
+                .name(fn_name)
+                .display_name("Container image tag resolves")
+                .description(
+                    "Resolves the digest of each image tag and warns when the latest tag is used",
+                )
+                .code_plaintext(CONTAINER_IMAGE_TAG_QUALIFICATION_CODE)
+                .handler("qualificationContainerImageTagResolves")
+                .backend_kind(FuncSpecBackendKind::JsAttribute)
+                .response_type(FuncSpecBackendResponseType::Qualification)
+                .build()?,
+        )
+        .build()?;
+
+    let spec = builder.func(qualification_func).build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    let func = Func::find_by_name(ctx, CONTAINER_IMAGE_TAG_QUALIFICATION)
+        .await?
+        .ok_or_else(|| {
+            BuiltinsError::FuncMetadata(format!(
+                "{CONTAINER_IMAGE_TAG_QUALIFICATION} was not installed"
+            ))
+        })?;
+
+    for schema_name in CONTAINER_IMAGE_SCHEMA_NAMES {
+        for schema in Schema::find_by_attr(ctx, "name", schema_name).await? {
+            for variant in schema.variants(ctx).await? {
+                SchemaVariant::upsert_leaf_function(
+                    ctx,
+                    *variant.id(),
+                    None,
+                    LeafKind::Qualification,
+                    &[LeafInputLocation::Domain, LeafInputLocation::Secrets],
+                    &func,
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use si_pkg::{
+    FuncArgumentKind, FuncArgumentSpec, FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType,
+    FuncSpecData, PkgSpec, SiPkg,
+};
+
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::{BuiltinsResult, DalContext};
+
+/// The AWS [`Schemas`](crate::Schema) that existing resources can be discovered for, along with
+/// the discovery func name, the declarations it needs and its implementation for each of them.
+///
+/// Discovery funcs receive the region to look into, and return one entry per resource found,
+/// shaped like a [`DiscoveredResource`](crate::discovery::DiscoveredResource).
+pub const AWS_DISCOVERIES: &[(&str, &str, &str, &str)] = &[
+    (
+        "Security Group",
+        "si:awsSecurityGroupDiscovery",
+        "",
+        SECURITY_GROUP_DISCOVERY_CODE,
+    ),
+    (
+        "Ingress",
+        "si:awsIngressDiscovery",
+        "const direction = \"ingress\";",
+        SECURITY_GROUP_RULE_DISCOVERY_CODE,
+    ),
+    (
+        "Egress",
+        "si:awsEgressDiscovery",
+        "const direction = \"egress\";",
+        SECURITY_GROUP_RULE_DISCOVERY_CODE,
+    ),
+];
+
+const SECURITY_GROUP_DISCOVERY_CODE: &str =
+    "async function discover(input: Input): Promise<Output> {
+    const child = await siExec.waitUntilEnd(\"aws\", [
+        \"ec2\",
+        \"describe-security-groups\",
+        \"--region\",
+        input.region,
+    ]);
+    if (child.exitCode !== 0) {
+        throw new Error(`Unable to describe security groups: ${child.stderr}`);
+    }
+
+    const groups = JSON.parse(child.stdout).SecurityGroups ?? [];
+    return groups.map((group) => ({
+        resourceId: group.GroupId,
+        name: group.GroupName,
+        domain: {
+            GroupName: group.GroupName,
+            Description: group.Description,
+            VpcId: group.VpcId,
+        },
+        payload: group,
+    }));
+}";
+
+/// The body shared by the security group rule discoveries, declaring the `direction` they
+/// discover. Rules are grouped by security group, the same way the "Ingress" and "Egress"
+/// [`Schemas`](crate::Schema) model them.
+const SECURITY_GROUP_RULE_DISCOVERY_CODE: &str =
+    "async function discover(input: Input): Promise<Output> {
+    const child = await siExec.waitUntilEnd(\"aws\", [
+        \"ec2\",
+        \"describe-security-group-rules\",
+        \"--region\",
+        input.region,
+    ]);
+    if (child.exitCode !== 0) {
+        throw new Error(`Unable to describe security group rules: ${child.stderr}`);
+    }
+
+    const rulesByGroup = {};
+    for (const rule of JSON.parse(child.stdout).SecurityGroupRules ?? []) {
+        if (!!rule.IsEgress !== (direction === \"egress\")) {
+            continue;
+        }
+        (rulesByGroup[rule.GroupId] ??= []).push(rule);
+    }
+
+    return Object.entries(rulesByGroup).map(([groupId, rules]) => ({
+        resourceId: `${groupId}/${direction}`,
+        name: `${groupId} ${direction}`,
+        securityGroupId: groupId,
+        domain: {
+            GroupId: groupId,
+            IpPermissions: rules.map((rule) => ({
+                IpProtocol: rule.IpProtocol,
+                FromPort: rule.FromPort,
+                ToPort: rule.ToPort,
+                CidrIp: rule.CidrIpv4,
+            })),
+        },
+        payload: { GroupId: groupId, SecurityGroupRules: rules },
+    }));
+}";
+
+/// Migrate the funcs discovering the existing resources of the [`Schemas`](crate::Schema) in
+/// [`AWS_DISCOVERIES`].
+pub async fn migrate_aws_discoveries(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-aws-discovery")
+        .version("2023-12-21")
+        .created_by("System Initiative");
+
+    for (schema_name, fn_name, declarations, code) in AWS_DISCOVERIES {
+        let discovery_func = FuncSpec::builder()
+            .name(*fn_name)
+            .unique_id(*fn_name)
+            .data(
+                FuncSpecData::builder()
+                    .name(*fn_name)
+                    .display_name(format!("Discover {schema_name} resources"))
+                    .description(format!(
+                        "Lists the existing {schema_name} resources of a region, so that they can \
+                        be imported as components"
+                    ))
+                    .code_plaintext(format!("{declarations}\n\n{code}"))
+                    .handler("discover")
+                    .backend_kind(FuncSpecBackendKind::JsAttribute)
+                    .response_type(FuncSpecBackendResponseType::Json)
+                    .build()?,
+            )
+            .argument(
+                FuncArgumentSpec::builder()
+                    .name("region")
+                    .kind(FuncArgumentKind::String)
+                    .build()?,
+            )
+            .build()?;
+        builder.func(discovery_func);
+    }
+
+    let spec = builder.build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    Ok(())
+}
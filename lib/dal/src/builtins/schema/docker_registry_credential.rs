@@ -0,0 +1,257 @@
+use si_pkg::{
+    AttrFuncInputSpec, AttrFuncInputSpecKind, AuthenticationFuncSpec, FuncSpec,
+    FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, PropSpec,
+    PropSpecWidgetKind, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecData,
+    SiPkg, SocketSpec, SocketSpecArity, SocketSpecData, SocketSpecKind,
+};
+
+use crate::func::intrinsics::IntrinsicFunc;
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
+use crate::{prop::PropPath, PropKind};
+use crate::{
+    BuiltinsError, BuiltinsResult, DalContext, Func, Schema, SchemaVariant, StandardModel,
+};
+
+/// The name of the secret kind (and [`Schema`]) used to authenticate against Docker registries.
+pub const DOCKER_REGISTRY_CREDENTIAL: &str = "Docker Registry Credential";
+
+const DOCKER_IMAGE_SCHEMA_NAME: &str = "Docker Image";
+const DOCKER_IMAGE_EXISTS_QUALIFICATION: &str = "si:qualificationDockerImageExists";
+
+const DOCKER_REGISTRY_CREDENTIAL_AUTH_CODE: &str =
+    "async function auth(secret: Input): Promise<Output> {
+    requestStorage.setItem(\"dockerRegistry\", secret.Registry ?? \"docker.io\");
+    requestStorage.setItem(\"dockerRegistryUsername\", secret.Username);
+    requestStorage.setItem(\"dockerRegistryPassword\", secret.Password);
+}";
+
+const DOCKER_IMAGE_EXISTS_QUALIFICATION_CODE: &str =
+    "async function qualificationDockerImageExists(component: Input): Promise<Output> {
+    const image = component.domain?.image;
+    if (!image) {
+        return { result: \"failure\", message: \"no image specified\" };
+    }
+
+    const args = [\"inspect\", \"--no-tags\"];
+    const username = requestStorage.getItem(\"dockerRegistryUsername\");
+    const password = requestStorage.getItem(\"dockerRegistryPassword\");
+    if (username && password) {
+        args.push(\"--creds\", `${username}:${password}`);
+    }
+
+    const registry = requestStorage.getItem(\"dockerRegistry\");
+    const reference = registry && !image.startsWith(registry) ? `${registry}/${image}` : image;
+    args.push(`docker://${reference}`);
+
+    const child = await siExec.waitUntilEnd(\"skopeo\", args);
+    if (child.exitCode !== 0) {
+        return {
+            result: \"failure\",
+            message: `image ${image} could not be resolved: ${child.stderr}`,
+        };
+    }
+
+    return { result: \"success\", message: `image ${image} exists` };
+}";
+
+/// Migrate the "Docker Registry Credential" secret-defining [`Schema`] and wire a qualification
+/// into the "Docker Image" [`Schema`] that checks the image can be resolved (optionally using the
+/// credential when the component has one attached).
+///
+/// The qualification runs in veritech and relies on the authentication func of the credential to
+/// place the registry credentials in request storage before it executes.
+pub async fn migrate_docker_registry_credential(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-docker-registry-credential")
+        .version("2023-12-14")
+        .created_by("System Initiative");
+
+    let identity_func_spec = IntrinsicFunc::Identity.to_spec()?;
+
+    let fn_name = "si:dockerRegistryCredentialAuth";
+    let auth_func = FuncSpec::builder()
+        .name(fn_name)
+        .unique_id(fn_name)
+        .data(
+            FuncSpecData::builder()
+                .name(fn_name)
+                .display_name("Docker Registry Credential authentication")
+                .code_plaintext(DOCKER_REGISTRY_CREDENTIAL_AUTH_CODE)
+                .handler("auth")
+                .backend_kind(FuncSpecBackendKind::JsAuthentication)
+                .response_type(FuncSpecBackendResponseType::Void)
+                .build()?,
+        )
+        .build()?;
+
+    let fn_name = DOCKER_IMAGE_EXISTS_QUALIFICATION;
+    let qualification_func = FuncSpec::builder()
+        .name(fn_name)
+        .unique_id(fn_name)
+        .data(
+            FuncSpecData::builder()
+                .name(fn_name)
+                .display_name("Docker Image exists in registry")
+                .description("Ensures the image (and tag) can be resolved in its registry")
+                .code_plaintext(DOCKER_IMAGE_EXISTS_QUALIFICATION_CODE)
+                .handler("qualificationDockerImageExists")
+                .backend_kind(FuncSpecBackendKind::JsAttribute)
+                .response_type(FuncSpecBackendResponseType::Qualification)
+                .build()?,
+        )
+        .build()?;
+
+    let scaffold_func_code = "function createAsset() {\
+                return new AssetBuilder().build();
+            }";
+    let fn_name = "si:scaffoldDockerRegistryCredentialAsset";
+    let scaffold_func = FuncSpec::builder()
+        .name(fn_name)
+        .unique_id(fn_name)
+        .data(
+            FuncSpecData::builder()
+                .name(fn_name)
+                .code_plaintext(scaffold_func_code)
+                .handler("createAsset")
+                .backend_kind(FuncSpecBackendKind::JsSchemaVariantDefinition)
+                .response_type(FuncSpecBackendResponseType::SchemaVariantDefinition)
+                .build()?,
+        )
+        .build()?;
+
+    let credential_schema = SchemaSpec::builder()
+        .name(DOCKER_REGISTRY_CREDENTIAL)
+        .data(
+            SchemaSpecData::builder()
+                .name(DOCKER_REGISTRY_CREDENTIAL)
+                .category("Docker")
+                .category_name(DOCKER_REGISTRY_CREDENTIAL)
+                .build()?,
+        )
+        .variant(
+            SchemaVariantSpec::builder()
+                .name("v0")
+                .unique_id("docker_registry_credential_sv")
+                .data(
+                    SchemaVariantSpecData::builder()
+                        .name("v0")
+                        .color("#4695E7")
+                        .func_unique_id(&scaffold_func.unique_id)
+                        .build()?,
+                )
+                .secret_definition_prop(
+                    PropSpec::builder()
+                        .name("Registry")
+                        .kind(PropKind::String)
+                        .documentation("The registry host, e.g. ghcr.io (defaults to docker.io)")
+                        .build()?,
+                )
+                .secret_definition_prop(
+                    PropSpec::builder()
+                        .name("Username")
+                        .kind(PropKind::String)
+                        .build()?,
+                )
+                .secret_definition_prop(
+                    PropSpec::builder()
+                        .name("Password")
+                        .kind(PropKind::String)
+                        .widget_kind(PropSpecWidgetKind::Password)
+                        .build()?,
+                )
+                .secret_prop(
+                    PropSpec::builder()
+                        .name(DOCKER_REGISTRY_CREDENTIAL)
+                        .kind(PropKind::String)
+                        .widget_kind(PropSpecWidgetKind::Secret)
+                        .widget_options(serde_json::json!([{
+                            "label": "secretKind",
+                            "value": DOCKER_REGISTRY_CREDENTIAL,
+                        }]))
+                        .build()?,
+                )
+                .socket(
+                    SocketSpec::builder()
+                        .name(DOCKER_REGISTRY_CREDENTIAL)
+                        .data(
+                            SocketSpecData::builder()
+                                .name(DOCKER_REGISTRY_CREDENTIAL)
+                                .connection_annotations(serde_json::to_string(&vec![
+                                    DOCKER_REGISTRY_CREDENTIAL.to_lowercase(),
+                                ])?)
+                                .kind(SocketSpecKind::Output)
+                                .arity(SocketSpecArity::One)
+                                .func_unique_id(&identity_func_spec.unique_id)
+                                .build()?,
+                        )
+                        .input(
+                            AttrFuncInputSpec::builder()
+                                .name("identity")
+                                .kind(AttrFuncInputSpecKind::Prop)
+                                .prop_path(PropPath::new([
+                                    "root",
+                                    "secrets",
+                                    DOCKER_REGISTRY_CREDENTIAL,
+                                ]))
+                                .build()?,
+                        )
+                        .build()?,
+                )
+                .auth_func(
+                    AuthenticationFuncSpec::builder()
+                        .func_unique_id(&auth_func.unique_id)
+                        .build()?,
+                )
+                .build()?,
+        )
+        .build()?;
+
+    let spec = builder
+        .func(identity_func_spec)
+        .func(auth_func)
+        .func(qualification_func)
+        .func(scaffold_func)
+        .schema(credential_schema)
+        .build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    attach_docker_image_exists_qualification(ctx).await
+}
+
+/// Attach the "image exists" qualification to every "Docker Image" [`SchemaVariant`], if the
+/// "Docker Image" [`Schema`] has been migrated.
+async fn attach_docker_image_exists_qualification(ctx: &DalContext) -> BuiltinsResult<()> {
+    let func = Func::find_by_name(ctx, DOCKER_IMAGE_EXISTS_QUALIFICATION)
+        .await?
+        .ok_or_else(|| {
+            BuiltinsError::FuncMetadata(format!(
+                "{DOCKER_IMAGE_EXISTS_QUALIFICATION} was not installed"
+            ))
+        })?;
+
+    for schema in Schema::find_by_attr(ctx, "name", &DOCKER_IMAGE_SCHEMA_NAME).await? {
+        for variant in schema.variants(ctx).await? {
+            SchemaVariant::upsert_leaf_function(
+                ctx,
+                *variant.id(),
+                None,
+                LeafKind::Qualification,
+                &[LeafInputLocation::Domain, LeafInputLocation::Secrets],
+                &func,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use crate::prop::PropPath;
+use crate::schema::variant::root_prop::component_type::ComponentType;
+use crate::{
+    AttributeContextBuilder, AttributePrototype, AttributePrototypeArgument, AttributeReadContext,
+    AttributeValue, AttributeValueError, BuiltinsError, BuiltinsResult, DalContext, Func,
+    InternalProviderId, Prop, PropKind, SchemaVariantId, StandardModel,
+};
+
+/// Helpers for wiring props of builtin [`SchemaVariants`](crate::SchemaVariant) after their
+/// packages have been imported.
+pub struct BuiltinSchemaHelpers;
+
+impl BuiltinSchemaHelpers {
+    /// Set a single entry of the map prop found at `map_prop_path` from the identity of an
+    /// [`InternalProvider`](crate::InternalProvider). The entry for `key` is created if the map
+    /// does not have one yet and the remaining entries are left untouched.
+    pub async fn connect_map_entry_to_provider(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        map_prop_path: &PropPath,
+        key: impl Into<String>,
+        internal_provider_id: InternalProviderId,
+    ) -> BuiltinsResult<()> {
+        let key = key.into();
+        let map_prop = Self::find_map_prop(ctx, schema_variant_id, map_prop_path).await?;
+        let item_prop = map_prop
+            .child_props(ctx)
+            .await?
+            .pop()
+            .ok_or(BuiltinsError::MissingItemPropForMapProp(*map_prop.id()))?;
+
+        let map_value = AttributeValue::find_for_context(
+            ctx,
+            AttributeReadContext::default_with_prop(*map_prop.id()),
+        )
+        .await?
+        .ok_or(AttributeValueError::Missing)?;
+
+        let item_write_context = AttributeContextBuilder::new()
+            .set_prop_id(*item_prop.id())
+            .to_context()?;
+        let item_value = match AttributeValue::find_with_parent_and_key_for_context(
+            ctx,
+            Some(*map_value.id()),
+            Some(key.clone()),
+            item_write_context.into(),
+        )
+        .await?
+        {
+            Some(item_value) => item_value,
+            None => {
+                let item_value_id = AttributeValue::insert_for_context(
+                    ctx,
+                    item_write_context,
+                    *map_value.id(),
+                    None,
+                    Some(key),
+                )
+                .await?;
+                AttributeValue::get_by_id(ctx, &item_value_id)
+                    .await?
+                    .ok_or(AttributeValueError::MissingForId(item_value_id))?
+            }
+        };
+
+        let mut prototype = item_value
+            .attribute_prototype(ctx)
+            .await?
+            .ok_or(AttributeValueError::MissingAttributePrototype)?;
+        Self::set_identity_from_provider(ctx, &mut prototype, internal_provider_id).await
+    }
+
+    /// Set the whole map prop found at `map_prop_path` from the identity of an
+    /// [`InternalProvider`](crate::InternalProvider), so every entry of the upstream map flows
+    /// into it. Entries wired with
+    /// [`connect_map_entry_to_provider`](Self::connect_map_entry_to_provider) keep their own
+    /// prototypes.
+    pub async fn connect_map_to_provider(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        map_prop_path: &PropPath,
+        internal_provider_id: InternalProviderId,
+    ) -> BuiltinsResult<()> {
+        let map_prop = Self::find_map_prop(ctx, schema_variant_id, map_prop_path).await?;
+        let mut prototype = AttributeValue::find_for_context(
+            ctx,
+            AttributeReadContext::default_with_prop(*map_prop.id()),
+        )
+        .await?
+        .ok_or(AttributeValueError::Missing)?
+        .attribute_prototype(ctx)
+        .await?
+        .ok_or(AttributeValueError::MissingAttributePrototype)?;
+        Self::set_identity_from_provider(ctx, &mut prototype, internal_provider_id).await
+    }
+
+    /// Read the default "/root/si/type" of a [`SchemaVariant`](crate::SchemaVariant).
+    pub async fn default_component_type(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> BuiltinsResult<ComponentType> {
+        let type_prop = Prop::find_prop_by_path(
+            ctx,
+            schema_variant_id,
+            &PropPath::new(["root", "si", "type"]),
+        )
+        .await?;
+        let value = AttributeValue::find_for_context(
+            ctx,
+            AttributeReadContext::default_with_prop(*type_prop.id()),
+        )
+        .await?
+        .ok_or(AttributeValueError::Missing)?
+        .get_value(ctx)
+        .await?;
+        Ok(match value {
+            Some(value) => serde_json::from_value(value)?,
+            None => ComponentType::Component,
+        })
+    }
+
+    async fn find_map_prop(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        map_prop_path: &PropPath,
+    ) -> BuiltinsResult<Prop> {
+        let map_prop = Prop::find_prop_by_path(ctx, schema_variant_id, map_prop_path).await?;
+        if *map_prop.kind() != PropKind::Map {
+            return Err(BuiltinsError::PropNotMap(*map_prop.id(), *map_prop.kind()));
+        }
+        Ok(map_prop)
+    }
+
+    /// Point a prototype at the identity func, fed by the given provider. An existing identity
+    /// argument is repointed rather than duplicated, which keeps the migration idempotent.
+    async fn set_identity_from_provider(
+        ctx: &DalContext,
+        prototype: &mut AttributePrototype,
+        internal_provider_id: InternalProviderId,
+    ) -> BuiltinsResult<()> {
+        let (identity_func, identity_func_argument) = Func::identity_with_argument(ctx).await?;
+        prototype.set_func_id(ctx, *identity_func.id()).await?;
+
+        let mut found = false;
+        for mut argument in
+            AttributePrototypeArgument::list_for_attribute_prototype(ctx, *prototype.id()).await?
+        {
+            if argument.func_argument_id() != *identity_func_argument.id() {
+                argument.delete_by_id(ctx).await?;
+            } else if argument.internal_provider_id() != internal_provider_id {
+                argument
+                    .set_internal_provider_id(ctx, internal_provider_id)
+                    .await?;
+                found = true;
+            } else {
+                found = true;
+            }
+        }
+
+        if !found {
+            AttributePrototypeArgument::new_for_intra_component(
+                ctx,
+                *prototype.id(),
+                *identity_func_argument.id(),
+                internal_provider_id,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
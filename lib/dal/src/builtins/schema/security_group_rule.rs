@@ -0,0 +1,154 @@
+use si_pkg::{
+    FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, SiPkg,
+};
+
+use crate::installed_pkg::InstalledPkg;
+use crate::pkg::import_pkg_from_pkg;
+use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
+use crate::{
+    BuiltinsError, BuiltinsResult, DalContext, Func, Schema, SchemaVariant, StandardModel,
+};
+
+/// The security group rule [`Schemas`](Schema), with the qualification checking each of them.
+const SECURITY_GROUP_RULE_QUALIFICATIONS: &[(&str, &str, &str)] = &[
+    (
+        "Ingress",
+        "ingress",
+        "si:qualificationIngressNotOverlyPermissive",
+    ),
+    (
+        "Egress",
+        "egress",
+        "si:qualificationEgressNotOverlyPermissive",
+    ),
+];
+
+/// The shared body of the qualifications, run after a `direction` constant is declared.
+///
+/// Ingress rules are flagged when they open a sensitive port, or every port, to the whole
+/// internet. Egress rules are only flagged when they allow every port, since reaching the internet
+/// on specific ports is what most egress rules are for.
+const SECURITY_GROUP_RULE_QUALIFICATION_CODE: &str =
+    "const sensitivePorts = {
+    22: \"SSH\",
+    23: \"Telnet\",
+    445: \"SMB\",
+    1433: \"SQL Server\",
+    3306: \"MySQL\",
+    3389: \"RDP\",
+    5432: \"PostgreSQL\",
+    5900: \"VNC\",
+    6379: \"Redis\",
+    9200: \"Elasticsearch\",
+    11211: \"Memcached\",
+    27017: \"MongoDB\",
+};
+const openCidrs = [\"0.0.0.0/0\", \"::/0\"];
+
+async function qualificationSecurityGroupRuleNotOverlyPermissive(component: Input): Promise<Output> {
+    const rules = component.domain?.IpPermissions ?? [];
+    const warnings = [];
+
+    rules.forEach((rule, index) => {
+        const cidrs = [rule?.CidrIp, rule?.CidrIpv6].filter((cidr) => openCidrs.includes(cidr));
+        if (cidrs.length === 0) {
+            return;
+        }
+        const label = `rule ${index + 1} (${cidrs.join(\", \")})`;
+
+        const protocol = `${rule.IpProtocol ?? \"\"}`.toLowerCase();
+        const fromPort = parseInt(rule.FromPort, 10);
+        const toPort = parseInt(rule.ToPort, 10);
+        const allPorts = protocol === \"-1\" || protocol === \"all\"
+            || ((protocol === \"tcp\" || protocol === \"udp\")
+                && (isNaN(fromPort) || fromPort <= 0)
+                && (isNaN(toPort) || toPort >= 65535));
+        if (allPorts) {
+            warnings.push(`${label} allows all ${direction} traffic`);
+            return;
+        }
+
+        if (direction !== \"ingress\" || isNaN(fromPort)) {
+            return;
+        }
+        const lastPort = isNaN(toPort) ? fromPort : toPort;
+        for (const [port, service] of Object.entries(sensitivePorts)) {
+            if (Number(port) >= fromPort && Number(port) <= lastPort) {
+                warnings.push(`${label} exposes port ${port} (${service}) to the internet`);
+            }
+        }
+    });
+
+    if (warnings.length > 0) {
+        return { result: \"warning\", message: warnings.join(\"\\n\") };
+    }
+    return { result: \"success\", message: \"no overly permissive rules\" };
+}";
+
+/// Migrate qualifications that flag overly permissive rules on the security group rule
+/// [`Schemas`](Schema) (see [`SECURITY_GROUP_RULE_QUALIFICATIONS`]), e.g. SSH open to
+/// "0.0.0.0/0". Findings are surfaced as warnings rather than failures, since an open rule can be
+/// intended.
+pub async fn migrate_security_group_rule_qualifications(ctx: &DalContext) -> BuiltinsResult<()> {
+    let mut builder = PkgSpec::builder();
+    builder
+        .name("si-security-group-rule-qualifications")
+        .version("2023-12-18")
+        .created_by("System Initiative");
+
+    for (schema_name, direction, fn_name) in SECURITY_GROUP_RULE_QUALIFICATIONS {
+        let code =
+            format!("const direction = \"{direction}\";\n{SECURITY_GROUP_RULE_QUALIFICATION_CODE}");
+        let qualification_func = FuncSpec::builder()
+            .name(*fn_name)
+            .unique_id(*fn_name)
+            .data(
+                FuncSpecData::builder()
+                    .name(*fn_name)
+                    .display_name(format!("{schema_name} rule is not overly permissive"))
+                    .description(format!(
+                        "Warns when an {direction} rule opens sensitive ports or all traffic to \
+                        the internet"
+                    ))
+                    .code_plaintext(code)
+                    .handler("qualificationSecurityGroupRuleNotOverlyPermissive")
+                    .backend_kind(FuncSpecBackendKind::JsAttribute)
+                    .response_type(FuncSpecBackendResponseType::Qualification)
+                    .build()?,
+            )
+            .build()?;
+        builder.func(qualification_func);
+    }
+
+    let spec = builder.build()?;
+
+    let pkg = SiPkg::load_from_spec(spec)?;
+    if InstalledPkg::find_by_hash(ctx, &pkg.hash()?.to_string())
+        .await?
+        .is_none()
+    {
+        import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+    }
+
+    for (schema_name, _, fn_name) in SECURITY_GROUP_RULE_QUALIFICATIONS {
+        let func = Func::find_by_name(ctx, fn_name)
+            .await?
+            .ok_or_else(|| BuiltinsError::FuncMetadata(format!("{fn_name} was not installed")))?;
+
+        for schema in Schema::find_by_attr(ctx, "name", schema_name).await? {
+            for variant in schema.variants(ctx).await? {
+                SchemaVariant::upsert_leaf_function(
+                    ctx,
+                    *variant.id(),
+                    None,
+                    LeafKind::Qualification,
+                    &[LeafInputLocation::Domain],
+                    &func,
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use crate::builtins::schema::helpers::BuiltinSchemaHelpers;
+use crate::socket::SocketArity;
+use crate::{prop::PropPath, PropKind};
+use crate::{
+    AttributePrototypeArgument, BuiltinsError, BuiltinsResult, DalContext, ExternalProvider, Func,
+    InternalProvider, InternalProviderError, Prop, Schema, SchemaVariant, StandardModel,
+};
+
+/// The name of the sockets carrying tags from a frame to its children.
+const TAGS_SOCKET: &str = "Tags";
+
+/// The frame [`Schemas`](Schema) that hand their tags down to the
+/// [`Components`](crate::Component) inside them.
+const TAGS_PROVIDERS: &[&str] = &["Region"];
+
+/// Give the frame [`Schemas`](Schema) listed in [`TAGS_PROVIDERS`] a "/root/domain/tags" map
+/// exposed through a "Tags" output socket, and give every other [`SchemaVariant`] with a
+/// "/root/domain/tags" map a "Tags" input socket feeding the whole map.
+///
+/// The "Name" entry of the map keeps following "/root/si/name", so a child ends up with the tags
+/// of its frame plus its own name.
+pub async fn migrate_tags(ctx: &DalContext) -> BuiltinsResult<()> {
+    for schema_name in TAGS_PROVIDERS {
+        for schema in Schema::find_by_attr(ctx, "name", schema_name).await? {
+            for mut variant in schema.variants(ctx).await? {
+                add_tags_output(ctx, &schema, &mut variant).await?;
+            }
+        }
+    }
+
+    for schema in Schema::list(ctx).await? {
+        if TAGS_PROVIDERS.contains(&schema.name()) {
+            continue;
+        }
+        for variant in schema.variants(ctx).await? {
+            add_tags_input(ctx, &variant).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn tags_prop_path() -> PropPath {
+    PropPath::new(["root", "domain", "tags"])
+}
+
+fn connection_annotations() -> BuiltinsResult<String> {
+    Ok(serde_json::to_string(&vec![TAGS_SOCKET.to_lowercase()])?)
+}
+
+/// Add a "/root/domain/tags" map to a frame [`SchemaVariant`] and expose it through a "Tags"
+/// output socket. Variants that already have the socket are left untouched.
+async fn add_tags_output(
+    ctx: &DalContext,
+    schema: &Schema,
+    schema_variant: &mut SchemaVariant,
+) -> BuiltinsResult<()> {
+    let schema_variant_id = *schema_variant.id();
+    if ExternalProvider::find_for_schema_variant_and_name(ctx, schema_variant_id, TAGS_SOCKET)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    let tags_prop = match Prop::find_prop_by_path_opt(ctx, schema_variant_id, &tags_prop_path())
+        .await?
+    {
+        Some(tags_prop) => tags_prop,
+        None => {
+            let domain_prop =
+                Prop::find_prop_by_path(ctx, schema_variant_id, &PropPath::new(["root", "domain"]))
+                    .await?;
+            let tags_prop = Prop::new(
+                ctx,
+                "tags",
+                PropKind::Map,
+                schema_variant_id,
+                Some(*domain_prop.id()),
+                None,
+                None,
+                None,
+            )
+            .await?;
+            Prop::new(
+                ctx,
+                "tag",
+                PropKind::String,
+                schema_variant_id,
+                Some(*tags_prop.id()),
+                None,
+                None,
+                None,
+            )
+            .await?;
+
+            // Finalizing again creates the values and the implicit provider of the new prop. It
+            // also resets the default component type, so hand the current one back.
+            let component_type =
+                BuiltinSchemaHelpers::default_component_type(ctx, schema_variant_id).await?;
+            schema_variant.finalize(ctx, Some(component_type)).await?;
+            tags_prop
+        }
+    };
+
+    let tags_internal_provider = InternalProvider::find_for_prop(ctx, *tags_prop.id())
+        .await?
+        .ok_or(InternalProviderError::NotFoundForProp(*tags_prop.id()))?;
+
+    let (identity_func, identity_func_binding, identity_fbrv) =
+        Func::identity_with_binding_and_return_value(ctx).await?;
+    let (external_provider, _output_socket) = ExternalProvider::new_with_socket(
+        ctx,
+        *schema.id(),
+        schema_variant_id,
+        TAGS_SOCKET,
+        None,
+        *identity_func.id(),
+        *identity_func_binding.id(),
+        *identity_fbrv.id(),
+        connection_annotations()?,
+        SocketArity::Many,
+        false,
+    )
+    .await?;
+
+    let (_, identity_func_argument) = Func::identity_with_argument(ctx).await?;
+    let prototype_id = external_provider.attribute_prototype_id().ok_or(
+        BuiltinsError::MissingAttributePrototypeForExternalProvider(*external_provider.id()),
+    )?;
+    AttributePrototypeArgument::new_for_intra_component(
+        ctx,
+        *prototype_id,
+        *identity_func_argument.id(),
+        *tags_internal_provider.id(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Feed the "/root/domain/tags" map of a [`SchemaVariant`] from a new "Tags" input socket, keeping
+/// its "Name" entry on "/root/si/name". Variants without the map, or that already have the socket,
+/// are left untouched.
+async fn add_tags_input(ctx: &DalContext, schema_variant: &SchemaVariant) -> BuiltinsResult<()> {
+    let schema_variant_id = *schema_variant.id();
+    let tags_prop_path = tags_prop_path();
+    match Prop::find_prop_by_path_opt(ctx, schema_variant_id, &tags_prop_path).await? {
+        Some(tags_prop) if *tags_prop.kind() == PropKind::Map => {}
+        _ => return Ok(()),
+    }
+    if InternalProvider::find_explicit_for_schema_variant_and_name(
+        ctx,
+        schema_variant_id,
+        TAGS_SOCKET,
+    )
+    .await?
+    .is_some()
+    {
+        return Ok(());
+    }
+
+    let (identity_func, identity_func_binding, identity_fbrv) =
+        Func::identity_with_binding_and_return_value(ctx).await?;
+    let (explicit_internal_provider, _input_socket) = InternalProvider::new_explicit_with_socket(
+        ctx,
+        schema_variant_id,
+        TAGS_SOCKET,
+        *identity_func.id(),
+        *identity_func_binding.id(),
+        *identity_fbrv.id(),
+        connection_annotations()?,
+        SocketArity::One,
+        false,
+    )
+    .await?;
+
+    BuiltinSchemaHelpers::connect_map_to_provider(
+        ctx,
+        schema_variant_id,
+        &tags_prop_path,
+        *explicit_internal_provider.id(),
+    )
+    .await?;
+
+    let name_prop = Prop::find_prop_by_path(
+        ctx,
+        schema_variant_id,
+        &PropPath::new(["root", "si", "name"]),
+    )
+    .await?;
+    let name_internal_provider = InternalProvider::find_for_prop(ctx, *name_prop.id())
+        .await?
+        .ok_or(InternalProviderError::NotFoundForProp(*name_prop.id()))?;
+    BuiltinSchemaHelpers::connect_map_entry_to_provider(
+        ctx,
+        schema_variant_id,
+        &tags_prop_path,
+        "Name",
+        *name_internal_provider.id(),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -8,21 +8,26 @@ use strum::{Display, EnumString};
 use telemetry::prelude::*;
 use thiserror::Error;
 
+use crate::attribute::context::cache::AttributeContextCache;
+use crate::func::cache::FuncCache;
 use crate::standard_model::{object_option_from_row_option, objects_from_rows};
 use crate::{
     action::ActionBag, pk, Action, ActionError, ActionId, HistoryActor, HistoryEvent,
     HistoryEventError, LabelListError, StandardModelError, Tenancy, Timestamp, TransactionsError,
-    User, UserError, UserPk, Visibility, WsEvent, WsEventError, WsPayload,
+    User, UserError, UserPk, Visibility, Webhook, WebhookError, WebhookEventKind, WsEvent,
+    WsEventError, WsPayload,
 };
-use crate::{ComponentError, DalContext, WsEventResult};
+use crate::{AttributeValueId, ComponentError, ComponentId, DalContext, PropId, WsEventResult};
+
+pub mod comparison;
 
 const CHANGE_SET_OPEN_LIST: &str = include_str!("queries/change_set/open_list.sql");
 const CHANGE_SET_GET_BY_PK: &str = include_str!("queries/change_set/get_by_pk.sql");
 const GET_ACTORS: &str = include_str!("queries/change_set/get_actors.sql");
+const LIST_CONFLICTS: &str = include_str!("queries/change_set/list_conflicts.sql");
 
 const BEGIN_MERGE_FLOW: &str = include_str!("queries/change_set/begin_merge_flow.sql");
 const CANCEL_MERGE_FLOW: &str = include_str!("queries/change_set/cancel_merge_flow.sql");
-const ABANDON_CHANGE_SET: &str = include_str!("queries/change_set/abandon_change_set.sql");
 
 const BEGIN_ABANDON_FLOW: &str = include_str!("queries/change_set/begin_abandon_flow.sql");
 const CANCEL_ABANDON_FLOW: &str = include_str!("queries/change_set/cancel_abandon_flow.sql");
@@ -38,6 +43,8 @@ pub enum ChangeSetError {
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid user actor pk")]
     InvalidActor(UserPk),
+    #[error("invalid change kind: {0}")]
+    InvalidChangeKind(String),
     #[error("invalid user system init")]
     InvalidUserSystemInit,
     #[error(transparent)]
@@ -57,6 +64,8 @@ pub enum ChangeSetError {
     #[error(transparent)]
     User(#[from] UserError),
     #[error(transparent)]
+    Webhook(#[from] WebhookError),
+    #[error(transparent)]
     WsEvent(#[from] WsEventError),
 }
 
@@ -76,6 +85,20 @@ pub enum ChangeSetStatus {
 
 pk!(ChangeSetPk);
 
+/// A value set in a change set that was also changed on head since, to something else. Applying
+/// the change set overwrites the head value.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetConflict {
+    pub component_id: ComponentId,
+    pub attribute_value_id: AttributeValueId,
+    pub prop_id: PropId,
+    pub prop_path: String,
+    pub head_value: Option<serde_json::Value>,
+    pub change_set_value: Option<serde_json::Value>,
+    pub head_updated_at: DateTime<Utc>,
+}
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
 pub struct ChangeSet {
     pub pk: ChangeSetPk,
@@ -223,6 +246,9 @@ impl ChangeSet {
         let updated_at: DateTime<Utc> = row.try_get("timestamp_updated_at")?;
         self.timestamp.updated_at = updated_at;
         self.status = ChangeSetStatus::Applied;
+        // Funcs, props and providers from the change set are now on head.
+        FuncCache::invalidate(ctx).await?;
+        AttributeContextCache::invalidate(ctx).await?;
         let _history_event = HistoryEvent::new(
             ctx,
             "change_set.apply",
@@ -247,6 +273,15 @@ impl ChangeSet {
             .await?
             .publish_on_commit(ctx)
             .await?;
+        Webhook::enqueue(
+            ctx,
+            WebhookEventKind::ChangeSetApplied,
+            serde_json::json!({ "changeSetPk": self.pk, "name": self.name, "userPk": user_pk }),
+        )
+        .await?;
+
+        // Work still pending for the change set is moot now that it is on head.
+        ctx.cancel_change_set_jobs(self.pk).await?;
 
         // Update the visibility.
         ctx.update_visibility(Visibility::new_head(false));
@@ -254,16 +289,43 @@ impl ChangeSet {
         Ok(())
     }
 
+    /// Marks the change set as abandoned and drops every row that was written in it. Clients
+    /// are told to leave the change set on commit.
+    #[instrument(skip(ctx))]
     pub async fn abandon(&mut self, ctx: &mut DalContext) -> ChangeSetResult<()> {
         let row = ctx
-            .pg_pool()
-            .get()
+            .txns()
             .await?
-            .query_one(ABANDON_CHANGE_SET, &[&self.pk])
+            .pg()
+            .query_one(
+                "SELECT timestamp_updated_at FROM change_set_abandon_v1($1, $2)",
+                &[&self.pk, &self.tenancy],
+            )
             .await?;
-        let updated_at: DateTime<Utc> = row.try_get("updated_at")?;
+        let updated_at: DateTime<Utc> = row.try_get("timestamp_updated_at")?;
         self.timestamp.updated_at = updated_at;
         self.status = ChangeSetStatus::Abandoned;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.abandon",
+            "Change Set abandoned",
+            &serde_json::json![{ "pk": &self.pk }],
+        )
+        .await?;
+
+        let user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => Some(*user_pk),
+            HistoryActor::SystemInit => None,
+        };
+        WsEvent::change_set_abandoned(ctx, self.pk, user_pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        ctx.cancel_change_set_jobs(self.pk).await?;
+
+        // Nothing is left to look at in the change set.
+        ctx.update_visibility(Visibility::new_head(false));
 
         Ok(())
     }
@@ -301,6 +363,32 @@ impl ChangeSet {
         Ok(Action::order(&ctx).await?)
     }
 
+    /// Lists the values set in this change set that were changed differently on head after the
+    /// change set started diverging from it.
+    #[instrument(skip_all)]
+    pub async fn conflicts(&self, ctx: &DalContext) -> ChangeSetResult<Vec<ChangeSetConflict>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_CONFLICTS, &[&ctx.tenancy().workspace_pk(), &self.pk])
+            .await?;
+
+        let mut conflicts = Vec::with_capacity(rows.len());
+        for row in rows {
+            conflicts.push(ChangeSetConflict {
+                component_id: row.try_get("component_id")?,
+                attribute_value_id: row.try_get("attribute_value_id")?,
+                prop_id: row.try_get("prop_id")?,
+                prop_path: row.try_get("prop_path")?,
+                head_value: row.try_get("head_value")?,
+                change_set_value: row.try_get("change_set_value")?,
+                head_updated_at: row.try_get("head_updated_at")?,
+            });
+        }
+        Ok(conflicts)
+    }
+
     pub async fn actors(&self, ctx: &DalContext) -> ChangeSetResult<Vec<String>> {
         let rows = ctx
             .txns()
@@ -346,6 +434,13 @@ impl WsEvent {
         WsEvent::new(ctx, WsPayload::ChangeSetCreated(change_set_pk)).await
     }
 
+    pub async fn change_set_written(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetWritten(change_set_pk)).await
+    }
+
     pub async fn change_set_abandoned(
         ctx: &DalContext,
         change_set_pk: ChangeSetPk,
@@ -0,0 +1,158 @@
+//! This module contains [`ChangeSetComparison`], the differences between two open
+//! [`ChangeSets`](ChangeSet) (rather than between a [`ChangeSet`] and head), so that parallel
+//! workstreams can see where they collide before either of them is applied.
+//!
+//! Each [`ChangeSet`] is seen as head with its own writes on top. Changes made by both
+//! [`ChangeSets`](ChangeSet) are flagged as collisions: applying one of them will overwrite (or
+//! conflict with) the other.
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::change_set::{ChangeSet, ChangeSetError, ChangeSetResult};
+use crate::edge::EdgeId;
+use crate::{AttributeValueId, ComponentId, DalContext, PropId, SocketId};
+
+const COMPARE_COMPONENTS: &str = include_str!("../queries/change_set/compare_components.sql");
+const COMPARE_VALUES: &str = include_str!("../queries/change_set/compare_values.sql");
+const COMPARE_EDGES: &str = include_str!("../queries/change_set/compare_edges.sql");
+
+/// How a [`ChangeSet`] changed a row relative to head.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeSetChangeKind {
+    Added,
+    Deleted,
+    Modified,
+}
+
+/// A [`Component`](crate::Component) changed by at least one of the compared
+/// [`ChangeSets`](ChangeSet). A side without a change left the component as it is on head.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentComparison {
+    pub component_id: ComponentId,
+    pub left_change: Option<ChangeSetChangeKind>,
+    pub right_change: Option<ChangeSetChangeKind>,
+    pub collides: bool,
+}
+
+/// The value of a [`Prop`](crate::Prop) of a [`Component`](crate::Component) that differs
+/// between the compared [`ChangeSets`](ChangeSet).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueComparison {
+    pub component_id: ComponentId,
+    pub attribute_value_id: AttributeValueId,
+    pub prop_id: PropId,
+    pub prop_path: String,
+    pub left_value: Option<serde_json::Value>,
+    pub right_value: Option<serde_json::Value>,
+    /// Whether the left [`ChangeSet`] wrote the value, rather than seeing the one on head.
+    pub left_changed: bool,
+    pub right_changed: bool,
+    pub collides: bool,
+}
+
+/// An [`Edge`](crate::Edge) that exists in one of the compared [`ChangeSets`](ChangeSet) but not
+/// in the other.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeComparison {
+    pub edge_id: EdgeId,
+    pub head_component_id: ComponentId,
+    pub head_socket_id: SocketId,
+    pub tail_component_id: ComponentId,
+    pub tail_socket_id: SocketId,
+    pub left_change: Option<ChangeSetChangeKind>,
+    pub right_change: Option<ChangeSetChangeKind>,
+    pub collides: bool,
+}
+
+/// The differences between two [`ChangeSets`](ChangeSet), the "left" and the "right" one.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetComparison {
+    pub components: Vec<ComponentComparison>,
+    pub values: Vec<ValueComparison>,
+    pub edges: Vec<EdgeComparison>,
+}
+
+impl ChangeSetComparison {
+    /// Whether applying one of the [`ChangeSets`](ChangeSet) would overwrite changes made by the
+    /// other.
+    pub fn collides(&self) -> bool {
+        self.components.iter().any(|component| component.collides)
+            || self.values.iter().any(|value| value.collides)
+            || self.edges.iter().any(|edge| edge.collides)
+    }
+}
+
+fn parse_change(change: Option<String>) -> ChangeSetResult<Option<ChangeSetChangeKind>> {
+    change
+        .map(|change| {
+            change
+                .parse()
+                .map_err(|_| ChangeSetError::InvalidChangeKind(change))
+        })
+        .transpose()
+}
+
+impl ChangeSet {
+    /// Compare this [`ChangeSet`] (the "left" one) with another one (the "right" one).
+    pub async fn compare(
+        &self,
+        ctx: &DalContext,
+        other: &ChangeSet,
+    ) -> ChangeSetResult<ChangeSetComparison> {
+        let txns = ctx.txns().await?;
+        let params: [&(dyn postgres_types::ToSql + Sync); 3] =
+            [&ctx.tenancy().workspace_pk(), &self.pk, &other.pk];
+
+        let mut comparison = ChangeSetComparison::default();
+
+        for row in txns.pg().query(COMPARE_COMPONENTS, &params).await? {
+            let left_change = parse_change(row.try_get("left_change")?)?;
+            let right_change = parse_change(row.try_get("right_change")?)?;
+            comparison.components.push(ComponentComparison {
+                component_id: row.try_get("component_id")?,
+                left_change,
+                right_change,
+                collides: left_change.is_some() && right_change.is_some(),
+            });
+        }
+
+        for row in txns.pg().query(COMPARE_VALUES, &params).await? {
+            let left_changed = row.try_get("left_changed")?;
+            let right_changed = row.try_get("right_changed")?;
+            comparison.values.push(ValueComparison {
+                component_id: row.try_get("component_id")?,
+                attribute_value_id: row.try_get("attribute_value_id")?,
+                prop_id: row.try_get("prop_id")?,
+                prop_path: row.try_get("prop_path")?,
+                left_value: row.try_get("left_value")?,
+                right_value: row.try_get("right_value")?,
+                left_changed,
+                right_changed,
+                collides: left_changed && right_changed,
+            });
+        }
+
+        for row in txns.pg().query(COMPARE_EDGES, &params).await? {
+            let left_change = parse_change(row.try_get("left_change")?)?;
+            let right_change = parse_change(row.try_get("right_change")?)?;
+            comparison.edges.push(EdgeComparison {
+                edge_id: row.try_get("edge_id")?,
+                head_component_id: row.try_get("head_component_id")?,
+                head_socket_id: row.try_get("head_socket_id")?,
+                tail_component_id: row.try_get("tail_component_id")?,
+                tail_socket_id: row.try_get("tail_socket_id")?,
+                left_change,
+                right_change,
+                collides: left_change.is_some() && right_change.is_some(),
+            });
+        }
+
+        Ok(comparison)
+    }
+}
@@ -0,0 +1,508 @@
+//! This module contains [`CloudFormationExport`], which assembles the AWS payloads generated for
+//! the [`Components`](crate::Component) in a [`ChangeSet`](crate::ChangeSet) into a single
+//! CloudFormation template.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    Component, ComponentError, ComponentId, DalContext, StandardModel, StandardModelError,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum CloudFormationError {
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+}
+
+pub type CloudFormationResult<T> = Result<T, CloudFormationError>;
+
+const TEMPLATE_FORMAT_VERSION: &str = "2010-09-09";
+
+/// Fields of the generated payloads that only make sense for API calls.
+const API_ONLY_FIELDS: &[&str] = &["ClientToken", "DryRun", "MaxCount", "MinCount", "Region"];
+
+/// Fields of camelCase payloads that may hold user-defined maps, whose keys must be left as-is.
+const OPAQUE_CAMEL_CASE_FIELDS: &[&str] = &["dockerLabels", "options", "tags"];
+
+/// How a generated payload is reshaped into CloudFormation resource properties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PayloadShape {
+    /// The payload already uses PascalCase names.
+    PascalCase,
+    /// The payload uses camelCase names, which are converted to PascalCase.
+    CamelCase,
+    /// The payload is a "create security group" request, which describes the group with
+    /// "Description" rather than "GroupDescription".
+    SecurityGroup,
+    /// The payload is an "authorize security group rule" request, whose first permission is
+    /// flattened. The peer security group is written to the given property.
+    SecurityGroupRule(&'static str),
+}
+
+/// The CloudFormation resource type and payload shape for each AWS "code generation" function.
+/// Generators that describe lookups rather than resources (e.g. AMIs) are not listed.
+const RESOURCE_MAPPINGS: &[(&str, &str, PayloadShape)] = &[
+    (
+        "si:awsEc2EbsVolumeJSON",
+        "AWS::EC2::Volume",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:awsEcsServiceGenerateJson",
+        "AWS::ECS::Service",
+        PayloadShape::CamelCase,
+    ),
+    (
+        "si:generateAwsCloudwatchLogGroupJSON",
+        "AWS::Logs::LogGroup",
+        PayloadShape::CamelCase,
+    ),
+    (
+        "si:generateAwsEc2JSON",
+        "AWS::EC2::Instance",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:generateAwsEcsJSON",
+        "AWS::ECS::Cluster",
+        PayloadShape::CamelCase,
+    ),
+    (
+        "si:generateAwsEcsTaskDefinitionJSON",
+        "AWS::ECS::TaskDefinition",
+        PayloadShape::CamelCase,
+    ),
+    (
+        "si:generateAwsEgressJSON",
+        "AWS::EC2::SecurityGroupEgress",
+        PayloadShape::SecurityGroupRule("DestinationSecurityGroupId"),
+    ),
+    (
+        "si:generateAwsEipJSON",
+        "AWS::EC2::EIP",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:generateAwsIamGroupJSON",
+        "AWS::IAM::Group",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:generateAwsIamInstanceProfileJSON",
+        "AWS::IAM::InstanceProfile",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:generateAwsIamRoleJSON",
+        "AWS::IAM::Role",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:generateAwsIngressJSON",
+        "AWS::EC2::SecurityGroupIngress",
+        PayloadShape::SecurityGroupRule("SourceSecurityGroupId"),
+    ),
+    (
+        "si:generateAwsKeyPairJSON",
+        "AWS::EC2::KeyPair",
+        PayloadShape::PascalCase,
+    ),
+    (
+        "si:generateAwsSecurityGroupJSON",
+        "AWS::EC2::SecurityGroup",
+        PayloadShape::SecurityGroup,
+    ),
+    (
+        "si:generateAwsTargetGroupJSON",
+        "AWS::ElasticLoadBalancingV2::TargetGroup",
+        PayloadShape::PascalCase,
+    ),
+];
+
+/// A [`Component`] that did not make it into the template.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudFormationSkippedComponent {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub reason: String,
+}
+
+/// A CloudFormation template assembled from generated code, along with the
+/// [`Components`](Component) that could not be represented in it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudFormationExport {
+    pub template: serde_json::Value,
+    pub skipped: Vec<CloudFormationSkippedComponent>,
+}
+
+struct ExportedResource {
+    component_id: ComponentId,
+    logical_id: String,
+    resource_type: &'static str,
+    properties: serde_json::Value,
+}
+
+impl CloudFormationExport {
+    /// Assemble a template out of every [`Component`] visible in the current
+    /// [`Visibility`](crate::Visibility).
+    ///
+    /// Configuration [`Edges`](crate::Edge) between exported [`Components`](Component) become
+    /// `DependsOn` entries, and any value the tail emits through the connected socket is replaced
+    /// with a `Ref` to the tail's resource in the head's properties.
+    #[instrument(skip_all)]
+    pub async fn assemble(ctx: &DalContext) -> CloudFormationResult<Self> {
+        let mut components = Component::list(ctx).await?;
+        let mut names = HashMap::new();
+        for component in &components {
+            names.insert(*component.id(), component.name(ctx).await?);
+        }
+        components.sort_by(|a, b| names[a.id()].cmp(&names[b.id()]).then(a.id().cmp(b.id())));
+
+        let mut resources: Vec<ExportedResource> = Vec::new();
+        let mut skipped = Vec::new();
+        let mut logical_ids = HashSet::new();
+        for component in &components {
+            let component_id = *component.id();
+            let component_name = names[&component_id].clone();
+
+            let code = Component::generated_code_by_func_name(ctx, component_id).await?;
+            let mut func_names: Vec<&String> = code.keys().collect();
+            func_names.sort();
+
+            let mut exported = false;
+            for func_name in func_names {
+                let (resource_type, shape) = match resource_mapping(func_name) {
+                    Some(mapping) => mapping,
+                    None => continue,
+                };
+                let payload: serde_json::Value = match serde_json::from_str(&code[func_name]) {
+                    Ok(payload) => payload,
+                    Err(err) => {
+                        skipped.push(CloudFormationSkippedComponent {
+                            component_id,
+                            component_name: component_name.clone(),
+                            reason: format!("generated code for {func_name} is not JSON: {err}"),
+                        });
+                        continue;
+                    }
+                };
+
+                let logical_id = unique_logical_id(&component_name, &mut logical_ids);
+                resources.push(ExportedResource {
+                    component_id,
+                    logical_id,
+                    resource_type,
+                    properties: properties_from_payload(payload, shape),
+                });
+                exported = true;
+            }
+
+            if !exported && !skipped.iter().any(|s| s.component_id == component_id) {
+                skipped.push(CloudFormationSkippedComponent {
+                    component_id,
+                    component_name,
+                    reason: "no CloudFormation resource is generated for this component".into(),
+                });
+            }
+        }
+
+        // Only the first resource of each component is referenced by its connections.
+        let mut logical_id_by_component: HashMap<ComponentId, String> = HashMap::new();
+        for resource in &resources {
+            logical_id_by_component
+                .entry(resource.component_id)
+                .or_insert_with(|| resource.logical_id.clone());
+        }
+
+        let mut template_resources = BTreeMap::new();
+        for mut resource in resources {
+            let mut depends_on = Vec::new();
+            for connection in
+                Component::connections_for_code_generation(ctx, resource.component_id).await?
+            {
+                let tail_logical_id = match logical_id_by_component.get(&connection.component_id) {
+                    Some(tail_logical_id) => tail_logical_id,
+                    None => continue,
+                };
+                if let Some(value) = &connection.value {
+                    replace_with_ref(&mut resource.properties, value, tail_logical_id);
+                }
+                if !depends_on.contains(tail_logical_id) {
+                    depends_on.push(tail_logical_id.clone());
+                }
+            }
+            depends_on.sort();
+
+            let mut template_resource = serde_json::json!({
+                "Type": resource.resource_type,
+                "Properties": resource.properties,
+            });
+            if !depends_on.is_empty() {
+                template_resource["DependsOn"] = serde_json::json!(depends_on);
+            }
+            template_resources.insert(resource.logical_id, template_resource);
+        }
+
+        Ok(Self {
+            template: serde_json::json!({
+                "AWSTemplateFormatVersion": TEMPLATE_FORMAT_VERSION,
+                "Description": "Exported from System Initiative",
+                "Resources": template_resources,
+            }),
+            skipped,
+        })
+    }
+}
+
+fn resource_mapping(func_name: &str) -> Option<(&'static str, PayloadShape)> {
+    RESOURCE_MAPPINGS
+        .iter()
+        .find(|(name, _, _)| *name == func_name)
+        .map(|(_, resource_type, shape)| (*resource_type, *shape))
+}
+
+/// Build a logical id out of the component name: CloudFormation only allows alphanumeric
+/// characters, so words are joined in PascalCase. Collisions get a numeric suffix.
+fn unique_logical_id(component_name: &str, taken: &mut HashSet<String>) -> String {
+    let mut base: String = component_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .map(upper_first)
+        .collect();
+    if !base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        base = format!("Component{base}");
+    }
+
+    let mut logical_id = base.clone();
+    let mut suffix = 2;
+    while taken.contains(&logical_id) {
+        logical_id = format!("{base}{suffix}");
+        suffix += 1;
+    }
+    taken.insert(logical_id.clone());
+    logical_id
+}
+
+fn upper_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Reshape a generated API payload into CloudFormation resource properties.
+fn properties_from_payload(payload: serde_json::Value, shape: PayloadShape) -> serde_json::Value {
+    let mut payload = match shape {
+        PayloadShape::CamelCase => pascal_case_keys(payload),
+        _ => payload,
+    };
+    let properties = match payload.as_object_mut() {
+        Some(properties) => properties,
+        None => return payload,
+    };
+
+    for field in API_ONLY_FIELDS {
+        properties.remove(*field);
+    }
+
+    // EC2 payloads tag through "TagSpecifications" and others through a "Tags" map, whereas
+    // CloudFormation expects a list of "Key"/"Value" pairs.
+    if let Some(tag_specifications) = properties.remove("TagSpecifications") {
+        if let Some(tags) = tag_specifications.get(0).and_then(|spec| spec.get("Tags")) {
+            properties.insert("Tags".to_owned(), tags.clone());
+        }
+    }
+    if let Some(serde_json::Value::Object(tags)) = properties.get("Tags").cloned() {
+        let tags: Vec<serde_json::Value> = tags
+            .into_iter()
+            .map(|(key, value)| serde_json::json!({ "Key": key, "Value": value }))
+            .collect();
+        properties.insert("Tags".to_owned(), tags.into());
+    }
+
+    // Policy documents are submitted to the API as strings, but are objects in a template.
+    for field in ["AssumeRolePolicyDocument", "PolicyDocument"] {
+        if let Some(serde_json::Value::String(document)) = properties.get(field) {
+            if let Ok(document) = serde_json::from_str::<serde_json::Value>(document) {
+                properties.insert(field.to_owned(), document);
+            }
+        }
+    }
+
+    match shape {
+        PayloadShape::PascalCase | PayloadShape::CamelCase => {}
+        PayloadShape::SecurityGroup => {
+            if let Some(description) = properties.remove("Description") {
+                properties.insert("GroupDescription".to_owned(), description);
+            }
+        }
+        PayloadShape::SecurityGroupRule(peer_key) => {
+            if let Some(permission) = properties
+                .remove("IpPermissions")
+                .and_then(|permissions| permissions.get(0).cloned())
+            {
+                for field in ["IpProtocol", "FromPort", "ToPort"] {
+                    if let Some(value) = permission.get(field) {
+                        properties.insert(field.to_owned(), value.clone());
+                    }
+                }
+                if let Some(cidr) = permission.pointer("/IpRanges/0/CidrIp") {
+                    properties.insert("CidrIp".to_owned(), cidr.clone());
+                }
+                if let Some(cidr) = permission.pointer("/Ipv6Ranges/0/CidrIpv6") {
+                    properties.insert("CidrIpv6".to_owned(), cidr.clone());
+                }
+                if let Some(group_id) = permission.pointer("/UserIdGroupPairs/0/GroupId") {
+                    properties.insert(peer_key.to_owned(), group_id.clone());
+                }
+            }
+        }
+    }
+
+    payload
+}
+
+fn pascal_case_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| {
+                let value = if value.is_object() && OPAQUE_CAMEL_CASE_FIELDS.contains(&key.as_str())
+                {
+                    value
+                } else {
+                    pascal_case_keys(value)
+                };
+                (upper_first(&key), value)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        serde_json::Value::Array(array) => array.into_iter().map(pascal_case_keys).collect(),
+        value => value,
+    }
+}
+
+/// Replace every occurrence of the given value within the properties with a `Ref` to the given
+/// logical id. Only strings and numbers are replaced, to avoid matching structural values.
+fn replace_with_ref(
+    properties: &mut serde_json::Value,
+    value: &serde_json::Value,
+    logical_id: &str,
+) {
+    if !(value.is_string() || value.is_number()) || value.as_str() == Some("") {
+        return;
+    }
+    match properties {
+        serde_json::Value::Object(object) => {
+            for child in object.values_mut() {
+                replace_with_ref(child, value, logical_id);
+            }
+        }
+        serde_json::Value::Array(array) => {
+            for child in array.iter_mut() {
+                replace_with_ref(child, value, logical_id);
+            }
+        }
+        property if property == value => {
+            *property = serde_json::json!({ "Ref": logical_id });
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ec2_payload_becomes_instance_properties() {
+        let payload = serde_json::json!({
+            "ImageId": "ami-0123",
+            "InstanceType": "t3.micro",
+            "MinCount": 1,
+            "MaxCount": 1,
+            "TagSpecifications": [{
+                "ResourceType": "instance",
+                "Tags": [{ "Key": "Name", "Value": "web" }],
+            }],
+        });
+        assert_eq!(
+            serde_json::json!({
+                "ImageId": "ami-0123",
+                "InstanceType": "t3.micro",
+                "Tags": [{ "Key": "Name", "Value": "web" }],
+            }),
+            properties_from_payload(payload, PayloadShape::PascalCase)
+        );
+    }
+
+    #[test]
+    fn ingress_payload_is_flattened() {
+        let payload = serde_json::json!({
+            "GroupId": "sg-1",
+            "IpPermissions": [{
+                "IpProtocol": "tcp",
+                "FromPort": 443,
+                "ToPort": 443,
+                "IpRanges": [{ "CidrIp": "0.0.0.0/0" }],
+            }],
+        });
+        assert_eq!(
+            serde_json::json!({
+                "GroupId": "sg-1",
+                "IpProtocol": "tcp",
+                "FromPort": 443,
+                "ToPort": 443,
+                "CidrIp": "0.0.0.0/0",
+            }),
+            properties_from_payload(
+                payload,
+                PayloadShape::SecurityGroupRule("SourceSecurityGroupId")
+            )
+        );
+    }
+
+    #[test]
+    fn camel_case_payload_and_tag_map() {
+        let payload = serde_json::json!({
+            "logGroupName": "/si/app",
+            "tags": { "team": "platform" },
+        });
+        assert_eq!(
+            serde_json::json!({
+                "LogGroupName": "/si/app",
+                "Tags": [{ "Key": "team", "Value": "platform" }],
+            }),
+            properties_from_payload(payload, PayloadShape::CamelCase)
+        );
+    }
+
+    #[test]
+    fn references_and_logical_ids() {
+        let mut properties = serde_json::json!({
+            "GroupId": "sg-1",
+            "SecurityGroupIds": ["sg-1", "sg-2"],
+        });
+        replace_with_ref(&mut properties, &serde_json::json!("sg-1"), "WebSg");
+        assert_eq!(
+            serde_json::json!({
+                "GroupId": { "Ref": "WebSg" },
+                "SecurityGroupIds": [{ "Ref": "WebSg" }, "sg-2"],
+            }),
+            properties
+        );
+
+        let mut taken = HashSet::new();
+        assert_eq!("WebServer", unique_logical_id("web server", &mut taken));
+        assert_eq!("WebServer2", unique_logical_id("web-server", &mut taken));
+        assert_eq!("Component1Sg", unique_logical_id("1 sg", &mut taken));
+    }
+}
@@ -17,6 +17,7 @@ pub type CodeViewResult<T> = Result<T, CodeViewError>;
 #[strum(serialize_all = "camelCase")]
 pub enum CodeLanguage {
     Diff,
+    Hcl,
     Json,
     String,
     Unknown,
@@ -29,6 +30,7 @@ impl TryFrom<String> for CodeLanguage {
     fn try_from(value: String) -> CodeViewResult<Self> {
         match value.to_lowercase().as_str() {
             "diff" => Ok(Self::Diff),
+            "hcl" => Ok(Self::Hcl),
             "json" => Ok(Self::Json),
             "string" => Ok(Self::String),
             "yaml" => Ok(Self::Yaml),
@@ -17,6 +17,7 @@ use crate::attribute::context::AttributeContextBuilder;
 use crate::attribute::value::AttributeValue;
 use crate::attribute::value::AttributeValueError;
 use crate::code_view::CodeViewError;
+use crate::component::owner::ComponentOwner;
 use crate::edge::EdgeKind;
 use crate::func::binding::FuncBindingError;
 use crate::func::binding_return_value::{FuncBindingReturnValueError, FuncBindingReturnValueId};
@@ -35,23 +36,37 @@ use crate::{
     AttributePrototype, AttributePrototypeArgumentError, AttributePrototypeError,
     AttributePrototypeId, AttributeReadContext, ComponentType, DalContext, EdgeError,
     ExternalProviderError, FixError, FixId, Func, FuncBackendKind, FuncError, HistoryActor,
-    HistoryEventError, IndexMap, Node, NodeError, PropError, RootPropChild, Schema, SchemaError,
-    SchemaId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError,
-    UserPk, Visibility, WorkspaceError, WsEvent, WsEventResult, WsPayload,
+    HistoryEventError, IndexMap, Node, NodeError, PropError, PropId, RootPropChild, Schema,
+    SchemaError, SchemaId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, UserPk, Visibility, WorkspaceError, WsEvent, WsEventResult, WsPayload,
 };
-use crate::{AttributeValueId, QualificationError};
+use crate::{AttributeValueId, QualificationError, ResourceIdentifierError};
 use crate::{Edge, FixResolverError, NodeKind};
 
+pub mod archive;
 pub mod code;
+pub mod dataset;
+pub mod delete;
 pub mod diff;
+pub mod duplicate;
+pub mod notes;
+pub mod owner;
 pub mod qualification;
 pub mod resource;
+pub mod search;
+pub mod snippet;
 pub mod status;
+pub mod strict;
+pub mod subsystem;
+pub mod template;
+pub mod validation;
 pub mod view;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ComponentError {
+    #[error("action error: {0}")]
+    Action(String),
     #[error(transparent)]
     ActionPrototype(#[from] ActionPrototypeError),
     #[error("attribute context error: {0}")]
@@ -91,6 +106,8 @@ pub enum ComponentError {
     ComponentView(#[from] ComponentViewError),
     #[error(transparent)]
     ContextTransaction(#[from] TransactionsError),
+    #[error("unterminated quote in dataset csv starting on line {0}")]
+    DatasetCsvUnterminatedQuote(usize),
     #[error("edge error: {0}")]
     Edge(#[from] EdgeError),
     /// Found an [`ExternalProviderError`](crate::ExternalProviderError).
@@ -125,6 +142,8 @@ pub enum ComponentError {
     InvalidContextForDiff,
     #[error("invalid func backend kind (0:?) for checking validations (need validation kind)")]
     InvalidFuncBackendKindForValidations(FuncBackendKind),
+    #[error("invalid prop value filter (expected \"<path> == <value>\"): {0}")]
+    InvalidPropValueFilter(String),
     #[error("attribute value does not have a prototype: {0}")]
     MissingAttributePrototype(AttributeValueId),
     #[error("attribute prototype does not have a function: {0}")]
@@ -151,16 +170,22 @@ pub enum ComponentError {
     /// [`AttributeValueId`](crate::AttributeValue).
     #[error("parent attribute value not found for attribute value: {0}")]
     ParentAttributeValueNotFound(AttributeValueId),
+    #[error(transparent)]
+    ParseFloat(#[from] std::num::ParseFloatError),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error(transparent)]
     PgPool(#[from] si_data_pg::PgPoolError),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
+    #[error("prop {0} is not defined on strict schema variant {1}")]
+    PropNotDefinedForStrictVariant(PropId, SchemaVariantId),
     #[error("qualification error: {0}")]
     Qualification(#[from] QualificationError),
     #[error("qualification result for {0} on component {1} has no value")]
     QualificationResultEmpty(String, ComponentId),
+    #[error("resource identifier error: {0}")]
+    ResourceIdentifier(#[from] ResourceIdentifierError),
     #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
     #[error("schema variant error: {0}")]
@@ -169,12 +194,18 @@ pub enum ComponentError {
     SchemaVariantNotFinalized(SchemaVariantId),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("schema variant {1} not found for schema {0}")]
+    SnippetSchemaVariantNotFound(String, String),
+    #[error("unsupported component snippet version: {0}")]
+    SnippetVersionUnsupported(u32),
     #[error("socket error: {0}")]
     Socket(#[from] SocketError),
     #[error("standard model error: {0}")]
     StandardModelError(#[from] StandardModelError),
     #[error("summary diagram error: {0}")]
     SummaryDiagram(String),
+    #[error("prop path {0} is not defined on strict schema variant {1}")]
+    UndefinedPropPath(String, SchemaVariantId),
     #[error("workspace error: {0}")]
     Workspace(#[from] WorkspaceError),
     #[error("ws event error: {0}")]
@@ -188,6 +219,7 @@ const FIND_SI_CHILD_PROP_ATTRIBUTE_VALUE: &str =
     include_str!("queries/component/find_si_child_attribute_value.sql");
 const LIST_FOR_SCHEMA_VARIANT: &str = include_str!("queries/component/list_for_schema_variant.sql");
 const LIST_FOR_SCHEMA: &str = include_str!("queries/component/list_for_schema.sql");
+const LIST_PAGE: &str = include_str!("queries/component/list_page.sql");
 const LIST_SOCKETS_FOR_SOCKET_EDGE_KIND: &str =
     include_str!("queries/component/list_sockets_for_socket_edge_kind.sql");
 const FIND_NAME: &str = include_str!("queries/component/find_name.sql");
@@ -242,6 +274,10 @@ pub struct Component {
     deletion_user_pk: Option<UserPk>,
     needs_destroy: bool,
     hidden: bool,
+    owner: Option<ComponentOwner>,
+    /// Archived [`Components`](Component) are kept out of the [`Diagram`](crate::Diagram) and
+    /// dependent value updates. See [`archive`](crate::component::archive).
+    archived: bool,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -351,6 +387,7 @@ impl Component {
     standard_model_accessor!(kind, Enum(ComponentKind), ComponentResult);
     standard_model_accessor!(needs_destroy, bool, ComponentResult);
     standard_model_accessor!(hidden, bool, ComponentResult);
+    standard_model_accessor!(archived, bool, ComponentResult);
     standard_model_accessor!(deletion_user_pk, Option<Pk(UserPk)>, ComponentResult);
 
     standard_model_belongs_to!(
@@ -477,6 +514,27 @@ impl Component {
         Ok(row.is_some())
     }
 
+    /// List at most `limit` [`Components`](Self), ordered by id, starting after the one with the
+    /// id of `cursor` (or from the first one if unset). Pass the id of the last [`Component`] of
+    /// a page as the `cursor` of the next one; a page shorter than `limit` is the last.
+    #[instrument(skip_all)]
+    pub async fn list_page(
+        ctx: &DalContext,
+        cursor: Option<ComponentId>,
+        limit: usize,
+    ) -> ComponentResult<Vec<Component>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_PAGE,
+                &[ctx.tenancy(), ctx.visibility(), &cursor, &(limit as i64)],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
     #[instrument(skip_all)]
     pub async fn list_for_schema(
         ctx: &DalContext,
@@ -844,6 +902,7 @@ impl Component {
                     edge.tail_node_id(),
                     edge.tail_socket_id(),
                     EdgeKind::Symbolic,
+                    *edge.creation_source(),
                 )
                 .await?;
             }
@@ -0,0 +1,69 @@
+//! This module contains the ability to archive a [`Component`]: an archived [`Component`] is
+//! hidden from the [`Diagram`](crate::Diagram) and skipped by
+//! [`DependentValuesUpdate`](crate::job::definition::DependentValuesUpdate), but, unlike a deleted
+//! one, all of its data and history are kept as they are.
+
+use telemetry::prelude::*;
+
+use crate::component::{ComponentError, ComponentResult};
+use crate::{
+    standard_model, AttributeValue, Component, ComponentId, DalContext, StandardModel, WsEvent,
+};
+
+const LIST_ARCHIVED: &str = include_str!("../queries/component/list_archived.sql");
+
+impl Component {
+    /// Archive the [`Component`] corresponding to the provided [`ComponentId`].
+    #[instrument(skip(ctx))]
+    pub async fn archive(ctx: &DalContext, component_id: ComponentId) -> ComponentResult<()> {
+        let mut component = Self::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        if component.archived() {
+            return Ok(());
+        }
+        component.set_archived(ctx, true).await?;
+
+        WsEvent::component_updated(ctx, component_id)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Unarchive the [`Component`] corresponding to the provided [`ComponentId`]. Since its values
+    /// were not kept up to date while it was archived, the ones that fell behind their inputs are
+    /// recomputed (see [`AttributeValue::repair_stale`]).
+    #[instrument(skip(ctx))]
+    pub async fn unarchive(ctx: &DalContext, component_id: ComponentId) -> ComponentResult<()> {
+        let mut component = Self::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        if !component.archived() {
+            return Ok(());
+        }
+        component.set_archived(ctx, false).await?;
+
+        let stale = AttributeValue::list_stale(ctx, Some(component_id)).await?;
+        AttributeValue::repair_stale(ctx, &stale).await?;
+
+        WsEvent::component_updated(ctx, component_id)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// List all archived [`Components`](Component).
+    pub async fn list_archived(ctx: &DalContext) -> ComponentResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_ARCHIVED, &[ctx.tenancy(), ctx.visibility()])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+}
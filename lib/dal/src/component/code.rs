@@ -6,9 +6,10 @@ use telemetry::prelude::*;
 use crate::attribute::value::AttributeValue;
 use crate::attribute::value::AttributeValueError;
 use crate::component::ComponentResult;
+use crate::edge::{EdgeError, EdgeKind};
 use crate::{
     AttributeReadContext, AttributeValueId, CodeLanguage, CodeView, ComponentError, ComponentId,
-    DalContext, StandardModel, WsEvent, WsPayload,
+    DalContext, Edge, ExternalProvider, Func, Socket, StandardModel, WsEvent, WsPayload,
 };
 use crate::{Component, SchemaVariant};
 use crate::{RootPropChild, WsEventResult};
@@ -21,6 +22,32 @@ struct CodeGenerationEntry {
     pub message: Option<String>,
 }
 
+/// The output of a single "code generation" [`Func`] for a [`Component`]. A variant may have
+/// several code generations (e.g. "AWS JSON" and "Terraform"), each producing its own artifact.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeGenerationArtifact {
+    pub func_name: String,
+    /// The display name of the [`Func`], falling back to its name.
+    pub name: String,
+    pub language: CodeLanguage,
+    /// [`None`] while the code is still being generated.
+    pub code: Option<String>,
+    pub message: Option<String>,
+}
+
+/// A summary of a [`Component`] connected to the "head" of a configuration [`Edge`], passed to
+/// "code generation" functions alongside the domain as the "connections" argument.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeGenerationConnection {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub schema_name: String,
+    pub socket_name: String,
+    pub value: Option<serde_json::Value>,
+}
+
 impl Component {
     /// List all [`CodeViews`](crate::CodeView) for based on the "code generation"
     /// [`leaves`](crate::schema::variant::leaves) for a given [`ComponentId`](Self).
@@ -29,42 +56,27 @@ impl Component {
         ctx: &DalContext,
         component_id: ComponentId,
     ) -> ComponentResult<(Vec<CodeView>, bool)> {
-        let component = Self::get_by_id(ctx, &component_id)
-            .await?
-            .ok_or(ComponentError::NotFound(component_id))?;
-        let schema_variant = component
-            .schema_variant(ctx)
-            .await?
-            .ok_or(ComponentError::NoSchemaVariant(component_id))?;
-
-        // Prepare to assemble code views and access the "/root/code" prop tree.
+        // If the map has been populated, we need to see if there are code views to generate.
         let mut code_views: Vec<CodeView> = Vec::new();
-        let code_map_implicit_internal_provider =
-            SchemaVariant::find_root_child_implicit_internal_provider(
-                ctx,
-                *schema_variant.id(),
-                RootPropChild::Code,
-            )
-            .await?;
-        let code_map_attribute_read_context = AttributeReadContext {
-            internal_provider_id: Some(*code_map_implicit_internal_provider.id()),
-            component_id: Some(component_id),
-            ..AttributeReadContext::default()
-        };
-        let code_map_attribute_value =
-            AttributeValue::find_for_context(ctx, code_map_attribute_read_context)
+        if let Some(code_map) = Self::code_generation_entries(ctx, component_id).await? {
+            // AWS payloads are also rendered as runnable CLI commands, which need the region.
+            let domain = if code_map
+                .keys()
+                .any(|func_name| aws_cli_operation(func_name).is_some())
+            {
+                Self::root_prop_child_attribute_value_for_component(
+                    ctx,
+                    component_id,
+                    RootPropChild::Domain,
+                )
                 .await?
-                .ok_or(AttributeValueError::NotFoundForReadContext(
-                    code_map_attribute_read_context,
-                ))?;
-        let maybe_code_map_value = code_map_attribute_value.get_value(ctx).await?;
-
-        // If the map has been populated, we need to see if there are code views to generate.
-        if let Some(code_map_value) = maybe_code_map_value {
-            let code_map: HashMap<String, CodeGenerationEntry> =
-                serde_json::from_value(code_map_value)?;
+                .get_value(ctx)
+                .await?
+            } else {
+                None
+            };
 
-            for entry in code_map.values() {
+            for (func_name, entry) in code_map.iter() {
                 // When a new code gen function is craeted the code/format entries will not yet be
                 // set, so just ignore them in the loop here. Function return value type checking
                 // should ensure that the executed function does not unset these itself.
@@ -92,7 +104,14 @@ impl Component {
 
                 let message = entry.message.clone();
 
+                let aws_cli_command = code
+                    .as_deref()
+                    .and_then(|code| aws_cli_command(func_name, code, domain.as_ref()));
+
                 code_views.push(CodeView::new(language, code, message));
+                if let Some(command) = aws_cli_command {
+                    code_views.push(CodeView::new(CodeLanguage::String, Some(command), None));
+                }
             }
         } else {
             return Ok((vec![], false));
@@ -100,6 +119,185 @@ impl Component {
         Ok((code_views, true))
     }
 
+    /// List the artifact produced by every "code generation" [`Func`] of a given
+    /// [`ComponentId`](Self), sorted by name. Unlike [`Self::list_code_generated`], every artifact
+    /// is named and entries that have not been generated yet are included without code.
+    #[instrument(skip_all)]
+    pub async fn list_code_generation_artifacts(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<CodeGenerationArtifact>> {
+        let mut artifacts = Vec::new();
+        for (func_name, entry) in Self::code_generation_entries(ctx, component_id)
+            .await?
+            .unwrap_or_default()
+        {
+            let name = match Func::find_by_name(ctx, &func_name).await? {
+                Some(func) => func.display_name().unwrap_or(func.name()).to_owned(),
+                None => func_name.clone(),
+            };
+            let language = match entry.format.as_deref() {
+                Some(format) if !format.is_empty() => CodeLanguage::try_from(format.to_owned())?,
+                _ => CodeLanguage::Unknown,
+            };
+
+            artifacts.push(CodeGenerationArtifact {
+                func_name,
+                name,
+                language,
+                code: entry.code.filter(|code| !code.is_empty()),
+                message: entry.message,
+            });
+        }
+
+        artifacts.sort_by(|a, b| (&a.name, &a.func_name).cmp(&(&b.name, &b.func_name)));
+        Ok(artifacts)
+    }
+
+    /// Collect the generated code for a given [`ComponentId`](Self), keyed by the name of the
+    /// "code generation" function that produced it. Entries that have not been generated yet
+    /// are skipped.
+    #[instrument(skip_all)]
+    pub async fn generated_code_by_func_name(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<HashMap<String, String>> {
+        Ok(Self::code_generation_entries(ctx, component_id)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(func_name, entry)| {
+                entry
+                    .code
+                    .filter(|code| !code.is_empty())
+                    .map(|code| (func_name, code))
+            })
+            .collect())
+    }
+
+    /// Collect the generated code of a [`Component`] keyed by the name of the "code generation"
+    /// [`Func`](crate::Func) that produced it, both on _head_ and in the current
+    /// [`Visibility`](crate::Visibility), in that order. A side is empty when the component does
+    /// not exist there.
+    #[instrument(skip_all)]
+    pub async fn generated_code_on_head_and_change_set(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<(HashMap<String, String>, HashMap<String, String>)> {
+        let head_ctx = ctx.clone_with_head();
+
+        let head = match Self::get_by_id(&head_ctx, &component_id).await? {
+            Some(_) => Self::generated_code_by_func_name(&head_ctx, component_id).await?,
+            None => HashMap::new(),
+        };
+        let change_set = if ctx.visibility().is_head() {
+            head.clone()
+        } else {
+            match Self::get_by_id(ctx, &component_id).await? {
+                Some(_) => Self::generated_code_by_func_name(ctx, component_id).await?,
+                None => HashMap::new(),
+            }
+        };
+
+        Ok((head, change_set))
+    }
+
+    /// Read the "/root/code" map for a given [`ComponentId`](Self), returning [`None`] if it has
+    /// not been populated.
+    async fn code_generation_entries(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Option<HashMap<String, CodeGenerationEntry>>> {
+        let component = Self::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        let schema_variant = component
+            .schema_variant(ctx)
+            .await?
+            .ok_or(ComponentError::NoSchemaVariant(component_id))?;
+
+        // Access the "/root/code" prop tree.
+        let code_map_implicit_internal_provider =
+            SchemaVariant::find_root_child_implicit_internal_provider(
+                ctx,
+                *schema_variant.id(),
+                RootPropChild::Code,
+            )
+            .await?;
+        let code_map_attribute_read_context = AttributeReadContext {
+            internal_provider_id: Some(*code_map_implicit_internal_provider.id()),
+            component_id: Some(component_id),
+            ..AttributeReadContext::default()
+        };
+        let code_map_attribute_value =
+            AttributeValue::find_for_context(ctx, code_map_attribute_read_context)
+                .await?
+                .ok_or(AttributeValueError::NotFoundForReadContext(
+                    code_map_attribute_read_context,
+                ))?;
+        let maybe_code_map_value = code_map_attribute_value.get_value(ctx).await?;
+
+        Ok(match maybe_code_map_value {
+            Some(code_map_value) => Some(serde_json::from_value(code_map_value)?),
+            None => None,
+        })
+    }
+
+    /// Collect a [`CodeGenerationConnection`] for every [`Component`] feeding one of the input
+    /// [`Sockets`](crate::Socket) of the given [`ComponentId`](Self) via a configuration
+    /// [`Edge`]. The value is what the tail [`Component`] emits through its
+    /// [`ExternalProvider`](crate::ExternalProvider).
+    pub async fn connections_for_code_generation(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<CodeGenerationConnection>> {
+        let mut connections = Vec::new();
+        for edge in Edge::list_for_component(ctx, component_id).await? {
+            if *edge.kind() != EdgeKind::Configuration || edge.head_component_id() != component_id {
+                continue;
+            }
+
+            let tail_component_id = edge.tail_component_id();
+            let tail_component = Self::get_by_id(ctx, &tail_component_id)
+                .await?
+                .ok_or(ComponentError::NotFound(tail_component_id))?;
+            let schema = tail_component
+                .schema(ctx)
+                .await?
+                .ok_or(ComponentError::NoSchema(tail_component_id))?;
+            let socket = Socket::get_by_id(ctx, edge.tail_socket_id())
+                .await?
+                .ok_or_else(|| EdgeError::SocketNotFound(*edge.tail_socket_id()))?;
+            let external_provider = ExternalProvider::find_for_socket(ctx, *socket.id())
+                .await?
+                .ok_or_else(|| EdgeError::ExternalProviderNotFoundForSocket(*socket.id()))?;
+
+            let read_context = AttributeReadContext {
+                external_provider_id: Some(*external_provider.id()),
+                component_id: Some(tail_component_id),
+                ..AttributeReadContext::default()
+            };
+            let value = match AttributeValue::find_for_context(ctx, read_context).await? {
+                Some(attribute_value) => attribute_value.get_value(ctx).await?,
+                None => None,
+            };
+
+            connections.push(CodeGenerationConnection {
+                component_id: tail_component_id,
+                component_name: tail_component.name(ctx).await?,
+                schema_name: schema.name().to_owned(),
+                socket_name: socket.name().to_owned(),
+                value,
+            });
+        }
+
+        // Keep the ordering stable so that unchanged connections do not produce new func bindings.
+        connections.sort_by(|a, b| {
+            (&a.socket_name, a.component_id).cmp(&(&b.socket_name, b.component_id))
+        });
+        Ok(connections)
+    }
+
     // TODO(nick): big query potential.
     /// Returns a [`HashSet`](std::collections::HashSet) of all the
     /// [`AttributeValueIds`](crate::AttributeValue) corresponding to "code generation"
@@ -121,7 +319,7 @@ impl Component {
     /// Returns a [`HashSet`](std::collections::HashSet) of all the
     /// [`AttributeValueIds`](crate::AttributeValue) corresponding to "code generation"
     /// [`leaves`](crate::schema::variant::leaves) for a given [`ComponentId`](Self).
-    async fn all_code_generation_attribute_values_for_component(
+    pub async fn all_code_generation_attribute_values_for_component(
         ctx: &DalContext,
         component_id: ComponentId,
     ) -> ComponentResult<HashSet<AttributeValueId>> {
@@ -141,6 +339,105 @@ impl Component {
     }
 }
 
+/// The AWS CLI operation each AWS "code generation" function produces the `--cli-input-json`
+/// payload for, as `(func name, service, operation, regional)`. These mirror the commands run by
+/// the corresponding create (or refresh, for lookups) actions.
+const AWS_CLI_OPERATIONS: &[(&str, &str, &str, bool)] = &[
+    ("si:awsEc2EbsVolumeJSON", "ec2", "create-volume", true),
+    (
+        "si:awsEcsServiceGenerateJson",
+        "ecs",
+        "create-service",
+        true,
+    ),
+    ("si:generateAwsAmiJSON", "ec2", "describe-images", true),
+    (
+        "si:generateAwsCloudwatchLogGroupJSON",
+        "logs",
+        "create-log-group",
+        true,
+    ),
+    ("si:generateAwsEc2JSON", "ec2", "run-instances", true),
+    ("si:generateAwsEcsJSON", "ecs", "create-cluster", true),
+    (
+        "si:generateAwsEcsTaskDefinitionJSON",
+        "ecs",
+        "register-task-definition",
+        true,
+    ),
+    (
+        "si:generateAwsEgressJSON",
+        "ec2",
+        "authorize-security-group-egress",
+        true,
+    ),
+    ("si:generateAwsEipJSON", "ec2", "allocate-address", true),
+    ("si:generateAwsIamGroupJSON", "iam", "create-group", false),
+    (
+        "si:generateAwsIamInstanceProfileJSON",
+        "iam",
+        "create-instance-profile",
+        false,
+    ),
+    ("si:generateAwsIamRoleJSON", "iam", "create-role", false),
+    (
+        "si:generateAwsIngressJSON",
+        "ec2",
+        "authorize-security-group-ingress",
+        true,
+    ),
+    ("si:generateAwsKeyPairJSON", "ec2", "create-key-pair", true),
+    (
+        "si:generateAwsSecurityGroupJSON",
+        "ec2",
+        "create-security-group",
+        true,
+    ),
+    (
+        "si:generateAwsTargetGroupJSON",
+        "elbv2",
+        "create-target-group",
+        true,
+    ),
+];
+
+fn aws_cli_operation(func_name: &str) -> Option<(&'static str, &'static str, bool)> {
+    AWS_CLI_OPERATIONS
+        .iter()
+        .find(|(name, _, _, _)| *name == func_name)
+        .map(|(_, service, operation, regional)| (*service, *operation, *regional))
+}
+
+/// Render the `aws` command line that submits the JSON payload generated by the given function.
+/// The region comes from the domain (falling back to `$AWS_REGION`) and the profile from
+/// `$AWS_PROFILE`, so the command can be copied into a shell as-is.
+fn aws_cli_command(
+    func_name: &str,
+    code: &str,
+    domain: Option<&serde_json::Value>,
+) -> Option<String> {
+    let (service, operation, regional) = aws_cli_operation(func_name)?;
+
+    let mut command = format!("aws {service} {operation}");
+    if regional {
+        let region = domain
+            .and_then(|domain| domain.get("region").or_else(|| domain.get("Region")))
+            .and_then(|region| region.as_str())
+            .filter(|region| !region.is_empty());
+        match region {
+            Some(region) => command.push_str(&format!(" --region {}", shell_quote(region))),
+            None => command.push_str(" --region \"$AWS_REGION\""),
+        }
+    }
+    command.push_str(" --profile \"${AWS_PROFILE:-default}\"");
+    command.push_str(&format!(" --cli-input-json {}", shell_quote(code)));
+    Some(command)
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 // NOTE(nick): consider moving this somewhere else.
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -148,6 +445,14 @@ pub struct CodeGeneratedPayload {
     component_id: ComponentId,
 }
 
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeArtifactGeneratedPayload {
+    component_id: ComponentId,
+    func_name: String,
+    format: Option<String>,
+}
+
 // NOTE(nick): consider moving this somewhere else.
 impl WsEvent {
     pub async fn code_generated(
@@ -160,4 +465,58 @@ impl WsEvent {
         )
         .await
     }
+
+    /// Sent for every [`CodeGenerationArtifact`] of a [`Component`] once its "code generation"
+    /// [`Func`] has finished, so that each format can be refreshed on its own.
+    pub async fn code_artifact_generated(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        func_name: String,
+        format: Option<String>,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::CodeArtifactGenerated(CodeArtifactGeneratedPayload {
+                component_id,
+                func_name,
+                format,
+            }),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aws_cli_command_for_regional_operation() {
+        let domain = serde_json::json!({ "region": "us-east-2" });
+        let command = aws_cli_command(
+            "si:generateAwsEc2JSON",
+            "{\"KeyName\": \"bob's key\"}",
+            Some(&domain),
+        )
+        .expect("ec2 payloads have a cli command");
+        assert_eq!(
+            "aws ec2 run-instances --region 'us-east-2' --profile \"${AWS_PROFILE:-default}\" --cli-input-json '{\"KeyName\": \"bob'\\''s key\"}'",
+            command
+        );
+    }
+
+    #[test]
+    fn aws_cli_command_for_global_operation() {
+        let command = aws_cli_command("si:generateAwsIamRoleJSON", "{}", None)
+            .expect("iam payloads have a cli command");
+        assert_eq!(
+            "aws iam create-role --profile \"${AWS_PROFILE:-default}\" --cli-input-json '{}'",
+            command
+        );
+    }
+
+    #[test]
+    fn aws_cli_command_for_unknown_func() {
+        assert!(aws_cli_command("si:generateYAML", "{}", None).is_none());
+    }
 }
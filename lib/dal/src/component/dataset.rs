@@ -0,0 +1,335 @@
+//! This module contains [`ComponentDataset`], a tabular set of prop values (e.g. parsed from a
+//! CSV file) used to create many similar [`Components`](crate::Component) at once, one per row.
+//!
+//! Columns are mapped to props below "/root/domain" by their JSON pointer (e.g.
+//! "/root/domain/GroupName"). Every row is turned into domain overrides, shaped the same way as
+//! those of a [`ComponentSnippet`](crate::component::snippet::ComponentSnippet), so that they
+//! can be applied with
+//! [`Component::apply_domain_overrides`](crate::Component::apply_domain_overrides).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::component::ComponentResult;
+use crate::prop::PropPath;
+use crate::{ComponentError, DalContext, Prop, PropKind, SchemaVariantId};
+
+/// Values (or CSV cells) that leave a prop unset.
+const EMPTY_CELL: &str = "";
+
+/// Rows of prop values, with one value per column.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDataset {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// The values of a single row, ready to be applied to a new [`Component`](crate::Component).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDatasetEntry {
+    /// The value of the name column, if one was chosen and set for the row.
+    pub name: Option<String>,
+    /// The overridden values, nested the same way as "/root/domain".
+    pub domain: serde_json::Value,
+}
+
+/// A problem found while validating a [`ComponentDataset`]. Problems with the mapping itself
+/// have no row.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDatasetProblem {
+    /// The zero-based index of the row.
+    pub row: Option<usize>,
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl ComponentDatasetProblem {
+    fn new(row: Option<usize>, column: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            row,
+            column: Some(column.into()),
+            message: message.into(),
+        }
+    }
+}
+
+/// A column resolved to the [`Prop`] it is mapped to.
+struct MappedColumn<'a> {
+    index: usize,
+    name: &'a str,
+    kind: PropKind,
+    /// The prop names below "/root/domain".
+    domain_path: Vec<String>,
+}
+
+impl ComponentDataset {
+    /// Parse a CSV document whose first record holds the column names. Every cell is kept as a
+    /// string and converted to the kind of the prop its column is mapped to during
+    /// [`validation`](Self::prepare).
+    pub fn from_csv(csv: &str) -> ComponentResult<Self> {
+        let mut records = parse_csv(csv)?.into_iter();
+        let columns = records.next().unwrap_or_default();
+        let rows = records
+            .map(|record| record.into_iter().map(serde_json::Value::String).collect())
+            .collect();
+        Ok(Self { columns, rows })
+    }
+
+    /// Validate the dataset against the [`SchemaVariant`](crate::SchemaVariant), given a
+    /// mapping from column names to prop paths and an optional column holding component names.
+    ///
+    /// Nothing is written: the entries are only meaningful when no problems are returned.
+    pub async fn prepare(
+        &self,
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        mapping: &HashMap<String, String>,
+        name_column: Option<&str>,
+    ) -> ComponentResult<(Vec<ComponentDatasetEntry>, Vec<ComponentDatasetProblem>)> {
+        let mut problems = Vec::new();
+
+        let mut columns = Vec::new();
+        for (column, path) in mapping {
+            match self
+                .map_column(ctx, schema_variant_id, column, path)
+                .await?
+            {
+                Ok(mapped) => columns.push(mapped),
+                Err(message) => problems.push(ComponentDatasetProblem::new(None, column, message)),
+            }
+        }
+        columns.sort_by_key(|column| column.index);
+
+        let name_index = match name_column {
+            Some(name_column) => {
+                let index = self.column_index(name_column);
+                if index.is_none() {
+                    problems.push(ComponentDatasetProblem::new(
+                        None,
+                        name_column,
+                        "column does not exist",
+                    ));
+                }
+                index
+            }
+            None => None,
+        };
+
+        let mut entries = Vec::with_capacity(self.rows.len());
+        for (row_index, row) in self.rows.iter().enumerate() {
+            if row.len() != self.columns.len() {
+                problems.push(ComponentDatasetProblem {
+                    row: Some(row_index),
+                    column: None,
+                    message: format!(
+                        "expected {} values but found {}",
+                        self.columns.len(),
+                        row.len()
+                    ),
+                });
+                continue;
+            }
+
+            let name = name_index
+                .and_then(|index| row.get(index))
+                .and_then(|value| match value {
+                    serde_json::Value::Null => None,
+                    serde_json::Value::String(name) if name == EMPTY_CELL => None,
+                    serde_json::Value::String(name) => Some(name.clone()),
+                    value => Some(value.to_string()),
+                });
+
+            let mut domain = serde_json::Map::new();
+            for column in &columns {
+                match coerce(column.kind, &row[column.index]) {
+                    Ok(Some(value)) => insert_at_path(&mut domain, &column.domain_path, value),
+                    Ok(None) => {}
+                    Err(message) => problems.push(ComponentDatasetProblem::new(
+                        Some(row_index),
+                        column.name,
+                        message,
+                    )),
+                }
+            }
+
+            entries.push(ComponentDatasetEntry {
+                name,
+                domain: serde_json::Value::Object(domain),
+            });
+        }
+
+        Ok((entries, problems))
+    }
+
+    fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|name| name == column)
+    }
+
+    /// Resolve a column to the [`Prop`] found at `path`, returning the reason the mapping is
+    /// invalid otherwise.
+    async fn map_column<'a>(
+        &self,
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        column: &'a str,
+        path: &str,
+    ) -> ComponentResult<Result<MappedColumn<'a>, String>> {
+        let index = match self.column_index(column) {
+            Some(index) => index,
+            None => return Ok(Err("column does not exist".to_owned())),
+        };
+
+        let domain_path: Vec<String> = match path.strip_prefix("/root/domain/") {
+            Some(domain_path) if !domain_path.is_empty() => {
+                domain_path.split('/').map(ToOwned::to_owned).collect()
+            }
+            _ => return Ok(Err(format!("{path} is not a prop below /root/domain"))),
+        };
+
+        // Values can only be nested below objects, every intermediate prop must be one.
+        let mut parts = vec!["root".to_owned(), "domain".to_owned()];
+        let mut kind = PropKind::Object;
+        for part in &domain_path {
+            if kind != PropKind::Object {
+                return Ok(Err(format!("{path} is not below objects only")));
+            }
+            parts.push(part.clone());
+            kind = match Prop::find_prop_by_path_opt(ctx, schema_variant_id, &PropPath::new(&parts))
+                .await?
+            {
+                Some(prop) => *prop.kind(),
+                None => return Ok(Err(format!("{path} does not exist"))),
+            };
+        }
+        if kind == PropKind::Object {
+            return Ok(Err(format!("{path} is an object, map its fields instead")));
+        }
+
+        Ok(Ok(MappedColumn {
+            index,
+            name: column,
+            kind,
+            domain_path,
+        }))
+    }
+}
+
+/// Convert a cell to a value of the given [`PropKind`]. Empty cells leave the prop unset.
+fn coerce(kind: PropKind, value: &serde_json::Value) -> Result<Option<serde_json::Value>, String> {
+    use serde_json::Value;
+
+    let text = match value {
+        Value::Null => return Ok(None),
+        Value::String(text) if text.trim() == EMPTY_CELL => return Ok(None),
+        Value::String(text) => Some(text.trim()),
+        _ => None,
+    };
+
+    let coerced = match (kind, value, text) {
+        (PropKind::String, Value::String(text), _) => Value::String(text.clone()),
+        (PropKind::String, Value::Number(_) | Value::Bool(_), _) => {
+            Value::String(value.to_string())
+        }
+        (PropKind::Integer, Value::Number(number), _) if number.is_i64() => value.clone(),
+        (PropKind::Integer, _, Some(text)) => match text.parse::<i64>() {
+            Ok(integer) => Value::from(integer),
+            Err(_) => return Err(format!("\"{text}\" is not an integer")),
+        },
+        (PropKind::Boolean, Value::Bool(_), _) => value.clone(),
+        (PropKind::Boolean, _, Some(text)) => match text.to_lowercase().as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return Err(format!("\"{text}\" is not a boolean")),
+        },
+        (PropKind::Array, Value::Array(_), _) | (PropKind::Map, Value::Object(_), _) => {
+            value.clone()
+        }
+        (PropKind::Array | PropKind::Map, _, Some(text)) => {
+            match (kind, serde_json::from_str::<Value>(text)) {
+                (PropKind::Array, Ok(parsed @ Value::Array(_)))
+                | (PropKind::Map, Ok(parsed @ Value::Object(_))) => parsed,
+                _ => return Err(format!("\"{text}\" is not a JSON {kind}")),
+            }
+        }
+        _ => return Err(format!("{value} is not a valid {kind}")),
+    };
+
+    Ok(Some(coerced))
+}
+
+fn insert_at_path(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    path: &[String],
+    value: serde_json::Value,
+) {
+    match path {
+        [] => {}
+        [name] => {
+            object.insert(name.clone(), value);
+        }
+        [name, rest @ ..] => {
+            let child = object
+                .entry(name.clone())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            if let serde_json::Value::Object(child) = child {
+                insert_at_path(child, rest, value);
+            }
+        }
+    }
+}
+
+/// Split a CSV document into records, handling quoted fields (with doubled quotes as escapes and
+/// embedded separators or newlines). Blank lines are skipped.
+fn parse_csv(csv: &str) -> ComponentResult<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut quote_line = None;
+
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quote_line.is_some() {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quote_line = None,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' => quote_line = Some(line),
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if let Some(quote_line) = quote_line {
+        return Err(ComponentError::DatasetCsvUnterminatedQuote(quote_line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|record| !(record.len() == 1 && record[0].is_empty()));
+
+    Ok(records)
+}
@@ -0,0 +1,119 @@
+//! This module contains the soft-deletion of [`Components`](Component) within a change set.
+//!
+//! Deleting a [`Component`] only marks it (and its edges) deleted in the change set: nothing
+//! happens to the real world until the change set is applied. If the component has a resource, a
+//! [`DeleteConfirmation`] records the delete [`Actions`](Action) queued to tear that resource
+//! down when the change set is applied. [`Restoring`](Component::restore) the component before
+//! that brings it back and drops those actions again.
+
+use serde::{Deserialize, Serialize};
+
+use crate::action_prototype::ActionPrototypeContextField;
+use crate::component::{ComponentError, ComponentResult};
+use crate::{
+    Action, ActionId, ActionKind, ActionPrototype, ActionPrototypeContext, Component, ComponentId,
+    DalContext, StandardModel, WsEvent,
+};
+
+/// What deleting a [`Component`] in a change set will do once the change set is applied.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteConfirmation {
+    pub component_id: ComponentId,
+    /// The delete [`Actions`](Action) queued to tear down the resource of the [`Component`].
+    /// Empty when it has no resource, in which case deleting it has no real-world effect.
+    pub resource_teardown_action_ids: Vec<ActionId>,
+}
+
+impl DeleteConfirmation {
+    /// Whether applying the change set will tear down a real resource.
+    pub fn tears_down_resource(&self) -> bool {
+        !self.resource_teardown_action_ids.is_empty()
+    }
+}
+
+impl Component {
+    /// Marks the [`Component`] and its edges deleted in the change set of the [`DalContext`].
+    ///
+    /// The actions already queued for the component in the change set are dropped, since there is
+    /// nothing left to act upon. If the component has a resource, its delete actions are queued
+    /// instead, so that the resource is torn down when the change set is applied.
+    pub async fn delete(&mut self, ctx: &DalContext) -> ComponentResult<DeleteConfirmation> {
+        remove_actions(ctx, self.id, None).await?;
+
+        let mut resource_teardown_action_ids = Vec::new();
+        if self.resource(ctx).await?.payload.is_some() {
+            let schema_variant_id = Self::schema_variant_id(ctx, self.id).await?;
+            for prototype in ActionPrototype::find_for_context_and_kind(
+                ctx,
+                ActionKind::Delete,
+                ActionPrototypeContext::new_for_context_field(
+                    ActionPrototypeContextField::SchemaVariant(schema_variant_id),
+                ),
+            )
+            .await?
+            {
+                let action = Action::new(ctx, *prototype.id(), self.id)
+                    .await
+                    .map_err(|e| ComponentError::Action(e.to_string()))?;
+                resource_teardown_action_ids.push(*action.id());
+            }
+        }
+
+        self.delete_and_propagate(ctx).await?;
+
+        Ok(DeleteConfirmation {
+            component_id: self.id,
+            resource_teardown_action_ids,
+        })
+    }
+
+    /// Undoes [`Component::delete`] before the change set is applied: the [`Component`] and its
+    /// edges are restored, and the actions queued to tear down its resource are dropped.
+    pub async fn restore(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Option<Self>> {
+        let component = Self::restore_and_propagate(ctx, component_id).await?;
+        remove_actions(ctx, component_id, Some(ActionKind::Delete)).await?;
+        Ok(component)
+    }
+}
+
+/// Removes the [`Actions`](Action) queued for the [`Component`] in the change set, only those of
+/// the given kind if one is given.
+async fn remove_actions(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    kind: Option<ActionKind>,
+) -> ComponentResult<()> {
+    if ctx.visibility().is_head() {
+        return Ok(());
+    }
+
+    let actions = Action::find_for_change_set(ctx)
+        .await
+        .map_err(|e| ComponentError::Action(e.to_string()))?;
+    for mut action in actions {
+        if *action.component_id() != component_id {
+            continue;
+        }
+        if let Some(kind) = kind {
+            let prototype = action
+                .prototype(ctx)
+                .await
+                .map_err(|e| ComponentError::Action(e.to_string()))?;
+            if *prototype.kind() != kind {
+                continue;
+            }
+        }
+
+        action.delete_by_id(ctx).await?;
+        WsEvent::action_removed(ctx, component_id, *action.id())
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
-//! This module contains [`ComponentDiff`].
+//! This module contains [`ComponentDiff`], [`ComponentCodeDiff`] and [`ComponentCodeGenerationDiff`].
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::component::ComponentResult;
 use crate::{
@@ -121,3 +122,297 @@ impl ComponentDiff {
         })
     }
 }
+
+/// A unified diff of the generated code of a [`Component`](crate::Component) for a single
+/// [`CodeLanguage`], comparing _head_ with the current [`Visibility`](crate::Visibility).
+/// Generated by [`Self::for_component()`] and [`Self::list()`].
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentCodeDiff {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub language: CodeLanguage,
+    pub diff: String,
+}
+
+impl ComponentCodeDiff {
+    /// Returns a [`ComponentCodeDiff`] for every [`CodeLanguage`] whose generated code differs
+    /// between _head_ and the current [`Visibility`](crate::Visibility) for the given
+    /// [`Component`](crate::Component). Nothing is returned when on _head_.
+    pub async fn for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<Self>> {
+        if ctx.visibility().is_head() {
+            return Ok(Vec::new());
+        }
+        if ctx.visibility().deleted_at.is_some() {
+            return Err(ComponentError::InvalidContextForDiff);
+        }
+        let head_ctx = ctx.clone_with_head();
+
+        let component_name = match Component::get_by_id(ctx, &component_id).await? {
+            Some(component) => component.name(ctx).await?,
+            None => match Component::get_by_id(&head_ctx, &component_id).await? {
+                Some(component) => component.name(&head_ctx).await?,
+                None => return Err(ComponentError::NotFound(component_id)),
+            },
+        };
+
+        let prev = Self::code_by_language(&head_ctx, component_id).await?;
+        let curr = Self::code_by_language(ctx, component_id).await?;
+
+        let languages: BTreeSet<&String> = prev.keys().chain(curr.keys()).collect();
+        let mut diffs = Vec::new();
+        for language in languages {
+            let prev_code = prev.get(language).map(|(_, code)| code.as_str());
+            let curr_code = curr.get(language).map(|(_, code)| code.as_str());
+            if prev_code == curr_code {
+                continue;
+            }
+
+            let code_language = curr
+                .get(language)
+                .or_else(|| prev.get(language))
+                .map(|(code_language, _)| *code_language)
+                .unwrap_or(CodeLanguage::Unknown);
+
+            diffs.push(Self {
+                component_id,
+                component_name: component_name.clone(),
+                language: code_language,
+                diff: unified_diff(
+                    &format!("head/{component_name}.{language}"),
+                    &format!("change_set/{component_name}.{language}"),
+                    prev_code,
+                    curr_code,
+                ),
+            });
+        }
+
+        Ok(diffs)
+    }
+
+    /// Returns the [`ComponentCodeDiffs`](Self) of every [`Component`](crate::Component) in the
+    /// current [`Visibility`](crate::Visibility), including those that only exist on _head_
+    /// (i.e. that have been deleted in the change set).
+    pub async fn list(ctx: &DalContext) -> ComponentResult<Vec<Self>> {
+        if ctx.visibility().is_head() {
+            return Ok(Vec::new());
+        }
+        let head_ctx = ctx.clone_with_head();
+
+        let mut component_ids = BTreeSet::new();
+        for component in Component::list(ctx).await? {
+            component_ids.insert(*component.id());
+        }
+        for component in Component::list(&head_ctx).await? {
+            component_ids.insert(*component.id());
+        }
+
+        let mut diffs = Vec::new();
+        for component_id in component_ids {
+            diffs.extend(Self::for_component(ctx, component_id).await?);
+        }
+
+        Ok(diffs)
+    }
+
+    /// Collects the generated code of a [`Component`](crate::Component) keyed by
+    /// [`CodeLanguage`]. Code views sharing a language are concatenated in a stable order.
+    async fn code_by_language(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<BTreeMap<String, (CodeLanguage, String)>> {
+        let mut code_by_language: BTreeMap<String, (CodeLanguage, Vec<String>)> = BTreeMap::new();
+        if Component::get_by_id(ctx, &component_id).await?.is_some() {
+            let (code_views, _) = Component::list_code_generated(ctx, component_id).await?;
+            for code_view in code_views {
+                if let Some(code) = code_view.code {
+                    code_by_language
+                        .entry(code_view.language.to_string())
+                        .or_insert_with(|| (code_view.language, Vec::new()))
+                        .1
+                        .push(code);
+                }
+            }
+        }
+
+        Ok(code_by_language
+            .into_iter()
+            .map(|(language, (code_language, mut code))| {
+                code.sort();
+                (language, (code_language, code.join(NEWLINE)))
+            })
+            .collect())
+    }
+}
+
+/// Whether a [`CodeDiffLine`] was added, removed or left untouched by the change set.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CodeDiffLineKind {
+    Added,
+    Removed,
+    Unchanged,
+}
+
+/// A single line of a [`CodeGenerationDiff`], along with its (1-based) line numbers on _head_
+/// and in the change set, when it exists there.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeDiffLine {
+    pub kind: CodeDiffLineKind,
+    pub content: String,
+    pub head_line: Option<usize>,
+    pub change_set_line: Option<usize>,
+}
+
+/// The code generated by a single "code generation" [`Func`](crate::Func) for a
+/// [`Component`](crate::Component) on _head_ and in the change set, line by line.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeGenerationDiff {
+    pub func_name: String,
+    /// [`None`] when the code is not generated on _head_ (e.g. for a new component).
+    pub head: Option<String>,
+    /// [`None`] when the code is not generated in the change set (e.g. for a deleted component).
+    pub change_set: Option<String>,
+    pub changed: bool,
+    pub lines: Vec<CodeDiffLine>,
+}
+
+/// The structured diff of all of the generated code of a [`Component`](crate::Component),
+/// comparing _head_ with the current [`Visibility`](crate::Visibility). Generated by
+/// [`Self::new()`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentCodeGenerationDiff {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    /// One entry per "code generation" [`Func`](crate::Func), sorted by name.
+    pub code_generations: Vec<CodeGenerationDiff>,
+}
+
+impl ComponentCodeGenerationDiff {
+    pub async fn new(ctx: &DalContext, component_id: ComponentId) -> ComponentResult<Self> {
+        if ctx.visibility().deleted_at.is_some() {
+            return Err(ComponentError::InvalidContextForDiff);
+        }
+        let head_ctx = ctx.clone_with_head();
+
+        let component_name = match Component::get_by_id(ctx, &component_id).await? {
+            Some(component) => component.name(ctx).await?,
+            None => match Component::get_by_id(&head_ctx, &component_id).await? {
+                Some(component) => component.name(&head_ctx).await?,
+                None => return Err(ComponentError::NotFound(component_id)),
+            },
+        };
+
+        let (head, change_set) =
+            Component::generated_code_on_head_and_change_set(ctx, component_id).await?;
+        let func_names: BTreeSet<&String> = head.keys().chain(change_set.keys()).collect();
+
+        let code_generations = func_names
+            .into_iter()
+            .map(|func_name| {
+                let head = head.get(func_name).cloned();
+                let change_set = change_set.get(func_name).cloned();
+                CodeGenerationDiff {
+                    func_name: func_name.clone(),
+                    changed: head != change_set,
+                    lines: diff_lines(head.as_deref(), change_set.as_deref()),
+                    head,
+                    change_set,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            component_id,
+            component_name,
+            code_generations,
+        })
+    }
+}
+
+/// Diffs two (possibly missing) pieces of code line by line, numbering the lines of both sides.
+fn diff_lines(head: Option<&str>, change_set: Option<&str>) -> Vec<CodeDiffLine> {
+    let mut head_line = 0;
+    let mut change_set_line = 0;
+    diff::lines(head.unwrap_or_default(), change_set.unwrap_or_default())
+        .into_iter()
+        .map(|diff_object| match diff_object {
+            diff::Result::Left(left) => {
+                head_line += 1;
+                CodeDiffLine {
+                    kind: CodeDiffLineKind::Removed,
+                    content: left.to_owned(),
+                    head_line: Some(head_line),
+                    change_set_line: None,
+                }
+            }
+            diff::Result::Both(unchanged, _) => {
+                head_line += 1;
+                change_set_line += 1;
+                CodeDiffLine {
+                    kind: CodeDiffLineKind::Unchanged,
+                    content: unchanged.to_owned(),
+                    head_line: Some(head_line),
+                    change_set_line: Some(change_set_line),
+                }
+            }
+            diff::Result::Right(right) => {
+                change_set_line += 1;
+                CodeDiffLine {
+                    kind: CodeDiffLineKind::Added,
+                    content: right.to_owned(),
+                    head_line: None,
+                    change_set_line: Some(change_set_line),
+                }
+            }
+        })
+        .collect()
+}
+
+/// Produces a unified diff with a single hunk covering the entirety of both sides.
+fn unified_diff(
+    prev_label: &str,
+    curr_label: &str,
+    prev: Option<&str>,
+    curr: Option<&str>,
+) -> String {
+    let prev = prev.unwrap_or_default();
+    let curr = curr.unwrap_or_default();
+
+    let mut prev_count = 0;
+    let mut curr_count = 0;
+    let mut body = Vec::new();
+    for diff_object in diff::lines(prev, curr) {
+        match diff_object {
+            diff::Result::Left(left) => {
+                prev_count += 1;
+                body.push(format!("-{left}"));
+            }
+            diff::Result::Both(unchanged, _) => {
+                prev_count += 1;
+                curr_count += 1;
+                body.push(format!(" {unchanged}"));
+            }
+            diff::Result::Right(right) => {
+                curr_count += 1;
+                body.push(format!("+{right}"));
+            }
+        }
+    }
+
+    let prev_start = if prev_count == 0 { 0 } else { 1 };
+    let curr_start = if curr_count == 0 { 0 } else { 1 };
+    let mut lines = vec![
+        format!("--- {prev_label}"),
+        format!("+++ {curr_label}"),
+        format!("@@ -{prev_start},{prev_count} +{curr_start},{curr_count} @@"),
+    ];
+    lines.extend(body);
+    lines.join(NEWLINE)
+}
@@ -0,0 +1,127 @@
+//! This module contains the ability to duplicate a [`Component`], or a set of them along with the
+//! [`Edges`](crate::Edge) between them, in place on the diagram.
+
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use telemetry::prelude::*;
+use veritech_client::ResourceStatus;
+
+use crate::component::ComponentResult;
+use crate::diagram::DiagramResult;
+use crate::func::backend::js_action::ActionRunResult;
+use crate::{
+    Component, ComponentError, ComponentId, Connection, DalContext, Edge, Node, StandardModel,
+};
+
+impl Component {
+    /// Create a copy of the given [`Component`], including all of its attribute values in the
+    /// current context. The copy is named after the original with a " - Copy" suffix, starts
+    /// without a resource and its [`Node`] is placed at the given offset from the original's.
+    #[instrument(skip(ctx))]
+    pub async fn duplicate(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        offset_x: f64,
+        offset_y: f64,
+    ) -> ComponentResult<(Self, Node)> {
+        let original = Self::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        let original_node = original
+            .node(ctx)
+            .await?
+            .pop()
+            .ok_or(ComponentError::NodeNotFoundForComponent(component_id))?;
+        let schema_variant = original
+            .schema_variant(ctx)
+            .await?
+            .ok_or(ComponentError::NoSchemaVariant(component_id))?;
+        let original_name = original.name(ctx).await?;
+
+        let (component, mut node) = Self::new(ctx, &original_name, *schema_variant.id()).await?;
+
+        let x: f64 = original_node.x().parse()?;
+        let y: f64 = original_node.y().parse()?;
+        node.set_geometry(
+            ctx,
+            (x + offset_x).to_string(),
+            (y + offset_y).to_string(),
+            original_node.width(),
+            original_node.height(),
+        )
+        .await?;
+
+        component.clone_attributes_from(ctx, component_id).await?;
+
+        // The copy does not exist anywhere yet, so it must not inherit the original's resource.
+        component
+            .set_resource_raw(
+                ctx,
+                ActionRunResult {
+                    status: Some(ResourceStatus::Ok),
+                    payload: None,
+                    message: None,
+                    logs: Vec::new(),
+                    last_synced: Some(Utc::now().to_rfc3339()),
+                    plan: Vec::new(),
+                },
+                false,
+            )
+            .await?;
+
+        component
+            .set_name(ctx, Some(format!("{original_name} - Copy")))
+            .await?;
+
+        Ok((component, node))
+    }
+
+    /// Duplicate every [`Component`] in the given set with [`Self::duplicate`], then recreate the
+    /// [`Edges`](Edge) between them so that the copies are connected to each other the same way
+    /// the originals are. Edges to [`Components`](Component) outside of the set are dropped.
+    ///
+    /// Returns the copies keyed by the [`ComponentId`] of their original.
+    #[instrument(skip(ctx))]
+    pub async fn clone_subgraph(
+        ctx: &DalContext,
+        component_ids: &[ComponentId],
+        offset_x: f64,
+        offset_y: f64,
+    ) -> DiagramResult<HashMap<ComponentId, (Self, Node)>> {
+        let mut copies = HashMap::new();
+        for component_id in component_ids {
+            if copies.contains_key(component_id) {
+                continue;
+            }
+            let copy = Self::duplicate(ctx, *component_id, offset_x, offset_y).await?;
+            copies.insert(*component_id, copy);
+        }
+
+        // Every edge between two selected components is listed for both of them.
+        let mut seen_edges = HashSet::new();
+        for component_id in component_ids {
+            for edge in Edge::list_for_component(ctx, *component_id).await? {
+                if !seen_edges.insert(*edge.id()) {
+                    continue;
+                }
+                if let (Some((_, tail_node)), Some((_, head_node))) = (
+                    copies.get(&edge.tail_component_id()),
+                    copies.get(&edge.head_component_id()),
+                ) {
+                    Connection::new(
+                        ctx,
+                        *tail_node.id(),
+                        edge.tail_socket_id(),
+                        *head_node.id(),
+                        edge.head_socket_id(),
+                        *edge.kind(),
+                        *edge.creation_source(),
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        Ok(copies)
+    }
+}
@@ -0,0 +1,108 @@
+//! This module contains the free-form markdown notes of a [`Component`], stored in the
+//! "/root/si/notes" [`Prop`](crate::Prop), and the means to render them.
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use telemetry::prelude::*;
+
+use crate::component::{ComponentError, ComponentResult};
+use crate::schema::variant::root_prop::SiPropChild;
+use crate::{AttributeContextBuilder, AttributeValue, Component, DalContext, StandardModel};
+
+impl Component {
+    /// Returns the markdown notes documenting [`self`](Self), if any were written.
+    ///
+    /// Mutate this with [`Self::set_notes()`].
+    pub async fn notes(&self, ctx: &DalContext) -> ComponentResult<Option<String>> {
+        let schema_variant_id = Self::schema_variant_id(ctx, self.id).await?;
+        let notes_attribute_value = Self::find_si_child_attribute_value(
+            ctx,
+            self.id,
+            schema_variant_id,
+            SiPropChild::Notes,
+        )
+        .await?;
+        let notes = notes_attribute_value
+            .get_value(ctx)
+            .await?
+            .map(serde_json::from_value)
+            .transpose()?;
+        Ok(notes)
+    }
+
+    /// Sets the field corresponding to "/root/si/notes" for the [`Component`]. Empty notes are
+    /// stored as unset.
+    #[instrument(skip(ctx, notes))]
+    pub async fn set_notes(&self, ctx: &DalContext, notes: Option<String>) -> ComponentResult<()> {
+        let notes = notes.filter(|notes| !notes.trim().is_empty());
+
+        let schema_variant_id = Self::schema_variant_id(ctx, self.id).await?;
+        let notes_attribute_value = Self::find_si_child_attribute_value(
+            ctx,
+            self.id,
+            schema_variant_id,
+            SiPropChild::Notes,
+        )
+        .await?;
+
+        // Like the type, the notes start out as the schema variant default and need a
+        // component-specific context the first time they're written.
+        let attribute_context = if notes_attribute_value.context.is_component_unset() {
+            AttributeContextBuilder::from(notes_attribute_value.context)
+                .set_component_id(self.id)
+                .to_context()?
+        } else {
+            notes_attribute_value.context
+        };
+
+        let si_attribute_value = notes_attribute_value
+            .parent_attribute_value(ctx)
+            .await?
+            .ok_or_else(|| {
+                ComponentError::ParentAttributeValueNotFound(*notes_attribute_value.id())
+            })?;
+        AttributeValue::update_for_context(
+            ctx,
+            *notes_attribute_value.id(),
+            Some(*si_attribute_value.id()),
+            attribute_context,
+            notes.map(serde_json::Value::String),
+            None,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Render markdown notes to HTML. Raw HTML in the notes is escaped rather than passed through
+/// and script links are dropped, so the result can be inserted into the page as is.
+pub fn render_notes(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) => Event::Text(raw),
+        Event::Start(Tag::Link(kind, dest, title)) => {
+            Event::Start(Tag::Link(kind, safe_destination(dest), title))
+        }
+        Event::Start(Tag::Image(kind, dest, title)) => {
+            Event::Start(Tag::Image(kind, safe_destination(dest), title))
+        }
+        event => event,
+    });
+
+    let mut rendered = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut rendered, events);
+    rendered
+}
+
+fn safe_destination(dest: CowStr<'_>) -> CowStr<'_> {
+    let scheme = dest.trim_start().to_ascii_lowercase();
+    if scheme.starts_with("javascript:")
+        || scheme.starts_with("vbscript:")
+        || scheme.starts_with("data:")
+    {
+        CowStr::Borrowed("")
+    } else {
+        dest
+    }
+}
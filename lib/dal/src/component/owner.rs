@@ -0,0 +1,132 @@
+//! This module contains [`ComponentOwner`], which records who is responsible for a
+//! [`Component`] (or frame), and the [`notifications`](OwnerNotificationPayload) that are routed
+//! to that owner instead of being broadcast to the whole workspace.
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display};
+
+use crate::component::ComponentResult;
+use crate::standard_model::TypeHint;
+use crate::{
+    standard_model, Component, ComponentId, DalContext, StandardModel, UserPk, WsEvent,
+    WsEventResult, WsPayload,
+};
+
+/// The owner of a [`Component`]: either a single user or a named team.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ComponentOwner {
+    #[serde(rename_all = "camelCase")]
+    Team { name: String },
+    #[serde(rename_all = "camelCase")]
+    User { user_pk: UserPk },
+}
+
+impl ComponentOwner {
+    /// The NATS subject token identifying this owner, e.g. "user.<pk>" or "team.<name>".
+    pub fn subject_token(&self) -> String {
+        match self {
+            Self::Team { name } => {
+                let name: String = name
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                format!("team.{name}")
+            }
+            Self::User { user_pk } => format!("user.{user_pk}"),
+        }
+    }
+}
+
+/// The reason an owner is being notified about one of their [`Components`](Component).
+#[remain::sorted]
+#[derive(AsRefStr, Deserialize, Serialize, Debug, Display, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum OwnerNotificationKind {
+    QualificationFailed,
+    ResourceDrifted,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerNotificationPayload {
+    pub component_id: ComponentId,
+    pub owner: ComponentOwner,
+    pub kind: OwnerNotificationKind,
+    pub message: String,
+}
+
+impl Component {
+    pub fn owner(&self) -> Option<&ComponentOwner> {
+        self.owner.as_ref()
+    }
+
+    pub async fn set_owner(
+        &mut self,
+        ctx: &DalContext,
+        owner: Option<ComponentOwner>,
+    ) -> ComponentResult<()> {
+        let value = owner.as_ref().map(serde_json::to_value).transpose()?;
+        let updated_at = standard_model::update(
+            ctx,
+            "components",
+            "owner",
+            self.id(),
+            &value,
+            TypeHint::JsonB,
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.owner = owner;
+        Ok(())
+    }
+
+    /// Publishes an [`OwnerNotificationPayload`] to the owner of the [`Component`], if it has
+    /// one. Returns whether a notification was sent.
+    pub async fn notify_owner(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        kind: OwnerNotificationKind,
+        message: impl Into<String>,
+    ) -> ComponentResult<bool> {
+        let owner = match Self::get_by_id(ctx, &component_id)
+            .await?
+            .and_then(|component| component.owner)
+        {
+            Some(owner) => owner,
+            None => return Ok(false),
+        };
+
+        WsEvent::owner_notification(
+            ctx,
+            OwnerNotificationPayload {
+                component_id,
+                owner: owner.clone(),
+                kind,
+                message: message.into(),
+            },
+        )
+        .await?
+        .publish_to_owner_on_commit(ctx, &owner)
+        .await?;
+
+        Ok(true)
+    }
+}
+
+impl WsEvent {
+    pub async fn owner_notification(
+        ctx: &DalContext,
+        payload: OwnerNotificationPayload,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::OwnerNotification(payload)).await
+    }
+}
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use telemetry::prelude::*;
 use veritech_client::ResourceStatus;
 
 use crate::attribute::context::AttributeContextBuilder;
@@ -12,8 +13,8 @@ use crate::func::binding_return_value::FuncBindingReturnValue;
 use crate::ws_event::WsEvent;
 use crate::{
     func::backend::js_action::ActionRunResult, ActionKind, ActionPrototype, ActionPrototypeContext,
-    AttributeReadContext, Component, ComponentError, ComponentId, DalContext, SchemaVariant,
-    StandardModel, WsPayload,
+    AttributeReadContext, Component, ComponentError, ComponentId, DalContext, ResourceIdentifier,
+    SchemaVariant, StandardModel, WsPayload,
 };
 use crate::{RootPropChild, WsEventResult};
 
@@ -121,6 +122,8 @@ impl Component {
             .await?
             .ok_or_else(|| AttributeValueError::ParentNotFound(*resource_attribute_value.id()))?;
 
+        let identifier = self.resource_identifier(ctx, &result).await?;
+
         let update_attribute_context =
             AttributeContextBuilder::from(resource_attribute_value.context)
                 .set_component_id(self.id)
@@ -135,9 +138,60 @@ impl Component {
             None,
         )
         .await?;
+
+        // Resources that are already modeled elsewhere are still recorded on the component, but
+        // the first component keeps the identifier.
+        match identifier {
+            Some(identifier) => {
+                match ResourceIdentifier::find_conflict(ctx, self.id, &identifier).await? {
+                    Some(existing_component_id) => warn!(
+                        %identifier,
+                        component_id = %self.id,
+                        %existing_component_id,
+                        "resource is already modeled by another component"
+                    ),
+                    None => {
+                        ResourceIdentifier::register(ctx, self.id, &identifier).await?;
+                    }
+                }
+            }
+            None => ResourceIdentifier::unregister_component(ctx, self.id).await?,
+        }
+
         Ok(true)
     }
 
+    /// Read the canonical identifier of the resource in `result`, if the
+    /// [`Schema`](crate::Schema) of [`self`](Self) says where to find it.
+    pub async fn resource_identifier(
+        &self,
+        ctx: &DalContext,
+        result: &ActionRunResult,
+    ) -> ComponentResult<Option<String>> {
+        Ok(match self.schema(ctx).await? {
+            Some(schema) => {
+                ResourceIdentifier::identifier_from_payload(&schema, result.payload.as_ref())
+            }
+            None => None,
+        })
+    }
+
+    /// Find the other [`Component`] already modeling the resource in `result`, if any, along with
+    /// the identifier of the resource.
+    pub async fn resource_identifier_conflict(
+        &self,
+        ctx: &DalContext,
+        result: &ActionRunResult,
+    ) -> ComponentResult<Option<(String, ComponentId)>> {
+        let identifier = match self.resource_identifier(ctx, result).await? {
+            Some(identifier) => identifier,
+            None => return Ok(None),
+        };
+        Ok(ResourceIdentifier::find_conflict(ctx, self.id, &identifier)
+            .await?
+            .map(|existing_component_id| (identifier, existing_component_id)))
+    }
+
     pub async fn act(&self, ctx: &DalContext, action: ActionKind) -> ComponentResult<()> {
         let schema_variant = self
             .schema_variant(ctx)
@@ -0,0 +1,320 @@
+//! This module contains [`ComponentSearch`], which finds [`Components`](Component) by the name of
+//! their [`Schema`](crate::Schema), a substring of their name, the status of their qualifications
+//! and the values of their props.
+//!
+//! Everything but the prop values is filtered in the database, against the qualification summary
+//! kept up to date for every [`Component`]. Prop values are then checked against the
+//! [`ComponentView`] of the remaining [`Components`](Component), which is why the results are
+//! paginated last.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::component::view::ComponentView;
+use crate::component::{ComponentError, ComponentResult};
+use crate::qualification::QualificationSubCheckStatus;
+use crate::{Component, ComponentId, DalContext};
+
+const SEARCH: &str = include_str!("../queries/component/search.sql");
+
+/// The number of results in a page when [`ComponentSearch::limit`] is unset.
+pub const COMPONENT_SEARCH_DEFAULT_LIMIT: usize = 50;
+/// The largest number of results in a page.
+pub const COMPONENT_SEARCH_MAX_LIMIT: usize = 500;
+
+/// How a [`PropValueFilter`] compares the value of a prop.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PropValueFilterOperator {
+    Equals,
+    NotEquals,
+}
+
+impl PropValueFilterOperator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Equals => "==",
+            Self::NotEquals => "!=",
+        }
+    }
+}
+
+/// A condition on the value of a prop, written as `<path> == <value>` or `<path> != <value>`
+/// (e.g. `region == us-east-1`).
+///
+/// A path starting with "/" is read from "/root" (e.g. "/si/name" or "/root/si/name"), any other
+/// path from "/root/domain" (e.g. "region" or "tags/Name"). Values are compared as they are
+/// rendered in the attributes panel: strings without their quotes and everything else as JSON.
+/// An unset prop is never equal to a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropValueFilter {
+    pointer: String,
+    operator: PropValueFilterOperator,
+    value: String,
+}
+
+impl PropValueFilter {
+    pub fn new(
+        path: impl AsRef<str>,
+        operator: PropValueFilterOperator,
+        value: impl Into<String>,
+    ) -> Self {
+        let path = path.as_ref().trim();
+        let pointer = match path.strip_prefix('/') {
+            Some(path) => {
+                let path = path.strip_prefix("root").unwrap_or(path);
+                format!("/{}", path.trim_start_matches('/'))
+            }
+            None => format!("/domain/{path}"),
+        };
+        Self {
+            pointer,
+            operator,
+            value: value.into(),
+        }
+    }
+
+    /// The JSON pointer of the prop in the properties of a [`ComponentView`].
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    pub fn operator(&self) -> PropValueFilterOperator {
+        self.operator
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Whether the properties of a [`ComponentView`] meet this condition.
+    pub fn matches(&self, properties: &Value) -> bool {
+        let equal = match properties.pointer(&self.pointer) {
+            None | Some(Value::Null) => false,
+            Some(Value::String(value)) => *value == self.value,
+            Some(value) => value.to_string() == self.value,
+        };
+        match self.operator {
+            PropValueFilterOperator::Equals => equal,
+            PropValueFilterOperator::NotEquals => !equal,
+        }
+    }
+}
+
+impl FromStr for PropValueFilter {
+    type Err = ComponentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "!=" is looked for first, since "==" would otherwise never match it.
+        for operator in [
+            PropValueFilterOperator::NotEquals,
+            PropValueFilterOperator::Equals,
+        ] {
+            if let Some((path, value)) = s.split_once(operator.as_str()) {
+                if path.trim().is_empty() {
+                    break;
+                }
+                return Ok(Self::new(path, operator, value.trim()));
+            }
+        }
+        Err(ComponentError::InvalidPropValueFilter(s.to_owned()))
+    }
+}
+
+impl fmt::Display for PropValueFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "/root{} {} {}",
+            self.pointer,
+            self.operator.as_str(),
+            self.value
+        )
+    }
+}
+
+/// The filters and page of a search for [`Components`](Component). Unset filters match every
+/// [`Component`] and archived [`Components`](Component) are never found.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentSearch {
+    /// The exact name of the [`Schema`](crate::Schema) of the [`Component`].
+    pub schema_name: Option<String>,
+    /// A case-insensitive substring of the name of the [`Component`].
+    pub name: Option<String>,
+    /// The overall status of the qualifications of the [`Component`]:
+    /// [`Unknown`](QualificationSubCheckStatus::Unknown) for a [`Component`] without any.
+    pub qualification_status: Option<QualificationSubCheckStatus>,
+    /// Conditions on prop values that must all be met.
+    pub prop_filters: Vec<PropValueFilter>,
+    pub offset: usize,
+    /// Capped at [`COMPONENT_SEARCH_MAX_LIMIT`].
+    pub limit: Option<usize>,
+}
+
+/// A [`Component`] found by a [`ComponentSearch`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSearchResult {
+    pub component_id: ComponentId,
+    pub name: String,
+    pub schema_name: String,
+    pub qualification_status: QualificationSubCheckStatus,
+}
+
+/// A page of the [`Components`](Component) found by a [`ComponentSearch`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSearchResults {
+    pub components: Vec<ComponentSearchResult>,
+    /// The number of [`Components`](Component) found across all pages.
+    pub total: usize,
+}
+
+impl Component {
+    /// Find the [`Components`](Component) meeting every filter of a [`ComponentSearch`], ordered
+    /// by name.
+    pub async fn search(
+        ctx: &DalContext,
+        search: &ComponentSearch,
+    ) -> ComponentResult<ComponentSearchResults> {
+        let name = search.name.as_deref().map(escape_like);
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                SEARCH,
+                &[ctx.tenancy(), ctx.visibility(), &search.schema_name, &name],
+            )
+            .await?;
+
+        let mut found = Vec::new();
+        for row in rows {
+            let total: Option<i64> = row.try_get("total")?;
+            let warned: Option<i64> = row.try_get("warned")?;
+            let succeeded: Option<i64> = row.try_get("succeeded")?;
+            let failed: Option<i64> = row.try_get("failed")?;
+            let qualification_status = qualification_status(
+                total.unwrap_or_default(),
+                warned.unwrap_or_default(),
+                succeeded.unwrap_or_default(),
+                failed.unwrap_or_default(),
+            );
+            if search
+                .qualification_status
+                .map_or(false, |wanted| wanted != qualification_status)
+            {
+                continue;
+            }
+
+            let component_id: ComponentId = row.try_get("component_id")?;
+            if !search.prop_filters.is_empty() {
+                let view = ComponentView::new(ctx, component_id).await?;
+                if !search
+                    .prop_filters
+                    .iter()
+                    .all(|filter| filter.matches(&view.properties))
+                {
+                    continue;
+                }
+            }
+
+            found.push(ComponentSearchResult {
+                component_id,
+                name: row.try_get("component_name")?,
+                schema_name: row.try_get("schema_name")?,
+                qualification_status,
+            });
+        }
+
+        let total = found.len();
+        let limit = search
+            .limit
+            .unwrap_or(COMPONENT_SEARCH_DEFAULT_LIMIT)
+            .min(COMPONENT_SEARCH_MAX_LIMIT);
+        let components = found.into_iter().skip(search.offset).take(limit).collect();
+
+        Ok(ComponentSearchResults { components, total })
+    }
+}
+
+/// The overall status of the qualifications of a [`Component`], from the counts of its
+/// qualification summary: the worst status of any of them.
+fn qualification_status(
+    total: i64,
+    warned: i64,
+    succeeded: i64,
+    failed: i64,
+) -> QualificationSubCheckStatus {
+    if failed > 0 {
+        QualificationSubCheckStatus::Failure
+    } else if warned > 0 {
+        QualificationSubCheckStatus::Warning
+    } else if total > 0 && succeeded == total {
+        QualificationSubCheckStatus::Success
+    } else {
+        QualificationSubCheckStatus::Unknown
+    }
+}
+
+/// Match a substring literally with `ILIKE`, whose wildcards are `%` and `_`.
+fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prop_value_filter_from_str() {
+        let filter: PropValueFilter = "region == us-east-1".parse().expect("could not parse");
+        assert_eq!("/domain/region", filter.pointer());
+        assert_eq!(PropValueFilterOperator::Equals, filter.operator());
+        assert_eq!("us-east-1", filter.value());
+
+        let filter: PropValueFilter = "/root/si/name!=web".parse().expect("could not parse");
+        assert_eq!("/si/name", filter.pointer());
+        assert_eq!(PropValueFilterOperator::NotEquals, filter.operator());
+        assert_eq!("web", filter.value());
+
+        assert!("region".parse::<PropValueFilter>().is_err());
+        assert!("== us-east-1".parse::<PropValueFilter>().is_err());
+    }
+
+    #[test]
+    fn prop_value_filter_matches() {
+        let properties = serde_json::json!({
+            "si": { "name": "web" },
+            "domain": { "region": "us-east-1", "port": 443, "tags": { "Name": "web" } },
+        });
+
+        let matches = |filter: &str| {
+            filter
+                .parse::<PropValueFilter>()
+                .expect("could not parse")
+                .matches(&properties)
+        };
+        assert!(matches("region == us-east-1"));
+        assert!(!matches("region != us-east-1"));
+        assert!(matches("port == 443"));
+        assert!(matches("tags/Name == web"));
+        assert!(matches("/si/name == web"));
+        assert!(!matches("missing == web"));
+        assert!(matches("missing != web"));
+    }
+
+    #[test]
+    fn escape_like_wildcards() {
+        assert_eq!("50\\%\\_off\\\\", escape_like("50%_off\\"));
+    }
+}
@@ -0,0 +1,263 @@
+//! This module contains [`ComponentSnippet`], a small, shareable representation of a configured
+//! [`Component`] that can be imported into another [`ChangeSet`](crate::ChangeSet).
+
+use async_recursion::async_recursion;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use telemetry::prelude::*;
+
+use crate::component::ComponentResult;
+use crate::func::intrinsics::IntrinsicFunc;
+use crate::{
+    AttributeContextBuilder, AttributeValue, Component, ComponentError, ComponentId, DalContext,
+    Func, FuncId, Node, Prop, PropError, PropKind, RootPropChild, Schema, SchemaVariantId,
+    StandardModel,
+};
+
+/// The current version of the [`ComponentSnippet`] format.
+pub const COMPONENT_SNIPPET_VERSION: u32 = 1;
+
+/// The [`IntrinsicFunc`] used when a value is set directly on a [`Component`].
+const SETTER_FUNCS: &[IntrinsicFunc] = &[
+    IntrinsicFunc::SetArray,
+    IntrinsicFunc::SetBoolean,
+    IntrinsicFunc::SetInteger,
+    IntrinsicFunc::SetMap,
+    IntrinsicFunc::SetObject,
+    IntrinsicFunc::SetString,
+];
+
+/// A [`Component`] reduced to its [`Schema`] reference and the values set directly on it under
+/// "/root/domain". Defaults, values computed by functions (including those flowing in through
+/// sockets) and everything outside of the domain, such as secrets, are left out.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSnippet {
+    pub version: u32,
+    pub name: String,
+    pub schema_name: String,
+    pub schema_variant_name: String,
+    /// The overridden values, nested the same way as "/root/domain".
+    pub domain: serde_json::Value,
+}
+
+impl Component {
+    /// Export the given [`Component`] as a [`ComponentSnippet`].
+    #[instrument(skip(ctx))]
+    pub async fn export_snippet(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<ComponentSnippet> {
+        let component = Self::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        let schema = component
+            .schema(ctx)
+            .await?
+            .ok_or(ComponentError::NoSchema(component_id))?;
+        let schema_variant = component
+            .schema_variant(ctx)
+            .await?
+            .ok_or(ComponentError::NoSchemaVariant(component_id))?;
+
+        let mut setter_func_ids = HashSet::new();
+        for intrinsic in SETTER_FUNCS {
+            for func in Func::find_all_by_name(ctx, intrinsic.name()).await? {
+                setter_func_ids.insert(*func.id());
+            }
+        }
+
+        let domain_attribute_value = Self::root_prop_child_attribute_value_for_component(
+            ctx,
+            component_id,
+            RootPropChild::Domain,
+        )
+        .await?;
+        let domain = Self::snippet_overrides(ctx, &domain_attribute_value, &setter_func_ids)
+            .await?
+            .unwrap_or_else(|| serde_json::json!({}));
+
+        Ok(ComponentSnippet {
+            version: COMPONENT_SNIPPET_VERSION,
+            name: component.name(ctx).await?,
+            schema_name: schema.name().to_owned(),
+            schema_variant_name: schema_variant.name().to_owned(),
+            domain,
+        })
+    }
+
+    /// Create a new [`Component`] from a [`ComponentSnippet`] and apply its overrides. Overrides
+    /// for props that no longer exist on the [`SchemaVariant`](crate::SchemaVariant) are ignored,
+    /// unless the variant is strict.
+    #[instrument(skip(ctx, snippet))]
+    pub async fn import_snippet(
+        ctx: &DalContext,
+        snippet: &ComponentSnippet,
+    ) -> ComponentResult<(Self, Node)> {
+        if snippet.version != COMPONENT_SNIPPET_VERSION {
+            return Err(ComponentError::SnippetVersionUnsupported(snippet.version));
+        }
+
+        let schema = Schema::find_by_name(ctx, &snippet.schema_name).await?;
+        let schema_variant = schema
+            .find_variant_by_name(ctx, &snippet.schema_variant_name)
+            .await?
+            .ok_or_else(|| {
+                ComponentError::SnippetSchemaVariantNotFound(
+                    snippet.schema_name.clone(),
+                    snippet.schema_variant_name.clone(),
+                )
+            })?;
+
+        let (component, node) = Self::new(ctx, &snippet.name, *schema_variant.id()).await?;
+        Self::apply_domain_overrides(ctx, *component.id(), &snippet.domain).await?;
+
+        Ok((component, node))
+    }
+
+    /// Set the values in `domain`, nested the same way as "/root/domain", on an existing
+    /// [`Component`]. Values for props that do not exist are ignored, unless the
+    /// [`SchemaVariant`](crate::SchemaVariant) is strict.
+    pub async fn apply_domain_overrides(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        domain: &serde_json::Value,
+    ) -> ComponentResult<()> {
+        let strict_schema_variant_id = Self::strict_schema_variant(ctx, component_id)
+            .await?
+            .map(|schema_variant| *schema_variant.id());
+        let domain_attribute_value = Self::root_prop_child_attribute_value_for_component(
+            ctx,
+            component_id,
+            RootPropChild::Domain,
+        )
+        .await?;
+        Self::apply_snippet_overrides(
+            ctx,
+            component_id,
+            &domain_attribute_value,
+            domain,
+            strict_schema_variant_id,
+        )
+        .await
+    }
+
+    #[async_recursion]
+    async fn snippet_overrides(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+        setter_func_ids: &HashSet<FuncId>,
+    ) -> ComponentResult<Option<serde_json::Value>> {
+        let prop = Self::snippet_prop(ctx, attribute_value).await?;
+        let children = attribute_value.child_attribute_values(ctx).await?;
+
+        // Objects are walked so that only the overridden fields are kept.
+        if *prop.kind() == PropKind::Object {
+            let mut overrides = serde_json::Map::new();
+            for child in children {
+                let child_prop = Self::snippet_prop(ctx, &child).await?;
+                if let Some(value) = Self::snippet_overrides(ctx, &child, setter_func_ids).await? {
+                    overrides.insert(child_prop.name().to_owned(), value);
+                }
+            }
+            return Ok((!overrides.is_empty()).then_some(serde_json::Value::Object(overrides)));
+        }
+
+        // Scalars, maps and arrays are kept whole when they (or their elements) were set.
+        let mut set_on_component =
+            Self::snippet_is_set_on_component(ctx, attribute_value, setter_func_ids).await?;
+        for child in &children {
+            if set_on_component {
+                break;
+            }
+            set_on_component =
+                Self::snippet_is_set_on_component(ctx, child, setter_func_ids).await?;
+        }
+        if !set_on_component {
+            return Ok(None);
+        }
+        Ok(attribute_value.get_value(ctx).await?)
+    }
+
+    async fn snippet_is_set_on_component(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+        setter_func_ids: &HashSet<FuncId>,
+    ) -> ComponentResult<bool> {
+        Ok(match attribute_value.attribute_prototype(ctx).await? {
+            Some(prototype) => {
+                prototype.context.component_id().is_some()
+                    && setter_func_ids.contains(&prototype.func_id())
+            }
+            None => false,
+        })
+    }
+
+    #[async_recursion]
+    async fn apply_snippet_overrides(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        attribute_value: &AttributeValue,
+        value: &serde_json::Value,
+        strict_schema_variant_id: Option<SchemaVariantId>,
+    ) -> ComponentResult<()> {
+        let prop = Self::snippet_prop(ctx, attribute_value).await?;
+
+        if let (PropKind::Object, serde_json::Value::Object(overrides)) = (prop.kind(), value) {
+            let mut applied = HashSet::new();
+            for child in attribute_value.child_attribute_values(ctx).await? {
+                let child_prop = Self::snippet_prop(ctx, &child).await?;
+                if let Some(child_value) = overrides.get(child_prop.name()) {
+                    Self::apply_snippet_overrides(
+                        ctx,
+                        component_id,
+                        &child,
+                        child_value,
+                        strict_schema_variant_id,
+                    )
+                    .await?;
+                    applied.insert(child_prop.name().to_owned());
+                }
+            }
+            if let Some(schema_variant_id) = strict_schema_variant_id {
+                if let Some(undefined) = overrides.keys().find(|key| !applied.contains(*key)) {
+                    return Err(ComponentError::UndefinedPropPath(
+                        format!("/{}/{undefined}", prop.path().with_replaced_sep("/")),
+                        schema_variant_id,
+                    ));
+                }
+            }
+            return Ok(());
+        }
+
+        let parent_attribute_value_id = attribute_value
+            .parent_attribute_value(ctx)
+            .await?
+            .map(|parent| *parent.id());
+        let context = AttributeContextBuilder::new()
+            .set_prop_id(*prop.id())
+            .set_component_id(component_id)
+            .to_context()?;
+        AttributeValue::update_for_context(
+            ctx,
+            *attribute_value.id(),
+            parent_attribute_value_id,
+            context,
+            Some(value.clone()),
+            None,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn snippet_prop(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+    ) -> ComponentResult<Prop> {
+        let prop_id = attribute_value.context.prop_id();
+        Ok(Prop::get_by_id(ctx, &prop_id)
+            .await?
+            .ok_or_else(|| PropError::NotFound(prop_id, *ctx.visibility()))?)
+    }
+}
@@ -0,0 +1,50 @@
+//! This module contains the checks that enforce the `strict` flag of a
+//! [`SchemaVariant`]: writes to a [`Component`] of a strict variant must target props that are
+//! defined on that variant.
+
+use crate::component::ComponentResult;
+use crate::{
+    Component, ComponentError, ComponentId, DalContext, Prop, PropError, PropId, SchemaVariant,
+    StandardModel,
+};
+
+impl Component {
+    /// Returns the [`SchemaVariant`] of the [`Component`] if that variant is strict.
+    pub async fn strict_schema_variant(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Option<SchemaVariant>> {
+        let component = Self::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        let schema_variant = component
+            .schema_variant(ctx)
+            .await?
+            .ok_or(ComponentError::NoSchemaVariant(component_id))?;
+        Ok(schema_variant.strict().then_some(schema_variant))
+    }
+
+    /// Ensure that the [`Prop`] is defined on the [`SchemaVariant`] of the [`Component`] if that
+    /// variant is strict.
+    pub async fn ensure_prop_defined(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        prop_id: PropId,
+    ) -> ComponentResult<()> {
+        let schema_variant = match Self::strict_schema_variant(ctx, component_id).await? {
+            Some(schema_variant) => schema_variant,
+            None => return Ok(()),
+        };
+
+        let prop = Prop::get_by_id(ctx, &prop_id)
+            .await?
+            .ok_or_else(|| PropError::NotFound(prop_id, *ctx.visibility()))?;
+        if prop.schema_variant_id() != *schema_variant.id() {
+            return Err(ComponentError::PropNotDefinedForStrictVariant(
+                prop_id,
+                *schema_variant.id(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! This module contains the naming side of [`Subsystems`](crate::ComponentType::Subsystem).
+//!
+//! A subsystem is a configuration frame that also namespaces the "/root/si/name" of the
+//! [`Components`](Component) inside of it: a generated name (e.g. "Docker Image 1234", see
+//! [`generate_name_from_schema_name`](crate::generate_name_from_schema_name)) is prefixed with the
+//! names of the subsystems around it, outermost first (e.g. "platform/web/Docker Image 1234").
+//! Names chosen by users are left alone.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::component::{ComponentError, ComponentResult};
+use crate::{Component, ComponentId, ComponentType, DalContext, Edge, StandardModel};
+
+/// The separator between the names of the subsystems and the name of a [`Component`].
+pub const SUBSYSTEM_NAMESPACE_SEPARATOR: char = '/';
+
+/// Returns the generated part of the name (i.e. without any namespace), if the name is one that
+/// was generated for the given schema name.
+pub fn generated_base_name<'a>(name: &'a str, schema_name: &str) -> Option<&'a str> {
+    let (prefix, unique_id) = name.rsplit_once(' ')?;
+    if unique_id.is_empty() || !unique_id.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let namespace = prefix.strip_suffix(schema_name)?;
+    if namespace.is_empty() || namespace.ends_with(SUBSYSTEM_NAMESPACE_SEPARATOR) {
+        Some(&name[namespace.len()..])
+    } else {
+        None
+    }
+}
+
+impl Component {
+    /// The namespace given to the [`Component`] by the subsystems it is in, outermost first, if
+    /// it is in any.
+    pub async fn subsystem_namespace(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Option<String>> {
+        let mut names = Vec::new();
+        let mut seen = HashSet::from([component_id]);
+        let mut current = component_id;
+        while let Some(parent_id) = Edge::get_parent_for_component(ctx, current).await? {
+            if !seen.insert(parent_id) {
+                break;
+            }
+            let parent = Component::get_by_id(ctx, &parent_id)
+                .await?
+                .ok_or(ComponentError::NotFound(parent_id))?;
+            if parent.get_type(ctx).await? == ComponentType::Subsystem {
+                names.push(parent.name(ctx).await?);
+            }
+            current = parent_id;
+        }
+
+        if names.is_empty() {
+            return Ok(None);
+        }
+        names.reverse();
+        Ok(Some(names.join(&SUBSYSTEM_NAMESPACE_SEPARATOR.to_string())))
+    }
+
+    /// Namespaces the generated names of the [`Component`] and of everything inside of it after
+    /// it moved in or out of a subsystem, and returns the [`ComponentIds`](ComponentId) that were
+    /// renamed.
+    pub async fn apply_subsystem_namespace(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentResult<Vec<ComponentId>> {
+        let mut renamed = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([component_id]);
+        while let Some(component_id) = queue.pop_front() {
+            if !seen.insert(component_id) {
+                continue;
+            }
+            queue.extend(Edge::list_children_for_component(ctx, component_id).await?);
+
+            let component = Component::get_by_id(ctx, &component_id)
+                .await?
+                .ok_or(ComponentError::NotFound(component_id))?;
+            let schema = match component.schema(ctx).await? {
+                Some(schema) => schema,
+                None => continue,
+            };
+            let name = component.name(ctx).await?;
+            let base_name = match generated_base_name(&name, schema.name()) {
+                Some(base_name) => base_name,
+                None => continue,
+            };
+
+            let namespaced = match Self::subsystem_namespace(ctx, component_id).await? {
+                Some(namespace) => {
+                    format!("{namespace}{SUBSYSTEM_NAMESPACE_SEPARATOR}{base_name}")
+                }
+                None => base_name.to_owned(),
+            };
+            if namespaced != name {
+                component.set_name(ctx, Some(namespaced)).await?;
+                renamed.push(component_id);
+            }
+        }
+
+        Ok(renamed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_generated_names_have_a_base_name() {
+        assert_eq!(
+            Some("Docker Image 1234"),
+            generated_base_name("Docker Image 1234", "Docker Image")
+        );
+        assert_eq!(
+            Some("Docker Image 1234"),
+            generated_base_name("platform/web/Docker Image 1234", "Docker Image")
+        );
+        assert_eq!(None, generated_base_name("my image", "Docker Image"));
+        assert_eq!(
+            None,
+            generated_base_name("Not a Docker Image 1234", "Docker Image")
+        );
+        assert_eq!(
+            None,
+            generated_base_name("Docker Image abcd", "Docker Image")
+        );
+    }
+}
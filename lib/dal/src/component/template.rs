@@ -0,0 +1,138 @@
+//! This module contains the ability to stamp out many copies of a [`Component`] (or of a frame
+//! along with everything inside of it) from a template, e.g. to stand up a fleet of similar
+//! servers at once.
+//!
+//! Every copy is numbered. The number replaces [`TEMPLATE_INDEX_PLACEHOLDER`] in the name pattern
+//! of the copies (e.g. "web-{{index}}" names them "web-1", "web-2", ...) and in the strings of
+//! their domain overrides.
+
+use serde_json::Value;
+use std::collections::{HashSet, VecDeque};
+use telemetry::prelude::*;
+
+use crate::diagram::DiagramResult;
+use crate::{Component, ComponentError, ComponentId, DalContext, Edge, Node, StandardModel};
+
+/// Replaced by the number of the copy in name patterns and overrides.
+pub const TEMPLATE_INDEX_PLACEHOLDER: &str = "{{index}}";
+
+/// Renders a name pattern (or any other string) for the copy with the given number.
+pub fn render_template(pattern: &str, index: usize) -> String {
+    pattern.replace(TEMPLATE_INDEX_PLACEHOLDER, &index.to_string())
+}
+
+/// Renders every string found in the overrides for the copy with the given number.
+pub fn render_template_overrides(overrides: &Value, index: usize) -> Value {
+    match overrides {
+        Value::String(string) => Value::String(render_template(string, index)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_template_overrides(item, index))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), render_template_overrides(value, index)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+impl Component {
+    /// The [`Component`] and everything inside of it, with the [`Component`] first.
+    pub async fn template_subtree(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> DiagramResult<Vec<ComponentId>> {
+        let mut subtree = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([component_id]);
+        while let Some(component_id) = queue.pop_front() {
+            if seen.insert(component_id) {
+                subtree.push(component_id);
+                queue.extend(Edge::list_children_for_component(ctx, component_id).await?);
+            }
+        }
+        Ok(subtree)
+    }
+
+    /// Creates the copy with the given number of the template [`Component`] and of everything
+    /// inside of it, placed at the given offset. The copy of the template is named after the
+    /// pattern and gets the domain overrides; the copies of its children keep their names.
+    ///
+    /// Returns the copy of the template and its [`Node`], along with the [`ComponentIds`](ComponentId)
+    /// of every copy made.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(ctx, overrides))]
+    pub async fn instantiate_template(
+        ctx: &DalContext,
+        template_id: ComponentId,
+        index: usize,
+        name_pattern: &str,
+        overrides: &Value,
+        offset_x: f64,
+        offset_y: f64,
+    ) -> DiagramResult<(Self, Node, Vec<ComponentId>)> {
+        let subtree = Self::template_subtree(ctx, template_id).await?;
+        let mut copies = Self::clone_subgraph(ctx, &subtree, offset_x, offset_y).await?;
+
+        let mut component_ids = Vec::with_capacity(subtree.len());
+        for original_id in &subtree {
+            if let Some((copy, _)) = copies.get(original_id) {
+                if *original_id != template_id {
+                    let original = Self::get_by_id(ctx, original_id)
+                        .await?
+                        .ok_or(ComponentError::NotFound(*original_id))?;
+                    copy.set_name(ctx, Some(original.name(ctx).await?)).await?;
+                }
+                component_ids.push(*copy.id());
+            }
+        }
+
+        let (component, node) = copies
+            .remove(&template_id)
+            .ok_or(ComponentError::NotFound(template_id))?;
+        component
+            .set_name(ctx, Some(render_template(name_pattern, index)))
+            .await?;
+        if !overrides.is_null() {
+            Self::apply_domain_overrides(
+                ctx,
+                *component.id(),
+                &render_template_overrides(overrides, index),
+            )
+            .await?;
+        }
+
+        Ok((component, node, component_ids))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_index_everywhere() {
+        assert_eq!("web-3", render_template("web-{{index}}", 3));
+        assert_eq!("web", render_template("web", 3));
+        assert_eq!(
+            serde_json::json!({
+                "hostname": "web-2.internal",
+                "ports": [80, "2"],
+                "enabled": true,
+            }),
+            render_template_overrides(
+                &serde_json::json!({
+                    "hostname": "web-{{index}}.internal",
+                    "ports": [80, "{{index}}"],
+                    "enabled": true,
+                }),
+                2,
+            )
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Validations run wherever the [`Component`](crate::Component) is edited, against the
+//! "validation_format" of each [`Prop`](crate::Prop). Their results are cached by the
+//! [`content generation`](crate::AttributeValue::content_generation) of each value, so only
+//! values that changed are validated again. This module contains the means to ask for a
+//! [`Component`](crate::Component) to be validated from scratch anyway.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ChangeSetPk, ComponentId, DalContext, WsEvent, WsEventResult, WsPayload};
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentRevalidationRequestedPayload {
+    component_id: ComponentId,
+    change_set_pk: ChangeSetPk,
+}
+
+impl WsEvent {
+    /// Tells clients to drop their cached validation results for a
+    /// [`Component`](crate::Component) and validate all of its values again.
+    pub async fn component_revalidation_requested(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ComponentRevalidationRequested(ComponentRevalidationRequestedPayload {
+                component_id,
+                change_set_pk: ctx.visibility().change_set_pk,
+            }),
+        )
+        .await
+    }
+}
@@ -0,0 +1,55 @@
+//! This module contains "confirmations", which compare a [`Component`](crate::Component)'s desired
+//! attribute values (its "/root/domain") with its last known resource state (its "/root/resource")
+//! and recommend actions to bring the two back in line when they have drifted apart.
+//!
+//! A [`ConfirmationPrototype`](prototype::ConfirmationPrototype) joins a confirmation
+//! [`Func`](crate::Func) to a [`SchemaVariant`](crate::SchemaVariant) (or a specific
+//! [`Component`](crate::Component)) and a [`ConfirmationResolver`](resolver::ConfirmationResolver)
+//! records the latest outcome of running it for a [`Component`](crate::Component).
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+
+use crate::ActionKind;
+
+pub mod prototype;
+pub mod resolver;
+
+/// The actions a confirmation [`Func`](crate::Func) can recommend.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum RecommendedAction {
+    /// The resource does not exist yet and should be created.
+    Create,
+    /// The resource exists, but the [`Component`](crate::Component) has been deleted.
+    Delete,
+    /// The resource exists, but no longer matches the desired attribute values.
+    Update,
+}
+
+impl From<RecommendedAction> for ActionKind {
+    fn from(value: RecommendedAction) -> Self {
+        match value {
+            RecommendedAction::Create => ActionKind::Create,
+            RecommendedAction::Delete => ActionKind::Delete,
+            // Updates are modeled as the "other" actions of a schema variant.
+            RecommendedAction::Update => ActionKind::Other,
+        }
+    }
+}
+
+/// The value a confirmation [`Func`](crate::Func) must return.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationOutput {
+    /// Whether or not the resource matches the desired attribute values.
+    pub success: bool,
+    /// The actions needed to bring the resource in line with the desired attribute values.
+    #[serde(default)]
+    pub recommended_actions: Vec<RecommendedAction>,
+    pub message: Option<String>,
+}
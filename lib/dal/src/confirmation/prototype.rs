@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, ComponentId, DalContext,
+    Func, FuncError, FuncId, HistoryEventError, SchemaVariantId, StandardModel, StandardModelError,
+    Tenancy, Timestamp, TransactionsError, Visibility,
+};
+
+const LIST_FOR_COMPONENT: &str =
+    include_str!("../queries/confirmation_prototype/list_for_component.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ConfirmationPrototypeError {
+    #[error("func: {0}")]
+    Func(#[from] FuncError),
+    #[error("history event: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("pg: {0}")]
+    Pg(#[from] PgError),
+    #[error("standard model error: {0}")]
+    StandardModelError(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ConfirmationPrototypeResult<T> = Result<T, ConfirmationPrototypeError>;
+
+/// The context a [`ConfirmationPrototype`] applies to. Prototypes with an unset
+/// [`ComponentId`] apply to every [`Component`](crate::Component) of the
+/// [`SchemaVariant`](crate::SchemaVariant).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationPrototypeContext {
+    pub component_id: ComponentId,
+    pub schema_variant_id: SchemaVariantId,
+}
+
+impl ConfirmationPrototypeContext {
+    pub fn new_for_schema_variant(schema_variant_id: SchemaVariantId) -> Self {
+        Self {
+            component_id: ComponentId::NONE,
+            schema_variant_id,
+        }
+    }
+}
+
+pk!(ConfirmationPrototypePk);
+pk!(ConfirmationPrototypeId);
+
+/// A ConfirmationPrototype joins a confirmation `FuncId` to a context (`ComponentId` and
+/// `SchemaVariantId`). Prototypes are unique by name within a context and a
+/// [`Component`](crate::Component) specific prototype replaces the
+/// [`SchemaVariant`](crate::SchemaVariant) one of the same name.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmationPrototype {
+    pk: ConfirmationPrototypePk,
+    id: ConfirmationPrototypeId,
+    name: String,
+    func_id: FuncId,
+    component_id: ComponentId,
+    schema_variant_id: SchemaVariantId,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ConfirmationPrototype,
+    pk: ConfirmationPrototypePk,
+    id: ConfirmationPrototypeId,
+    table_name: "confirmation_prototypes",
+    history_event_label_base: "confirmation_prototype",
+    history_event_message_name: "Confirmation Prototype"
+}
+
+impl ConfirmationPrototype {
+    #[instrument(skip_all)]
+    pub async fn upsert(
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: &str,
+        context: ConfirmationPrototypeContext,
+    ) -> ConfirmationPrototypeResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM confirmation_prototype_upsert_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &func_id,
+                    &name,
+                    &context.component_id,
+                    &context.schema_variant_id,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    /// List the [`ConfirmationPrototypes`](Self) that apply to a [`Component`](crate::Component)
+    /// of the given [`SchemaVariant`](crate::SchemaVariant).
+    pub async fn list_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        schema_variant_id: SchemaVariantId,
+    ) -> ConfirmationPrototypeResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_COMPONENT,
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &component_id,
+                    &schema_variant_id,
+                ],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    pub async fn func(&self, ctx: &DalContext) -> ConfirmationPrototypeResult<Func> {
+        let func = Func::get_by_id(ctx, &self.func_id)
+            .await?
+            .ok_or(FuncError::NotFound(self.func_id))?;
+        Ok(func)
+    }
+
+    standard_model_accessor!(name, String, ConfirmationPrototypeResult);
+    standard_model_accessor!(func_id, Pk(FuncId), ConfirmationPrototypeResult);
+    standard_model_accessor!(component_id, Pk(ComponentId), ConfirmationPrototypeResult);
+    standard_model_accessor!(
+        schema_variant_id,
+        Pk(SchemaVariantId),
+        ConfirmationPrototypeResult
+    );
+
+    pub fn context(&self) -> ConfirmationPrototypeContext {
+        ConfirmationPrototypeContext {
+            component_id: self.component_id,
+            schema_variant_id: self.schema_variant_id,
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::component::view::ComponentViewError;
+use crate::confirmation::prototype::{
+    ConfirmationPrototype, ConfirmationPrototypeError, ConfirmationPrototypeId,
+};
+use crate::confirmation::{ConfirmationOutput, RecommendedAction};
+use crate::func::before::before_funcs_for_component;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, AttributeValueId, Component,
+    ComponentId, ComponentView, DalContext, FuncBinding, FuncBindingError, FuncBindingId,
+    FuncError, FuncId, HistoryEventError, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, Visibility,
+};
+
+const LIST_FOR_COMPONENT: &str =
+    include_str!("../queries/confirmation_resolver/list_for_component.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ConfirmationResolverError {
+    #[error("component error: {0}")]
+    Component(String),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
+    #[error("component view error: {0}")]
+    ComponentView(#[from] ComponentViewError),
+    #[error("confirmation prototype error: {0}")]
+    ConfirmationPrototype(#[from] ConfirmationPrototypeError),
+    #[error("func error: {0}")]
+    Func(#[from] FuncError),
+    #[error("func binding error: {0}")]
+    FuncBinding(#[from] FuncBindingError),
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ConfirmationResolverResult<T> = Result<T, ConfirmationResolverError>;
+
+pk!(ConfirmationResolverPk);
+pk!(ConfirmationResolverId);
+
+/// Records the latest outcome of running a [`ConfirmationPrototype`] for a
+/// [`Component`](crate::Component).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmationResolver {
+    pk: ConfirmationResolverPk,
+    id: ConfirmationResolverId,
+    confirmation_prototype_id: ConfirmationPrototypeId,
+    component_id: ComponentId,
+    /// The [`FuncId`] of the confirmation func that was run.
+    func_id: FuncId,
+    /// The [`FuncBindingId`] of the latest execution of the confirmation func.
+    func_binding_id: FuncBindingId,
+    /// Whether or not the resource matches the desired attribute values.
+    success: bool,
+    message: Option<String>,
+    recommended_actions: Vec<RecommendedAction>,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ConfirmationResolver,
+    pk: ConfirmationResolverPk,
+    id: ConfirmationResolverId,
+    table_name: "confirmation_resolvers",
+    history_event_label_base: "confirmation_resolver",
+    history_event_message_name: "Confirmation Resolver"
+}
+
+impl ConfirmationResolver {
+    #[instrument(skip_all)]
+    async fn upsert(
+        ctx: &DalContext,
+        confirmation_prototype_id: ConfirmationPrototypeId,
+        component_id: ComponentId,
+        func_id: FuncId,
+        func_binding_id: FuncBindingId,
+        output: &ConfirmationOutput,
+    ) -> ConfirmationResolverResult<Self> {
+        let recommended_actions = serde_json::to_value(&output.recommended_actions)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM confirmation_resolver_upsert_v1($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &confirmation_prototype_id,
+                    &component_id,
+                    &func_id,
+                    &func_binding_id,
+                    &output.success,
+                    &output.message,
+                    &recommended_actions,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    /// Run every [`ConfirmationPrototype`] that applies to the [`Component`](crate::Component)
+    /// and record their outcomes. Confirmation [`Funcs`](crate::Func) receive the "domain",
+    /// "resource" and "deleted_at" subtrees of the [`Component`](crate::Component) as their input.
+    pub async fn run_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ConfirmationResolverResult<Vec<Self>> {
+        // Deleted components still need to confirm that their resource was deleted.
+        let deleted_ctx = &ctx.clone_with_delete_visibility();
+        let component = Component::get_by_id(deleted_ctx, &component_id)
+            .await?
+            .ok_or(ConfirmationResolverError::ComponentNotFound(component_id))?;
+        let schema_variant_id = Component::schema_variant_id(deleted_ctx, *component.id())
+            .await
+            .map_err(|e| ConfirmationResolverError::Component(e.to_string()))?;
+
+        let prototypes =
+            ConfirmationPrototype::list_for_component(ctx, component_id, schema_variant_id).await?;
+        if prototypes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let properties = ComponentView::new(ctx, component_id).await?.properties;
+        let args = serde_json::json!({
+            "domain": properties.get("domain").cloned().unwrap_or_default(),
+            "resource": properties.get("resource").cloned().unwrap_or_default(),
+            "deleted_at": properties.get("deleted_at").cloned().unwrap_or_default(),
+        });
+        let before = before_funcs_for_component(ctx, &component_id).await?;
+
+        let mut resolvers = Vec::with_capacity(prototypes.len());
+        for prototype in prototypes {
+            let (func_binding, func_binding_return_value) =
+                FuncBinding::create_and_execute_for_component(
+                    ctx,
+                    args.clone(),
+                    prototype.func_id(),
+                    before.clone(),
+                    component_id,
+                    AttributeValueId::NONE,
+                )
+                .await?;
+            let output = ConfirmationOutput::deserialize(
+                func_binding_return_value
+                    .value()
+                    .unwrap_or(&serde_json::Value::Null),
+            )?;
+
+            resolvers.push(
+                Self::upsert(
+                    ctx,
+                    *prototype.id(),
+                    component_id,
+                    prototype.func_id(),
+                    *func_binding.id(),
+                    &output,
+                )
+                .await?,
+            );
+        }
+
+        Ok(resolvers)
+    }
+
+    /// List the recorded [`ConfirmationResolvers`](Self) for a [`Component`](crate::Component).
+    pub async fn list_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ConfirmationResolverResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_COMPONENT,
+                &[ctx.tenancy(), ctx.visibility(), &component_id],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    standard_model_accessor!(
+        confirmation_prototype_id,
+        Pk(ConfirmationPrototypeId),
+        ConfirmationResolverResult
+    );
+    standard_model_accessor!(component_id, Pk(ComponentId), ConfirmationResolverResult);
+    standard_model_accessor!(func_id, Pk(FuncId), ConfirmationResolverResult);
+    standard_model_accessor!(
+        func_binding_id,
+        Pk(FuncBindingId),
+        ConfirmationResolverResult
+    );
+    standard_model_accessor!(success, bool, ConfirmationResolverResult);
+    standard_model_accessor!(message, Option<String>, ConfirmationResolverResult);
+
+    pub fn recommended_actions(&self) -> &[RecommendedAction] {
+        &self.recommended_actions
+    }
+}
@@ -1,6 +1,10 @@
-use std::{mem, path::PathBuf, sync::Arc};
+use std::{
+    fmt, mem,
+    path::PathBuf,
+    sync::{Arc, PoisonError},
+};
 
-use futures::Future;
+use futures::{future::BoxFuture, Future};
 use serde::{Deserialize, Serialize};
 use si_crypto::SymmetricCryptoService;
 use si_data_nats::{NatsClient, NatsError, NatsTxn};
@@ -11,12 +15,14 @@ use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
 use veritech_client::{Client as VeritechClient, CycloneEncryptionKey};
 
 use crate::{
+    attribute::context::cache::AttributeContextCache,
+    func::cache::FuncCache,
     job::{
         processor::{JobQueueProcessor, JobQueueProcessorError},
         producer::{BlockingJobError, BlockingJobResult, JobProducer},
         queue::JobQueue,
     },
-    HistoryActor, StandardModel, Tenancy, TenancyError, Visibility,
+    ChangeSetPk, HistoryActor, StandardModel, Tenancy, TenancyError, Visibility,
 };
 
 /// A context type which contains handles to common core service dependencies.
@@ -141,6 +147,13 @@ impl ConnectionState {
         matches!(self, Self::Connections(_))
     }
 
+    fn take_commit_hooks(&mut self) -> Vec<CommitHook> {
+        match self {
+            Self::Transactions(txns) => txns.commit_hooks.take(),
+            _ => Vec::new(),
+        }
+    }
+
     fn txns(&mut self) -> &mut Transactions {
         match self {
             Self::Transactions(txns) => txns,
@@ -223,6 +236,9 @@ pub struct DalContext {
     /// Determines if we should not enqueue dependent value update jobs for attribute updates in
     /// this context. Useful for builtin migrations, since we don't care about attribute values propagation then.
     no_dependent_values: bool,
+    /// Determines if lookups read through the [`AttributeContextCache`] of the current
+    /// transactions. Off by default, see [`Self::enable_attribute_context_cache()`].
+    attribute_context_cache: bool,
 }
 
 impl DalContext {
@@ -244,16 +260,10 @@ impl DalContext {
         }
     }
 
-    /// Consumes all inner transactions and committing all changes made within them.
+    /// Consumes all inner transactions and committing all changes made within them, then runs
+    /// the hooks registered with [`Self::on_commit()`].
     pub async fn commit(&self) -> Result<(), TransactionsError> {
-        if self.blocking {
-            self.blocking_commit().await?;
-        } else {
-            let mut guard = self.conns_state.lock().await;
-            *guard = guard.take().commit().await?;
-        }
-
-        Ok(())
+        self.commit_and_run_hooks(self.blocking).await
     }
 
     pub fn blocking(&self) -> bool {
@@ -264,6 +274,18 @@ impl DalContext {
         self.no_dependent_values
     }
 
+    /// Makes [`Prop`](crate::Prop) and [`InternalProvider`](crate::InternalProvider) lookups of
+    /// this context (and of the contexts cloned from it) read through the
+    /// [`AttributeContextCache`] of the current transactions. Meant for flows that fetch the same
+    /// entities over and over within one transaction.
+    pub fn enable_attribute_context_cache(&mut self) {
+        self.attribute_context_cache = true;
+    }
+
+    pub fn attribute_context_cache_enabled(&self) -> bool {
+        self.attribute_context_cache
+    }
+
     pub fn services_context(&self) -> ServicesContext {
         self.services_context.clone()
     }
@@ -273,15 +295,63 @@ impl DalContext {
     }
 
     /// Consumes all inner transactions, committing all changes made within them, and
-    /// blocks until all queued jobs have reported as finishing.
+    /// blocks until all queued jobs have reported as finishing. The hooks registered with
+    /// [`Self::on_commit()`] run afterwards.
     pub async fn blocking_commit(&self) -> Result<(), TransactionsError> {
-        let mut guard = self.conns_state.lock().await;
-
-        *guard = guard.take().blocking_commit().await?;
+        self.commit_and_run_hooks(true).await
+    }
 
+    /// Registers a hook to run exactly once, after the current transactions commit. The hook is
+    /// dropped without running if the transactions are rolled back or fail to commit, so it is
+    /// the place for side effects, like publishing a [`WsEvent`](crate::WsEvent) or enqueueing
+    /// a job, that must not happen for discarded work.
+    ///
+    /// Hooks run in the order they were registered, with a context sharing this one's
+    /// connections. Whatever a hook does through the context (including registering further
+    /// hooks) is committed once all the hooks have run.
+    pub async fn on_commit<F, Fut, E>(&self, hook: F) -> Result<(), TransactionsError>
+    where
+        F: FnOnce(DalContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Into<CommitHookError>,
+    {
+        self.txns().await?.commit_hooks.push(Box::new(move |ctx| {
+            Box::pin(async move { hook(ctx).await.map_err(Into::into) })
+        }));
         Ok(())
     }
 
+    async fn commit_and_run_hooks(&self, blocking: bool) -> Result<(), TransactionsError> {
+        loop {
+            let hooks = {
+                let mut guard = self.conns_state.lock().await;
+                let hooks = guard.take_commit_hooks();
+                *guard = if blocking {
+                    guard.take().blocking_commit().await?
+                } else {
+                    guard.take().commit().await?
+                };
+                hooks
+            };
+            if hooks.is_empty() {
+                return Ok(());
+            }
+
+            // The work the hooks were registered for is committed, so every hook gets to run
+            // even if an earlier one fails.
+            let mut first_err = None;
+            for hook in hooks {
+                if let Err(err) = hook(self.clone()).await {
+                    error!(error = ?err, "commit hook failed");
+                    first_err.get_or_insert(err);
+                }
+            }
+            if let Some(err) = first_err {
+                return Err(TransactionsError::CommitHook(err));
+            }
+        }
+    }
+
     /// Rolls all inner transactions back, discarding all changes made within them.
     ///
     /// This is equivalent to the transaction's `Drop` implementations, but provides any error
@@ -412,6 +482,24 @@ impl DalContext {
         Ok(())
     }
 
+    /// Drops the queued jobs for the given change set and, on commit, asks the job processor
+    /// to cancel the ones that were already dispatched. Used when a change set is abandoned or
+    /// applied, so its pending work stops consuming capacity.
+    pub async fn cancel_change_set_jobs(
+        &self,
+        change_set_pk: ChangeSetPk,
+    ) -> Result<(), TransactionsError> {
+        if change_set_pk == ChangeSetPk::NONE {
+            return Ok(());
+        }
+        self.txns()
+            .await?
+            .job_queue
+            .cancel_change_set_jobs(change_set_pk)
+            .await;
+        Ok(())
+    }
+
     /// Similar to `enqueue_job`, except that instead of waiting to flush the job to
     /// the processing system on `commit`, the job is immediately flushed, and the
     /// processor is expected to not return until the job has finished. Returns the
@@ -617,6 +705,7 @@ impl DalContextBuilder {
             visibility: Visibility::new_head(false),
             history_actor: HistoryActor::SystemInit,
             no_dependent_values: self.no_dependent_values,
+            attribute_context_cache: false,
         })
     }
 
@@ -634,6 +723,7 @@ impl DalContextBuilder {
             history_actor: access_builder.history_actor,
             visibility: Visibility::new_head(false),
             no_dependent_values: self.no_dependent_values,
+            attribute_context_cache: false,
         })
     }
 
@@ -651,6 +741,7 @@ impl DalContextBuilder {
             visibility: request_context.visibility,
             history_actor: request_context.history_actor,
             no_dependent_values: self.no_dependent_values,
+            attribute_context_cache: false,
         })
     }
 
@@ -687,6 +778,8 @@ impl DalContextBuilder {
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum TransactionsError {
+    #[error("commit hook error: {0}")]
+    CommitHook(CommitHookError),
     #[error(transparent)]
     JobQueueProcessor(#[from] JobQueueProcessorError),
     #[error(transparent)]
@@ -707,6 +800,36 @@ pub enum TransactionsError {
     TxnStart(&'static str),
 }
 
+/// The error a hook registered with [`DalContext::on_commit()`] can fail with.
+pub type CommitHookError = Box<dyn std::error::Error + Send + Sync>;
+
+type CommitHook =
+    Box<dyn FnOnce(DalContext) -> BoxFuture<'static, Result<(), CommitHookError>> + Send>;
+
+/// The hooks registered with [`DalContext::on_commit()`] for a set of [`Transactions`].
+#[derive(Clone, Default)]
+struct CommitHooks(Arc<std::sync::Mutex<Vec<CommitHook>>>);
+
+impl CommitHooks {
+    fn push(&self, hook: CommitHook) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(hook);
+    }
+
+    fn take(&self) -> Vec<CommitHook> {
+        mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl fmt::Debug for CommitHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.0.lock().unwrap_or_else(PoisonError::into_inner).len();
+        f.debug_tuple("CommitHooks").field(&len).finish()
+    }
+}
+
 /// A type which holds ownership over connections that can be used to start transactions.
 #[derive(Debug)]
 pub struct Connections {
@@ -762,6 +885,13 @@ pub struct Transactions {
     nats_txn: NatsTxn,
     job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
     job_queue: JobQueue,
+    /// Read-through cache of func lookups made within these transactions.
+    func_cache: FuncCache,
+    /// Opt-in read-through cache of prop and internal provider lookups made within these
+    /// transactions.
+    attribute_context_cache: AttributeContextCache,
+    /// Hooks to run once these transactions are committed.
+    commit_hooks: CommitHooks,
 }
 
 impl Transactions {
@@ -776,6 +906,9 @@ impl Transactions {
             nats_txn,
             job_processor,
             job_queue: JobQueue::new(),
+            func_cache: FuncCache::default(),
+            attribute_context_cache: AttributeContextCache::default(),
+            commit_hooks: CommitHooks::default(),
         }
     }
 
@@ -789,6 +922,22 @@ impl Transactions {
         &self.nats_txn
     }
 
+    pub(crate) fn func_cache(&self) -> &FuncCache {
+        &self.func_cache
+    }
+
+    pub(crate) fn func_cache_mut(&mut self) -> &mut FuncCache {
+        &mut self.func_cache
+    }
+
+    pub(crate) fn attribute_context_cache(&self) -> &AttributeContextCache {
+        &self.attribute_context_cache
+    }
+
+    pub(crate) fn attribute_context_cache_mut(&mut self) -> &mut AttributeContextCache {
+        &mut self.attribute_context_cache
+    }
+
     /// Consumes all inner transactions, committing all changes made within them, and returns
     /// underlying connections.
     #[instrument(
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use si_data_pg::PgError;
+use std::collections::HashSet;
 use std::num::{ParseFloatError, ParseIntError};
 use strum::{AsRefStr, Display, EnumString};
 
@@ -14,12 +15,14 @@ use crate::provider::internal::InternalProviderError;
 use crate::schema::variant::SchemaVariantError;
 use crate::socket::SocketError;
 use crate::{
-    ActionPrototypeError, AttributeContextBuilderError, AttributePrototypeArgumentError,
-    AttributeValueError, ComponentError, ComponentId, DalContext, EdgeError, NodeError, NodeId,
-    NodeKind, PropError, SchemaError, SocketId, StandardModelError,
+    ActionPrototypeError, AttributeContextBuilderError, AttributeContextError,
+    AttributePrototypeArgumentError, AttributePrototypeError, AttributeValueError, Component,
+    ComponentError, ComponentId, DalContext, EdgeError, NodeError, NodeId, NodeKind, PropError,
+    SchemaError, SocketId, StandardModel, StandardModelError, TransactionsError,
 };
 
 pub mod connection;
+pub mod socket_suggestion;
 pub(crate) mod summary_diagram;
 
 #[remain::sorted]
@@ -28,7 +31,11 @@ pub enum DiagramError {
     #[error("action prototype: {0}")]
     ActionPrototype(#[from] ActionPrototypeError),
     #[error("attribute context error: {0}")]
+    AttributeContext(#[from] AttributeContextError),
+    #[error("attribute context builder error: {0}")]
     AttributeContextBuilder(#[from] AttributeContextBuilderError),
+    #[error("attribute prototype error: {0}")]
+    AttributePrototype(#[from] AttributePrototypeError),
     #[error("attribute prototype argument error: {0}")]
     AttributePrototypeArgument(#[from] AttributePrototypeArgumentError),
     #[error("attribute prototype not found")]
@@ -91,6 +98,8 @@ pub enum DiagramError {
     StandardModel(#[from] StandardModelError),
     #[error("summary diagram error: {0}")]
     SummaryDiagram(String),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
 }
 
 pub type DiagramResult<T> = Result<T, DiagramError>;
@@ -108,6 +117,19 @@ pub enum DiagramKind {
     Configuration,
 }
 
+/// The largest number of components in a [`DiagramPage`].
+pub const DIAGRAM_PAGE_MAX_LIMIT: usize = 1000;
+
+/// A page of a [`Diagram`], assembled by [`Diagram::assemble_page`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramPage {
+    #[serde(flatten)]
+    pub diagram: Diagram,
+    /// The cursor to assemble the next page from, unset if this is the last page.
+    pub next_cursor: Option<ComponentId>,
+}
+
 /// The shape of assembled graph-related information required to render a graphical/visual diagram.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -120,18 +142,93 @@ pub struct Diagram {
 
 impl Diagram {
     /// Assemble a [`Diagram`](Self) based on existing [`Nodes`](crate::Node) and
-    /// [`Connections`](crate::Connection).
+    /// [`Connections`](crate::Connection). [`Components`](crate::Component) that have been
+    /// [`archived`](crate::component::archive), and their edges, are left out.
     pub async fn assemble(ctx: &DalContext) -> DiagramResult<Self> {
-        let components = summary_diagram::component_list(ctx)
+        let mut components = summary_diagram::component_list(ctx)
             .await
             .map_err(|e| DiagramError::SummaryDiagram(e.to_string()))?;
-        let edges = summary_diagram::edge_list(ctx)
+        let mut edges = summary_diagram::edge_list(ctx)
             .await
             .map_err(|e| DiagramError::SummaryDiagram(e.to_string()))?;
 
+        let archived_component_ids: HashSet<ComponentId> = Component::list_archived(ctx)
+            .await?
+            .iter()
+            .map(|component| *component.id())
+            .collect();
+        if !archived_component_ids.is_empty() {
+            let mut archived_node_ids = HashSet::new();
+            components.retain(|component| {
+                if archived_component_ids.contains(&component.component_id()) {
+                    archived_node_ids.insert(component.node_id());
+                    false
+                } else {
+                    true
+                }
+            });
+            edges.retain(|edge| {
+                !archived_node_ids.contains(&edge.from_node_id())
+                    && !archived_node_ids.contains(&edge.to_node_id())
+            });
+        }
+
         Ok(Self { edges, components })
     }
 
+    /// Assemble a page of a [`Diagram`](Self): at most `limit` components, in a stable order,
+    /// starting after the [`DiagramPage::next_cursor`] of the previous page (or from the first one
+    /// if `cursor` is unset), so that clients can load large diagrams incrementally.
+    ///
+    /// A page holds the edges connected to its components, so an edge between components of two
+    /// pages is part of both. As with [`Self::assemble`], archived
+    /// [`Components`](crate::Component) and their edges are left out, which can leave a page
+    /// short without it being the last one: only an unset [`DiagramPage::next_cursor`] means
+    /// there is nothing left to load.
+    pub async fn assemble_page(
+        ctx: &DalContext,
+        cursor: Option<ComponentId>,
+        limit: usize,
+    ) -> DiagramResult<DiagramPage> {
+        let limit = limit.clamp(1, DIAGRAM_PAGE_MAX_LIMIT);
+        let mut components = summary_diagram::component_list_page(ctx, cursor, limit as i64)
+            .await
+            .map_err(|e| DiagramError::SummaryDiagram(e.to_string()))?;
+        let next_cursor = if components.len() == limit {
+            components.last().map(|component| component.component_id())
+        } else {
+            None
+        };
+
+        let mut archived_component_ids = HashSet::new();
+        let mut archived_node_ids = HashSet::new();
+        for component in Component::list_archived(ctx).await? {
+            archived_component_ids.insert(*component.id());
+            for node in component.node(ctx).await? {
+                archived_node_ids.insert(*node.id());
+            }
+        }
+        components.retain(|component| !archived_component_ids.contains(&component.component_id()));
+
+        let node_ids: HashSet<NodeId> = components
+            .iter()
+            .map(|component| component.node_id())
+            .collect();
+        let mut edges = summary_diagram::edge_list(ctx)
+            .await
+            .map_err(|e| DiagramError::SummaryDiagram(e.to_string()))?;
+        edges.retain(|edge| {
+            (node_ids.contains(&edge.from_node_id()) || node_ids.contains(&edge.to_node_id()))
+                && !archived_node_ids.contains(&edge.from_node_id())
+                && !archived_node_ids.contains(&edge.to_node_id())
+        });
+
+        Ok(DiagramPage {
+            diagram: Self { edges, components },
+            next_cursor,
+        })
+    }
+
     pub fn components(&self) -> &[SummaryDiagramComponent] {
         &self.components
     }
@@ -2,11 +2,13 @@ use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 
 use crate::diagram::DiagramResult;
-use crate::edge::{Edge, EdgeId, EdgeKind};
+use crate::edge::{Edge, EdgeCreationSource, EdgeId, EdgeKind};
+use crate::job::definition::DependentValuesUpdate;
 use crate::socket::{SocketEdgeKind, SocketId};
 use crate::{
-    node::NodeId, Component, ComponentError, DalContext, DiagramError, Socket, SocketArity,
-    StandardModel, User,
+    node::NodeId, AttributePrototype, AttributePrototypeArgument, AttributeReadContext,
+    AttributeValue, AttributeValueId, Component, ComponentError, DalContext, DiagramError, Socket,
+    SocketArity, StandardModel, User,
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
@@ -21,6 +23,7 @@ pub struct Vertex {
 pub struct Connection {
     pub id: EdgeId,
     pub classification: EdgeKind,
+    pub creation_source: EdgeCreationSource,
     pub source: Vertex,
     pub destination: Vertex,
     pub created_by: Option<User>,
@@ -36,6 +39,7 @@ impl Connection {
         to_node_id: NodeId,
         to_socket_id: SocketId,
         edge_kind: EdgeKind,
+        creation_source: EdgeCreationSource,
     ) -> DiagramResult<Self> {
         let from_component = Component::find_for_node(ctx, from_node_id)
             .await?
@@ -105,6 +109,7 @@ impl Connection {
             from_node_id,
             from_socket_id,
             edge_kind,
+            creation_source,
         )
         .await?;
 
@@ -139,6 +144,7 @@ impl Connection {
             parent_node_id,
             *to_socket.id(),
             EdgeKind::Symbolic,
+            EdgeCreationSource::Manual,
         )
         .await
     }
@@ -147,6 +153,7 @@ impl Connection {
         Self {
             id: *edge.id(),
             classification: *edge.kind(),
+            creation_source: *edge.creation_source(),
             source: Vertex {
                 node_id: edge.tail_node_id(),
                 socket_id: edge.tail_socket_id(),
@@ -168,6 +175,130 @@ impl Connection {
         Ok(())
     }
 
+    /// Deletes the [`Connection`] for the given [`EdgeId`] like
+    /// [`delete_for_edge`](Self::delete_for_edge) does, and cleans up after it: once nothing
+    /// connects the source socket to the destination [`Component`] anymore, the attribute
+    /// prototypes of the destination that were bound to the source provider are reset to their
+    /// defaults. Dependent values are updated for every [`AttributeValue`] affected, whose ids are
+    /// returned.
+    pub async fn delete(ctx: &DalContext, edge_id: EdgeId) -> DiagramResult<Vec<AttributeValueId>> {
+        let mut edge = Edge::get_by_id(ctx, &edge_id)
+            .await?
+            .ok_or(DiagramError::EdgeNotFound)?;
+        if *edge.kind() == EdgeKind::Symbolic {
+            edge.delete_and_propagate(ctx).await?;
+            return Ok(Vec::new());
+        }
+
+        let tail_component_id = edge.tail_component_id();
+        let head_component_id = edge.head_component_id();
+        let tail_socket_id = edge.tail_socket_id();
+        let external_provider = Socket::get_by_id(ctx, &tail_socket_id)
+            .await?
+            .ok_or(DiagramError::SocketNotFound)?
+            .external_provider(ctx)
+            .await?
+            .ok_or(DiagramError::ExternalProviderNotFoundForSocket(
+                tail_socket_id,
+            ))?;
+
+        edge.delete_and_propagate(ctx).await?;
+
+        // Another connection from the same socket still feeds the destination.
+        let still_connected = Edge::list_for_component(ctx, head_component_id)
+            .await?
+            .into_iter()
+            .any(|edge| {
+                *edge.kind() == EdgeKind::Configuration
+                    && edge.head_component_id() == head_component_id
+                    && edge.tail_component_id() == tail_component_id
+                    && edge.tail_socket_id() == tail_socket_id
+            });
+        if still_connected {
+            return Ok(Vec::new());
+        }
+
+        // The prototypes of the sockets themselves belong to their connections, so only the ones
+        // the destination set on its own props are considered.
+        let prototypes = AttributePrototype::list_by_head_from_external_provider_use_with_tail(
+            ctx,
+            *external_provider.id(),
+            tail_component_id,
+        )
+        .await?
+        .into_iter()
+        .filter(|group| group.head_component_id == head_component_id)
+        .map(|group| group.attribute_prototype)
+        .filter(|prototype| {
+            prototype.context.component_id() == head_component_id
+                && prototype.context.prop_id().is_some()
+        });
+
+        let mut affected = Vec::new();
+        for prototype in prototypes {
+            let mut remaining = false;
+            for mut argument in
+                AttributePrototypeArgument::list_for_attribute_prototype(ctx, *prototype.id())
+                    .await?
+            {
+                if argument.external_provider_id() == *external_provider.id()
+                    && argument.tail_component_id() == tail_component_id
+                {
+                    argument.delete_by_id(ctx).await?;
+                } else {
+                    remaining = true;
+                }
+            }
+
+            for mut attribute_value in prototype.attribute_values(ctx).await? {
+                if attribute_value.context.component_id() != head_component_id {
+                    continue;
+                }
+
+                if remaining {
+                    attribute_value.update_from_prototype_function(ctx).await?;
+                    affected.push(*attribute_value.id());
+                    continue;
+                }
+
+                // Nothing feeds the prototype anymore: back to the value of the schema variant.
+                let default_context =
+                    AttributeReadContext::from(prototype.context.less_specific()?);
+                let default_value = match AttributeValue::find_for_context(ctx, default_context)
+                    .await?
+                {
+                    Some(default_attribute_value) => default_attribute_value.get_value(ctx).await?,
+                    None => None,
+                };
+                let parent_attribute_value_id = attribute_value
+                    .parent_attribute_value(ctx)
+                    .await?
+                    .map(|parent| *parent.id());
+                let (_, attribute_value_id) = AttributeValue::update_for_context(
+                    ctx,
+                    *attribute_value.id(),
+                    parent_attribute_value_id,
+                    prototype.context,
+                    default_value,
+                    prototype.key().map(|key| key.to_string()),
+                )
+                .await?;
+                affected.push(attribute_value_id);
+            }
+        }
+
+        if !affected.is_empty() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                affected.clone(),
+            ))
+            .await?;
+        }
+
+        Ok(affected)
+    }
+
     pub async fn restore_for_edge(ctx: &DalContext, edge_id: EdgeId) -> DiagramResult<()> {
         Edge::restore_by_id(ctx, edge_id).await?;
         Ok(())
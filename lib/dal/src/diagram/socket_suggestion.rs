@@ -0,0 +1,149 @@
+//! This module contains [`SocketSuggestion`], which lists the [`Sockets`](crate::Socket) on the
+//! diagram that a given [`Socket`](crate::Socket) can be connected to.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use telemetry::prelude::*;
+
+use crate::diagram::DiagramResult;
+use crate::edge::{Edge, EdgeKind};
+use crate::socket::{SocketEdgeKind, SocketId};
+use crate::{
+    node::NodeId, Component, ComponentError, ComponentId, DalContext, DiagramError, Node, SchemaId,
+    Socket, StandardModel,
+};
+
+/// A [`Socket`] on another [`Component`] that an edge can be drawn to, along with what it was
+/// ranked by.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketSuggestion {
+    pub node_id: NodeId,
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub socket_id: SocketId,
+    pub socket_name: String,
+    /// The distance between the two [`Nodes`](Node) on the diagram.
+    pub distance: f64,
+    /// How many configuration [`Edges`](Edge) already connect [`Components`](Component) of the
+    /// two [`Schemas`](crate::Schema).
+    pub schema_affinity: usize,
+}
+
+impl SocketSuggestion {
+    /// List the [`Sockets`](Socket) compatible with the given [`Socket`] on the given
+    /// [`Node`], skipping those on the same [`Node`] and those already connected to it. The
+    /// suggestions are ranked by schema affinity first and by proximity second.
+    #[instrument(skip(ctx))]
+    pub async fn list(
+        ctx: &DalContext,
+        node_id: NodeId,
+        socket_id: SocketId,
+    ) -> DiagramResult<Vec<Self>> {
+        let node = Node::get_by_id(ctx, &node_id)
+            .await?
+            .ok_or(DiagramError::NodeNotFound)?;
+        let component = Component::find_for_node(ctx, node_id)
+            .await?
+            .ok_or(ComponentError::NotFoundForNode(node_id))?;
+        let socket = Socket::get_by_id(ctx, &socket_id)
+            .await?
+            .ok_or(DiagramError::SocketNotFound)?;
+        let is_output = *socket.edge_kind() == SocketEdgeKind::ConfigurationOutput;
+        let (x, y) = node_position(&node)?;
+
+        let mut schema_ids: HashMap<ComponentId, SchemaId> = HashMap::new();
+        let schema_id = Component::schema_id(ctx, *component.id()).await?;
+        schema_ids.insert(*component.id(), schema_id);
+
+        let configuration_edges = Edge::list_for_kind(ctx, EdgeKind::Configuration).await?;
+
+        let mut schema_affinities: HashMap<SchemaId, usize> = HashMap::new();
+        for edge in &configuration_edges {
+            let mut edge_schema_ids = Vec::with_capacity(2);
+            for edge_component_id in [edge.tail_component_id(), edge.head_component_id()] {
+                let edge_schema_id = match schema_ids.get(&edge_component_id) {
+                    Some(edge_schema_id) => *edge_schema_id,
+                    None => {
+                        let edge_schema_id = Component::schema_id(ctx, edge_component_id).await?;
+                        schema_ids.insert(edge_component_id, edge_schema_id);
+                        edge_schema_id
+                    }
+                };
+                edge_schema_ids.push(edge_schema_id);
+            }
+            match edge_schema_ids.as_slice() {
+                [tail, head] if *tail == schema_id => {
+                    *schema_affinities.entry(*head).or_default() += 1;
+                }
+                [tail, head] if *head == schema_id => {
+                    *schema_affinities.entry(*tail).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+
+        let mut suggestions = Vec::new();
+        for other_component in Component::list(ctx).await? {
+            if other_component.id() == component.id() {
+                continue;
+            }
+            let other_node = match other_component.node(ctx).await?.pop() {
+                Some(other_node) => other_node,
+                None => continue,
+            };
+            let (other_x, other_y) = node_position(&other_node)?;
+            let other_schema_id = match schema_ids.get(other_component.id()) {
+                Some(other_schema_id) => *other_schema_id,
+                None => Component::schema_id(ctx, *other_component.id()).await?,
+            };
+
+            for other_socket in Socket::list_for_component(ctx, *other_component.id()).await? {
+                if other_socket.ui_hidden() || !socket.is_compatible_with(&other_socket) {
+                    continue;
+                }
+
+                let already_connected = configuration_edges.iter().any(|edge| {
+                    let (tail, head) = if is_output {
+                        ((node_id, socket_id), (*other_node.id(), *other_socket.id()))
+                    } else {
+                        ((*other_node.id(), *other_socket.id()), (node_id, socket_id))
+                    };
+                    (edge.tail_node_id(), edge.tail_socket_id()) == tail
+                        && (edge.head_node_id(), edge.head_socket_id()) == head
+                });
+                if already_connected {
+                    continue;
+                }
+
+                suggestions.push(Self {
+                    node_id: *other_node.id(),
+                    component_id: *other_component.id(),
+                    component_name: other_component.name(ctx).await?,
+                    socket_id: *other_socket.id(),
+                    socket_name: other_socket
+                        .human_name()
+                        .unwrap_or(other_socket.name())
+                        .to_owned(),
+                    distance: (other_x - x).hypot(other_y - y),
+                    schema_affinity: schema_affinities
+                        .get(&other_schema_id)
+                        .copied()
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        suggestions.sort_by(|a, b| {
+            b.schema_affinity
+                .cmp(&a.schema_affinity)
+                .then(a.distance.total_cmp(&b.distance))
+        });
+
+        Ok(suggestions)
+    }
+}
+
+fn node_position(node: &Node) -> DiagramResult<(f64, f64)> {
+    Ok((node.x().parse()?, node.y().parse()?))
+}
@@ -7,7 +7,7 @@ use thiserror::Error;
 
 use crate::change_status::ChangeStatus;
 use crate::diagram::DiagramResult;
-use crate::edge::{EdgeId, EdgeKind};
+use crate::edge::{EdgeCreationSource, EdgeId, EdgeKind};
 use crate::history_event::HistoryEventMetadata;
 use crate::schema::SchemaUiMenu;
 use crate::socket::SocketEdgeKind;
@@ -21,6 +21,8 @@ use crate::{
 
 const LIST_SUMMARY_DIAGRAM_COMPONENTS: &str =
     include_str!("../queries/summary_diagram/list_summary_diagram_components.sql");
+const LIST_SUMMARY_DIAGRAM_COMPONENTS_PAGE: &str =
+    include_str!("../queries/summary_diagram/list_summary_diagram_components_page.sql");
 const LIST_SUMMARY_DIAGRAM_EDGES: &str =
     include_str!("../queries/summary_diagram/list_summary_diagram_edges.sql");
 
@@ -102,6 +104,14 @@ impl SummaryDiagramComponent {
     pub fn has_resource(&self) -> bool {
         self.has_resource
     }
+
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    pub fn node_id(&self) -> NodeId {
+        self.node_id
+    }
 }
 
 pub async fn create_component_entry(
@@ -312,6 +322,31 @@ pub async fn component_list(
     Ok(objects)
 }
 
+/// List at most `limit` components, ordered by id, starting after the component with the id of
+/// `cursor` (or from the first one if unset).
+pub async fn component_list_page(
+    ctx: &DalContext,
+    cursor: Option<ComponentId>,
+    limit: i64,
+) -> SummaryDiagramResult<Vec<SummaryDiagramComponent>> {
+    let rows = ctx
+        .txns()
+        .await?
+        .pg()
+        .query(
+            LIST_SUMMARY_DIAGRAM_COMPONENTS_PAGE,
+            &[
+                ctx.tenancy(),
+                &ctx.visibility().change_set_pk,
+                &cursor,
+                &limit,
+            ],
+        )
+        .await?;
+    let objects: Vec<SummaryDiagramComponent> = objects_from_rows(rows)?;
+    Ok(objects)
+}
+
 pk!(SummaryDiagramEdgePk);
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all(serialize = "camelCase"))]
@@ -332,6 +367,7 @@ pub struct SummaryDiagramEdge {
     change_status: String,
     created_info: serde_json::Value,
     deleted_info: serde_json::Value,
+    creation_source: EdgeCreationSource,
 }
 
 impl_standard_model! {
@@ -347,6 +383,18 @@ impl SummaryDiagramEdge {
     pub fn edge_id(&self) -> EdgeId {
         self.edge_id
     }
+
+    pub fn from_node_id(&self) -> NodeId {
+        self.from_node_id
+    }
+
+    pub fn to_node_id(&self) -> NodeId {
+        self.to_node_id
+    }
+
+    pub fn creation_source(&self) -> EdgeCreationSource {
+        self.creation_source
+    }
 }
 
 pub async fn create_edge_entry(ctx: &DalContext, edge: &Edge) -> SummaryDiagramResult<()> {
@@ -365,7 +413,7 @@ pub async fn create_edge_entry(ctx: &DalContext, edge: &Edge) -> SummaryDiagramR
         .await?
         .pg()
         .query_one(
-            "SELECT object FROM summary_diagram_edge_create_v1($1, $2, $3, $4, $5, $6, $7, $8)",
+            "SELECT object FROM summary_diagram_edge_create_v1($1, $2, $3, $4, $5, $6, $7, $8, $9)",
             &[
                 ctx.tenancy(),
                 ctx.visibility(),
@@ -375,6 +423,7 @@ pub async fn create_edge_entry(ctx: &DalContext, edge: &Edge) -> SummaryDiagramR
                 &edge.head_node_id(),
                 &edge.head_socket_id(),
                 &serde_json::to_value(created_info)?,
+                &edge.creation_source().to_string(),
             ],
         )
         .await?;
@@ -0,0 +1,295 @@
+//! This module contains the import of existing cloud resources as [`Components`](Component).
+//!
+//! The discovery funcs installed by the builtins (see [`AWS_DISCOVERIES`]) run through veritech,
+//! authenticated with an "AWS Credential" secret, and list the resources of a region. Every
+//! resource that is not modeled yet becomes a [`Component`] whose domain is set from the live
+//! resource and whose resource is the live payload. Its identifier is registered as a
+//! [`ResourceIdentifier`], so that the resource is never imported twice and so that confirmations
+//! can detect drift later on.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use telemetry::prelude::*;
+use thiserror::Error;
+use veritech_client::ResourceStatus;
+
+use crate::builtins::schema::aws_credential::AWS_CREDENTIAL_AUTH_FUNC;
+use crate::builtins::schema::discovery::AWS_DISCOVERIES;
+use crate::edge::{EdgeCreationSource, EdgeKind};
+use crate::func::backend::js_action::ActionRunResult;
+use crate::func::before::before_funcs_for_secret;
+use crate::socket::{SocketEdgeKind, SocketError};
+use crate::{
+    Component, ComponentError, ComponentId, Connection, DalContext, DiagramError, EncryptedSecret,
+    Func, FuncBinding, FuncBindingError, FuncError, NodeId, ResourceIdentifier,
+    ResourceIdentifierError, Schema, SchemaError, SecretId, Socket, StandardModel,
+    StandardModelError,
+};
+
+/// The socket connecting a security group to its rules.
+const SECURITY_GROUP_ID_SOCKET: &str = "Security Group ID";
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DiscoveryError {
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("diagram error: {0}")]
+    Diagram(#[from] DiagramError),
+    #[error("func error: {0}")]
+    Func(#[from] FuncError),
+    #[error("func binding error: {0}")]
+    FuncBinding(#[from] FuncBindingError),
+    #[error("discovery func not found: {0}")]
+    FuncNotFound(String),
+    #[error("resource identifier error: {0}")]
+    ResourceIdentifier(#[from] ResourceIdentifierError),
+    #[error("schema error: {0}")]
+    Schema(#[from] SchemaError),
+    #[error("secret not found: {0}")]
+    SecretNotFound(SecretId),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("socket error: {0}")]
+    Socket(#[from] SocketError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+}
+
+pub type DiscoveryResult<T> = Result<T, DiscoveryError>;
+
+/// A live resource, as listed by a discovery func.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredResource {
+    /// The canonical cloud identifier of the resource (e.g. "sg-0123").
+    pub resource_id: String,
+    pub name: String,
+    /// The security group a rule belongs to, for rules.
+    #[serde(default)]
+    pub security_group_id: Option<String>,
+    /// The attribute values of the resource, shaped like "/root/domain".
+    #[serde(default)]
+    pub domain: serde_json::Value,
+    /// The resource itself, as its refresh action would record it.
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+/// A live resource that was imported as a [`Component`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedResource {
+    pub schema_name: String,
+    pub resource_id: String,
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+}
+
+/// A live resource that was not imported because a [`Component`] already models it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedResource {
+    pub schema_name: String,
+    pub resource_id: String,
+    pub component_id: ComponentId,
+}
+
+/// The outcome of [`import_aws_resources`].
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryReport {
+    pub imported: Vec<ImportedResource>,
+    pub skipped: Vec<SkippedResource>,
+}
+
+/// Lists the live resources of the region for every [`Schema`] in [`AWS_DISCOVERIES`], along
+/// with the name of the [`Schema`] modeling them, authenticating with the given "AWS Credential"
+/// secret.
+#[instrument(skip(ctx))]
+pub async fn discover_aws_resources(
+    ctx: &DalContext,
+    secret_id: SecretId,
+    region: &str,
+) -> DiscoveryResult<Vec<(String, DiscoveredResource)>> {
+    let encrypted_secret = EncryptedSecret::get_by_id(ctx, &secret_id)
+        .await?
+        .ok_or(DiscoveryError::SecretNotFound(secret_id))?;
+    let before = before_funcs_for_secret(ctx, encrypted_secret, AWS_CREDENTIAL_AUTH_FUNC).await?;
+    let args = serde_json::json!({ "region": region });
+
+    let mut discovered = Vec::new();
+    for (schema_name, fn_name, _, _) in AWS_DISCOVERIES {
+        let func = Func::find_by_name(ctx, fn_name)
+            .await?
+            .ok_or_else(|| DiscoveryError::FuncNotFound(fn_name.to_string()))?;
+        let (_, func_binding_return_value) =
+            FuncBinding::create_and_execute(ctx, args.clone(), *func.id(), before.clone()).await?;
+
+        let resources: Vec<DiscoveredResource> = match func_binding_return_value.value() {
+            Some(value) => serde_json::from_value(value.clone())?,
+            None => Vec::new(),
+        };
+        discovered.extend(
+            resources
+                .into_iter()
+                .map(|resource| (schema_name.to_string(), resource)),
+        );
+    }
+
+    Ok(discovered)
+}
+
+/// Imports the live resources of the region (see [`discover_aws_resources`]) as
+/// [`Components`](Component), skipping those already modeled. Rules are connected to the
+/// security group they belong to when it is imported along with them.
+#[instrument(skip(ctx))]
+pub async fn import_aws_resources(
+    ctx: &DalContext,
+    secret_id: SecretId,
+    region: &str,
+) -> DiscoveryResult<DiscoveryReport> {
+    let discovered = discover_aws_resources(ctx, secret_id, region).await?;
+    import_discovered_resources(ctx, discovered).await
+}
+
+/// Imports the given resources as [`Components`](Component) of the [`Schemas`](Schema) they are
+/// paired with, skipping those already modeled.
+pub async fn import_discovered_resources(
+    ctx: &DalContext,
+    discovered: Vec<(String, DiscoveredResource)>,
+) -> DiscoveryResult<DiscoveryReport> {
+    let mut report = DiscoveryReport::default();
+    let mut nodes_by_resource_id = HashMap::new();
+    let mut rules = Vec::new();
+
+    for (schema_name, resource) in discovered {
+        if let Some(component_id) =
+            ResourceIdentifier::find_conflict(ctx, ComponentId::NONE, &resource.resource_id).await?
+        {
+            report.skipped.push(SkippedResource {
+                schema_name,
+                resource_id: resource.resource_id,
+                component_id,
+            });
+            continue;
+        }
+
+        let schema_variant_id =
+            Schema::default_schema_variant_id_for_name(ctx, &schema_name).await?;
+        let (component, node) = Component::new(ctx, &resource.name, schema_variant_id).await?;
+        if resource.domain.is_object() {
+            Component::apply_domain_overrides(ctx, *component.id(), &resource.domain).await?;
+        }
+
+        component
+            .set_resource_raw(
+                ctx,
+                ActionRunResult {
+                    status: Some(ResourceStatus::Ok),
+                    payload: Some(resource.payload),
+                    message: None,
+                    logs: Vec::new(),
+                    last_synced: Some(Utc::now().to_rfc3339()),
+                    plan: Vec::new(),
+                },
+                false,
+            )
+            .await?;
+        // The schema may not know where to find the identifier in the payload, but the discovery
+        // func always does.
+        ResourceIdentifier::register(ctx, *component.id(), &resource.resource_id).await?;
+
+        nodes_by_resource_id.insert(resource.resource_id.clone(), *node.id());
+        if let Some(security_group_id) = resource.security_group_id {
+            rules.push((security_group_id, *node.id()));
+        }
+        report.imported.push(ImportedResource {
+            schema_name,
+            resource_id: resource.resource_id,
+            component_id: *component.id(),
+            node_id: *node.id(),
+        });
+    }
+
+    for (security_group_id, rule_node_id) in rules {
+        if let Some(security_group_node_id) = nodes_by_resource_id.get(&security_group_id) {
+            connect_security_group(ctx, *security_group_node_id, rule_node_id).await?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Feeds the id of the security group to one of its rules, when both of them have the socket.
+async fn connect_security_group(
+    ctx: &DalContext,
+    security_group_node_id: NodeId,
+    rule_node_id: NodeId,
+) -> DiscoveryResult<()> {
+    let from_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        SECURITY_GROUP_ID_SOCKET,
+        SocketEdgeKind::ConfigurationOutput,
+        security_group_node_id,
+    )
+    .await?;
+    let to_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        SECURITY_GROUP_ID_SOCKET,
+        SocketEdgeKind::ConfigurationInput,
+        rule_node_id,
+    )
+    .await?;
+
+    match (from_socket, to_socket) {
+        (Some(from_socket), Some(to_socket)) => {
+            Connection::new(
+                ctx,
+                security_group_node_id,
+                *from_socket.id(),
+                rule_node_id,
+                *to_socket.id(),
+                EdgeKind::Configuration,
+                EdgeCreationSource::Import,
+            )
+            .await?;
+        }
+        _ => debug!(
+            %security_group_node_id,
+            %rule_node_id,
+            "skipping security group connection, socket not found"
+        ),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_discovered_resources() {
+        let resources: Vec<DiscoveredResource> = serde_json::from_value(serde_json::json!([
+            {
+                "resourceId": "sg-0123",
+                "name": "web",
+                "domain": { "GroupName": "web" },
+                "payload": { "GroupId": "sg-0123" },
+            },
+            {
+                "resourceId": "sg-0123/ingress",
+                "name": "sg-0123 ingress",
+                "securityGroupId": "sg-0123",
+            },
+        ]))
+        .expect("could not deserialize discovered resources");
+
+        assert_eq!("sg-0123", resources[0].resource_id);
+        assert_eq!(None, resources[0].security_group_id);
+        assert_eq!(Some("sg-0123".to_string()), resources[1].security_group_id);
+        assert!(resources[1].domain.is_null());
+    }
+}
@@ -16,8 +16,9 @@ use crate::standard_model::objects_from_rows;
 use crate::{
     diagram, impl_standard_model, pk, socket::SocketId, standard_model, standard_model_accessor,
     AttributeReadContext, AttributeValue, AttributeValueError, ComponentId, ExternalProviderError,
-    Func, FuncError, HistoryActor, HistoryEventError, InternalProviderError, Node, PropId, Socket,
-    StandardModel, StandardModelError, Tenancy, Timestamp, UserPk, Visibility,
+    Func, FuncError, HistoryActor, HistoryEventError, InternalProviderError, Node, PropId,
+    RootPropChild, Socket, StandardModel, StandardModelError, Tenancy, Timestamp, UserPk,
+    Visibility,
 };
 use crate::{
     AttributePrototypeArgument, AttributePrototypeArgumentError, Component, DalContext,
@@ -123,6 +124,33 @@ pub enum EdgeKind {
     Symbolic,
 }
 
+/// How an [`Edge`](Edge) came to be.
+#[remain::sorted]
+#[derive(
+    Deserialize,
+    Serialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Display,
+    EnumString,
+    AsRefStr,
+    Copy,
+    Default,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum EdgeCreationSource {
+    /// Created when a component was put in a frame, by connecting the matching sockets.
+    FrameAutoConnection,
+    /// Created by importing a package or instantiating a template.
+    Import,
+    /// Drawn by a user.
+    #[default]
+    Manual,
+}
+
 pk!(EdgeId);
 pk!(EdgePk);
 
@@ -144,6 +172,7 @@ pub struct Edge {
     creation_user_pk: Option<UserPk>,
     deletion_user_pk: Option<UserPk>,
     deleted_implicitly: bool,
+    creation_source: EdgeCreationSource,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -189,6 +218,7 @@ impl Edge {
         tail_object_kind: VertexObjectKind,
         tail_object_id: EdgeObjectId,
         tail_socket_id: SocketId,
+        creation_source: EdgeCreationSource,
     ) -> EdgeResult<Self> {
         let actor_user_pk = match ctx.history_actor() {
             HistoryActor::User(user_pk) => Some(*user_pk),
@@ -200,7 +230,7 @@ impl Edge {
             .await?
             .pg()
             .query_one(
-                "SELECT object FROM edge_create_v1($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+                "SELECT object FROM edge_create_v1($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
                 &[
                     ctx.tenancy(),
                     ctx.visibility(),
@@ -214,6 +244,7 @@ impl Edge {
                     &tail_object_id,
                     &tail_socket_id,
                     &actor_user_pk,
+                    &creation_source.to_string(),
                 ],
             )
             .await?;
@@ -242,6 +273,7 @@ impl Edge {
         tail_node_id: NodeId,
         tail_socket_id: SocketId,
         edge_kind: EdgeKind,
+        creation_source: EdgeCreationSource,
     ) -> EdgeResult<Self> {
         // Revive edge if it already exists
         if let Some(equivalent_edge) = {
@@ -297,6 +329,17 @@ impl Edge {
                 *tail_component.id(),
             )
             .await?;
+
+            // Code generation functions receive the connected components as an input, so they
+            // need to be re-run for the head component now that a new connection exists.
+            let head_domain_attribute_value =
+                Self::head_domain_attribute_value(ctx, *head_component.id()).await?;
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                vec![*head_domain_attribute_value.id()],
+            ))
+            .await?;
         }
 
         // NOTE(nick): a lot of hardcoded values here that'll likely need to be adjusted.
@@ -311,6 +354,7 @@ impl Edge {
             VertexObjectKind::Configuration,
             EdgeObjectId::from(*tail_component.id()),
             tail_socket_id,
+            creation_source,
         )
         .await?;
         Ok(edge)
@@ -330,6 +374,13 @@ impl Edge {
     standard_model_accessor!(creation_user_pk, Option<Pk(UserPk)>, EdgeResult);
     standard_model_accessor!(deletion_user_pk, Option<Pk(UserPk)>, EdgeResult);
     standard_model_accessor!(deleted_implicitly, bool, EdgeResult);
+    standard_model_accessor!(creation_source, Enum(EdgeCreationSource), EdgeResult);
+
+    /// Whether the [`Edge`](Self) was created implicitly, when its [`Component`] was put in a
+    /// frame, rather than drawn by a user or imported.
+    pub fn auto_created(&self) -> bool {
+        self.creation_source == EdgeCreationSource::FrameAutoConnection
+    }
 
     pub async fn list_children_for_node(
         ctx: &DalContext,
@@ -520,10 +571,15 @@ impl Edge {
 
         attr_value.update_from_prototype_function(ctx).await?;
 
+        // Seeding with the domain as well ensures code generation functions, which receive the
+        // connected components as an input, are re-run without the removed connection.
+        let head_domain_attribute_value =
+            Self::head_domain_attribute_value(ctx, head_component_id).await?;
+
         ctx.enqueue_job(DependentValuesUpdate::new(
             ctx.access_builder(),
             *ctx.visibility(),
-            vec![*attr_value.id()],
+            vec![*attr_value.id(), *head_domain_attribute_value.id()],
         ))
         .await?;
 
@@ -534,6 +590,19 @@ impl Edge {
         Ok(())
     }
 
+    async fn head_domain_attribute_value(
+        ctx: &DalContext,
+        head_component_id: ComponentId,
+    ) -> EdgeResult<AttributeValue> {
+        Component::root_prop_child_attribute_value_for_component(
+            ctx,
+            head_component_id,
+            RootPropChild::Domain,
+        )
+        .await
+        .map_err(|err| EdgeError::Component(err.to_string()))
+    }
+
     pub async fn restore_by_id(ctx: &DalContext, edge_id: EdgeId) -> EdgeResult<Option<Self>> {
         let ctx_with_deleted = &ctx.clone_with_delete_visibility();
 
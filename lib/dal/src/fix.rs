@@ -14,9 +14,10 @@ use crate::{
     func::backend::js_action::ActionRunResult, impl_standard_model, pk, standard_model,
     standard_model_accessor, standard_model_accessor_ro, standard_model_belongs_to, ActionId,
     ActionKind, ActionPrototype, ActionPrototypeError, ActionPrototypeId, Component,
-    ComponentError, ComponentId, DalContext, FixBatch, FixResolverError, Func, FuncError,
-    HistoryEventError, ResourceView, SchemaError, StandardModel, StandardModelError, Tenancy,
-    Timestamp, TransactionsError, Visibility, WsEvent, WsEventError, WsEventResult, WsPayload,
+    ComponentError, ComponentId, DalContext, EdgeError, FixBatch, FixResolverError, Func,
+    FuncError, HistoryEventError, ResourceView, SchemaError, StandardModel, StandardModelError,
+    Tenancy, Timestamp, TransactionsError, Visibility, WsEvent, WsEventError, WsEventResult,
+    WsPayload,
 };
 use veritech_client::ResourceStatus;
 
@@ -74,6 +75,8 @@ pub enum FixError {
     BatchAlreadyStarted(FixId, FixBatchId),
     #[error(transparent)]
     Component(#[from] ComponentError),
+    #[error(transparent)]
+    Edge(#[from] EdgeError),
     #[error("completion status is empty")]
     EmptyCompletionStatus,
     #[error(transparent)]
@@ -355,6 +358,7 @@ impl Fix {
                         // TODO: add proper logs here
                         logs: vec![],
                         last_synced: None,
+                        plan: Vec::new(),
                     })
                 } else {
                     None
@@ -459,3 +463,33 @@ impl WsEvent {
         .await
     }
 }
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FixStarted {
+    id: FixId,
+    batch_id: FixBatchId,
+    component_id: ComponentId,
+    action: ActionKind,
+}
+
+impl WsEvent {
+    pub async fn fix_started(
+        ctx: &DalContext,
+        id: FixId,
+        batch_id: FixBatchId,
+        component_id: ComponentId,
+        action: ActionKind,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::FixStarted(FixStarted {
+                id,
+                batch_id,
+                component_id,
+                action,
+            }),
+        )
+        .await
+    }
+}
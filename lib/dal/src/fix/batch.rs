@@ -3,13 +3,14 @@
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use telemetry::prelude::*;
 
 use crate::{
     fix::{FixCompletionStatus, FixError, FixResult},
     impl_standard_model, pk, standard_model, standard_model_accessor, standard_model_has_many,
-    DalContext, Fix, StandardModel, Tenancy, Timestamp, Visibility, WsEvent, WsEventResult,
-    WsPayload,
+    Component, ComponentId, DalContext, Edge, Fix, SchemaId, StandardModel, Tenancy, Timestamp,
+    Visibility, WsEvent, WsEventResult, WsPayload,
 };
 
 pk!(FixBatchPk);
@@ -44,6 +45,9 @@ pub struct FixBatch {
     finished_at: Option<String>,
     /// Indicates the state of the [`FixBatch`] when finished.
     completion_status: Option<FixCompletionStatus>,
+    /// The filter used to select which [`Components`](crate::Component) the batch targets, if
+    /// the batch was scoped.
+    target_filter: Option<FixBatchTargetFilter>,
 }
 
 impl_standard_model! {
@@ -55,23 +59,96 @@ impl_standard_model! {
     history_event_message_name: "FixBatch"
 }
 
+/// Scopes a [`FixBatch`] to the [`Components`](crate::Component) matching _every_ populated
+/// criterion. An empty filter matches all components.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FixBatchTargetFilter {
+    /// Only target these components.
+    #[serde(default)]
+    pub component_ids: Vec<ComponentId>,
+    /// Only target components nested (at any depth) within one of these frames.
+    #[serde(default)]
+    pub frame_ids: Vec<ComponentId>,
+    /// Only target components of these schemas.
+    #[serde(default)]
+    pub schema_ids: Vec<SchemaId>,
+}
+
+impl FixBatchTargetFilter {
+    pub fn is_empty(&self) -> bool {
+        self.component_ids.is_empty() && self.frame_ids.is_empty() && self.schema_ids.is_empty()
+    }
+
+    /// Evaluates the filter against a [`Component`](crate::Component). Deleted components are
+    /// evaluated too, since their "delete" actions may be part of the batch.
+    pub async fn matches(&self, ctx: &DalContext, component_id: ComponentId) -> FixResult<bool> {
+        if !self.component_ids.is_empty() && !self.component_ids.contains(&component_id) {
+            return Ok(false);
+        }
+
+        let ctx = &ctx.clone_with_delete_visibility();
+
+        if !self.schema_ids.is_empty()
+            && !self
+                .schema_ids
+                .contains(&Component::schema_id(ctx, component_id).await?)
+        {
+            return Ok(false);
+        }
+
+        if !self.frame_ids.is_empty() {
+            let mut seen = HashSet::from([component_id]);
+            let mut current = component_id;
+            loop {
+                match Edge::get_parent_for_component(ctx, current).await? {
+                    Some(parent_id) if self.frame_ids.contains(&parent_id) => break,
+                    Some(parent_id) if seen.insert(parent_id) => current = parent_id,
+                    _ => return Ok(false),
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
 impl FixBatch {
     #[instrument(skip_all)]
-    pub async fn new(ctx: &DalContext, author: impl AsRef<str>, actors: &str) -> FixResult<Self> {
+    pub async fn new(
+        ctx: &DalContext,
+        author: impl AsRef<str>,
+        actors: &str,
+        target_filter: Option<&FixBatchTargetFilter>,
+    ) -> FixResult<Self> {
         let author = author.as_ref();
+        let target_filter = target_filter
+            .filter(|filter| !filter.is_empty())
+            .map(serde_json::to_value)
+            .transpose()?;
         let row = ctx
             .txns()
             .await?
             .pg()
             .query_one(
-                "SELECT object FROM fix_batch_create_v1($1, $2, $3, $4)",
-                &[ctx.tenancy(), ctx.visibility(), &author, &actors],
+                "SELECT object FROM fix_batch_create_v2($1, $2, $3, $4, $5)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &author,
+                    &actors,
+                    &target_filter,
+                ],
             )
             .await?;
         let object = standard_model::finish_create_from_row(ctx, row).await?;
         Ok(object)
     }
 
+    pub fn target_filter(&self) -> Option<&FixBatchTargetFilter> {
+        self.target_filter.as_ref()
+    }
+
     standard_model_accessor!(started_at, Option<String>, FixResult);
     standard_model_accessor!(finished_at, Option<String>, FixResult);
     standard_model_accessor!(
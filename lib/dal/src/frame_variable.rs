@@ -0,0 +1,261 @@
+//! This module contains [`FrameVariables`](FrameVariable), named values defined on a
+//! configuration frame that flow down to every [`Component`] placed inside of it.
+//!
+//! A variable is exposed to a child [`Component`] through the
+//! [`InternalProvider`](crate::InternalProvider) of each of its unconnected input
+//! [`Sockets`](crate::Socket) sharing the variable's name. This allows per-environment settings
+//! (e.g. the environment name or a cost center) to reach every child without a dedicated
+//! [`Component`] per value. When nested frames define the same variable, the nearest one wins.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::edge::EdgeKind;
+use crate::socket::{SocketEdgeKind, SocketError, SocketKind};
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, AttributeContextBuilder,
+    AttributeContextBuilderError, AttributeReadContext, AttributeValue, AttributeValueError,
+    Component, ComponentError, ComponentId, DalContext, Edge, EdgeError, HistoryEventError, Socket,
+    SocketId, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError, Visibility,
+};
+
+const LIST_FOR_COMPONENT: &str = include_str!("queries/frame_variable/list_for_component.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FrameVariableError {
+    #[error("attribute context builder error: {0}")]
+    AttributeContextBuilder(#[from] AttributeContextBuilderError),
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value not found for context: {0:?}")]
+    AttributeValueNotFoundForContext(AttributeReadContext),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
+    #[error("edge error: {0}")]
+    Edge(#[from] EdgeError),
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("socket error: {0}")]
+    Socket(#[from] SocketError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FrameVariableResult<T> = Result<T, FrameVariableError>;
+
+pk!(FrameVariablePk);
+pk!(FrameVariableId);
+
+/// A named value defined on a configuration frame [`Component`]. Variables are unique by name
+/// within a frame.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FrameVariable {
+    pk: FrameVariablePk,
+    id: FrameVariableId,
+    /// The [`ComponentId`] of the frame defining the variable.
+    component_id: ComponentId,
+    name: String,
+    value: Option<serde_json::Value>,
+    /// Secret variables hold the id of a [`Secret`](crate::Secret) rather than a literal value.
+    secret: bool,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: FrameVariable,
+    pk: FrameVariablePk,
+    id: FrameVariableId,
+    table_name: "frame_variables",
+    history_event_label_base: "frame_variable",
+    history_event_message_name: "Frame Variable"
+}
+
+impl FrameVariable {
+    #[instrument(skip(ctx, value))]
+    pub async fn upsert(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        name: &str,
+        value: Option<serde_json::Value>,
+        secret: bool,
+    ) -> FrameVariableResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM frame_variable_upsert_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &component_id,
+                    &name,
+                    &value,
+                    &secret,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    /// List the [`FrameVariables`](Self) defined on a frame [`Component`].
+    pub async fn list_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> FrameVariableResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_COMPONENT,
+                &[ctx.tenancy(), ctx.visibility(), &component_id],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Expose the [`FrameVariables`](Self) of a frame [`Component`] to one of its children,
+    /// setting every unconnected input [`Socket`] of the child named after a variable. Sockets in
+    /// `skip_socket_ids` are left untouched.
+    ///
+    /// Returns the [`SocketIds`](SocketId) that were set.
+    pub async fn expose_to_component(
+        ctx: &DalContext,
+        frame_component_id: ComponentId,
+        component_id: ComponentId,
+        skip_socket_ids: &[SocketId],
+    ) -> FrameVariableResult<Vec<SocketId>> {
+        let variables = Self::list_for_component(ctx, frame_component_id).await?;
+        Self::expose_variables_to_component(ctx, &variables, component_id, skip_socket_ids).await
+    }
+
+    async fn expose_variables_to_component(
+        ctx: &DalContext,
+        variables: &[Self],
+        component_id: ComponentId,
+        skip_socket_ids: &[SocketId],
+    ) -> FrameVariableResult<Vec<SocketId>> {
+        if variables.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Explicit connections always take precedence over variables.
+        let connected_socket_ids: Vec<SocketId> = Edge::list_for_component(ctx, component_id)
+            .await?
+            .into_iter()
+            .filter(|edge| {
+                *edge.kind() == EdgeKind::Configuration && edge.head_component_id() == component_id
+            })
+            .map(|edge| edge.head_socket_id())
+            .collect();
+
+        let mut exposed_socket_ids = Vec::new();
+        for socket in Socket::list_for_component(ctx, component_id).await? {
+            if *socket.kind() == SocketKind::Frame
+                || *socket.edge_kind() != SocketEdgeKind::ConfigurationInput
+                || skip_socket_ids.contains(socket.id())
+                || connected_socket_ids.contains(socket.id())
+            {
+                continue;
+            }
+
+            let provider = match socket.internal_provider(ctx).await? {
+                Some(provider) => provider,
+                None => continue,
+            };
+            let variable = match variables.iter().find(|v| v.name() == provider.name()) {
+                Some(variable) => variable,
+                None => continue,
+            };
+
+            let read_context = AttributeReadContext {
+                internal_provider_id: Some(*provider.id()),
+                component_id: Some(component_id),
+                ..Default::default()
+            };
+            let attribute_value = AttributeValue::find_for_context(ctx, read_context)
+                .await?
+                .ok_or(FrameVariableError::AttributeValueNotFoundForContext(
+                    read_context,
+                ))?;
+            let context = AttributeContextBuilder::new()
+                .set_internal_provider_id(*provider.id())
+                .set_component_id(component_id)
+                .to_context()?;
+            AttributeValue::update_for_context(
+                ctx,
+                *attribute_value.id(),
+                None,
+                context,
+                variable.value.clone(),
+                None,
+            )
+            .await?;
+
+            exposed_socket_ids.push(*socket.id());
+        }
+
+        Ok(exposed_socket_ids)
+    }
+
+    /// Expose [`self`](Self) to every descendant of its frame, stopping at nested configuration
+    /// frames that define a variable of the same name.
+    pub async fn expose_to_descendants(&self, ctx: &DalContext) -> FrameVariableResult<()> {
+        let mut queue: VecDeque<(ComponentId, ComponentId)> =
+            Edge::list_children_for_component(ctx, self.component_id)
+                .await?
+                .into_iter()
+                .map(|child_id| (self.component_id, child_id))
+                .collect();
+
+        while let Some((frame_component_id, component_id)) = queue.pop_front() {
+            let shadowed = Self::list_for_component(ctx, frame_component_id)
+                .await?
+                .iter()
+                .any(|variable| variable.name == self.name && variable.id != self.id);
+            if shadowed {
+                continue;
+            }
+
+            Self::expose_variables_to_component(ctx, std::slice::from_ref(self), component_id, &[])
+                .await?;
+
+            let component = Component::get_by_id(ctx, &component_id)
+                .await?
+                .ok_or(FrameVariableError::ComponentNotFound(component_id))?;
+            if component.get_type(ctx).await?.is_configuration_frame_down() {
+                queue.extend(
+                    Edge::list_children_for_component(ctx, component_id)
+                        .await?
+                        .into_iter()
+                        .map(|child_id| (component_id, child_id)),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    standard_model_accessor!(component_id, Pk(ComponentId), FrameVariableResult);
+    standard_model_accessor!(name, String, FrameVariableResult);
+    standard_model_accessor!(value, OptionJson<JsonValue>, FrameVariableResult);
+    standard_model_accessor!(secret, bool, FrameVariableResult);
+}
@@ -10,6 +10,7 @@ use thiserror::Error;
 use veritech_client::CycloneValueEncryptError;
 
 use crate::func::argument::FuncArgumentError;
+use crate::func::cache::FuncCache;
 use crate::{
     generate_unique_id, impl_standard_model, pk, standard_model, standard_model_accessor,
     standard_model_accessor_ro, ChangeSetPk, DalContext, FuncBinding, HistoryEventError,
@@ -24,9 +25,12 @@ pub mod backend;
 pub mod before;
 pub mod binding;
 pub mod binding_return_value;
+pub mod cache;
 pub mod execution;
+pub mod execution_log;
 pub mod identity;
 pub mod intrinsics;
+pub mod lint;
 
 pub fn is_intrinsic(name: &str) -> bool {
     intrinsics::IntrinsicFunc::iter().any(|intrinsic| intrinsic.name() == name)
@@ -80,6 +84,8 @@ pub enum FuncError {
     TooManyFuncsFoundForIdentity,
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("veritech client error: {0}")]
+    VeritechClient(#[from] veritech_client::ClientError),
 }
 
 pub type FuncResult<T> = Result<T, FuncError>;
@@ -157,6 +163,7 @@ impl Func {
                 ],
             )
             .await?;
+        FuncCache::invalidate(ctx).await?;
         let object = standard_model::finish_create_from_row(ctx, row).await?;
         Ok(object)
     }
@@ -257,7 +264,22 @@ impl Func {
     }
 
     pub async fn find_by_name(ctx: &DalContext, name: &str) -> FuncResult<Option<Self>> {
-        Ok(Self::find_by_attr(ctx, "name", &name).await?.pop())
+        Ok(Self::find_all_by_name(ctx, name).await?.pop())
+    }
+
+    /// Finds every [`Func`] with the given name, reading through the [`FuncCache`] of the
+    /// current transactions.
+    pub async fn find_all_by_name(ctx: &DalContext, name: &str) -> FuncResult<Vec<Self>> {
+        if let Some(funcs) = ctx.txns().await?.func_cache().funcs_by_name(ctx, name) {
+            return Ok(funcs);
+        }
+
+        let funcs = Self::find_by_attr(ctx, "name", &name).await?;
+        ctx.txns()
+            .await?
+            .func_cache_mut()
+            .insert_funcs_by_name(ctx, name, &funcs);
+        Ok(funcs)
     }
 
     /// Returns `true` if this function is one handled internally by the `dal`, `false` if the
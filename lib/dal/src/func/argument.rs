@@ -7,6 +7,7 @@ use thiserror::Error;
 
 use si_pkg::FuncArgumentKind as PkgFuncArgumentKind;
 
+use crate::func::cache::FuncCache;
 use crate::{
     impl_standard_model, pk, standard_model, standard_model_accessor, AttributePrototypeArgument,
     AttributePrototypeArgumentError, AttributePrototypeId, DalContext, FuncId, HistoryEventError,
@@ -161,6 +162,7 @@ impl FuncArgument {
                 ],
             )
             .await?;
+        FuncCache::invalidate(ctx).await?;
 
         Ok(standard_model::finish_create_from_row(ctx, row).await?)
     }
@@ -236,21 +238,37 @@ impl FuncArgument {
         name: &str,
         func_id: FuncId,
     ) -> FuncArgumentResult<Option<Self>> {
-        Ok(
-            match ctx
-                .txns()
-                .await?
-                .pg()
-                .query_opt(
-                    FIND_BY_NAME_FOR_FUNC,
-                    &[ctx.tenancy(), ctx.visibility(), &name, &func_id],
-                )
+        if let Some(argument) = ctx
+            .txns()
+            .await?
+            .func_cache()
+            .argument_by_name(ctx, func_id, name)
+        {
+            return Ok(Some(argument));
+        }
+
+        let argument: Option<Self> = match ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                FIND_BY_NAME_FOR_FUNC,
+                &[ctx.tenancy(), ctx.visibility(), &name, &func_id],
+            )
+            .await?
+        {
+            Some(row) => standard_model::object_from_row(row)?,
+            None => None,
+        };
+
+        if let Some(argument) = &argument {
+            ctx.txns()
                 .await?
-            {
-                Some(row) => standard_model::object_from_row(row)?,
-                None => None,
-            },
-        )
+                .func_cache_mut()
+                .insert_argument_by_name(ctx, func_id, name, argument);
+        }
+
+        Ok(argument)
     }
 
     /// Remove the [`FuncArgument`](Self) along with any [`AttributePrototypeArgument`](crate::AttributePrototypeArgument) rows that reference it.
@@ -16,17 +16,23 @@ pub mod boolean;
 pub mod diff;
 pub mod identity;
 pub mod integer;
+pub mod join;
 pub mod js_action;
 pub mod js_attribute;
 pub mod js_reconciliation;
 pub mod js_schema_variant_definition;
 pub mod map;
 pub mod object;
+pub mod select;
 pub mod string;
+pub mod template;
+pub mod wasm_attribute;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum FuncBackendError {
+    #[error("base64 decode error: {0}")]
+    Base64Decode(#[from] base64::DecodeError),
     #[error("expected same array entry prop kinds - expected {0}, found: {1}")]
     DifferingArrayEntryPropKinds(PropKind, PropKind),
     #[error("dispatch func missing code_base64 {0}")]
@@ -37,6 +43,10 @@ pub enum FuncBackendError {
     FunctionResultActionRun(FunctionResult<ActionRunResultSuccess>),
     #[error("invalid data - expected a valid array entry value, got: {0}")]
     InvalidArrayEntryData(serde_json::Value),
+    #[error("invalid select path: {0}")]
+    InvalidSelectPath(String),
+    #[error("invalid template, a placeholder is not closed: {0}")]
+    InvalidTemplate(String),
     #[error("result failure: kind={kind}, message={message}, backend={backend}")]
     ResultFailure {
         kind: String,
@@ -47,10 +57,22 @@ pub enum FuncBackendError {
     SendError,
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("template placeholder selects no value: {0}")]
+    TemplateMissingValue(String),
     #[error("unable to decode ulid")]
     Ulid(#[from] ulid::DecodeError),
     #[error("veritech client error: {0}")]
     VeritechClient(#[from] veritech_client::ClientError),
+    #[error("wasm error: {0}")]
+    Wasm(#[from] wasmi::Error),
+    #[error("wasm execution task failed: {0}")]
+    WasmJoin(#[from] tokio::task::JoinError),
+    #[error("wasm module is missing the {0} export")]
+    WasmMissingExport(String),
+    #[error("wasm module accessed memory out of bounds")]
+    WasmOutOfBounds,
+    #[error("wasm execution exceeded its time limit of {0:?}")]
+    WasmTimeout(std::time::Duration),
 }
 
 pub type FuncBackendResult<T> = Result<T, FuncBackendError>;
@@ -77,6 +99,8 @@ pub enum FuncBackendKind {
     /// Mathematical identity of the [`Func`](crate::Func)'s arguments.
     Identity,
     Integer,
+    /// Joins values into a string, natively.
+    Join,
     JsAction,
     JsAttribute,
     JsAuthentication,
@@ -85,9 +109,15 @@ pub enum FuncBackendKind {
     JsValidation,
     Map,
     Object,
+    /// Selects values out of a JSON document with a path, natively.
+    Select,
     String,
+    /// Formats a string template, natively.
+    Template,
     Unset,
     Validation,
+    /// A pure transform compiled to WebAssembly, run in-process.
+    WasmAttribute,
 }
 
 #[remain::sorted]
@@ -0,0 +1,58 @@
+//! This module contains [`FuncBackendJoin`], which joins values into a string natively instead
+//! of dispatching to veritech.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{FuncBackend, FuncBackendResult};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendJoinArgs {
+    /// The values to join. A single value is joined as if it were the only element of an array.
+    pub values: Option<serde_json::Value>,
+    /// Put between every pair of values. Defaults to joining them without one.
+    pub separator: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendJoin {
+    args: FuncBackendJoinArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendJoin {
+    type Args = FuncBackendJoinArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let values = match self.args.values {
+            Some(serde_json::Value::Array(values)) => values,
+            Some(value) => vec![value],
+            None => Vec::new(),
+        };
+        let joined = values
+            .iter()
+            .filter(|value| !value.is_null())
+            .map(render)
+            .collect::<Vec<_>>()
+            .join(self.args.separator.as_deref().unwrap_or_default());
+
+        let value = serde_json::Value::String(joined);
+        Ok((Some(value.clone()), Some(value)))
+    }
+}
+
+/// Renders a value for interpolation into a string: strings as they are, `null` as nothing and
+/// everything else as compact JSON.
+pub(crate) fn render(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(string) => string.clone(),
+        serde_json::Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
@@ -11,9 +11,22 @@ use crate::func::backend::{
     ExtractPayload, FuncBackendError, FuncBackendResult, FuncDispatch, FuncDispatchContext,
 };
 
+/// The key of the [`FuncBackendJsActionArgs`] requesting a
+/// [dry run](veritech_client::ActionRunRequest::dry_run) when set to `true`.
+pub const DRY_RUN_ARG: &str = "dryRun";
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct FuncBackendJsActionArgs(serde_json::Value);
 
+impl FuncBackendJsActionArgs {
+    fn dry_run(&self) -> bool {
+        self.0
+            .get(DRY_RUN_ARG)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
 #[derive(Debug)]
 pub struct FuncBackendJsAction {
     pub context: FuncDispatchContext,
@@ -32,6 +45,7 @@ impl FuncDispatch for FuncBackendJsAction {
         args: Self::Args,
         before: Vec<BeforeFunction>,
     ) -> Box<Self> {
+        let dry_run = args.dry_run();
         let request = ActionRunRequest {
             // Once we start tracking the state of these executions, then this id will be useful,
             // but for now it's passed along and back, and is opaue
@@ -40,6 +54,7 @@ impl FuncDispatch for FuncBackendJsAction {
             code_base64: code_base64.into(),
             args: serde_json::to_value(args).unwrap(),
             before,
+            dry_run,
         };
 
         Box::new(Self { context, request })
@@ -95,6 +110,7 @@ impl FuncDispatch for FuncBackendJsAction {
                     status: ResourceStatus::Error,
                     message: Some(failure.error.message.clone()),
                     error: Some(serde_json::to_string(&failure.error)?),
+                    plan: Vec::new(),
                 })
             }
         };
@@ -118,6 +134,9 @@ pub struct ActionRunResult {
     pub logs: Vec<String>,
     #[serde(default)]
     pub last_synced: Option<String>,
+    /// What a dry run would have done. Always empty for actual runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plan: Vec<serde_json::Value>,
 }
 
 impl ExtractPayload for ActionRunResultSuccess {
@@ -130,6 +149,7 @@ impl ExtractPayload for ActionRunResultSuccess {
             message: self.message.or(self.error),
             logs: Default::default(),
             last_synced: Some(Utc::now().to_rfc3339()),
+            plan: self.plan,
         })
     }
 }
@@ -0,0 +1,148 @@
+//! This module contains [`FuncBackendSelect`], which selects values out of a JSON document with
+//! a path, natively instead of dispatching to veritech.
+//!
+//! Paths are a subset of JSONPath: an optional leading `$`, followed by `.key`, `['key']`,
+//! `[index]` and `[*]` (or `.*`) segments. A leading key needs no dot, so `a.b` is the same as
+//! `$.a.b`. A path without wildcards selects a single value (or nothing); a path with wildcards
+//! selects an array of every match.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{FuncBackend, FuncBackendError, FuncBackendResult};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendSelectArgs {
+    pub value: Option<serde_json::Value>,
+    pub path: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendSelect {
+    args: FuncBackendSelectArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendSelect {
+    type Args = FuncBackendSelectArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let segments = parse_path(&self.args.path)?;
+        let value = self.args.value.unwrap_or(serde_json::Value::Null);
+        let matches = select(&value, &segments);
+
+        let selected = if segments.contains(&PathSegment::Wildcard) {
+            Some(serde_json::Value::Array(
+                matches.into_iter().cloned().collect(),
+            ))
+        } else {
+            matches.into_iter().next().cloned()
+        };
+        Ok((selected.clone(), selected))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// Parses a path in the syntax described in the [`module`](self) documentation.
+pub(crate) fn parse_path(path: &str) -> FuncBackendResult<Vec<PathSegment>> {
+    let invalid = || FuncBackendError::InvalidSelectPath(path.to_owned());
+    let path = path.trim();
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    // A leading key may omit its dot.
+    if !rest.is_empty() && !rest.starts_with(['.', '[']) {
+        if path.starts_with('$') {
+            return Err(invalid());
+        }
+        let end = rest.find(['.', '[']).unwrap_or(rest.len());
+        segments.push(key_segment(&rest[..end]).ok_or_else(invalid)?);
+        rest = &rest[end..];
+    }
+
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            segments.push(key_segment(&after_dot[..end]).ok_or_else(invalid)?);
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket.find(']').ok_or_else(invalid)?;
+            let inner = after_bracket[..end].trim();
+            let segment = if inner == "*" {
+                PathSegment::Wildcard
+            } else if let Some(key) = quoted(inner) {
+                PathSegment::Key(key.to_owned())
+            } else {
+                PathSegment::Index(inner.parse().map_err(|_| invalid())?)
+            };
+            segments.push(segment);
+            rest = &after_bracket[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Every value at the end of the `segments`, in document order.
+pub(crate) fn select<'a>(
+    value: &'a serde_json::Value,
+    segments: &[PathSegment],
+) -> Vec<&'a serde_json::Value> {
+    let mut current = vec![value];
+    for segment in segments {
+        current = current
+            .into_iter()
+            .flat_map(|value| -> Vec<&serde_json::Value> {
+                match (segment, value) {
+                    (PathSegment::Key(key), serde_json::Value::Object(map)) => {
+                        map.get(key).into_iter().collect()
+                    }
+                    (PathSegment::Index(index), serde_json::Value::Array(items)) => {
+                        items.get(*index).into_iter().collect()
+                    }
+                    (PathSegment::Wildcard, serde_json::Value::Object(map)) => {
+                        map.values().collect()
+                    }
+                    (PathSegment::Wildcard, serde_json::Value::Array(items)) => {
+                        items.iter().collect()
+                    }
+                    _ => Vec::new(),
+                }
+            })
+            .collect();
+    }
+    current
+}
+
+fn key_segment(key: &str) -> Option<PathSegment> {
+    match key {
+        "" => None,
+        "*" => Some(PathSegment::Wildcard),
+        key => Some(PathSegment::Key(key.to_owned())),
+    }
+}
+
+fn quoted(inner: &str) -> Option<&str> {
+    inner
+        .strip_prefix('\'')
+        .and_then(|inner| inner.strip_suffix('\''))
+        .or_else(|| {
+            inner
+                .strip_prefix('"')
+                .and_then(|inner| inner.strip_suffix('"'))
+        })
+}
@@ -0,0 +1,64 @@
+//! This module contains [`FuncBackendTemplate`], which formats a string template natively
+//! instead of dispatching to veritech.
+//!
+//! Placeholders are written `{{path}}`, where the path selects a value out of the `values`
+//! argument with the syntax of [`FuncBackendSelect`](super::select::FuncBackendSelect). For
+//! example, `arn:aws:iam::{{accountId}}:role/{{name}}` with values
+//! `{ "accountId": "123456789012", "name": "deploy" }`. A placeholder selecting nothing is an
+//! error, rather than silently rendering an incomplete string.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::func::backend::{
+    join::render,
+    select::{parse_path, select},
+    FuncBackend, FuncBackendError, FuncBackendResult,
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendTemplateArgs {
+    pub template: String,
+    pub values: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendTemplate {
+    args: FuncBackendTemplateArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendTemplate {
+    type Args = FuncBackendTemplateArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let values = self.args.values.unwrap_or(serde_json::Value::Null);
+        let mut rendered = String::with_capacity(self.args.template.len());
+        let mut rest = self.args.template.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let after_open = &rest[start + 2..];
+            let end = after_open
+                .find("}}")
+                .ok_or_else(|| FuncBackendError::InvalidTemplate(self.args.template.clone()))?;
+            let path = after_open[..end].trim();
+            let value = select(&values, &parse_path(path)?)
+                .into_iter()
+                .next()
+                .ok_or_else(|| FuncBackendError::TemplateMissingValue(path.to_owned()))?;
+            rendered.push_str(&render(value));
+            rest = &after_open[end + 2..];
+        }
+        rendered.push_str(rest);
+
+        let value = serde_json::Value::String(rendered);
+        Ok((Some(value.clone()), Some(value)))
+    }
+}
@@ -0,0 +1,116 @@
+//! This module contains [`FuncBackendWasmAttribute`], which runs small, pure attribute
+//! transforms compiled to WebAssembly in-process instead of dispatching them to veritech.
+//!
+//! The module is instantiated without any imports, so it can only compute over its input. It
+//! must export a `memory`, an `alloc(len: i32) -> i32` function and the
+//! [`Func`](crate::Func)'s handler, with the signature `(ptr: i32, len: i32) -> i64`. The
+//! handler receives the JSON encoded arguments and returns where its JSON encoded result lives
+//! in memory, packed as `(ptr << 32) | len`. Execution is bounded by a fuel budget, a wall clock
+//! deadline and a cap on linear memory.
+
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use telemetry::prelude::*;
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::func::backend::{FuncBackendError, FuncBackendResult};
+use crate::{Func, StandardModel};
+
+/// The number of instructions (roughly) a single execution may run.
+const FUEL: u64 = 10_000_000;
+/// The most linear memory a module may grow to.
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+/// How long a single execution may take, including compiling the module. Fuel bounds the
+/// instructions run, but not the time spent in host code like growing memory.
+const MAX_DURATION: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct FuncBackendWasmAttribute {
+    code: Vec<u8>,
+    handler: String,
+    args: serde_json::Value,
+}
+
+impl FuncBackendWasmAttribute {
+    pub fn create(func: &Func, args: &serde_json::Value) -> FuncBackendResult<Self> {
+        let code_base64 = func
+            .code_base64()
+            .ok_or_else(|| FuncBackendError::DispatchMissingBase64(*func.id()))?;
+        let handler = func
+            .handler()
+            .ok_or_else(|| FuncBackendError::DispatchMissingHandler(*func.id()))?;
+        Ok(Self {
+            code: general_purpose::STANDARD_NO_PAD.decode(code_base64)?,
+            handler: handler.to_owned(),
+            args: args.clone(),
+        })
+    }
+
+    pub async fn create_and_execute(
+        func: &Func,
+        args: &serde_json::Value,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let executor = Self::create(func, args)?;
+        // The fuel budget bounds how long this runs, but it's still CPU bound work. Past the
+        // deadline the result is abandoned, and the fuel budget stops the task soon after.
+        let value = tokio::time::timeout(
+            MAX_DURATION,
+            tokio::task::spawn_blocking(move || executor.run()),
+        )
+        .await
+        .map_err(|_| FuncBackendError::WasmTimeout(MAX_DURATION))???;
+        Ok((Some(value.clone()), Some(value)))
+    }
+
+    #[instrument(name = "funcbackend.wasm_attribute.run", skip_all, level = "debug")]
+    fn run(&self) -> FuncBackendResult<serde_json::Value> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &self.code[..])?;
+
+        let mut store = Store::new(
+            &engine,
+            StoreLimitsBuilder::new()
+                .memory_size(MAX_MEMORY_BYTES)
+                .instances(1)
+                .memories(1)
+                .tables(1)
+                .build(),
+        );
+        store.limiter(|limits| limits);
+        store.add_fuel(FUEL).map_err(wasmi::Error::from)?;
+
+        // No host functions are defined, so a module importing anything fails to instantiate.
+        let linker = <Linker<StoreLimits>>::new(&engine);
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| FuncBackendError::WasmMissingExport("memory".to_owned()))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")?;
+        let handler = instance.get_typed_func::<(i32, i32), i64>(&store, &self.handler)?;
+
+        let input = serde_json::to_vec(&self.args)?;
+        let input_len =
+            i32::try_from(input.len()).map_err(|_| FuncBackendError::WasmOutOfBounds)?;
+        let input_ptr = alloc.call(&mut store, input_len)?;
+        memory
+            .write(&mut store, input_ptr as u32 as usize, &input)
+            .map_err(wasmi::Error::from)?;
+
+        let packed = handler.call(&mut store, (input_ptr, input_len))? as u64;
+        let output_ptr = (packed >> 32) as usize;
+        let output_len = (packed & 0xffff_ffff) as usize;
+        if output_ptr + output_len > memory.data(&store).len() {
+            return Err(FuncBackendError::WasmOutOfBounds);
+        }
+        let mut output = vec![0; output_len];
+        memory
+            .read(&store, output_ptr, &mut output)
+            .map_err(wasmi::Error::from)?;
+
+        Ok(serde_json::from_slice(&output)?)
+    }
+}
@@ -35,21 +35,44 @@ pub async fn before_funcs_for_component(
         func,
     } in standard_model::objects_from_rows(rows)?
     {
-        // Decrypt message from EncryptedSecret
-        let mut arg = encrypted_secret.decrypt(ctx).await?.message().into_inner();
-        // Re-encrypt raw Value for transmission to Cyclone via Veritech
-        encrypt_value_tree(&mut arg, ctx.encryption_key())?;
-
-        results.push(BeforeFunction {
-            handler: func
-                .handler
-                .ok_or_else(|| FuncError::MissingHandler(func.id))?,
-            code_base64: func
-                .code_base64
-                .ok_or_else(|| FuncError::MissingCode(func.id))?,
-            arg,
-        })
+        results.push(before_function(ctx, encrypted_secret, func).await?);
     }
 
     Ok(results)
 }
+
+/// Builds the before functions authenticating with a secret that is not used by any
+/// [`Component`](crate::Component) (e.g. to discover the resources the secret has access to),
+/// using the authentication [`Func`] with the given name.
+pub async fn before_funcs_for_secret(
+    ctx: &DalContext,
+    encrypted_secret: EncryptedSecret,
+    auth_func_name: &str,
+) -> FuncResult<Vec<BeforeFunction>> {
+    let func = Func::find_by_name(ctx, auth_func_name)
+        .await?
+        .ok_or_else(|| FuncError::NotFoundByName(auth_func_name.to_owned()))?;
+
+    Ok(vec![before_function(ctx, encrypted_secret, func).await?])
+}
+
+async fn before_function(
+    ctx: &DalContext,
+    encrypted_secret: EncryptedSecret,
+    func: Func,
+) -> FuncResult<BeforeFunction> {
+    // Decrypt message from EncryptedSecret
+    let mut arg = encrypted_secret.decrypt(ctx).await?.message().into_inner();
+    // Re-encrypt raw Value for transmission to Cyclone via Veritech
+    encrypt_value_tree(&mut arg, ctx.encryption_key())?;
+
+    Ok(BeforeFunction {
+        handler: func
+            .handler
+            .ok_or_else(|| FuncError::MissingHandler(func.id))?,
+        code_base64: func
+            .code_base64
+            .ok_or_else(|| FuncError::MissingCode(func.id))?,
+        arg,
+    })
+}
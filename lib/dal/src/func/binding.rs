@@ -1,3 +1,4 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use si_data_nats::NatsError;
@@ -16,13 +17,17 @@ use crate::{
         diff::FuncBackendDiff,
         identity::FuncBackendIdentity,
         integer::FuncBackendInteger,
+        join::FuncBackendJoin,
         js_action::FuncBackendJsAction,
         js_attribute::{FuncBackendJsAttribute, FuncBackendJsAttributeArgs},
         js_reconciliation::FuncBackendJsReconciliation,
         js_schema_variant_definition::FuncBackendJsSchemaVariantDefinition,
         map::FuncBackendMap,
         object::FuncBackendObject,
+        select::FuncBackendSelect,
         string::FuncBackendString,
+        template::FuncBackendTemplate,
+        wasm_attribute::FuncBackendWasmAttribute,
         FuncBackend, FuncDispatch, FuncDispatchContext, InvalidResolverFunctionTypeError,
     },
     TransactionsError, WsEvent, WsEventError, WsEventResult, WsPayload,
@@ -32,11 +37,12 @@ use crate::{
     Func, FuncBackendError, FuncBackendKind, HistoryEventError, StandardModel, StandardModelError,
     Timestamp, Visibility,
 };
-use crate::{DalContext, Tenancy};
+use crate::{AttributeValueId, ComponentId, DalContext, Tenancy};
 
 use super::{
     binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueError},
     execution::{FuncExecution, FuncExecutionError},
+    execution_log::{FuncExecutionLog, FuncExecutionLogError},
     FuncId,
 };
 
@@ -59,6 +65,8 @@ pub enum FuncBindingError {
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
     #[error("func execution tracking error: {0}")]
     FuncExecutionError(#[from] FuncExecutionError),
+    #[error("func execution log error: {0}")]
+    FuncExecutionLog(#[from] FuncExecutionLogError),
     #[error("unable to retrieve func for func binding: {0:?}")]
     FuncNotFound(FuncBindingPk),
     #[error("history event error: {0}")]
@@ -143,8 +151,13 @@ impl FuncBinding {
                 ],
             )
             .await?;
-        let object: FuncBinding = standard_model::finish_create_from_row(ctx, row).await?;
+        let mut object: FuncBinding = standard_model::finish_create_from_row(ctx, row).await?;
         object.set_func(ctx, &func_id).await?;
+
+        // The stored args are jsonb, which does not preserve the order of object keys. Keep the
+        // caller's args so that execution sees the keys in the order they were assembled in.
+        object.args = args;
+
         Ok(object)
     }
 
@@ -188,8 +201,32 @@ impl FuncBinding {
             .ok_or(FuncError::NotFound(func_id))?;
         let func_binding = Self::new(ctx, args, func_id, func.backend_kind).await?;
 
-        let func_binding_return_value: FuncBindingReturnValue =
-            func_binding.execute(ctx, before).await?;
+        let func_binding_return_value: FuncBindingReturnValue = func_binding
+            .execute(ctx, before, ComponentId::NONE, AttributeValueId::NONE)
+            .await?;
+
+        Ok((func_binding, func_binding_return_value))
+    }
+
+    /// Runs [`Self::new()`] and executes on behalf of a [`Component`](crate::Component), and
+    /// optionally of one of its [`AttributeValues`](crate::AttributeValue), so that the
+    /// [`FuncExecutionLog`] of the execution can be found for them.
+    pub async fn create_and_execute_for_component(
+        ctx: &DalContext,
+        args: serde_json::Value,
+        func_id: FuncId,
+        before: Vec<BeforeFunction>,
+        component_id: ComponentId,
+        attribute_value_id: AttributeValueId,
+    ) -> FuncBindingResult<(Self, FuncBindingReturnValue)> {
+        let func = Func::get_by_id(ctx, &func_id)
+            .await?
+            .ok_or(FuncError::NotFound(func_id))?;
+        let func_binding = Self::new(ctx, args, func_id, func.backend_kind).await?;
+
+        let func_binding_return_value: FuncBindingReturnValue = func_binding
+            .execute(ctx, before, component_id, attribute_value_id)
+            .await?;
 
         Ok((func_binding, func_binding_return_value))
     }
@@ -208,22 +245,38 @@ impl FuncBinding {
         result: FuncBindingResult,
     );
 
-    // For a given [`FuncBinding`](Self), execute using veritech.
+    // For a given [`FuncBinding`](Self), execute using veritech, recording a
+    // [`FuncExecutionLog`] whether or not the execution succeeds.
     async fn execute(
         &self,
         ctx: &DalContext,
         before: Vec<BeforeFunction>,
+        component_id: ComponentId,
+        attribute_value_id: AttributeValueId,
     ) -> FuncBindingResult<FuncBindingReturnValue> {
         let (func, execution, context, mut rx) = self.prepare_execution(ctx).await?;
-        let value = self
+        let started_at = Utc::now();
+        let result = self
             .execute_critical_section(func.clone(), context, before)
-            .await?;
+            .await;
 
         let mut output = Vec::new();
         while let Some(output_stream) = rx.recv().await {
             output.push(output_stream);
         }
 
+        FuncExecutionLog::record(
+            ctx,
+            &execution,
+            component_id,
+            attribute_value_id,
+            started_at,
+            &output,
+            result.as_ref().err().map(ToString::to_string),
+        )
+        .await?;
+        let value = result?;
+
         self.postprocess_execution(ctx, output, &func, value, execution)
             .await
     }
@@ -277,10 +330,16 @@ impl FuncBinding {
             FuncBackendKind::Identity => FuncBackendIdentity::create_and_execute(&self.args).await,
             FuncBackendKind::Diff => FuncBackendDiff::create_and_execute(&self.args).await,
             FuncBackendKind::Integer => FuncBackendInteger::create_and_execute(&self.args).await,
+            FuncBackendKind::Join => FuncBackendJoin::create_and_execute(&self.args).await,
             FuncBackendKind::Map => FuncBackendMap::create_and_execute(&self.args).await,
             FuncBackendKind::Object => FuncBackendObject::create_and_execute(&self.args).await,
+            FuncBackendKind::Select => FuncBackendSelect::create_and_execute(&self.args).await,
             FuncBackendKind::String => FuncBackendString::create_and_execute(&self.args).await,
+            FuncBackendKind::Template => FuncBackendTemplate::create_and_execute(&self.args).await,
             FuncBackendKind::Unset => Ok((None, None)),
+            FuncBackendKind::WasmAttribute => {
+                FuncBackendWasmAttribute::create_and_execute(&func, &self.args).await
+            }
             FuncBackendKind::Validation => {
                 unimplemented!("direct Validation function execution is deprecated")
             }
@@ -362,11 +421,15 @@ impl FuncBinding {
             | FuncBackendKind::Identity
             | FuncBackendKind::Diff
             | FuncBackendKind::Integer
+            | FuncBackendKind::Join
             | FuncBackendKind::Map
             | FuncBackendKind::Object
+            | FuncBackendKind::Select
             | FuncBackendKind::String
+            | FuncBackendKind::Template
             | FuncBackendKind::Unset
-            | FuncBackendKind::Validation => {}
+            | FuncBackendKind::Validation
+            | FuncBackendKind::WasmAttribute => {}
 
             FuncBackendKind::JsAction
             | FuncBackendKind::JsAttribute
@@ -0,0 +1,95 @@
+//! This module contains [`FuncCache`], a read-through cache for the [`Func`] and [`FuncArgument`]
+//! lookups that are performed repeatedly on hot paths (e.g. finding "si:identity" or a builtin by
+//! name for every prop during a migration), along with the [`IdentityFunc`].
+//!
+//! The cache lives on the [`Transactions`](crate::Transactions) of a [`DalContext`] and is dropped
+//! along with them on commit or rollback, so it never outlives the database transaction that
+//! populated it and can never observe another request's writes. Entries are keyed by
+//! [`Tenancy`] and [`Visibility`] and the whole cache is cleared whenever a [`Func`] or
+//! [`FuncArgument`] is mutated through the standard model (or a change set is applied).
+
+use std::collections::HashMap;
+
+use crate::func::argument::FuncArgument;
+use crate::func::identity::IdentityFunc;
+use crate::{DalContext, Func, FuncId, Tenancy, TransactionsError, Visibility};
+
+/// The tables whose mutations invalidate the [`FuncCache`].
+const CACHED_TABLES: &[&str] = &["funcs", "func_arguments"];
+
+type Scope = (Tenancy, Visibility);
+
+#[derive(Debug, Default)]
+pub struct FuncCache {
+    funcs_by_name: HashMap<(Scope, String), Vec<Func>>,
+    arguments_by_name: HashMap<(Scope, FuncId, String), FuncArgument>,
+    identities: HashMap<Scope, IdentityFunc>,
+}
+
+impl FuncCache {
+    /// Returns `true` if writes to the given table must invalidate the cache.
+    pub fn caches_table(table: &str) -> bool {
+        CACHED_TABLES.contains(&table)
+    }
+
+    pub fn clear(&mut self) {
+        self.funcs_by_name.clear();
+        self.arguments_by_name.clear();
+        self.identities.clear();
+    }
+
+    /// Clears the cache of the current transactions, if any have been started.
+    pub async fn invalidate(ctx: &DalContext) -> Result<(), TransactionsError> {
+        ctx.txns().await?.func_cache_mut().clear();
+        Ok(())
+    }
+
+    pub(crate) fn funcs_by_name(&self, ctx: &DalContext, name: &str) -> Option<Vec<Func>> {
+        self.funcs_by_name
+            .get(&(scope(ctx), name.to_owned()))
+            .cloned()
+    }
+
+    /// Only non-empty results are cached: a miss is cheap to repeat and caching it would hide
+    /// funcs created through paths that bypass the standard model.
+    pub(crate) fn insert_funcs_by_name(&mut self, ctx: &DalContext, name: &str, funcs: &[Func]) {
+        if !funcs.is_empty() {
+            self.funcs_by_name
+                .insert((scope(ctx), name.to_owned()), funcs.to_vec());
+        }
+    }
+
+    pub(crate) fn argument_by_name(
+        &self,
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: &str,
+    ) -> Option<FuncArgument> {
+        self.arguments_by_name
+            .get(&(scope(ctx), func_id, name.to_owned()))
+            .cloned()
+    }
+
+    pub(crate) fn insert_argument_by_name(
+        &mut self,
+        ctx: &DalContext,
+        func_id: FuncId,
+        name: &str,
+        argument: &FuncArgument,
+    ) {
+        self.arguments_by_name
+            .insert((scope(ctx), func_id, name.to_owned()), argument.clone());
+    }
+
+    pub(crate) fn identity(&self, ctx: &DalContext) -> Option<IdentityFunc> {
+        self.identities.get(&scope(ctx)).cloned()
+    }
+
+    pub(crate) fn insert_identity(&mut self, ctx: &DalContext, identity: &IdentityFunc) {
+        self.identities.insert(scope(ctx), identity.clone());
+    }
+}
+
+fn scope(ctx: &DalContext) -> Scope {
+    (*ctx.tenancy(), *ctx.visibility())
+}
@@ -284,6 +284,7 @@ impl FuncExecution {
     }
 
     standard_model_accessor_ro!(func_id, FuncId);
+    standard_model_accessor_ro!(func_binding_id, FuncBindingId);
     standard_model_accessor_ro!(function_failure, Option<FunctionResultFailure>);
     standard_model_accessor_ro!(func_binding_args, serde_json::Value);
     standard_model_accessor_ro!(handler, Option<String>);
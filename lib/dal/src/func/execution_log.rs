@@ -0,0 +1,249 @@
+//! This module contains [`FuncExecutionLog`], a record of the output and timing of a
+//! [`Func`](crate::Func) run by veritech.
+//!
+//! Logs are recorded as [`FuncBindings`](crate::FuncBinding) execute, whether or not the
+//! execution succeeds, so that users can debug why a code generation or qualification produced
+//! a result. Only the [`latest logs`](FUNC_EXECUTION_LOG_RETENTION) are kept for every
+//! [`Func`](crate::Func) and [`Component`](crate::Component).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use thiserror::Error;
+use veritech_client::OutputStream;
+
+use crate::func::binding::FuncBindingId;
+use crate::func::execution::{FuncExecution, FuncExecutionPk};
+use crate::{
+    pk, standard_model, AttributeValueId, ChangeSetPk, ComponentId, DalContext, FuncBackendKind,
+    FuncId, StandardModelError, TransactionsError, WorkspacePk,
+};
+
+const INSERT: &str = include_str!("../queries/func_execution_log/insert.sql");
+const PRUNE: &str = include_str!("../queries/func_execution_log/prune.sql");
+const LIST: &str = include_str!("../queries/func_execution_log/list.sql");
+
+/// How many logs are kept for a [`Func`](crate::Func) on a [`Component`](crate::Component), in a
+/// change set.
+pub const FUNC_EXECUTION_LOG_RETENTION: i64 = 20;
+/// The maximum number of logs returned by [`FuncExecutionLog::list()`].
+pub const FUNC_EXECUTION_LOG_LIST_LIMIT: i64 = 100;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum FuncExecutionLogError {
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type FuncExecutionLogResult<T> = Result<T, FuncExecutionLogError>;
+
+pk!(FuncExecutionLogPk);
+
+/// The output and timing of a single veritech run of a [`Func`](crate::Func).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FuncExecutionLog {
+    pk: FuncExecutionLogPk,
+    #[serde(rename = "tenancy_workspace_pk")]
+    workspace_pk: WorkspacePk,
+    #[serde(rename = "visibility_change_set_pk")]
+    change_set_pk: ChangeSetPk,
+    func_execution_pk: FuncExecutionPk,
+    func_id: FuncId,
+    func_binding_id: FuncBindingId,
+    component_id: ComponentId,
+    attribute_value_id: AttributeValueId,
+    backend_kind: FuncBackendKind,
+    success: bool,
+    failure: Option<String>,
+    stdout: Vec<String>,
+    stderr: Vec<String>,
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+    duration_ms: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl FuncExecutionLog {
+    pub fn pk(&self) -> FuncExecutionLogPk {
+        self.pk
+    }
+
+    pub fn change_set_pk(&self) -> ChangeSetPk {
+        self.change_set_pk
+    }
+
+    pub fn func_execution_pk(&self) -> FuncExecutionPk {
+        self.func_execution_pk
+    }
+
+    pub fn func_id(&self) -> FuncId {
+        self.func_id
+    }
+
+    pub fn func_binding_id(&self) -> FuncBindingId {
+        self.func_binding_id
+    }
+
+    /// The [`Component`](crate::Component) the [`Func`](crate::Func) ran for, or
+    /// [`ComponentId::NONE`] if it did not run for one.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// The [`AttributeValue`](crate::AttributeValue) the [`Func`](crate::Func) computed, or
+    /// [`AttributeValueId::NONE`] if it did not compute one.
+    pub fn attribute_value_id(&self) -> AttributeValueId {
+        self.attribute_value_id
+    }
+
+    pub fn backend_kind(&self) -> FuncBackendKind {
+        self.backend_kind
+    }
+
+    pub fn success(&self) -> bool {
+        self.success
+    }
+
+    /// Why the execution failed, if it did.
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+
+    pub fn stdout(&self) -> &[String] {
+        &self.stdout
+    }
+
+    pub fn stderr(&self) -> &[String] {
+        &self.stderr
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    pub fn finished_at(&self) -> DateTime<Utc> {
+        self.finished_at
+    }
+
+    pub fn duration_ms(&self) -> i64 {
+        self.duration_ms
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Record the output of a [`FuncExecution`] that started at `started_at` and just finished,
+    /// pruning the logs beyond the [`retention`](FUNC_EXECUTION_LOG_RETENTION). Lines of the
+    /// `stderr` stream are kept apart from every other line. Executions of builtin backends,
+    /// which do not run in veritech, are not recorded.
+    pub async fn record(
+        ctx: &DalContext,
+        execution: &FuncExecution,
+        component_id: ComponentId,
+        attribute_value_id: AttributeValueId,
+        started_at: DateTime<Utc>,
+        output: &[OutputStream],
+        failure: Option<String>,
+    ) -> FuncExecutionLogResult<Option<Self>> {
+        let workspace_pk = match ctx.tenancy().workspace_pk() {
+            Some(workspace_pk) => workspace_pk,
+            None => return Ok(None),
+        };
+        if !runs_in_veritech(*execution.backend_kind()) {
+            return Ok(None);
+        }
+
+        let mut lines = output.to_vec();
+        lines.sort_by_key(|line| line.timestamp);
+        let (stderr, stdout): (Vec<OutputStream>, Vec<OutputStream>) =
+            lines.into_iter().partition(|line| line.stream == "stderr");
+        let stdout: Vec<String> = stdout.into_iter().map(|line| line.message).collect();
+        let stderr: Vec<String> = stderr.into_iter().map(|line| line.message).collect();
+
+        let finished_at = Utc::now();
+        let duration_ms = (finished_at - started_at).num_milliseconds();
+
+        let txns = ctx.txns().await?;
+        let row = txns
+            .pg()
+            .query_one(
+                INSERT,
+                &[
+                    &workspace_pk,
+                    &ctx.visibility().change_set_pk,
+                    &execution.pk(),
+                    execution.func_id(),
+                    execution.func_binding_id(),
+                    &component_id,
+                    &attribute_value_id,
+                    &execution.backend_kind().to_string(),
+                    &failure.is_none(),
+                    &failure,
+                    &stdout,
+                    &stderr,
+                    &started_at,
+                    &finished_at,
+                    &duration_ms,
+                ],
+            )
+            .await?;
+        txns.pg()
+            .execute(
+                PRUNE,
+                &[
+                    &workspace_pk,
+                    &ctx.visibility().change_set_pk,
+                    execution.func_id(),
+                    &component_id,
+                    &FUNC_EXECUTION_LOG_RETENTION,
+                ],
+            )
+            .await?;
+
+        Ok(Some(standard_model::object_from_row(row)?))
+    }
+
+    /// List the most recent logs, optionally only those for a [`Component`](crate::Component)
+    /// and/or a [`Func`](crate::Func), most recent first. Logs recorded on head are included when
+    /// in a change set.
+    pub async fn list(
+        ctx: &DalContext,
+        component_id: Option<ComponentId>,
+        func_id: Option<FuncId>,
+    ) -> FuncExecutionLogResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST,
+                &[
+                    &ctx.tenancy().workspace_pk(),
+                    &ctx.visibility().change_set_pk,
+                    &component_id,
+                    &func_id,
+                    &FUNC_EXECUTION_LOG_LIST_LIMIT,
+                ],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+}
+
+fn runs_in_veritech(backend_kind: FuncBackendKind) -> bool {
+    matches!(
+        backend_kind,
+        FuncBackendKind::JsAction
+            | FuncBackendKind::JsAttribute
+            | FuncBackendKind::JsAuthentication
+            | FuncBackendKind::JsReconciliation
+            | FuncBackendKind::JsSchemaVariantDefinition
+            | FuncBackendKind::JsValidation
+    )
+}
@@ -10,14 +10,28 @@ use crate::{
 
 const IDENTITY_FUNC_NAME: &str = "si:identity";
 
+/// The identity [`Func`](crate::Func), its [`FuncArgument`](crate::FuncArgument), and the
+/// [`FuncBinding`](crate::FuncBinding) and
+/// [`FuncBindingReturnValue`](crate::FuncBindingReturnValue) of an identity of nothing, which
+/// every value and provider wired through the identity [`Func`](crate::Func) starts from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentityFunc {
+    pub func: Func,
+    pub func_argument: FuncArgument,
+    pub func_binding: FuncBinding,
+    pub func_binding_return_value: FuncBindingReturnValue,
+}
+
 impl Func {
-    /// Returns the identity [`Func`](Self) with its corresponding
-    /// [`FuncBinding`](crate::FuncBinding) and
-    /// [`FuncBindingReturnValue`](crate::FuncBindingReturnValue).
-    pub async fn identity_with_binding_and_return_value(
-        ctx: &DalContext,
-    ) -> FuncResult<(Func, FuncBinding, FuncBindingReturnValue)> {
-        let func = Self::identity_func(ctx).await?;
+    /// Returns the [`IdentityFunc`]. It is resolved (and its binding executed) once per
+    /// transactions, then read from the [`FuncCache`](crate::func::cache::FuncCache), so that
+    /// migrations and runtime wiring share the same binding rather than creating one each time.
+    pub async fn identity(ctx: &DalContext) -> FuncResult<IdentityFunc> {
+        if let Some(identity) = ctx.txns().await?.func_cache().identity(ctx) {
+            return Ok(identity);
+        }
+
+        let (func, func_argument) = Self::identity_with_argument(ctx).await?;
         let (func_binding, func_binding_return_value) = FuncBinding::create_and_execute(
             ctx,
             serde_json::json![{ "identity": null }],
@@ -27,7 +41,32 @@ impl Func {
         .await
         .map_err(|e| FuncError::FuncBinding(e.to_string()))?;
 
-        Ok((func, func_binding, func_binding_return_value))
+        let identity = IdentityFunc {
+            func,
+            func_argument,
+            func_binding,
+            func_binding_return_value,
+        };
+        ctx.txns()
+            .await?
+            .func_cache_mut()
+            .insert_identity(ctx, &identity);
+        Ok(identity)
+    }
+
+    /// Returns the identity [`Func`](Self) with its corresponding
+    /// [`FuncBinding`](crate::FuncBinding) and
+    /// [`FuncBindingReturnValue`](crate::FuncBindingReturnValue), as memoized by
+    /// [`Self::identity()`].
+    pub async fn identity_with_binding_and_return_value(
+        ctx: &DalContext,
+    ) -> FuncResult<(Func, FuncBinding, FuncBindingReturnValue)> {
+        let identity = Self::identity(ctx).await?;
+        Ok((
+            identity.func,
+            identity.func_binding,
+            identity.func_binding_return_value,
+        ))
     }
 
     /// Returns the identity [`Func`](Self) with its corresponding
@@ -42,7 +81,7 @@ impl Func {
 
     /// Returns the identity [`Func`](Self).
     pub async fn identity_func(ctx: &DalContext) -> FuncResult<Func> {
-        let mut found_funcs = Func::find_by_attr(ctx, "name", &IDENTITY_FUNC_NAME).await?;
+        let mut found_funcs = Func::find_all_by_name(ctx, IDENTITY_FUNC_NAME).await?;
         let func = found_funcs.pop().ok_or(FuncError::IdentityFuncNotFound)?;
         match found_funcs.is_empty() {
             true => Ok(func),
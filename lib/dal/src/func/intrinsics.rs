@@ -11,12 +11,15 @@ use strum::{AsRefStr, Display, EnumIter, EnumString, IntoEnumIterator};
 #[derive(AsRefStr, Display, EnumIter, EnumString, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IntrinsicFunc {
     Identity,
+    Join,
+    Select,
     SetArray,
     SetBoolean,
     SetInteger,
     SetMap,
     SetObject,
     SetString,
+    Template,
     Unset,
     Validation,
 }
@@ -66,6 +69,22 @@ impl IntrinsicFunc {
                         .map_err(|e| FuncError::IntrinsicSpecCreation(e.to_string()))?,
                 );
             }
+            Self::Join => {
+                builder
+                    .unique_id("5511653b390502651897708b045b6dcbb155e4284d82cf3094b53cc315f35b73");
+                data_builder.backend_kind(FuncSpecBackendKind::Join);
+                data_builder.response_type(FuncSpecBackendResponseType::String);
+                builder.argument(argument("values", FuncArgumentKind::Any)?);
+                builder.argument(argument("separator", FuncArgumentKind::String)?);
+            }
+            Self::Select => {
+                builder
+                    .unique_id("a76d8af8663307d18ed69fc975cfcdc31a2601c5fd87ea8a9428aae0a0d124f5");
+                data_builder.backend_kind(FuncSpecBackendKind::Select);
+                data_builder.response_type(FuncSpecBackendResponseType::Json);
+                builder.argument(argument("value", FuncArgumentKind::Any)?);
+                builder.argument(argument("path", FuncArgumentKind::String)?);
+            }
             Self::SetArray => {
                 builder
                     .unique_id("51049a590fb64860f159972012ac2657c629479a244d6bcc4b1b73ba4b29f87f");
@@ -102,6 +121,14 @@ impl IntrinsicFunc {
                 data_builder.backend_kind(FuncSpecBackendKind::String);
                 data_builder.response_type(FuncSpecBackendResponseType::String);
             }
+            Self::Template => {
+                builder
+                    .unique_id("e0e243a2e02b4d17887650a9fa2ef2ddcb1b97e7fe12baf7ea3ac2fe0969d2d1");
+                data_builder.backend_kind(FuncSpecBackendKind::Template);
+                data_builder.response_type(FuncSpecBackendResponseType::String);
+                builder.argument(argument("template", FuncArgumentKind::String)?);
+                builder.argument(argument("values", FuncArgumentKind::Any)?);
+            }
             Self::Unset => {
                 builder
                     .unique_id("8143ff98fbe8954bb3ab89ee521335d45ba9a42b7b79289eff53b503c4392c37");
@@ -129,12 +156,15 @@ impl IntrinsicFunc {
     pub fn name(&self) -> &str {
         match self {
             Self::Identity => "si:identity",
+            Self::Join => "si:join",
+            Self::Select => "si:select",
             Self::SetArray => "si:setArray",
             Self::SetBoolean => "si:setBoolean",
             Self::SetInteger => "si:setInteger",
             Self::SetMap => "si:setMap",
             Self::SetObject => "si:setObject",
             Self::SetString => "si:setString",
+            Self::Template => "si:template",
             Self::Unset => "si:unset",
             Self::Validation => "si:validation",
         }
@@ -143,12 +173,15 @@ impl IntrinsicFunc {
     pub fn maybe_from_str(s: &str) -> Option<Self> {
         Some(match s {
             "si:identity" => Self::Identity,
+            "si:join" => Self::Join,
+            "si:select" => Self::Select,
             "si:setArray" => Self::SetArray,
             "si:setBoolean" => Self::SetBoolean,
             "si:setInteger" => Self::SetInteger,
             "si:setMap" => Self::SetMap,
             "si:setObject" => Self::SetObject,
             "si:setString" => Self::SetString,
+            "si:template" => Self::Template,
             "si:unset" => Self::Unset,
             "si:validation" => Self::Validation,
             _ => {
@@ -157,3 +190,11 @@ impl IntrinsicFunc {
         })
     }
 }
+
+fn argument(name: &str, kind: FuncArgumentKind) -> FuncResult<FuncArgumentSpec> {
+    FuncArgumentSpec::builder()
+        .name(name)
+        .kind(kind)
+        .build()
+        .map_err(|e| FuncError::IntrinsicSpecCreation(e.to_string()))
+}
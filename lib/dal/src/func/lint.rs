@@ -0,0 +1,130 @@
+//! This module contains [`FuncCodeProblem`], the compile and lint feedback on the code of a JS
+//! [`Func`] that is being authored, so that problems surface before the code is saved rather than
+//! when the [`Func`] is first executed.
+//!
+//! The code is checked by veritech, with the same runtime that will execute it: it is compiled
+//! and run with a probe handler that only reports whether the [`Func`] handler is defined.
+
+use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use veritech_client::{FunctionResult, ResolverFunctionRequest, ResolverFunctionResponseType};
+
+use crate::{DalContext, Func, FuncBackendKind, FuncResult};
+
+/// The name of the handler that probes the code of the checked [`Func`].
+const PROBE_HANDLER: &str = "__siCheckFuncHandler";
+
+/// A problem found in the code of a [`Func`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncCodeProblem {
+    /// The kind of problem, e.g. "SyntaxError" or "MissingHandler".
+    pub kind: String,
+    pub message: String,
+}
+
+impl FuncCodeProblem {
+    fn new(kind: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Whether a handler can be referenced from the probe, i.e. whether it is a plain identifier.
+fn is_identifier(handler: &str) -> bool {
+    let mut chars = handler.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' || first == '$' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+impl Func {
+    /// Check code authored for this [`Func`] without saving it: veritech compiles it and looks
+    /// up the handler of the [`Func`]. Only JS [`Funcs`](Func) are checked; other backends never
+    /// report problems.
+    pub async fn check_code(
+        &self,
+        ctx: &DalContext,
+        code: &str,
+    ) -> FuncResult<Vec<FuncCodeProblem>> {
+        match self.backend_kind() {
+            FuncBackendKind::JsAction
+            | FuncBackendKind::JsAttribute
+            | FuncBackendKind::JsAuthentication
+            | FuncBackendKind::JsReconciliation
+            | FuncBackendKind::JsSchemaVariantDefinition
+            | FuncBackendKind::JsValidation => {}
+            _ => return Ok(Vec::new()),
+        }
+
+        let handler = match self.handler() {
+            Some(handler) if is_identifier(handler) => handler,
+            Some(handler) => {
+                return Ok(vec![FuncCodeProblem::new(
+                    "InvalidHandler",
+                    format!("handler \"{handler}\" is not a valid function name"),
+                )])
+            }
+            None => {
+                return Ok(vec![FuncCodeProblem::new(
+                    "MissingHandler",
+                    "func has no handler",
+                )])
+            }
+        };
+
+        let probe =
+            format!("{code}\nfunction {PROBE_HANDLER}() {{\n  return typeof {handler};\n}}\n");
+        let request = ResolverFunctionRequest {
+            execution_id: "checkfunc".to_owned(),
+            handler: PROBE_HANDLER.to_owned(),
+            component: Default::default(),
+            response_type: ResolverFunctionResponseType::String,
+            code_base64: general_purpose::STANDARD_NO_PAD.encode(probe),
+            before: Vec::new(),
+        };
+
+        // The output of the probe is of no interest, but veritech needs somewhere to send it.
+        let (output_tx, mut rx) = mpsc::channel(64);
+        tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+        let problems = match ctx
+            .veritech()
+            .execute_resolver_function(output_tx, &request)
+            .await?
+        {
+            FunctionResult::Failure(failure) => vec![FuncCodeProblem::new(
+                failure.error.kind,
+                failure.error.message,
+            )],
+            FunctionResult::Success(success) if success.data.as_str() != Some("function") => {
+                vec![FuncCodeProblem::new(
+                    "MissingHandler",
+                    format!("no function named \"{handler}\" is defined"),
+                )]
+            }
+            FunctionResult::Success(_) => Vec::new(),
+        };
+
+        Ok(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handler_identifiers() {
+        assert!(is_identifier("qualificationDockerImageExists"));
+        assert!(is_identifier("_generate$Code2"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("2fast"));
+        assert!(!is_identifier("main; process.exit()"));
+    }
+}
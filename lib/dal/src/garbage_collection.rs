@@ -0,0 +1,92 @@
+//! This module contains [`garbage_collect`], which hard-deletes the rows of standard model tables
+//! that were soft-deleted long enough ago that nothing can observe them anymore. It is run
+//! periodically by the [`GarbageCollector`](crate::tasks::GarbageCollector).
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{DalContext, TransactionsError};
+
+/// The standard model tables whose soft-deleted rows are garbage collected.
+pub const GARBAGE_COLLECTED_TABLES: &[&str] = &[
+    "attribute_values",
+    "attribute_value_belongs_to_attribute_value",
+    "attribute_value_belongs_to_attribute_prototype",
+    "attribute_prototypes",
+    "attribute_prototype_arguments",
+    "edges",
+];
+
+/// The maximum number of rows deleted per statement while collecting, to keep locks short.
+const GARBAGE_COLLECTION_BATCH_SIZE: i64 = 1000;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum GarbageCollectionError {
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type GarbageCollectionResult<T> = Result<T, GarbageCollectionError>;
+
+/// The number of rows reclaimed by a [`garbage_collect`] run, per table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GarbageCollectionReport {
+    pub reclaimed: BTreeMap<String, u64>,
+}
+
+impl GarbageCollectionReport {
+    /// The number of rows reclaimed in `table`.
+    pub fn reclaimed_for(&self, table: &str) -> u64 {
+        self.reclaimed.get(table).copied().unwrap_or_default()
+    }
+
+    /// The number of rows reclaimed across all tables.
+    pub fn total(&self) -> u64 {
+        self.reclaimed.values().sum()
+    }
+}
+
+/// Hard-delete the rows of the [`garbage collected tables`](GARBAGE_COLLECTED_TABLES) that were
+/// soft-deleted before `older_than`, in batches, across all workspaces. Rows deleted in a change
+/// set that is still open are kept, since they hide the matching rows of head.
+#[instrument(skip(ctx))]
+pub async fn garbage_collect(
+    ctx: &DalContext,
+    older_than: DateTime<Utc>,
+) -> GarbageCollectionResult<GarbageCollectionReport> {
+    let txns = ctx.txns().await?;
+    let mut report = GarbageCollectionReport::default();
+    for table in GARBAGE_COLLECTED_TABLES {
+        let mut reclaimed = 0;
+        loop {
+            let row = txns
+                .pg()
+                .query_one(
+                    "SELECT deleted FROM garbage_collect_table_v1($1, $2, $3)",
+                    &[table, &older_than, &GARBAGE_COLLECTION_BATCH_SIZE],
+                )
+                .await?;
+            let batch: i64 = row.try_get("deleted")?;
+            reclaimed += batch as u64;
+            if batch < GARBAGE_COLLECTION_BATCH_SIZE {
+                break;
+            }
+        }
+        if reclaimed > 0 {
+            debug!(%table, reclaimed, "garbage collected soft-deleted rows");
+        }
+        report.reclaimed.insert(table.to_string(), reclaimed);
+    }
+    info!(
+        reclaimed = report.total(),
+        "garbage collected soft-deleted rows"
+    );
+    Ok(report)
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
@@ -5,22 +6,33 @@ use strum::Display as StrumDisplay;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::{pk, DalContext, Timestamp, UserPk};
+use crate::{pk, standard_model, DalContext, StandardModelError, Timestamp, UserPk};
 use crate::{Tenancy, TransactionsError};
 
 pub use metadata::HistoryEventMetadata;
 
 mod metadata;
 
+const LIST_FOR_ENTITY: &str = include_str!("queries/history_event/list_for_entity.sql");
+
+/// The number of events in a [`HistoryEventPage`] when no limit is requested.
+pub const HISTORY_EVENT_PAGE_DEFAULT_LIMIT: usize = 50;
+/// The largest number of events in a [`HistoryEventPage`].
+pub const HISTORY_EVENT_PAGE_MAX_LIMIT: usize = 500;
+
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum HistoryEventError {
+    #[error("invalid time range: {0} is not before {1}")]
+    InvalidTimeRange(DateTime<Utc>, DateTime<Utc>),
     #[error("nats txn error: {0}")]
     Nats(#[from] NatsError),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
 }
@@ -51,6 +63,34 @@ impl From<UserPk> for HistoryActor {
 
 pk!(HistoryEventPk);
 
+/// Narrows down the events listed by [`HistoryEvent::list_for_entity`]. Unset filters match
+/// every event.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEventFilters {
+    /// Only list the events of this actor.
+    pub actor: Option<HistoryActor>,
+    /// Only list the events that happened at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only list the events that happened before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Only list the events whose label starts with this prefix, such as `"component."`.
+    pub label_prefix: Option<String>,
+    /// The [`HistoryEventPage::next_cursor`] of the previous page.
+    pub cursor: Option<HistoryEventPk>,
+    /// The number of events in a page, [`HISTORY_EVENT_PAGE_DEFAULT_LIMIT`] if unset.
+    pub limit: Option<usize>,
+}
+
+/// A page of events, newest first, listed by [`HistoryEvent::list_for_entity`].
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEventPage {
+    pub events: Vec<HistoryEvent>,
+    /// The cursor to list the next page from, unset if this is the last page.
+    pub next_cursor: Option<HistoryEventPk>,
+}
+
 /// HistoryEvents are the audit trail for things in SI. They track
 /// that a specific actor did something, and optionally store data
 /// associated with the activity for posterity.
@@ -92,4 +132,56 @@ impl HistoryEvent {
         let object: HistoryEvent = serde_json::from_value(json)?;
         Ok(object)
     }
+
+    /// List a page of the events of the current [`Workspace`](crate::Workspace) about an entity,
+    /// newest first. An event is about an entity when its data refers to the entity's id (or pk)
+    /// at the top level, which is the case for the events recorded when a
+    /// [`standard model`](crate::StandardModel) object is created, updated or deleted.
+    #[instrument(skip(ctx))]
+    pub async fn list_for_entity(
+        ctx: &DalContext,
+        entity_id: impl ToString + std::fmt::Debug,
+        filters: &HistoryEventFilters,
+    ) -> HistoryEventResult<HistoryEventPage> {
+        if let (Some(since), Some(until)) = (filters.since, filters.until) {
+            if since >= until {
+                return Err(HistoryEventError::InvalidTimeRange(since, until));
+            }
+        }
+        let limit = filters
+            .limit
+            .unwrap_or(HISTORY_EVENT_PAGE_DEFAULT_LIMIT)
+            .clamp(1, HISTORY_EVENT_PAGE_MAX_LIMIT);
+        let actor = filters.actor.map(serde_json::to_value).transpose()?;
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_ENTITY,
+                &[
+                    ctx.tenancy(),
+                    &entity_id.to_string(),
+                    &actor,
+                    &filters.since,
+                    &filters.until,
+                    &filters.label_prefix,
+                    &filters.cursor,
+                    &(limit as i64),
+                ],
+            )
+            .await?;
+        let events: Vec<HistoryEvent> = standard_model::objects_from_rows(rows)?;
+        let next_cursor = if events.len() == limit {
+            events.last().map(|event| event.pk)
+        } else {
+            None
+        };
+
+        Ok(HistoryEventPage {
+            events,
+            next_cursor,
+        })
+    }
 }
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 use crate::attribute::value::AttributeValueId;
+use crate::prop::MapKeyOrder;
 
 /// An IndexMap keeps track of which 'child' attribute resolvers of an
 /// Array or Map property exist, their order, and what keys (if any) they
@@ -47,6 +48,41 @@ impl IndexMap {
         self.key_map.remove(&attribute_value_id);
     }
 
+    /// Move an entry to `index` (or to the end, if `index` is past it). Does nothing if the entry
+    /// is not in the index map.
+    pub fn move_to(&mut self, attribute_value_id: AttributeValueId, index: usize) {
+        if let Some(current_index) = self.order.iter().position(|av| *av == attribute_value_id) {
+            self.order.remove(current_index);
+            let index = index.min(self.order.len());
+            self.order.insert(index, attribute_value_id);
+        }
+    }
+
+    /// Replace the order of the entries. Returns `false`, leaving the index map untouched, if
+    /// `order` does not contain exactly the entries already in the index map.
+    pub fn reorder(&mut self, order: Vec<AttributeValueId>) -> bool {
+        let mut current: Vec<AttributeValueId> = self.order.clone();
+        let mut new: Vec<AttributeValueId> = order.clone();
+        current.sort();
+        new.sort();
+        if current != new {
+            return false;
+        }
+        self.order = order;
+        true
+    }
+
+    /// Key every entry by its position in the order, which is how the entries of an array are
+    /// keyed. This should be called after removing or moving array entries.
+    pub fn reindex(&mut self) {
+        self.key_map = self
+            .order
+            .iter()
+            .enumerate()
+            .map(|(index, attribute_value_id)| (*attribute_value_id, index.to_string()))
+            .collect();
+    }
+
     /// Returns the order of attribute resolvers for this index map as
     /// array; it does not include the keys.
     pub fn order(&self) -> &[AttributeValueId] {
@@ -68,6 +104,22 @@ impl IndexMap {
             })
             .collect()
     }
+
+    /// Returns the order of attribute resolvers for this index map according to the given
+    /// [`MapKeyOrder`]. Entries with the same key keep their insertion order.
+    pub fn order_by(&self, map_key_order: MapKeyOrder) -> Vec<AttributeValueId> {
+        match map_key_order {
+            MapKeyOrder::Insertion => self.order.clone(),
+            MapKeyOrder::Key => {
+                let mut order_as_map = self.order_as_map();
+                order_as_map.sort_by(|(left, _), (right, _)| left.cmp(right));
+                order_as_map
+                    .into_iter()
+                    .map(|(_, attribute_value_id)| attribute_value_id)
+                    .collect()
+            }
+        }
+    }
 }
 
 impl<'a> postgres_types::FromSql<'a> for IndexMap {
@@ -144,4 +196,59 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn move_and_reorder_array() {
+        let mut index_map = IndexMap::new();
+
+        let first_id = AttributeValueId::generate();
+        let second_id = AttributeValueId::generate();
+        let third_id = AttributeValueId::generate();
+        index_map.push(first_id, None);
+        index_map.push(second_id, None);
+        index_map.push(third_id, None);
+
+        index_map.move_to(third_id, 0);
+        index_map.reindex();
+        assert_eq!(
+            index_map.order_as_map(),
+            &[
+                ("0".to_string(), third_id),
+                ("1".to_string(), first_id),
+                ("2".to_string(), second_id)
+            ]
+        );
+
+        assert!(!index_map.reorder(vec![first_id, second_id]));
+        assert_eq!(index_map.order(), &[third_id, first_id, second_id]);
+        assert!(index_map.reorder(vec![second_id, first_id, third_id]));
+        assert_eq!(index_map.order(), &[second_id, first_id, third_id]);
+
+        index_map.delete(first_id);
+        index_map.reindex();
+        assert_eq!(
+            index_map.order_as_map(),
+            &[("0".to_string(), second_id), ("1".to_string(), third_id)]
+        );
+    }
+
+    #[test]
+    fn order_by() {
+        let mut index_map = IndexMap::new();
+
+        let first_id = AttributeValueId::generate();
+        let second_id = AttributeValueId::generate();
+        let third_id = AttributeValueId::generate();
+        index_map.push(first_id, Some("spiritbox".to_string()));
+        index_map.push(second_id, Some("erra".to_string()));
+        index_map.push(third_id, Some("loathe".to_string()));
+        assert_eq!(
+            index_map.order_by(MapKeyOrder::Insertion),
+            vec![first_id, second_id, third_id]
+        );
+        assert_eq!(
+            index_map.order_by(MapKeyOrder::Key),
+            vec![second_id, third_id, first_id]
+        );
+    }
 }
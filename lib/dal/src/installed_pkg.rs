@@ -1,13 +1,19 @@
+use std::collections::{HashMap, HashSet};
+
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
+use si_pkg::{SiPkg, SiPkgMetadata};
 use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::{
-    impl_standard_model, pk, standard_model, standard_model_accessor, DalContext,
-    HistoryEventError, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError,
-    Visibility,
+    impl_standard_model, pk,
+    pkg::{import_pkg_from_pkg, ImportOptions, PkgError, PkgResult},
+    schema::variant::definition::SchemaVariantDefinition,
+    standard_model, standard_model_accessor, Component, DalContext, Func, HistoryEvent,
+    HistoryEventError, Schema, SchemaVariant, SchemaVariantId, StandardModel, StandardModelError,
+    Tenancy, Timestamp, TransactionsError, Visibility,
 };
 
 pub mod asset;
@@ -26,6 +32,8 @@ pub enum InstalledPkgError {
         InstalledPkgAssetKind,
         InstalledPkgAssetKind,
     ),
+    #[error("package {0} has unmet dependencies: {}", .1.join(", "))]
+    MissingDependency(String, Vec<String>),
     #[error("nats txn error: {0}")]
     Nats(#[from] NatsError),
     #[error("pg error: {0}")]
@@ -53,6 +61,7 @@ pub struct InstalledPkg {
     pk: InstalledPkgPk,
     id: InstalledPkgId,
     name: String,
+    version: Option<String>,
     root_hash: String,
     #[serde(flatten)]
     tenancy: Tenancy,
@@ -76,17 +85,19 @@ impl InstalledPkg {
     pub async fn new(
         ctx: &DalContext,
         name: impl AsRef<str>,
+        version: impl AsRef<str>,
         root_hash: impl AsRef<str>,
     ) -> InstalledPkgResult<Self> {
         let name = name.as_ref();
+        let version = version.as_ref();
         let root_hash = root_hash.as_ref();
         let row = ctx
             .txns()
             .await?
             .pg()
             .query_one(
-                "SELECT object FROM installed_pkg_create_v1($1, $2, $3, $4)",
-                &[ctx.tenancy(), ctx.visibility(), &name, &root_hash],
+                "SELECT object FROM installed_pkg_create_v2($1, $2, $3, $4, $5)",
+                &[ctx.tenancy(), ctx.visibility(), &name, &version, &root_hash],
             )
             .await?;
         let object = standard_model::finish_create_from_row(ctx, row).await?;
@@ -94,9 +105,271 @@ impl InstalledPkg {
     }
 
     standard_model_accessor!(name, String, InstalledPkgResult);
+    standard_model_accessor!(version, Option<String>, InstalledPkgResult);
     standard_model_accessor!(root_hash, String, InstalledPkgResult);
 
     pub async fn find_by_hash(ctx: &DalContext, hash: &str) -> InstalledPkgResult<Option<Self>> {
         Ok(Self::find_by_attr(ctx, "root_hash", &hash).await?.pop())
     }
+
+    /// Resolve the dependencies declared in the metadata of a package against the packages
+    /// installed in the workspace. A dependency pinned to a root hash is only satisfied by that
+    /// exact installation, otherwise any installed package with a matching name will do.
+    ///
+    /// Fails with [`InstalledPkgError::MissingDependency`] listing every unmet requirement.
+    pub async fn resolve_dependencies(
+        ctx: &DalContext,
+        metadata: &SiPkgMetadata,
+    ) -> InstalledPkgResult<Vec<Self>> {
+        let mut resolved = vec![];
+        let mut unmet = vec![];
+
+        for dependency in metadata.dependencies() {
+            let installed = match dependency.root_hash.as_deref() {
+                Some(root_hash) => Self::find_by_hash(ctx, root_hash)
+                    .await?
+                    .filter(|installed| installed.name() == dependency.name),
+                None => Self::find_by_attr(ctx, "name", &dependency.name)
+                    .await?
+                    .pop(),
+            };
+
+            match installed {
+                Some(installed) => resolved.push(installed),
+                None => unmet.push(match dependency.root_hash.as_deref() {
+                    Some(root_hash) => format!("{}@{root_hash}", dependency.name),
+                    None => dependency.name.to_owned(),
+                }),
+            }
+        }
+
+        if !unmet.is_empty() {
+            return Err(InstalledPkgError::MissingDependency(
+                metadata.name().to_owned(),
+                unmet,
+            ));
+        }
+
+        Ok(resolved)
+    }
+
+    /// Upgrade this installed package to `new_pkg`, which is expected to be a newer version of the
+    /// same package. Schemas whose contents are unchanged (by hash) are left alone and re-recorded
+    /// as assets of the new installation, while changed schemas get new
+    /// [`SchemaVariants`](crate::SchemaVariant) created on the existing [`Schema`] (when the spec
+    /// carries a unique id to match against). Schemas that did not exist in the previous version
+    /// are installed as usual.
+    ///
+    /// The previous installation record (and its asset records) is removed and the upgrade is
+    /// recorded as a [`HistoryEvent`].
+    pub async fn upgrade(
+        &self,
+        ctx: &DalContext,
+        new_pkg: &SiPkg,
+    ) -> PkgResult<InstalledPkgUpgrade> {
+        let new_root_hash = new_pkg.hash()?.to_string();
+        if new_root_hash == self.root_hash {
+            return Err(PkgError::PackageAlreadyInstalled(new_root_hash));
+        }
+
+        let metadata = new_pkg.metadata()?;
+
+        let mut previous_schema_hashes = HashSet::new();
+        let mut previous_schemas: HashMap<String, Schema> = HashMap::new();
+        let previous_assets = InstalledPkgAsset::list_for_installed_pkg_id(ctx, self.id).await?;
+        for asset in &previous_assets {
+            if let InstalledPkgAssetTyped::Schema { id, hash, .. } = asset.into() {
+                previous_schema_hashes.insert(hash);
+                if let Some(schema) = Schema::get_by_id(ctx, &id).await? {
+                    previous_schemas.insert(schema.name().to_owned(), schema);
+                }
+            }
+        }
+
+        let mut unchanged_schemas = vec![];
+        let mut upgraded_schemas = vec![];
+        let mut added_schemas = vec![];
+        let mut upgrade_schemas = HashMap::new();
+        for schema_spec in new_pkg.schemas()? {
+            let name = schema_spec.name().to_owned();
+            if previous_schema_hashes.contains(&schema_spec.hash().to_string()) {
+                unchanged_schemas.push(name);
+            } else if let Some(schema) = previous_schemas.get(&name) {
+                upgrade_schemas.insert(name.clone(), schema.to_owned());
+                upgraded_schemas.push(name);
+            } else {
+                added_schemas.push(name);
+            }
+        }
+
+        let (installed_pkg_id, schema_variant_ids, _) = import_pkg_from_pkg(
+            ctx,
+            new_pkg,
+            Some(ImportOptions {
+                upgrade_schemas: Some(upgrade_schemas),
+                ..Default::default()
+            }),
+            false,
+        )
+        .await?;
+        let installed_pkg_id = installed_pkg_id.ok_or(PkgError::PackageUpgradeNotRecorded(
+            metadata.name().to_owned(),
+        ))?;
+
+        for mut asset in previous_assets {
+            asset.delete_by_id(ctx).await?;
+        }
+        let mut previous = self.clone();
+        previous.delete_by_id(ctx).await?;
+
+        let upgrade = InstalledPkgUpgrade {
+            installed_pkg_id,
+            previous_installed_pkg_id: self.id,
+            name: metadata.name().to_owned(),
+            previous_version: self.version.clone(),
+            version: metadata.version().to_owned(),
+            unchanged_schemas,
+            upgraded_schemas,
+            added_schemas,
+            schema_variant_ids,
+        };
+
+        HistoryEvent::new(
+            ctx,
+            "installed_pkg.upgrade",
+            "Installed Package upgraded",
+            &serde_json::to_value(&upgrade)?,
+        )
+        .await
+        .map_err(InstalledPkgError::from)?;
+
+        Ok(upgrade)
+    }
+
+    /// Uninstall this package, removing the [`Schemas`](Schema),
+    /// [`SchemaVariants`](SchemaVariant), [`SchemaVariantDefinitions`](SchemaVariantDefinition)
+    /// and [`Funcs`](Func) it created. Assets that another installed package also recorded (by
+    /// hash) are left in place for it, as are intrinsic funcs.
+    ///
+    /// Fails with [`PkgError::PackageInUse`] if any [`Component`] still uses one of its schema
+    /// variants. The installation record and its asset records are removed and the uninstall is
+    /// recorded as a [`HistoryEvent`].
+    pub async fn uninstall(&self, ctx: &DalContext) -> PkgResult<InstalledPkgUninstall> {
+        let assets = InstalledPkgAsset::list_for_installed_pkg_id(ctx, self.id).await?;
+
+        let mut component_ids = vec![];
+        for asset in &assets {
+            if let InstalledPkgAssetTyped::SchemaVariant { id, .. } = asset.into() {
+                for component in Component::list_for_schema_variant(ctx, id).await? {
+                    component_ids.push(*component.id());
+                }
+            }
+        }
+        if !component_ids.is_empty() {
+            return Err(PkgError::PackageInUse(self.name.clone(), component_ids));
+        }
+
+        let mut removed_assets = vec![];
+        let mut shared_assets = vec![];
+        for mut asset in assets {
+            let mut shared = InstalledPkgAsset::list_for_kind_and_hash(
+                ctx,
+                *asset.asset_kind(),
+                asset.asset_hash(),
+            )
+            .await?
+            .iter()
+            .any(|other| {
+                other.installed_pkg_id() != self.id && other.asset_id() == asset.asset_id()
+            });
+
+            let typed: InstalledPkgAssetTyped = (&asset).into();
+            // Intrinsic funcs are used by every schema variant, whoever installed them.
+            if let InstalledPkgAssetTyped::Func { id, .. } = &typed {
+                if let Some(func) = Func::get_by_id(ctx, id).await? {
+                    shared |= func.is_intrinsic();
+                }
+            }
+            if shared {
+                shared_assets.push(typed);
+            } else {
+                match &typed {
+                    InstalledPkgAssetTyped::Func { id, .. } => {
+                        if let Some(mut func) = Func::get_by_id(ctx, id).await? {
+                            func.delete_by_id(ctx).await?;
+                        }
+                    }
+                    InstalledPkgAssetTyped::Schema { id, .. } => {
+                        if let Some(mut schema) = Schema::get_by_id(ctx, id).await? {
+                            schema.delete_by_id(ctx).await?;
+                        }
+                    }
+                    InstalledPkgAssetTyped::SchemaVariant { id, .. } => {
+                        if let Some(mut schema_variant) = SchemaVariant::get_by_id(ctx, id).await? {
+                            schema_variant.delete_by_id(ctx).await?;
+                        }
+                    }
+                    InstalledPkgAssetTyped::SchemaVariantDefinition { id, .. } => {
+                        if let Some(mut definition) =
+                            SchemaVariantDefinition::get_by_id(ctx, id).await?
+                        {
+                            definition.delete_by_id(ctx).await?;
+                        }
+                    }
+                }
+                removed_assets.push(typed);
+            }
+
+            asset.delete_by_id(ctx).await?;
+        }
+
+        let mut installed_pkg = self.clone();
+        installed_pkg.delete_by_id(ctx).await?;
+
+        let uninstall = InstalledPkgUninstall {
+            installed_pkg_id: self.id,
+            name: self.name.clone(),
+            version: self.version.clone(),
+            removed_assets,
+            shared_assets,
+        };
+
+        HistoryEvent::new(
+            ctx,
+            "installed_pkg.uninstall",
+            "Installed Package uninstalled",
+            &serde_json::to_value(&uninstall)?,
+        )
+        .await
+        .map_err(InstalledPkgError::from)?;
+
+        Ok(uninstall)
+    }
+}
+
+/// The outcome of [`InstalledPkg::upgrade`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPkgUpgrade {
+    pub installed_pkg_id: InstalledPkgId,
+    pub previous_installed_pkg_id: InstalledPkgId,
+    pub name: String,
+    pub previous_version: Option<String>,
+    pub version: String,
+    pub unchanged_schemas: Vec<String>,
+    pub upgraded_schemas: Vec<String>,
+    pub added_schemas: Vec<String>,
+    pub schema_variant_ids: Vec<SchemaVariantId>,
+}
+
+/// The outcome of [`InstalledPkg::uninstall`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPkgUninstall {
+    pub installed_pkg_id: InstalledPkgId,
+    pub name: String,
+    pub version: Option<String>,
+    pub removed_assets: Vec<InstalledPkgAssetTyped>,
+    /// Assets left in place because another installed package also uses them.
+    pub shared_assets: Vec<InstalledPkgAssetTyped>,
 }
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use si_data_nats::NatsError;
 use si_data_pg::{PgError, PgPoolError};
+use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
 
@@ -11,9 +12,10 @@ use crate::diagram::summary_diagram::SummaryDiagramError;
 use crate::{
     fix::FixError, func::binding_return_value::FuncBindingReturnValueError,
     job::producer::BlockingJobError, job::producer::JobProducerError, status::StatusUpdaterError,
-    AccessBuilder, ActionPrototypeError, ActionPrototypeId, AttributeValueError, ComponentError,
-    ComponentId, DalContext, DalContextBuilder, FixBatchId, FixResolverError, StandardModelError,
-    TransactionsError, Visibility, WsEventError,
+    AccessBuilder, ActionPrototypeError, ActionPrototypeId, AttributeValueError, ChangeSet,
+    ChangeSetError, ChangeSetPk, ChangeSetStatus, ComponentError, ComponentId, DalContext,
+    DalContextBuilder, FixBatchId, FixResolverError, HistoryEventError, StandardModelError,
+    TransactionsError, Visibility, WebhookError, WsEventError,
 };
 
 #[remain::sorted]
@@ -32,6 +34,8 @@ pub enum JobConsumerError {
     #[error("Error blocking on job: {0}")]
     BlockingJob(#[from] BlockingJobError),
     #[error(transparent)]
+    ChangeSet(#[from] ChangeSetError),
+    #[error(transparent)]
     Chrono(#[from] chrono::ParseError),
     #[error(transparent)]
     Component(#[from] ComponentError),
@@ -49,6 +53,8 @@ pub enum JobConsumerError {
     FixResolver(#[from] FixResolverError),
     #[error(transparent)]
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
+    #[error(transparent)]
+    HistoryEvent(#[from] HistoryEventError),
     #[error("Invalid job arguments. Expected: {0} Actual: {1:?}")]
     InvalidArguments(String, Vec<Value>),
     #[error(transparent)]
@@ -84,6 +90,8 @@ pub enum JobConsumerError {
     #[error(transparent)]
     UlidDecode(#[from] ulid::DecodeError),
     #[error(transparent)]
+    Webhook(#[from] WebhookError),
+    #[error(transparent)]
     WsEvent(#[from] WsEventError),
 }
 
@@ -128,6 +136,15 @@ pub trait JobConsumer: std::fmt::Debug + Sync + JobConsumerMetadata {
             .build(self.access_builder().build(self.visibility()))
             .await?;
 
+        if change_set_is_closed(&ctx).await? {
+            info!(
+                job = self.type_name(),
+                change_set_pk = %ctx.visibility().change_set_pk,
+                "skipping job for an abandoned or applied change set"
+            );
+            return Ok(());
+        }
+
         self.run(&mut ctx).await?;
 
         ctx.commit().await?;
@@ -135,3 +152,19 @@ pub trait JobConsumer: std::fmt::Debug + Sync + JobConsumerMetadata {
         Ok(())
     }
 }
+
+/// Returns whether or not the change set of the context has been abandoned or applied, in which
+/// case jobs enqueued for it no longer need to run.
+pub async fn change_set_is_closed(ctx: &DalContext) -> JobConsumerResult<bool> {
+    let change_set_pk = ctx.visibility().change_set_pk;
+    if change_set_pk == ChangeSetPk::NONE {
+        return Ok(false);
+    }
+    Ok(matches!(
+        ChangeSet::get_by_pk(ctx, &change_set_pk).await?,
+        Some(ChangeSet {
+            status: ChangeSetStatus::Abandoned | ChangeSetStatus::Applied,
+            ..
+        })
+    ))
+}
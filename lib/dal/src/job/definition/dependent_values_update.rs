@@ -6,6 +6,9 @@ use std::{collections::HashMap, collections::HashSet, convert::TryFrom};
 use telemetry::prelude::*;
 use tokio::task::JoinSet;
 
+use crate::component::owner::OwnerNotificationKind;
+use crate::provider::history::ProviderValueHistoryEntry;
+use crate::qualification::QualificationSummaryForComponent;
 use crate::tasks::StatusReceiverClient;
 use crate::tasks::StatusReceiverRequest;
 use crate::{diagram, ComponentId};
@@ -15,10 +18,13 @@ use crate::{
     },
     job::producer::{JobProducer, JobProducerResult},
     AccessBuilder, AttributeValue, AttributeValueError, AttributeValueId, AttributeValueResult,
-    DalContext, StandardModel, StatusUpdater, Visibility, WsEvent,
+    Component, DalContext, StandardModel, StatusUpdater, Visibility, Webhook, WebhookEventKind,
+    WsEvent,
 };
 use crate::{FuncBindingReturnValue, InternalProvider};
 
+const DEPENDENT_VALUES_UPDATE_TYPE_NAME: &str = "DependentValuesUpdate";
+
 #[derive(Debug, Deserialize, Serialize)]
 struct DependentValuesUpdateArgs {
     attribute_values: Vec<AttributeValueId>,
@@ -68,11 +74,41 @@ impl JobProducer for DependentValuesUpdate {
             self.clone(),
         ))?)
     }
+
+    /// Two [`DependentValuesUpdate`] jobs for the same access builder and visibility collapse into
+    /// one covering the union of their [`AttributeValueIds`](AttributeValueId), in enqueue order.
+    fn coalesce(
+        &self,
+        other: &(dyn JobProducer + Send + Sync),
+    ) -> JobProducerResult<Option<Box<dyn JobProducer + Send + Sync>>> {
+        if other.type_name() != self.type_name()
+            || other.access_builder() != self.access_builder
+            || other.visibility().to_non_deleted() != self.visibility
+        {
+            return Ok(None);
+        }
+
+        let other_args: DependentValuesUpdateArgs = serde_json::from_value(other.arg()?)?;
+
+        let mut seen: HashSet<AttributeValueId> = self.attribute_values.iter().copied().collect();
+        let mut attribute_values = self.attribute_values.clone();
+        for attribute_value_id in other_args.attribute_values {
+            if seen.insert(attribute_value_id) {
+                attribute_values.push(attribute_value_id);
+            }
+        }
+
+        Ok(Some(Self::new(
+            self.access_builder,
+            self.visibility,
+            attribute_values,
+        )))
+    }
 }
 
 impl JobConsumerMetadata for DependentValuesUpdate {
     fn type_name(&self) -> String {
-        "DependentValuesUpdate".to_string()
+        DEPENDENT_VALUES_UPDATE_TYPE_NAME.to_string()
     }
 
     fn access_builder(&self) -> AccessBuilder {
@@ -278,6 +314,7 @@ impl DependentValuesUpdate {
                                 task_ctx,
                                 attribute_value,
                                 pub_council.clone(),
+                                self.job_id(),
                                 Span::current(),
                             ));
                         }
@@ -391,6 +428,7 @@ async fn update_value(
     ctx: DalContext,
     mut attribute_value: AttributeValue,
     council: council_server::PubClient,
+    job_id: Option<String>,
     parent_span: Span,
 ) -> JobConsumerResult<()> {
     let update_result = attribute_value.update_from_prototype_function(&ctx).await;
@@ -402,6 +440,11 @@ async fn update_value(
             .failed_processing_value(attribute_value.id().into())
             .await?;
         ctx.rollback().await?;
+    } else if let Err(error) =
+        ProviderValueHistoryEntry::record(&ctx, &attribute_value, job_id.as_deref()).await
+    {
+        // The history is only a debugging aid, it must not keep the value from being processed.
+        warn!(%error, attribute_value_id = %attribute_value.id(), "could not record provider value history");
     }
 
     // If this is for an internal provider corresponding to a root prop for the schema variant of an existing component,
@@ -513,6 +556,35 @@ async fn update_summary_tables(
             }
         }
     }
+    // Only notify the owner when the component starts failing, not on every recompute of an
+    // already failing component, and only tell clients about counts that actually changed.
+    let previous_counts: Option<(i64, i64, i64, i64)> = ctx
+        .txns()
+        .await?
+        .pg()
+        .query_opt(
+            "SELECT total, warned, succeeded, failed FROM summary_qualifications
+             WHERE id = $1 AND tenancy_workspace_pk = $2 AND visibility_change_set_pk = $3",
+            &[
+                &component_id,
+                &ctx.tenancy().workspace_pk(),
+                &ctx.visibility().change_set_pk,
+            ],
+        )
+        .await?
+        .map(|row| {
+            Ok::<_, si_data_pg::PgError>((
+                row.try_get("total")?,
+                row.try_get("warned")?,
+                row.try_get("succeeded")?,
+                row.try_get("failed")?,
+            ))
+        })
+        .transpose()?;
+    let previously_failed = previous_counts
+        .map(|(.., failed)| failed)
+        .unwrap_or_default();
+
     let _row = ctx
         .txns()
         .await?
@@ -533,6 +605,24 @@ async fn update_summary_tables(
         )
         .await?;
 
+    if previous_counts != Some((total, warned, succeeded, failed)) || deleted_at.is_some() {
+        WsEvent::qualification_status_changed(
+            ctx,
+            QualificationSummaryForComponent {
+                component_id,
+                component_name: name.clone(),
+                total,
+                warned,
+                succeeded,
+                failed,
+            },
+            deleted_at.is_some(),
+        )
+        .await?
+        .publish_on_commit(ctx)
+        .await?;
+    }
+
     diagram::summary_diagram::component_update(
         ctx,
         &component_id,
@@ -544,6 +634,28 @@ async fn update_summary_tables(
     )
     .await?;
 
+    if failed > 0 && previously_failed == 0 && deleted_at_datetime.is_none() {
+        Component::notify_owner(
+            ctx,
+            component_id,
+            OwnerNotificationKind::QualificationFailed,
+            format!("{failed} of {total} qualifications failed for {name}"),
+        )
+        .await?;
+        Webhook::enqueue(
+            ctx,
+            WebhookEventKind::QualificationFailed,
+            serde_json::json!({
+                "componentId": component_id,
+                "componentName": name,
+                "changeSetPk": ctx.visibility().change_set_pk,
+                "total": total,
+                "failed": failed,
+            }),
+        )
+        .await?;
+    }
+
     WsEvent::component_updated(ctx, component_id)
         .await?
         .publish_on_commit(ctx)
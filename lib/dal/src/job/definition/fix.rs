@@ -17,7 +17,7 @@ use crate::{
     },
     AccessBuilder, ActionKind, ActionPrototype, ActionPrototypeId, Component, ComponentId,
     DalContext, Fix, FixBatch, FixBatchId, FixCompletionStatus, FixId, FixResolver, StandardModel,
-    Visibility, WsEvent,
+    Visibility, Webhook, WebhookEventKind, WsEvent,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -271,6 +271,24 @@ impl TryFrom<JobInfo> for FixesJob {
     }
 }
 
+/// What the [`ActionCompleted`](WebhookEventKind::ActionCompleted) webhook event says about a
+/// finished [`Fix`].
+fn action_completed_data(
+    fix: &Fix,
+    batch_id: FixBatchId,
+    action_kind: ActionKind,
+    completion_status: FixCompletionStatus,
+) -> serde_json::Value {
+    serde_json::json!({
+        "fixId": fix.id(),
+        "batchId": batch_id,
+        "componentId": fix.component_id(),
+        "actionKind": action_kind,
+        "completionStatus": completion_status,
+        "completionMessage": fix.completion_message(),
+    })
+}
+
 async fn finish_batch(ctx: &DalContext, id: FixBatchId) -> JobConsumerResult<()> {
     // Mark the batch as completed.
     let mut batch = FixBatch::get_by_id(ctx, &id)
@@ -316,6 +334,19 @@ async fn fix_task(
     let mut fix = Fix::get_by_id(&ctx, &fix_item.id)
         .await?
         .ok_or(FixError::MissingFix(fix_item.id))?;
+    // Let clients know the fix is running before waiting on it, since fixes can take a while.
+    WsEvent::fix_started(
+        &ctx,
+        *fix.id(),
+        batch_id,
+        fix_item.component_id,
+        *action.kind(),
+    )
+    .await?
+    .publish_on_commit(&ctx)
+    .await?;
+    ctx.commit().await?;
+
     let resource = fix.run(&ctx, &action).await?;
     let completion_status: FixCompletionStatus = *fix
         .completion_status()
@@ -350,6 +381,12 @@ async fn fix_task(
     .await?
     .publish_on_commit(&ctx)
     .await?;
+    Webhook::enqueue(
+        &ctx,
+        WebhookEventKind::ActionCompleted,
+        action_completed_data(&fix, batch_id, *action.kind(), completion_status),
+    )
+    .await?;
 
     // Commit progress so far, and wait for dependent values propagation so we can run
     // consecutive fixes that depend on the /root/resource from the previous fix.
@@ -415,6 +452,7 @@ async fn process_failed_fix_inner(
                     message: Some(err.clone()),
                     logs: logs.clone(),
                     last_synced: None,
+                    plan: Vec::new(),
                 };
 
                 fix.stamp_finished(
@@ -445,6 +483,12 @@ async fn process_failed_fix_inner(
             .await?
             .publish_on_commit(ctx)
             .await?;
+            Webhook::enqueue(
+                ctx,
+                WebhookEventKind::ActionCompleted,
+                action_completed_data(&fix, batch_id, *action.kind(), FixCompletionStatus::Error),
+            )
+            .await?;
         } else {
             warn!(%id, "fix not found by id");
         }
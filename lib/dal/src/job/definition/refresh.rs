@@ -5,14 +5,16 @@ use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 
 use crate::{
+    component::owner::OwnerNotificationKind,
     job::{
         consumer::{
             JobConsumer, JobConsumerError, JobConsumerMetadata, JobConsumerResult, JobInfo,
         },
         producer::{JobProducer, JobProducerResult},
     },
-    AccessBuilder, ActionKind, Component, ComponentId, DalContext, StandardModel, Visibility,
-    WsEvent,
+    workspace::digest::RESOURCE_DRIFTED_HISTORY_EVENT_LABEL,
+    AccessBuilder, ActionKind, Component, ComponentId, DalContext, HistoryEvent, StandardModel,
+    Visibility, WsEvent,
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -89,8 +91,38 @@ impl JobConsumer for RefreshJob {
             let component = Component::get_by_id(ctx, component_id)
                 .await?
                 .ok_or(JobConsumerError::ComponentNotFound(*component_id))?;
+            let previous_resource = component.resource(ctx).await?;
             component.act(ctx, ActionKind::Refresh).await?;
 
+            // A refresh that changes an existing resource means the real world drifted from
+            // what we last observed.
+            if previous_resource.payload.is_some() {
+                let resource = component.resource(ctx).await?;
+                if resource.payload != previous_resource.payload {
+                    let component_name = component.name(ctx).await?;
+
+                    // Recorded so that drift shows up in workspace activity digests.
+                    HistoryEvent::new(
+                        ctx,
+                        RESOURCE_DRIFTED_HISTORY_EVENT_LABEL,
+                        "Resource drifted",
+                        &serde_json::json![{
+                            "componentId": component.id(),
+                            "componentName": &component_name,
+                        }],
+                    )
+                    .await?;
+
+                    Component::notify_owner(
+                        ctx,
+                        *component.id(),
+                        OwnerNotificationKind::ResourceDrifted,
+                        format!("resource for {component_name} drifted on refresh"),
+                    )
+                    .await?;
+                }
+            }
+
             WsEvent::resource_refreshed(ctx, *component.id())
                 .await?
                 .publish_on_commit(ctx)
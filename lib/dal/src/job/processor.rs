@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{
     job::producer::{BlockingJobError, BlockingJobResult, JobProducer, JobProducerError},
     job::queue::JobQueue,
+    ChangeSetPk,
 };
 
 mod nats_processor;
@@ -34,6 +35,13 @@ pub trait JobQueueProcessor: std::fmt::Debug + DynClone {
     ) -> BlockingJobResult;
     async fn process_queue(&self, queue: JobQueue) -> JobQueueProcessorResult<()>;
     async fn blocking_process_queue(&self, queue: JobQueue) -> JobQueueProcessorResult<()>;
+    /// Ask the job executors to stop running the jobs dispatched for the given change set.
+    /// Jobs that have not started yet are skipped when they are picked up (see
+    /// [`JobConsumer::run_job`](crate::job::consumer::JobConsumer::run_job)).
+    async fn cancel_change_set_jobs(
+        &self,
+        change_set_pk: ChangeSetPk,
+    ) -> JobQueueProcessorResult<()>;
 }
 
 dyn_clone::clone_trait_object!(JobQueueProcessor);
@@ -10,29 +10,44 @@ use crate::job::{
     producer::{BlockingJobError, BlockingJobResult, JobProducer, JobProducerError},
     queue::JobQueue,
 };
+use crate::ChangeSetPk;
 
 use super::{JobQueueProcessor, JobQueueProcessorError, JobQueueProcessorResult};
 
 const NATS_JOB_QUEUE: &str = "pinga-jobs";
+const NATS_JOB_CANCELLATION: &str = "pinga-jobs-cancel";
 
 #[derive(Clone, Debug)]
 pub struct NatsProcessor {
     client: NatsClient,
     pinga_subject: Subject,
+    pinga_cancellation_subject: Subject,
 }
 
 impl NatsProcessor {
     pub fn new(client: NatsClient) -> Self {
-        let pinga_subject = if let Some(prefix) = client.metadata().subject_prefix() {
-            format!("{prefix}.{NATS_JOB_QUEUE}").into()
-        } else {
-            NATS_JOB_QUEUE.into()
-        };
+        let (pinga_subject, pinga_cancellation_subject) =
+            if let Some(prefix) = client.metadata().subject_prefix() {
+                (
+                    format!("{prefix}.{NATS_JOB_QUEUE}").into(),
+                    format!("{prefix}.{NATS_JOB_CANCELLATION}").into(),
+                )
+            } else {
+                (NATS_JOB_QUEUE.into(), NATS_JOB_CANCELLATION.into())
+            };
 
         Self {
             client,
             pinga_subject,
+            pinga_cancellation_subject,
+        }
+    }
+
+    async fn cancel_all_change_set_jobs(&self, queue: &JobQueue) -> JobQueueProcessorResult<()> {
+        for change_set_pk in queue.drain_cancelled_change_sets().await {
+            self.cancel_change_set_jobs(change_set_pk).await?;
         }
+        Ok(())
     }
 
     #[instrument(
@@ -165,6 +180,7 @@ impl JobQueueProcessor for NatsProcessor {
         let span = Span::current();
         span.record("queue.size", queue.size().await);
 
+        self.cancel_all_change_set_jobs(&queue).await?;
         self.push_all_jobs(queue).await?;
 
         Ok(())
@@ -182,10 +198,34 @@ impl JobQueueProcessor for NatsProcessor {
         let span = Span::current();
         span.record("queue.size", queue.size().await);
 
+        self.cancel_all_change_set_jobs(&queue).await?;
         self.block_on_jobs(queue.drain().await)
             .instrument(info_span!("nats_processor.block_on_jobs"))
             .await?;
 
         Ok(())
     }
+
+    #[instrument(
+        name = "nats_processor.cancel_change_set_jobs",
+        level = "info",
+        skip_all,
+        fields(change_set_pk = %change_set_pk)
+    )]
+    async fn cancel_change_set_jobs(
+        &self,
+        change_set_pk: ChangeSetPk,
+    ) -> JobQueueProcessorResult<()> {
+        let mut headers = HeaderMap::new();
+        inject_headers(&mut headers);
+
+        self.client
+            .publish_with_headers(
+                self.pinga_cancellation_subject.clone(),
+                headers,
+                serde_json::to_vec(&change_set_pk)?.into(),
+            )
+            .await
+            .map_err(|err| JobQueueProcessorError::Transport(Box::new(err)))
+    }
 }
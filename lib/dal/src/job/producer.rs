@@ -18,6 +18,16 @@ pub type JobProducerResult<T> = Result<T, JobProducerError>;
 
 pub trait JobProducer: std::fmt::Debug + Send + JobConsumerMetadata {
     fn arg(&self) -> JobProducerResult<serde_json::Value>;
+
+    /// Attempt to merge `other` into this job, returning the combined job if both can be
+    /// represented by a single one. Used by the [`JobQueue`](crate::job::queue::JobQueue) to
+    /// collapse redundant jobs enqueued within the same transaction.
+    fn coalesce(
+        &self,
+        _other: &(dyn JobProducer + Send + Sync),
+    ) -> JobProducerResult<Option<Box<dyn JobProducer + Send + Sync>>> {
+        Ok(None)
+    }
 }
 
 pub type BlockingJobResult = Result<(), BlockingJobError>;
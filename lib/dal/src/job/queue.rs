@@ -1,25 +1,74 @@
 use super::producer::JobProducer;
+use crate::ChangeSetPk;
 use std::{collections::VecDeque, sync::Arc};
+use telemetry::prelude::*;
 use tokio::sync::Mutex;
 
 #[derive(Debug, Clone, Default)]
 pub struct JobQueue {
     queue: Arc<Mutex<VecDeque<Box<dyn JobProducer + Send + Sync>>>>,
+    cancelled_change_sets: Arc<Mutex<Vec<ChangeSetPk>>>,
 }
 
 impl JobQueue {
     pub fn new() -> Self {
         Self {
             queue: Default::default(),
+            cancelled_change_sets: Default::default(),
         }
     }
 
+    /// Add a job to the queue. If a queued job can absorb it (see
+    /// [`JobProducer::coalesce`]), the two are merged in place instead, so that multiple
+    /// enqueues within one commit result in a single job.
     pub async fn enqueue_job(&self, job: Box<dyn JobProducer + Send + Sync>) {
+        if self
+            .cancelled_change_sets
+            .lock()
+            .await
+            .contains(&job.visibility().change_set_pk)
+        {
+            debug!(job = ?job, "dropping job for a cancelled change set");
+            return;
+        }
+
         let mut lock = self.queue.lock().await;
 
+        for queued in lock.iter_mut() {
+            match queued.coalesce(job.as_ref()) {
+                Ok(Some(coalesced)) => {
+                    *queued = coalesced;
+                    return;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(error = ?err, "unable to coalesce job, enqueueing it separately");
+                }
+            }
+        }
+
         lock.push_back(job);
     }
 
+    /// Drop the queued jobs for the given change set and record it, so that the
+    /// [`JobQueueProcessor`](crate::job::processor::JobQueueProcessor) cancels the jobs already
+    /// dispatched for it once the queue is processed.
+    pub async fn cancel_change_set_jobs(&self, change_set_pk: ChangeSetPk) {
+        self.queue
+            .lock()
+            .await
+            .retain(|job| job.visibility().change_set_pk != change_set_pk);
+
+        let mut cancelled_change_sets = self.cancelled_change_sets.lock().await;
+        if !cancelled_change_sets.contains(&change_set_pk) {
+            cancelled_change_sets.push(change_set_pk);
+        }
+    }
+
+    pub async fn drain_cancelled_change_sets(&self) -> Vec<ChangeSetPk> {
+        std::mem::take(&mut *self.cancelled_change_sets.lock().await)
+    }
+
     pub async fn fetch_job(&self) -> Option<Box<dyn JobProducer + Send + Sync>> {
         self.queue.lock().await.pop_front()
     }
@@ -18,6 +18,7 @@ pub use action_prototype::{
     ActionPrototypeView,
 };
 pub use actor_view::ActorView;
+pub use api_token::{ApiToken, ApiTokenError, ApiTokenPk, ApiTokenResult, ApiTokenScope};
 pub use attribute::value::view::AttributeView;
 pub use attribute::{
     context::{
@@ -36,22 +37,33 @@ pub use attribute::{
         AttributeValueResult,
     },
 };
-pub use builtins::{BuiltinsError, BuiltinsResult};
-pub use change_set::{ChangeSet, ChangeSetError, ChangeSetPk, ChangeSetStatus};
+pub use builtins::{
+    BuiltinSchemaFamily, BuiltinSchemaMigrationMode, BuiltinsError, BuiltinsResult,
+};
+pub use change_set::{ChangeSet, ChangeSetConflict, ChangeSetError, ChangeSetPk, ChangeSetStatus};
 pub use code_view::{CodeLanguage, CodeView};
 pub use component::{
     resource::ResourceView, status::ComponentStatus, status::HistoryActorTimestamp, Component,
     ComponentError, ComponentId, ComponentView, ComponentViewProperties,
 };
+pub use confirmation::{
+    prototype::{
+        ConfirmationPrototype, ConfirmationPrototypeContext, ConfirmationPrototypeError,
+        ConfirmationPrototypeId,
+    },
+    resolver::{ConfirmationResolver, ConfirmationResolverError, ConfirmationResolverId},
+    ConfirmationOutput, RecommendedAction,
+};
 pub use context::{
-    AccessBuilder, Connections, DalContext, DalContextBuilder, RequestContext, ServicesContext,
-    Transactions, TransactionsError,
+    AccessBuilder, CommitHookError, Connections, DalContext, DalContextBuilder, RequestContext,
+    ServicesContext, Transactions, TransactionsError,
 };
-pub use diagram::{connection::Connection, Diagram, DiagramError, DiagramKind};
+pub use diagram::{connection::Connection, Diagram, DiagramError, DiagramKind, DiagramPage};
 pub use edge::{Edge, EdgeError, EdgeResult};
-pub use fix::batch::{FixBatch, FixBatchId};
+pub use fix::batch::{FixBatch, FixBatchId, FixBatchTargetFilter};
 pub use fix::resolver::{FixResolver, FixResolverError, FixResolverId};
 pub use fix::{Fix, FixCompletionStatus, FixError, FixId};
+pub use frame_variable::{FrameVariable, FrameVariableError, FrameVariableId};
 pub use func::argument::FuncArgument;
 pub use func::binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueError};
 pub use func::{
@@ -59,7 +71,13 @@ pub use func::{
     binding::{FuncBinding, FuncBindingError, FuncBindingId},
     Func, FuncError, FuncId, FuncResult,
 };
-pub use history_event::{HistoryActor, HistoryEvent, HistoryEventError};
+pub use garbage_collection::{
+    garbage_collect, GarbageCollectionError, GarbageCollectionReport, GarbageCollectionResult,
+};
+pub use history_event::{
+    HistoryActor, HistoryEvent, HistoryEventError, HistoryEventFilters, HistoryEventPage,
+    HistoryEventPk,
+};
 pub use index_map::IndexMap;
 pub use job::definition::DependentValuesUpdate;
 pub use job::processor::{JobQueueProcessor, NatsProcessor};
@@ -70,7 +88,9 @@ pub use label_list::{LabelEntry, LabelList, LabelListError};
 pub use node::NodeId;
 pub use node::{Node, NodeError, NodeKind};
 pub use node_menu::NodeMenuError;
-pub use prop::{Prop, PropError, PropId, PropKind, PropPk, PropResult};
+pub use permission::Permission;
+pub use prop::{MapKeyOrder, Prop, PropError, PropId, PropKind, PropPk, PropResult};
+pub use prop_permission::{PropAccess, PropPermission, PropPermissionError, PropPermissionResult};
 pub use prototype_context::HasPrototypeContext;
 pub use prototype_list_for_func::{
     PrototypeListForFunc, PrototypeListForFuncError, PrototypeListForFuncResult,
@@ -82,6 +102,7 @@ pub use reconciliation_prototype::{
     ReconciliationPrototype, ReconciliationPrototypeContext, ReconciliationPrototypeError,
     ReconciliationPrototypeId,
 };
+pub use resource_identifier::{ResourceIdentifier, ResourceIdentifierError, ResourceIdentifierId};
 pub use schema::variant::leaves::LeafInput;
 pub use schema::variant::leaves::LeafInputLocation;
 pub use schema::variant::leaves::LeafKind;
@@ -91,12 +112,15 @@ pub use schema::variant::root_prop::RootPropChild;
 pub use schema::variant::SchemaVariantError;
 pub use schema::{Schema, SchemaError, SchemaId, SchemaPk, SchemaVariant, SchemaVariantId};
 pub use secret::{
-    DecryptedSecret, EncryptedSecret, Secret, SecretAlgorithm, SecretError, SecretId, SecretPk,
-    SecretResult, SecretVersion,
+    DecryptedSecret, EncryptedSecret, NewSecretValue, Secret, SecretAlgorithm, SecretError,
+    SecretId, SecretPk, SecretResult, SecretVersion,
 };
 use si_data_nats::{NatsClient, NatsError};
 use si_data_pg::{PgError, PgPool, PgPoolError};
 pub use socket::{Socket, SocketArity, SocketId};
+pub use socket_merge_strategy::{
+    MergeStrategy, SocketMergeStrategy, SocketMergeStrategyError, SocketMergeStrategyId,
+};
 pub use standard_model::{StandardModel, StandardModelError, StandardModelResult};
 pub use status::{
     StatusUpdate, StatusUpdateError, StatusUpdateResult, StatusUpdater, StatusUpdaterError,
@@ -104,9 +128,13 @@ pub use status::{
 use telemetry::prelude::*;
 pub use tenancy::{Tenancy, TenancyError};
 pub use timestamp::{Timestamp, TimestampError};
-pub use user::{User, UserClaim, UserError, UserPk, UserResult};
+pub use user::{User, UserClaim, UserError, UserPk, UserResult, WorkspaceRole};
 use veritech_client::CycloneEncryptionKey;
 pub use visibility::{Visibility, VisibilityError};
+pub use webhook::{
+    Webhook, WebhookDelivery, WebhookDeliveryPk, WebhookDeliveryStatus, WebhookError,
+    WebhookEventKind, WebhookPk, WebhookResult,
+};
 pub use workspace::{Workspace, WorkspaceError, WorkspacePk, WorkspaceResult, WorkspaceSignup};
 pub use ws_event::{WsEvent, WsEventError, WsEventResult, WsPayload};
 
@@ -115,18 +143,24 @@ use crate::builtins::SelectedTestBuiltinSchemas;
 pub mod action;
 pub mod action_prototype;
 pub mod actor_view;
+pub mod api_token;
 pub mod attribute;
 pub mod authentication_prototype;
 pub mod builtins;
 pub mod change_set;
 pub mod change_status;
+pub mod cloudformation;
 pub mod code_view;
 pub mod component;
+pub mod confirmation;
 pub mod context;
 pub mod diagram;
+pub mod discovery;
 pub mod edge;
 pub mod fix;
+pub mod frame_variable;
 pub mod func;
+pub mod garbage_collection;
 pub mod history_event;
 pub mod index_map;
 pub mod installed_pkg;
@@ -137,8 +171,10 @@ pub mod key_pair;
 pub mod label_list;
 pub mod node;
 pub mod node_menu;
+pub mod permission;
 pub mod pkg;
 pub mod prop;
+pub mod prop_permission;
 pub mod prop_tree;
 pub mod property_editor;
 pub mod prototype_context;
@@ -146,10 +182,12 @@ pub mod prototype_list_for_func;
 pub mod provider;
 pub mod qualification;
 pub mod reconciliation_prototype;
+pub mod resource_identifier;
 pub mod schema;
 pub mod secret;
 pub mod serde_impls;
 pub mod socket;
+pub mod socket_merge_strategy;
 pub mod standard_accessors;
 pub mod standard_model;
 pub mod standard_pk;
@@ -159,6 +197,7 @@ pub mod tenancy;
 pub mod timestamp;
 pub mod user;
 pub mod visibility;
+pub mod webhook;
 pub mod workspace;
 pub mod ws_event;
 
@@ -0,0 +1,27 @@
+ALTER TABLE fix_batches
+ADD COLUMN target_filter jsonb;
+
+CREATE OR REPLACE FUNCTION fix_batch_create_v2(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_author text,
+    this_actors text,
+    this_target_filter jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           fix_batches%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+INSERT INTO fix_batches (tenancy_workspace_pk, visibility_change_set_pk, author, actors, target_filter)
+VALUES (this_tenancy_record.tenancy_workspace_pk,
+        this_visibility_record.visibility_change_set_pk, this_author, this_actors, this_target_filter)
+    RETURNING * INTO this_new_row;
+
+object := row_to_json(this_new_row);
+END
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,2 @@
+ALTER TABLE components
+ADD COLUMN owner jsonb;
@@ -0,0 +1,10 @@
+CREATE TABLE workspace_digest_configs
+(
+    workspace_pk                ident primary key REFERENCES workspaces (pk),
+    enabled                     bool                     NOT NULL DEFAULT TRUE,
+    window_hours                integer                  NOT NULL DEFAULT 24,
+    webhook_url                 text,
+    last_sent_at                timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
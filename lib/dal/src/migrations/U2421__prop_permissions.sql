@@ -0,0 +1,12 @@
+ALTER TABLE user_belongs_to_workspaces
+    ADD COLUMN role text NOT NULL DEFAULT 'admin';
+
+CREATE TABLE prop_permissions
+(
+    workspace_pk                ident                    NOT NULL REFERENCES workspaces (pk),
+    path                        text                     NOT NULL,
+    allowed_roles               text[]                   NOT NULL DEFAULT '{}',
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (workspace_pk, path)
+);
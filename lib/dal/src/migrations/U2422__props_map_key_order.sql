@@ -0,0 +1 @@
+ALTER TABLE props ADD COLUMN map_key_order text NOT NULL DEFAULT 'insertion';
@@ -0,0 +1,2 @@
+ALTER TABLE components
+ADD COLUMN archived boolean NOT NULL DEFAULT false;
@@ -0,0 +1,2 @@
+ALTER TABLE props
+    ADD COLUMN default_value jsonb;
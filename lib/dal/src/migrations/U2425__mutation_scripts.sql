@@ -0,0 +1,13 @@
+CREATE TABLE mutation_scripts
+(
+    pk           ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    workspace_pk ident                    NOT NULL REFERENCES workspaces (pk),
+    name         text                     NOT NULL,
+    version      integer                  NOT NULL,
+    description  text,
+    parameters   jsonb                    NOT NULL DEFAULT '[]'::jsonb,
+    operations   jsonb                    NOT NULL DEFAULT '[]'::jsonb,
+    created_at   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    UNIQUE (workspace_pk, name, version)
+);
@@ -0,0 +1,15 @@
+CREATE TABLE attribute_value_audits
+(
+    pk                                    ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    tenancy_workspace_pk                  ident                    NOT NULL,
+    visibility_change_set_pk              ident                    NOT NULL DEFAULT ident_nil_v1(),
+    attribute_value_id                    ident                    NOT NULL,
+    component_id                          ident                    NOT NULL,
+    prop_id                               ident                    NOT NULL,
+    previous_func_binding_return_value_id ident,
+    func_binding_return_value_id          ident                    NOT NULL,
+    actor                                 jsonb                    NOT NULL,
+    created_at                            timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX ON attribute_value_audits (tenancy_workspace_pk, component_id, prop_id);
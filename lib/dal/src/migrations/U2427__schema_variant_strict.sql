@@ -0,0 +1 @@
+ALTER TABLE schema_variants ADD COLUMN strict boolean NOT NULL DEFAULT false;
@@ -0,0 +1,13 @@
+CREATE TABLE workspace_retention_policies
+(
+    workspace_pk                            ident primary key        REFERENCES workspaces (pk),
+    func_binding_return_value_history_hours integer                  NOT NULL,
+    created_at                              timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                              timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+-- Pruning checks every table that can point at a func binding return value.
+CREATE INDEX ON func_binding_return_values (tenancy_workspace_pk, created_at);
+CREATE INDEX ON validation_resolvers (attribute_value_func_binding_return_value_id);
+CREATE INDEX ON attribute_value_audits (func_binding_return_value_id);
+CREATE INDEX ON attribute_value_audits (previous_func_binding_return_value_id);
@@ -0,0 +1,32 @@
+CREATE OR REPLACE FUNCTION change_set_abandon_v1(this_change_set_pk ident,
+                                                 this_tenancy jsonb,
+                                                 OUT timestamp_updated_at timestamp with time zone) AS
+$$
+DECLARE
+    standard_model  standard_models%ROWTYPE;
+    this_table_name regclass;
+BEGIN
+    UPDATE change_sets
+    SET status     = 'Abandoned',
+        updated_at = clock_timestamp()
+    WHERE pk = this_change_set_pk
+    RETURNING updated_at INTO timestamp_updated_at;
+
+    -- Nothing in an abandoned change set can ever make it to head, so its rows are dropped
+    -- rather than left around for every visibility query to filter out.
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            this_table_name := standard_model.table_name::regclass;
+
+            EXECUTE format('DELETE FROM %1$I ' ||
+                           'WHERE visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, tenancy_workspace_pk)',
+                           this_table_name, this_change_set_pk, this_tenancy);
+        END LOOP;
+
+    DELETE
+    FROM attribute_value_audits
+    WHERE visibility_change_set_pk = this_change_set_pk
+      AND in_tenancy_v1(this_tenancy, tenancy_workspace_pk);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,100 @@
+ALTER TABLE edges ADD COLUMN creation_source text NOT NULL DEFAULT 'manual';
+ALTER TABLE summary_diagram_edges ADD COLUMN creation_source text NOT NULL DEFAULT 'manual';
+
+DROP FUNCTION IF EXISTS edge_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_kind text,
+    this_head_node_id ident,
+    this_head_object_kind text,
+    this_head_object_id ident,
+    this_head_socket_id ident,
+    this_tail_node_id ident,
+    this_tail_object_kind text,
+    this_tail_object_id ident,
+    this_tail_socket_id ident,
+    this_user_pk ident
+);
+
+CREATE OR REPLACE FUNCTION edge_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_kind text,
+    this_head_node_id ident,
+    this_head_object_kind text,
+    this_head_object_id ident,
+    this_head_socket_id ident,
+    this_tail_node_id ident,
+    this_tail_object_kind text,
+    this_tail_object_id ident,
+    this_tail_socket_id ident,
+    this_user_pk ident,
+    this_creation_source text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           edges%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO edges (tenancy_workspace_pk,
+                       visibility_change_set_pk, kind,
+                       head_node_id, head_object_kind, head_object_id, head_socket_id,
+                       tail_node_id, tail_object_kind, tail_object_id, tail_socket_id, creation_user_pk,
+                       creation_source)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_kind,
+            this_head_node_id, this_head_object_kind, this_head_object_id,
+            this_head_socket_id, this_tail_node_id, this_tail_object_kind,
+            this_tail_object_id, this_tail_socket_id, this_user_pk,
+            this_creation_source)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+DROP FUNCTION IF EXISTS summary_diagram_edge_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_id ident,
+    this_from_node_id ident,
+    this_from_socket_id ident,
+    this_to_node_id ident,
+    this_to_socket_id ident,
+    this_created_info jsonb
+);
+
+CREATE OR REPLACE FUNCTION summary_diagram_edge_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_id ident,
+    this_from_node_id ident,
+    this_from_socket_id ident,
+    this_to_node_id ident,
+    this_to_socket_id ident,
+    this_created_info jsonb,
+    this_creation_source text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           summary_diagram_edges%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO summary_diagram_edges (id, tenancy_workspace_pk, visibility_change_set_pk, visibility_deleted_at,
+                                       edge_id, from_node_id, from_socket_id, to_node_id,
+                                       to_socket_id, change_status, created_info, creation_source)
+    VALUES (this_id, this_tenancy_record.tenancy_workspace_pk, this_visibility_record.visibility_change_set_pk,
+            this_visibility_record.visibility_deleted_at, this_id, this_from_node_id, this_from_socket_id,
+            this_to_node_id, this_to_socket_id, 'added', this_created_info, this_creation_source)
+    RETURNING * INTO this_new_row;
+END
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,16 @@
+CREATE TABLE api_tokens
+(
+    pk                 ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    workspace_pk       ident                    NOT NULL REFERENCES workspaces (pk),
+    name               text                     NOT NULL,
+    scope              text                     NOT NULL,
+    token_hash         text                     NOT NULL UNIQUE,
+    created_by_user_pk ident                    NOT NULL REFERENCES users (pk),
+    expires_at         timestamp with time zone,
+    last_used_at       timestamp with time zone,
+    revoked_at         timestamp with time zone,
+    created_at         timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at         timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX api_tokens_workspace_pk_idx ON api_tokens (workspace_pk);
@@ -0,0 +1,41 @@
+-- A counter bumped every time the value of an attribute value changes, so clients can tell which
+-- values need to be validated again without comparing the values themselves.
+ALTER TABLE attribute_values
+    ADD COLUMN content_generation bigint NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION attribute_value_content_generation_trigger_v1() RETURNS trigger AS
+$$
+DECLARE
+    head_content_generation           bigint;
+    head_func_binding_return_value_id ident;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW.func_binding_return_value_id IS DISTINCT FROM OLD.func_binding_return_value_id THEN
+            NEW.content_generation := OLD.content_generation + 1;
+        END IF;
+    ELSIF NEW.visibility_change_set_pk != ident_nil_v1() THEN
+        -- Rows copied from head into a change set carry the head generation along, and are
+        -- usually copied in order to change the value.
+        SELECT content_generation, func_binding_return_value_id
+        INTO head_content_generation, head_func_binding_return_value_id
+        FROM attribute_values
+        WHERE id = NEW.id
+          AND tenancy_workspace_pk = NEW.tenancy_workspace_pk
+          AND visibility_change_set_pk = ident_nil_v1()
+          AND visibility_deleted_at IS NULL;
+
+        IF FOUND
+            AND head_func_binding_return_value_id IS DISTINCT FROM NEW.func_binding_return_value_id THEN
+            NEW.content_generation := head_content_generation + 1;
+        END IF;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE PLPGSQL;
+
+CREATE TRIGGER attribute_value_content_generation
+    BEFORE INSERT OR UPDATE OF func_binding_return_value_id
+    ON attribute_values
+    FOR EACH ROW
+EXECUTE FUNCTION attribute_value_content_generation_trigger_v1();
@@ -0,0 +1,140 @@
+CREATE TABLE confirmation_prototypes
+(
+    pk                          ident primary key default ident_create_v1(),
+    id                          ident not null default ident_create_v1(),
+    tenancy_workspace_pk        ident                    NOT NULL,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    func_id                     ident                    NOT NULL,
+    name                        text                     NOT NULL,
+    component_id                ident                    NOT NULL,
+    schema_variant_id           ident                    NOT NULL
+);
+CREATE UNIQUE INDEX unique_confirmation_prototypes_for_context
+    ON confirmation_prototypes (name,
+                                schema_variant_id,
+                                component_id,
+                                tenancy_workspace_pk,
+                                visibility_change_set_pk);
+SELECT standard_model_table_constraints_v1('confirmation_prototypes');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('confirmation_prototypes', 'model', 'confirmation_prototype', 'Confirmation Prototype');
+
+CREATE OR REPLACE FUNCTION confirmation_prototype_upsert_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_func_id ident,
+    this_name text,
+    this_component_id ident,
+    this_schema_variant_id ident,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           confirmation_prototypes%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO confirmation_prototypes (tenancy_workspace_pk,
+                                         visibility_change_set_pk,
+                                         func_id,
+                                         name,
+                                         component_id,
+                                         schema_variant_id)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_func_id,
+            this_name,
+            this_component_id,
+            this_schema_variant_id)
+    ON CONFLICT (name, schema_variant_id, component_id, tenancy_workspace_pk, visibility_change_set_pk)
+    DO UPDATE SET func_id = this_func_id, updated_at = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE TABLE confirmation_resolvers
+(
+    pk                          ident primary key default ident_create_v1(),
+    id                          ident not null default ident_create_v1(),
+    tenancy_workspace_pk        ident                    NOT NULL,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    confirmation_prototype_id   ident                    NOT NULL,
+    component_id                ident                    NOT NULL,
+    func_id                     ident                    NOT NULL,
+    func_binding_id             ident                    NOT NULL,
+    success                     bool                     NOT NULL,
+    message                     text,
+    recommended_actions         jsonb                    NOT NULL DEFAULT '[]'::jsonb
+);
+CREATE UNIQUE INDEX unique_confirmation_resolvers_for_component
+    ON confirmation_resolvers (confirmation_prototype_id,
+                               component_id,
+                               tenancy_workspace_pk,
+                               visibility_change_set_pk);
+CREATE INDEX ON confirmation_resolvers (component_id);
+SELECT standard_model_table_constraints_v1('confirmation_resolvers');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('confirmation_resolvers', 'model', 'confirmation_resolver', 'Confirmation Resolver');
+
+CREATE OR REPLACE FUNCTION confirmation_resolver_upsert_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_confirmation_prototype_id ident,
+    this_component_id ident,
+    this_func_id ident,
+    this_func_binding_id ident,
+    this_success bool,
+    this_message text,
+    this_recommended_actions jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           confirmation_resolvers%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO confirmation_resolvers (tenancy_workspace_pk,
+                                        visibility_change_set_pk,
+                                        confirmation_prototype_id,
+                                        component_id,
+                                        func_id,
+                                        func_binding_id,
+                                        success,
+                                        message,
+                                        recommended_actions)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_confirmation_prototype_id,
+            this_component_id,
+            this_func_id,
+            this_func_binding_id,
+            this_success,
+            this_message,
+            this_recommended_actions)
+    ON CONFLICT (confirmation_prototype_id, component_id, tenancy_workspace_pk, visibility_change_set_pk)
+    DO UPDATE SET func_id             = this_func_id,
+                  func_binding_id     = this_func_binding_id,
+                  success             = this_success,
+                  message             = this_message,
+                  recommended_actions = this_recommended_actions,
+                  updated_at          = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
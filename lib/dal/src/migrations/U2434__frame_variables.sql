@@ -0,0 +1,63 @@
+CREATE TABLE frame_variables
+(
+    pk                          ident primary key default ident_create_v1(),
+    id                          ident not null default ident_create_v1(),
+    tenancy_workspace_pk        ident                    NOT NULL,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    component_id                ident                    NOT NULL,
+    name                        text                     NOT NULL,
+    value                       jsonb,
+    secret                      bool                     NOT NULL DEFAULT false
+);
+CREATE UNIQUE INDEX unique_frame_variables_for_component
+    ON frame_variables (name,
+                        component_id,
+                        tenancy_workspace_pk,
+                        visibility_change_set_pk);
+SELECT standard_model_table_constraints_v1('frame_variables');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('frame_variables', 'model', 'frame_variable', 'Frame Variable');
+
+CREATE OR REPLACE FUNCTION frame_variable_upsert_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident,
+    this_name text,
+    this_value jsonb,
+    this_secret bool,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           frame_variables%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO frame_variables (tenancy_workspace_pk,
+                                 visibility_change_set_pk,
+                                 component_id,
+                                 name,
+                                 value,
+                                 secret)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_component_id,
+            this_name,
+            this_value,
+            this_secret)
+    ON CONFLICT (name, component_id, tenancy_workspace_pk, visibility_change_set_pk)
+    DO UPDATE SET value                 = this_value,
+                  secret                = this_secret,
+                  visibility_deleted_at = NULL,
+                  updated_at            = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,3 @@
+-- How often, in seconds, the resources of a schema's components are refreshed by the resource
+-- scheduler. Resources are only refreshed on demand when unset.
+ALTER TABLE schemas ADD COLUMN refresh_interval_seconds bigint;
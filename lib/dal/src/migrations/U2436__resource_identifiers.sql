@@ -0,0 +1,58 @@
+-- Where to find the canonical cloud identifier (e.g. "sg-...") of a resource in the payload of
+-- the schema's components, as a JSON pointer (e.g. "/GroupId").
+ALTER TABLE schemas ADD COLUMN resource_identifier_path text;
+
+CREATE TABLE resource_identifiers
+(
+    pk                          ident primary key default ident_create_v1(),
+    id                          ident not null default ident_create_v1(),
+    tenancy_workspace_pk        ident                    NOT NULL,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    component_id                ident                    NOT NULL,
+    identifier                  text                     NOT NULL
+);
+CREATE UNIQUE INDEX unique_resource_identifiers_for_workspace
+    ON resource_identifiers (identifier,
+                             tenancy_workspace_pk,
+                             visibility_change_set_pk);
+CREATE INDEX ON resource_identifiers (component_id);
+SELECT standard_model_table_constraints_v1('resource_identifiers');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('resource_identifiers', 'model', 'resource_identifier', 'Resource Identifier');
+
+CREATE OR REPLACE FUNCTION resource_identifier_upsert_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident,
+    this_identifier text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           resource_identifiers%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO resource_identifiers (tenancy_workspace_pk,
+                                      visibility_change_set_pk,
+                                      component_id,
+                                      identifier)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_component_id,
+            this_identifier)
+    ON CONFLICT (identifier, tenancy_workspace_pk, visibility_change_set_pk)
+    DO UPDATE SET component_id          = this_component_id,
+                  visibility_deleted_at = NULL,
+                  updated_at            = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,18 @@
+CREATE TABLE provider_value_histories
+(
+    pk                           ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    tenancy_workspace_pk         ident                    NOT NULL,
+    visibility_change_set_pk     ident                    NOT NULL DEFAULT ident_nil_v1(),
+    attribute_value_id           ident                    NOT NULL,
+    component_id                 ident                    NOT NULL,
+    internal_provider_id         ident                    NOT NULL,
+    external_provider_id         ident                    NOT NULL,
+    func_binding_return_value_id ident                    NOT NULL,
+    value                        jsonb,
+    job_id                       text,
+    created_at                   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX ON provider_value_histories (tenancy_workspace_pk, component_id, internal_provider_id,
+                                          external_provider_id);
+CREATE INDEX ON provider_value_histories (attribute_value_id, created_at);
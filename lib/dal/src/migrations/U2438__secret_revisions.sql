@@ -0,0 +1,20 @@
+-- Rotating a secret replaces its encrypted contents while keeping its id. The revision counts
+-- the rotations, so that consumers can tell which contents they last used.
+ALTER TABLE encrypted_secrets ADD COLUMN revision bigint NOT NULL DEFAULT 1;
+
+CREATE OR REPLACE VIEW secrets AS
+SELECT pk,
+       id,
+       tenancy_workspace_pk,
+       visibility_change_set_pk,
+       visibility_deleted_at,
+       key_pair_pk,
+       created_at,
+       created_by,
+       updated_at,
+       updated_by,
+       name,
+       definition,
+       description,
+       revision
+FROM encrypted_secrets;
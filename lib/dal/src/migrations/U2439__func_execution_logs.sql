@@ -0,0 +1,23 @@
+CREATE TABLE func_execution_logs
+(
+    pk                       ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    tenancy_workspace_pk     ident                    NOT NULL,
+    visibility_change_set_pk ident                    NOT NULL DEFAULT ident_nil_v1(),
+    func_execution_pk        ident                    NOT NULL,
+    func_id                  ident                    NOT NULL,
+    func_binding_id          ident                    NOT NULL,
+    component_id             ident                    NOT NULL DEFAULT ident_nil_v1(),
+    attribute_value_id       ident                    NOT NULL DEFAULT ident_nil_v1(),
+    backend_kind             text                     NOT NULL,
+    success                  bool                     NOT NULL,
+    failure                  text,
+    stdout                   text[]                   NOT NULL DEFAULT '{}',
+    stderr                   text[]                   NOT NULL DEFAULT '{}',
+    started_at               timestamp with time zone NOT NULL,
+    finished_at              timestamp with time zone NOT NULL,
+    duration_ms              bigint                   NOT NULL,
+    created_at               timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX ON func_execution_logs (tenancy_workspace_pk, component_id, func_id, created_at);
+CREATE INDEX ON func_execution_logs (tenancy_workspace_pk, func_id, created_at);
@@ -0,0 +1,7 @@
+CREATE TABLE builtin_migration_checkpoints
+(
+    name         text                     NOT NULL,
+    content_hash text                     NOT NULL,
+    completed_at timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (name, content_hash)
+);
@@ -0,0 +1,33 @@
+CREATE TABLE webhooks
+(
+    pk           ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    workspace_pk ident                    NOT NULL REFERENCES workspaces (pk),
+    url          text                     NOT NULL,
+    events       text[]                   NOT NULL,
+    enabled      bool                     NOT NULL DEFAULT TRUE,
+    created_at   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at   timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX webhooks_workspace_pk_idx ON webhooks (workspace_pk);
+
+CREATE TABLE webhook_deliveries
+(
+    pk                   ident primary key        NOT NULL DEFAULT ident_create_v1(),
+    webhook_pk           ident                    NOT NULL REFERENCES webhooks (pk) ON DELETE CASCADE,
+    workspace_pk         ident                    NOT NULL REFERENCES workspaces (pk),
+    event                text                     NOT NULL,
+    payload              jsonb                    NOT NULL,
+    status               text                     NOT NULL DEFAULT 'pending',
+    attempts             integer                  NOT NULL DEFAULT 0,
+    next_attempt_at      timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    last_attempt_at      timestamp with time zone,
+    last_response_status integer,
+    last_error           text,
+    delivered_at         timestamp with time zone,
+    created_at           timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at           timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX webhook_deliveries_webhook_pk_idx ON webhook_deliveries (webhook_pk, created_at);
+CREATE INDEX webhook_deliveries_pending_idx ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
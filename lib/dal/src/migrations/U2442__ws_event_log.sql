@@ -0,0 +1,18 @@
+CREATE TABLE ws_event_cursors
+(
+    workspace_pk ident primary key NOT NULL,
+    last_cursor  bigint            NOT NULL
+);
+
+CREATE TABLE ws_event_log
+(
+    workspace_pk  ident                    NOT NULL,
+    cursor        bigint                   NOT NULL,
+    change_set_pk ident                    NOT NULL,
+    kind          text                     NOT NULL,
+    event         jsonb                    NOT NULL,
+    created_at    timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (workspace_pk, cursor)
+);
+
+CREATE INDEX ws_event_log_created_at_idx ON ws_event_log (created_at);
@@ -0,0 +1,58 @@
+CREATE TABLE socket_merge_strategies
+(
+    pk                          ident primary key default ident_create_v1(),
+    id                          ident not null default ident_create_v1(),
+    tenancy_workspace_pk        ident                    NOT NULL,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    component_id                ident                    NOT NULL,
+    socket_id                   ident                    NOT NULL,
+    strategy                    text                     NOT NULL
+);
+CREATE UNIQUE INDEX unique_socket_merge_strategies_for_component
+    ON socket_merge_strategies (socket_id,
+                                component_id,
+                                tenancy_workspace_pk,
+                                visibility_change_set_pk);
+SELECT standard_model_table_constraints_v1('socket_merge_strategies');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('socket_merge_strategies', 'model', 'socket_merge_strategy', 'Socket Merge Strategy');
+
+CREATE OR REPLACE FUNCTION socket_merge_strategy_upsert_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident,
+    this_socket_id ident,
+    this_strategy text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           socket_merge_strategies%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO socket_merge_strategies (tenancy_workspace_pk,
+                                         visibility_change_set_pk,
+                                         component_id,
+                                         socket_id,
+                                         strategy)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_component_id,
+            this_socket_id,
+            this_strategy)
+    ON CONFLICT (socket_id, component_id, tenancy_workspace_pk, visibility_change_set_pk)
+    DO UPDATE SET strategy              = this_strategy,
+                  visibility_deleted_at = NULL,
+                  updated_at            = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
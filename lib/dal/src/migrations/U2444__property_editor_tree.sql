@@ -0,0 +1,120 @@
+-- Returns the property editor node for an attribute value: its prop, its current value and, in
+-- order, the nodes of its child attribute values in the given context.
+CREATE OR REPLACE FUNCTION property_editor_tree_node_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_context jsonb,
+    this_attribute_value_id ident
+)
+    RETURNS jsonb
+    LANGUAGE plpgsql
+    STABLE
+    PARALLEL SAFE
+AS
+$$
+DECLARE
+    this_node     jsonb;
+    this_order    jsonb;
+    this_children jsonb;
+BEGIN
+    SELECT jsonb_build_object(
+                   'id', av.id,
+                   'propId', prop.id,
+                   'name', prop.name,
+                   'kind', prop.kind,
+                   'widgetKind', prop.widget_kind,
+                   'hidden', prop.hidden,
+                   'key', av.key,
+                   'generation', av.content_generation,
+                   'value', COALESCE(fbrv.value, 'null'::jsonb),
+                   'isFromExternalSource', EXISTS(
+                           SELECT 1
+                           FROM attribute_value_belongs_to_attribute_prototype_v1(this_tenancy, this_visibility) AS avbtap
+                                    INNER JOIN attribute_prototype_arguments_v1(this_tenancy, this_visibility) AS apa
+                                               ON apa.attribute_prototype_id = avbtap.belongs_to_id
+                                    INNER JOIN socket_belongs_to_internal_provider_v1(this_tenancy, this_visibility) AS sbtip
+                                               ON sbtip.belongs_to_id = apa.internal_provider_id
+                                    INNER JOIN edges_v1(this_tenancy, this_visibility) AS edges
+                                               ON edges.head_socket_id = sbtip.object_id
+                           WHERE avbtap.object_id = av.id
+                             AND edges.head_object_id = (this_context ->> 'attribute_context_component_id')::ident
+                       )
+               ),
+           av.index_map -> 'order'
+    INTO this_node, this_order
+    FROM attribute_values_v1(this_tenancy, this_visibility) AS av
+             INNER JOIN props_v1(this_tenancy, this_visibility) AS prop
+                        ON av.attribute_context_prop_id = prop.id
+             LEFT JOIN func_binding_return_values_v1(this_tenancy, this_visibility) AS fbrv
+                       ON fbrv.id = av.func_binding_return_value_id
+    WHERE av.id = this_attribute_value_id;
+
+    IF this_node IS NULL THEN
+        RETURN NULL;
+    END IF;
+
+    -- Elements of arrays and maps follow the index map of their parent, the children of objects
+    -- the order they were created in.
+    SELECT COALESCE(jsonb_agg(property_editor_tree_node_v1(this_tenancy,
+                                                           this_visibility,
+                                                           this_context,
+                                                           children.attribute_value_id)
+                              ORDER BY (SELECT index_map_order.position
+                                        FROM jsonb_array_elements_text(this_order)
+                                                 WITH ORDINALITY AS index_map_order(id, position)
+                                        WHERE index_map_order.id::ident = children.attribute_value_id)
+                                  NULLS FIRST,
+                                  children.attribute_value_id),
+                    '[]'::jsonb)
+    INTO this_children
+    FROM (SELECT DISTINCT ON (
+        av.attribute_context_prop_id,
+        COALESCE(av.key, '')
+        ) av.id AS attribute_value_id
+          FROM attribute_values_v1(this_tenancy, this_visibility) AS av
+                   INNER JOIN attribute_value_belongs_to_attribute_value_v1(this_tenancy,
+                                                                            this_visibility) AS avbtav
+                              ON av.id = avbtav.object_id
+          WHERE in_attribute_context_v1(this_context, av)
+            AND avbtav.belongs_to_id = this_attribute_value_id
+          ORDER BY av.attribute_context_prop_id DESC,
+                   COALESCE(av.key, ''),
+                   av.attribute_context_component_id DESC) AS children;
+
+    RETURN this_node || jsonb_build_object('children', this_children);
+END;
+$$;
+
+-- Returns the whole property editor tree of a component, from the value of its root prop down,
+-- as a single document.
+CREATE OR REPLACE FUNCTION property_editor_tree_for_component_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident
+)
+    RETURNS jsonb
+    LANGUAGE sql
+    STABLE
+    PARALLEL SAFE
+AS
+$$
+SELECT property_editor_tree_node_v1(
+               this_tenancy,
+               this_visibility,
+               context.context,
+               attribute_value_id_for_prop_and_context_v1(
+                       this_tenancy,
+                       this_visibility,
+                       context.context,
+                       cbtsv.belongs_to_id
+                   )
+           )
+FROM component_belongs_to_schema_variant_v1(this_tenancy, this_visibility) AS cbtsv,
+     LATERAL (SELECT jsonb_build_object(
+                             'attribute_context_prop_id', NULL,
+                             'attribute_context_internal_provider_id', ident_nil_v1(),
+                             'attribute_context_external_provider_id', ident_nil_v1(),
+                             'attribute_context_component_id', this_component_id
+                         ) AS context) AS context
+WHERE cbtsv.object_id = this_component_id
+$$;
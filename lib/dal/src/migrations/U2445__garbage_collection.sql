@@ -0,0 +1,29 @@
+-- Hard-delete, in one batch, the rows of a standard model table that were soft-deleted before
+-- this_older_than. Rows deleted in a change set that is still open are kept, since they shadow
+-- the matching head rows for as long as the change set lives. Returns the number of rows deleted.
+CREATE OR REPLACE FUNCTION garbage_collect_table_v1(this_table_name text,
+                                                    this_older_than timestamp with time zone,
+                                                    this_batch_size bigint,
+                                                    OUT deleted bigint)
+AS
+$$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM standard_models WHERE table_name = this_table_name) THEN
+        RAISE 'garbage_collect_table_v1: % is not a standard model table', this_table_name;
+    END IF;
+
+    EXECUTE format('WITH collectable AS (SELECT t.pk'
+                   '                     FROM %1$I AS t'
+                   '                     LEFT JOIN change_sets AS cs'
+                   '                         ON cs.pk = t.visibility_change_set_pk'
+                   '                     WHERE t.visibility_deleted_at IS NOT NULL'
+                   '                       AND t.visibility_deleted_at < $1'
+                   '                       AND (t.visibility_change_set_pk = ident_nil_v1()'
+                   '                         OR cs.pk IS NULL'
+                   '                         OR cs.status IN (''Abandoned'', ''Applied'', ''Closed''))'
+                   '                     LIMIT $2)'
+                   ' DELETE FROM %1$I WHERE pk IN (SELECT pk FROM collectable)', this_table_name)
+        USING this_older_than, this_batch_size;
+    GET DIAGNOSTICS deleted = ROW_COUNT;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,106 @@
+//! This module contains [`Permission`], what a [`User`] may do in a
+//! [`Workspace`](crate::Workspace), and the permissions granted by each [`WorkspaceRole`].
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+
+use crate::{DalContext, User, UserPk, UserResult, WorkspacePk, WorkspaceRole};
+
+/// Something a [`User`] may or may not be allowed to do, granted through their
+/// [`WorkspaceRole`].
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Deserialize,
+    Serialize,
+    Debug,
+    Display,
+    EnumIter,
+    EnumString,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum Permission {
+    /// Look at the diagram and the components on it.
+    #[serde(rename = "diagram:read")]
+    #[strum(serialize = "diagram:read")]
+    DiagramRead,
+    /// Change the diagram, e.g. create, connect or delete components.
+    #[serde(rename = "diagram:write")]
+    #[strum(serialize = "diagram:write")]
+    DiagramWrite,
+}
+
+impl Permission {
+    /// Whether the permission allows changing anything.
+    pub fn is_write(&self) -> bool {
+        match self {
+            Self::DiagramRead => false,
+            Self::DiagramWrite => true,
+        }
+    }
+}
+
+impl WorkspaceRole {
+    /// The [`Permissions`](Permission) held by members with this role.
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Self::Admin | Self::Editor => &[Permission::DiagramRead, Permission::DiagramWrite],
+            Self::Viewer => &[Permission::DiagramRead],
+        }
+    }
+
+    pub fn grants(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+impl User {
+    /// Whether the given [`User`] holds `permission` in the given
+    /// [`Workspace`](crate::Workspace). Users who aren't members hold none.
+    pub async fn has_permission(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+        permission: Permission,
+    ) -> UserResult<bool> {
+        Ok(Self::workspace_role(ctx, user_pk, workspace_pk)
+            .await?
+            .is_some_and(|role| role.grants(permission)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn viewers_can_only_read() {
+        for permission in Permission::iter() {
+            assert_eq!(
+                !permission.is_write(),
+                WorkspaceRole::Viewer.grants(permission)
+            );
+            assert!(WorkspaceRole::Admin.grants(permission));
+            assert!(WorkspaceRole::Editor.grants(permission));
+        }
+    }
+
+    #[test]
+    fn serializes_as_area_and_access() {
+        assert_eq!("diagram:write", Permission::DiagramWrite.to_string());
+        assert_eq!(
+            Permission::DiagramRead,
+            "diagram:read".parse().expect("could not parse permission")
+        );
+        assert_eq!(
+            serde_json::json!("diagram:read"),
+            serde_json::to_value(Permission::DiagramRead).expect("could not serialize")
+        );
+    }
+}
@@ -101,6 +101,8 @@ pub enum PkgError {
     ConflictingMapKeyPrototypes(PropId),
     #[error("expected data on an SiPkg node, but none found: {0}")]
     DataNotFound(String),
+    #[error("resource {0} is already modeled by component {1}")]
+    DuplicateResource(String, ComponentId),
     #[error(transparent)]
     Edge(#[from] EdgeError),
     #[error("edge refers to component not in export: {0}")]
@@ -185,6 +187,10 @@ pub enum PkgError {
     Node(#[from] NodeError),
     #[error("Package with that hash already installed: {0}")]
     PackageAlreadyInstalled(String),
+    #[error("Package {0} is still used by components: {1:?}")]
+    PackageInUse(String, Vec<ComponentId>),
+    #[error("Package upgrade for {0} did not record an installed package")]
+    PackageUpgradeNotRecorded(String),
     #[error(transparent)]
     Pkg(#[from] SiPkgError),
     #[error(transparent)]
@@ -253,6 +259,7 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::Diff => Self::Diff,
             FuncBackendKind::Identity => Self::Identity,
             FuncBackendKind::Integer => Self::Integer,
+            FuncBackendKind::Join => Self::Join,
             FuncBackendKind::JsAction => Self::JsAction,
             FuncBackendKind::JsAttribute => Self::JsAttribute,
             FuncBackendKind::JsReconciliation => Self::JsReconciliation,
@@ -260,10 +267,13 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::JsValidation => Self::JsValidation,
             FuncBackendKind::Map => Self::Map,
             FuncBackendKind::Object => Self::Object,
+            FuncBackendKind::Select => Self::Select,
             FuncBackendKind::String => Self::String,
+            FuncBackendKind::Template => Self::Template,
             FuncBackendKind::Unset => Self::Unset,
             FuncBackendKind::Validation => Self::Validation,
             FuncBackendKind::JsAuthentication => Self::JsAuthentication,
+            FuncBackendKind::WasmAttribute => Self::WasmAttribute,
         }
     }
 }
@@ -276,6 +286,7 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::Diff => Self::Diff,
             FuncSpecBackendKind::Identity => Self::Identity,
             FuncSpecBackendKind::Integer => Self::Integer,
+            FuncSpecBackendKind::Join => Self::Join,
             FuncSpecBackendKind::JsAction => Self::JsAction,
             FuncSpecBackendKind::JsAttribute => Self::JsAttribute,
             FuncSpecBackendKind::JsReconciliation => Self::JsReconciliation,
@@ -283,10 +294,13 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::JsValidation => Self::JsValidation,
             FuncSpecBackendKind::Map => Self::Map,
             FuncSpecBackendKind::Object => Self::Object,
+            FuncSpecBackendKind::Select => Self::Select,
             FuncSpecBackendKind::String => Self::String,
+            FuncSpecBackendKind::Template => Self::Template,
             FuncSpecBackendKind::Unset => Self::Unset,
             FuncSpecBackendKind::Validation => Self::Validation,
             FuncSpecBackendKind::JsAuthentication => Self::JsAuthentication,
+            FuncSpecBackendKind::WasmAttribute => Self::WasmAttribute,
         }
     }
 }
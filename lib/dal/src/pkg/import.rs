@@ -20,7 +20,7 @@ use telemetry::prelude::*;
 use crate::authentication_prototype::{AuthenticationPrototype, AuthenticationPrototypeContext};
 use crate::{
     component::ComponentKind,
-    edge::EdgeKind,
+    edge::{EdgeCreationSource, EdgeKind},
     func::{
         self,
         argument::{FuncArgumentError, FuncArgumentKind},
@@ -80,6 +80,9 @@ pub struct ImportOptions {
     /// If set to `true` then we will set the functions to a builtin
     /// in the UI. They will be marked as such.
     pub is_builtin: bool,
+    /// Existing schemas, keyed by name, that should receive new variants instead of being
+    /// created from scratch. Used when upgrading an installed package.
+    pub upgrade_schemas: Option<HashMap<String, Schema>>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -431,6 +434,20 @@ async fn import_change_set(
             metadata.name(),
         );
 
+        if let (Some(schema), Some(unique_id)) = (
+            options
+                .upgrade_schemas
+                .as_ref()
+                .and_then(|schemas| schemas.get(schema_spec.name())),
+            schema_spec.unique_id(),
+        ) {
+            thing_map.insert(
+                change_set_pk,
+                unique_id.to_owned(),
+                Thing::Schema(schema.to_owned()),
+            );
+        }
+
         let (_, schema_variant_ids) = import_schema(
             ctx,
             change_set_pk,
@@ -575,6 +592,7 @@ async fn import_edge(
                             EdgeSpecKind::Configuration => EdgeKind::Configuration,
                             EdgeSpecKind::Symbolic => EdgeKind::Symbolic,
                         },
+                        EdgeCreationSource::Import,
                     )
                     .await?,
                 )
@@ -881,7 +899,16 @@ async fn import_component(
 
     if let Some(resource_value) = resource_value {
         if force_resource_patch || change_set_pk == ChangeSetPk::NONE {
-            if let Ok(result) = serde_json::from_value(resource_value) {
+            if let Ok(result) = serde_json::from_value::<ActionRunResult>(resource_value) {
+                // Importing must not model a resource that another component already models.
+                if let Some((identifier, existing_component_id)) =
+                    component.resource_identifier_conflict(ctx, &result).await?
+                {
+                    return Err(PkgError::DuplicateResource(
+                        identifier,
+                        existing_component_id,
+                    ));
+                }
                 component.set_resource(ctx, result).await?;
             }
         }
@@ -1296,13 +1323,20 @@ pub async fn import_pkg_from_pkg(
 
     let metadata = pkg.metadata()?;
 
+    InstalledPkg::resolve_dependencies(ctx, &metadata).await?;
+
     let installed_pkg_id = if options.no_record {
         None
     } else {
         Some(
-            *InstalledPkg::new(ctx, metadata.name(), pkg.hash()?.to_string())
-                .await?
-                .id(),
+            *InstalledPkg::new(
+                ctx,
+                metadata.name(),
+                metadata.version(),
+                pkg.hash()?.to_string(),
+            )
+            .await?
+            .id(),
         )
     };
 
@@ -1985,28 +2019,10 @@ struct AttrFuncInfo {
     inputs: Vec<SiPkgAttrFuncInputView>,
 }
 
-#[remain::sorted]
-#[derive(Clone, Debug)]
-enum DefaultValueInfo {
-    Boolean {
-        prop_id: PropId,
-        default_value: bool,
-    },
-    Number {
-        prop_id: PropId,
-        default_value: i64,
-    },
-    String {
-        prop_id: PropId,
-        default_value: String,
-    },
-}
-
 struct PropVisitContext<'a> {
     pub ctx: &'a DalContext,
     pub schema_variant_id: SchemaVariantId,
     pub attr_funcs: Mutex<Vec<AttrFuncInfo>>,
-    pub default_values: Mutex<Vec<DefaultValueInfo>>,
     pub map_key_funcs: Mutex<Vec<(String, AttrFuncInfo)>>,
 }
 
@@ -2040,34 +2056,16 @@ async fn import_leaf_function(
     Ok(())
 }
 
-// TODO: cache this so we don't fetch it for every socket
 async fn get_identity_func(
     ctx: &DalContext,
 ) -> PkgResult<(Func, FuncBinding, FuncBindingReturnValue, FuncArgument)> {
-    let func_name = "si:identity";
-    let func_argument_name = "identity";
-    let func: Func = Func::find_by_name(ctx, func_name)
-        .await?
-        .ok_or_else(|| FuncError::NotFoundByName(func_name.to_string()))?;
-
-    let func_id = *func.id();
-    let (func_binding, func_binding_return_value) = FuncBinding::create_and_execute(
-        ctx,
-        serde_json::json![{ "identity": null }],
-        func_id,
-        vec![],
-    )
-    .await?;
-    let func_argument = FuncArgument::find_by_name_for_func(ctx, func_argument_name, func_id)
-        .await?
-        .ok_or_else(|| {
-            PkgError::MissingIntrinsicFuncArgument(
-                func_name.to_string(),
-                func_argument_name.to_string(),
-            )
-        })?;
-
-    Ok((func, func_binding, func_binding_return_value, func_argument))
+    let identity = Func::identity(ctx).await?;
+    Ok((
+        identity.func,
+        identity.func_binding,
+        identity.func_binding_return_value,
+        identity.func_argument,
+    ))
 }
 
 async fn create_socket(
@@ -2389,7 +2387,6 @@ async fn import_auth_func(
 #[derive(Default, Clone, Debug)]
 struct CreatePropsSideEffects {
     attr_funcs: Vec<AttrFuncInfo>,
-    default_values: Vec<DefaultValueInfo>,
     map_key_funcs: Vec<(String, AttrFuncInfo)>,
 }
 
@@ -2407,7 +2404,6 @@ impl Extend<CreatePropsSideEffects> for CreatePropsSideEffects {
     fn extend<T: IntoIterator<Item = CreatePropsSideEffects>>(&mut self, iter: T) {
         for element in iter {
             self.attr_funcs.extend(element.attr_funcs);
-            self.default_values.extend(element.default_values);
             self.map_key_funcs.extend(element.map_key_funcs);
         }
     }
@@ -2424,7 +2420,6 @@ async fn create_props(
         ctx,
         schema_variant_id,
         attr_funcs: Mutex::new(vec![]),
-        default_values: Mutex::new(vec![]),
         map_key_funcs: Mutex::new(vec![]),
     };
 
@@ -2436,7 +2431,6 @@ async fn create_props(
 
     Ok(CreatePropsSideEffects {
         attr_funcs: context.attr_funcs.into_inner(),
-        default_values: context.default_values.into_inner(),
         map_key_funcs: context.map_key_funcs.into_inner(),
     })
 }
@@ -2708,24 +2702,15 @@ async fn import_schema_variant(
             .await?;
         }
 
-        // Default values must be set before attribute functions are configured so they don't
-        // override the prototypes set there
-        for default_value_info in side_effects.default_values {
-            set_default_value(ctx, default_value_info).await?;
-        }
-
         // Set a default name value for all name props, this ensures region has a name before
         // the function is executed
         {
             let name_prop = schema_variant
                 .find_prop(ctx, &["root", "si", "name"])
                 .await?;
-            let name_default_value_info = DefaultValueInfo::String {
-                prop_id: *name_prop.id(),
-                default_value: schema.name().to_lowercase(),
-            };
-
-            set_default_value(ctx, name_default_value_info).await?;
+            name_prop
+                .set_default_value(ctx, schema.name().to_lowercase())
+                .await?;
         }
 
         for si_prop_func in variant_spec.si_prop_funcs()? {
@@ -2886,33 +2871,6 @@ pub async fn attach_resource_payload_to_value(
     Ok(())
 }
 
-async fn set_default_value(
-    ctx: &DalContext,
-    default_value_info: DefaultValueInfo,
-) -> PkgResult<()> {
-    let prop = match &default_value_info {
-        DefaultValueInfo::Number { prop_id, .. }
-        | DefaultValueInfo::String { prop_id, .. }
-        | DefaultValueInfo::Boolean { prop_id, .. } => Prop::get_by_id(ctx, prop_id)
-            .await?
-            .ok_or(PkgError::MissingProp(*prop_id))?,
-    };
-
-    match default_value_info {
-        DefaultValueInfo::Boolean { default_value, .. } => {
-            prop.set_default_value(ctx, default_value).await?
-        }
-        DefaultValueInfo::Number { default_value, .. } => {
-            prop.set_default_value(ctx, default_value).await?
-        }
-        DefaultValueInfo::String { default_value, .. } => {
-            prop.set_default_value(ctx, default_value).await?
-        }
-    }
-
-    Ok(())
-}
-
 async fn import_attr_func_for_prop(
     ctx: &DalContext,
     change_set_pk: ChangeSetPk,
@@ -3295,7 +3253,7 @@ async fn create_prop(
     parent_prop_info: Option<(PropId, PropPath)>,
     ctx: &PropVisitContext<'_>,
 ) -> PkgResult<Option<(PropId, PropPath)>> {
-    let prop = {
+    let mut prop = {
         let parent_path = parent_prop_info
             .as_ref()
             .map(|info| info.1.to_owned())
@@ -3321,57 +3279,27 @@ async fn create_prop(
 
     let prop_id = *prop.id();
 
-    // Both attribute functions and default values have to be set *after* the schema variant is
-    // "finalized", so we can't do until we construct the *entire* prop tree. Hence we push work
-    // queues up to the outer context via the PropVisitContext, which uses Mutexes for interior
-    // mutability (maybe there's a better type for that here?)
-
-    if let Some(data) = spec.data() {
-        if let Some(default_value_info) = match &spec {
-            SiPkgProp::String { .. } => {
-                if let Some(serde_json::Value::String(default_value)) = &data.default_value {
-                    Some(DefaultValueInfo::String {
-                        prop_id,
-                        default_value: default_value.to_owned(),
-                    })
-                } else {
-                    // Raise error here for type mismatch
-                    None
-                }
-            }
-            SiPkgProp::Number { .. } => {
-                if let Some(serde_json::Value::Number(default_value_number)) = &data.default_value {
-                    if default_value_number.is_i64() {
-                        default_value_number
-                            .as_i64()
-                            .map(|dv_i64| DefaultValueInfo::Number {
-                                prop_id,
-                                default_value: dv_i64,
-                            })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            SiPkgProp::Boolean { .. } => {
-                if let Some(serde_json::Value::Bool(default_value)) = &data.default_value {
-                    Some(DefaultValueInfo::Boolean {
-                        prop_id,
-                        default_value: *default_value,
-                    })
-                } else {
-                    None
-                }
-            }
+    // Default values are set when the schema variant is finalized, which happens before any
+    // attribute functions are configured, so they don't override the prototypes set there.
+    if let Some(default_value) = spec.data().and_then(|data| data.default_value.as_ref()) {
+        let default_value_matches_kind = match (&spec, default_value) {
+            (SiPkgProp::String { .. }, serde_json::Value::String(_))
+            | (SiPkgProp::Boolean { .. }, serde_json::Value::Bool(_)) => true,
+            (SiPkgProp::Number { .. }, serde_json::Value::Number(number)) => number.is_i64(),
             // Default values for complex types are not yet supported in packages
-            _ => None,
-        } {
-            ctx.default_values.lock().await.push(default_value_info);
+            _ => false,
+        };
+        if default_value_matches_kind {
+            prop.set_default_value_on_finalize(ctx.ctx, Some(default_value.to_owned()))
+                .await?;
         }
     }
 
+    // Attribute functions have to be set *after* the schema variant is "finalized", so we can't
+    // do until we construct the *entire* prop tree. Hence we push work queues up to the outer
+    // context via the PropVisitContext, which uses Mutexes for interior mutability (maybe there's
+    // a better type for that here?)
+
     if matches!(&spec, SiPkgProp::Map { .. }) {
         for map_key_func in spec.map_key_funcs()? {
             let key = map_key_func.key();
@@ -1,4 +1,5 @@
 use async_recursion::async_recursion;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use si_data_pg::PgError;
@@ -9,7 +10,7 @@ use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::standard_model::{
-    finish_create_from_row, object_option_from_row_option, objects_from_rows,
+    finish_create_from_row, object_option_from_row_option, objects_from_rows, TypeHint,
 };
 use crate::{
     attribute::{prototype::AttributePrototype, value::AttributeValue},
@@ -23,12 +24,15 @@ use crate::{
     property_editor::schema::WidgetKind,
     standard_model, standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
     AttributeContext, AttributeContextBuilder, AttributeContextBuilderError,
-    AttributePrototypeError, AttributeReadContext, DalContext, Func, FuncError, FuncId,
-    HistoryEventError, SchemaVariantId, StandardModel, StandardModelError, Tenancy, Timestamp,
-    Visibility,
+    AttributePrototypeArgument, AttributePrototypeArgumentError, AttributePrototypeError,
+    AttributeReadContext, DalContext, Func, FuncError, FuncId, HistoryEventError, InternalProvider,
+    InternalProviderError, SchemaVariant, SchemaVariantId, StandardModel, StandardModelError,
+    Tenancy, Timestamp, Visibility,
 };
 use crate::{AttributeValueError, AttributeValueId, FuncBackendResponseType, TransactionsError};
 
+pub mod validation;
+
 /// This is the separator used for the "path" column. It is a vertical tab character, which should
 /// not (we'll see) be able to be provided by our users in [`Prop`] names.
 pub const PROP_PATH_SEPARATOR: &str = "\x0B";
@@ -129,10 +133,16 @@ pub enum PropError {
     AttributeContext(#[from] AttributeContextBuilderError),
     #[error("AttributePrototype error: {0}")]
     AttributePrototype(#[from] AttributePrototypeError),
+    #[error("AttributePrototypeArgument error: {0}")]
+    AttributePrototypeArgument(#[from] AttributePrototypeArgumentError),
     #[error("AttributeValue error: {0}")]
     AttributeValue(#[from] AttributeValueError),
+    #[error("cannot delete root prop: {0}")]
+    CannotDeleteRootProp(PropId),
     #[error("default diff function not found")]
     DefaultDiffFunctionNotFound,
+    #[error("prop {0} already has a child named {1}")]
+    DuplicateChildName(PropId, String),
     #[error("expected child prop not found with name {0}")]
     ExpectedChildNotFound(String),
     #[error("Func error: {0}")]
@@ -143,6 +153,8 @@ pub enum PropError {
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
+    #[error("InternalProvider error: {0}")]
+    InternalProvider(#[from] Box<InternalProviderError>),
     #[error("Map prop {0} is missing element child")]
     MapMissingElementChild(PropId),
     #[error("missing a func: {0}")]
@@ -157,6 +169,8 @@ pub enum PropError {
     ParentPropIsNotObjectForPropWithDefaultValue(PropKind),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("schema variant {0} has been finalized, its props can no longer be edited")]
+    SchemaVariantFinalized(SchemaVariantId),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error("unable to set default value for non scalar prop type")]
@@ -165,6 +179,10 @@ pub enum PropError {
     StandardModel(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("validation format of prop {0} could not be run: {1}")]
+    ValidationFormat(PropId, String),
+    #[error("veritech client error: {0}")]
+    VeritechClient(#[from] veritech_client::ClientError),
 }
 
 pub type PropResult<T> = Result<T, PropError>;
@@ -212,6 +230,33 @@ impl From<PropKind> for PropSpecKind {
 
 impl ToLabelList for PropKind {}
 
+/// The order in which the entries of a [`Map`](PropKind::Map) [`Prop`] appear when its value is
+/// assembled, e.g. as an input to code generation.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum MapKeyOrder {
+    /// The entries appear in the order they were inserted in.
+    #[default]
+    Insertion,
+    /// The entries are sorted by their key.
+    Key,
+}
+
 impl From<PropKind> for WidgetKind {
     fn from(prop: PropKind) -> Self {
         match prop {
@@ -278,6 +323,11 @@ pub struct Prop {
     diff_func_id: Option<FuncId>,
     /// A serialized validation format JSON object for the prop
     validation_format: Option<String>,
+    /// The order of the entries of a [`Map`](PropKind::Map) [`Prop`]. Ignored for other kinds.
+    map_key_order: MapKeyOrder,
+    /// The default value to set when the [`SchemaVariant`] is
+    /// [`finalized`](crate::SchemaVariant::finalize).
+    default_value: Option<Value>,
 }
 
 impl_standard_model! {
@@ -353,7 +403,38 @@ impl Prop {
         )
         .await
     }
-    standard_model_accessor!(name, String, PropResult);
+
+    /// Create a new [`Prop`] with its documentation and, optionally, a default value that will be
+    /// set when the [`SchemaVariant`] is [`finalized`](crate::SchemaVariant::finalize). Only
+    /// scalar [`Props`](Prop) can have default values.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_with_default_value(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        kind: PropKind,
+        schema_variant_id: SchemaVariantId,
+        parent_prop_id: Option<PropId>,
+        documentation: Option<String>,
+        default_value: Option<Value>,
+    ) -> PropResult<Self> {
+        let mut prop = Self::new(
+            ctx,
+            name,
+            kind,
+            schema_variant_id,
+            parent_prop_id,
+            None,
+            documentation,
+            None,
+        )
+        .await?;
+        if default_value.is_some() {
+            prop.set_default_value_on_finalize(ctx, default_value)
+                .await?;
+        }
+        Ok(prop)
+    }
+
     standard_model_accessor!(kind, Enum(PropKind), PropResult);
     standard_model_accessor!(widget_kind, Enum(WidgetKind), PropResult);
     standard_model_accessor!(widget_options, Option<Value>, PropResult);
@@ -364,6 +445,47 @@ impl Prop {
     standard_model_accessor!(diff_func_id, Option<Pk(FuncId)>, PropResult);
     standard_model_accessor!(schema_variant_id, Pk(SchemaVariantId), PropResult);
     standard_model_accessor!(validation_format, Option<String>, PropResult);
+    standard_model_accessor!(map_key_order, Enum(MapKeyOrder), PropResult);
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn default_value(&self) -> Option<&Value> {
+        self.default_value.as_ref()
+    }
+
+    /// Record the default value to set when the [`SchemaVariant`] is
+    /// [`finalized`](crate::SchemaVariant::finalize). Use
+    /// [`set_default_value`](Self::set_default_value) for a [`SchemaVariant`] that already has
+    /// been.
+    pub async fn set_default_value_on_finalize(
+        &mut self,
+        ctx: &DalContext,
+        default_value: Option<Value>,
+    ) -> PropResult<()> {
+        if default_value.is_some()
+            && !matches!(
+                self.kind,
+                PropKind::String | PropKind::Boolean | PropKind::Integer
+            )
+        {
+            return Err(PropError::SetDefaultForNonScalar(self.kind));
+        }
+
+        let updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "default_value",
+            self.id(),
+            &default_value,
+            TypeHint::JsonB,
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.default_value = default_value;
+        Ok(())
+    }
 
     pub fn path(&self) -> PropPath {
         self.path.to_owned().into()
@@ -555,8 +677,9 @@ impl Prop {
         }]);
 
         let func_name = "si:unset".to_string();
-        let mut funcs = Func::find_by_attr(ctx, "name", &func_name).await?;
-        let func = funcs.pop().ok_or(PropError::MissingFunc(func_name))?;
+        let func = Func::find_by_name(ctx, &func_name)
+            .await?
+            .ok_or(PropError::MissingFunc(func_name))?;
 
         // No matter what, we need a FuncBindingReturnValueId to create a new attribute prototype.
         // If the func binding was created, we execute on it to generate our value id. Otherwise,
@@ -660,10 +783,166 @@ impl Prop {
     }
 
     pub async fn set_default_diff(&mut self, ctx: &DalContext) -> PropResult<()> {
-        let func = Func::find_by_attr(ctx, "name", &"si:diff")
+        let func = Func::find_by_name(ctx, "si:diff")
             .await?
-            .pop()
             .ok_or(PropError::DefaultDiffFunctionNotFound)?;
         self.set_diff_func_id(ctx, Some(*func.id())).await
     }
+
+    /// Rename [`self`](Self) within an unfinalized [`SchemaVariant`], updating the "path" of
+    /// [`self`](Self) and every descendant as well as the name of the implicit
+    /// [`InternalProvider`] (if one has been created).
+    pub async fn set_name(&mut self, ctx: &DalContext, name: impl AsRef<str>) -> PropResult<()> {
+        let name = name.as_ref();
+        if name == self.name {
+            return Ok(());
+        }
+        self.ensure_schema_variant_is_unfinalized(ctx).await?;
+
+        let path = match self.parent_prop(ctx).await? {
+            Some(parent_prop) => {
+                if parent_prop
+                    .child_props(ctx)
+                    .await?
+                    .iter()
+                    .any(|sibling| sibling.name() == name)
+                {
+                    return Err(PropError::DuplicateChildName(
+                        *parent_prop.id(),
+                        name.to_owned(),
+                    ));
+                }
+                parent_prop.path().join(&PropPath::new([name]))
+            }
+            None => PropPath::new([name]),
+        };
+
+        standard_model::update(
+            ctx,
+            Self::table_name(),
+            "name",
+            self.id(),
+            &name,
+            TypeHint::Text,
+        )
+        .await?;
+        self.name = name.to_owned();
+        self.timestamp.updated_at = self.set_path(ctx, &path).await?;
+
+        // Descendant paths are prefixed with ours, so they need to follow along.
+        let mut work_queue: VecDeque<(PropPath, Prop)> = self
+            .child_props(ctx)
+            .await?
+            .into_iter()
+            .map(|child_prop| (path.clone(), child_prop))
+            .collect();
+        while let Some((parent_path, child_prop)) = work_queue.pop_front() {
+            let child_path = parent_path.join(&PropPath::new([child_prop.name()]));
+            child_prop.set_path(ctx, &child_path).await?;
+            work_queue.extend(
+                child_prop
+                    .child_props(ctx)
+                    .await?
+                    .into_iter()
+                    .map(|grandchild_prop| (child_path.clone(), grandchild_prop)),
+            );
+        }
+
+        if let Some(mut internal_provider) = InternalProvider::find_for_prop(ctx, self.id)
+            .await
+            .map_err(Box::new)?
+        {
+            internal_provider
+                .set_name(ctx, name)
+                .await
+                .map_err(Box::new)?;
+        }
+
+        Ok(())
+    }
+
+    async fn set_path(&self, ctx: &DalContext, path: &PropPath) -> PropResult<DateTime<Utc>> {
+        Ok(standard_model::update(
+            ctx,
+            Self::table_name(),
+            "path",
+            self.id(),
+            &path.as_str(),
+            TypeHint::Text,
+        )
+        .await?)
+    }
+
+    /// Delete [`self`](Self) and all of its descendants from an unfinalized [`SchemaVariant`].
+    ///
+    /// For every deleted [`Prop`], its [`AttributePrototypes`](AttributePrototype) (and their
+    /// values) are removed, and so is its implicit [`InternalProvider`] (if one has been created),
+    /// along with every [`AttributePrototypeArgument`] that used the provider as an input.
+    /// Validations are stored on the [`Prop`] itself and go away with it.
+    pub async fn delete_recursive(self, ctx: &DalContext) -> PropResult<()> {
+        if self.parent_prop(ctx).await?.is_none() {
+            return Err(PropError::CannotDeleteRootProp(self.id));
+        }
+        self.ensure_schema_variant_is_unfinalized(ctx).await?;
+
+        // Collect the whole subtree first, then delete it leaves first so that no child is ever
+        // left without its parent.
+        let mut props = Vec::new();
+        let mut work_queue = VecDeque::from([self]);
+        while let Some(prop) = work_queue.pop_front() {
+            work_queue.extend(prop.child_props(ctx).await?);
+            props.push(prop);
+        }
+
+        for mut prop in props.into_iter().rev() {
+            let attribute_context = AttributeContext::builder()
+                .set_prop_id(*prop.id())
+                .to_context()?;
+            for prototype in AttributePrototype::list_for_context(ctx, attribute_context).await? {
+                AttributePrototype::remove(ctx, prototype.id(), true).await?;
+            }
+
+            if let Some(mut internal_provider) = InternalProvider::find_for_prop(ctx, prop.id)
+                .await
+                .map_err(Box::new)?
+            {
+                for consumer_prototype in AttributePrototype::list_from_internal_provider_use(
+                    ctx,
+                    *internal_provider.id(),
+                )
+                .await?
+                {
+                    for mut argument in AttributePrototypeArgument::list_for_attribute_prototype(
+                        ctx,
+                        *consumer_prototype.id(),
+                    )
+                    .await?
+                    {
+                        if argument.internal_provider_id() == *internal_provider.id() {
+                            argument.delete_by_id(ctx).await?;
+                        }
+                    }
+                }
+                if let Some(attribute_prototype_id) = internal_provider.attribute_prototype_id() {
+                    AttributePrototype::remove(ctx, attribute_prototype_id, true).await?;
+                }
+                internal_provider.delete_by_id(ctx).await?;
+            }
+
+            standard_model::unset_belongs_to(ctx, "prop_belongs_to_prop", prop.id()).await?;
+            prop.delete_by_id(ctx).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_schema_variant_is_unfinalized(&self, ctx: &DalContext) -> PropResult<()> {
+        if let Some(schema_variant) = SchemaVariant::get_by_id(ctx, &self.schema_variant_id).await?
+        {
+            if schema_variant.finalized_once() {
+                return Err(PropError::SchemaVariantFinalized(self.schema_variant_id));
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,87 @@
+//! This module contains [`PropValueValidation`], the outcome of checking a candidate value against
+//! the "validation_format" of a [`Prop`] without persisting it, so that problems surface while a
+//! value is being typed rather than after it is saved.
+//!
+//! The check is run by veritech, with the same Joi that the "validation_format" was described
+//! with and that clients validate saved values with.
+
+use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use veritech_client::{FunctionResult, ValidationRequest};
+
+use crate::{DalContext, Prop, PropError, PropResult};
+
+/// The name of the handler that validates the candidate value.
+const VALIDATE_HANDLER: &str = "__siValidatePropValue";
+
+/// The outcome of validating a candidate value for a [`Prop`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PropValueValidation {
+    pub valid: bool,
+    /// Why the value is invalid, e.g. "\"value\" must be one of [tcp, udp, icmp]".
+    pub message: Option<String>,
+}
+
+impl PropValueValidation {
+    fn valid() -> Self {
+        Self {
+            valid: true,
+            message: None,
+        }
+    }
+}
+
+impl Prop {
+    /// Validate a candidate value against the "validation_format" of this [`Prop`] without
+    /// persisting it. [`Props`](Prop) without a "validation_format" accept any value.
+    pub async fn validate_value(
+        &self,
+        ctx: &DalContext,
+        value: Option<serde_json::Value>,
+    ) -> PropResult<PropValueValidation> {
+        let validation_format = match self.validation_format() {
+            Some(validation_format) => validation_format,
+            None => return Ok(PropValueValidation::valid()),
+        };
+
+        // Joi treats null as a value, which would fail validations for typed props that are unset,
+        // so it is handed undefined instead (as clients do).
+        let code = format!(
+            "const format = JSON.parse({});
+function {VALIDATE_HANDLER}(value) {{
+  const {{ error }} = Joi.build(format).validate(value === null ? undefined : value);
+  return {{ valid: !error, message: error?.message }};
+}}
+",
+            serde_json::to_string(validation_format)?,
+        );
+        let request = ValidationRequest {
+            execution_id: "validatepropvalue".to_owned(),
+            handler: VALIDATE_HANDLER.to_owned(),
+            value: value.unwrap_or(serde_json::Value::Null),
+            code_base64: general_purpose::STANDARD_NO_PAD.encode(code),
+            before: Vec::new(),
+        };
+
+        // The output of the validation is of no interest, but veritech needs somewhere to send it.
+        let (output_tx, mut rx) = mpsc::channel(64);
+        tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+        match ctx
+            .veritech()
+            .execute_validation(output_tx, &request)
+            .await?
+        {
+            FunctionResult::Success(success) => Ok(PropValueValidation {
+                valid: success.valid,
+                message: success.message,
+            }),
+            FunctionResult::Failure(failure) => Err(PropError::ValidationFormat(
+                *self.id(),
+                failure.error.message,
+            )),
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! This module contains [`PropPermission`], which restricts sensitive [`Props`](crate::Prop) (and
+//! everything beneath them) to certain [`WorkspaceRoles`](WorkspaceRole), and [`PropAccess`],
+//! which enforces those restrictions for the actor of a [`DalContext`].
+
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use std::collections::HashMap;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::prop::PropPath;
+use crate::property_editor::tree::PropertyEditorTreeNode;
+use crate::property_editor::values::PropertyEditorValues;
+use crate::{
+    standard_model, DalContext, HistoryActor, Prop, PropId, StandardModel, StandardModelError,
+    Timestamp, TransactionsError, User, UserError, WorkspacePk, WorkspaceRole,
+};
+
+const LIST: &str = include_str!("queries/prop_permission/list.sql");
+const UPSERT: &str = include_str!("queries/prop_permission/upsert.sql");
+const REMOVE: &str = include_str!("queries/prop_permission/remove.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum PropPermissionError {
+    #[error("access to prop {0} is restricted for role {1:?}")]
+    Forbidden(String, Option<WorkspaceRole>),
+    #[error("invalid prop path (must start with \"/root\"): {0}")]
+    InvalidPath(String),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error("prop not found: {0}")]
+    PropNotFound(PropId),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    User(#[from] UserError),
+}
+
+pub type PropPermissionResult<T> = Result<T, PropPermissionError>;
+
+/// Restricts the [`Prop`] found at `path` (e.g. "/root/secrets") and all of its descendants to
+/// the `allowed_roles` within a [`Workspace`](crate::Workspace).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PropPermission {
+    workspace_pk: WorkspacePk,
+    path: String,
+    allowed_roles: Vec<WorkspaceRole>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl PropPermission {
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn allowed_roles(&self) -> &[WorkspaceRole] {
+        &self.allowed_roles
+    }
+
+    /// Returns true if the [`Prop`] at the given [`PropPath`] falls under this restriction.
+    pub fn covers(&self, prop_path: &PropPath) -> bool {
+        prop_path.is_descendant_of(&prop_path_from_str(&self.path))
+    }
+
+    pub async fn list(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+    ) -> PropPermissionResult<Vec<Self>> {
+        let rows = ctx.txns().await?.pg().query(LIST, &[&workspace_pk]).await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Create or replace the restriction for the given path.
+    pub async fn upsert(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        path: impl AsRef<str>,
+        allowed_roles: Vec<WorkspaceRole>,
+    ) -> PropPermissionResult<Self> {
+        let path = normalize_path(path.as_ref())?;
+        let allowed_roles: Vec<String> = allowed_roles.iter().map(ToString::to_string).collect();
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(UPSERT, &[&workspace_pk, &path, &allowed_roles])
+            .await?;
+        Ok(standard_model::object_from_row(row)?)
+    }
+
+    pub async fn remove(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        path: impl AsRef<str>,
+    ) -> PropPermissionResult<()> {
+        let path = normalize_path(path.as_ref())?;
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(REMOVE, &[&workspace_pk, &path])
+            .await?;
+        Ok(())
+    }
+}
+
+/// The [`PropPermissions`](PropPermission) that apply to the actor of a [`DalContext`], along
+/// with the actor's [`WorkspaceRole`].
+#[derive(Debug, Clone)]
+pub struct PropAccess {
+    role: Option<WorkspaceRole>,
+    restrictions: Vec<PropPermission>,
+}
+
+impl PropAccess {
+    /// Gather the restrictions for the [`Workspace`](crate::Workspace) in the tenancy of the
+    /// context and the role of its [`HistoryActor`]. The system is never restricted.
+    #[instrument(skip(ctx))]
+    pub async fn for_context(ctx: &DalContext) -> PropPermissionResult<Self> {
+        let (user_pk, workspace_pk) = match (ctx.history_actor(), ctx.tenancy().workspace_pk()) {
+            (HistoryActor::User(user_pk), Some(workspace_pk)) => (*user_pk, workspace_pk),
+            _ => return Ok(Self::unrestricted()),
+        };
+
+        let restrictions = PropPermission::list(ctx, workspace_pk).await?;
+        if restrictions.is_empty() {
+            return Ok(Self::unrestricted());
+        }
+        let role = User::workspace_role(ctx, user_pk, workspace_pk).await?;
+
+        Ok(Self { role, restrictions })
+    }
+
+    fn unrestricted() -> Self {
+        Self {
+            role: None,
+            restrictions: Vec::new(),
+        }
+    }
+
+    pub fn role(&self) -> Option<WorkspaceRole> {
+        self.role
+    }
+
+    /// Returns true if every restriction covering the given [`PropPath`] allows the role.
+    pub fn can_access(&self, prop_path: &PropPath) -> bool {
+        self.restrictions
+            .iter()
+            .filter(|restriction| restriction.covers(prop_path))
+            .all(|restriction| match self.role {
+                Some(role) => restriction.allowed_roles.contains(&role),
+                None => false,
+            })
+    }
+
+    /// Fails with [`PropPermissionError::Forbidden`] if the given [`Prop`] may not be written.
+    pub async fn ensure_can_write(
+        &self,
+        ctx: &DalContext,
+        prop_id: PropId,
+    ) -> PropPermissionResult<()> {
+        if self.restrictions.is_empty() {
+            return Ok(());
+        }
+        let prop = Prop::get_by_id(ctx, &prop_id)
+            .await?
+            .ok_or(PropPermissionError::PropNotFound(prop_id))?;
+        let prop_path = prop.path();
+        if !self.can_access(&prop_path) {
+            return Err(PropPermissionError::Forbidden(
+                format!("/{}", prop_path.with_replaced_sep("/")),
+                self.role,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Replace the values of the restricted [`Props`](Prop) with `null`.
+    pub async fn redact(
+        &self,
+        ctx: &DalContext,
+        values: &mut PropertyEditorValues,
+    ) -> PropPermissionResult<()> {
+        if self.restrictions.is_empty() {
+            return Ok(());
+        }
+
+        let mut accessible_by_prop: HashMap<PropId, bool> = HashMap::new();
+        for value in values.values.values_mut() {
+            let prop_id = value.prop_id();
+            let accessible = match accessible_by_prop.get(&prop_id) {
+                Some(accessible) => *accessible,
+                None => {
+                    let prop = Prop::get_by_id(ctx, &prop_id)
+                        .await?
+                        .ok_or(PropPermissionError::PropNotFound(prop_id))?;
+                    let accessible = self.can_access(&prop.path());
+                    accessible_by_prop.insert(prop_id, accessible);
+                    accessible
+                }
+            };
+            if !accessible {
+                value.redact();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace the values of the restricted [`Props`](Prop) of a property editor tree with
+    /// `null`.
+    pub async fn redact_tree(
+        &self,
+        ctx: &DalContext,
+        tree: &mut PropertyEditorTreeNode,
+    ) -> PropPermissionResult<()> {
+        if self.restrictions.is_empty() {
+            return Ok(());
+        }
+
+        let mut accessible_by_prop: HashMap<PropId, bool> = HashMap::new();
+        let mut work_queue = vec![tree];
+        while let Some(node) = work_queue.pop() {
+            let prop_id = node.prop_id();
+            let accessible = match accessible_by_prop.get(&prop_id) {
+                Some(accessible) => *accessible,
+                None => {
+                    let prop = Prop::get_by_id(ctx, &prop_id)
+                        .await?
+                        .ok_or(PropPermissionError::PropNotFound(prop_id))?;
+                    let accessible = self.can_access(&prop.path());
+                    accessible_by_prop.insert(prop_id, accessible);
+                    accessible
+                }
+            };
+            if !accessible {
+                node.redact();
+            }
+            work_queue.extend(node.children.iter_mut());
+        }
+
+        Ok(())
+    }
+}
+
+/// Accepts "/root/secrets" (or "root/secrets") and returns "/root/secrets".
+fn normalize_path(path: &str) -> PropPermissionResult<String> {
+    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    if parts.first() != Some(&"root") {
+        return Err(PropPermissionError::InvalidPath(path.to_owned()));
+    }
+    Ok(format!("/{}", parts.join("/")))
+}
+
+fn prop_path_from_str(path: &str) -> PropPath {
+    PropPath::new(path.split('/').filter(|part| !part.is_empty()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn access(
+        role: Option<WorkspaceRole>,
+        restrictions: &[(&str, &[WorkspaceRole])],
+    ) -> PropAccess {
+        PropAccess {
+            role,
+            restrictions: restrictions
+                .iter()
+                .map(|(path, allowed_roles)| PropPermission {
+                    workspace_pk: WorkspacePk::NONE,
+                    path: path.to_string(),
+                    allowed_roles: allowed_roles.to_vec(),
+                    timestamp: Timestamp::now(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn restrictions_cover_descendants() {
+        let access = access(
+            Some(WorkspaceRole::Editor),
+            &[("/root/secrets", &[WorkspaceRole::Admin])],
+        );
+
+        assert!(!access.can_access(&PropPath::new(["root", "secrets"])));
+        assert!(!access.can_access(&PropPath::new(["root", "secrets", "aws_credential"])));
+        assert!(access.can_access(&PropPath::new(["root", "domain", "region"])));
+        assert!(access.can_access(&PropPath::new(["root"])));
+    }
+
+    #[test]
+    fn every_covering_restriction_must_allow_the_role() {
+        let access = access(
+            Some(WorkspaceRole::Editor),
+            &[
+                (
+                    "/root/domain",
+                    &[WorkspaceRole::Admin, WorkspaceRole::Editor],
+                ),
+                ("/root/domain/password", &[WorkspaceRole::Admin]),
+            ],
+        );
+
+        assert!(access.can_access(&PropPath::new(["root", "domain", "region"])));
+        assert!(!access.can_access(&PropPath::new(["root", "domain", "password"])));
+    }
+
+    #[test]
+    fn members_without_a_role_are_restricted() {
+        let access = access(None, &[("/root/secrets", &[WorkspaceRole::Admin])]);
+
+        assert!(!access.can_access(&PropPath::new(["root", "secrets"])));
+        assert!(access.can_access(&PropPath::new(["root", "domain"])));
+    }
+
+    #[test]
+    fn normalizes_paths() {
+        assert_eq!(
+            "/root/secrets",
+            normalize_path("root/secrets/").expect("could not normalize path")
+        );
+        assert!(normalize_path("/domain/region").is_err());
+    }
+}
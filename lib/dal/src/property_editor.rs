@@ -12,6 +12,7 @@ use crate::{
 };
 
 pub mod schema;
+pub mod tree;
 pub mod values;
 
 #[remain::sorted]
@@ -3,16 +3,18 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use strum::{AsRefStr, Display, EnumString};
 
 use si_pkg::PropSpecWidgetKind;
 
 use crate::property_editor::{PropertyEditorError, PropertyEditorPropId, PropertyEditorResult};
-use crate::{DalContext, Prop, PropKind, SchemaVariant, SchemaVariantId, StandardModel};
+use crate::{DalContext, Prop, PropId, PropKind, SchemaVariant, SchemaVariantId, StandardModel};
 
 const PROPERTY_EDITOR_SCHEMA_FOR_SCHEMA_VARIANT: &str =
     include_str!("../queries/property_editor_schema_for_schema_variant.sql");
+const PROPERTY_EDITOR_SOCKET_DRIVEN_PROPS_FOR_SCHEMA_VARIANT: &str =
+    include_str!("../queries/property_editor_socket_driven_props_for_schema_variant.sql");
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -36,6 +38,21 @@ impl PropertyEditorSchema {
         let mut child_props: HashMap<PropertyEditorPropId, Vec<PropertyEditorPropId>> =
             HashMap::new();
 
+        // Props whose values are set from an input socket on the schema variant level are marked,
+        // so that the editor can render them as driven by a connection.
+        let socket_driven_prop_ids: HashSet<PropId> = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                PROPERTY_EDITOR_SOCKET_DRIVEN_PROPS_FOR_SCHEMA_VARIANT,
+                &[ctx.tenancy(), ctx.visibility(), &schema_variant.id()],
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.try_get("prop_id"))
+            .collect::<Result<_, _>>()?;
+
         let rows = ctx
             .txns()
             .await?
@@ -53,7 +70,9 @@ impl PropertyEditorSchema {
             if prop.json_pointer().starts_with("/root/secret_definition") {
                 continue;
             }
-            let property_editor_prop = PropertyEditorProp::new(prop);
+            let is_socket_driven = socket_driven_prop_ids.contains(prop.id());
+            let mut property_editor_prop = PropertyEditorProp::new(prop);
+            property_editor_prop.is_socket_driven = is_socket_driven;
 
             let maybe_child_prop_ids: Option<Vec<PropertyEditorPropId>> =
                 row.try_get("child_prop_ids")?;
@@ -85,6 +104,7 @@ pub struct PropertyEditorProp {
     pub doc_link: Option<String>,
     pub documentation: Option<String>,
     pub validation_format: Option<String>,
+    pub is_socket_driven: bool,
 }
 
 impl PropertyEditorProp {
@@ -100,6 +120,7 @@ impl PropertyEditorProp {
             doc_link: prop.doc_link().map(Into::into),
             documentation: prop.documentation().map(Into::into),
             validation_format: prop.validation_format().map(Into::into),
+            is_socket_driven: false,
         }
     }
 }
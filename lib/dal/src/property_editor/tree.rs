@@ -0,0 +1,74 @@
+//! This module contains the ability to fetch the entire tree of a [`Component`](crate::Component)'s
+//! properties, along with their current values, in a single query.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::property_editor::{
+    PropertyEditorError, PropertyEditorPropId, PropertyEditorResult, PropertyEditorValueId,
+};
+use crate::{ComponentId, DalContext, PropId, PropKind};
+
+const PROPERTY_EDITOR_TREE_FOR_COMPONENT: &str =
+    include_str!("../queries/property_editor_tree_for_component.sql");
+
+/// A [`Prop`](crate::Prop) of a [`Component`](crate::Component) along with its current value
+/// and, in order, the nodes of its child values. The tree starts at the root [`Prop`](crate::Prop)
+/// and is assembled by the database, rather than value by value like
+/// [`PropertyEditorValues`](crate::property_editor::values::PropertyEditorValues).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyEditorTreeNode {
+    pub id: PropertyEditorValueId,
+    pub prop_id: PropertyEditorPropId,
+    pub name: String,
+    pub kind: PropKind,
+    pub widget_kind: String,
+    pub hidden: bool,
+    /// The key of the value, for elements of maps.
+    pub key: Option<String>,
+    /// The [`content generation`](crate::AttributeValue::content_generation) of the value.
+    pub generation: i64,
+    pub value: Value,
+    pub is_from_external_source: bool,
+    pub children: Vec<PropertyEditorTreeNode>,
+}
+
+impl PropertyEditorTreeNode {
+    /// Returns the tree of the [`Component`](crate::Component)'s properties, starting at its root.
+    pub async fn for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> PropertyEditorResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                PROPERTY_EDITOR_TREE_FOR_COMPONENT,
+                &[ctx.tenancy(), ctx.visibility(), &component_id],
+            )
+            .await?;
+        let tree: Option<Value> = row.try_get("object")?;
+        match tree {
+            Some(tree) => Ok(serde_json::from_value(tree)?),
+            None => Err(PropertyEditorError::RootPropNotFound),
+        }
+    }
+
+    /// Returns the node of the child [`Prop`](crate::Prop) with the given name, for objects.
+    pub fn child(&self, name: impl AsRef<str>) -> Option<&Self> {
+        self.children
+            .iter()
+            .find(|child| child.name == name.as_ref())
+    }
+
+    pub fn prop_id(&self) -> PropId {
+        self.prop_id.into()
+    }
+
+    /// Hide the value from an actor that may not read it.
+    pub fn redact(&mut self) {
+        self.value = Value::Null;
+    }
+}
@@ -72,6 +72,7 @@ impl PropertyEditorValues {
                     id: work_attribute_value_id.into(),
                     prop_id: (*work.prop.id()).into(),
                     key: work.attribute_value.key().map(Into::into),
+                    generation: work.attribute_value.content_generation(),
                     value: work
                         .func_binding_return_value
                         .and_then(|f| f.value().cloned())
@@ -107,6 +108,8 @@ pub struct PropertyEditorValue {
     pub id: PropertyEditorValueId,
     prop_id: PropertyEditorPropId,
     pub key: Option<String>,
+    /// The [`content generation`](AttributeValue::content_generation) of the value.
+    generation: i64,
     value: Value,
     is_from_external_source: bool,
 }
@@ -120,10 +123,19 @@ impl PropertyEditorValue {
         self.value.clone()
     }
 
+    pub fn generation(&self) -> i64 {
+        self.generation
+    }
+
     pub fn prop_id(&self) -> PropId {
         self.prop_id.into()
     }
 
+    /// Hide the value from an actor that may not read it.
+    pub fn redact(&mut self) {
+        self.value = Value::Null;
+    }
+
     /// Returns the [`Prop`](crate::Prop) corresponding to the "prop_id" field.
     pub async fn prop(&self, ctx: &DalContext) -> PropertyEditorResult<Prop> {
         let prop = Prop::get_by_id(ctx, &self.prop_id.into())
@@ -12,4 +12,5 @@
 //!   (or we will at least have enough data to know which providers the user wants to "connect")
 
 pub mod external;
+pub mod history;
 pub mod internal;
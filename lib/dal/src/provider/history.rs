@@ -0,0 +1,207 @@
+//! This module contains [`ProviderValueHistoryEntry`], a record of a value emitted by an
+//! [`ExternalProvider`](crate::ExternalProvider) or an explicit
+//! [`InternalProvider`](crate::InternalProvider) of a [`Component`](crate::Component), and which
+//! job computed it.
+//!
+//! Entries are recorded as [`DependentValuesUpdate`](crate::job::definition::DependentValuesUpdate)
+//! jobs update the values of providers, so that the sequence of values flowing across
+//! connected [`Components`](crate::Component) can be inspected when they oscillate. Only the
+//! [`latest entries`](PROVIDER_VALUE_HISTORY_RETENTION) are kept for every value.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use thiserror::Error;
+
+use crate::func::binding_return_value::{
+    FuncBindingReturnValue, FuncBindingReturnValueError, FuncBindingReturnValueId,
+};
+use crate::{
+    pk, standard_model, AttributeValue, AttributeValueId, ChangeSetPk, ComponentId, DalContext,
+    ExternalProviderId, InternalProvider, InternalProviderError, InternalProviderId, StandardModel,
+    StandardModelError, TransactionsError, WorkspacePk,
+};
+
+const INSERT: &str = include_str!("../queries/provider_value_history/insert.sql");
+const PRUNE: &str = include_str!("../queries/provider_value_history/prune.sql");
+const LIST_FOR_COMPONENT_AND_PROVIDER: &str =
+    include_str!("../queries/provider_value_history/list_for_component_and_provider.sql");
+
+/// How many entries are kept for the value of a provider on a component, in a change set.
+pub const PROVIDER_VALUE_HISTORY_RETENTION: i64 = 50;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ProviderValueHistoryError {
+    #[error("func binding return value error: {0}")]
+    FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
+    #[error("internal provider error: {0}")]
+    InternalProvider(#[from] InternalProviderError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ProviderValueHistoryResult<T> = Result<T, ProviderValueHistoryError>;
+
+pk!(ProviderValueHistoryEntryPk);
+
+/// A value emitted by a provider of a [`Component`](crate::Component), and the job that computed
+/// it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProviderValueHistoryEntry {
+    pk: ProviderValueHistoryEntryPk,
+    #[serde(rename = "tenancy_workspace_pk")]
+    workspace_pk: WorkspacePk,
+    #[serde(rename = "visibility_change_set_pk")]
+    change_set_pk: ChangeSetPk,
+    attribute_value_id: AttributeValueId,
+    component_id: ComponentId,
+    internal_provider_id: InternalProviderId,
+    external_provider_id: ExternalProviderId,
+    func_binding_return_value_id: FuncBindingReturnValueId,
+    value: Option<serde_json::Value>,
+    job_id: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl ProviderValueHistoryEntry {
+    pub fn pk(&self) -> ProviderValueHistoryEntryPk {
+        self.pk
+    }
+
+    pub fn change_set_pk(&self) -> ChangeSetPk {
+        self.change_set_pk
+    }
+
+    pub fn attribute_value_id(&self) -> AttributeValueId {
+        self.attribute_value_id
+    }
+
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    pub fn internal_provider_id(&self) -> InternalProviderId {
+        self.internal_provider_id
+    }
+
+    pub fn external_provider_id(&self) -> ExternalProviderId {
+        self.external_provider_id
+    }
+
+    pub fn func_binding_return_value_id(&self) -> FuncBindingReturnValueId {
+        self.func_binding_return_value_id
+    }
+
+    pub fn value(&self) -> Option<&serde_json::Value> {
+        self.value.as_ref()
+    }
+
+    /// The id of the job that computed the value, if it was computed by a queued job.
+    pub fn job_id(&self) -> Option<&str> {
+        self.job_id.as_deref()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Record the current value of an [`AttributeValue`] if it belongs to the
+    /// [`ExternalProvider`](crate::ExternalProvider) or explicit [`InternalProvider`] of a
+    /// [`Component`](crate::Component), pruning the entries beyond the
+    /// [`retention`](PROVIDER_VALUE_HISTORY_RETENTION). Values of implicit
+    /// [`InternalProviders`](InternalProvider) (those of props) are not recorded.
+    pub async fn record(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+        job_id: Option<&str>,
+    ) -> ProviderValueHistoryResult<Option<Self>> {
+        let workspace_pk = match ctx.tenancy().workspace_pk() {
+            Some(workspace_pk) => workspace_pk,
+            None => return Ok(None),
+        };
+        let context = attribute_value.context;
+        if context.is_component_unset() {
+            return Ok(None);
+        }
+        if context.is_external_provider_unset() {
+            if context.is_internal_provider_unset() {
+                return Ok(None);
+            }
+            let internal_provider =
+                InternalProvider::get_by_id(ctx, &context.internal_provider_id()).await?;
+            if internal_provider.map_or(true, |provider| provider.is_internal_consumer()) {
+                return Ok(None);
+            }
+        }
+
+        let value =
+            FuncBindingReturnValue::get_by_id(ctx, &attribute_value.func_binding_return_value_id())
+                .await?
+                .and_then(|func_binding_return_value| {
+                    func_binding_return_value.unprocessed_value().cloned()
+                });
+
+        let txns = ctx.txns().await?;
+        let row = txns
+            .pg()
+            .query_one(
+                INSERT,
+                &[
+                    &workspace_pk,
+                    &ctx.visibility().change_set_pk,
+                    attribute_value.id(),
+                    &context.component_id(),
+                    &context.internal_provider_id(),
+                    &context.external_provider_id(),
+                    &attribute_value.func_binding_return_value_id(),
+                    &value,
+                    &job_id,
+                ],
+            )
+            .await?;
+        txns.pg()
+            .execute(
+                PRUNE,
+                &[
+                    &workspace_pk,
+                    &ctx.visibility().change_set_pk,
+                    attribute_value.id(),
+                    &PROVIDER_VALUE_HISTORY_RETENTION,
+                ],
+            )
+            .await?;
+
+        Ok(Some(standard_model::object_from_row(row)?))
+    }
+
+    /// List the values emitted by a provider of a [`Component`](crate::Component), most recent
+    /// first. Entries recorded on head are included when in a change set.
+    pub async fn list_for_component_and_provider(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        internal_provider_id: InternalProviderId,
+        external_provider_id: ExternalProviderId,
+    ) -> ProviderValueHistoryResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_COMPONENT_AND_PROVIDER,
+                &[
+                    &ctx.tenancy().workspace_pk(),
+                    &ctx.visibility().change_set_pk,
+                    &component_id,
+                    &internal_provider_id,
+                    &external_provider_id,
+                ],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+}
@@ -545,6 +545,18 @@ impl InternalProvider {
         ctx: &DalContext,
         socket_id: SocketId,
     ) -> InternalProviderResult<Option<Self>> {
+        let cached = ctx.attribute_context_cache_enabled();
+        if cached {
+            if let Some(internal_provider) = ctx
+                .txns()
+                .await?
+                .attribute_context_cache()
+                .explicit_internal_provider_by_socket(ctx, socket_id)
+            {
+                return Ok(Some(internal_provider));
+            }
+        }
+
         let row = ctx
             .txns()
             .await?
@@ -554,7 +566,14 @@ impl InternalProvider {
                 &[ctx.tenancy(), ctx.visibility(), &socket_id],
             )
             .await?;
-        Ok(object_option_from_row_option(row)?)
+        let internal_provider: Option<Self> = object_option_from_row_option(row)?;
+        if let (true, Some(internal_provider)) = (cached, &internal_provider) {
+            ctx.txns()
+                .await?
+                .attribute_context_cache_mut()
+                .insert_explicit_internal_provider_by_socket(ctx, socket_id, internal_provider);
+        }
+        Ok(internal_provider)
     }
 
     /// Find all [`Self`] for a given [`AttributePrototypeId`](crate::AttributePrototype).
@@ -606,13 +625,32 @@ impl InternalProvider {
         ctx: &DalContext,
         prop_id: PropId,
     ) -> InternalProviderResult<Option<Self>> {
+        let cached = ctx.attribute_context_cache_enabled();
+        if cached {
+            if let Some(internal_provider) = ctx
+                .txns()
+                .await?
+                .attribute_context_cache()
+                .internal_provider_by_prop(ctx, prop_id)
+            {
+                return Ok(Some(internal_provider));
+            }
+        }
+
         let row = ctx
             .txns()
             .await?
             .pg()
             .query_opt(FIND_FOR_PROP, &[ctx.tenancy(), ctx.visibility(), &prop_id])
             .await?;
-        Ok(object_option_from_row_option(row)?)
+        let internal_provider: Option<Self> = object_option_from_row_option(row)?;
+        if let (true, Some(internal_provider)) = (cached, &internal_provider) {
+            ctx.txns()
+                .await?
+                .attribute_context_cache_mut()
+                .insert_internal_provider_by_prop(ctx, prop_id, internal_provider);
+        }
+        Ok(internal_provider)
     }
 
     #[tracing::instrument(skip(ctx))]
@@ -9,12 +9,12 @@ use crate::func::binding_return_value::FuncBindingReturnValueId;
 use crate::{
     func::binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueError},
     ws_event::{WsEvent, WsPayload},
-    ComponentError, ComponentId, DalContext, FuncId, StandardModel, StandardModelError,
-    WsEventResult,
+    ChangeSetPk, ComponentError, ComponentId, DalContext, FuncId, StandardModel,
+    StandardModelError, WsEventResult,
 };
 use crate::{standard_model, TransactionsError};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct QualificationSummaryForComponent {
     pub component_id: ComponentId,
     pub component_name: String,
@@ -33,6 +33,17 @@ pub struct QualificationSummary {
     pub components: Vec<QualificationSummaryForComponent>,
 }
 
+impl QualificationSummaryForComponent {
+    /// Returns the qualification counts of a [`Component`](crate::Component), or `None` if they
+    /// haven't been computed yet.
+    pub async fn get_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> QualificationSummaryResult<Option<Self>> {
+        Ok(standard_model::get_by_id(ctx, "summary_qualifications", &component_id).await?)
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -242,3 +253,42 @@ impl WsEvent {
         .await
     }
 }
+
+/// The qualification counts of a [`Component`](crate::Component) after they changed, so
+/// summaries can be kept up to date without fetching them again.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QualificationStatusChangedPayload {
+    component_id: ComponentId,
+    component_name: String,
+    change_set_pk: ChangeSetPk,
+    total: i64,
+    warned: i64,
+    succeeded: i64,
+    failed: i64,
+    /// Whether the component was deleted, in which case it should be dropped from summaries.
+    deleted: bool,
+}
+
+impl WsEvent {
+    pub async fn qualification_status_changed(
+        ctx: &DalContext,
+        summary: QualificationSummaryForComponent,
+        deleted: bool,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::QualificationStatusChanged(QualificationStatusChangedPayload {
+                component_id: summary.component_id,
+                component_name: summary.component_name,
+                change_set_pk: ctx.visibility().change_set_pk,
+                total: summary.total,
+                warned: summary.warned,
+                succeeded: summary.succeeded,
+                failed: summary.failed,
+                deleted,
+            }),
+        )
+        .await
+    }
+}
@@ -0,0 +1,6 @@
+UPDATE api_tokens AS t
+SET last_used_at = CLOCK_TIMESTAMP()
+WHERE t.token_hash = $1
+  AND t.revoked_at IS NULL
+  AND (t.expires_at IS NULL OR t.expires_at > CLOCK_TIMESTAMP())
+RETURNING row_to_json(t.*) AS object
@@ -0,0 +1,3 @@
+INSERT INTO api_tokens AS t (workspace_pk, name, scope, token_hash, created_by_user_pk, expires_at)
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING row_to_json(t.*) AS object
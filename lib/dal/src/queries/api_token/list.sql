@@ -0,0 +1,4 @@
+SELECT row_to_json(t.*) AS object
+FROM api_tokens AS t
+WHERE t.workspace_pk = $1
+ORDER BY t.created_at DESC
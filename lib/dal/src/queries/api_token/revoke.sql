@@ -0,0 +1,6 @@
+UPDATE api_tokens AS t
+SET revoked_at = COALESCE(t.revoked_at, CLOCK_TIMESTAMP()),
+    updated_at = CLOCK_TIMESTAMP()
+WHERE t.workspace_pk = $1
+  AND t.pk = $2
+RETURNING row_to_json(t.*) AS object
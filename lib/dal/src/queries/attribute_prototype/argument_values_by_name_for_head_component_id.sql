@@ -1,6 +1,7 @@
 /*
     This query groups arguments that belong to an attribute prototype by name. For every argument that shares the same
-    name, they will be in the same "array".
+    name, they will be in the same "array", in the order the arguments were created. The tail component of every
+    argument is in "tail_component_ids", in the same order.
 
     { key: name, value: [argument_with_same_name_1, argument_with_same_name_2] },
     { key: name, value: [argument_that_only_has_this_name] }
@@ -11,8 +12,14 @@ FROM (SELECT attribute_prototype_id,
              array_agg(CASE
                            WHEN internal_provider_data.internal_provider_id IS NOT NULL
                                THEN internal_provider_data.value
-                           ELSE external_provider_data.value END) AS values
+                           ELSE external_provider_data.value END
+                       ORDER BY prototype_argument_data.created_at,
+                           prototype_argument_data.tail_component_id) AS values,
+             array_agg(prototype_argument_data.tail_component_id
+                       ORDER BY prototype_argument_data.created_at,
+                           prototype_argument_data.tail_component_id) AS tail_component_ids
       FROM (SELECT apa.attribute_prototype_id,
+                   apa.created_at,
                    fa.name,
                    apa.internal_provider_id,
                    apa.external_provider_id,
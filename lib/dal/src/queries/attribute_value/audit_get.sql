@@ -0,0 +1,5 @@
+SELECT row_to_json(a.*) AS object
+FROM attribute_value_audits AS a
+WHERE a.tenancy_workspace_pk = $1
+  AND a.visibility_change_set_pk IN ($2, ident_nil_v1())
+  AND a.pk = $3
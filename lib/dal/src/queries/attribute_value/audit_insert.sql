@@ -0,0 +1,5 @@
+INSERT INTO attribute_value_audits AS a (tenancy_workspace_pk, visibility_change_set_pk, attribute_value_id,
+                                         component_id, prop_id, previous_func_binding_return_value_id,
+                                         func_binding_return_value_id, actor)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+RETURNING row_to_json(a.*) AS object
@@ -0,0 +1,7 @@
+SELECT row_to_json(a.*) AS object
+FROM attribute_value_audits AS a
+WHERE a.tenancy_workspace_pk = $1
+  AND a.visibility_change_set_pk IN ($2, ident_nil_v1())
+  AND a.component_id = $3
+  AND a.prop_id = $4
+ORDER BY a.created_at DESC
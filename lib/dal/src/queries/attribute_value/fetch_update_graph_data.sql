@@ -1,4 +1,13 @@
-SELECT attribute_value_id,
-       array_agg(dependent_attribute_value_id) AS dependent_attribute_value_ids
-FROM attribute_value_affected_graph_v1($1, $2, $3)
-GROUP BY attribute_value_id;
+WITH archived_attribute_value_ids AS (
+    SELECT av.id
+    FROM attribute_values_v1($1, $2) AS av
+    INNER JOIN components_v1($1, $2) AS c
+        ON c.id = av.attribute_context_component_id
+    WHERE c.archived
+)
+SELECT graph.attribute_value_id,
+       array_agg(graph.dependent_attribute_value_id) AS dependent_attribute_value_ids
+FROM attribute_value_affected_graph_v1($1, $2, $3) AS graph
+WHERE graph.attribute_value_id NOT IN (SELECT id FROM archived_attribute_value_ids)
+  AND graph.dependent_attribute_value_id NOT IN (SELECT id FROM archived_attribute_value_ids)
+GROUP BY graph.attribute_value_id;
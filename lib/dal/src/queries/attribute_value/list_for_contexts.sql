@@ -0,0 +1,21 @@
+SELECT DISTINCT ON (
+    contexts.index,
+    COALESCE(belongs_to_id, ''),
+    attribute_context_prop_id,
+    attribute_context_internal_provider_id,
+    attribute_context_external_provider_id,
+    COALESCE(key, '')
+    ) contexts.index       AS index,
+      row_to_json(av.*)    AS object
+FROM jsonb_array_elements($3) WITH ORDINALITY AS contexts(context, index)
+         INNER JOIN attribute_values_v1($1, $2) AS av
+                    ON in_attribute_context_v1(contexts.context, av)
+         LEFT JOIN attribute_value_belongs_to_attribute_value_v1($1, $2) AS avbtav
+                   ON avbtav.object_id = av.id
+ORDER BY contexts.index,
+         COALESCE(belongs_to_id, ''),
+         attribute_context_prop_id DESC,
+         attribute_context_internal_provider_id DESC,
+         attribute_context_external_provider_id DESC,
+         COALESCE(key, ''),
+         attribute_context_component_id DESC
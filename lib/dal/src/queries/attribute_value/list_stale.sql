@@ -0,0 +1,22 @@
+SELECT DISTINCT ON (av.id) av.id                             AS attribute_value_id,
+                           av.attribute_context_component_id AS component_id,
+                           av.updated_at                     AS updated_at,
+                           upstream.id                       AS upstream_attribute_value_id,
+                           upstream.updated_at               AS upstream_updated_at
+FROM attribute_values_v1($1, $2) AS av
+         INNER JOIN attribute_value_belongs_to_attribute_prototype_v1($1, $2) AS avbtap
+                    ON avbtap.object_id = av.id
+         INNER JOIN attribute_prototype_arguments_v1($1, $2) AS apa
+                    ON apa.attribute_prototype_id = avbtap.belongs_to_id
+         INNER JOIN attribute_values_v1($1, $2) AS upstream
+                    ON (apa.internal_provider_id != ident_nil_v1()
+                        AND upstream.attribute_context_internal_provider_id = apa.internal_provider_id
+                        AND upstream.attribute_context_component_id = av.attribute_context_component_id)
+                        OR (apa.external_provider_id != ident_nil_v1()
+                            AND upstream.attribute_context_external_provider_id = apa.external_provider_id
+                            AND upstream.attribute_context_component_id = apa.tail_component_id
+                            AND apa.head_component_id = av.attribute_context_component_id)
+WHERE av.attribute_context_component_id != ident_nil_v1()
+  AND ($3::ident IS NULL OR av.attribute_context_component_id = $3)
+  AND upstream.updated_at > av.updated_at
+ORDER BY av.id, upstream.updated_at DESC
@@ -0,0 +1,4 @@
+SELECT EXISTS(SELECT 1
+              FROM builtin_migration_checkpoints AS c
+              WHERE c.name = $1
+                AND c.content_hash = $2) AS completed
@@ -0,0 +1,3 @@
+INSERT INTO builtin_migration_checkpoints (name, content_hash)
+VALUES ($1, $2)
+ON CONFLICT (name, content_hash) DO NOTHING
@@ -1,4 +0,0 @@
-UPDATE change_sets
-SET status = 'Abandoned', updated_at = now()
-WHERE pk = $1
-RETURNING updated_at
\ No newline at end of file
@@ -0,0 +1,57 @@
+-- Components written in either of two change sets, with how each change set changed them: their
+-- row was created ("Added"), deleted ("Deleted") or either it or one of its values was written
+-- ("Modified"). A change set that did not touch the component has no change.
+WITH touched AS (SELECT c.id
+                 FROM components AS c
+                 WHERE c.tenancy_workspace_pk = $1
+                   AND c.visibility_change_set_pk IN ($2, $3)
+                 UNION
+                 SELECT av.attribute_context_component_id
+                 FROM attribute_values AS av
+                 WHERE av.tenancy_workspace_pk = $1
+                   AND av.visibility_change_set_pk IN ($2, $3)
+                   AND av.attribute_context_component_id != ident_nil_v1())
+SELECT t.id AS component_id,
+       CASE
+           WHEN lc.deleted THEN 'Deleted'
+           WHEN lc.deleted IS NOT NULL AND head.present IS NULL THEN 'Added'
+           WHEN lc.deleted IS NOT NULL OR lv.touched THEN 'Modified'
+           END AS left_change,
+       CASE
+           WHEN rc.deleted THEN 'Deleted'
+           WHEN rc.deleted IS NOT NULL AND head.present IS NULL THEN 'Added'
+           WHEN rc.deleted IS NOT NULL OR rv.touched THEN 'Modified'
+           END AS right_change
+FROM touched AS t
+         LEFT JOIN LATERAL (SELECT TRUE AS present
+                            FROM components AS c
+                            WHERE c.id = t.id
+                              AND c.tenancy_workspace_pk = $1
+                              AND c.visibility_change_set_pk = ident_nil_v1()
+                              AND c.visibility_deleted_at IS NULL
+                            LIMIT 1) AS head ON TRUE
+         LEFT JOIN LATERAL (SELECT c.visibility_deleted_at IS NOT NULL AS deleted
+                            FROM components AS c
+                            WHERE c.id = t.id
+                              AND c.tenancy_workspace_pk = $1
+                              AND c.visibility_change_set_pk = $2
+                            LIMIT 1) AS lc ON TRUE
+         LEFT JOIN LATERAL (SELECT c.visibility_deleted_at IS NOT NULL AS deleted
+                            FROM components AS c
+                            WHERE c.id = t.id
+                              AND c.tenancy_workspace_pk = $1
+                              AND c.visibility_change_set_pk = $3
+                            LIMIT 1) AS rc ON TRUE
+         LEFT JOIN LATERAL (SELECT TRUE AS touched
+                            FROM attribute_values AS av
+                            WHERE av.attribute_context_component_id = t.id
+                              AND av.tenancy_workspace_pk = $1
+                              AND av.visibility_change_set_pk = $2
+                            LIMIT 1) AS lv ON TRUE
+         LEFT JOIN LATERAL (SELECT TRUE AS touched
+                            FROM attribute_values AS av
+                            WHERE av.attribute_context_component_id = t.id
+                              AND av.tenancy_workspace_pk = $1
+                              AND av.visibility_change_set_pk = $3
+                            LIMIT 1) AS rv ON TRUE
+ORDER BY component_id
@@ -0,0 +1,49 @@
+-- Edges written in either of two change sets, with how each change set changed them. Edges are
+-- never updated in place, so they can only be "Added" or "Deleted".
+WITH touched AS (SELECT DISTINCT e.id
+                 FROM edges AS e
+                 WHERE e.tenancy_workspace_pk = $1
+                   AND e.visibility_change_set_pk IN ($2, $3))
+SELECT t.id                                                        AS edge_id,
+       e.head_object_id                                            AS head_component_id,
+       e.head_socket_id                                            AS head_socket_id,
+       e.tail_object_id                                            AS tail_component_id,
+       e.tail_socket_id                                            AS tail_socket_id,
+       CASE
+           WHEN le.deleted THEN 'Deleted'
+           WHEN le.deleted IS NOT NULL AND head.present IS NULL THEN 'Added'
+           WHEN le.deleted IS NOT NULL THEN 'Modified'
+           END                                                     AS left_change,
+       CASE
+           WHEN re.deleted THEN 'Deleted'
+           WHEN re.deleted IS NOT NULL AND head.present IS NULL THEN 'Added'
+           WHEN re.deleted IS NOT NULL THEN 'Modified'
+           END                                                     AS right_change
+FROM touched AS t
+         INNER JOIN LATERAL (SELECT edges.*
+                             FROM edges
+                             WHERE edges.id = t.id
+                               AND edges.tenancy_workspace_pk = $1
+                               AND edges.visibility_change_set_pk IN ($2, $3, ident_nil_v1())
+                             LIMIT 1) AS e ON TRUE
+         LEFT JOIN LATERAL (SELECT TRUE AS present
+                            FROM edges
+                            WHERE edges.id = t.id
+                              AND edges.tenancy_workspace_pk = $1
+                              AND edges.visibility_change_set_pk = ident_nil_v1()
+                              AND edges.visibility_deleted_at IS NULL
+                            LIMIT 1) AS head ON TRUE
+         LEFT JOIN LATERAL (SELECT edges.visibility_deleted_at IS NOT NULL AS deleted
+                            FROM edges
+                            WHERE edges.id = t.id
+                              AND edges.tenancy_workspace_pk = $1
+                              AND edges.visibility_change_set_pk = $2
+                            LIMIT 1) AS le ON TRUE
+         LEFT JOIN LATERAL (SELECT edges.visibility_deleted_at IS NOT NULL AS deleted
+                            FROM edges
+                            WHERE edges.id = t.id
+                              AND edges.tenancy_workspace_pk = $1
+                              AND edges.visibility_change_set_pk = $3
+                            LIMIT 1) AS re ON TRUE
+WHERE le.deleted IS DISTINCT FROM re.deleted
+ORDER BY edge_id
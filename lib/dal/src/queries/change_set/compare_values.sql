@@ -0,0 +1,63 @@
+-- Values of component props written in either of two change sets, as seen from each of them
+-- (falling back to head), where the two change sets disagree.
+WITH touched AS (SELECT DISTINCT av.id
+                 FROM attribute_values AS av
+                 WHERE av.tenancy_workspace_pk = $1
+                   AND av.visibility_change_set_pk IN ($2, $3)
+                   AND av.attribute_context_component_id != ident_nil_v1()
+                   AND av.attribute_context_prop_id != ident_nil_v1())
+SELECT t.id                                                      AS attribute_value_id,
+       COALESCE(l.component_id, r.component_id)                  AS component_id,
+       COALESCE(l.prop_id, r.prop_id)                            AS prop_id,
+       '/' || replace(p.path, E'\x0B', '/')                      AS prop_path,
+       CASE WHEN l.deleted THEN NULL ELSE l_fbrv.value END       AS left_value,
+       CASE WHEN r.deleted THEN NULL ELSE r_fbrv.value END       AS right_value,
+       COALESCE(l.visibility_change_set_pk = $2, FALSE)          AS left_changed,
+       COALESCE(r.visibility_change_set_pk = $3, FALSE)          AS right_changed
+FROM touched AS t
+         LEFT JOIN LATERAL (SELECT av.attribute_context_component_id AS component_id,
+                                   av.attribute_context_prop_id      AS prop_id,
+                                   av.func_binding_return_value_id,
+                                   av.visibility_change_set_pk,
+                                   av.visibility_deleted_at IS NOT NULL AS deleted
+                            FROM attribute_values AS av
+                            WHERE av.id = t.id
+                              AND av.tenancy_workspace_pk = $1
+                              AND av.visibility_change_set_pk IN ($2, ident_nil_v1())
+                            ORDER BY av.visibility_change_set_pk = $2 DESC
+                            LIMIT 1) AS l ON TRUE
+         LEFT JOIN LATERAL (SELECT av.attribute_context_component_id AS component_id,
+                                   av.attribute_context_prop_id      AS prop_id,
+                                   av.func_binding_return_value_id,
+                                   av.visibility_change_set_pk,
+                                   av.visibility_deleted_at IS NOT NULL AS deleted
+                            FROM attribute_values AS av
+                            WHERE av.id = t.id
+                              AND av.tenancy_workspace_pk = $1
+                              AND av.visibility_change_set_pk IN ($3, ident_nil_v1())
+                            ORDER BY av.visibility_change_set_pk = $3 DESC
+                            LIMIT 1) AS r ON TRUE
+         LEFT JOIN LATERAL (SELECT fbrv.value
+                            FROM func_binding_return_values AS fbrv
+                            WHERE fbrv.id = l.func_binding_return_value_id
+                              AND fbrv.tenancy_workspace_pk = $1
+                              AND fbrv.visibility_change_set_pk IN ($2, ident_nil_v1())
+                            ORDER BY fbrv.visibility_change_set_pk = $2 DESC
+                            LIMIT 1) AS l_fbrv ON TRUE
+         LEFT JOIN LATERAL (SELECT fbrv.value
+                            FROM func_binding_return_values AS fbrv
+                            WHERE fbrv.id = r.func_binding_return_value_id
+                              AND fbrv.tenancy_workspace_pk = $1
+                              AND fbrv.visibility_change_set_pk IN ($3, ident_nil_v1())
+                            ORDER BY fbrv.visibility_change_set_pk = $3 DESC
+                            LIMIT 1) AS r_fbrv ON TRUE
+         LEFT JOIN LATERAL (SELECT props.path
+                            FROM props
+                            WHERE props.id = COALESCE(l.prop_id, r.prop_id)
+                              AND props.tenancy_workspace_pk = $1
+                              AND props.visibility_change_set_pk IN ($2, $3, ident_nil_v1())
+                            ORDER BY props.visibility_change_set_pk = ident_nil_v1()
+                            LIMIT 1) AS p ON TRUE
+WHERE (CASE WHEN l.deleted THEN NULL ELSE l_fbrv.value END)
+          IS DISTINCT FROM (CASE WHEN r.deleted THEN NULL ELSE r_fbrv.value END)
+ORDER BY component_id, prop_path
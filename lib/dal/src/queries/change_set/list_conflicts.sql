@@ -0,0 +1,48 @@
+-- Values set in the change set whose head counterpart was changed, to something else, after the
+-- change set started diverging from head.
+SELECT cs.attribute_context_component_id     AS component_id,
+       cs.id                                 AS attribute_value_id,
+       cs.attribute_context_prop_id          AS prop_id,
+       '/' || replace(p.path, E'\x0B', '/')  AS prop_path,
+       head_fbrv.value                       AS head_value,
+       cs_fbrv.value                         AS change_set_value,
+       head.updated_at                       AS head_updated_at
+FROM attribute_values AS cs
+         INNER JOIN attribute_values AS head
+                    ON head.id = cs.id
+                        AND head.tenancy_workspace_pk = cs.tenancy_workspace_pk
+                        AND head.visibility_change_set_pk = ident_nil_v1()
+                        AND head.visibility_deleted_at IS NULL
+         INNER JOIN LATERAL (SELECT fbrv.value
+                             FROM func_binding_return_values AS fbrv
+                             WHERE fbrv.id = cs.func_binding_return_value_id
+                               AND fbrv.tenancy_workspace_pk = cs.tenancy_workspace_pk
+                               AND fbrv.visibility_change_set_pk IN (cs.visibility_change_set_pk, ident_nil_v1())
+                             ORDER BY fbrv.visibility_change_set_pk DESC
+                             LIMIT 1) AS cs_fbrv ON TRUE
+         INNER JOIN LATERAL (SELECT fbrv.value
+                             FROM func_binding_return_values AS fbrv
+                             WHERE fbrv.id = head.func_binding_return_value_id
+                               AND fbrv.tenancy_workspace_pk = head.tenancy_workspace_pk
+                               AND fbrv.visibility_change_set_pk = ident_nil_v1()
+                             LIMIT 1) AS head_fbrv ON TRUE
+         INNER JOIN LATERAL (SELECT props.path
+                             FROM props
+                             WHERE props.id = cs.attribute_context_prop_id
+                               AND props.tenancy_workspace_pk = cs.tenancy_workspace_pk
+                               AND props.visibility_change_set_pk IN (cs.visibility_change_set_pk, ident_nil_v1())
+                             ORDER BY props.visibility_change_set_pk DESC
+                             LIMIT 1) AS p ON TRUE
+WHERE cs.tenancy_workspace_pk = $1
+  AND cs.visibility_change_set_pk = $2
+  AND cs.visibility_deleted_at IS NULL
+  AND cs.attribute_context_component_id != ident_nil_v1()
+  AND cs.attribute_context_prop_id != ident_nil_v1()
+  AND head.updated_at > cs.created_at
+  AND head_fbrv.value IS DISTINCT FROM cs_fbrv.value
+  AND EXISTS (SELECT 1
+              FROM attribute_value_audits AS a
+              WHERE a.tenancy_workspace_pk = cs.tenancy_workspace_pk
+                AND a.visibility_change_set_pk = cs.visibility_change_set_pk
+                AND a.attribute_value_id = cs.id)
+ORDER BY component_id, prop_path
@@ -0,0 +1,4 @@
+SELECT row_to_json(c.*) AS object
+FROM components_v1($1, $2) AS c
+WHERE c.archived
+ORDER BY c.id;
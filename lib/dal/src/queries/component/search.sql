@@ -0,0 +1,26 @@
+SELECT c.id                                  AS component_id,
+       COALESCE(sq.component_name, '')       AS component_name,
+       schemas.name                          AS schema_name,
+       sq.total                              AS total,
+       sq.warned                             AS warned,
+       sq.succeeded                          AS succeeded,
+       sq.failed                             AS failed
+FROM components_v1($1, $2) AS c
+         INNER JOIN component_belongs_to_schema_v1($1, $2) AS cbts
+                    ON cbts.object_id = c.id
+         INNER JOIN schemas_v1($1, $2) AS schemas
+                    ON schemas.id = cbts.belongs_to_id
+         LEFT JOIN LATERAL (SELECT summary_qualifications.*
+                            FROM summary_qualifications
+                            WHERE summary_qualifications.component_id = c.id
+                              AND in_tenancy_v1($1, summary_qualifications.tenancy_workspace_pk)
+                              AND is_visible_v1($2,
+                                                summary_qualifications.visibility_change_set_pk,
+                                                summary_qualifications.visibility_deleted_at)
+                            ORDER BY summary_qualifications.visibility_change_set_pk DESC,
+                                     summary_qualifications.visibility_deleted_at DESC NULLS FIRST
+                            LIMIT 1) AS sq ON TRUE
+WHERE NOT c.archived
+  AND ($3::text IS NULL OR schemas.name = $3)
+  AND ($4::text IS NULL OR sq.component_name ILIKE '%' || $4 || '%')
+ORDER BY component_name, c.id
@@ -0,0 +1,6 @@
+SELECT DISTINCT ON (confirmation_prototypes.name) row_to_json(confirmation_prototypes.*) AS object
+FROM confirmation_prototypes_v1($1, $2) AS confirmation_prototypes
+WHERE (confirmation_prototypes.component_id = $3 OR confirmation_prototypes.component_id = ident_nil_v1())
+      AND confirmation_prototypes.schema_variant_id = $4
+ORDER BY confirmation_prototypes.name,
+         confirmation_prototypes.component_id DESC;
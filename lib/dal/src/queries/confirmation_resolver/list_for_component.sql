@@ -0,0 +1,4 @@
+SELECT row_to_json(confirmation_resolvers.*) AS object
+FROM confirmation_resolvers_v1($1, $2) AS confirmation_resolvers
+WHERE confirmation_resolvers.component_id = $3
+ORDER BY confirmation_resolvers.created_at;
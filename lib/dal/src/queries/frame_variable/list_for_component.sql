@@ -0,0 +1,4 @@
+SELECT row_to_json(frame_variables.*) AS object
+FROM frame_variables_v1($1, $2) AS frame_variables
+WHERE frame_variables.component_id = $3
+ORDER BY frame_variables.name;
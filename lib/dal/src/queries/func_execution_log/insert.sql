@@ -0,0 +1,5 @@
+INSERT INTO func_execution_logs AS l (tenancy_workspace_pk, visibility_change_set_pk, func_execution_pk, func_id,
+                                      func_binding_id, component_id, attribute_value_id, backend_kind, success,
+                                      failure, stdout, stderr, started_at, finished_at, duration_ms)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+RETURNING row_to_json(l.*) AS object
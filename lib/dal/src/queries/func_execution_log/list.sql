@@ -0,0 +1,8 @@
+SELECT row_to_json(l.*) AS object
+FROM func_execution_logs AS l
+WHERE l.tenancy_workspace_pk = $1
+  AND l.visibility_change_set_pk IN ($2, ident_nil_v1())
+  AND ($3::ident IS NULL OR l.component_id = $3)
+  AND ($4::ident IS NULL OR l.func_id = $4)
+ORDER BY l.created_at DESC
+LIMIT $5
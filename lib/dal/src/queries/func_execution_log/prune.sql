@@ -0,0 +1,14 @@
+DELETE
+FROM func_execution_logs AS l
+WHERE l.tenancy_workspace_pk = $1
+  AND l.visibility_change_set_pk = $2
+  AND l.func_id = $3
+  AND l.component_id = $4
+  AND l.pk NOT IN (SELECT kept.pk
+                   FROM func_execution_logs AS kept
+                   WHERE kept.tenancy_workspace_pk = $1
+                     AND kept.visibility_change_set_pk = $2
+                     AND kept.func_id = $3
+                     AND kept.component_id = $4
+                   ORDER BY kept.created_at DESC
+                   LIMIT $5)
@@ -0,0 +1,13 @@
+-- An event is about an entity when one of the top level values of its data is the entity's id
+-- (or pk). Events are listed newest first, starting after the $7 cursor.
+SELECT row_to_json(he.*) AS object
+FROM history_events AS he
+WHERE in_tenancy_v1($1, he.tenancy_workspace_pk)
+  AND jsonb_path_exists(he.data, '$.* ? (@ == $entity_id)', jsonb_build_object('entity_id', $2::text))
+  AND ($3::jsonb IS NULL OR he.actor = $3)
+  AND ($4::timestamptz IS NULL OR he.created_at >= $4)
+  AND ($5::timestamptz IS NULL OR he.created_at < $5)
+  AND ($6::text IS NULL OR he.label LIKE $6 || '%')
+  AND ($7::ident IS NULL OR he.pk < $7)
+ORDER BY he.pk DESC
+LIMIT $8;
@@ -0,0 +1,5 @@
+SELECT row_to_json(s.*) AS object
+FROM mutation_scripts AS s
+WHERE s.workspace_pk = $1
+  AND s.name = $2
+  AND s.version = $3
@@ -0,0 +1,4 @@
+SELECT DISTINCT ON (s.name) row_to_json(s.*) AS object
+FROM mutation_scripts AS s
+WHERE s.workspace_pk = $1
+ORDER BY s.name, s.version DESC
@@ -0,0 +1,8 @@
+INSERT INTO mutation_scripts AS s (workspace_pk, name, version, description, parameters, operations)
+VALUES ($1, $2,
+        (SELECT COALESCE(MAX(version), 0) + 1
+         FROM mutation_scripts
+         WHERE workspace_pk = $1
+           AND name = $2),
+        $3, $4, $5)
+RETURNING row_to_json(s.*) AS object
@@ -0,0 +1,4 @@
+SELECT row_to_json(p.*) AS object
+FROM prop_permissions AS p
+WHERE p.workspace_pk = $1
+ORDER BY p.path ASC
@@ -0,0 +1,4 @@
+DELETE
+FROM prop_permissions
+WHERE workspace_pk = $1
+  AND path = $2
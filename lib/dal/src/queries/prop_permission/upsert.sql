@@ -0,0 +1,5 @@
+INSERT INTO prop_permissions AS p (workspace_pk, path, allowed_roles)
+VALUES ($1, $2, $3)
+ON CONFLICT (workspace_pk, path) DO UPDATE SET allowed_roles = EXCLUDED.allowed_roles,
+                                               updated_at    = CLOCK_TIMESTAMP()
+RETURNING row_to_json(p.*) AS object
@@ -0,0 +1,10 @@
+SELECT DISTINCT ap.attribute_context_prop_id AS prop_id
+FROM attribute_prototypes_v1($1, $2) AS ap
+         INNER JOIN attribute_prototype_arguments_v1($1, $2) AS apa
+                    ON apa.attribute_prototype_id = ap.id
+         INNER JOIN socket_belongs_to_internal_provider_v1($1, $2) AS sbtip
+                    ON sbtip.belongs_to_id = apa.internal_provider_id
+         INNER JOIN socket_many_to_many_schema_variants_v1($1, $2) AS socket_to_schema_variant
+                    ON socket_to_schema_variant.left_object_id = sbtip.object_id
+WHERE socket_to_schema_variant.right_object_id = $3
+  AND ap.attribute_context_component_id = ident_nil_v1()
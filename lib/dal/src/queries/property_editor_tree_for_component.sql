@@ -0,0 +1 @@
+SELECT property_editor_tree_for_component_v1($1, $2, $3) AS object
@@ -0,0 +1,5 @@
+INSERT INTO provider_value_histories AS h (tenancy_workspace_pk, visibility_change_set_pk, attribute_value_id,
+                                           component_id, internal_provider_id, external_provider_id,
+                                           func_binding_return_value_id, value, job_id)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+RETURNING row_to_json(h.*) AS object
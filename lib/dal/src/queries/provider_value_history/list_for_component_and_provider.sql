@@ -0,0 +1,8 @@
+SELECT row_to_json(h.*) AS object
+FROM provider_value_histories AS h
+WHERE h.tenancy_workspace_pk = $1
+  AND h.visibility_change_set_pk IN ($2, ident_nil_v1())
+  AND h.component_id = $3
+  AND h.internal_provider_id = $4
+  AND h.external_provider_id = $5
+ORDER BY h.created_at DESC
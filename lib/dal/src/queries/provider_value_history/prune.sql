@@ -0,0 +1,12 @@
+DELETE
+FROM provider_value_histories AS h
+WHERE h.tenancy_workspace_pk = $1
+  AND h.visibility_change_set_pk = $2
+  AND h.attribute_value_id = $3
+  AND h.pk NOT IN (SELECT kept.pk
+                   FROM provider_value_histories AS kept
+                   WHERE kept.tenancy_workspace_pk = $1
+                     AND kept.visibility_change_set_pk = $2
+                     AND kept.attribute_value_id = $3
+                   ORDER BY kept.created_at DESC
+                   LIMIT $4)
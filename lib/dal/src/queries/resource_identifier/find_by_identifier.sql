@@ -0,0 +1,3 @@
+SELECT row_to_json(resource_identifiers.*) AS object
+FROM resource_identifiers_v1($1, $2) AS resource_identifiers
+WHERE resource_identifiers.identifier = $3;
@@ -0,0 +1,10 @@
+SELECT DISTINCT av.attribute_context_component_id AS component_id
+FROM attribute_values_v1($1, $2) av
+         JOIN props_v1($1, $2) secret_prop
+              ON secret_prop.id = av.attribute_context_prop_id
+                  AND secret_prop.path LIKE 'rootsecrets%'
+         JOIN func_binding_return_values_v1($1, $2) fbrv
+              ON av.func_binding_return_value_id = fbrv.id AND fbrv.value IS NOT NULL
+         JOIN components_v1($1, $2) c ON c.id = av.attribute_context_component_id
+WHERE (fbrv.value #>> '{}')::ident = $3
+ORDER BY component_id;
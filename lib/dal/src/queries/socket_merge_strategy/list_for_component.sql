@@ -0,0 +1,4 @@
+SELECT row_to_json(socket_merge_strategies.*) AS object
+FROM socket_merge_strategies_v1($1, $2) AS socket_merge_strategies
+WHERE socket_merge_strategies.component_id = $3
+ORDER BY socket_merge_strategies.socket_id;
@@ -0,0 +1,3 @@
+INSERT INTO webhooks AS w (workspace_pk, url, events, enabled)
+VALUES ($1, $2, $3, $4)
+RETURNING row_to_json(w.*) AS object
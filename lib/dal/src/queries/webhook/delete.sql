@@ -0,0 +1,5 @@
+DELETE
+FROM webhooks AS w
+WHERE w.workspace_pk = $1
+  AND w.pk = $2
+RETURNING row_to_json(w.*) AS object
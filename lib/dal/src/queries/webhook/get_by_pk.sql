@@ -0,0 +1,4 @@
+SELECT row_to_json(w.*) AS object
+FROM webhooks AS w
+WHERE w.workspace_pk = $1
+  AND w.pk = $2
@@ -0,0 +1,8 @@
+UPDATE webhooks AS w
+SET url        = $3,
+    events     = $4,
+    enabled    = $5,
+    updated_at = CLOCK_TIMESTAMP()
+WHERE w.workspace_pk = $1
+  AND w.pk = $2
+RETURNING row_to_json(w.*) AS object
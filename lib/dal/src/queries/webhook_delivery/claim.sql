@@ -0,0 +1,7 @@
+UPDATE webhook_deliveries AS d
+SET next_attempt_at = $3,
+    updated_at      = CLOCK_TIMESTAMP()
+WHERE d.pk = $1
+  AND d.status = 'pending'
+  AND d.next_attempt_at <= $2
+RETURNING row_to_json(d.*) AS object
@@ -0,0 +1,7 @@
+INSERT INTO webhook_deliveries AS d (webhook_pk, workspace_pk, event, payload)
+SELECT w.pk, w.workspace_pk, $2, $3
+FROM webhooks AS w
+WHERE w.workspace_pk = $1
+  AND w.enabled
+  AND $2 = ANY (w.events)
+RETURNING row_to_json(d.*) AS object
@@ -0,0 +1,8 @@
+SELECT row_to_json(d.*) AS object
+FROM webhook_deliveries AS d
+         INNER JOIN webhooks AS w ON w.pk = d.webhook_pk
+    AND w.enabled
+WHERE d.status = 'pending'
+  AND d.next_attempt_at <= $1
+ORDER BY d.next_attempt_at, d.pk
+LIMIT $2
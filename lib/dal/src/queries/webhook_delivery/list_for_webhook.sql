@@ -0,0 +1,6 @@
+SELECT row_to_json(d.*) AS object
+FROM webhook_deliveries AS d
+WHERE d.workspace_pk = $1
+  AND d.webhook_pk = $2
+ORDER BY d.created_at DESC, d.pk DESC
+LIMIT $3
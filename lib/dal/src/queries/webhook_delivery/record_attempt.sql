@@ -0,0 +1,11 @@
+UPDATE webhook_deliveries AS d
+SET status               = $2,
+    attempts             = d.attempts + 1,
+    next_attempt_at      = $3,
+    last_attempt_at      = $4,
+    last_response_status = $5,
+    last_error           = $6,
+    delivered_at         = $7,
+    updated_at           = CLOCK_TIMESTAMP()
+WHERE d.pk = $1
+RETURNING row_to_json(d.*) AS object
@@ -0,0 +1,3 @@
+SELECT row_to_json(c.*) AS object
+FROM workspace_digest_configs AS c
+WHERE c.workspace_pk = $1
@@ -0,0 +1,9 @@
+SELECT change_sets.pk         AS pk,
+       change_sets.name       AS name,
+       change_sets.updated_at AS applied_at
+FROM change_sets
+WHERE change_sets.tenancy_workspace_pk = $1
+  AND change_sets.status = 'Applied'
+  AND change_sets.updated_at >= $2
+  AND change_sets.updated_at < $3
+ORDER BY change_sets.updated_at, change_sets.pk
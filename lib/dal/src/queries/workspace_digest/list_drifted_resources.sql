@@ -0,0 +1,10 @@
+SELECT (history_events.data ->> 'componentId')::ident AS component_id,
+       history_events.data ->> 'componentName'        AS component_name,
+       MAX(history_events.created_at)                 AS drifted_at
+FROM history_events
+WHERE history_events.tenancy_workspace_pk = $1
+  AND history_events.label = 'component.resource.drifted'
+  AND history_events.created_at >= $2
+  AND history_events.created_at < $3
+GROUP BY component_id, component_name
+ORDER BY drifted_at, component_id
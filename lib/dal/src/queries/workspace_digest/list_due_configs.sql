@@ -0,0 +1,9 @@
+SELECT row_to_json(c.*) AS object
+FROM workspace_digest_configs AS c
+         INNER JOIN workspaces ON workspaces.pk = c.workspace_pk
+    AND workspaces.visibility_deleted_at IS NULL
+WHERE c.enabled
+  AND c.webhook_url IS NOT NULL
+  AND (c.last_sent_at IS NULL
+    OR c.last_sent_at + make_interval(hours => c.window_hours) <= $1)
+ORDER BY c.workspace_pk
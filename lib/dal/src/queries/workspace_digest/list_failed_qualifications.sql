@@ -0,0 +1,11 @@
+SELECT DISTINCT ON (summary_qualifications.component_id) summary_qualifications.component_id   AS component_id,
+                                                         summary_qualifications.component_name AS component_name,
+                                                         summary_qualifications.failed         AS failed
+FROM summary_qualifications
+WHERE summary_qualifications.tenancy_workspace_pk = $1
+  AND summary_qualifications.visibility_change_set_pk = ident_nil_v1()
+  AND summary_qualifications.visibility_deleted_at IS NULL
+  AND summary_qualifications.failed > 0
+  AND summary_qualifications.updated_at >= $2
+  AND summary_qualifications.updated_at < $3
+ORDER BY summary_qualifications.component_id
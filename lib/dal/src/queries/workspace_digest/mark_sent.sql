@@ -0,0 +1,5 @@
+UPDATE workspace_digest_configs
+SET last_sent_at = $2,
+    updated_at   = CLOCK_TIMESTAMP()
+WHERE workspace_pk = $1
+RETURNING updated_at
@@ -0,0 +1,7 @@
+INSERT INTO workspace_digest_configs AS c (workspace_pk, enabled, window_hours, webhook_url)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT (workspace_pk) DO UPDATE SET enabled      = EXCLUDED.enabled,
+                                         window_hours = EXCLUDED.window_hours,
+                                         webhook_url  = EXCLUDED.webhook_url,
+                                         updated_at   = CLOCK_TIMESTAMP()
+RETURNING row_to_json(c.*) AS object
@@ -0,0 +1,6 @@
+SELECT row_to_json(r.*) AS object
+FROM (SELECT w.pk                                                   AS workspace_pk,
+             COALESCE(p.func_binding_return_value_history_hours, $2) AS func_binding_return_value_history_hours
+      FROM workspaces AS w
+               LEFT JOIN workspace_retention_policies AS p ON p.workspace_pk = w.pk
+      WHERE w.pk = $1) AS r
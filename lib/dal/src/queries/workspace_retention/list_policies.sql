@@ -0,0 +1,5 @@
+SELECT row_to_json(r.*) AS object
+FROM (SELECT w.pk                                                   AS workspace_pk,
+             COALESCE(p.func_binding_return_value_history_hours, $1) AS func_binding_return_value_history_hours
+      FROM workspaces AS w
+               LEFT JOIN workspace_retention_policies AS p ON p.workspace_pk = w.pk) AS r
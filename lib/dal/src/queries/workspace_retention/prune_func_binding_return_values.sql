@@ -0,0 +1,20 @@
+-- A return value is superseded once no attribute value (in any change set, deleted or not),
+-- validation resolver or attribute value audit refers to it anymore.
+WITH superseded AS (SELECT fbrv.pk
+                    FROM func_binding_return_values AS fbrv
+                    WHERE fbrv.tenancy_workspace_pk = $1
+                      AND fbrv.created_at < $2
+                      AND NOT EXISTS (SELECT 1
+                                      FROM attribute_values AS av
+                                      WHERE av.func_binding_return_value_id = fbrv.id)
+                      AND NOT EXISTS (SELECT 1
+                                      FROM validation_resolvers AS vr
+                                      WHERE vr.attribute_value_func_binding_return_value_id = fbrv.id)
+                      AND NOT EXISTS (SELECT 1
+                                      FROM attribute_value_audits AS ava
+                                      WHERE ava.func_binding_return_value_id = fbrv.id
+                                         OR ava.previous_func_binding_return_value_id = fbrv.id)
+                    LIMIT $3)
+DELETE
+FROM func_binding_return_values
+WHERE pk IN (SELECT pk FROM superseded)
@@ -0,0 +1,7 @@
+INSERT INTO workspace_retention_policies AS p (workspace_pk, func_binding_return_value_history_hours)
+VALUES ($1, $2)
+ON CONFLICT (workspace_pk) DO UPDATE SET func_binding_return_value_history_hours = EXCLUDED.func_binding_return_value_history_hours,
+                                         updated_at                              = CLOCK_TIMESTAMP()
+RETURNING json_build_object('workspace_pk', p.workspace_pk,
+                            'func_binding_return_value_history_hours',
+                            p.func_binding_return_value_history_hours) AS object
@@ -0,0 +1,6 @@
+SELECT l.cursor, l.event
+FROM ws_event_log AS l
+WHERE l.workspace_pk = $1
+  AND l.cursor > $2
+ORDER BY l.cursor
+LIMIT $3
@@ -0,0 +1,3 @@
+SELECT MIN(l.cursor) AS cursor
+FROM ws_event_log AS l
+WHERE l.workspace_pk = $1
@@ -0,0 +1,3 @@
+DELETE
+FROM ws_event_log
+WHERE created_at < $1
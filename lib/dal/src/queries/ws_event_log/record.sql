@@ -0,0 +1,11 @@
+WITH next AS (
+    INSERT INTO ws_event_cursors (workspace_pk, last_cursor)
+        VALUES ($1, 1)
+        ON CONFLICT (workspace_pk)
+            DO UPDATE SET last_cursor = ws_event_cursors.last_cursor + 1
+        RETURNING last_cursor)
+INSERT
+INTO ws_event_log (workspace_pk, cursor, change_set_pk, kind, event)
+SELECT $1, next.last_cursor, $2, $3, $4
+FROM next
+RETURNING cursor
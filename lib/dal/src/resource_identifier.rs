@@ -0,0 +1,184 @@
+//! This module contains [`ResourceIdentifier`], the registry of the canonical cloud identifiers
+//! (e.g. "sg-0123" or "vpc-4567") of the resources modeled by [`Components`](Component).
+//!
+//! An identifier is read from the resource payload of a [`Component`] whenever its resource is
+//! set (by a refresh, an action or an import), using the
+//! [`resource identifier path`](crate::Schema::resource_identifier_path) of its [`Schema`].
+//! Identifiers are unique per workspace, so that the same underlying resource is not modeled by
+//! two [`Components`](Component).
+
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, Component, ComponentId,
+    DalContext, HistoryEventError, Schema, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, Visibility,
+};
+
+const FIND_BY_IDENTIFIER: &str = include_str!("queries/resource_identifier/find_by_identifier.sql");
+const LIST_FOR_COMPONENT: &str = include_str!("queries/resource_identifier/list_for_component.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ResourceIdentifierError {
+    #[error("resource {0} is already modeled by component {1}")]
+    AlreadyRegistered(String, ComponentId),
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ResourceIdentifierResult<T> = Result<T, ResourceIdentifierError>;
+
+pk!(ResourceIdentifierPk);
+pk!(ResourceIdentifierId);
+
+/// The canonical identifier of the resource modeled by a [`Component`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResourceIdentifier {
+    pk: ResourceIdentifierPk,
+    id: ResourceIdentifierId,
+    component_id: ComponentId,
+    identifier: String,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ResourceIdentifier,
+    pk: ResourceIdentifierPk,
+    id: ResourceIdentifierId,
+    table_name: "resource_identifiers",
+    history_event_label_base: "resource_identifier",
+    history_event_message_name: "Resource Identifier"
+}
+
+impl ResourceIdentifier {
+    /// Read the identifier out of a resource payload, following the
+    /// [`resource identifier path`](Schema::resource_identifier_path) of the [`Schema`].
+    pub fn identifier_from_payload(
+        schema: &Schema,
+        payload: Option<&serde_json::Value>,
+    ) -> Option<String> {
+        let value = payload?.pointer(schema.resource_identifier_path()?)?;
+        match value {
+            serde_json::Value::String(identifier) if !identifier.is_empty() => {
+                Some(identifier.clone())
+            }
+            serde_json::Value::Number(identifier) => Some(identifier.to_string()),
+            _ => None,
+        }
+    }
+
+    pub async fn find_by_identifier(
+        ctx: &DalContext,
+        identifier: &str,
+    ) -> ResourceIdentifierResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                FIND_BY_IDENTIFIER,
+                &[ctx.tenancy(), ctx.visibility(), &identifier],
+            )
+            .await?;
+        Ok(standard_model::object_option_from_row_option(row)?)
+    }
+
+    pub async fn list_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ResourceIdentifierResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_COMPONENT,
+                &[ctx.tenancy(), ctx.visibility(), &component_id],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Find the [`Component`], other than `component_id`, already modeling the resource with
+    /// the given identifier. Registrations of [`Components`](Component) that no longer exist are
+    /// ignored.
+    pub async fn find_conflict(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        identifier: &str,
+    ) -> ResourceIdentifierResult<Option<ComponentId>> {
+        let registered = match Self::find_by_identifier(ctx, identifier).await? {
+            Some(registered) if registered.component_id != component_id => registered,
+            _ => return Ok(None),
+        };
+        let exists = Component::get_by_id(ctx, &registered.component_id)
+            .await?
+            .is_some();
+        Ok(exists.then_some(registered.component_id))
+    }
+
+    /// Record the identifier of the resource modeled by a [`Component`], replacing any identifier
+    /// previously recorded for it.
+    #[instrument(skip(ctx))]
+    pub async fn register(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        identifier: &str,
+    ) -> ResourceIdentifierResult<Self> {
+        if let Some(existing_component_id) =
+            Self::find_conflict(ctx, component_id, identifier).await?
+        {
+            return Err(ResourceIdentifierError::AlreadyRegistered(
+                identifier.to_owned(),
+                existing_component_id,
+            ));
+        }
+
+        for mut previous in Self::list_for_component(ctx, component_id).await? {
+            if previous.identifier != identifier {
+                previous.delete_by_id(ctx).await?;
+            }
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM resource_identifier_upsert_v1($1, $2, $3, $4)",
+                &[ctx.tenancy(), ctx.visibility(), &component_id, &identifier],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    /// Forget the identifiers recorded for a [`Component`], e.g. once its resource is gone.
+    pub async fn unregister_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ResourceIdentifierResult<()> {
+        for mut registered in Self::list_for_component(ctx, component_id).await? {
+            registered.delete_by_id(ctx).await?;
+        }
+        Ok(())
+    }
+
+    standard_model_accessor!(component_id, Pk(ComponentId), ResourceIdentifierResult);
+    standard_model_accessor!(identifier, String, ResourceIdentifierResult);
+}
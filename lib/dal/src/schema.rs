@@ -102,6 +102,13 @@ pub struct Schema {
     ui_hidden: bool,
     default_schema_variant_id: Option<SchemaVariantId>,
     component_kind: ComponentKind,
+    /// How often, in seconds, the [`ResourceScheduler`](crate::tasks::ResourceScheduler)
+    /// refreshes the resources of the [`Components`](crate::Component) of [`self`](Self).
+    refresh_interval_seconds: Option<i64>,
+    /// A JSON pointer to the canonical cloud identifier in the resource payload of the
+    /// [`Components`](crate::Component) of [`self`](Self), recorded as a
+    /// [`ResourceIdentifier`](crate::ResourceIdentifier).
+    resource_identifier_path: Option<String>,
 }
 
 impl_standard_model! {
@@ -148,6 +155,8 @@ impl Schema {
         Option<Pk(SchemaVariantId)>,
         SchemaResult
     );
+    standard_model_accessor!(refresh_interval_seconds, OptionBigInt<i64>, SchemaResult);
+    standard_model_accessor!(resource_identifier_path, Option<String>, SchemaResult);
 
     standard_model_has_many!(
         lookup_fn: ui_menus,
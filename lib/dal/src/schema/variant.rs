@@ -41,6 +41,7 @@ use crate::{
 use self::leaves::{LeafInput, LeafInputLocation, LeafKind};
 
 pub mod definition;
+pub mod duplicate;
 pub mod leaves;
 pub mod root_prop;
 
@@ -110,6 +111,8 @@ pub enum SchemaVariantError {
     LeafFunctionMismatch(FuncBackendResponseType, LeafKind),
     #[error("leaf function ({0}) must be JsAttribute")]
     LeafFunctionMustBeJsAttribute(FuncId),
+    #[error("no {1:?} leaf populated by func {0}")]
+    LeafNotFound(FuncId, LeafKind),
     #[error("link not found in doc links map for doc link ref: {0}")]
     LinkNotFoundForDocLinkRef(String),
     #[error("must provide children for object with name: ({0})")]
@@ -190,6 +193,9 @@ pub struct SchemaVariant {
     // NOTE(nick): we may want to replace this with a better solution. We use this to ensure
     // components are not created unless the variant has been finalized at least once.
     finalized_once: bool,
+    /// When set, writes to prop paths that are not defined on [`self`](Self) are rejected instead
+    /// of being ignored.
+    strict: bool,
 }
 
 impl_standard_model! {
@@ -296,6 +302,7 @@ impl SchemaVariant {
 
         Self::create_default_prototypes_and_values(ctx, self.id).await?;
         Self::create_implicit_internal_providers(ctx, self.id).await?;
+        Self::set_prop_default_values(ctx, self.id).await?;
         if !self.finalized_once() {
             self.set_finalized_once(ctx, true).await?;
         }
@@ -427,6 +434,21 @@ impl SchemaVariant {
         Ok(())
     }
 
+    /// Sets the default values recorded on [`Props`](crate::Prop) of the [`SchemaVariant`] with
+    /// [`Prop::set_default_value_on_finalize`](crate::Prop::set_default_value_on_finalize).
+    async fn set_prop_default_values(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> SchemaVariantResult<()> {
+        for prop in Self::all_props(ctx, schema_variant_id).await? {
+            if let Some(default_value) = prop.default_value() {
+                prop.set_default_value(ctx, default_value).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     standard_model_accessor!(default_color, Option<String>, SchemaVariantResult);
     standard_model_accessor!(pkg_created_at, Option<DateTimeUtc>, SchemaVariantResult);
     standard_model_accessor!(ui_hidden, bool, SchemaVariantResult);
@@ -434,6 +456,7 @@ impl SchemaVariant {
     standard_model_accessor!(root_prop_id, Option<Pk(PropId)>, SchemaVariantResult);
     standard_model_accessor!(link, Option<String>, SchemaVariantResult);
     standard_model_accessor!(finalized_once, bool, SchemaVariantResult);
+    standard_model_accessor!(strict, bool, SchemaVariantResult);
     standard_model_accessor!(
         schema_variant_definition_id,
         Option<Pk(SchemaVariantDefinitionId)>,
@@ -0,0 +1,244 @@
+//! This module contains the ability to duplicate a [`SchemaVariant`] into a new, editable
+//! [`SchemaVariant`] for the same [`Schema`](crate::Schema) (e.g. starting a "v1" from a "v0").
+
+use std::collections::{HashMap, VecDeque};
+use strum::IntoEnumIterator;
+use telemetry::prelude::*;
+
+use crate::func::argument::FuncArgument;
+use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
+use crate::schema::variant::{SchemaVariantError, SchemaVariantResult};
+use crate::socket::{SocketEdgeKind, SocketKind};
+use crate::{
+    DalContext, ExternalProvider, Func, InternalProvider, Prop, PropId, SchemaVariant,
+    SchemaVariantId, Socket, StandardModel,
+};
+
+impl SchemaVariant {
+    /// Create a new [`SchemaVariant`] named `new_name` for the same [`Schema`](crate::Schema) as
+    /// the given [`SchemaVariant`] and deep-copy its [`Prop`] tree (including validation formats),
+    /// its input and output [`Sockets`](Socket) (and their providers) and its code generation and
+    /// qualification leaves.
+    ///
+    /// Functions bound to individual [`Props`](Prop) and output [`Sockets`](Socket) are not
+    /// copied. Since leaves can only be inserted once the default values exist, the new
+    /// [`SchemaVariant`] has been finalized once, but [`Self::finalize`] must be called again
+    /// after editing it, like for any other [`SchemaVariant`] under construction.
+    #[instrument(skip(ctx, new_name))]
+    pub async fn duplicate(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        new_name: impl AsRef<str>,
+    ) -> SchemaVariantResult<Self> {
+        let original = Self::get_by_id(ctx, &schema_variant_id)
+            .await?
+            .ok_or(SchemaVariantError::NotFound(schema_variant_id))?;
+        let schema = original
+            .schema(ctx)
+            .await?
+            .ok_or(SchemaVariantError::MissingSchema(schema_variant_id))?;
+
+        let (mut duplicate, _) = Self::new(ctx, *schema.id(), new_name).await?;
+        duplicate
+            .set_link(ctx, original.link().map(ToOwned::to_owned))
+            .await?;
+        duplicate
+            .set_default_color(ctx, original.default_color().map(ToOwned::to_owned))
+            .await?;
+        duplicate.set_ui_hidden(ctx, original.ui_hidden()).await?;
+        duplicate.set_strict(ctx, original.strict()).await?;
+
+        Self::duplicate_props(ctx, schema_variant_id, *duplicate.id()).await?;
+        Self::duplicate_sockets(ctx, schema_variant_id, &duplicate).await?;
+        Self::duplicate_leaves(ctx, schema_variant_id, *duplicate.id()).await?;
+
+        Self::get_by_id(ctx, duplicate.id())
+            .await?
+            .ok_or(SchemaVariantError::NotFound(*duplicate.id()))
+    }
+
+    /// Walk the original [`Prop`] tree and create every [`Prop`] missing from the duplicate's
+    /// tree. The [`Props`](Prop) created alongside the [`RootProp`](crate::RootProp) are reused
+    /// and connected [`Props`](Prop) are re-pointed at their counterparts in the duplicate.
+    async fn duplicate_props(
+        ctx: &DalContext,
+        original_schema_variant_id: SchemaVariantId,
+        duplicate_schema_variant_id: SchemaVariantId,
+    ) -> SchemaVariantResult<()> {
+        let original_root_prop = Self::find_root_prop(ctx, original_schema_variant_id)
+            .await?
+            .ok_or(SchemaVariantError::PropNotFound("/root"))?;
+        let duplicate_root_prop = Self::find_root_prop(ctx, duplicate_schema_variant_id)
+            .await?
+            .ok_or(SchemaVariantError::PropNotFound("/root"))?;
+
+        let mut prop_ids: HashMap<PropId, PropId> = HashMap::new();
+        let mut connected_props = Vec::new();
+
+        let mut work_queue = VecDeque::from([(original_root_prop, *duplicate_root_prop.id())]);
+        while let Some((original_parent, duplicate_parent_id)) = work_queue.pop_front() {
+            for original_prop in original_parent.child_props(ctx).await? {
+                let duplicate_prop = match Prop::find_prop_by_path_opt(
+                    ctx,
+                    duplicate_schema_variant_id,
+                    &original_prop.path(),
+                )
+                .await?
+                {
+                    Some(existing_prop) => existing_prop,
+                    None => {
+                        let mut prop = Prop::new(
+                            ctx,
+                            original_prop.name(),
+                            *original_prop.kind(),
+                            duplicate_schema_variant_id,
+                            Some(duplicate_parent_id),
+                            Some((
+                                *original_prop.widget_kind(),
+                                original_prop.widget_options().cloned(),
+                            )),
+                            original_prop.documentation().map(ToOwned::to_owned),
+                            original_prop.validation_format().map(ToOwned::to_owned),
+                        )
+                        .await?;
+                        prop.set_doc_link(ctx, original_prop.doc_link().map(ToOwned::to_owned))
+                            .await?;
+                        prop.set_hidden(ctx, original_prop.hidden()).await?;
+                        prop
+                    }
+                };
+
+                prop_ids.insert(*original_prop.id(), *duplicate_prop.id());
+                if original_prop.refers_to_prop_id().is_some()
+                    || original_prop.diff_func_id().is_some()
+                {
+                    connected_props.push((original_prop.clone(), duplicate_prop));
+                }
+                work_queue.push_back((original_prop, *duplicate_prop.id()));
+            }
+        }
+
+        for (original_prop, mut duplicate_prop) in connected_props {
+            let refers_to_prop_id = original_prop
+                .refers_to_prop_id()
+                .and_then(|refers_to_prop_id| prop_ids.get(refers_to_prop_id).copied());
+            duplicate_prop
+                .set_refers_to_prop_id(ctx, refers_to_prop_id)
+                .await?;
+            duplicate_prop
+                .set_diff_func_id(ctx, original_prop.diff_func_id().copied())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Recreate the explicit input and output [`Sockets`](Socket) of the original. The "Frame"
+    /// [`Sockets`](Socket) are skipped since [`Self::new`] creates them.
+    async fn duplicate_sockets(
+        ctx: &DalContext,
+        original_schema_variant_id: SchemaVariantId,
+        duplicate: &SchemaVariant,
+    ) -> SchemaVariantResult<()> {
+        let schema_id = *duplicate
+            .schema(ctx)
+            .await?
+            .ok_or(SchemaVariantError::MissingSchema(*duplicate.id()))?
+            .id();
+        let (identity_func, identity_func_binding, identity_func_binding_return_value) =
+            Func::identity_with_binding_and_return_value(ctx).await?;
+
+        let original = Self::get_by_id(ctx, &original_schema_variant_id)
+            .await?
+            .ok_or(SchemaVariantError::NotFound(original_schema_variant_id))?;
+        for original_socket in original.sockets(ctx).await? {
+            if *original_socket.kind() == SocketKind::Frame {
+                continue;
+            }
+
+            let mut socket = match original_socket.edge_kind() {
+                SocketEdgeKind::ConfigurationInput => {
+                    let (_, socket) = InternalProvider::new_explicit_with_socket(
+                        ctx,
+                        *duplicate.id(),
+                        original_socket.name(),
+                        *identity_func.id(),
+                        *identity_func_binding.id(),
+                        *identity_func_binding_return_value.id(),
+                        original_socket.connection_annotations(),
+                        *original_socket.arity(),
+                        false,
+                    )
+                    .await?;
+                    socket
+                }
+                SocketEdgeKind::ConfigurationOutput => {
+                    let type_definition =
+                        ExternalProvider::find_for_socket(ctx, *original_socket.id())
+                            .await?
+                            .and_then(|provider| provider.type_definition().map(ToOwned::to_owned));
+                    let (_, socket) = ExternalProvider::new_with_socket(
+                        ctx,
+                        schema_id,
+                        *duplicate.id(),
+                        original_socket.name(),
+                        type_definition,
+                        *identity_func.id(),
+                        *identity_func_binding.id(),
+                        *identity_func_binding_return_value.id(),
+                        original_socket.connection_annotations(),
+                        *original_socket.arity(),
+                        false,
+                    )
+                    .await?;
+                    socket
+                }
+            };
+
+            socket
+                .set_human_name(ctx, original_socket.human_name().map(ToOwned::to_owned))
+                .await?;
+            socket.set_required(ctx, original_socket.required()).await?;
+            socket
+                .set_ui_hidden(ctx, original_socket.ui_hidden())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Insert the code generation and qualification [`Funcs`](Func) of the original into the
+    /// duplicate's leaves, with the same inputs.
+    async fn duplicate_leaves(
+        ctx: &DalContext,
+        original_schema_variant_id: SchemaVariantId,
+        duplicate_schema_variant_id: SchemaVariantId,
+    ) -> SchemaVariantResult<()> {
+        for leaf_kind in LeafKind::iter() {
+            for (_, func) in
+                Self::find_leaf_item_functions(ctx, original_schema_variant_id, leaf_kind).await?
+            {
+                let input_locations: Vec<LeafInputLocation> =
+                    FuncArgument::list_for_func(ctx, *func.id())
+                        .await?
+                        .iter()
+                        .filter_map(|argument| {
+                            LeafInputLocation::maybe_from_arg_name(argument.name())
+                        })
+                        .collect();
+
+                Self::upsert_leaf_function(
+                    ctx,
+                    duplicate_schema_variant_id,
+                    None,
+                    leaf_kind,
+                    &input_locations,
+                    &func,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -294,4 +294,27 @@ impl SchemaVariant {
         // used for intelligence functions.
         Ok((*map_prop.id(), inserted_attribute_prototype))
     }
+
+    /// Remove the entry populated by the provided [`Func`](crate::Func) from the "/root" subtree
+    /// corresponding to the [`LeafKind`], along with the values it produced.
+    pub async fn remove_leaf(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        leaf_kind: LeafKind,
+        func_id: FuncId,
+    ) -> SchemaVariantResult<()> {
+        let attribute_prototype =
+            SchemaVariant::find_leaf_item_functions(ctx, schema_variant_id, leaf_kind)
+                .await?
+                .into_iter()
+                .find(|(_, func)| *func.id() == func_id)
+                .map(|(attribute_prototype, _)| attribute_prototype)
+                .ok_or(SchemaVariantError::LeafNotFound(func_id, leaf_kind))?;
+
+        // Leaves are keyed entries of their map, so removing one is allowed even though it lives
+        // in the least specific context.
+        AttributePrototype::remove(ctx, attribute_prototype.id(), false).await?;
+
+        Ok(())
+    }
 }
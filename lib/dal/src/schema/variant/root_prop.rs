@@ -57,6 +57,8 @@ pub enum SiPropChild {
     Color,
     /// Corresponds to the "/root/si/name" [`Prop`](crate::Prop).
     Name,
+    /// Corresponds to the "/root/si/notes" [`Prop`](crate::Prop).
+    Notes,
     /// Corresponds to the "/root/si/protected" [`Prop`](crate::Prop).
     Protected,
     /// Corresponds to the "/root/si/type" [`Prop`](crate::Prop).
@@ -68,6 +70,7 @@ impl SiPropChild {
     pub fn prop_name(&self) -> &'static str {
         match self {
             Self::Name => "name",
+            Self::Notes => "notes",
             Self::Protected => "protected",
             Self::Type => "type",
             Self::Color => "color",
@@ -256,6 +259,10 @@ impl SchemaVariant {
                         "label": "Aggregation Frame",
                         "value": "aggregationFrame",
                     },
+                    {
+                        "label": "Subsystem",
+                        "value": "subsystem",
+                    },
                 ])),
             )),
             None,
@@ -274,6 +281,19 @@ impl SchemaVariant {
         .await?;
         color_prop.set_widget_kind(ctx, WidgetKind::Color).await?;
 
+        // Free-form markdown documenting the intent behind the component.
+        let mut notes_prop = Prop::new_without_ui_optionals(
+            ctx,
+            "notes",
+            PropKind::String,
+            schema_variant_id,
+            Some(si_prop_id),
+        )
+        .await?;
+        notes_prop
+            .set_widget_kind(ctx, WidgetKind::TextArea)
+            .await?;
+
         Ok(si_prop_id)
     }
 
@@ -294,9 +314,7 @@ impl SchemaVariant {
         resource_value_prop.set_hidden(ctx, true).await?;
 
         if let Some(reconciliation_func) =
-            Func::find_by_attr(ctx, "name", &"si:defaultReconciliation")
-                .await?
-                .pop()
+            Func::find_by_name(ctx, "si:defaultReconciliation").await?
         {
             ReconciliationPrototype::upsert(
                 ctx,
@@ -45,6 +45,12 @@ pub enum ComponentType {
     ConfigurationFrameDown,
     #[strum(serialize = "ConfigurationFrameUp", serialize = "configurationFrameUp")]
     ConfigurationFrameUp,
+    /// A [`ConfigurationFrameDown`](Self::ConfigurationFrameDown) that also namespaces the
+    /// generated names of the components inside of it (see
+    /// [`subsystem`](crate::component::subsystem)).
+    #[serde(alias = "Subsystem")]
+    #[strum(serialize = "Subsystem", serialize = "subsystem")]
+    Subsystem,
 }
 
 impl From<SchemaVariantSpecComponentType> for ComponentType {
@@ -54,6 +60,7 @@ impl From<SchemaVariantSpecComponentType> for ComponentType {
             SchemaVariantSpecComponentType::AggregationFrame => Self::AggregationFrame,
             SchemaVariantSpecComponentType::ConfigurationFrameDown => Self::ConfigurationFrameDown,
             SchemaVariantSpecComponentType::ConfigurationFrameUp => Self::ConfigurationFrameUp,
+            SchemaVariantSpecComponentType::Subsystem => Self::Subsystem,
         }
     }
 }
@@ -65,6 +72,7 @@ impl From<ComponentType> for SchemaVariantSpecComponentType {
             ComponentType::AggregationFrame => Self::AggregationFrame,
             ComponentType::ConfigurationFrameDown => Self::ConfigurationFrameDown,
             ComponentType::ConfigurationFrameUp => Self::ConfigurationFrameUp,
+            ComponentType::Subsystem => Self::Subsystem,
         }
     }
 }
@@ -77,6 +85,13 @@ impl ComponentType {
             Self::AggregationFrame => "Aggregation Frame",
             Self::ConfigurationFrameDown => "Configuration Frame (down)",
             Self::ConfigurationFrameUp => "Configuration Frame (up)",
+            Self::Subsystem => "Subsystem",
         }
     }
+
+    /// Whether [`self`](Self) pushes the values of its sockets down to the components inside of
+    /// it, like a [`ConfigurationFrameDown`](Self::ConfigurationFrameDown) does.
+    pub fn is_configuration_frame_down(&self) -> bool {
+        matches!(self, Self::ConfigurationFrameDown | Self::Subsystem)
+    }
 }
@@ -24,13 +24,15 @@ use crate::{
     property_editor::schema::PropertyEditorPropWidgetKind,
     serde_impls::{base64_bytes_serde, nonce_serde},
     standard_model::{self, objects_from_rows, TypeHint},
-    standard_model_accessor, standard_model_accessor_ro, ActorView, ChangeSetPk, DalContext,
-    HistoryActor, HistoryEvent, HistoryEventError, KeyPair, KeyPairError, StandardModel,
-    StandardModelError, Tenancy, Timestamp, TransactionsError, UserPk, Visibility, WsEvent,
-    WsEventResult, WsPayload,
+    standard_model_accessor, standard_model_accessor_ro, ActorView, ChangeSetPk, ComponentId,
+    DalContext, HistoryActor, HistoryEvent, HistoryEventError, KeyPair, KeyPairError,
+    StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError, UserPk, Visibility,
+    WsEvent, WsEventError, WsEventResult, WsPayload,
 };
 
 const LIST_SECRET_DEFINITIONS: &str = include_str!("queries/secrets/list_secret_definitions.sql");
+const LIST_CONSUMING_COMPONENTS: &str =
+    include_str!("queries/secrets/list_consuming_components.sql");
 
 /// Error type for Secrets.
 #[remain::sorted]
@@ -56,6 +58,8 @@ pub enum SecretError {
     SymmetricCrypto(#[from] SymmetricCryptoError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
 }
 
 /// Result type for Secrets.
@@ -76,6 +80,7 @@ pub struct Secret {
     key_pair_pk: KeyPairPk,
     definition: String,
     description: Option<String>,
+    revision: i64,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -98,6 +103,12 @@ impl_standard_model! {
 impl Secret {
     standard_model_accessor_ro!(name, str);
 
+    /// The revision of the encrypted contents: 1 when created, bumped by every
+    /// [`rotation`](Self::rotate).
+    pub fn revision(&self) -> i64 {
+        self.revision
+    }
+
     // Update the underlying `encrypted_secrets` table rather than attempting to update the
     // `secrets` view
     pub async fn set_name(
@@ -135,6 +146,85 @@ impl Secret {
     pub async fn key_pair(&self, ctx: &DalContext) -> SecretResult<KeyPair> {
         Ok(KeyPair::get_by_pk(ctx, self.key_pair_pk).await?)
     }
+
+    /// Replace the encrypted contents of the secret with `new_value`, keeping its id, and bump its
+    /// [`revision`](Self::revision). The [`Components`](crate::Component) consuming the secret are
+    /// told to re-run their confirmations through a [`WsEvent`], published on commit.
+    pub async fn rotate(
+        &mut self,
+        ctx: &DalContext,
+        new_value: NewSecretValue,
+    ) -> SecretResult<()> {
+        let mut encrypted_secret = EncryptedSecret::get_by_id(ctx, self.id())
+            .await?
+            .ok_or(SecretError::SecretNotFound(self.id))?;
+
+        encrypted_secret.set_crypted(ctx, new_value.crypted).await?;
+        encrypted_secret
+            .set_key_pair_pk(ctx, new_value.key_pair_pk)
+            .await?;
+        encrypted_secret.set_version(ctx, new_value.version).await?;
+        encrypted_secret
+            .set_algorithm(ctx, new_value.algorithm)
+            .await?;
+        if let HistoryActor::User(user_pk) = ctx.history_actor() {
+            encrypted_secret.set_updated_by(ctx, Some(*user_pk)).await?;
+        }
+        let revision = encrypted_secret.revision() + 1;
+        encrypted_secret.set_revision(ctx, revision).await?;
+
+        let component_ids = self.list_consuming_components(ctx).await?;
+        WsEvent::secret_rotated(ctx, self.id, revision, component_ids)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        *self = encrypted_secret.into();
+
+        Ok(())
+    }
+
+    /// List the [`Components`](crate::Component) whose "/root/secrets" props are set to this
+    /// secret.
+    pub async fn list_consuming_components(
+        &self,
+        ctx: &DalContext,
+    ) -> SecretResult<Vec<ComponentId>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_CONSUMING_COMPONENTS,
+                &[ctx.tenancy(), ctx.visibility(), &self.id],
+            )
+            .await?;
+        let mut component_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            component_ids.push(row.try_get("component_id")?);
+        }
+        Ok(component_ids)
+    }
+}
+
+/// The new encrypted contents of a [`Secret`], sealed with the public key of a [`KeyPair`].
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSecretValue {
+    pub crypted: Vec<u8>,
+    pub key_pair_pk: KeyPairPk,
+    pub version: SecretVersion,
+    pub algorithm: SecretAlgorithm,
+}
+
+impl fmt::Debug for NewSecretValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NewSecretValue")
+            .field("key_pair_pk", &self.key_pair_pk)
+            .field("version", &self.version)
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
@@ -151,6 +241,17 @@ pub struct SecretUpdatedPayload {
     change_set_pk: ChangeSetPk,
 }
 
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRotatedPayload {
+    secret_id: SecretId,
+    revision: i64,
+    /// The [`Components`](crate::Component) consuming the secret, whose confirmations should be
+    /// re-run.
+    component_ids: Vec<ComponentId>,
+    change_set_pk: ChangeSetPk,
+}
+
 impl WsEvent {
     pub async fn secret_created(ctx: &DalContext, secret_id: SecretId) -> WsEventResult<Self> {
         WsEvent::new(
@@ -173,6 +274,24 @@ impl WsEvent {
         )
         .await
     }
+
+    pub async fn secret_rotated(
+        ctx: &DalContext,
+        secret_id: SecretId,
+        revision: i64,
+        component_ids: Vec<ComponentId>,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::SecretRotated(SecretRotatedPayload {
+                secret_id,
+                revision,
+                component_ids,
+                change_set_pk: ctx.visibility().change_set_pk,
+            }),
+        )
+        .await
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -182,6 +301,7 @@ pub struct SecretView {
     pub name: String,
     pub definition: String,
     pub description: Option<String>,
+    pub revision: i64,
     pub created_info: HistoryEventMetadata,
     pub updated_info: Option<HistoryEventMetadata>,
 }
@@ -225,6 +345,7 @@ impl SecretView {
             name: secret.name,
             definition: secret.definition,
             description: secret.description,
+            revision: secret.revision,
             created_info,
             updated_info,
         })
@@ -240,6 +361,7 @@ impl From<EncryptedSecret> for Secret {
             key_pair_pk: value.key_pair_pk,
             definition: value.definition,
             description: value.description,
+            revision: value.revision,
             tenancy: value.tenancy,
             timestamp: value.timestamp,
             created_by: value.created_by,
@@ -271,6 +393,7 @@ pub struct EncryptedSecret {
     crypted: Vec<u8>,
     version: SecretVersion,
     algorithm: SecretAlgorithm,
+    revision: i64,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -291,6 +414,7 @@ impl fmt::Debug for EncryptedSecret {
             .field("description", &self.description)
             .field("version", &self.version)
             .field("algorithm", &self.algorithm)
+            .field("revision", &self.revision)
             .field("key_hash", &self.key_hash)
             .field("tenancy", &self.tenancy)
             .field("timestamp", &self.timestamp)
@@ -363,6 +487,7 @@ impl EncryptedSecret {
     standard_model_accessor!(algorithm, Enum(SecretAlgorithm), SecretResult);
     standard_model_accessor!(updated_by, Option<Pk(UserPk)>, SecretResult);
     standard_model_accessor!(key_pair_pk, Pk(KeyPairPk), SecretResult);
+    standard_model_accessor!(revision, i64, SecretResult);
 
     // Once created, this object field is immutable
     standard_model_accessor_ro!(definition, String);
@@ -600,6 +725,7 @@ mod tests {
                 crypted: double_crypted,
                 version: Default::default(),
                 algorithm: Default::default(),
+                revision: 1,
                 tenancy: Tenancy::new(wid),
                 timestamp: Timestamp::now(),
                 created_by: None,
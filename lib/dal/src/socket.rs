@@ -375,4 +375,107 @@ impl Socket {
             .await?;
         Ok(standard_model::option_object_from_row(maybe_row)?)
     }
+
+    /// Returns the parsed connection annotations for [`Self`], falling back to the name of the
+    /// [`Socket`] when none were declared. Annotations that cannot be parsed are skipped.
+    pub fn parsed_connection_annotations(&self) -> Vec<Vec<String>> {
+        let annotations: Vec<String> = serde_json::from_str(self.connection_annotations())
+            .unwrap_or_else(|_| vec![self.name().to_owned()]);
+        annotations
+            .iter()
+            .filter_map(|annotation| parse_connection_annotation(annotation))
+            .collect()
+    }
+
+    /// Returns whether or not an edge can be drawn from [`Self`] to the other [`Socket`]: one
+    /// must be an output and the other an input, and one of the output's annotations must fit
+    /// one of the input's.
+    pub fn is_compatible_with(&self, other: &Socket) -> bool {
+        let (output, input) = match (self.edge_kind(), other.edge_kind()) {
+            (SocketEdgeKind::ConfigurationOutput, SocketEdgeKind::ConfigurationInput) => {
+                (self, other)
+            }
+            (SocketEdgeKind::ConfigurationInput, SocketEdgeKind::ConfigurationOutput) => {
+                (other, self)
+            }
+            _ => return false,
+        };
+
+        let input_annotations = input.parsed_connection_annotations();
+        output
+            .parsed_connection_annotations()
+            .iter()
+            .any(|output_annotation| {
+                input_annotations.iter().any(|input_annotation| {
+                    connection_annotation_fits_reference(output_annotation, input_annotation)
+                })
+            })
+    }
+}
+
+/// Parses a connection annotation, such as `"Port<String>"`, into its chain of lowercased types,
+/// from the outermost to the innermost (`["port", "string"]`). Returns [`None`] if the
+/// annotation is malformed.
+///
+/// This mirrors `parseConnectionAnnotation` in `lib/ts-lib`.
+pub fn parse_connection_annotation(annotation: &str) -> Option<Vec<String>> {
+    let mut types = Vec::new();
+    let mut token = annotation;
+    loop {
+        let (head, tail) = match token.find('<') {
+            Some(index) => (&token[..index], Some(token[index + 1..].strip_suffix('>')?)),
+            None => (token, None),
+        };
+        if head.is_empty()
+            || !head
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == ' ')
+        {
+            return None;
+        }
+        types.push(head.trim().to_lowercase());
+
+        match tail {
+            Some(tail) if !tail.is_empty() => token = tail,
+            Some(_) => return None,
+            None => return Some(types),
+        }
+    }
+}
+
+/// Returns whether or not the target annotation fits the reference annotation, which is the
+/// case if it is the same type or a supertype thereof (e.g. `["port", "string"]` fits
+/// `["string"]`).
+///
+/// This mirrors `connectionAnnotationFitsReference` in `lib/ts-lib`.
+pub fn connection_annotation_fits_reference(target: &[String], reference: &[String]) -> bool {
+    target.len() >= reference.len() && target.ends_with(reference)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_nested_connection_annotation() {
+        assert_eq!(
+            Some(vec!["port".to_string(), "string".to_string()]),
+            parse_connection_annotation("Port<String>")
+        );
+        assert_eq!(
+            Some(vec!["docker image".to_string()]),
+            parse_connection_annotation("Docker Image")
+        );
+        assert_eq!(None, parse_connection_annotation("Port<String"));
+        assert_eq!(None, parse_connection_annotation("<String>"));
+    }
+
+    #[test]
+    fn supertype_fits_reference() {
+        let port_string = parse_connection_annotation("Port<String>").expect("could not parse");
+        let string = parse_connection_annotation("String").expect("could not parse");
+        assert!(connection_annotation_fits_reference(&port_string, &string));
+        assert!(connection_annotation_fits_reference(&string, &string));
+        assert!(!connection_annotation_fits_reference(&string, &port_string));
+    }
 }
@@ -0,0 +1,245 @@
+//! This module contains [`SocketMergeStrategies`](SocketMergeStrategy), which decide how an
+//! [`AggregationFrame`](crate::ComponentType::AggregationFrame) merges the values of its children.
+//!
+//! An aggregation frame collects the output of every child connected to one of its output
+//! [`Sockets`](crate::Socket) into the [`ExternalProvider`](crate::ExternalProvider) behind that
+//! socket. Without a strategy, the values are handed over as they come: a lone value as is, and
+//! several as an array. A [`MergeStrategy`] makes the shape predictable instead, whatever the
+//! number of children.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_data_pg::PgError;
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::socket::SocketError;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, ComponentId, DalContext,
+    ExternalProviderId, HistoryEventError, Socket, SocketId, StandardModel, StandardModelError,
+    Tenancy, Timestamp, TransactionsError, Visibility,
+};
+
+const LIST_FOR_COMPONENT: &str =
+    include_str!("queries/socket_merge_strategy/list_for_component.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum SocketMergeStrategyError {
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("socket error: {0}")]
+    Socket(#[from] SocketError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type SocketMergeStrategyResult<T> = Result<T, SocketMergeStrategyError>;
+
+/// How the values of the children of an aggregation frame are merged, in the order the children
+/// were connected to the frame.
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Display, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum MergeStrategy {
+    /// An array of every value, where values that are arrays themselves are flattened into it and
+    /// unset values are left out.
+    ArrayAppend,
+    /// The first value that is set, if any.
+    FirstWins,
+    /// An object mapping the [`ComponentId`] of every child to its value.
+    KeyedMap,
+}
+
+impl MergeStrategy {
+    /// Merges the values, each paired with the [`ComponentId`] of the child it comes from.
+    pub fn merge(&self, values: Vec<(ComponentId, Value)>) -> Value {
+        match self {
+            Self::ArrayAppend => {
+                let mut merged = Vec::new();
+                for (_, value) in values {
+                    match value {
+                        Value::Null => {}
+                        Value::Array(items) => merged.extend(items),
+                        value => merged.push(value),
+                    }
+                }
+                Value::Array(merged)
+            }
+            Self::FirstWins => values
+                .into_iter()
+                .map(|(_, value)| value)
+                .find(|value| !value.is_null())
+                .unwrap_or(Value::Null),
+            Self::KeyedMap => Value::Object(
+                values
+                    .into_iter()
+                    .map(|(component_id, value)| (component_id.to_string(), value))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+pk!(SocketMergeStrategyPk);
+pk!(SocketMergeStrategyId);
+
+/// The [`MergeStrategy`] of an output [`Socket`] of an aggregation frame [`Component`]. There is
+/// at most one per socket of a frame.
+///
+/// [`Component`]: crate::Component
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SocketMergeStrategy {
+    pk: SocketMergeStrategyPk,
+    id: SocketMergeStrategyId,
+    /// The [`ComponentId`] of the aggregation frame.
+    component_id: ComponentId,
+    socket_id: SocketId,
+    strategy: MergeStrategy,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: SocketMergeStrategy,
+    pk: SocketMergeStrategyPk,
+    id: SocketMergeStrategyId,
+    table_name: "socket_merge_strategies",
+    history_event_label_base: "socket_merge_strategy",
+    history_event_message_name: "Socket Merge Strategy"
+}
+
+impl SocketMergeStrategy {
+    #[instrument(skip(ctx))]
+    pub async fn upsert(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        socket_id: SocketId,
+        strategy: MergeStrategy,
+    ) -> SocketMergeStrategyResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM socket_merge_strategy_upsert_v1($1, $2, $3, $4, $5)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &component_id,
+                    &socket_id,
+                    &strategy.as_ref(),
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    /// List the [`SocketMergeStrategies`](Self) of a frame [`Component`](crate::Component).
+    pub async fn list_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> SocketMergeStrategyResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_COMPONENT,
+                &[ctx.tenancy(), ctx.visibility(), &component_id],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Find the [`MergeStrategy`] of the [`Socket`] behind the [`ExternalProvider`] of a frame
+    /// [`Component`](crate::Component), if one was chosen.
+    ///
+    /// [`ExternalProvider`]: crate::ExternalProvider
+    pub async fn find_for_external_provider(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        external_provider_id: ExternalProviderId,
+    ) -> SocketMergeStrategyResult<Option<MergeStrategy>> {
+        let strategies = Self::list_for_component(ctx, component_id).await?;
+        if strategies.is_empty() {
+            return Ok(None);
+        }
+
+        let socket_ids: HashSet<SocketId> =
+            Socket::find_for_external_provider(ctx, external_provider_id)
+                .await?
+                .iter()
+                .map(|socket| *socket.id())
+                .collect();
+        Ok(strategies
+            .into_iter()
+            .find(|strategy| socket_ids.contains(&strategy.socket_id))
+            .map(|strategy| strategy.strategy))
+    }
+
+    standard_model_accessor!(component_id, Pk(ComponentId), SocketMergeStrategyResult);
+    standard_model_accessor!(socket_id, Pk(SocketId), SocketMergeStrategyResult);
+    standard_model_accessor!(strategy, Enum(MergeStrategy), SocketMergeStrategyResult);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> (ComponentId, ComponentId, Vec<(ComponentId, Value)>) {
+        let (first, second) = (ComponentId::generate(), ComponentId::generate());
+        let values = vec![
+            (first, Value::Null),
+            (second, serde_json::json!(["a", "b"])),
+            (ComponentId::generate(), serde_json::json!("c")),
+        ];
+        (first, second, values)
+    }
+
+    #[test]
+    fn array_append_flattens_and_skips_unset_values() {
+        let (_, _, values) = values();
+        assert_eq!(
+            serde_json::json!(["a", "b", "c"]),
+            MergeStrategy::ArrayAppend.merge(values)
+        );
+    }
+
+    #[test]
+    fn first_wins_takes_the_first_set_value() {
+        let (_, _, values) = values();
+        assert_eq!(
+            serde_json::json!(["a", "b"]),
+            MergeStrategy::FirstWins.merge(values)
+        );
+        assert_eq!(Value::Null, MergeStrategy::FirstWins.merge(Vec::new()));
+    }
+
+    #[test]
+    fn keyed_map_keys_values_by_component() {
+        let (first, second, values) = values();
+        let merged = MergeStrategy::KeyedMap.merge(values);
+        assert_eq!(Some(&Value::Null), merged.get(first.to_string()));
+        assert_eq!(
+            Some(&serde_json::json!(["a", "b"])),
+            merged.get(second.to_string())
+        );
+        assert_eq!(3, merged.as_object().map(|map| map.len()).unwrap_or(0));
+    }
+}
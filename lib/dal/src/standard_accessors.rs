@@ -303,6 +303,7 @@ macro_rules! standard_model_accessor {
                     &Self::history_event_message("updated"),
                     &serde_json::json![{
                         "pk": self.pk,
+                        "id": self.id(),
                         "field": stringify!($column),
                         "value": &value,
                     }],
@@ -339,6 +340,7 @@ macro_rules! standard_model_accessor {
                     &Self::history_event_message("updated"),
                     &serde_json::json![{
                         "pk": self.pk,
+                        "id": self.id(),
                         "field": stringify!($column),
                         "value": &value,
                     }],
@@ -375,6 +377,7 @@ macro_rules! standard_model_accessor {
                     &Self::history_event_message("updated"),
                     &serde_json::json![{
                         "pk": self.pk,
+                        "id": self.id(),
                         "field": stringify!($column),
                         "value": &value,
                     }],
@@ -410,6 +413,7 @@ macro_rules! standard_model_accessor {
                     &Self::history_event_message("updated"),
                     &serde_json::json![{
                         "pk": self.pk,
+                        "id": self.id(),
                         "field": stringify!($column),
                         "value": &value,
                     }],
@@ -445,6 +449,7 @@ macro_rules! standard_model_accessor {
                     &Self::history_event_message("updated"),
                     &serde_json::json![{
                         "pk": self.pk,
+                        "id": self.id(),
                         "field": stringify!($column),
                         "value": &value,
                     }],
@@ -9,6 +9,8 @@ use strum::AsRefStr;
 use telemetry::prelude::*;
 use thiserror::Error;
 
+use crate::attribute::context::cache::AttributeContextCache;
+use crate::func::cache::FuncCache;
 use crate::{DalContext, HistoryEvent, HistoryEventError, Timestamp, Visibility};
 
 #[remain::sorted]
@@ -76,6 +78,21 @@ pub async fn get_by_id<ID: Send + Sync + ToSql, OBJECT: DeserializeOwned>(
     table: &str,
     id: &ID,
 ) -> StandardModelResult<Option<OBJECT>> {
+    // Ids are keyed by their debug representation, which every ToSql type has.
+    let cache_key = (ctx.attribute_context_cache_enabled()
+        && AttributeContextCache::caches_table(table))
+    .then(|| format!("{id:?}"));
+    if let Some(cache_key) = &cache_key {
+        if let Some(json) = ctx
+            .txns()
+            .await?
+            .attribute_context_cache()
+            .object_by_id(ctx, table, cache_key)
+        {
+            return Ok(Some(serde_json::from_value(json)?));
+        }
+    }
+
     let row_option = ctx
         .txns()
         .await?
@@ -85,7 +102,17 @@ pub async fn get_by_id<ID: Send + Sync + ToSql, OBJECT: DeserializeOwned>(
             &[&table, ctx.tenancy(), ctx.visibility(), &id],
         )
         .await?;
-    object_option_from_row_option(row_option)
+    match (row_option, cache_key) {
+        (Some(row), Some(cache_key)) => {
+            let json: serde_json::Value = row.try_get("object")?;
+            ctx.txns()
+                .await?
+                .attribute_context_cache_mut()
+                .insert_object_by_id(ctx, table, &cache_key, &json);
+            Ok(Some(serde_json::from_value(json)?))
+        }
+        (row_option, _) => object_option_from_row_option(row_option),
+    }
 }
 
 // This likely has some fun bugs living inside it when the value you pass is not
@@ -504,6 +531,7 @@ where
             ],
         )
         .await?;
+    invalidate_caches(ctx, table).await?;
     row.try_get("updated_at")
         .map_err(|_| StandardModelError::ModelMissing(table.to_string(), id.to_string()))
 }
@@ -540,6 +568,7 @@ pub async fn delete_by_id<ID: Send + Sync + ToSql + std::fmt::Display>(
             &[&table, ctx.tenancy(), ctx.visibility(), &id],
         )
         .await?;
+    invalidate_caches(ctx, table).await?;
     row.try_get("deleted_at")
         .map_err(|_| StandardModelError::ModelMissing(table.to_string(), id.to_string()))
 }
@@ -559,6 +588,7 @@ pub async fn delete_by_pk<PK: Send + Sync + ToSql + std::fmt::Display>(
             &[&table, ctx.tenancy(), &pk],
         )
         .await?;
+    invalidate_caches(ctx, table).await?;
     row.try_get("updated_at")
         .map_err(|_| StandardModelError::ModelMissing(table.to_string(), pk.to_string()))
 }
@@ -578,6 +608,7 @@ pub async fn undelete<PK: Send + Sync + ToSql + std::fmt::Display>(
             &[&table, ctx.tenancy(), &pk],
         )
         .await?;
+    invalidate_caches(ctx, table).await?;
     row.try_get("updated_at")
         .map_err(|_| StandardModelError::ModelMissing(table.to_string(), pk.to_string()))
 }
@@ -597,10 +628,23 @@ pub async fn hard_delete<PK: Send + Sync + ToSql + std::fmt::Display, OBJECT: De
             &[&table, &pk],
         )
         .await?;
+    invalidate_caches(ctx, table).await?;
     let json: serde_json::Value = row.try_get("object")?;
     Ok(serde_json::from_value(json)?)
 }
 
+/// Clears the [`FuncCache`] and the [`AttributeContextCache`] when `table` backs a model they
+/// cache.
+async fn invalidate_caches(ctx: &DalContext, table: &str) -> StandardModelResult<()> {
+    if FuncCache::caches_table(table) {
+        FuncCache::invalidate(ctx).await?;
+    }
+    if AttributeContextCache::caches_table(table) {
+        AttributeContextCache::invalidate(ctx).await?;
+    }
+    Ok(())
+}
+
 #[instrument(level = "trace", skip(ctx))]
 pub async fn finish_create_from_row<Object: Send + Sync + DeserializeOwned + StandardModel>(
     ctx: &DalContext,
@@ -611,7 +655,7 @@ pub async fn finish_create_from_row<Object: Send + Sync + DeserializeOwned + Sta
         ctx,
         Object::history_event_label(vec!["create"]),
         Object::history_event_message("created"),
-        &serde_json::json![{ "visibility": ctx.visibility() }],
+        &serde_json::json![{ "id": json["id"], "visibility": ctx.visibility() }],
     )
     .await?;
     let object: Object = serde_json::from_value(json)?;
@@ -2,9 +2,19 @@
 //! SI binaries that are dependent on the [`dal`](crate).
 
 // This modules should remain private! Add "pub use" statements to use their contents.
+mod digest_scheduler;
+mod garbage_collector;
 mod resource_scheduler;
+mod retention_scheduler;
 mod status_receiver;
+mod webhook_dispatcher;
 
+pub use digest_scheduler::{DigestScheduler, DigestSchedulerError};
+pub use garbage_collector::{
+    GarbageCollector, GarbageCollectorError, GARBAGE_COLLECTION_RETENTION_DAYS,
+};
 pub use resource_scheduler::{ResourceScheduler, ResourceSchedulerError};
+pub use retention_scheduler::{RetentionScheduler, RetentionSchedulerError};
 pub use status_receiver::client::StatusReceiverClient;
 pub use status_receiver::{StatusReceiver, StatusReceiverError, StatusReceiverRequest};
+pub use webhook_dispatcher::{WebhookDispatcher, WebhookDispatcherError};
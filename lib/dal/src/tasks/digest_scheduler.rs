@@ -0,0 +1,111 @@
+//! This module contains [`DigestScheduler`], which is a "long-running" task that delivers
+//! [`workspace activity digests`](crate::workspace::digest) on a cadence.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, time};
+
+use crate::workspace::digest::{WorkspaceDigest, WorkspaceDigestConfig, WorkspaceDigestError};
+use crate::{ServicesContext, Tenancy, TransactionsError};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DigestSchedulerError {
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    WorkspaceDigest(#[from] WorkspaceDigestError),
+}
+
+pub type DigestSchedulerResult<T> = Result<T, DigestSchedulerError>;
+
+/// The digest scheduler periodically looks for workspaces whose digest window has elapsed,
+/// builds their digest and delivers it to the configured webhook. A workspace whose delivery
+/// fails is retried on the next tick.
+#[derive(Debug, Clone)]
+pub struct DigestScheduler {
+    services_context: ServicesContext,
+}
+
+impl DigestScheduler {
+    pub fn new(services_context: ServicesContext) -> DigestScheduler {
+        DigestScheduler { services_context }
+    }
+
+    /// Starts the scheduler in a spawned task that runs until a shutdown is requested.
+    pub fn start(self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    info!("Digest Scheduler received shutdown request, bailing out");
+                },
+                _ = self.start_task() => {}
+            }
+            info!("Digest Scheduler stopped");
+        });
+    }
+
+    #[instrument(name = "digest_scheduler.run", skip_all, level = "debug")]
+    async fn run(&self) -> DigestSchedulerResult<()> {
+        let now = Utc::now();
+        let due = {
+            let ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            // Configurations are listed across all workspaces.
+            let due = WorkspaceDigestConfig::list_due(&ctx, now).await?;
+            ctx.commit().await?;
+            due
+        };
+
+        for mut config in due {
+            let webhook_url = match config.webhook_url() {
+                Some(webhook_url) => webhook_url.to_owned(),
+                None => continue,
+            };
+
+            let mut ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            ctx.update_tenancy(Tenancy::new(config.workspace_pk()));
+
+            let digest =
+                WorkspaceDigest::build(&ctx, config.workspace_pk(), config.window_hours(), now)
+                    .await?;
+            if let Err(err) = digest.deliver(&webhook_url).await {
+                warn!(
+                    error = ?err,
+                    workspace_pk = %config.workspace_pk(),
+                    "unable to deliver workspace digest"
+                );
+                continue;
+            }
+            config.mark_sent(&ctx, now).await?;
+            ctx.commit().await?;
+        }
+
+        Ok(())
+    }
+
+    /// The internal task spawned by `start`. Every 15 minutes, it delivers the digests that
+    /// are due.
+    #[instrument(name = "digest_scheduler.start_task", skip_all, level = "debug")]
+    async fn start_task(&self) {
+        let mut interval = time::interval(Duration::from_secs(900));
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.run().await {
+                error!("{err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! This module contains [`GarbageCollector`], which is a "long-running" task that hard-deletes
+//! soft-deleted rows via [`garbage_collect`](crate::garbage_collection::garbage_collect).
+
+use std::time::Duration;
+
+use chrono::Utc;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, time};
+
+use crate::garbage_collection::{garbage_collect, GarbageCollectionError};
+use crate::{ServicesContext, TransactionsError};
+
+/// How long soft-deleted rows are kept before being garbage collected.
+pub const GARBAGE_COLLECTION_RETENTION_DAYS: i64 = 30;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum GarbageCollectorError {
+    #[error(transparent)]
+    GarbageCollection(#[from] GarbageCollectionError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type GarbageCollectorResult<T> = Result<T, GarbageCollectorError>;
+
+/// The garbage collector periodically hard-deletes the rows that were soft-deleted more than
+/// [`GARBAGE_COLLECTION_RETENTION_DAYS`] ago.
+#[derive(Debug, Clone)]
+pub struct GarbageCollector {
+    services_context: ServicesContext,
+}
+
+impl GarbageCollector {
+    pub fn new(services_context: ServicesContext) -> GarbageCollector {
+        GarbageCollector { services_context }
+    }
+
+    /// Starts the collector in a spawned task that runs until a shutdown is requested.
+    pub fn start(self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    info!("Garbage Collector received shutdown request, bailing out");
+                },
+                _ = self.start_task() => {}
+            }
+            info!("Garbage Collector stopped");
+        });
+    }
+
+    #[instrument(name = "garbage_collector.run", skip_all, level = "debug")]
+    async fn run(&self) -> GarbageCollectorResult<()> {
+        let older_than = Utc::now() - chrono::Duration::days(GARBAGE_COLLECTION_RETENTION_DAYS);
+        let ctx = self
+            .services_context
+            .clone()
+            .into_builder(false)
+            .build_default()
+            .await?;
+
+        match garbage_collect(&ctx, older_than).await {
+            Ok(report) => {
+                ctx.commit().await?;
+                for (table, reclaimed) in &report.reclaimed {
+                    info!(%table, reclaimed, "garbage collector reclaimed rows");
+                }
+            }
+            Err(err) => {
+                ctx.rollback().await?;
+                return Err(err.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The internal task spawned by `start`. Every day, it collects the soft-deleted rows that
+    /// fell out of the retention window.
+    #[instrument(name = "garbage_collector.start_task", skip_all, level = "debug")]
+    async fn start_task(&self) {
+        let mut interval = time::interval(Duration::from_secs(86400));
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.run().await {
+                error!("{err}");
+            }
+        }
+    }
+}
@@ -1,19 +1,27 @@
 //! This module contains [`ResourceScheduler`], which is a "long-running" tasks that performs
 //! [`resource`](crate::component::resource) syncing on a cadence.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use si_data_nats::NatsError;
 use si_data_pg::{PgError, PgPoolError};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::{sync::broadcast, time};
 
-use crate::{standard_model, Component, ServicesContext, StandardModelError, TransactionsError};
+use crate::job::definition::RefreshJob;
+use crate::{
+    standard_model, Component, ComponentError, ComponentId, DalContext, ServicesContext,
+    StandardModel, StandardModelError, Tenancy, TransactionsError, WorkspacePk,
+};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ResourceSchedulerError {
+    #[error(transparent)]
+    Component(#[from] ComponentError),
     #[error(transparent)]
     Nats(#[from] NatsError),
     #[error(transparent)]
@@ -32,9 +40,10 @@ pub type ResourceSchedulerResult<T> = Result<T, ResourceSchedulerError>;
 
 /// The resource scheduler handles looking up all the components, and scheduling
 /// their resources to refresh. Eventually, it should become smart enough to parallelize,
-/// it might be extracted to a fully separate service, etc etc. For now,
-/// it is the dumbest thing that could possibly work - no more often than every 30
-/// seconds, it will ask a resource to refresh
+/// it might be extracted to a fully separate service, etc etc. For now, every minute it
+/// enqueues a [`RefreshJob`] for the components on head whose
+/// [`Schema`](crate::Schema) has a refresh interval and whose resource was last synced
+/// longer ago than that interval.
 #[derive(Debug, Clone)]
 pub struct ResourceScheduler {
     services_context: ServicesContext,
@@ -61,33 +70,93 @@ impl ResourceScheduler {
     }
 
     #[instrument(name = "resource_scheduler.run", skip_all, level = "debug")]
-    async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        /*
-        let components = self.components().await?;
-        info!("Refresh {} resources", components.len());
-
-        for component in components {
-            // First we're building a ctx with no tenancy at head, then updating it with a
-            // workspace head request context
-
-            let builder = self.services_context.clone().into_builder(false);
-            let mut ctx = builder.build_default().await?;
-
-            ctx.update_tenancy(*component.tenancy());
+    async fn run(&self) -> ResourceSchedulerResult<()> {
+        let now = Utc::now();
+        let mut due: HashMap<WorkspacePk, Vec<ComponentId>> = HashMap::new();
+
+        for component in self.components().await? {
+            let workspace_pk = match component.tenancy().workspace_pk() {
+                Some(workspace_pk) => workspace_pk,
+                None => continue,
+            };
+
+            let mut ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            ctx.update_tenancy(Tenancy::new(workspace_pk));
+            ctx.update_with_deleted_visibility();
+
+            if Self::is_due(&ctx, &component, now).await? {
+                due.entry(workspace_pk).or_default().push(*component.id());
+            }
+            ctx.commit().await?;
+        }
 
-            component.act(&ctx, "refresh").await?;
+        for (workspace_pk, component_ids) in due {
+            info!(%workspace_pk, "Refresh {} resources", component_ids.len());
+
+            let mut ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            ctx.update_tenancy(Tenancy::new(workspace_pk));
+
+            ctx.enqueue_job(RefreshJob::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                component_ids,
+            ))
+            .await?;
             ctx.commit().await?;
         }
-        */
+
         Ok(())
     }
 
-    /// The internal task spawned by `start`. No more frequently than every 30
-    /// seconds, it will iterate over all the components on head in the database and
-    /// schedule them to refresh.
+    /// Whether the resource of a [`Component`] exists and was last synced longer ago than the
+    /// refresh interval of its [`Schema`](crate::Schema).
+    async fn is_due(
+        ctx: &DalContext,
+        component: &Component,
+        now: DateTime<Utc>,
+    ) -> ResourceSchedulerResult<bool> {
+        let refresh_interval_seconds = match component
+            .schema(ctx)
+            .await?
+            .and_then(|schema| schema.refresh_interval_seconds().copied())
+        {
+            Some(refresh_interval_seconds) => refresh_interval_seconds,
+            None => return Ok(false),
+        };
+
+        let resource = component.resource(ctx).await?;
+        if resource.payload.is_none() {
+            return Ok(false);
+        }
+
+        let last_synced = resource
+            .last_synced
+            .as_deref()
+            .and_then(|last_synced| DateTime::parse_from_rfc3339(last_synced).ok());
+        Ok(match last_synced {
+            Some(last_synced) => {
+                now.signed_duration_since(last_synced)
+                    >= chrono::Duration::seconds(refresh_interval_seconds)
+            }
+            None => true,
+        })
+    }
+
+    /// The internal task spawned by `start`. Every minute, it will iterate over all the
+    /// components on head in the database and schedule the ones that are due to refresh.
     #[instrument(name = "resource_scheduler.start_task", skip_all, level = "debug")]
     async fn start_task(&self) {
-        let mut interval = time::interval(Duration::from_secs(300));
+        let mut interval = time::interval(Duration::from_secs(60));
         loop {
             interval.tick().await;
             match self.run().await {
@@ -0,0 +1,121 @@
+//! This module contains [`RetentionScheduler`], which is a "long-running" task that prunes
+//! superseded data according to [`workspace retention policies`](crate::workspace::retention).
+
+use std::time::Duration;
+
+use chrono::Utc;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, time};
+
+use crate::workspace::retention::{WorkspaceRetentionError, WorkspaceRetentionPolicy};
+use crate::{ServicesContext, Tenancy, TransactionsError, WsEvent, WsEventError};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum RetentionSchedulerError {
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    WorkspaceRetention(#[from] WorkspaceRetentionError),
+    #[error(transparent)]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type RetentionSchedulerResult<T> = Result<T, RetentionSchedulerError>;
+
+/// The retention scheduler periodically prunes the superseded
+/// [`FuncBindingReturnValues`](crate::FuncBindingReturnValue) of every workspace, one
+/// transaction per workspace, so a failure in one workspace doesn't hold back the others. It
+/// also drops the [`WsEvents`](WsEvent) that fell out of the replay window.
+#[derive(Debug, Clone)]
+pub struct RetentionScheduler {
+    services_context: ServicesContext,
+}
+
+impl RetentionScheduler {
+    pub fn new(services_context: ServicesContext) -> RetentionScheduler {
+        RetentionScheduler { services_context }
+    }
+
+    /// Starts the scheduler in a spawned task that runs until a shutdown is requested.
+    pub fn start(self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    info!("Retention Scheduler received shutdown request, bailing out");
+                },
+                _ = self.start_task() => {}
+            }
+            info!("Retention Scheduler stopped");
+        });
+    }
+
+    #[instrument(name = "retention_scheduler.run", skip_all, level = "debug")]
+    async fn run(&self) -> RetentionSchedulerResult<()> {
+        let now = Utc::now();
+        let policies = {
+            let ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            // The replay log of WsEvents is pruned across all workspaces, regardless of policies.
+            let deleted =
+                WsEvent::prune_replay_log(&ctx, WsEvent::replay_window_start(now)).await?;
+            if deleted > 0 {
+                debug!(deleted, "pruned ws event replay log");
+            }
+            // Policies are listed across all workspaces.
+            let policies = WorkspaceRetentionPolicy::list(&ctx).await?;
+            ctx.commit().await?;
+            policies
+        };
+
+        for policy in policies {
+            let mut ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            ctx.update_tenancy(Tenancy::new(policy.workspace_pk()));
+
+            match policy.prune(&ctx, now).await {
+                Ok(deleted) => {
+                    ctx.commit().await?;
+                    if deleted > 0 {
+                        debug!(
+                            workspace_pk = %policy.workspace_pk(),
+                            deleted,
+                            "pruned superseded func binding return values"
+                        );
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        error = ?err,
+                        workspace_pk = %policy.workspace_pk(),
+                        "unable to prune superseded func binding return values"
+                    );
+                    ctx.rollback().await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The internal task spawned by `start`. Every hour, it prunes every workspace.
+    #[instrument(name = "retention_scheduler.start_task", skip_all, level = "debug")]
+    async fn start_task(&self) {
+        let mut interval = time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.run().await {
+                error!("{err}");
+            }
+        }
+    }
+}
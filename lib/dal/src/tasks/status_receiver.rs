@@ -17,7 +17,7 @@ use tokio::sync::broadcast;
 use crate::{
     AttributeValue, AttributeValueError, AttributeValueId, Component, ComponentId, DalContext,
     DalContextBuilder, ServicesContext, StandardModel, StandardModelError, Tenancy,
-    TransactionsError, Visibility, WsEvent,
+    TransactionsError, Visibility, WsEvent, WsEventError,
 };
 
 pub mod client;
@@ -45,6 +45,8 @@ pub enum StatusReceiverError {
     Subscriber(#[from] SubscriberError),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    WsEvent(#[from] WsEventError),
 }
 
 pub type StatusReceiverResult<T> = Result<T, StatusReceiverError>;
@@ -179,8 +181,11 @@ impl StatusReceiver {
 
         // Send events according to every value in the dependency graph.
         let mut seen_code_generation_components: HashSet<ComponentId> = HashSet::new();
+        let mut seen_code_generation_values: HashSet<AttributeValueId> = HashSet::new();
         for dependent_value in flattened_dependent_graph {
-            if code_generation_attribute_values.contains(dependent_value) {
+            if code_generation_attribute_values.contains(dependent_value)
+                && seen_code_generation_values.insert(*dependent_value)
+            {
                 let attribute_value = AttributeValue::get_by_id(&ctx, dependent_value)
                     .await?
                     .ok_or(AttributeValueError::NotFound(
@@ -188,9 +193,33 @@ impl StatusReceiver {
                         *ctx.visibility(),
                     ))?;
                 let component_id = attribute_value.context.component_id();
-                if component_id != ComponentId::NONE
-                    && !seen_code_generation_components.contains(&component_id)
-                {
+                if component_id == ComponentId::NONE {
+                    continue;
+                }
+
+                // Every code generation of the component produces its own artifact, keyed by the
+                // name of its func.
+                if let Some(func_name) = attribute_value.key() {
+                    let format = attribute_value.get_value(&ctx).await?.and_then(|value| {
+                        value
+                            .get("format")
+                            .and_then(|format| format.as_str())
+                            .map(ToOwned::to_owned)
+                    });
+                    Self::publish_immediately(
+                        &ctx,
+                        WsEvent::code_artifact_generated(
+                            &ctx,
+                            component_id,
+                            func_name.to_owned(),
+                            format,
+                        )
+                        .await?,
+                    )
+                    .await?;
+                }
+
+                if !seen_code_generation_components.contains(&component_id) {
                     trace!("publishing code generated for component ({component_id}), tenancy ({:?}) and visibility ({:?})", *ctx.tenancy(), *ctx.visibility());
                     Self::publish_immediately(
                         &ctx,
@@ -221,9 +250,7 @@ impl StatusReceiver {
     /// This method requires an owned [`WsEvent`](crate::WsEvent), despite it not needing to,
     //  because [`events`](crate::WsEvent) should likely not be reused.
     async fn publish_immediately(ctx: &DalContext, ws_event: WsEvent) -> StatusReceiverResult<()> {
-        let subject = format!("si.workspace_pk.{}.event", ws_event.workspace_pk());
-        let msg_bytes = serde_json::to_vec(&ws_event)?;
-        ctx.nats_conn().publish(subject, msg_bytes.into()).await?;
+        ws_event.publish_immediately(ctx).await?;
         Ok(())
     }
 }
@@ -0,0 +1,114 @@
+//! This module contains [`WebhookDispatcher`], which is a "long-running" task that sends pending
+//! [`webhook deliveries`](crate::webhook::WebhookDelivery).
+
+use std::time::Duration;
+
+use chrono::Utc;
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, time};
+
+use crate::webhook::{Webhook, WebhookDelivery, WebhookError};
+use crate::{ServicesContext, Tenancy, TransactionsError};
+
+/// The most deliveries sent on a single tick. The rest wait for the next one.
+const DELIVERIES_PER_TICK: i64 = 100;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookDispatcherError {
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+}
+
+pub type WebhookDispatcherResult<T> = Result<T, WebhookDispatcherError>;
+
+/// The webhook dispatcher periodically sends the deliveries whose next attempt is due. Each
+/// delivery is claimed and committed before it is sent, so that several dispatchers (one per
+/// sdf) never send it at the same time, and its outcome is recorded in a transaction of its own.
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcher {
+    services_context: ServicesContext,
+}
+
+impl WebhookDispatcher {
+    pub fn new(services_context: ServicesContext) -> WebhookDispatcher {
+        WebhookDispatcher { services_context }
+    }
+
+    /// Starts the dispatcher in a spawned task that runs until a shutdown is requested.
+    pub fn start(self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    info!("Webhook Dispatcher received shutdown request, bailing out");
+                },
+                _ = self.start_task() => {}
+            }
+            info!("Webhook Dispatcher stopped");
+        });
+    }
+
+    #[instrument(name = "webhook_dispatcher.run", skip_all, level = "debug")]
+    async fn run(&self) -> WebhookDispatcherResult<()> {
+        let due = {
+            let ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            // Deliveries are listed across all workspaces.
+            let due = WebhookDelivery::list_due(&ctx, Utc::now(), DELIVERIES_PER_TICK).await?;
+            ctx.commit().await?;
+            due
+        };
+
+        for delivery in due {
+            let mut ctx = self
+                .services_context
+                .clone()
+                .into_builder(false)
+                .build_default()
+                .await?;
+            ctx.update_tenancy(Tenancy::new(delivery.workspace_pk()));
+
+            let mut delivery = match WebhookDelivery::claim(&ctx, delivery.pk(), Utc::now()).await?
+            {
+                Some(delivery) => delivery,
+                None => {
+                    ctx.rollback().await?;
+                    continue;
+                }
+            };
+            let webhook = match Webhook::get_by_pk(&ctx, delivery.webhook_pk()).await? {
+                Some(webhook) => webhook,
+                None => {
+                    ctx.rollback().await?;
+                    continue;
+                }
+            };
+            ctx.commit().await?;
+
+            delivery.deliver(&ctx, &webhook).await?;
+            ctx.commit().await?;
+        }
+
+        Ok(())
+    }
+
+    /// The internal task spawned by `start`. Every 10 seconds, it sends the deliveries that are
+    /// due.
+    #[instrument(name = "webhook_dispatcher.start_task", skip_all, level = "debug")]
+    async fn start_task(&self) {
+        let mut interval = time::interval(Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.run().await {
+                error!("{err}");
+            }
+        }
+    }
+}
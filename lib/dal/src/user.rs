@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
+use strum::{Display, EnumIter};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -14,6 +15,8 @@ use crate::{
 const USER_GET_BY_PK: &str = include_str!("queries/user/get_by_pk.sql");
 const USER_GET_BY_EMAIL_RAW: &str = include_str!("queries/user/get_by_email_raw.sql");
 const USER_LIST_FOR_WORKSPACE: &str = include_str!("queries/user/list_members_for_workspace.sql");
+const USER_GET_WORKSPACE_ROLE: &str = include_str!("queries/user/get_workspace_role.sql");
+const USER_SET_WORKSPACE_ROLE: &str = include_str!("queries/user/set_workspace_role.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -30,6 +33,8 @@ pub enum UserError {
     NotFoundInTenancy(UserPk, Tenancy),
     #[error("no workspace in tenancy")]
     NoWorkspaceInTenancy,
+    #[error("user {0} is not a member of workspace {1}")]
+    NotWorkspaceMember(UserPk, WorkspacePk),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("error serializing/deserializing json: {0}")]
@@ -42,6 +47,18 @@ pub type UserResult<T> = Result<T, UserError>;
 
 pk!(UserPk);
 
+/// The role a [`User`] holds in a [`Workspace`](crate::Workspace). Roles are used to restrict
+/// access to sensitive props (see [`PropPermission`](crate::PropPermission)).
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WorkspaceRole {
+    Admin,
+    Editor,
+    Viewer,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct User {
     pk: UserPk,
@@ -179,6 +196,51 @@ impl User {
         Ok(())
     }
 
+    /// Returns the [`WorkspaceRole`] of the given [`User`] in the given
+    /// [`Workspace`](crate::Workspace), or `None` if they are not a member.
+    pub async fn workspace_role(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+    ) -> UserResult<Option<WorkspaceRole>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(USER_GET_WORKSPACE_ROLE, &[&user_pk, &workspace_pk])
+            .await?;
+        match row {
+            Some(row) => {
+                let role: String = row.try_get("role")?;
+                Ok(Some(serde_json::from_value(serde_json::Value::String(
+                    role,
+                ))?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_workspace_role(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+        role: WorkspaceRole,
+    ) -> UserResult<()> {
+        let updated = ctx
+            .txns()
+            .await?
+            .pg()
+            .execute(
+                USER_SET_WORKSPACE_ROLE,
+                &[&user_pk, &workspace_pk, &role.to_string()],
+            )
+            .await?;
+        if updated == 0 {
+            return Err(UserError::NotWorkspaceMember(user_pk, workspace_pk));
+        }
+        Ok(())
+    }
+
     pub async fn list_members_for_workspace(
         ctx: &DalContext,
         workspace_pk: String,
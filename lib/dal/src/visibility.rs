@@ -18,7 +18,7 @@ pub enum VisibilityError {
 
 pub type VisibilityResult<T> = Result<T, VisibilityError>;
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Visibility {
     #[serde(
         rename = "visibility_change_set_pk",
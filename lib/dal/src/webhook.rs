@@ -0,0 +1,555 @@
+//! This module contains [`Webhook`], a URL of a [`Workspace`](crate::Workspace) that is sent a
+//! JSON payload whenever one of the [`events`](WebhookEventKind) it subscribes to happens, and
+//! [`WebhookDelivery`], the audit record of sending one of those payloads.
+//!
+//! Deliveries are recorded in the same transaction as the event that caused them, so an event
+//! that is rolled back is never announced. They are sent afterwards by the
+//! [`WebhookDispatcher`](crate::tasks::WebhookDispatcher), which retries failed deliveries with
+//! an exponential backoff until [`WEBHOOK_MAX_ATTEMPTS`] is reached.
+
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+use url::Url;
+
+use crate::{
+    pk, standard_model, DalContext, HistoryEvent, HistoryEventError, StandardModelError, Timestamp,
+    TransactionsError, WorkspacePk,
+};
+
+const CREATE: &str = include_str!("queries/webhook/create.sql");
+const GET_BY_PK: &str = include_str!("queries/webhook/get_by_pk.sql");
+const LIST: &str = include_str!("queries/webhook/list.sql");
+const UPDATE: &str = include_str!("queries/webhook/update.sql");
+const DELETE: &str = include_str!("queries/webhook/delete.sql");
+const ENQUEUE_DELIVERIES: &str = include_str!("queries/webhook_delivery/enqueue.sql");
+const LIST_DELIVERIES_FOR_WEBHOOK: &str =
+    include_str!("queries/webhook_delivery/list_for_webhook.sql");
+const LIST_DUE_DELIVERIES: &str = include_str!("queries/webhook_delivery/list_due.sql");
+const CLAIM_DELIVERY: &str = include_str!("queries/webhook_delivery/claim.sql");
+const RECORD_DELIVERY_ATTEMPT: &str = include_str!("queries/webhook_delivery/record_attempt.sql");
+
+/// The number of times a [`WebhookDelivery`] is attempted before it is given up on.
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 8;
+/// How long to wait before retrying a [`WebhookDelivery`] that failed once. The wait doubles
+/// with every further failure, up to [`WEBHOOK_MAX_BACKOFF_SECS`].
+pub const WEBHOOK_BASE_BACKOFF_SECS: i64 = 30;
+pub const WEBHOOK_MAX_BACKOFF_SECS: i64 = 3600;
+/// How long a receiver has to answer before the attempt counts as failed.
+const DELIVERY_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+/// How long a claimed [`WebhookDelivery`] is hidden from other dispatchers while it is sent.
+const CLAIM_LEASE_SECS: i64 = 60;
+
+/// The header carrying the [`WebhookDeliveryPk`], which receivers can use to drop the duplicate
+/// of a delivery that was retried after they had already handled it.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-SI-Webhook-Delivery";
+/// The header carrying the [`WebhookEventKind`].
+pub const WEBHOOK_EVENT_HEADER: &str = "X-SI-Webhook-Event";
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("invalid webhook url {0}: only absolute http and https urls are supported")]
+    InvalidUrl(String),
+    #[error("webhooks must subscribe to at least one event")]
+    NoEvents,
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error("webhook not found: {0}")]
+    NotFound(WebhookPk),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type WebhookResult<T> = Result<T, WebhookError>;
+
+pk!(WebhookPk);
+pk!(WebhookDeliveryPk);
+
+/// Something that happened in a [`Workspace`](crate::Workspace) that a [`Webhook`] can
+/// subscribe to.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Deserialize, Serialize, Debug, Display, EnumString, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WebhookEventKind {
+    /// An action ran to completion, successfully or not.
+    ActionCompleted,
+    /// A change set was applied to head.
+    ChangeSetApplied,
+    /// The qualifications of a component started failing.
+    QualificationFailed,
+}
+
+/// Where a [`WebhookDelivery`] is at.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Deserialize, Serialize, Debug, Display, EnumString, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WebhookDeliveryStatus {
+    /// Every attempt failed and no more will be made.
+    Failed,
+    /// Waiting for its first attempt or for a retry.
+    Pending,
+    /// The receiver answered with a success status.
+    Succeeded,
+}
+
+/// The body POSTed to a [`Webhook`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub event: WebhookEventKind,
+    pub workspace_pk: WorkspacePk,
+    pub occurred_at: DateTime<Utc>,
+    /// What happened, e.g. the change set that was applied.
+    pub data: serde_json::Value,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct Webhook {
+    pk: WebhookPk,
+    workspace_pk: WorkspacePk,
+    url: String,
+    events: Vec<WebhookEventKind>,
+    enabled: bool,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl Webhook {
+    pub fn pk(&self) -> WebhookPk {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn events(&self) -> &[WebhookEventKind] {
+        &self.events
+    }
+
+    /// Disabled webhooks are sent nothing, including the deliveries that were pending when they
+    /// were disabled (until they are enabled again).
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Create a webhook in the current [`Workspace`](crate::Workspace).
+    #[instrument(skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        url: impl AsRef<str> + std::fmt::Debug,
+        events: Vec<WebhookEventKind>,
+        enabled: bool,
+    ) -> WebhookResult<Self> {
+        let url = validate_url(url.as_ref())?;
+        let events = event_names(&events)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(CREATE, &[&workspace_pk(ctx)?, &url, &events, &enabled])
+            .await?;
+        let webhook: Self = standard_model::object_from_row(row)?;
+
+        HistoryEvent::new(
+            ctx,
+            "webhook.create",
+            "Webhook created",
+            &serde_json::json!({ "pk": webhook.pk, "url": webhook.url }),
+        )
+        .await?;
+
+        Ok(webhook)
+    }
+
+    pub async fn get_by_pk(ctx: &DalContext, pk: WebhookPk) -> WebhookResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(GET_BY_PK, &[&workspace_pk(ctx)?, &pk])
+            .await?;
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+
+    /// List every webhook of the current [`Workspace`](crate::Workspace), oldest first.
+    pub async fn list(ctx: &DalContext) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST, &[&workspace_pk(ctx)?])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Replace the url, events and enabled flag of a webhook of the current
+    /// [`Workspace`](crate::Workspace). Pending deliveries are sent to the new url.
+    #[instrument(skip(ctx))]
+    pub async fn update(
+        ctx: &DalContext,
+        pk: WebhookPk,
+        url: impl AsRef<str> + std::fmt::Debug,
+        events: Vec<WebhookEventKind>,
+        enabled: bool,
+    ) -> WebhookResult<Self> {
+        let url = validate_url(url.as_ref())?;
+        let events = event_names(&events)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(UPDATE, &[&workspace_pk(ctx)?, &pk, &url, &events, &enabled])
+            .await?
+            .ok_or(WebhookError::NotFound(pk))?;
+        Ok(standard_model::object_from_row(row)?)
+    }
+
+    /// Delete a webhook of the current [`Workspace`](crate::Workspace), along with its
+    /// deliveries.
+    #[instrument(skip(ctx))]
+    pub async fn delete(ctx: &DalContext, pk: WebhookPk) -> WebhookResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(DELETE, &[&workspace_pk(ctx)?, &pk])
+            .await?
+            .ok_or(WebhookError::NotFound(pk))?;
+        let webhook: Self = standard_model::object_from_row(row)?;
+
+        HistoryEvent::new(
+            ctx,
+            "webhook.delete",
+            "Webhook deleted",
+            &serde_json::json!({ "pk": webhook.pk, "url": webhook.url }),
+        )
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// Record a [`WebhookDelivery`] of `event` for every enabled webhook of the current
+    /// [`Workspace`](crate::Workspace) subscribing to it. Nothing is recorded for contexts
+    /// without a workspace.
+    pub async fn enqueue(
+        ctx: &DalContext,
+        event: WebhookEventKind,
+        data: serde_json::Value,
+    ) -> WebhookResult<Vec<WebhookDelivery>> {
+        let workspace_pk = match ctx.tenancy().workspace_pk() {
+            Some(workspace_pk) => workspace_pk,
+            None => return Ok(Vec::new()),
+        };
+        let payload = serde_json::to_value(WebhookPayload {
+            event,
+            workspace_pk,
+            occurred_at: Utc::now(),
+            data,
+        })?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                ENQUEUE_DELIVERIES,
+                &[&workspace_pk, &event.as_ref(), &payload],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct WebhookDelivery {
+    pk: WebhookDeliveryPk,
+    webhook_pk: WebhookPk,
+    workspace_pk: WorkspacePk,
+    event: WebhookEventKind,
+    payload: serde_json::Value,
+    status: WebhookDeliveryStatus,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    last_attempt_at: Option<DateTime<Utc>>,
+    last_response_status: Option<i32>,
+    last_error: Option<String>,
+    delivered_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl WebhookDelivery {
+    pub fn pk(&self) -> WebhookDeliveryPk {
+        self.pk
+    }
+
+    pub fn webhook_pk(&self) -> WebhookPk {
+        self.webhook_pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn event(&self) -> WebhookEventKind {
+        self.event
+    }
+
+    /// The serialized [`WebhookPayload`].
+    pub fn payload(&self) -> &serde_json::Value {
+        &self.payload
+    }
+
+    pub fn status(&self) -> WebhookDeliveryStatus {
+        self.status
+    }
+
+    pub fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    pub fn next_attempt_at(&self) -> DateTime<Utc> {
+        self.next_attempt_at
+    }
+
+    pub fn last_attempt_at(&self) -> Option<DateTime<Utc>> {
+        self.last_attempt_at
+    }
+
+    /// The HTTP status the receiver answered the last attempt with, if it answered at all.
+    pub fn last_response_status(&self) -> Option<i32> {
+        self.last_response_status
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn delivered_at(&self) -> Option<DateTime<Utc>> {
+        self.delivered_at
+    }
+
+    /// List the most recent deliveries of a webhook of the current
+    /// [`Workspace`](crate::Workspace), most recent first.
+    pub async fn list_for_webhook(
+        ctx: &DalContext,
+        webhook_pk: WebhookPk,
+        limit: i64,
+    ) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_DELIVERIES_FOR_WEBHOOK,
+                &[&workspace_pk(ctx)?, &webhook_pk, &limit],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// List up to `limit` pending deliveries of enabled webhooks, across all workspaces, whose
+    /// next attempt is due as of `now`.
+    pub async fn list_due(
+        ctx: &DalContext,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_DUE_DELIVERIES, &[&now, &limit])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Push the next attempt of a due delivery back by a lease, so that other dispatchers leave
+    /// it alone while it is being sent. Returns `None` if it is no longer due, e.g. because
+    /// another dispatcher claimed it first. The claim only holds once committed.
+    pub async fn claim(
+        ctx: &DalContext,
+        pk: WebhookDeliveryPk,
+        now: DateTime<Utc>,
+    ) -> WebhookResult<Option<Self>> {
+        let lease_until = now + Duration::seconds(CLAIM_LEASE_SECS);
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(CLAIM_DELIVERY, &[&pk, &now, &lease_until])
+            .await?;
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+
+    /// POST the payload to the url of `webhook` and record the outcome of the attempt. A failed
+    /// attempt is retried after [`webhook_backoff`], unless it was the last one.
+    #[instrument(skip_all, fields(webhook_delivery.pk = %self.pk))]
+    pub async fn deliver(&mut self, ctx: &DalContext, webhook: &Webhook) -> WebhookResult<()> {
+        let response = reqwest::Client::new()
+            .post(webhook.url())
+            .timeout(DELIVERY_TIMEOUT)
+            .header(WEBHOOK_DELIVERY_HEADER, self.pk.to_string())
+            .header(WEBHOOK_EVENT_HEADER, self.event.as_ref())
+            .json(&self.payload)
+            .send()
+            .await;
+        let (response_status, error) = match response {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16()),
+                Some(format!("receiver answered with {}", response.status())),
+            ),
+            Err(err) => (
+                err.status().map(|status| status.as_u16()),
+                Some(err.to_string()),
+            ),
+        };
+
+        self.record_attempt(ctx, Utc::now(), response_status, error)
+            .await
+    }
+
+    /// Record an attempt that ended at `attempted_at`. Without an `error`, the delivery
+    /// succeeded.
+    pub async fn record_attempt(
+        &mut self,
+        ctx: &DalContext,
+        attempted_at: DateTime<Utc>,
+        response_status: Option<u16>,
+        error: Option<String>,
+    ) -> WebhookResult<()> {
+        let attempts = self.attempts + 1;
+        let (status, next_attempt_at, delivered_at) = match error {
+            None => (
+                WebhookDeliveryStatus::Succeeded,
+                attempted_at,
+                Some(attempted_at),
+            ),
+            Some(_) if attempts >= WEBHOOK_MAX_ATTEMPTS => {
+                (WebhookDeliveryStatus::Failed, attempted_at, None)
+            }
+            Some(_) => (
+                WebhookDeliveryStatus::Pending,
+                attempted_at + webhook_backoff(attempts),
+                None,
+            ),
+        };
+        if let Some(error) = &error {
+            warn!(
+                webhook_delivery.pk = %self.pk,
+                attempts,
+                error = %error,
+                "webhook delivery attempt failed"
+            );
+        }
+
+        let response_status = response_status.map(i32::from);
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                RECORD_DELIVERY_ATTEMPT,
+                &[
+                    &self.pk,
+                    &status.as_ref(),
+                    &next_attempt_at,
+                    &attempted_at,
+                    &response_status,
+                    &error,
+                    &delivered_at,
+                ],
+            )
+            .await?;
+        *self = standard_model::object_from_row(row)?;
+        Ok(())
+    }
+}
+
+/// How long to wait before the next attempt of a [`WebhookDelivery`] that has failed `attempts`
+/// times: [`WEBHOOK_BASE_BACKOFF_SECS`], doubled for every failure after the first and capped
+/// at [`WEBHOOK_MAX_BACKOFF_SECS`].
+pub fn webhook_backoff(attempts: i32) -> Duration {
+    let doublings = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let secs = WEBHOOK_BASE_BACKOFF_SECS.saturating_mul(1 << doublings);
+    Duration::seconds(secs.min(WEBHOOK_MAX_BACKOFF_SECS))
+}
+
+fn validate_url(url: &str) -> WebhookResult<String> {
+    match Url::parse(url.trim()) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(parsed.to_string()),
+        _ => Err(WebhookError::InvalidUrl(url.to_owned())),
+    }
+}
+
+fn event_names(events: &[WebhookEventKind]) -> WebhookResult<Vec<&str>> {
+    if events.is_empty() {
+        return Err(WebhookError::NoEvents);
+    }
+    let mut names: Vec<&str> = events.iter().map(AsRef::as_ref).collect();
+    names.sort_unstable();
+    names.dedup();
+    Ok(names)
+}
+
+fn workspace_pk(ctx: &DalContext) -> WebhookResult<WorkspacePk> {
+    ctx.tenancy()
+        .workspace_pk()
+        .ok_or(WebhookError::NoWorkspaceInTenancy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(Duration::seconds(30), webhook_backoff(1));
+        assert_eq!(Duration::seconds(60), webhook_backoff(2));
+        assert_eq!(Duration::seconds(240), webhook_backoff(4));
+        assert_eq!(
+            Duration::seconds(3600),
+            webhook_backoff(WEBHOOK_MAX_ATTEMPTS)
+        );
+        assert_eq!(Duration::seconds(3600), webhook_backoff(i32::MAX));
+    }
+
+    #[test]
+    fn urls_must_be_absolute_http() {
+        assert_eq!(
+            "https://hooks.example.com/si",
+            validate_url(" https://hooks.example.com/si ").expect("valid url")
+        );
+        assert!(validate_url("ftp://example.com").is_err());
+        assert!(validate_url("/relative").is_err());
+    }
+}
@@ -10,6 +10,11 @@ use crate::{
     TransactionsError, User, UserError, UserPk,
 };
 
+pub mod digest;
+pub mod retention;
+pub mod script;
+pub mod template;
+
 const WORKSPACE_GET_BY_PK: &str = include_str!("queries/workspace/get_by_pk.sql");
 const WORKSPACE_FIND_BY_NAME: &str = include_str!("queries/workspace/find_by_name.sql");
 const WORKSPACE_LIST_FOR_USER: &str = include_str!("queries/workspace/list_for_user.sql");
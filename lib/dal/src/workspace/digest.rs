@@ -0,0 +1,322 @@
+//! This module contains [`WorkspaceDigest`], a summary of recent activity in a
+//! [`Workspace`](crate::Workspace), and [`WorkspaceDigestConfig`], which controls whether and
+//! where the digest is delivered.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    standard_model, ChangeSetPk, ComponentId, DalContext, StandardModelError, Timestamp,
+    TransactionsError, Workspace, WorkspaceError, WorkspacePk,
+};
+
+const GET_CONFIG: &str = include_str!("../queries/workspace_digest/get_config.sql");
+const UPSERT_CONFIG: &str = include_str!("../queries/workspace_digest/upsert_config.sql");
+const LIST_DUE_CONFIGS: &str = include_str!("../queries/workspace_digest/list_due_configs.sql");
+const MARK_SENT: &str = include_str!("../queries/workspace_digest/mark_sent.sql");
+const LIST_APPLIED_CHANGE_SETS: &str =
+    include_str!("../queries/workspace_digest/list_applied_change_sets.sql");
+const LIST_FAILED_QUALIFICATIONS: &str =
+    include_str!("../queries/workspace_digest/list_failed_qualifications.sql");
+const LIST_DRIFTED_RESOURCES: &str =
+    include_str!("../queries/workspace_digest/list_drifted_resources.sql");
+
+/// The label of the [`HistoryEvent`](crate::HistoryEvent) recorded when a refresh finds that a
+/// resource drifted.
+pub const RESOURCE_DRIFTED_HISTORY_EVENT_LABEL: &str = "component.resource.drifted";
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WorkspaceDigestError {
+    #[error("invalid digest window: {0} hours")]
+    InvalidWindow(i32),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error("workspace not found: {0}")]
+    WorkspaceNotFound(WorkspacePk),
+}
+
+pub type WorkspaceDigestResult<T> = Result<T, WorkspaceDigestError>;
+
+/// Per [`Workspace`](crate::Workspace) settings for the activity digest. A digest covering the
+/// last `window_hours` is sent to `webhook_url` at most once per window.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceDigestConfig {
+    workspace_pk: WorkspacePk,
+    enabled: bool,
+    window_hours: i32,
+    webhook_url: Option<String>,
+    last_sent_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl WorkspaceDigestConfig {
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn window_hours(&self) -> i32 {
+        self.window_hours
+    }
+
+    pub fn webhook_url(&self) -> Option<&str> {
+        self.webhook_url.as_deref()
+    }
+
+    pub fn last_sent_at(&self) -> Option<DateTime<Utc>> {
+        self.last_sent_at
+    }
+
+    pub async fn get(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+    ) -> WorkspaceDigestResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(GET_CONFIG, &[&workspace_pk])
+            .await?;
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+
+    /// Create or replace the digest settings for a [`Workspace`](crate::Workspace).
+    pub async fn upsert(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        enabled: bool,
+        window_hours: i32,
+        webhook_url: Option<String>,
+    ) -> WorkspaceDigestResult<Self> {
+        if window_hours <= 0 {
+            return Err(WorkspaceDigestError::InvalidWindow(window_hours));
+        }
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                UPSERT_CONFIG,
+                &[&workspace_pk, &enabled, &window_hours, &webhook_url],
+            )
+            .await?;
+        Ok(standard_model::object_from_row(row)?)
+    }
+
+    /// List the enabled configurations, across all workspaces, whose window has elapsed since
+    /// the last digest was sent as of `now`.
+    pub async fn list_due(
+        ctx: &DalContext,
+        now: DateTime<Utc>,
+    ) -> WorkspaceDigestResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_DUE_CONFIGS, &[&now])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    pub async fn mark_sent(
+        &mut self,
+        ctx: &DalContext,
+        sent_at: DateTime<Utc>,
+    ) -> WorkspaceDigestResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(MARK_SENT, &[&self.workspace_pk, &sent_at])
+            .await?;
+        self.last_sent_at = Some(sent_at);
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestAppliedChangeSet {
+    pub pk: ChangeSetPk,
+    pub name: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestFailedQualification {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub failed: i64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestDriftedResource {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub drifted_at: DateTime<Utc>,
+}
+
+/// A summary of the activity in a [`Workspace`](crate::Workspace) over a window of time. The
+/// serialized form is the webhook payload; its `text` field holds a rendered, human readable
+/// version of the digest (which chat webhooks display as-is).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDigest {
+    pub workspace_pk: WorkspacePk,
+    pub workspace_name: String,
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub applied_change_sets: Vec<DigestAppliedChangeSet>,
+    pub failed_qualifications: Vec<DigestFailedQualification>,
+    pub drifted_resources: Vec<DigestDriftedResource>,
+    pub text: String,
+}
+
+impl WorkspaceDigest {
+    /// Assemble the digest for the `window_hours` leading up to `until`. Only activity on head
+    /// is considered.
+    #[instrument(skip(ctx))]
+    pub async fn build(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        window_hours: i32,
+        until: DateTime<Utc>,
+    ) -> WorkspaceDigestResult<Self> {
+        if window_hours <= 0 {
+            return Err(WorkspaceDigestError::InvalidWindow(window_hours));
+        }
+        let workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+            .await?
+            .ok_or(WorkspaceDigestError::WorkspaceNotFound(workspace_pk))?;
+        let since = until - Duration::hours(window_hours.into());
+
+        let txns = ctx.txns().await?;
+
+        let mut applied_change_sets = Vec::new();
+        for row in txns
+            .pg()
+            .query(LIST_APPLIED_CHANGE_SETS, &[&workspace_pk, &since, &until])
+            .await?
+        {
+            applied_change_sets.push(DigestAppliedChangeSet {
+                pk: row.try_get("pk")?,
+                name: row.try_get("name")?,
+                applied_at: row.try_get("applied_at")?,
+            });
+        }
+
+        let mut failed_qualifications = Vec::new();
+        for row in txns
+            .pg()
+            .query(LIST_FAILED_QUALIFICATIONS, &[&workspace_pk, &since, &until])
+            .await?
+        {
+            failed_qualifications.push(DigestFailedQualification {
+                component_id: row.try_get("component_id")?,
+                component_name: row.try_get("component_name")?,
+                failed: row.try_get("failed")?,
+            });
+        }
+
+        let mut drifted_resources = Vec::new();
+        for row in txns
+            .pg()
+            .query(LIST_DRIFTED_RESOURCES, &[&workspace_pk, &since, &until])
+            .await?
+        {
+            drifted_resources.push(DigestDriftedResource {
+                component_id: row.try_get("component_id")?,
+                component_name: row.try_get("component_name")?,
+                drifted_at: row.try_get("drifted_at")?,
+            });
+        }
+
+        let mut digest = Self {
+            workspace_pk,
+            workspace_name: workspace.name().to_owned(),
+            since,
+            until,
+            applied_change_sets,
+            failed_qualifications,
+            drifted_resources,
+            text: String::new(),
+        };
+        digest.text = digest.render();
+        Ok(digest)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.applied_change_sets.is_empty()
+            && self.failed_qualifications.is_empty()
+            && self.drifted_resources.is_empty()
+    }
+
+    fn render(&self) -> String {
+        let mut lines = vec![format!(
+            "Activity in {} from {} to {}",
+            self.workspace_name,
+            self.since.to_rfc3339(),
+            self.until.to_rfc3339()
+        )];
+        if self.is_empty() {
+            lines.push("No change sets were applied and nothing failed or drifted.".to_owned());
+            return lines.join("\n");
+        }
+
+        lines.push(format!(
+            "Change sets applied: {}",
+            self.applied_change_sets.len()
+        ));
+        for change_set in &self.applied_change_sets {
+            lines.push(format!("  - {}", change_set.name));
+        }
+        lines.push(format!(
+            "Components failing qualifications: {}",
+            self.failed_qualifications.len()
+        ));
+        for qualification in &self.failed_qualifications {
+            lines.push(format!(
+                "  - {} ({} failed)",
+                qualification.component_name, qualification.failed
+            ));
+        }
+        lines.push(format!(
+            "Resources drifted: {}",
+            self.drifted_resources.len()
+        ));
+        for resource in &self.drifted_resources {
+            lines.push(format!("  - {}", resource.component_name));
+        }
+        lines.join("\n")
+    }
+
+    /// POST the digest as JSON to the given webhook.
+    pub async fn deliver(&self, webhook_url: &str) -> WorkspaceDigestResult<()> {
+        reqwest::Client::new()
+            .post(webhook_url)
+            .json(self)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
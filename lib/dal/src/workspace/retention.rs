@@ -0,0 +1,153 @@
+//! This module contains [`WorkspaceRetentionPolicy`], which controls how long superseded
+//! [`FuncBindingReturnValues`](crate::FuncBindingReturnValue) are kept around in a
+//! [`Workspace`](crate::Workspace) before being pruned.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{standard_model, DalContext, StandardModelError, TransactionsError, WorkspacePk};
+
+const GET_POLICY: &str = include_str!("../queries/workspace_retention/get_policy.sql");
+const LIST_POLICIES: &str = include_str!("../queries/workspace_retention/list_policies.sql");
+const UPSERT_POLICY: &str = include_str!("../queries/workspace_retention/upsert_policy.sql");
+const PRUNE_FUNC_BINDING_RETURN_VALUES: &str =
+    include_str!("../queries/workspace_retention/prune_func_binding_return_values.sql");
+
+/// How long superseded return values are kept when a workspace has no policy of its own.
+pub const DEFAULT_FUNC_BINDING_RETURN_VALUE_HISTORY_HOURS: i32 = 168;
+
+/// The maximum number of rows deleted per statement while pruning, to keep locks short.
+const PRUNE_BATCH_SIZE: i64 = 1000;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WorkspaceRetentionError {
+    #[error("invalid history window: {0} hours")]
+    InvalidHistoryWindow(i32),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type WorkspaceRetentionResult<T> = Result<T, WorkspaceRetentionError>;
+
+/// Per [`Workspace`](crate::Workspace) retention settings. A
+/// [`FuncBindingReturnValue`](crate::FuncBindingReturnValue) is superseded once nothing refers
+/// to it anymore; it is pruned once it is older than the history window. The current value of
+/// every [`AttributeValue`](crate::AttributeValue), in every change set, is always kept.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceRetentionPolicy {
+    workspace_pk: WorkspacePk,
+    func_binding_return_value_history_hours: i32,
+}
+
+impl WorkspaceRetentionPolicy {
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn func_binding_return_value_history_hours(&self) -> i32 {
+        self.func_binding_return_value_history_hours
+    }
+
+    /// Returns the policy in effect for a [`Workspace`](crate::Workspace), falling back to the
+    /// defaults when none was set. Returns `None` if the workspace does not exist.
+    pub async fn get(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+    ) -> WorkspaceRetentionResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(
+                GET_POLICY,
+                &[
+                    &workspace_pk,
+                    &DEFAULT_FUNC_BINDING_RETURN_VALUE_HISTORY_HOURS,
+                ],
+            )
+            .await?;
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+
+    /// List the policies in effect for every workspace.
+    pub async fn list(ctx: &DalContext) -> WorkspaceRetentionResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_POLICIES,
+                &[&DEFAULT_FUNC_BINDING_RETURN_VALUE_HISTORY_HOURS],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Create or replace the retention settings for a [`Workspace`](crate::Workspace).
+    pub async fn upsert(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        func_binding_return_value_history_hours: i32,
+    ) -> WorkspaceRetentionResult<Self> {
+        if func_binding_return_value_history_hours < 0 {
+            return Err(WorkspaceRetentionError::InvalidHistoryWindow(
+                func_binding_return_value_history_hours,
+            ));
+        }
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                UPSERT_POLICY,
+                &[&workspace_pk, &func_binding_return_value_history_hours],
+            )
+            .await?;
+        Ok(standard_model::object_from_row(row)?)
+    }
+
+    /// Delete the superseded return values that fell out of the history window as of `now`.
+    /// Returns the number of rows deleted.
+    pub async fn prune(
+        &self,
+        ctx: &DalContext,
+        now: DateTime<Utc>,
+    ) -> WorkspaceRetentionResult<u64> {
+        let cutoff = now - Duration::hours(self.func_binding_return_value_history_hours.into());
+        Self::prune_func_binding_return_values(ctx, self.workspace_pk, cutoff).await
+    }
+
+    /// Delete the superseded return values of a [`Workspace`](crate::Workspace) created before
+    /// `cutoff`, in batches. Returns the number of rows deleted.
+    #[instrument(skip(ctx))]
+    pub async fn prune_func_binding_return_values(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        cutoff: DateTime<Utc>,
+    ) -> WorkspaceRetentionResult<u64> {
+        let txns = ctx.txns().await?;
+        let mut deleted = 0;
+        loop {
+            let batch = txns
+                .pg()
+                .execute(
+                    PRUNE_FUNC_BINDING_RETURN_VALUES,
+                    &[&workspace_pk, &cutoff, &PRUNE_BATCH_SIZE],
+                )
+                .await?;
+            deleted += batch;
+            if batch < PRUNE_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,464 @@
+//! This module contains [`MutationScript`], a named and parameterized sequence of
+//! [`operations`](MutationScriptOperation) saved in a [`Workspace`](crate::Workspace) that can be
+//! re-run in new [`ChangeSets`](ChangeSet), e.g. "add standard ingress rules to a security group".
+//!
+//! Saving a script under an existing name creates a new version; older versions are kept and can
+//! still be run.
+
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use std::collections::{BTreeSet, HashMap};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::component::snippet::{ComponentSnippet, COMPONENT_SNIPPET_VERSION};
+use crate::workspace::template::{connect_sockets, WorkspaceTemplateError};
+use crate::{
+    pk, standard_model, ChangeSet, ChangeSetError, ChangeSetPk, Component, ComponentError,
+    ComponentId, Connection, DalContext, DiagramError, NodeError, NodeId, StandardModel,
+    StandardModelError, Timestamp, TransactionsError, Visibility, WorkspacePk,
+};
+
+const SAVE: &str = include_str!("../queries/mutation_script/save.sql");
+const GET_LATEST: &str = include_str!("../queries/mutation_script/get_latest.sql");
+const GET_VERSION: &str = include_str!("../queries/mutation_script/get_version.sql");
+const LIST_LATEST: &str = include_str!("../queries/mutation_script/list_latest.sql");
+const LIST_VERSIONS: &str = include_str!("../queries/mutation_script/list_versions.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum MutationScriptError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component {0} not found in mutation script {1}")]
+    ComponentNotFound(String, String),
+    #[error("diagram error: {0}")]
+    Diagram(#[from] DiagramError),
+    #[error("component key {0} is used more than once in mutation script {1}")]
+    DuplicateComponentKey(String, String),
+    #[error("missing argument for parameter {0} of mutation script {1}")]
+    MissingArgument(String, String),
+    #[error("node error: {0}")]
+    Node(#[from] NodeError),
+    #[error("no node found for component: {0}")]
+    NodeNotFoundForComponent(ComponentId),
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error("undeclared parameter {0} used in mutation script {1}")]
+    UndeclaredParameter(String, String),
+    #[error("unknown argument {0} for mutation script {1}")]
+    UnknownArgument(String, String),
+    #[error("workspace template error: {0}")]
+    WorkspaceTemplate(#[from] WorkspaceTemplateError),
+}
+
+pub type MutationScriptResult<T> = Result<T, MutationScriptError>;
+
+pk!(MutationScriptPk);
+
+/// A parameter of a [`MutationScript`]. Occurrences of `{{name}}` in the string values of the
+/// [`operations`](MutationScriptOperation) are replaced by the argument given when running the
+/// script, or by the `default` if none is given.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationScriptParameter {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
+}
+
+/// A single step of a [`MutationScript`]. Components are referred to either by the `key` of a
+/// [`CreateComponent`](Self::CreateComponent) operation earlier in the script or by
+/// [`ComponentId`], which is usually passed in as an argument.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MutationScriptOperation {
+    #[serde(rename_all = "camelCase")]
+    CreateComponent {
+        key: String,
+        name: String,
+        schema_name: String,
+        schema_variant_name: String,
+        /// The frame this [`Component`] is placed in, if any.
+        #[serde(default)]
+        parent: Option<String>,
+        x: f64,
+        y: f64,
+        /// Values to set under "/root/domain", in the same shape as a [`ComponentSnippet`].
+        #[serde(default = "empty_domain")]
+        domain: serde_json::Value,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetDomain {
+        component: String,
+        domain: serde_json::Value,
+    },
+    #[serde(rename_all = "camelCase")]
+    Connect {
+        from: String,
+        from_socket: String,
+        to: String,
+        to_socket: String,
+    },
+}
+
+fn empty_domain() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+/// A saved version of a named sequence of [`operations`](MutationScriptOperation).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MutationScript {
+    pk: MutationScriptPk,
+    workspace_pk: WorkspacePk,
+    name: String,
+    version: i32,
+    description: Option<String>,
+    parameters: Vec<MutationScriptParameter>,
+    operations: Vec<MutationScriptOperation>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl MutationScript {
+    pub fn pk(&self) -> MutationScriptPk {
+        self.pk
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn parameters(&self) -> &[MutationScriptParameter] {
+        &self.parameters
+    }
+
+    pub fn operations(&self) -> &[MutationScriptOperation] {
+        &self.operations
+    }
+
+    /// Save a new version of the script called `name` in the current
+    /// [`Workspace`](crate::Workspace). Every placeholder used in `operations` must be declared
+    /// in `parameters`.
+    #[instrument(skip(ctx, description, parameters, operations))]
+    pub async fn save(
+        ctx: &DalContext,
+        name: impl AsRef<str> + std::fmt::Debug,
+        description: Option<String>,
+        parameters: Vec<MutationScriptParameter>,
+        operations: Vec<MutationScriptOperation>,
+    ) -> MutationScriptResult<Self> {
+        let name = name.as_ref();
+        let declared: BTreeSet<&str> = parameters.iter().map(|p| p.name.as_str()).collect();
+        let mut used = BTreeSet::new();
+        collect_placeholders(&serde_json::to_value(&operations)?, &mut used);
+        if let Some(undeclared) = used.iter().find(|used| !declared.contains(used.as_str())) {
+            return Err(MutationScriptError::UndeclaredParameter(
+                undeclared.clone(),
+                name.to_owned(),
+            ));
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                SAVE,
+                &[
+                    &workspace_pk(ctx)?,
+                    &name,
+                    &description,
+                    &serde_json::to_value(&parameters)?,
+                    &serde_json::to_value(&operations)?,
+                ],
+            )
+            .await?;
+        Ok(standard_model::object_from_row(row)?)
+    }
+
+    /// Find the script called `name`, either a specific `version` or the latest one.
+    pub async fn get(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        version: Option<i32>,
+    ) -> MutationScriptResult<Option<Self>> {
+        let name = name.as_ref();
+        let workspace_pk = workspace_pk(ctx)?;
+        let txns = ctx.txns().await?;
+        let row = match version {
+            Some(version) => {
+                txns.pg()
+                    .query_opt(GET_VERSION, &[&workspace_pk, &name, &version])
+                    .await?
+            }
+            None => {
+                txns.pg()
+                    .query_opt(GET_LATEST, &[&workspace_pk, &name])
+                    .await?
+            }
+        };
+        Ok(standard_model::option_object_from_row(row)?)
+    }
+
+    /// List the latest version of every script in the current [`Workspace`](crate::Workspace).
+    pub async fn list(ctx: &DalContext) -> MutationScriptResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_LATEST, &[&workspace_pk(ctx)?])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// List every version of the script called `name`, oldest first.
+    pub async fn list_versions(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+    ) -> MutationScriptResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(LIST_VERSIONS, &[&workspace_pk(ctx)?, &name.as_ref()])
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
+    /// Run the [`operations`](MutationScriptOperation) of [`self`](Self) with the provided
+    /// arguments in a new [`ChangeSet`], whose pk is returned. The caller is responsible for
+    /// committing.
+    #[instrument(skip_all, fields(script = %self.name, version = self.version))]
+    pub async fn run(
+        &self,
+        ctx: &DalContext,
+        arguments: HashMap<String, serde_json::Value>,
+    ) -> MutationScriptResult<ChangeSetPk> {
+        let operations = self.operations_with_arguments(arguments)?;
+
+        let change_set = ChangeSet::new(
+            ctx,
+            format!("{} v{}", self.name, self.version),
+            self.description.as_ref(),
+        )
+        .await?;
+        let ctx = ctx.clone_with_new_visibility(Visibility::new(change_set.pk, None));
+
+        let mut node_ids: HashMap<String, NodeId> = HashMap::new();
+        for operation in operations {
+            match operation {
+                MutationScriptOperation::CreateComponent {
+                    key,
+                    name,
+                    schema_name,
+                    schema_variant_name,
+                    parent,
+                    x,
+                    y,
+                    domain,
+                } => {
+                    if node_ids.contains_key(&key) {
+                        return Err(MutationScriptError::DuplicateComponentKey(
+                            key,
+                            self.name.clone(),
+                        ));
+                    }
+                    let snippet = ComponentSnippet {
+                        version: COMPONENT_SNIPPET_VERSION,
+                        name,
+                        schema_name,
+                        schema_variant_name,
+                        domain,
+                    };
+                    let (_, mut node) = Component::import_snippet(&ctx, &snippet).await?;
+                    node.set_geometry(
+                        &ctx,
+                        x.to_string(),
+                        y.to_string(),
+                        None::<String>,
+                        None::<String>,
+                    )
+                    .await?;
+                    if let Some(parent) = parent {
+                        let parent_node_id = self.node_id(&ctx, &node_ids, &parent).await?;
+                        Connection::new_to_parent(&ctx, *node.id(), parent_node_id).await?;
+                    }
+                    node_ids.insert(key, *node.id());
+                }
+                MutationScriptOperation::SetDomain { component, domain } => {
+                    let node_id = self.node_id(&ctx, &node_ids, &component).await?;
+                    let component_id = *Component::find_for_node(&ctx, node_id)
+                        .await?
+                        .ok_or(ComponentError::NotFoundForNode(node_id))?
+                        .id();
+                    Component::apply_domain_overrides(&ctx, component_id, &domain).await?;
+                }
+                MutationScriptOperation::Connect {
+                    from,
+                    from_socket,
+                    to,
+                    to_socket,
+                } => {
+                    connect_sockets(
+                        &ctx,
+                        &from,
+                        self.node_id(&ctx, &node_ids, &from).await?,
+                        &from_socket,
+                        &to,
+                        self.node_id(&ctx, &node_ids, &to).await?,
+                        &to_socket,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        Ok(change_set.pk)
+    }
+
+    /// Substitute the arguments, or the parameter defaults, into the operations.
+    fn operations_with_arguments(
+        &self,
+        mut arguments: HashMap<String, serde_json::Value>,
+    ) -> MutationScriptResult<Vec<MutationScriptOperation>> {
+        let mut values = HashMap::new();
+        for parameter in &self.parameters {
+            let value = arguments
+                .remove(&parameter.name)
+                .or_else(|| parameter.default.clone())
+                .ok_or_else(|| {
+                    MutationScriptError::MissingArgument(parameter.name.clone(), self.name.clone())
+                })?;
+            values.insert(parameter.name.as_str(), value);
+        }
+        if let Some(unknown) = arguments.into_keys().next() {
+            return Err(MutationScriptError::UnknownArgument(
+                unknown,
+                self.name.clone(),
+            ));
+        }
+
+        let mut operations = serde_json::to_value(&self.operations)?;
+        substitute_placeholders(&mut operations, &values);
+        Ok(serde_json::from_value(operations)?)
+    }
+
+    /// Resolve a component reference: the key of a component created earlier in the script, or
+    /// the [`ComponentId`] of an existing one.
+    async fn node_id(
+        &self,
+        ctx: &DalContext,
+        node_ids: &HashMap<String, NodeId>,
+        component: &str,
+    ) -> MutationScriptResult<NodeId> {
+        if let Some(node_id) = node_ids.get(component) {
+            return Ok(*node_id);
+        }
+        let not_found =
+            || MutationScriptError::ComponentNotFound(component.to_owned(), self.name.clone());
+        let component_id: ComponentId = component.parse().map_err(|_| not_found())?;
+        let component = Component::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or_else(not_found)?;
+        let node = component
+            .node(ctx)
+            .await?
+            .pop()
+            .ok_or(MutationScriptError::NodeNotFoundForComponent(component_id))?;
+        Ok(*node.id())
+    }
+}
+
+fn workspace_pk(ctx: &DalContext) -> MutationScriptResult<WorkspacePk> {
+    ctx.tenancy()
+        .workspace_pk()
+        .ok_or(MutationScriptError::NoWorkspaceInTenancy)
+}
+
+/// Returns the parameter name if `value` is exactly one placeholder, e.g. `"{{groupId}}"`.
+fn whole_placeholder(value: &str) -> Option<&str> {
+    let name = value.strip_prefix("{{")?.strip_suffix("}}")?;
+    (!name.contains("{{") && !name.contains("}}")).then_some(name.trim())
+}
+
+fn collect_placeholders(value: &serde_json::Value, used: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::String(string) => {
+            let mut rest = string.as_str();
+            while let Some(start) = rest.find("{{") {
+                let Some(end) = rest[start..].find("}}") else {
+                    break;
+                };
+                used.insert(rest[start + 2..start + end].trim().to_owned());
+                rest = &rest[start + end + 2..];
+            }
+        }
+        serde_json::Value::Array(values) => {
+            values
+                .iter()
+                .for_each(|value| collect_placeholders(value, used));
+        }
+        serde_json::Value::Object(map) => {
+            map.values()
+                .for_each(|value| collect_placeholders(value, used));
+        }
+        _ => {}
+    }
+}
+
+/// Replace placeholders in every string of `value`. A string that is exactly one placeholder is
+/// replaced by the argument itself, so that numbers, booleans and objects keep their type.
+fn substitute_placeholders(
+    value: &mut serde_json::Value,
+    arguments: &HashMap<&str, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::String(string) => {
+            if let Some(argument) = whole_placeholder(string).and_then(|name| arguments.get(name)) {
+                *value = argument.clone();
+                return;
+            }
+            for (name, argument) in arguments {
+                let replacement = match argument {
+                    serde_json::Value::String(argument) => argument.clone(),
+                    argument => argument.to_string(),
+                };
+                *string = string
+                    .replace(&format!("{{{{{name}}}}}"), &replacement)
+                    .replace(&format!("{{{{ {name} }}}}"), &replacement);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            values
+                .iter_mut()
+                .for_each(|value| substitute_placeholders(value, arguments));
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut()
+                .for_each(|value| substitute_placeholders(value, arguments));
+        }
+        _ => {}
+    }
+}
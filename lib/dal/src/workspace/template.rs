@@ -0,0 +1,325 @@
+//! This module contains [`WorkspaceTemplate`], a named starter diagram that a new
+//! [`Workspace`](crate::Workspace) can be bootstrapped from when signing up.
+//!
+//! Bootstrapping installs the packages the template depends on and then creates its
+//! [`Components`](Component) and [`Connections`](Connection) in a new [`ChangeSet`], so that the
+//! diagram can be reviewed before it is applied.
+
+use serde::{Deserialize, Serialize};
+use si_pkg::{SiPkg, SiPkgError};
+use std::collections::HashMap;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::component::snippet::{ComponentSnippet, COMPONENT_SNIPPET_VERSION};
+use crate::edge::{EdgeCreationSource, EdgeKind};
+use crate::installed_pkg::{InstalledPkg, InstalledPkgError};
+use crate::job::definition::DependentValuesUpdate;
+use crate::pkg::{import_pkg_from_pkg, PkgError};
+use crate::socket::{SocketEdgeKind, SocketError};
+use crate::{
+    AttributeReadContext, AttributeValue, AttributeValueError, ChangeSet, ChangeSetError,
+    ChangeSetPk, Component, ComponentError, Connection, DalContext, DiagramError, InternalProvider,
+    InternalProviderError, NodeError, NodeId, Socket, StandardModel, TransactionsError, Visibility,
+    WsEvent, WsEventError,
+};
+
+/// The builtin [`WorkspaceTemplates`](WorkspaceTemplate), as JSON.
+const BUILTIN_TEMPLATES: &[&str] = &[include_str!("templates/three-tier-aws-web-app.json")];
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WorkspaceTemplateError {
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value not found for context: {0:?}")]
+    AttributeValueNotFoundForContext(AttributeReadContext),
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component key not found in template {0}: {1}")]
+    ComponentKeyNotFound(String, String),
+    #[error("diagram error: {0}")]
+    Diagram(#[from] DiagramError),
+    #[error("installed pkg error: {0}")]
+    InstalledPkg(#[from] InstalledPkgError),
+    #[error("internal provider error: {0}")]
+    InternalProvider(#[from] InternalProviderError),
+    #[error("internal provider not found for socket: {0}")]
+    InternalProviderNotFoundForSocket(String),
+    #[error("pkgs path not set, cannot install template packages")]
+    MissingPkgsPath,
+    #[error("node error: {0}")]
+    Node(#[from] NodeError),
+    #[error("pkg error: {0}")]
+    Pkg(#[from] PkgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("si pkg error: {0}")]
+    SiPkg(#[from] SiPkgError),
+    #[error("socket error: {0}")]
+    Socket(#[from] SocketError),
+    #[error("socket {1} ({2}) not found for template component {0}")]
+    SocketNotFound(String, String, SocketEdgeKind),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type WorkspaceTemplateResult<T> = Result<T, WorkspaceTemplateError>;
+
+/// A named starter diagram: the packages it needs, the [`Components`](Component) to create and
+/// the [`Connections`](Connection) between them.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTemplate {
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    /// Package file names, relative to the configured pkgs path.
+    pub packages: Vec<String>,
+    pub components: Vec<WorkspaceTemplateComponent>,
+    pub connections: Vec<WorkspaceTemplateConnection>,
+}
+
+/// A [`Component`] of a [`WorkspaceTemplate`]. The `key` is only used to refer to the
+/// [`Component`] from within the template.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTemplateComponent {
+    pub key: String,
+    pub name: String,
+    pub schema_name: String,
+    pub schema_variant_name: String,
+    /// The key of the frame this [`Component`] is placed in, if any.
+    #[serde(default)]
+    pub parent: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub width: Option<f64>,
+    #[serde(default)]
+    pub height: Option<f64>,
+    /// Values to set under "/root/domain", in the same shape as a [`ComponentSnippet`].
+    #[serde(default = "empty_domain")]
+    pub domain: serde_json::Value,
+}
+
+/// A [`Connection`] from an output socket of one [`WorkspaceTemplateComponent`] to an input
+/// socket of another, both referred to by key.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTemplateConnection {
+    pub from: String,
+    pub from_socket: String,
+    pub to: String,
+    pub to_socket: String,
+}
+
+fn empty_domain() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+impl WorkspaceTemplate {
+    /// List the builtin [`WorkspaceTemplates`](Self).
+    pub fn list() -> WorkspaceTemplateResult<Vec<Self>> {
+        let mut templates = Vec::with_capacity(BUILTIN_TEMPLATES.len());
+        for template in BUILTIN_TEMPLATES {
+            templates.push(serde_json::from_str(template)?);
+        }
+        Ok(templates)
+    }
+
+    /// Find a builtin [`WorkspaceTemplate`] by name.
+    pub fn find_by_name(name: impl AsRef<str>) -> WorkspaceTemplateResult<Option<Self>> {
+        let name = name.as_ref();
+        Ok(Self::list()?
+            .into_iter()
+            .find(|template| template.name == name))
+    }
+
+    /// Install the packages [`self`](Self) depends on and instantiate its diagram in a new
+    /// [`ChangeSet`], whose pk is returned. The caller is responsible for committing.
+    #[instrument(skip_all, fields(template = %self.name))]
+    pub async fn bootstrap(&self, ctx: &DalContext) -> WorkspaceTemplateResult<ChangeSetPk> {
+        self.install_packages(ctx).await?;
+
+        let change_set = ChangeSet::new(ctx, &self.display_name, None).await?;
+        let ctx = ctx.clone_with_new_visibility(Visibility::new(change_set.pk, None));
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+
+        let mut node_ids: HashMap<&str, NodeId> = HashMap::new();
+        for template_component in &self.components {
+            let snippet = ComponentSnippet {
+                version: COMPONENT_SNIPPET_VERSION,
+                name: template_component.name.clone(),
+                schema_name: template_component.schema_name.clone(),
+                schema_variant_name: template_component.schema_variant_name.clone(),
+                domain: template_component.domain.clone(),
+            };
+            let (_, mut node) = Component::import_snippet(&ctx, &snippet).await?;
+            node.set_geometry(
+                &ctx,
+                template_component.x.to_string(),
+                template_component.y.to_string(),
+                template_component.width.map(|width| width.to_string()),
+                template_component.height.map(|height| height.to_string()),
+            )
+            .await?;
+            node_ids.insert(template_component.key.as_str(), *node.id());
+        }
+
+        for template_component in &self.components {
+            if let Some(parent) = &template_component.parent {
+                Connection::new_to_parent(
+                    &ctx,
+                    self.node_id(&node_ids, &template_component.key)?,
+                    self.node_id(&node_ids, parent)?,
+                )
+                .await?;
+            }
+        }
+
+        for connection in &self.connections {
+            self.connect(&ctx, &node_ids, connection).await?;
+        }
+
+        Ok(change_set.pk)
+    }
+
+    /// Import every package of [`self`](Self) that has not been installed yet.
+    async fn install_packages(&self, ctx: &DalContext) -> WorkspaceTemplateResult<()> {
+        let pkgs_path = ctx
+            .pkgs_path()
+            .ok_or(WorkspaceTemplateError::MissingPkgsPath)?;
+
+        for package in &self.packages {
+            let pkg = SiPkg::load_from_file(pkgs_path.join(package)).await?;
+            let root_hash = pkg.hash()?.to_string();
+            if InstalledPkg::find_by_hash(ctx, &root_hash).await?.is_none() {
+                info!("installing {package} for workspace template {}", self.name);
+                import_pkg_from_pkg(ctx, &pkg, None, true).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn connect(
+        &self,
+        ctx: &DalContext,
+        node_ids: &HashMap<&str, NodeId>,
+        connection: &WorkspaceTemplateConnection,
+    ) -> WorkspaceTemplateResult<()> {
+        connect_sockets(
+            ctx,
+            &connection.from,
+            self.node_id(node_ids, &connection.from)?,
+            &connection.from_socket,
+            &connection.to,
+            self.node_id(node_ids, &connection.to)?,
+            &connection.to_socket,
+        )
+        .await
+    }
+
+    fn node_id(
+        &self,
+        node_ids: &HashMap<&str, NodeId>,
+        key: &str,
+    ) -> WorkspaceTemplateResult<NodeId> {
+        node_ids.get(key).copied().ok_or_else(|| {
+            WorkspaceTemplateError::ComponentKeyNotFound(self.name.clone(), key.to_owned())
+        })
+    }
+}
+
+/// Connect the output socket named `from_socket_name` of the [`Node`](crate::Node)
+/// `from_node_id` to the input socket named `to_socket_name` of `to_node_id`, and pull the value
+/// through the new [`Connection`] right away. `from` and `to` only label the nodes in errors.
+pub(crate) async fn connect_sockets(
+    ctx: &DalContext,
+    from: &str,
+    from_node_id: NodeId,
+    from_socket_name: &str,
+    to: &str,
+    to_node_id: NodeId,
+    to_socket_name: &str,
+) -> WorkspaceTemplateResult<()> {
+    let from_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        from_socket_name,
+        SocketEdgeKind::ConfigurationOutput,
+        from_node_id,
+    )
+    .await?
+    .ok_or_else(|| {
+        WorkspaceTemplateError::SocketNotFound(
+            from.to_owned(),
+            from_socket_name.to_owned(),
+            SocketEdgeKind::ConfigurationOutput,
+        )
+    })?;
+    let to_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        to_socket_name,
+        SocketEdgeKind::ConfigurationInput,
+        to_node_id,
+    )
+    .await?
+    .ok_or_else(|| {
+        WorkspaceTemplateError::SocketNotFound(
+            to.to_owned(),
+            to_socket_name.to_owned(),
+            SocketEdgeKind::ConfigurationInput,
+        )
+    })?;
+
+    Connection::new(
+        ctx,
+        from_node_id,
+        *from_socket.id(),
+        to_node_id,
+        *to_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Import,
+    )
+    .await?;
+
+    // Pull the value through the new connection right away, like when a user draws it.
+    let to_component = Component::find_for_node(ctx, to_node_id)
+        .await?
+        .ok_or(ComponentError::NotFoundForNode(to_node_id))?;
+    let to_internal_provider = InternalProvider::find_explicit_for_socket(ctx, *to_socket.id())
+        .await?
+        .ok_or_else(|| {
+            WorkspaceTemplateError::InternalProviderNotFoundForSocket(to_socket_name.to_owned())
+        })?;
+    let to_attribute_value_context = AttributeReadContext {
+        internal_provider_id: Some(*to_internal_provider.id()),
+        component_id: Some(*to_component.id()),
+        ..Default::default()
+    };
+    let mut to_attribute_value = AttributeValue::find_for_context(ctx, to_attribute_value_context)
+        .await?
+        .ok_or(WorkspaceTemplateError::AttributeValueNotFoundForContext(
+            to_attribute_value_context,
+        ))?;
+    to_attribute_value
+        .update_from_prototype_function(ctx)
+        .await?;
+
+    ctx.enqueue_job(DependentValuesUpdate::new(
+        ctx.access_builder(),
+        *ctx.visibility(),
+        vec![*to_attribute_value.id()],
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
-use si_data_pg::PgError;
+use si_data_pg::{PgError, PgPoolError};
+use strum::IntoStaticStr;
 use thiserror::Error;
 use ulid::Ulid;
 
 use crate::action::{ActionAddedPayload, ActionRemovedPayload};
+use crate::attribute::value::AttributeValueUpdatedPayload;
 use crate::change_set::{ChangeSetActorPayload, ChangeSetMergeVotePayload};
+use crate::component::owner::OwnerNotificationPayload;
+use crate::component::validation::ComponentRevalidationRequestedPayload;
 use crate::component::{ComponentCreatedPayload, ComponentUpdatedPayload};
 use crate::func::{FuncCreatedPayload, FuncDeletedPayload, FuncRevertedPayload, FuncSavedPayload};
 use crate::pkg::{
@@ -16,18 +20,27 @@ use crate::schema::variant::definition::{
     SchemaVariantDefinitionClonedPayload, SchemaVariantDefinitionCreatedPayload,
     SchemaVariantDefinitionSavedPayload,
 };
-use crate::secret::{SecretCreatedPayload, SecretUpdatedPayload};
+use crate::secret::{SecretCreatedPayload, SecretRotatedPayload, SecretUpdatedPayload};
 use crate::{
-    component::{code::CodeGeneratedPayload, resource::ResourceRefreshedPayload},
-    fix::{batch::FixBatchReturn, FixReturn},
+    component::{
+        code::{CodeArtifactGeneratedPayload, CodeGeneratedPayload},
+        resource::ResourceRefreshedPayload,
+    },
+    fix::{batch::FixBatchReturn, FixReturn, FixStarted},
     func::binding::LogLinePayload,
-    qualification::QualificationCheckPayload,
+    qualification::{QualificationCheckPayload, QualificationStatusChangedPayload},
     status::StatusMessage,
     user::{CursorPayload, OnlinePayload},
     AttributeValueId, ChangeSetPk, ComponentId, DalContext, PropId, SchemaPk, SocketId,
     StandardModelError, TransactionsError, WorkspacePk,
 };
 
+pub mod publisher;
+pub mod replay;
+
+pub use publisher::WS_EVENT_VERSION;
+pub use replay::{ReplayFinishedPayload, WsEventReplay, WS_EVENT_REPLAY_LIMIT};
+
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum WsEventError {
@@ -39,6 +52,8 @@ pub enum WsEventError {
     NoWorkspaceInTenancy,
     #[error(transparent)]
     Pg(#[from] PgError),
+    #[error(transparent)]
+    PgPool(#[from] PgPoolError),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
@@ -50,7 +65,7 @@ pub enum WsEventError {
 pub type WsEventResult<T> = Result<T, WsEventError>;
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, IntoStaticStr)]
 #[serde(tag = "kind", content = "data")]
 #[allow(clippy::large_enum_variant)]
 pub enum WsPayload {
@@ -58,6 +73,7 @@ pub enum WsPayload {
     ActionRemoved(ActionRemovedPayload),
     AsyncError(ErrorPayload),
     AsyncFinish(FinishPayload),
+    AttributeValueUpdated(AttributeValueUpdatedPayload),
     ChangeSetAbandoned(ChangeSetActorPayload),
     ChangeSetAbandonVote(ChangeSetMergeVotePayload),
     ChangeSetApplied(ChangeSetActorPayload),
@@ -70,12 +86,15 @@ pub enum WsPayload {
     ChangeSetMergeVote(ChangeSetMergeVotePayload),
     ChangeSetWritten(ChangeSetPk),
     CheckedQualifications(QualificationCheckPayload),
+    CodeArtifactGenerated(CodeArtifactGeneratedPayload),
     CodeGenerated(CodeGeneratedPayload),
     ComponentCreated(ComponentCreatedPayload),
+    ComponentRevalidationRequested(ComponentRevalidationRequestedPayload),
     ComponentUpdated(ComponentUpdatedPayload),
     Cursor(CursorPayload),
     FixBatchReturn(FixBatchReturn),
     FixReturn(FixReturn),
+    FixStarted(FixStarted),
     FuncCreated(FuncCreatedPayload),
     FuncDeleted(FuncDeletedPayload),
     FuncReverted(FuncRevertedPayload),
@@ -84,12 +103,16 @@ pub enum WsPayload {
     LogLine(LogLinePayload),
     ModuleImported(ModuleImportedPayload),
     Online(OnlinePayload),
+    OwnerNotification(OwnerNotificationPayload),
+    QualificationStatusChanged(QualificationStatusChangedPayload),
+    ReplayFinished(ReplayFinishedPayload),
     ResourceRefreshed(ResourceRefreshedPayload),
     SchemaCreated(SchemaPk),
     SchemaVariantDefinitionCloned(SchemaVariantDefinitionClonedPayload),
     SchemaVariantDefinitionCreated(SchemaVariantDefinitionCreatedPayload),
     SchemaVariantDefinitionSaved(SchemaVariantDefinitionSavedPayload),
     SecretCreated(SecretCreatedPayload),
+    SecretRotated(SecretRotatedPayload),
     SecretUpdated(SecretUpdatedPayload),
     StatusUpdate(StatusMessage),
     WorkspaceExported(WorkspaceExportPayload),
@@ -137,6 +160,10 @@ pub struct WsEvent {
     version: i64,
     workspace_pk: WorkspacePk,
     change_set_pk: ChangeSetPk,
+    /// The position of the event in the replay log of its workspace, for the events that are
+    /// [`replayable`](WsPayload::is_replayable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cursor: Option<i64>,
     payload: WsPayload,
 }
 
@@ -147,9 +174,10 @@ impl WsEvent {
         payload: WsPayload,
     ) -> WsEventResult<Self> {
         Ok(WsEvent {
-            version: 1,
+            version: WS_EVENT_VERSION,
             workspace_pk,
             change_set_pk,
+            cursor: None,
             payload,
         })
     }
@@ -168,12 +196,8 @@ impl WsEvent {
         self.workspace_pk
     }
 
-    /// Publishes the [`event`](Self) to the [`NatsTxn`](si_data_nats::NatsTxn). When the
-    /// transaction is committed, the [`event`](Self) will be published for external use.
-    pub async fn publish_on_commit(&self, ctx: &DalContext) -> WsEventResult<()> {
-        let subject = format!("si.workspace_pk.{}.event", self.workspace_pk);
-        ctx.txns().await?.nats().publish(subject, &self).await?;
-        Ok(())
+    pub fn cursor(&self) -> Option<i64> {
+        self.cursor
     }
 }
 
@@ -0,0 +1,161 @@
+//! This module contains the subjects [`WsEvents`](WsEvent) are published on and the publishing
+//! layer that puts every workspace-wide event on all of them.
+//!
+//! Every event is published twice:
+//!
+//! - on "si.workspace_pk.<workspace_pk>.event", which sdf relays to the browsers of the
+//!   workspace over their WebSocket connections
+//! - on "si.workspace.<workspace_pk>.events.<kind>", for external subscribers (e.g. automation
+//!   that would rather not hold a WebSocket connection open)
+//!
+//! The external hierarchy is the one to build on: "<kind>" is the "kind" of the
+//! [`payload`](WsPayload) (e.g. "ChangeSetApplied"), so subscribers can pick the events they
+//! care about with "si.workspace.<workspace_pk>.events.ChangeSetApplied" or take them all with
+//! "si.workspace.<workspace_pk>.events.*". Messages are the JSON serialized [`WsEvent`], whose
+//! "version" is [`WS_EVENT_VERSION`]; it is bumped whenever the shape of an event changes in a
+//! way existing subscribers would trip over.
+//!
+//! Events routed to the owner of a [`Component`](crate::Component) are private to that owner and
+//! are not bridged.
+//!
+//! [`Replayable`](WsPayload::is_replayable) events are [`recorded`](super::replay) before they
+//! are published, which is why they are always published after the transactions of the
+//! [`DalContext`] are committed rather than with them.
+
+use si_data_nats::NatsClient;
+
+use super::{WsEvent, WsEventResult, WsPayload};
+use crate::component::owner::ComponentOwner;
+use crate::DalContext;
+
+/// The version of the [`WsEvent`] messages, which is part of every message.
+pub const WS_EVENT_VERSION: i64 = 1;
+
+impl WsPayload {
+    /// The "kind" of the payload, as it is serialized (e.g. "ChangeSetApplied").
+    pub fn kind(&self) -> &'static str {
+        self.into()
+    }
+}
+
+impl WsEvent {
+    /// The subject sdf relays to the browsers of the workspace.
+    pub fn subject(&self) -> String {
+        format!("si.workspace_pk.{}.event", self.workspace_pk)
+    }
+
+    /// The subject sdf relays to the sessions of the given [`ComponentOwner`] only.
+    pub fn owner_subject(&self, owner: &ComponentOwner) -> String {
+        format!(
+            "si.workspace_pk.{}.owner.{}.event",
+            self.workspace_pk,
+            owner.subject_token()
+        )
+    }
+
+    /// The subject external subscribers listen on.
+    pub fn external_subject(&self) -> String {
+        format!(
+            "si.workspace.{}.events.{}",
+            self.workspace_pk,
+            self.payload.kind()
+        )
+    }
+
+    /// Publishes the [`event`](Self) to the [`NatsTxn`](si_data_nats::NatsTxn), on every
+    /// subject. When the transaction is committed, the [`event`](Self) will be published for
+    /// external use.
+    ///
+    /// [`Replayable`](WsPayload::is_replayable) events are handed to
+    /// [`publish_after_commit`](Self::publish_after_commit) instead, so that they are recorded
+    /// in the replay log in the order they are published.
+    pub async fn publish_on_commit(&self, ctx: &DalContext) -> WsEventResult<()> {
+        if self.payload.is_replayable() {
+            return self.clone().publish_after_commit(ctx).await;
+        }
+
+        let txns = ctx.txns().await?;
+        txns.nats().publish(self.subject(), &self).await?;
+        txns.nats().publish(self.external_subject(), &self).await?;
+        Ok(())
+    }
+
+    /// Publishes the [`event`](Self) directly to NATS once the transactions of the
+    /// [`DalContext`] are committed, through a [`commit hook`](DalContext::on_commit). Nothing
+    /// is published if they are rolled back instead.
+    pub async fn publish_after_commit(self, ctx: &DalContext) -> WsEventResult<()> {
+        ctx.on_commit(move |ctx| async move { self.publish_immediately(&ctx).await })
+            .await?;
+        Ok(())
+    }
+
+    /// Records the [`event`](Self) in the replay log if it is
+    /// [`replayable`](WsPayload::is_replayable) and publishes it on every subject right away,
+    /// regardless of any transaction.
+    ///
+    /// # Notes
+    ///
+    /// This should only be done unless the caller is _certain_ that the [`event`](Self) should
+    /// be published immediately. If unsure, use [`publish_on_commit`](Self::publish_on_commit).
+    pub async fn publish_immediately(mut self, ctx: &DalContext) -> WsEventResult<()> {
+        self.record(ctx).await?;
+        self.publish_ephemeral(ctx.nats_conn()).await
+    }
+
+    /// Publishes the [`event`](Self) on every subject right away without recording it, which is
+    /// only meant for events that are never replayed (e.g. cursors and presence).
+    pub async fn publish_ephemeral(&self, nats: &NatsClient) -> WsEventResult<()> {
+        let msg_bytes = serde_json::to_vec(&self)?;
+        nats.publish(self.subject(), msg_bytes.clone().into())
+            .await?;
+        nats.publish(self.external_subject(), msg_bytes.into())
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the event on a subject scoped to the given [`ComponentOwner`] so that only the
+    /// owner's sessions receive it, rather than broadcasting it workspace-wide.
+    pub async fn publish_to_owner_on_commit(
+        &self,
+        ctx: &DalContext,
+        owner: &ComponentOwner,
+    ) -> WsEventResult<()> {
+        ctx.txns()
+            .await?
+            .nats()
+            .publish(self.owner_subject(owner), &self)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChangeSetPk, SchemaPk, WorkspacePk};
+
+    #[test]
+    fn external_subject_ends_with_the_payload_kind() {
+        let workspace_pk = WorkspacePk::generate();
+        let event = WsEvent {
+            version: WS_EVENT_VERSION,
+            workspace_pk,
+            change_set_pk: ChangeSetPk::NONE,
+            cursor: None,
+            payload: WsPayload::SchemaCreated(SchemaPk::generate()),
+        };
+
+        assert_eq!(
+            format!("si.workspace_pk.{workspace_pk}.event"),
+            event.subject()
+        );
+        assert_eq!(
+            format!("si.workspace.{workspace_pk}.events.SchemaCreated"),
+            event.external_subject()
+        );
+        assert_eq!(
+            Some("SchemaCreated"),
+            serde_json::to_value(&event).expect("could not serialize")["payload"]["kind"].as_str()
+        );
+    }
+}
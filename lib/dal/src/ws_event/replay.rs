@@ -0,0 +1,194 @@
+//! This module contains the replay log of [`WsEvents`](WsEvent), which lets WebSocket clients
+//! that lost their connection catch up on what they missed.
+//!
+//! Every [`replayable`](WsPayload::is_replayable) event is recorded in the log of its workspace
+//! right before it is published, and gets the next cursor of that workspace. Cursors increase
+//! monotonically: the cursor of a workspace is taken and the event recorded in a single
+//! statement, so events are committed to the log in the order of their cursors. A reconnecting
+//! client hands the cursor of the last event it saw to sdf, which [`replays`](WsEvent::replay)
+//! the events recorded after it.
+//!
+//! The log only covers the recent past: events older than [`WS_EVENT_REPLAY_WINDOW_HOURS`] are
+//! [`pruned`](WsEvent::prune_replay_log).
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{WsEvent, WsEventError, WsEventResult, WsPayload, WS_EVENT_VERSION};
+use crate::{ChangeSetPk, DalContext, WorkspacePk};
+
+const RECORD: &str = include_str!("../queries/ws_event_log/record.sql");
+const LIST_SINCE: &str = include_str!("../queries/ws_event_log/list_since.sql");
+const OLDEST_CURSOR: &str = include_str!("../queries/ws_event_log/oldest_cursor.sql");
+const PRUNE: &str = include_str!("../queries/ws_event_log/prune.sql");
+
+/// The most events replayed to a single client. A client that missed more than this is better
+/// off reloading everything.
+pub const WS_EVENT_REPLAY_LIMIT: i64 = 1000;
+
+/// How long events are kept in the replay log.
+pub const WS_EVENT_REPLAY_WINDOW_HOURS: i64 = 24;
+
+impl WsPayload {
+    /// Whether the payload is recorded in the replay log. These are the events a client cannot
+    /// afford to miss: the ones telling it that a change set was written to and the progress of
+    /// the values being updated.
+    pub fn is_replayable(&self) -> bool {
+        matches!(self, Self::ChangeSetWritten(_) | Self::StatusUpdate(_))
+    }
+}
+
+/// The events recorded after a cursor, as returned by [`WsEvent::replay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WsEventReplay {
+    /// The events, ordered by cursor.
+    pub events: Vec<WsEvent>,
+    /// Whether the events are _all_ of the events the client missed. This is not the case when
+    /// some of them were pruned already, or when there were more than the limit.
+    pub complete: bool,
+}
+
+/// The payload of the event that closes a replay.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFinishedPayload {
+    /// The cursor of the last event replayed, or the cursor the client asked for if nothing was
+    /// replayed.
+    cursor: i64,
+    /// If false, the client missed events that cannot be replayed and should reload instead.
+    complete: bool,
+}
+
+impl WsEvent {
+    /// Records the [`event`](Self) in the replay log of its workspace, if it is
+    /// [`replayable`](WsPayload::is_replayable), and sets its cursor.
+    ///
+    /// The event is recorded on a connection of its own, outside of the transactions of the
+    /// [`DalContext`], since it is about to be published.
+    pub async fn record(&mut self, ctx: &DalContext) -> WsEventResult<()> {
+        if !self.payload.is_replayable() || self.cursor.is_some() {
+            return Ok(());
+        }
+
+        let row = ctx
+            .pg_pool()
+            .get()
+            .await?
+            .query_one(
+                RECORD,
+                &[
+                    &self.workspace_pk,
+                    &self.change_set_pk,
+                    &self.payload.kind(),
+                    &serde_json::to_value(&self)?,
+                ],
+            )
+            .await?;
+        self.cursor = Some(row.try_get("cursor")?);
+        Ok(())
+    }
+
+    /// Returns up to `limit` events of the workspace of the [`DalContext`] that were recorded
+    /// after the given cursor.
+    pub async fn replay(ctx: &DalContext, cursor: i64, limit: i64) -> WsEventResult<WsEventReplay> {
+        let workspace_pk = workspace_pk(ctx)?;
+        let txns = ctx.txns().await?;
+
+        // One more than the limit is fetched to find out whether the client missed more.
+        let rows = txns
+            .pg()
+            .query(LIST_SINCE, &[&workspace_pk, &cursor, &(limit + 1)])
+            .await?;
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut event: WsEvent = serde_json::from_value(row.try_get("event")?)?;
+            event.cursor = Some(row.try_get("cursor")?);
+            events.push(event);
+        }
+        let truncated = events.len() as i64 > limit;
+        events.truncate(limit.max(0) as usize);
+
+        let oldest: Option<i64> = txns
+            .pg()
+            .query_one(OLDEST_CURSOR, &[&workspace_pk])
+            .await?
+            .try_get("cursor")?;
+        let pruned = oldest.map_or(false, |oldest| oldest > cursor + 1);
+
+        Ok(WsEventReplay {
+            events,
+            complete: !truncated && !pruned,
+        })
+    }
+
+    /// Deletes the events recorded before the given time, across all workspaces, and returns how
+    /// many were deleted.
+    pub async fn prune_replay_log(ctx: &DalContext, before: DateTime<Utc>) -> WsEventResult<u64> {
+        let deleted = ctx.txns().await?.pg().execute(PRUNE, &[&before]).await?;
+        Ok(deleted)
+    }
+
+    /// The time before which events fall out of the replay log.
+    pub fn replay_window_start(now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::hours(WS_EVENT_REPLAY_WINDOW_HOURS)
+    }
+
+    /// The event sent to a client once the events it missed have been replayed.
+    pub fn replay_finished(
+        workspace_pk: WorkspacePk,
+        requested_cursor: i64,
+        replay: &WsEventReplay,
+    ) -> Self {
+        let cursor = replay
+            .events
+            .last()
+            .and_then(WsEvent::cursor)
+            .unwrap_or(requested_cursor);
+        WsEvent {
+            version: WS_EVENT_VERSION,
+            workspace_pk,
+            change_set_pk: ChangeSetPk::NONE,
+            cursor: None,
+            payload: WsPayload::ReplayFinished(ReplayFinishedPayload {
+                cursor,
+                complete: replay.complete,
+            }),
+        }
+    }
+}
+
+fn workspace_pk(ctx: &DalContext) -> WsEventResult<WorkspacePk> {
+    ctx.tenancy()
+        .workspace_pk()
+        .ok_or(WsEventError::NoWorkspaceInTenancy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_written_and_status_events_are_replayable() {
+        assert!(WsPayload::ChangeSetWritten(ChangeSetPk::generate()).is_replayable());
+        assert!(!WsPayload::ChangeSetCreated(ChangeSetPk::generate()).is_replayable());
+    }
+
+    #[test]
+    fn cursor_is_only_serialized_once_recorded() {
+        let mut event = WsEvent {
+            version: WS_EVENT_VERSION,
+            workspace_pk: WorkspacePk::generate(),
+            change_set_pk: ChangeSetPk::NONE,
+            cursor: None,
+            payload: WsPayload::ChangeSetWritten(ChangeSetPk::NONE),
+        };
+        let value = serde_json::to_value(&event).expect("could not serialize");
+        assert!(value.get("cursor").is_none());
+
+        event.cursor = Some(42);
+        let value = serde_json::to_value(&event).expect("could not serialize");
+        assert_eq!(Some(42), value["cursor"].as_i64());
+        let round_trip: WsEvent = serde_json::from_value(value).expect("could not deserialize");
+        assert_eq!(event, round_trip);
+    }
+}
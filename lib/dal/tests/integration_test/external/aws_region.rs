@@ -466,3 +466,73 @@ async fn aws_region_to_aws_ec2_intelligence_switch_component_type(ctx: &DalConte
             .expect("could not convert to value") // actual
     );
 }
+
+#[test]
+async fn aws_region_tags_flow_to_aws_ec2(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let ec2_bag = bagger.create_component(ctx, "server", "EC2 Instance").await;
+    let region_bag = bagger.create_component(ctx, "region", "Region").await;
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let region_tags_prop_id = *region_bag
+        .find_prop(ctx, &["root", "domain", "tags"])
+        .await
+        .id();
+    region_bag
+        .update_attribute_value_for_prop(
+            ctx,
+            region_tags_prop_id,
+            Some(serde_json::json![{ "Owner": "ops", "Stage": "dev" }]),
+        )
+        .await;
+
+    let region_external_provider = ExternalProvider::find_for_schema_variant_and_name(
+        ctx,
+        region_bag.schema_variant_id,
+        "Tags",
+    )
+    .await
+    .expect("cannot find external provider")
+    .expect("external provider not found");
+    let ec2_explicit_internal_provider =
+        InternalProvider::find_explicit_for_schema_variant_and_name(
+            ctx,
+            ec2_bag.schema_variant_id,
+            "Tags",
+        )
+        .await
+        .expect("cannot find explicit internal provider")
+        .expect("explicit internal provider not found");
+    Edge::connect_providers_for_components(
+        ctx,
+        *ec2_explicit_internal_provider.id(),
+        ec2_bag.component_id,
+        *region_external_provider.id(),
+        region_bag.component_id,
+    )
+    .await
+    .expect("could not connect providers");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Every tag of the frame reaches the child, which keeps its own name.
+    let ec2_properties = ec2_bag
+        .component_view_properties(ctx)
+        .await
+        .drop_qualification()
+        .to_value()
+        .expect("could not convert to value");
+    assert_eq!(
+        serde_json::json![{
+            "Name": "server",
+            "Owner": "ops",
+            "Stage": "dev",
+        }], // expected
+        ec2_properties["domain"]["tags"], // actual
+    );
+}
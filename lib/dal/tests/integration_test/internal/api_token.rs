@@ -0,0 +1,75 @@
+use chrono::{Duration, Utc};
+use dal::{ApiToken, ApiTokenError, ApiTokenScope, DalContext, HistoryActor, User, UserPk};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn create_authenticate_and_revoke(ctx: &DalContext) {
+    // Only users can create tokens.
+    let result = ApiToken::new(ctx, "ci", ApiTokenScope::ReadOnly, None).await;
+    assert!(matches!(result, Err(ApiTokenError::NoUserInContext)));
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+    let user = User::new(
+        ctx,
+        UserPk::generate(),
+        "ozzy",
+        "ozzy@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    user.associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("cannot associate workspace");
+    let user_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(user.pk()));
+
+    let (token, raw_token) = ApiToken::new(&user_ctx, "ci", ApiTokenScope::ReadWrite, None)
+        .await
+        .expect("cannot create api token");
+    assert!(ApiToken::is_api_token(&raw_token));
+    assert_eq!(workspace_pk, token.workspace_pk());
+    assert_eq!(user.pk(), token.created_by_user_pk());
+    assert!(token.scope().allows_writes());
+    assert!(token.last_used_at().is_none());
+
+    let authenticated = ApiToken::authenticate(ctx, &raw_token)
+        .await
+        .expect("cannot authenticate")
+        .expect("token not authenticated");
+    assert_eq!(token.pk(), authenticated.pk());
+    assert!(authenticated.last_used_at().is_some());
+    assert!(ApiToken::authenticate(ctx, "si_api_canoe")
+        .await
+        .expect("cannot authenticate")
+        .is_none());
+
+    let (expired, raw_expired) = ApiToken::new(
+        &user_ctx,
+        "expired",
+        ApiTokenScope::ReadOnly,
+        Some(Utc::now() - Duration::hours(1)),
+    )
+    .await
+    .expect("cannot create api token");
+    assert!(!expired.scope().allows_writes());
+    assert!(ApiToken::authenticate(ctx, &raw_expired)
+        .await
+        .expect("cannot authenticate")
+        .is_none());
+
+    let tokens = ApiToken::list(ctx).await.expect("cannot list api tokens");
+    assert_eq!(2, tokens.len());
+
+    let revoked = ApiToken::revoke(ctx, token.pk())
+        .await
+        .expect("cannot revoke api token");
+    assert!(revoked.revoked_at().is_some());
+    assert!(ApiToken::authenticate(ctx, &raw_token)
+        .await
+        .expect("cannot authenticate")
+        .is_none());
+}
@@ -5,8 +5,8 @@ use dal::{
         backend::string::FuncBackendStringArgs,
         binding::FuncBinding,
     },
-    AttributePrototypeArgument, DalContext, Func, FuncBackendKind, FuncBackendResponseType,
-    InternalProvider, PropKind, StandardModel,
+    AttributePrototypeArgument, AttributePrototypeArgumentError, ComponentId, DalContext, Func,
+    FuncBackendKind, FuncBackendResponseType, InternalProvider, PropKind, StandardModel,
 };
 use dal_test::{
     test,
@@ -120,3 +120,116 @@ async fn create_and_list_for_attribute_prototype(ctx: &DalContext) {
         *attribute_prototype.id()
     );
 }
+
+#[test]
+async fn rebind_intra_component(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+
+    // domain: Object
+    // ├─ name: String
+    // └─ other: String
+    let name_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "name",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await;
+    let other_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "other",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await;
+
+    let func = Func::new(
+        ctx,
+        "test:setString",
+        FuncBackendKind::String,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    let func_arg = FuncArgument::new(ctx, "title", FuncArgumentKind::String, None, *func.id())
+        .await
+        .expect("cannot create func argument");
+    let args = FuncBackendStringArgs::new("starfield".to_string());
+
+    let (func_binding, func_binding_return_value) = FuncBinding::create_and_execute(
+        ctx,
+        serde_json::to_value(args).expect("cannot turn args into json"),
+        *func.id(),
+        vec![],
+    )
+    .await
+    .expect("failed to execute func binding");
+
+    let context = AttributeContext::builder()
+        .set_prop_id(*name_prop.id())
+        .to_context()
+        .expect("cannot create context");
+
+    let attribute_prototype = AttributePrototype::new(
+        ctx,
+        *func.id(),
+        *func_binding.id(),
+        *func_binding_return_value.id(),
+        context,
+        None,
+        None,
+    )
+    .await
+    .expect("cannot create new attribute prototype");
+
+    let name_internal_provider =
+        InternalProvider::new_implicit(ctx, *name_prop.id(), *schema_variant.id())
+            .await
+            .expect("could not create internal provider");
+    let other_internal_provider =
+        InternalProvider::new_implicit(ctx, *other_prop.id(), *schema_variant.id())
+            .await
+            .expect("could not create internal provider");
+
+    let mut argument = AttributePrototypeArgument::new_for_intra_component(
+        ctx,
+        *attribute_prototype.id(),
+        *func_arg.id(),
+        *name_internal_provider.id(),
+    )
+    .await
+    .expect("could not create attribute prototype argument");
+
+    argument
+        .rebind(ctx, Some(*other_internal_provider.id()), None, None)
+        .await
+        .expect("could not rebind attribute prototype argument");
+
+    let found_argument = AttributePrototypeArgument::get_by_id(ctx, argument.id())
+        .await
+        .expect("could not get attribute prototype argument")
+        .expect("attribute prototype argument not found");
+    assert_eq!(
+        *other_internal_provider.id(),
+        found_argument.internal_provider_id()
+    );
+    assert_eq!(*func_arg.id(), found_argument.func_argument_id());
+
+    // An intra component argument cannot be turned into an inter component one.
+    let result = argument
+        .rebind(ctx, None, None, Some(ComponentId::generate()))
+        .await;
+    assert!(matches!(
+        result,
+        Err(AttributePrototypeArgumentError::CannotFlipSetFieldToUnset(
+            _
+        ))
+    ));
+}
@@ -1,5 +1,6 @@
 use pretty_assertions_sorted::assert_eq;
 
+use dal::attribute::value::audit::AttributeValueAudit;
 use dal::{
     attribute::context::AttributeContextBuilder, component::view::ComponentView, generate_name,
     AttributeContext, AttributeReadContext, AttributeValue, Component, DalContext, PropKind,
@@ -707,3 +708,443 @@ async fn list_payload(ctx: &DalContext) {
     assert_eq!(found_name.replace('"', ""), name);
     assert_eq!(si_name_value, domain_name_value);
 }
+
+#[test]
+async fn list_stale_after_dependent_values_update(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, _root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let (component, _) = Component::new_for_default_variant_from_schema(ctx, "stale", *schema.id())
+        .await
+        .expect("Unable to create component");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Once the dependent values update has run, nothing should lag behind its inputs.
+    let stale = AttributeValue::list_stale(ctx, Some(*component.id()))
+        .await
+        .expect("could not list stale values");
+    assert_eq!(
+        Vec::<dal::attribute::value::stale::StaleAttributeValue>::new(),
+        stale
+    );
+
+    let enqueued = AttributeValue::repair_stale(ctx, &stale)
+        .await
+        .expect("could not repair stale values");
+    assert!(enqueued.is_empty());
+}
+
+#[test]
+async fn insert_remove_and_reorder_array_elements(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let schema_variant_id = *schema_variant.id();
+
+    let array_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "array_prop",
+        PropKind::Array,
+        schema_variant_id,
+        Some(root.domain_prop_id),
+    )
+    .await;
+    let array_element = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "array_element",
+        PropKind::String,
+        schema_variant_id,
+        Some(*array_prop.id()),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "Array Component", *schema.id())
+            .await
+            .expect("Unable to create component");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let array_read_context = AttributeReadContext {
+        prop_id: Some(*array_prop.id()),
+        component_id: Some(*component.id()),
+        ..AttributeReadContext::default()
+    };
+    let array_value = AttributeValue::find_for_context(ctx, array_read_context)
+        .await
+        .expect("cannot get array AttributeValue")
+        .expect("array AttributeValue not found");
+    let update_context = AttributeContextBuilder::from(array_read_context)
+        .set_prop_id(*array_element.id())
+        .to_context()
+        .expect("cannot build write AttributeContext");
+
+    // The first element creates the array in the component context.
+    let first_id = AttributeValue::insert_for_context(
+        ctx,
+        update_context,
+        *array_value.id(),
+        Some(serde_json::json!("first")),
+        None,
+    )
+    .await
+    .expect("cannot insert new array element");
+    let array_value = AttributeValue::find_for_context(ctx, array_read_context)
+        .await
+        .expect("cannot get array AttributeValue")
+        .expect("array AttributeValue not found");
+    let array_value_id = *array_value.id();
+
+    let third_id = AttributeValue::insert_into_array(
+        ctx,
+        array_value_id,
+        Some(serde_json::json!("third")),
+        None,
+    )
+    .await
+    .expect("cannot append array element");
+    let second_id = AttributeValue::insert_into_array(
+        ctx,
+        array_value_id,
+        Some(serde_json::json!("second")),
+        Some(1),
+    )
+    .await
+    .expect("cannot insert array element");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        serde_json::json!(["first", "second", "third"]),
+        ComponentView::new(ctx, *component.id())
+            .await
+            .expect("cannot get component view")
+            .properties["domain"]["array_prop"],
+    );
+
+    AttributeValue::reorder_array(ctx, array_value_id, vec![third_id, first_id, second_id])
+        .await
+        .expect("cannot reorder array");
+    AttributeValue::reorder_array(ctx, array_value_id, vec![third_id, first_id])
+        .await
+        .expect_err("reordering with missing elements should fail");
+    AttributeValue::remove_from_array(ctx, array_value_id, first_id)
+        .await
+        .expect("cannot remove array element");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        serde_json::json!(["third", "second"]),
+        ComponentView::new(ctx, *component.id())
+            .await
+            .expect("cannot get component view")
+            .properties["domain"]["array_prop"],
+    );
+}
+
+#[test]
+async fn audit_and_revert_to(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let name_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "name_prop",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "Audited component", *schema.id())
+            .await
+            .expect("Unable to create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let read_context = AttributeReadContext {
+        prop_id: Some(*name_prop.id()),
+        component_id: Some(*component.id()),
+        ..AttributeReadContext::default()
+    };
+    let update_context = AttributeContextBuilder::from(read_context)
+        .to_context()
+        .expect("cannot build write AttributeContext");
+    let domain_value_id = *AttributeValue::find_for_context(
+        ctx,
+        AttributeReadContext {
+            prop_id: Some(root.domain_prop_id),
+            ..read_context
+        },
+    )
+    .await
+    .expect("cannot get domain AttributeValue")
+    .expect("domain AttributeValue not found")
+    .id();
+
+    for name in ["Miles", "Coltrane"] {
+        let name_value = AttributeValue::find_for_context(ctx, read_context)
+            .await
+            .expect("cannot get name AttributeValue")
+            .expect("name AttributeValue not found");
+        AttributeValue::update_for_context(
+            ctx,
+            *name_value.id(),
+            Some(domain_value_id),
+            update_context,
+            Some(serde_json::json!(name)),
+            None,
+        )
+        .await
+        .expect("cannot set value for context");
+    }
+
+    let audits =
+        AttributeValueAudit::list_for_component_and_prop(ctx, *component.id(), *name_prop.id())
+            .await
+            .expect("cannot list audits");
+    assert_eq!(2, audits.len());
+    let miles = audits.last().expect("no audits");
+    assert_eq!(
+        Some(serde_json::json!("Miles")),
+        miles.value(ctx).await.expect("cannot get audited value")
+    );
+    assert_eq!(ctx.history_actor(), &miles.actor());
+
+    AttributeValue::revert_to(ctx, miles.pk())
+        .await
+        .expect("cannot revert to audit");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        serde_json::json!("Miles"),
+        ComponentView::new(ctx, *component.id())
+            .await
+            .expect("cannot get component view")
+            .properties["domain"]["name_prop"],
+    );
+    assert_eq!(
+        3,
+        AttributeValueAudit::list_for_component_and_prop(ctx, *component.id(), *name_prop.id())
+            .await
+            .expect("cannot list audits")
+            .len()
+    );
+}
+
+#[test]
+async fn content_generation(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let name_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "name_prop",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    let other_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "other_prop",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "Generational component", *schema.id())
+            .await
+            .expect("Unable to create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let read_context = AttributeReadContext {
+        prop_id: Some(*name_prop.id()),
+        component_id: Some(*component.id()),
+        ..AttributeReadContext::default()
+    };
+    let other_read_context = AttributeReadContext {
+        prop_id: Some(*other_prop.id()),
+        ..read_context
+    };
+    let update_context = AttributeContextBuilder::from(read_context)
+        .to_context()
+        .expect("cannot build write AttributeContext");
+    let domain_value_id = *AttributeValue::find_for_context(
+        ctx,
+        AttributeReadContext {
+            prop_id: Some(root.domain_prop_id),
+            ..read_context
+        },
+    )
+    .await
+    .expect("cannot get domain AttributeValue")
+    .expect("domain AttributeValue not found")
+    .id();
+
+    let other_generation = AttributeValue::find_for_context(ctx, other_read_context)
+        .await
+        .expect("cannot get other AttributeValue")
+        .expect("other AttributeValue not found")
+        .content_generation();
+
+    let mut generations = Vec::new();
+    for name in ["Miles", "Coltrane"] {
+        let name_value = AttributeValue::find_for_context(ctx, read_context)
+            .await
+            .expect("cannot get name AttributeValue")
+            .expect("name AttributeValue not found");
+        AttributeValue::update_for_context(
+            ctx,
+            *name_value.id(),
+            Some(domain_value_id),
+            update_context,
+            Some(serde_json::json!(name)),
+            None,
+        )
+        .await
+        .expect("cannot set value for context");
+        ctx.blocking_commit()
+            .await
+            .expect("could not commit & run jobs");
+
+        generations.push(
+            AttributeValue::find_for_context(ctx, read_context)
+                .await
+                .expect("cannot get name AttributeValue")
+                .expect("name AttributeValue not found")
+                .content_generation(),
+        );
+    }
+    assert!(generations[1] > generations[0]);
+
+    // Values that weren't touched keep their generation, so their validations can be reused.
+    assert_eq!(
+        other_generation,
+        AttributeValue::find_for_context(ctx, other_read_context)
+            .await
+            .expect("cannot get other AttributeValue")
+            .expect("other AttributeValue not found")
+            .content_generation()
+    );
+}
+
+#[test]
+async fn find_many_for_contexts(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let name_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "name_prop",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "Basic component", *schema.id())
+            .await
+            .expect("Unable to create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let contexts = [
+        AttributeReadContext {
+            prop_id: Some(root.domain_prop_id),
+            component_id: Some(*component.id()),
+            ..AttributeReadContext::default()
+        },
+        AttributeReadContext {
+            prop_id: Some(*name_prop.id()),
+            component_id: Some(*component.id()),
+            ..AttributeReadContext::default()
+        },
+        AttributeReadContext {
+            prop_id: Some(*name_prop.id()),
+            ..AttributeReadContext::default()
+        },
+    ];
+
+    let found = AttributeValue::find_many_for_contexts(ctx, &contexts)
+        .await
+        .expect("cannot find AttributeValues for contexts");
+    assert_eq!(contexts.len(), found.len());
+    for context in contexts {
+        let expected = AttributeValue::find_for_context(ctx, context)
+            .await
+            .expect("cannot get AttributeValue")
+            .expect("AttributeValue not found");
+        assert_eq!(
+            expected.id(),
+            found
+                .get(&context)
+                .expect("AttributeValue not found in batch")
+                .id(),
+        );
+    }
+
+    assert!(AttributeValue::find_many_for_contexts(ctx, &[])
+        .await
+        .expect("cannot find AttributeValues for no contexts")
+        .is_empty());
+}
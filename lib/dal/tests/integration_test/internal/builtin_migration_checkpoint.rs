@@ -0,0 +1,41 @@
+use dal::builtins::checkpoint::BuiltinMigrationCheckpoint;
+use dal::{BuiltinsError, DalContext};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+async fn run_skips_completed_migrations(ctx: &DalContext) {
+    let runs = AtomicUsize::new(0);
+    let migrate = || async {
+        runs.fetch_add(1, Ordering::SeqCst);
+        Ok::<(), BuiltinsError>(())
+    };
+
+    let checkpoint = BuiltinMigrationCheckpoint::new("test_migration", "fn migrate() {}");
+    assert!(!checkpoint
+        .is_completed(ctx)
+        .await
+        .expect("could not check checkpoint"));
+
+    checkpoint
+        .run(ctx, migrate())
+        .await
+        .expect("could not run migration");
+    assert!(checkpoint
+        .is_completed(ctx)
+        .await
+        .expect("could not check checkpoint"));
+    checkpoint
+        .run(ctx, migrate())
+        .await
+        .expect("could not run migration");
+    assert_eq!(1, runs.load(Ordering::SeqCst));
+
+    // A changed migration runs again.
+    BuiltinMigrationCheckpoint::new("test_migration", "fn migrate() { canoe(); }")
+        .run(ctx, migrate())
+        .await
+        .expect("could not run migration");
+    assert_eq!(2, runs.load(Ordering::SeqCst));
+}
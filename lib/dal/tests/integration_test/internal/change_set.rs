@@ -1,4 +1,11 @@
-use dal::{ChangeSet, ChangeSetStatus, DalContext, Visibility};
+use dal::change_set::comparison::ChangeSetChangeKind;
+use dal::{
+    ChangeSet, ChangeSetStatus, Component, ComponentId, DalContext, PropKind, Schema,
+    StandardModel, Visibility,
+};
+use dal_test::test_harness::{
+    create_prop_without_ui_optionals, create_schema, create_schema_variant_with_root,
+};
 use dal_test::{helpers::create_change_set, test, DalContextHeadMutRef, DalContextHeadRef};
 
 #[test]
@@ -35,6 +42,206 @@ async fn apply(ctx: &mut DalContext) {
     ctx.update_visibility(Visibility::new_head(false));
 }
 
+#[test]
+async fn abandon(ctx: &mut DalContext) {
+    let change_set_pk = ctx.visibility().change_set_pk;
+    let schema = create_schema(ctx).await;
+    let mut change_set = ChangeSet::get_by_pk(ctx, &change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+
+    change_set
+        .abandon(ctx)
+        .await
+        .expect("cannot abandon change set");
+    assert_eq!(&change_set.status, &ChangeSetStatus::Abandoned);
+    assert!(ctx.visibility().is_head());
+
+    // The rows written in the change set are gone.
+    ctx.update_visibility(Visibility::new_change_set(change_set_pk, false));
+    assert!(Schema::get_by_id(ctx, schema.id())
+        .await
+        .expect("could not get schema")
+        .is_none());
+    ctx.update_visibility(Visibility::new_head(false));
+}
+
+async fn set_rule(
+    ctx: &mut DalContext,
+    change_set: &ChangeSet,
+    component_id: ComponentId,
+    rule: &str,
+) {
+    ctx.update_visibility(Visibility::new_change_set(change_set.pk, false));
+    Component::apply_domain_overrides(ctx, component_id, &serde_json::json!({ "rule": rule }))
+        .await
+        .expect("could not set rule");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+}
+
+#[test]
+async fn conflicts(ctx: &mut DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let rule_prop = create_prop_without_ui_optionals(
+        ctx,
+        "rule",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize schema variant");
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "firewall", *schema.id())
+            .await
+            .expect("could not create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+
+    let slow = ChangeSet::new(ctx, "slow", None)
+        .await
+        .expect("cannot create change set");
+    set_rule(ctx, &slow, *component.id(), "allow tcp/22").await;
+    assert!(slow
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts")
+        .is_empty());
+
+    ctx.update_visibility(Visibility::new_head(false));
+    let mut fast = ChangeSet::new(ctx, "fast", None)
+        .await
+        .expect("cannot create change set");
+    set_rule(ctx, &fast, *component.id(), "allow tcp/443").await;
+    fast.apply(ctx).await.expect("cannot apply change set");
+
+    let conflicts = slow.conflicts(ctx).await.expect("could not list conflicts");
+    assert_eq!(1, conflicts.len());
+    let conflict = &conflicts[0];
+    assert_eq!(*component.id(), conflict.component_id);
+    assert_eq!(*rule_prop.id(), conflict.prop_id);
+    assert_eq!("/root/domain/rule", conflict.prop_path);
+    assert_eq!(
+        Some(serde_json::json!("allow tcp/443")),
+        conflict.head_value
+    );
+    assert_eq!(
+        Some(serde_json::json!("allow tcp/22")),
+        conflict.change_set_value
+    );
+}
+
+#[test]
+async fn compare(ctx: &mut DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let rule_prop = create_prop_without_ui_optionals(
+        ctx,
+        "rule",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize schema variant");
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "firewall", *schema.id())
+            .await
+            .expect("could not create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+
+    ctx.update_visibility(Visibility::new_head(false));
+    let left = ChangeSet::new(ctx, "left", None)
+        .await
+        .expect("cannot create change set");
+    ctx.update_visibility(Visibility::new_head(false));
+    let right = ChangeSet::new(ctx, "right", None)
+        .await
+        .expect("cannot create change set");
+
+    set_rule(ctx, &left, *component.id(), "allow tcp/22").await;
+    let comparison = left
+        .compare(ctx, &right)
+        .await
+        .expect("could not compare change sets");
+    assert!(!comparison.collides());
+    let rule = comparison
+        .values
+        .iter()
+        .find(|value| value.prop_id == *rule_prop.id())
+        .expect("rule value not compared");
+    assert_eq!(Some(serde_json::json!("allow tcp/22")), rule.left_value);
+    assert!(rule.left_changed && !rule.right_changed);
+
+    set_rule(ctx, &right, *component.id(), "allow tcp/443").await;
+    let comparison = left
+        .compare(ctx, &right)
+        .await
+        .expect("could not compare change sets");
+    assert!(comparison.collides());
+    let rule = comparison
+        .values
+        .iter()
+        .find(|value| value.prop_id == *rule_prop.id())
+        .expect("rule value not compared");
+    assert_eq!("/root/domain/rule", rule.prop_path);
+    assert_eq!(Some(serde_json::json!("allow tcp/22")), rule.left_value);
+    assert_eq!(Some(serde_json::json!("allow tcp/443")), rule.right_value);
+    assert!(rule.collides);
+    assert_eq!(
+        vec![(
+            *component.id(),
+            Some(ChangeSetChangeKind::Modified),
+            Some(ChangeSetChangeKind::Modified)
+        )],
+        comparison
+            .components
+            .iter()
+            .map(|c| (c.component_id, c.left_change, c.right_change))
+            .collect::<Vec<_>>()
+    );
+    assert!(comparison.edges.is_empty());
+
+    ctx.update_visibility(Visibility::new_head(false));
+}
+
 #[test]
 async fn list_open(DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>) {
     let a_change_set = create_change_set(ctx).await;
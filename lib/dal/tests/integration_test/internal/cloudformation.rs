@@ -0,0 +1,35 @@
+use dal::cloudformation::CloudFormationExport;
+use dal::DalContext;
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn assemble_skips_components_without_resources(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let starfield = bagger.create_component(ctx, "starfield", "starfield").await;
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let export = CloudFormationExport::assemble(ctx)
+        .await
+        .expect("could not assemble cloudformation template");
+    assert_eq!(
+        serde_json::json!({
+            "AWSTemplateFormatVersion": "2010-09-09",
+            "Description": "Exported from System Initiative",
+            "Resources": {},
+        }), // expected
+        export.template, // actual
+    );
+    assert_eq!(
+        vec![starfield.component_id], // expected
+        export
+            .skipped
+            .iter()
+            .map(|skipped| skipped.component_id)
+            .collect::<Vec<_>>(), // actual
+    );
+}
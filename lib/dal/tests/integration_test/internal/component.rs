@@ -1,4 +1,4 @@
-use dal::edge::EdgeKind;
+use dal::edge::{EdgeCreationSource, EdgeKind};
 use dal::schema::variant::root_prop::SiPropChild;
 use dal::socket::SocketEdgeKind;
 use dal::{
@@ -20,9 +20,18 @@ use dal_test::{
 use pretty_assertions_sorted::assert_eq;
 use veritech_client::ResourceStatus;
 
+mod archive;
 mod code;
+mod dataset;
+mod delete;
+mod duplicate;
+mod notes;
+mod owner;
 mod qualification;
 mod resource;
+mod search;
+mod snippet;
+mod template;
 mod view;
 
 #[test]
@@ -421,6 +430,7 @@ async fn dependent_values_resource_intelligence(mut octx: DalContext) {
                 logs: Default::default(),
                 message: Default::default(),
                 last_synced: Default::default(),
+                plan: Vec::new(),
             },
         )
         .await
@@ -561,6 +571,7 @@ async fn create_delete_and_restore_components(ctx: &mut DalContext) {
         starfield_bag.node_id,
         *to_fallout_socket.id(),
         EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
     )
     .await
     .expect("could not create connection");
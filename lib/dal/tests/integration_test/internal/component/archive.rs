@@ -0,0 +1,96 @@
+use dal::edge::{EdgeCreationSource, EdgeKind};
+use dal::{
+    socket::SocketEdgeKind, Component, Connection, DalContext, Diagram, Socket, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn archive_and_unarchive(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let starfield_bag = bagger.create_component(ctx, "head", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        starfield_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        starfield_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not create connection");
+
+    let diagram = Diagram::assemble(ctx)
+        .await
+        .expect("cannot assemble diagram");
+    assert_eq!(diagram.components().len(), 2);
+    assert_eq!(diagram.edges().len(), 1);
+
+    Component::archive(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not archive component");
+
+    // The archived component and its edge leave the diagram, but the component itself stays.
+    let diagram = Diagram::assemble(ctx)
+        .await
+        .expect("cannot assemble diagram");
+    assert_eq!(diagram.components().len(), 1);
+    assert_eq!(
+        starfield_bag.component_id,
+        diagram.components()[0].component_id()
+    );
+    assert!(diagram.edges().is_empty());
+
+    let component = Component::get_by_id(ctx, &fallout_bag.component_id)
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    assert!(component.archived());
+    assert_eq!(
+        "tail",
+        component.name(ctx).await.expect("could not get name")
+    );
+    let archived: Vec<_> = Component::list_archived(ctx)
+        .await
+        .expect("could not list archived components")
+        .iter()
+        .map(|component| *component.id())
+        .collect();
+    assert_eq!(vec![fallout_bag.component_id], archived);
+
+    Component::unarchive(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not unarchive component");
+
+    let diagram = Diagram::assemble(ctx)
+        .await
+        .expect("cannot assemble diagram");
+    assert_eq!(diagram.components().len(), 2);
+    assert_eq!(diagram.edges().len(), 1);
+    assert!(Component::list_archived(ctx)
+        .await
+        .expect("could not list archived components")
+        .is_empty());
+}
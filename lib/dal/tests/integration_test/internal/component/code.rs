@@ -1,17 +1,24 @@
 use pretty_assertions_sorted::assert_eq;
 
+use dal::component::code::{CodeGenerationArtifact, CodeGenerationConnection};
+use dal::component::diff::{
+    CodeDiffLine, CodeDiffLineKind, ComponentCodeDiff, ComponentCodeGenerationDiff,
+};
 use dal::component::ComponentKind;
+use dal::edge::{EdgeCreationSource, EdgeKind};
 use dal::func::argument::{FuncArgument, FuncArgumentKind};
 use dal::schema::variant::leaves::LeafKind;
+use dal::socket::SocketEdgeKind;
 use dal::{
     attribute::context::AttributeContextBuilder,
     schema::variant::leaves::{LeafInput, LeafInputLocation},
     FuncBackendKind, FuncBackendResponseType, Prop, Schema,
 };
 use dal::{
-    AttributeReadContext, AttributeValue, CodeLanguage, Component, ComponentView, DalContext, Func,
-    PropKind, SchemaVariant, StandardModel,
+    AttributeReadContext, AttributeValue, CodeLanguage, Component, ComponentView, Connection,
+    DalContext, Func, PropKind, SchemaVariant, Socket, StandardModel,
 };
+use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::test;
 use dal_test::test_harness::{create_schema, create_schema_variant_with_root};
 
@@ -155,6 +162,45 @@ async fn add_code_generation_and_list_code_views(ctx: &DalContext) {
     assert!(code_views.is_empty());
     assert_eq!(CodeLanguage::Yaml, code_view.language);
     assert_eq!(Some("poop: canoe\n".to_string()), code_view.code);
+
+    // The component only exists in the change set, so its code diff is entirely additive.
+    let mut code_diffs = ComponentCodeDiff::for_component(ctx, *component.id())
+        .await
+        .expect("could not get code diffs for component");
+    let code_diff = code_diffs.pop().expect("code diffs are empty");
+    assert!(code_diffs.is_empty());
+    assert_eq!(CodeLanguage::Yaml, code_diff.language);
+    assert_eq!(
+        "--- head/component.yaml\n+++ change_set/component.yaml\n@@ -0,0 +1,1 @@\n+poop: canoe", // expected
+        code_diff.diff, // actual
+    );
+
+    // The structured diff resolves the same code on both sides, keyed by code generation func.
+    let code_generation_diff = ComponentCodeGenerationDiff::new(ctx, *component.id())
+        .await
+        .expect("could not get code generation diff for component");
+    assert_eq!("component", code_generation_diff.component_name);
+    let code_generation = code_generation_diff
+        .code_generations
+        .first()
+        .expect("code generations are empty");
+    assert_eq!(1, code_generation_diff.code_generations.len());
+    assert_eq!("test:codeGeneration", code_generation.func_name);
+    assert_eq!(None, code_generation.head);
+    assert_eq!(
+        Some("poop: canoe\n".to_string()),
+        code_generation.change_set
+    );
+    assert!(code_generation.changed);
+    assert_eq!(
+        vec![CodeDiffLine {
+            kind: CodeDiffLineKind::Added,
+            content: "poop: canoe".to_string(),
+            head_line: None,
+            change_set_line: Some(1),
+        }], // expected
+        code_generation.lines, // actual
+    );
 }
 
 #[test]
@@ -386,6 +432,10 @@ async fn all_code_generation_attribute_values(ctx: &DalContext) {
         .set_handler(ctx, Some("generateYAML"))
         .await
         .expect("set handler");
+    func_two
+        .set_display_name(ctx, Some("Terraform"))
+        .await
+        .expect("set display name");
     let func_two_domain_argument = FuncArgument::new(
         ctx,
         "domain",
@@ -531,6 +581,30 @@ async fn all_code_generation_attribute_values(ctx: &DalContext) {
 
     // Finally, check the results again to ensure that they didn't drift.
     check_results(ctx).await;
+
+    // Each code generation of the variant is its own artifact, named after its func.
+    let artifacts = Component::list_code_generation_artifacts(ctx, *navi_component.id())
+        .await
+        .expect("could not list code generation artifacts");
+    assert_eq!(
+        vec![
+            CodeGenerationArtifact {
+                func_name: "test:codeGenerationTwo".to_string(),
+                name: "Terraform".to_string(),
+                language: CodeLanguage::Yaml,
+                code: Some("ange1: omen\n".to_string()),
+                message: None,
+            },
+            CodeGenerationArtifact {
+                func_name: "test:codeGenerationOne".to_string(),
+                name: "test:codeGenerationOne".to_string(),
+                language: CodeLanguage::Yaml,
+                code: Some("ange1: omen\n".to_string()),
+                message: None,
+            },
+        ], // expected
+        artifacts, // actual
+    );
 }
 
 async fn check_results(ctx: &DalContext) {
@@ -588,3 +662,282 @@ async fn check_results(ctx: &DalContext) {
     assert!(found_kru_one_code_generation_one);
     assert!(found_kru_two_code_generation_one);
 }
+
+#[test]
+async fn connections_for_code_generation(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let from_fallout = bagger.create_component(ctx, "from", "fallout").await;
+    let to_starfield = bagger.create_component(ctx, "to", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        from_fallout.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        to_starfield.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+
+    Connection::new(
+        ctx,
+        from_fallout.node_id,
+        *output_socket.id(),
+        to_starfield.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not create connection");
+
+    let special_prop = from_fallout
+        .find_prop(ctx, &["root", "domain", "special"])
+        .await;
+    from_fallout
+        .update_attribute_value_for_prop(ctx, *special_prop.id(), Some(serde_json::json!["foo"]))
+        .await;
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // The head of the edge sees the tail, along with what the tail emits through the socket.
+    let connections = Component::connections_for_code_generation(ctx, to_starfield.component_id)
+        .await
+        .expect("could not list connections for code generation");
+    assert_eq!(
+        vec![CodeGenerationConnection {
+            component_id: from_fallout.component_id,
+            component_name: "from".to_string(),
+            schema_name: "fallout".to_string(),
+            socket_name: "bethesda".to_string(),
+            value: Some(serde_json::json!["foo"]),
+        }], // expected
+        connections, // actual
+    );
+
+    // The tail of the edge has no inbound connections.
+    let connections = Component::connections_for_code_generation(ctx, from_fallout.component_id)
+        .await
+        .expect("could not list connections for code generation");
+    assert!(connections.is_empty());
+}
+
+#[test]
+async fn remove_code_generation(ctx: &DalContext) {
+    let schema = create_schema(ctx).await;
+    let (mut schema_variant, _) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("unable to finalize schema variant");
+    let schema_variant_id = *schema_variant.id();
+
+    let mut func = Func::new(
+        ctx,
+        "test:codeGeneration",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::CodeGeneration,
+    )
+    .await
+    .expect("could not create func");
+    func.set_code_plaintext(
+        ctx,
+        Some("function generate(input) { return { format: \"json\", code: \"{}\" }; }"),
+    )
+    .await
+    .expect("set code");
+    func.set_handler(ctx, Some("generate"))
+        .await
+        .expect("set handler");
+
+    SchemaVariant::upsert_leaf_function(
+        ctx,
+        schema_variant_id,
+        None,
+        LeafKind::CodeGeneration,
+        &[LeafInputLocation::Domain],
+        &func,
+    )
+    .await
+    .expect("could not add code generation");
+    let leaf_funcs =
+        SchemaVariant::find_leaf_item_functions(ctx, schema_variant_id, LeafKind::CodeGeneration)
+            .await
+            .expect("could not find code generation funcs");
+    assert_eq!(
+        vec![*func.id()],
+        leaf_funcs
+            .iter()
+            .map(|(_, func)| *func.id())
+            .collect::<Vec<_>>()
+    );
+
+    SchemaVariant::remove_leaf(ctx, schema_variant_id, LeafKind::CodeGeneration, *func.id())
+        .await
+        .expect("could not remove code generation");
+    assert!(SchemaVariant::find_leaf_item_functions(
+        ctx,
+        schema_variant_id,
+        LeafKind::CodeGeneration
+    )
+    .await
+    .expect("could not find code generation funcs")
+    .is_empty());
+
+    // Removing it again fails, there's nothing left to remove.
+    assert!(SchemaVariant::remove_leaf(
+        ctx,
+        schema_variant_id,
+        LeafKind::CodeGeneration,
+        *func.id(),
+    )
+    .await
+    .is_err());
+}
+
+#[test]
+async fn code_generation_only_reruns_when_inputs_change(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let schema_variant_id = *schema_variant.id();
+
+    let poop_prop = dal_test::test_harness::create_prop_without_ui_optionals(
+        ctx,
+        "poop",
+        PropKind::String,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await;
+    let mut func = Func::new(
+        ctx,
+        "test:codeGeneration",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::CodeGeneration,
+    )
+    .await
+    .expect("could not create func");
+    let code = "function generateYAML(input) {
+      return {
+        format: \"yaml\",
+        code: Object.keys(input.domain).length > 0 ? YAML.stringify(input.domain) : \"\"
+      };
+    }";
+    func.set_code_plaintext(ctx, Some(code))
+        .await
+        .expect("set code");
+    func.set_handler(ctx, Some("generateYAML"))
+        .await
+        .expect("set handler");
+    let func_argument =
+        FuncArgument::new(ctx, "domain", FuncArgumentKind::Object, None, *func.id())
+            .await
+            .expect("could not create func argument");
+    SchemaVariant::add_leaf(
+        ctx,
+        *func.id(),
+        schema_variant_id,
+        None,
+        LeafKind::CodeGeneration,
+        vec![LeafInput {
+            location: LeafInputLocation::Domain,
+            func_argument_id: *func_argument.id(),
+        }],
+    )
+    .await
+    .expect("could not add code generation");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("unable to finalize schema variant");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (component, _) = Component::new(ctx, "component", schema_variant_id)
+        .await
+        .expect("cannot create component");
+    let component_id = *component.id();
+    let read_context = AttributeReadContext {
+        prop_id: Some(*poop_prop.id()),
+        component_id: Some(component_id),
+        ..AttributeReadContext::default()
+    };
+
+    // Sets the prop, then returns the func binding of the code generation along with its code.
+    let set_poop = |value: &'static str| async move {
+        let attribute_value = AttributeValue::find_for_context(ctx, read_context)
+            .await
+            .expect("could not perform find for context")
+            .expect("attribute value not found");
+        let parent_attribute_value = attribute_value
+            .parent_attribute_value(ctx)
+            .await
+            .expect("could not perform find parent attribute value")
+            .expect("no parent attribute value found");
+        let context = AttributeContextBuilder::from(read_context)
+            .to_context()
+            .expect("could not convert builder to attribute context");
+        AttributeValue::update_for_context(
+            ctx,
+            *attribute_value.id(),
+            Some(*parent_attribute_value.id()),
+            context,
+            Some(serde_json::json![value]),
+            None,
+        )
+        .await
+        .expect("could not perform update for context");
+        ctx.blocking_commit()
+            .await
+            .expect("could not commit & run jobs");
+
+        let code_generation_attribute_value_ids =
+            Component::all_code_generation_attribute_values_for_component(ctx, component_id)
+                .await
+                .expect("could not list code generation attribute values");
+        assert_eq!(1, code_generation_attribute_value_ids.len());
+        let code_generation_attribute_value_id = code_generation_attribute_value_ids
+            .into_iter()
+            .next()
+            .expect("no code generation attribute value");
+        let code_generation_attribute_value =
+            AttributeValue::get_by_id(ctx, &code_generation_attribute_value_id)
+                .await
+                .expect("could not get attribute value")
+                .expect("attribute value not found");
+        let code = code_generation_attribute_value
+            .get_value(ctx)
+            .await
+            .expect("could not get value")
+            .and_then(|value| value.get("code").cloned());
+        (code_generation_attribute_value.func_binding_id(), code)
+    };
+
+    let (func_binding_id, code) = set_poop("canoe").await;
+    assert_eq!(Some(serde_json::json!["poop: canoe\n"]), code);
+
+    // Setting the same value again does not change what the code generation reads.
+    let (unchanged_func_binding_id, unchanged_code) = set_poop("canoe").await;
+    assert_eq!(func_binding_id, unchanged_func_binding_id);
+    assert_eq!(code, unchanged_code);
+
+    let (changed_func_binding_id, changed_code) = set_poop("kayak").await;
+    assert_ne!(func_binding_id, changed_func_binding_id);
+    assert_eq!(Some(serde_json::json!["poop: kayak\n"]), changed_code);
+}
@@ -0,0 +1,131 @@
+use dal::component::dataset::{ComponentDataset, ComponentDatasetEntry};
+use dal::{Component, ComponentError, ComponentView, DalContext, StandardModel};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use std::collections::HashMap;
+
+use super::view::create_schema_with_string_props;
+
+#[test]
+async fn create_components_from_csv(ctx: &DalContext) {
+    let (_schema, schema_variant, _bohemian_prop, _killer_prop, _root_prop) =
+        create_schema_with_string_props(ctx).await;
+
+    let dataset = ComponentDataset::from_csv(
+        "name,song,extra\r\ncapoeira,Galileo,ignored\n\"samba, de roda\",\"\"\"Figaro\"\"\",\n\n",
+    )
+    .expect("could not parse csv");
+    assert_eq!(vec!["name", "song", "extra"], dataset.columns);
+    assert_eq!(2, dataset.rows.len());
+
+    let mapping = HashMap::from([(
+        "song".to_owned(),
+        "/root/domain/bohemian_rhapsody".to_owned(),
+    )]);
+    let (entries, problems) = dataset
+        .prepare(ctx, *schema_variant.id(), &mapping, Some("name"))
+        .await
+        .expect("could not prepare dataset");
+    assert!(problems.is_empty());
+    assert_eq!(
+        vec![
+            ComponentDatasetEntry {
+                name: Some("capoeira".to_owned()),
+                domain: serde_json::json![{ "bohemian_rhapsody": "Galileo" }],
+            },
+            ComponentDatasetEntry {
+                name: Some("samba, de roda".to_owned()),
+                domain: serde_json::json![{ "bohemian_rhapsody": "\"Figaro\"" }],
+            },
+        ], // expected
+        entries, // actual
+    );
+
+    let entry = entries.first().expect("no entries");
+    let (component, _) = Component::new(
+        ctx,
+        entry.name.as_deref().expect("no name"),
+        *schema_variant.id(),
+    )
+    .await
+    .expect("Unable to create component");
+    Component::apply_domain_overrides(ctx, *component.id(), &entry.domain)
+        .await
+        .expect("could not apply overrides");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let component_view = ComponentView::new(ctx, *component.id())
+        .await
+        .expect("cannot get component view");
+    assert_eq!(
+        serde_json::json![
+            {
+                "si": {
+                    "name": "capoeira",
+                    "type": "component",
+                    "protected": false
+                },
+                "domain": {
+                    "bohemian_rhapsody": "Galileo"
+                }
+            }
+        ], // expected
+        component_view.properties, // actual
+    );
+}
+
+#[test]
+async fn dry_run_reports_problems(ctx: &DalContext) {
+    let (_schema, schema_variant, _bohemian_prop, _killer_prop, _root_prop) =
+        create_schema_with_string_props(ctx).await;
+
+    let dataset = ComponentDataset {
+        columns: vec!["song".to_owned(), "album".to_owned()],
+        rows: vec![
+            vec![
+                serde_json::json!["Galileo"],
+                serde_json::json!["A Night at the Opera"],
+            ],
+            vec![
+                serde_json::json![{ "not": "a string" }],
+                serde_json::Value::Null,
+            ],
+            vec![serde_json::json!["Scaramouche"]],
+        ],
+    };
+    let mapping = HashMap::from([
+        (
+            "song".to_owned(),
+            "/root/domain/bohemian_rhapsody".to_owned(),
+        ),
+        ("album".to_owned(), "/root/domain/album".to_owned()),
+        ("missing".to_owned(), "/root/domain/killer_queen".to_owned()),
+    ]);
+    let (_entries, problems) = dataset
+        .prepare(ctx, *schema_variant.id(), &mapping, None)
+        .await
+        .expect("could not prepare dataset");
+
+    let mut summary: Vec<(Option<usize>, Option<&str>)> = problems
+        .iter()
+        .map(|problem| (problem.row, problem.column.as_deref()))
+        .collect();
+    summary.sort();
+    assert_eq!(
+        vec![
+            (None, Some("album")),
+            (None, Some("missing")),
+            (Some(1), Some("song")),
+            (Some(2), None),
+        ], // expected
+        summary, // actual
+    );
+
+    assert!(matches!(
+        ComponentDataset::from_csv("name\n\"unterminated"),
+        Err(ComponentError::DatasetCsvUnterminatedQuote(2))
+    ));
+}
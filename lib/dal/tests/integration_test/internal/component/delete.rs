@@ -0,0 +1,87 @@
+use dal::edge::{EdgeCreationSource, EdgeKind};
+use dal::{socket::SocketEdgeKind, Component, Connection, DalContext, Edge, Socket, StandardModel};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn delete_and_restore(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let starfield_bag = bagger.create_component(ctx, "head", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        starfield_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        starfield_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not connect");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let mut component = Component::get_by_id(ctx, &fallout_bag.component_id)
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    let confirmation = component.delete(ctx).await.expect("could not delete");
+
+    // Without a resource, there is nothing to tear down.
+    assert_eq!(fallout_bag.component_id, confirmation.component_id);
+    assert!(!confirmation.tears_down_resource());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert!(Component::get_by_id(ctx, &fallout_bag.component_id)
+        .await
+        .expect("could not get component")
+        .is_none());
+    assert!(Edge::list_for_component(ctx, starfield_bag.component_id)
+        .await
+        .expect("could not list edges")
+        .is_empty());
+
+    let restored = Component::restore(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not restore")
+        .expect("component not restored");
+    assert_eq!(fallout_bag.component_id, *restored.id());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        1,
+        Edge::list_for_component(ctx, starfield_bag.component_id)
+            .await
+            .expect("could not list edges")
+            .len()
+    );
+}
@@ -0,0 +1,150 @@
+use dal::edge::{EdgeCreationSource, EdgeKind};
+use dal::socket::SocketEdgeKind;
+use dal::{
+    AttributeContext, AttributeValue, Component, ComponentView, Connection, DalContext, Edge,
+    Socket, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+use super::view::create_schema_with_string_props;
+
+#[test]
+async fn duplicate(ctx: &DalContext) {
+    let (_schema, schema_variant, bohemian_prop, _killer_prop, root_prop) =
+        create_schema_with_string_props(ctx).await;
+    let (component, mut node) = Component::new(ctx, "capoeira", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+    node.set_geometry(ctx, "100", "200", Some("500"), Some("500"))
+        .await
+        .expect("could not set node geometry");
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+
+    let domain_context = base_attribute_context
+        .clone()
+        .set_prop_id(root_prop.domain_prop_id)
+        .to_context()
+        .expect("cannot create domain AttributeContext");
+    let domain_value = AttributeValue::find_for_context(ctx, domain_context.into())
+        .await
+        .expect("could not fetch domain AttributeValue")
+        .expect("could not find domain AttributeValue");
+
+    let bohemian_context = base_attribute_context
+        .clone()
+        .set_prop_id(*bohemian_prop.id())
+        .to_context()
+        .expect("cannot create bohemian AttributeContext");
+    let bohemian_value = AttributeValue::find_for_context(ctx, bohemian_context.into())
+        .await
+        .expect("could not retrieve bohemian AttributeValue")
+        .expect("could not find bohemian AttributeValue");
+    AttributeValue::update_for_context(
+        ctx,
+        *bohemian_value.id(),
+        Some(*domain_value.id()),
+        bohemian_context,
+        Some(serde_json::json!["Galileo"]),
+        None,
+    )
+    .await
+    .expect("could not update bohemian prop value");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (duplicate, duplicate_node) = Component::duplicate(ctx, *component.id(), 50.0, 50.0)
+        .await
+        .expect("could not duplicate component");
+    assert_ne!(component.id(), duplicate.id());
+    assert_eq!("150", duplicate_node.x());
+    assert_eq!("250", duplicate_node.y());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let component_view = ComponentView::new(ctx, *duplicate.id())
+        .await
+        .expect("cannot get component view");
+    assert_eq!(
+        serde_json::json!["capoeira - Copy"],    // expected
+        component_view.properties["si"]["name"], // actual
+    );
+    assert_eq!(
+        serde_json::json![{ "bohemian_rhapsody": "Galileo" }], // expected
+        component_view.properties["domain"],                   // actual
+    );
+}
+
+#[test]
+async fn clone_subgraph(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout = bagger.create_component(ctx, "fallout", "fallout").await;
+    let starfield = bagger.create_component(ctx, "starfield", "starfield").await;
+    let outsider = bagger.create_component(ctx, "outsider", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    for head in [&starfield, &outsider] {
+        let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+            ctx,
+            "bethesda",
+            SocketEdgeKind::ConfigurationInput,
+            head.node_id,
+        )
+        .await
+        .expect("could not perform socket find")
+        .expect("could not find socket");
+        Connection::new(
+            ctx,
+            fallout.node_id,
+            *output_socket.id(),
+            head.node_id,
+            *input_socket.id(),
+            EdgeKind::Configuration,
+            EdgeCreationSource::Manual,
+        )
+        .await
+        .expect("could not create connection");
+    }
+
+    let copies = Component::clone_subgraph(
+        ctx,
+        &[fallout.component_id, starfield.component_id],
+        50.0,
+        50.0,
+    )
+    .await
+    .expect("could not clone subgraph");
+    assert_eq!(2, copies.len());
+    let (fallout_copy, _) = copies
+        .get(&fallout.component_id)
+        .expect("fallout was not copied");
+    let (starfield_copy, _) = copies
+        .get(&starfield.component_id)
+        .expect("starfield was not copied");
+
+    // Only the edge between the two selected components is recreated, and only once.
+    let edges: Vec<Edge> = Edge::list_for_component(ctx, *fallout_copy.id())
+        .await
+        .expect("could not list edges")
+        .into_iter()
+        .filter(|edge| *edge.kind() == EdgeKind::Configuration)
+        .collect();
+    assert_eq!(1, edges.len());
+    assert_eq!(*fallout_copy.id(), edges[0].tail_component_id());
+    assert_eq!(*starfield_copy.id(), edges[0].head_component_id());
+}
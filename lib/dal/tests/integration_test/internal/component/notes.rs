@@ -0,0 +1,50 @@
+use dal::component::notes::render_notes;
+use dal::DalContext;
+use dal_test::test;
+use dal_test::test_harness::create_component_and_schema;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn set_notes(ctx: &DalContext) {
+    let component = create_component_and_schema(ctx).await;
+    assert_eq!(
+        None,
+        component.notes(ctx).await.expect("could not get notes")
+    );
+
+    let notes = "This SG is **PCI-scoped**.".to_owned();
+    component
+        .set_notes(ctx, Some(notes.clone()))
+        .await
+        .expect("could not set notes");
+    assert_eq!(
+        Some(notes),
+        component.notes(ctx).await.expect("could not get notes")
+    );
+
+    // Clearing the notes leaves them unset rather than empty.
+    component
+        .set_notes(ctx, Some("  ".to_owned()))
+        .await
+        .expect("could not clear notes");
+    assert_eq!(
+        None,
+        component.notes(ctx).await.expect("could not get notes")
+    );
+}
+
+#[test]
+async fn render(_ctx: &DalContext) {
+    assert_eq!(
+        "<p>This SG is <strong>PCI-scoped</strong>.</p>\n",
+        render_notes("This SG is **PCI-scoped**.")
+    );
+    assert_eq!(
+        "<p>Hi &lt;b onclick=&quot;alert(1)&quot;&gt;there&lt;/b&gt;</p>\n",
+        render_notes("Hi <b onclick=\"alert(1)\">there</b>")
+    );
+    assert_eq!(
+        "<p><a href=\"\">canoe</a></p>\n",
+        render_notes("[canoe](javascript:alert(1))")
+    );
+}
@@ -0,0 +1,47 @@
+use dal::component::owner::{ComponentOwner, OwnerNotificationKind};
+use dal::{Component, DalContext, StandardModel};
+use dal_test::test;
+use dal_test::test_harness::create_component_and_schema;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn set_owner(ctx: &DalContext) {
+    let mut component = create_component_and_schema(ctx).await;
+    assert!(component.owner().is_none());
+
+    // Without an owner, nothing is routed.
+    let notified = Component::notify_owner(
+        ctx,
+        *component.id(),
+        OwnerNotificationKind::QualificationFailed,
+        "poop",
+    )
+    .await
+    .expect("could not notify owner");
+    assert!(!notified);
+
+    let owner = ComponentOwner::Team {
+        name: "platform ops".to_owned(),
+    };
+    assert_eq!("team.platform_ops", owner.subject_token());
+    component
+        .set_owner(ctx, Some(owner.clone()))
+        .await
+        .expect("could not set owner");
+
+    let component = Component::get_by_id(ctx, component.id())
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    assert_eq!(Some(&owner), component.owner());
+
+    let notified = Component::notify_owner(
+        ctx,
+        *component.id(),
+        OwnerNotificationKind::ResourceDrifted,
+        "canoe",
+    )
+    .await
+    .expect("could not notify owner");
+    assert!(notified);
+}
@@ -4,7 +4,7 @@ use dal::func::argument::{FuncArgument, FuncArgumentKind};
 use dal::schema::variant::leaves::LeafKind;
 use dal::{
     attribute::context::AttributeContextBuilder,
-    qualification::QualificationSubCheckStatus,
+    qualification::{QualificationSubCheckStatus, QualificationSummaryForComponent},
     schema::variant::leaves::{LeafInput, LeafInputLocation},
     AttributeReadContext, AttributeValue, Component, ComponentView, DalContext, Func,
     FuncBackendKind, FuncBackendResponseType, PropKind, SchemaVariant, StandardModel,
@@ -181,4 +181,19 @@ async fn add_and_list_qualifications(ctx: &DalContext) {
             .status,
         QualificationSubCheckStatus::Success,
     );
+
+    // The summary reflects the result, so it can be fetched for this component alone.
+    let summary = QualificationSummaryForComponent::get_for_component(ctx, *component.id())
+        .await
+        .expect("could not get qualification summary")
+        .expect("qualification summary not found");
+    assert_eq!(
+        (1, 1, 0, 0),
+        (
+            summary.total,
+            summary.succeeded,
+            summary.warned,
+            summary.failed
+        )
+    );
 }
@@ -54,6 +54,7 @@ async fn get_resource(mut octx: DalContext) {
                 message: None,
                 logs: vec![],
                 last_synced: Default::default(),
+                plan: Vec::new(),
             },
         )
         .await
@@ -82,6 +83,7 @@ async fn get_resource(mut octx: DalContext) {
                 message: None,
                 logs: vec![],
                 last_synced: Default::default(),
+                plan: Vec::new(),
             },
         )
         .await
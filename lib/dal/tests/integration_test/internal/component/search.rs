@@ -0,0 +1,75 @@
+use dal::component::search::ComponentSearch;
+use dal::{Component, DalContext};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn search(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let vault = bagger.create_component(ctx, "vault 101", "fallout").await;
+    let megaton = bagger.create_component(ctx, "megaton", "fallout").await;
+    let ship = bagger.create_component(ctx, "frontier", "starfield").await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let found_ids = |search: ComponentSearch| async move {
+        Component::search(ctx, &search)
+            .await
+            .expect("could not search components")
+            .components
+            .into_iter()
+            .map(|found| found.component_id)
+            .collect::<Vec<_>>()
+    };
+
+    // Results are ordered by name.
+    assert_eq!(
+        vec![ship.component_id, megaton.component_id, vault.component_id],
+        found_ids(ComponentSearch::default()).await
+    );
+    assert_eq!(
+        vec![megaton.component_id, vault.component_id],
+        found_ids(ComponentSearch {
+            schema_name: Some("fallout".to_owned()),
+            ..Default::default()
+        })
+        .await
+    );
+    assert_eq!(
+        vec![vault.component_id],
+        found_ids(ComponentSearch {
+            name: Some("VAULT".to_owned()),
+            ..Default::default()
+        })
+        .await
+    );
+    assert_eq!(
+        vec![megaton.component_id],
+        found_ids(ComponentSearch {
+            prop_filters: vec!["/si/name == megaton".parse().expect("could not parse")],
+            ..Default::default()
+        })
+        .await
+    );
+
+    let page = Component::search(
+        ctx,
+        &ComponentSearch {
+            offset: 1,
+            limit: Some(1),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("could not search components");
+    assert_eq!(3, page.total);
+    assert_eq!(
+        vec![megaton.component_id],
+        page.components
+            .into_iter()
+            .map(|found| found.component_id)
+            .collect::<Vec<_>>()
+    );
+}
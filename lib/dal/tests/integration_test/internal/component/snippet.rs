@@ -0,0 +1,146 @@
+use dal::{
+    AttributeContext, AttributeValue, Component, ComponentError, ComponentView, DalContext,
+    StandardModel,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+use super::view::create_schema_with_string_props;
+
+#[test]
+async fn export_and_import_snippet(ctx: &DalContext) {
+    let (schema, schema_variant, bohemian_prop, _killer_prop, root_prop) =
+        create_schema_with_string_props(ctx).await;
+    let (component, _) = Component::new(ctx, "capoeira", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+
+    let domain_context = base_attribute_context
+        .clone()
+        .set_prop_id(root_prop.domain_prop_id)
+        .to_context()
+        .expect("cannot create domain AttributeContext");
+    let domain_value = AttributeValue::find_for_context(ctx, domain_context.into())
+        .await
+        .expect("could not fetch domain AttributeValue")
+        .expect("could not find domain AttributeValue");
+
+    let bohemian_context = base_attribute_context
+        .clone()
+        .set_prop_id(*bohemian_prop.id())
+        .to_context()
+        .expect("cannot create bohemian AttributeContext");
+    let bohemian_value = AttributeValue::find_for_context(ctx, bohemian_context.into())
+        .await
+        .expect("could not retrieve bohemian AttributeValue")
+        .expect("could not find bohemian AttributeValue");
+    AttributeValue::update_for_context(
+        ctx,
+        *bohemian_value.id(),
+        Some(*domain_value.id()),
+        bohemian_context,
+        Some(serde_json::json!["Galileo"]),
+        None,
+    )
+    .await
+    .expect("could not update bohemian prop value");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let snippet = Component::export_snippet(ctx, *component.id())
+        .await
+        .expect("could not export snippet");
+    assert_eq!(schema.name(), snippet.schema_name);
+    assert_eq!(schema_variant.name(), snippet.schema_variant_name);
+    // Only the value set on the component is exported, not the untouched "killer_queen".
+    assert_eq!(
+        serde_json::json![{ "bohemian_rhapsody": "Galileo" }], // expected
+        snippet.domain,                                        // actual
+    );
+
+    let (imported, _) = Component::import_snippet(ctx, &snippet)
+        .await
+        .expect("could not import snippet");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let component_view = ComponentView::new(ctx, *imported.id())
+        .await
+        .expect("cannot get component view");
+    assert_eq!(
+        serde_json::json![
+            {
+                "si": {
+                    "name": "capoeira",
+                    "type": "component",
+                    "protected": false
+                },
+                "domain": {
+                    "bohemian_rhapsody": "Galileo"
+                }
+            }
+        ], // expected
+        component_view.properties, // actual
+    );
+}
+
+#[test]
+async fn strict_variant_rejects_undefined_props(ctx: &DalContext) {
+    let (_schema, mut schema_variant, bohemian_prop, _killer_prop, _root_prop) =
+        create_schema_with_string_props(ctx).await;
+    let (_other_schema, _other_schema_variant, other_prop, _, _) =
+        create_schema_with_string_props(ctx).await;
+    let (component, _) = Component::new(ctx, "capoeira", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+
+    // Not strict: typos are ignored.
+    Component::apply_domain_overrides(
+        ctx,
+        *component.id(),
+        &serde_json::json![{ "bohemian_rapsody": "Galileo" }],
+    )
+    .await
+    .expect("could not apply overrides");
+    Component::ensure_prop_defined(ctx, *component.id(), *other_prop.id())
+        .await
+        .expect("props are not checked for non strict variants");
+
+    schema_variant
+        .set_strict(ctx, true)
+        .await
+        .expect("could not set strict");
+
+    let result = Component::apply_domain_overrides(
+        ctx,
+        *component.id(),
+        &serde_json::json![{ "bohemian_rhapsody": "Galileo", "bohemian_rapsody": "Galileo" }],
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(ComponentError::UndefinedPropPath(path, _)) if path == "/root/domain/bohemian_rapsody"
+    ));
+    Component::apply_domain_overrides(
+        ctx,
+        *component.id(),
+        &serde_json::json![{ "bohemian_rhapsody": "Galileo" }],
+    )
+    .await
+    .expect("could not apply overrides");
+
+    Component::ensure_prop_defined(ctx, *component.id(), *bohemian_prop.id())
+        .await
+        .expect("prop is defined on the variant");
+    assert!(matches!(
+        Component::ensure_prop_defined(ctx, *component.id(), *other_prop.id()).await,
+        Err(ComponentError::PropNotDefinedForStrictVariant(_, _))
+    ));
+}
@@ -0,0 +1,54 @@
+use dal::{Component, ComponentView, DalContext, StandardModel};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+use super::view::create_schema_with_string_props;
+
+#[test]
+async fn instantiate_template(ctx: &DalContext) {
+    let (_schema, schema_variant, _bohemian_prop, _killer_prop, _root_prop) =
+        create_schema_with_string_props(ctx).await;
+    let (template, mut node) = Component::new(ctx, "capoeira", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+    node.set_geometry(ctx, "100", "200", Some("500"), Some("500"))
+        .await
+        .expect("could not set node geometry");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (copy, copy_node, created_component_ids) = Component::instantiate_template(
+        ctx,
+        *template.id(),
+        2,
+        "web-{{index}}",
+        &serde_json::json!({ "bohemian_rhapsody": "scaramouche-{{index}}" }),
+        800.0,
+        0.0,
+    )
+    .await
+    .expect("could not instantiate template");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(vec![*copy.id()], created_component_ids);
+    assert_eq!("900", copy_node.x());
+    assert_eq!(
+        "web-2",
+        copy.name(ctx).await.expect("could not get name").as_str()
+    );
+
+    let view = ComponentView::new(ctx, *copy.id())
+        .await
+        .expect("could not get component view");
+    assert_eq!(
+        Some(&serde_json::json!("scaramouche-2")),
+        view.properties
+            .get("domain")
+            .and_then(|domain| domain.get("bohemian_rhapsody"))
+    );
+}
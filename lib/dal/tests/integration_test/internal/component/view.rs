@@ -1,8 +1,9 @@
 use pretty_assertions_sorted::assert_eq;
 
 use dal::{
-    schema::RootProp, AttributeContext, AttributeValue, Component, ComponentView, DalContext, Prop,
-    PropKind, Schema, SchemaVariant, StandardModel,
+    schema::RootProp, AttributeContext, AttributeReadContext, AttributeValue, AttributeView,
+    Component, ComponentView, DalContext, MapKeyOrder, Prop, PropKind, Schema, SchemaVariant,
+    StandardModel,
 };
 use dal_test::{
     test,
@@ -1470,3 +1471,106 @@ async fn complex_nested_array_of_objects_with_a_map(ctx: &DalContext) {
         component_view.properties, // actual
     );
 }
+
+#[test]
+async fn map_key_order(ctx: &DalContext) {
+    let (_schema, schema_variant, mut album_prop, album_item_prop, root_prop) =
+        create_simple_map(ctx).await;
+    let (component, _) = Component::new(ctx, "ordem", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+
+    let domain_context = base_attribute_context
+        .clone()
+        .set_prop_id(root_prop.domain_prop_id)
+        .to_context()
+        .expect("could not create domain AttributeContext");
+    let domain_value = AttributeValue::find_for_context(ctx, domain_context.into())
+        .await
+        .expect("could not retrieve domain AttributeValue")
+        .expect("could not find domain AttributeValue");
+
+    let album_context = base_attribute_context
+        .clone()
+        .set_prop_id(*album_prop.id())
+        .to_context()
+        .expect("could not create album AttributeContext");
+    let unset_album_value = AttributeValue::find_for_context(ctx, album_context.into())
+        .await
+        .expect("could not retrieve album AttributeValue")
+        .expect("could not find album AttributeValue");
+    let (_, album_value_id) = AttributeValue::update_for_context(
+        ctx,
+        *unset_album_value.id(),
+        Some(*domain_value.id()),
+        album_context,
+        Some(serde_json::json![{}]),
+        None,
+    )
+    .await
+    .expect("could not update album AttributeValue");
+
+    let album_item_context = base_attribute_context
+        .clone()
+        .set_prop_id(*album_item_prop.id())
+        .to_context()
+        .expect("could not create album item AttributeContext");
+    for (key, value) in [
+        ("meshuggah", "destroy erase improve"),
+        ("black_dahlia", "nocturnal"),
+        ("gojira", "from mars to sirius"),
+    ] {
+        let _ = AttributeValue::insert_for_context(
+            ctx,
+            album_item_context,
+            album_value_id,
+            Some(serde_json::json![value]),
+            Some(key.to_string()),
+        )
+        .await
+        .expect("could not insert album item");
+    }
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let album_keys = |properties: &serde_json::Value| -> Vec<String> {
+        properties["albums"]
+            .as_object()
+            .expect("albums is not an object")
+            .keys()
+            .cloned()
+            .collect()
+    };
+    let domain_view_context = AttributeReadContext {
+        prop_id: None,
+        ..AttributeReadContext::from(domain_context)
+    };
+
+    // The stored component view goes through jsonb, so we look at the assembled view instead.
+    // The std assertion is used since the sorted one would hide the order of the keys.
+    let view = AttributeView::new(ctx, domain_view_context, Some(*domain_value.id()))
+        .await
+        .expect("could not create attribute view");
+    std::assert_eq!(
+        vec!["meshuggah", "black_dahlia", "gojira"], // expected
+        album_keys(view.value()),                    // actual
+    );
+
+    album_prop
+        .set_map_key_order(ctx, MapKeyOrder::Key)
+        .await
+        .expect("could not set map key order");
+
+    let view = AttributeView::new(ctx, domain_view_context, Some(*domain_value.id()))
+        .await
+        .expect("could not create attribute view");
+    std::assert_eq!(
+        vec!["black_dahlia", "gojira", "meshuggah"], // expected
+        album_keys(view.value()),                    // actual
+    );
+}
@@ -0,0 +1,91 @@
+use dal::func::backend::js_action::ActionRunResult;
+use dal::{
+    ConfirmationPrototype, ConfirmationPrototypeContext, ConfirmationResolver, DalContext, Func,
+    FuncBackendKind, FuncBackendResponseType, RecommendedAction, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use veritech_client::ResourceStatus;
+
+#[test]
+async fn run_confirmations_for_component(mut octx: DalContext) {
+    let ctx = &mut octx;
+
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "fallout", "fallout").await;
+
+    let mut func = Func::new(
+        ctx,
+        "test:confirmation",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Json,
+    )
+    .await
+    .expect("could not create func");
+    let code = r#"function confirm(component) {
+        if (!component.resource?.payload) {
+            return { success: false, recommendedActions: ["create"], message: "missing" };
+        }
+        return { success: true, recommendedActions: [] };
+    }"#;
+    func.set_code_plaintext(ctx, Some(code))
+        .await
+        .expect("set code");
+    func.set_handler(ctx, Some("confirm"))
+        .await
+        .expect("set handler");
+
+    let prototype = ConfirmationPrototype::upsert(
+        ctx,
+        *func.id(),
+        "test:confirmation",
+        ConfirmationPrototypeContext::new_for_schema_variant(fallout_bag.schema_variant_id),
+    )
+    .await
+    .expect("could not upsert confirmation prototype");
+
+    // Without a resource, the confirmation recommends creating one.
+    let resolvers = ConfirmationResolver::run_for_component(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not run confirmations");
+    assert_eq!(1, resolvers.len());
+    let resolver = resolvers.first().expect("no resolvers found");
+    assert_eq!(*prototype.id(), resolver.confirmation_prototype_id());
+    assert!(!resolver.success());
+    assert_eq!(Some("missing"), resolver.message());
+    assert_eq!(&[RecommendedAction::Create], resolver.recommended_actions());
+
+    fallout_bag
+        .component(ctx)
+        .await
+        .set_resource_raw(
+            ctx,
+            ActionRunResult {
+                status: Some(ResourceStatus::Ok),
+                payload: Some(serde_json::json![{ "poop": true }]),
+                message: None,
+                logs: vec![],
+                last_synced: Default::default(),
+                plan: Vec::new(),
+            },
+            false,
+        )
+        .await
+        .expect("could not set resource");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Running again replaces the recorded outcome.
+    ConfirmationResolver::run_for_component(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not run confirmations");
+    let resolvers = ConfirmationResolver::list_for_component(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not list confirmation resolvers");
+    assert_eq!(1, resolvers.len());
+    let resolver = resolvers.first().expect("no resolvers found");
+    assert!(resolver.success());
+    assert!(resolver.recommended_actions().is_empty());
+}
@@ -0,0 +1,59 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use dal::{DalContext, TransactionsError};
+use dal_test::test;
+
+fn counting_hook(
+    counter: &Arc<AtomicUsize>,
+) -> impl FnOnce(DalContext) -> futures::future::Ready<Result<(), TransactionsError>> {
+    let counter = counter.clone();
+    move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        futures::future::ready(Ok(()))
+    }
+}
+
+#[test]
+async fn on_commit_runs_once_after_commit(ctx: &DalContext) {
+    let runs = Arc::new(AtomicUsize::new(0));
+    ctx.on_commit(counting_hook(&runs))
+        .await
+        .expect("could not register commit hook");
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+    ctx.blocking_commit().await.expect("could not commit");
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+    ctx.blocking_commit().await.expect("could not commit");
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+async fn on_commit_runs_hooks_registered_by_hooks(ctx: &DalContext) {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let hook = counting_hook(&runs);
+    ctx.on_commit(move |ctx| async move {
+        ctx.on_commit(hook).await?;
+        Ok::<_, TransactionsError>(())
+    })
+    .await
+    .expect("could not register commit hook");
+
+    ctx.blocking_commit().await.expect("could not commit");
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+async fn on_commit_drops_hooks_on_rollback(ctx: &DalContext) {
+    let runs = Arc::new(AtomicUsize::new(0));
+    ctx.on_commit(counting_hook(&runs))
+        .await
+        .expect("could not register commit hook");
+
+    ctx.rollback().await.expect("could not roll back");
+    ctx.blocking_commit().await.expect("could not commit");
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+}
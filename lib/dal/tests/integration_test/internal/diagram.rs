@@ -1,4 +1,5 @@
-use dal::edge::EdgeKind;
+use dal::diagram::socket_suggestion::SocketSuggestion;
+use dal::edge::{EdgeCreationSource, EdgeKind};
 use dal::{socket::SocketEdgeKind, Connection, DalContext, Diagram, Node, Socket, StandardModel};
 use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::test;
@@ -109,6 +110,7 @@ async fn get_diagram_and_create_and_delete_connection(ctx: &DalContext) {
         starfield_bag.node_id,
         *input_socket.id(),
         EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
     )
     .await
     .expect("could not create connection");
@@ -142,3 +144,160 @@ async fn get_diagram_and_create_and_delete_connection(ctx: &DalContext) {
     // Check that no connections exist on the diagram.
     assert_eq!(diagram.edges().len(), 0);
 }
+
+#[test]
+async fn assemble_page(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let starfield_bag = bagger.create_component(ctx, "head", "starfield").await;
+    let other_bag = bagger.create_component(ctx, "other", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        other_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        other_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not create connection");
+
+    // Pages follow the order of the component ids.
+    let mut expected = vec![
+        fallout_bag.component_id,
+        starfield_bag.component_id,
+        other_bag.component_id,
+    ];
+    expected.sort();
+
+    let first_page = Diagram::assemble_page(ctx, None, 2)
+        .await
+        .expect("could not assemble diagram page");
+    let first_page_ids: Vec<_> = first_page
+        .diagram
+        .components()
+        .iter()
+        .map(|component| component.component_id())
+        .collect();
+    assert_eq!(expected[..2].to_vec(), first_page_ids);
+    assert_eq!(Some(expected[1]), first_page.next_cursor);
+
+    let last_page = Diagram::assemble_page(ctx, first_page.next_cursor, 2)
+        .await
+        .expect("could not assemble diagram page");
+    let last_page_ids: Vec<_> = last_page
+        .diagram
+        .components()
+        .iter()
+        .map(|component| component.component_id())
+        .collect();
+    assert_eq!(expected[2..].to_vec(), last_page_ids);
+    assert_eq!(None, last_page.next_cursor);
+
+    // A page holds the edges connected to its components, even when the other end is in another
+    // page.
+    for (page, ids) in [(first_page, first_page_ids), (last_page, last_page_ids)] {
+        let connected =
+            ids.contains(&fallout_bag.component_id) || ids.contains(&other_bag.component_id);
+        assert_eq!(usize::from(connected), page.diagram.edges().len());
+    }
+}
+
+#[test]
+async fn list_socket_suggestions(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout = bagger.create_component(ctx, "fallout", "fallout").await;
+    let near_starfield = bagger.create_component(ctx, "near", "starfield").await;
+    let far_starfield = bagger.create_component(ctx, "far", "starfield").await;
+
+    for (node_id, x) in [
+        (fallout.node_id, "0"),
+        (near_starfield.node_id, "100"),
+        (far_starfield.node_id, "1000"),
+    ] {
+        let mut node = Node::get_by_id(ctx, &node_id)
+            .await
+            .expect("could not get node")
+            .expect("node not found");
+        node.set_geometry(ctx, x, "0", Some("500"), Some("500"))
+            .await
+            .expect("could not set geometry");
+    }
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+
+    // Only the "bethesda" inputs accept what the "bethesda" output emits; the nearest comes first.
+    let suggestions = SocketSuggestion::list(ctx, fallout.node_id, *output_socket.id())
+        .await
+        .expect("could not list socket suggestions");
+    assert_eq!(
+        vec![
+            (near_starfield.component_id, "bethesda".to_string()),
+            (far_starfield.component_id, "bethesda".to_string()),
+        ], // expected
+        suggestions
+            .iter()
+            .map(|suggestion| (suggestion.component_id, suggestion.socket_name.clone()))
+            .collect::<Vec<_>>(), // actual
+    );
+    assert!(suggestions
+        .iter()
+        .all(|suggestion| suggestion.schema_affinity == 0));
+
+    let far_input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        far_starfield.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout.node_id,
+        *output_socket.id(),
+        far_starfield.node_id,
+        *far_input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not create connection");
+
+    // The connected socket is no longer suggested and the remaining one gains affinity.
+    let suggestions = SocketSuggestion::list(ctx, fallout.node_id, *output_socket.id())
+        .await
+        .expect("could not list socket suggestions");
+    assert_eq!(1, suggestions.len());
+    assert_eq!(near_starfield.component_id, suggestions[0].component_id);
+    assert_eq!(1, suggestions[0].schema_affinity);
+}
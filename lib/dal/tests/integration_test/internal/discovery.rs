@@ -0,0 +1,83 @@
+use dal::discovery::{import_discovered_resources, DiscoveredResource};
+use dal::{Component, ComponentView, DalContext, ResourceIdentifier, StandardModel};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+fn discovered(resource_id: &str, name: &str) -> (String, DiscoveredResource) {
+    (
+        "fallout".to_owned(),
+        DiscoveredResource {
+            resource_id: resource_id.to_owned(),
+            name: name.to_owned(),
+            security_group_id: None,
+            domain: serde_json::json![{ "special": name, "rads": 42 }],
+            payload: serde_json::json![{ "Id": resource_id }],
+        },
+    )
+}
+
+#[test]
+async fn import_discovered_resources_once(ctx: &DalContext) {
+    let report = import_discovered_resources(
+        ctx,
+        vec![discovered("res-1", "vault"), discovered("res-2", "megaton")],
+    )
+    .await
+    .expect("could not import discovered resources");
+    assert!(report.skipped.is_empty());
+    assert_eq!(2, report.imported.len());
+
+    let imported = report.imported.first().expect("nothing was imported");
+    assert_eq!("res-1", imported.resource_id);
+    let registered = ResourceIdentifier::find_by_identifier(ctx, "res-1")
+        .await
+        .expect("could not find resource identifier")
+        .expect("resource identifier was not registered");
+    assert_eq!(imported.component_id, registered.component_id());
+
+    let component = Component::get_by_id(ctx, &imported.component_id)
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    assert_eq!(
+        "vault",
+        component.name(ctx).await.expect("could not get name")
+    );
+    assert_eq!(
+        Some(serde_json::json![{ "Id": "res-1" }]),
+        component
+            .resource(ctx)
+            .await
+            .expect("could not get resource")
+            .payload
+    );
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    let properties = ComponentView::new(ctx, imported.component_id)
+        .await
+        .expect("could not get component view")
+        .properties;
+    assert_eq!(
+        Some(&serde_json::json!("vault")),
+        properties.pointer("/domain/special")
+    );
+    assert_eq!(
+        Some(&serde_json::json!(42)),
+        properties.pointer("/domain/rads")
+    );
+
+    // Importing again only picks up the resources that are not modeled yet.
+    let report = import_discovered_resources(
+        ctx,
+        vec![discovered("res-1", "vault"), discovered("res-3", "rivet")],
+    )
+    .await
+    .expect("could not import discovered resources");
+    assert_eq!(1, report.imported.len());
+    assert_eq!("res-3", report.imported[0].resource_id);
+    assert_eq!(1, report.skipped.len());
+    assert_eq!("res-1", report.skipped[0].resource_id);
+    assert_eq!(imported.component_id, report.skipped[0].component_id);
+}
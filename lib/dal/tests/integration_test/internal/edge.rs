@@ -1,5 +1,5 @@
 use dal::{
-    edge::{EdgeKind, EdgeObjectId, VertexObjectKind},
+    edge::{EdgeCreationSource, EdgeKind, EdgeObjectId, VertexObjectKind},
     socket::SocketEdgeKind,
     Connection, DalContext, Edge, Socket, StandardModel,
 };
@@ -43,6 +43,7 @@ async fn new(ctx: &DalContext) {
         VertexObjectKind::Configuration,
         EdgeObjectId::from(fallout_bag.component_id),
         *output_socket.id(),
+        EdgeCreationSource::Manual,
     )
     .await
     .expect("cannot create new edge");
@@ -93,6 +94,7 @@ async fn create_delete_and_restore_edges(ctx: &DalContext) {
         to_starfield.node_id,
         *input_socket.id(),
         EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
     )
     .await
     .expect("could not create connection");
@@ -239,6 +241,7 @@ async fn create_multiple_connections_and_delete(ctx: &DalContext) {
         starfield_bag.node_id,
         *to_socket.id(),
         EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
     )
     .await
     .expect("could not create connection");
@@ -250,6 +253,7 @@ async fn create_multiple_connections_and_delete(ctx: &DalContext) {
         starfield_bag.node_id,
         *to_socket.id(),
         EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
     )
     .await
     .expect("could not create connection");
@@ -359,3 +363,139 @@ async fn create_multiple_connections_and_delete(ctx: &DalContext) {
             .expect("could not convert to value") // actual
     );
 }
+
+#[test]
+async fn creation_source(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let from_fallout = bagger.create_component(ctx, "from", "fallout").await;
+    let to_starfield = bagger.create_component(ctx, "to", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        from_fallout.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        to_starfield.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+
+    let connection = Connection::new(
+        ctx,
+        from_fallout.node_id,
+        *output_socket.id(),
+        to_starfield.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::FrameAutoConnection,
+    )
+    .await
+    .expect("could not create connection");
+    assert_eq!(
+        connection.creation_source,
+        EdgeCreationSource::FrameAutoConnection
+    );
+
+    let edge = Edge::get_by_id(ctx, &connection.id)
+        .await
+        .expect("could not get edge")
+        .expect("edge not found");
+    assert_eq!(
+        edge.creation_source(),
+        &EdgeCreationSource::FrameAutoConnection
+    );
+    assert!(edge.auto_created());
+}
+
+#[test]
+async fn delete_connection_updates_dependent_values(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+
+    let from_fallout = bagger.create_component(ctx, "from", "fallout").await;
+    let to_starfield = bagger.create_component(ctx, "to", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        from_fallout.node_id,
+    )
+    .await
+    .expect("could not perform socket find'")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        to_starfield.node_id,
+    )
+    .await
+    .expect("could not perform socket find'")
+    .expect("could not find socket");
+
+    let connection = Connection::new(
+        ctx,
+        from_fallout.node_id,
+        *output_socket.id(),
+        to_starfield.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not create connection");
+
+    let special_prop = from_fallout
+        .find_prop(ctx, &["root", "domain", "special"])
+        .await;
+    from_fallout
+        .update_attribute_value_for_prop(ctx, *special_prop.id(), Some(serde_json::json!["foo"]))
+        .await;
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // The domain of "to" only binds the socket at the schema variant level, so there is nothing
+    // specific to the component to reset.
+    let affected = Connection::delete(ctx, connection.id)
+        .await
+        .expect("unable to delete connection");
+    assert!(affected.is_empty());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert!(Edge::get_by_id(ctx, &connection.id)
+        .await
+        .expect("could not get edge")
+        .is_none());
+    assert_eq!(
+        serde_json::json![{
+            "si": {
+               "name": "to",
+               "type": "component",
+               "color": "#ffffff",
+               "protected": false,
+           },
+           "domain": {
+               "name": "to",
+           },
+        }], // expected
+        to_starfield
+            .component_view_properties(ctx)
+            .await
+            .to_value()
+            .expect("could not convert to value") // actual
+    );
+}
@@ -0,0 +1,41 @@
+use dal::{DalContext, HistoryEvent};
+use dal_test::{test, FaultInjector};
+
+#[test(pg_fail_every = 1)]
+async fn failing_every_query(ctx: &DalContext, faults: FaultInjector) {
+    let result = HistoryEvent::new(
+        ctx,
+        "fault_injection.test",
+        "should not be created",
+        &serde_json::json!({}),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(faults.pg_queries_failed() >= 1);
+}
+
+#[test]
+async fn failing_queries_once_armed(ctx: &DalContext, faults: FaultInjector) {
+    HistoryEvent::new(
+        ctx,
+        "fault_injection.test",
+        "created before faults are applied",
+        &serde_json::json!({}),
+    )
+    .await
+    .expect("able to create history event before faults are applied");
+    assert_eq!(0, faults.pg_queries_failed());
+
+    faults.fail_every_pg_query(Some(1));
+    let result = HistoryEvent::new(
+        ctx,
+        "fault_injection.test",
+        "should not be created",
+        &serde_json::json!({}),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(faults.pg_queries_failed() >= 1);
+}
@@ -0,0 +1,44 @@
+use dal::{DalContext, FixBatchTargetFilter};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+
+#[test]
+async fn target_filter_matches_components_and_schemas(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let starfield_bag = bagger.create_component(ctx, "starfield", "starfield").await;
+    let fallout_bag = bagger.create_component(ctx, "fallout", "fallout").await;
+
+    let empty = FixBatchTargetFilter::default();
+    assert!(empty.is_empty());
+    assert!(empty
+        .matches(ctx, starfield_bag.component_id)
+        .await
+        .expect("could not evaluate filter"));
+
+    let by_schema = FixBatchTargetFilter {
+        schema_ids: vec![fallout_bag.schema_id],
+        ..Default::default()
+    };
+    assert!(!by_schema
+        .matches(ctx, starfield_bag.component_id)
+        .await
+        .expect("could not evaluate filter"));
+    assert!(by_schema
+        .matches(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not evaluate filter"));
+
+    let by_component_and_schema = FixBatchTargetFilter {
+        component_ids: vec![fallout_bag.component_id],
+        schema_ids: vec![starfield_bag.schema_id],
+        ..Default::default()
+    };
+    assert!(!by_component_and_schema
+        .matches(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not evaluate filter"));
+    assert!(!by_component_and_schema
+        .matches(ctx, starfield_bag.component_id)
+        .await
+        .expect("could not evaluate filter"));
+}
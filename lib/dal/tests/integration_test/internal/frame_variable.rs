@@ -0,0 +1,111 @@
+use dal::socket::SocketEdgeKind;
+use dal::{ComponentType, Connection, DalContext, FrameVariable, Socket, StandardModel};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn expose_to_children(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let frame_bag = bagger.create_component(ctx, "frame", "fallout").await;
+    let child_bag = bagger.create_component(ctx, "child", "starfield").await;
+
+    frame_bag
+        .component(ctx)
+        .await
+        .set_type(ctx, ComponentType::ConfigurationFrameDown)
+        .await
+        .expect("could not set type");
+    Connection::new_to_parent(ctx, child_bag.node_id, frame_bag.node_id)
+        .await
+        .expect("could not connect child to frame");
+
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        child_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+
+    FrameVariable::upsert(
+        ctx,
+        frame_bag.component_id,
+        "bethesda",
+        Some(serde_json::json!["production"]),
+        false,
+    )
+    .await
+    .expect("could not upsert frame variable");
+    let exposed_socket_ids = FrameVariable::expose_to_component(
+        ctx,
+        frame_bag.component_id,
+        child_bag.component_id,
+        &[],
+    )
+    .await
+    .expect("could not expose frame variables");
+    assert_eq!(vec![*input_socket.id()], exposed_socket_ids);
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        serde_json::json![{
+           "si": {
+               "name": "child",
+               "type": "component",
+               "color": "#ffffff",
+               "protected": false,
+           },
+           "domain": {
+               "name": "child",
+               "attributes": "production",
+           },
+        }], // expected
+        child_bag
+            .component_view_properties(ctx)
+            .await
+            .to_value()
+            .expect("could not convert to value") // actual
+    );
+
+    // Redefining the variable flows down to the children already inside the frame.
+    let variable = FrameVariable::upsert(
+        ctx,
+        frame_bag.component_id,
+        "bethesda",
+        Some(serde_json::json!["staging"]),
+        false,
+    )
+    .await
+    .expect("could not upsert frame variable");
+    variable
+        .expose_to_descendants(ctx)
+        .await
+        .expect("could not expose frame variable");
+    assert_eq!(
+        1,
+        FrameVariable::list_for_component(ctx, frame_bag.component_id)
+            .await
+            .expect("could not list frame variables")
+            .len()
+    );
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let properties = child_bag
+        .component_view_properties(ctx)
+        .await
+        .to_value()
+        .expect("could not convert to value");
+    assert_eq!(
+        Some(&serde_json::json!["staging"]),
+        properties.pointer("/domain/attributes")
+    );
+}
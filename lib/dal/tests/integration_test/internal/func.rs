@@ -1,3 +1,4 @@
+use base64::{engine::general_purpose, Engine};
 use strum::IntoEnumIterator;
 
 use dal::{
@@ -16,6 +17,7 @@ use dal_test::{
     test_harness::{create_func, create_func_binding},
 };
 
+mod intrinsics;
 mod reconciliation;
 mod schema_variant_definition;
 
@@ -110,6 +112,105 @@ async fn func_binding_execute_unset(ctx: &DalContext) {
     assert_eq!(return_value.unprocessed_value(), None,);
 }
 
+/// A module exporting `memory`, `alloc`, which always hands out offset 1024, and `echo`, which
+/// returns its input as the result.
+const ECHO_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic & version
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01,
+    0x7e, // types
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x19, 0x03, // exports
+    0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00, //
+    0x05, b'a', b'l', b'l', b'o', b'c', 0x00, 0x00, //
+    0x04, b'e', b'c', b'h', b'o', 0x00, 0x01, //
+    0x0a, 0x14, 0x02, // code
+    0x05, 0x00, 0x41, 0x80, 0x08, 0x0b, //
+    0x0c, 0x00, 0x20, 0x00, 0xad, 0x42, 0x20, 0x86, 0x20, 0x01, 0xad, 0x84, 0x0b,
+];
+
+#[test]
+async fn func_binding_execute_wasm_attribute(ctx: &DalContext) {
+    let name = dal_test::test_harness::generate_fake_name();
+    let mut func = Func::new(
+        ctx,
+        name,
+        FuncBackendKind::WasmAttribute,
+        FuncBackendResponseType::Json,
+    )
+    .await
+    .expect("cannot create func");
+    func.set_code_base64(
+        ctx,
+        Some(general_purpose::STANDARD_NO_PAD.encode(ECHO_WASM)),
+    )
+    .await
+    .expect("could not set code");
+    func.set_handler(ctx, Some("echo"))
+        .await
+        .expect("could not set handler");
+
+    let args = serde_json::json!({ "name": "poop canoe", "ports": [80, 443] });
+    let (_, return_value) = FuncBinding::create_and_execute(ctx, args.clone(), *func.id(), vec![])
+        .await
+        .expect("failed to execute func binding");
+    assert_eq!(Some(&args), return_value.value());
+
+    func.set_handler(ctx, Some("missing"))
+        .await
+        .expect("could not set handler");
+    assert!(
+        FuncBinding::create_and_execute(ctx, args, *func.id(), vec![])
+            .await
+            .is_err()
+    );
+}
+
+/// A module exporting `memory`, `alloc`, which always hands out offset 1024, and `spin`, which
+/// never returns.
+const SPIN_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic & version
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01,
+    0x7e, // types
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x19, 0x03, // exports
+    0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00, //
+    0x05, b'a', b'l', b'l', b'o', b'c', 0x00, 0x00, //
+    0x04, b's', b'p', b'i', b'n', 0x00, 0x01, //
+    0x0a, 0x10, 0x02, // code
+    0x05, 0x00, 0x41, 0x80, 0x08, 0x0b, //
+    0x08, 0x00, 0x03, 0x40, 0x0c, 0x00, 0x0b, 0x00, 0x0b,
+];
+
+#[test]
+async fn func_binding_execute_wasm_attribute_is_bounded(ctx: &DalContext) {
+    let name = dal_test::test_harness::generate_fake_name();
+    let mut func = Func::new(
+        ctx,
+        name,
+        FuncBackendKind::WasmAttribute,
+        FuncBackendResponseType::Json,
+    )
+    .await
+    .expect("cannot create func");
+    func.set_code_base64(
+        ctx,
+        Some(general_purpose::STANDARD_NO_PAD.encode(SPIN_WASM)),
+    )
+    .await
+    .expect("could not set code");
+    func.set_handler(ctx, Some("spin"))
+        .await
+        .expect("could not set handler");
+
+    assert!(
+        FuncBinding::create_and_execute(ctx, serde_json::json!({}), *func.id(), vec![])
+            .await
+            .is_err()
+    );
+}
+
 #[test]
 async fn func_argument_new(ctx: &DalContext) {
     let func_id = FuncId::generate();
@@ -231,3 +332,75 @@ async fn duplicate(ctx: &DalContext) {
         new_func.handler()  // actual
     );
 }
+
+#[test]
+async fn find_by_name_reads_through_cache(ctx: &DalContext) {
+    let name = generate_name();
+    assert!(Func::find_by_name(ctx, &name)
+        .await
+        .expect("could not find func by name")
+        .is_none());
+
+    // Misses are not cached, so a func created afterwards is found.
+    let mut func = Func::new(
+        ctx,
+        &name,
+        FuncBackendKind::String,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    let found = Func::find_by_name(ctx, &name)
+        .await
+        .expect("could not find func by name")
+        .expect("func not found");
+    assert_eq!(func.id(), found.id());
+
+    // Mutating the func must not leave a stale copy behind.
+    func.set_display_name(ctx, Some("cached"))
+        .await
+        .expect("cannot set display name");
+    let found = Func::find_by_name(ctx, &name)
+        .await
+        .expect("could not find func by name")
+        .expect("func not found");
+    assert_eq!(Some("cached"), found.display_name());
+
+    let mut argument = FuncArgument::new(ctx, "poop", FuncArgumentKind::String, None, *func.id())
+        .await
+        .expect("cannot create func argument");
+    assert_eq!(
+        Some(*argument.id()),
+        FuncArgument::find_by_name_for_func(ctx, "poop", *func.id())
+            .await
+            .expect("could not find func argument")
+            .map(|argument| *argument.id())
+    );
+
+    argument
+        .delete_by_id(ctx)
+        .await
+        .expect("cannot delete func argument");
+    assert!(FuncArgument::find_by_name_for_func(ctx, "poop", *func.id())
+        .await
+        .expect("could not find func argument")
+        .is_none());
+}
+
+#[test]
+async fn identity_is_resolved_once_per_transactions(ctx: &DalContext) {
+    let identity = Func::identity(ctx).await.expect("could not get identity");
+    assert_eq!("si:identity", identity.func.name());
+    assert_eq!("identity", identity.func_argument.name());
+
+    let (func, func_binding, func_binding_return_value) =
+        Func::identity_with_binding_and_return_value(ctx)
+            .await
+            .expect("could not get identity with binding and return value");
+    assert_eq!(identity.func, func);
+    assert_eq!(identity.func_binding, func_binding);
+    assert_eq!(
+        identity.func_binding_return_value,
+        func_binding_return_value
+    );
+}
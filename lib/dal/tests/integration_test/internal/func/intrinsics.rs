@@ -0,0 +1,101 @@
+use dal::{func::intrinsics::IntrinsicFunc, DalContext, Func, FuncBinding, StandardModel};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use serde_json::json;
+
+async fn execute(
+    ctx: &DalContext,
+    intrinsic: IntrinsicFunc,
+    args: serde_json::Value,
+) -> Option<serde_json::Value> {
+    let func = Func::find_by_name(ctx, intrinsic.name())
+        .await
+        .expect("could not find func")
+        .expect("intrinsic func not found");
+    let (_, return_value) = FuncBinding::create_and_execute(ctx, args, *func.id(), vec![])
+        .await
+        .expect("failed to execute func binding");
+    return_value.value().cloned()
+}
+
+#[test]
+async fn join(ctx: &DalContext) {
+    assert_eq!(
+        Some(json!["arn:aws:3"]),
+        execute(
+            ctx,
+            IntrinsicFunc::Join,
+            json!({ "values": ["arn", "aws", null, 3], "separator": ":" }),
+        )
+        .await
+    );
+    assert_eq!(
+        Some(json!["us-east-1"]),
+        execute(ctx, IntrinsicFunc::Join, json!({ "values": "us-east-1" })).await
+    );
+}
+
+#[test]
+async fn template(ctx: &DalContext) {
+    assert_eq!(
+        Some(json!["arn:aws:iam::123456789012:role/deploy"]),
+        execute(
+            ctx,
+            IntrinsicFunc::Template,
+            json!({
+                "template": "arn:aws:iam::{{accountId}}:role/{{ roles[1] }}",
+                "values": { "accountId": "123456789012", "roles": ["admin", "deploy"] },
+            }),
+        )
+        .await
+    );
+
+    let func = Func::find_by_name(ctx, IntrinsicFunc::Template.name())
+        .await
+        .expect("could not find func")
+        .expect("intrinsic func not found");
+    for template in ["{{region}}", "{{accountId"] {
+        assert!(FuncBinding::create_and_execute(
+            ctx,
+            json!({ "template": template, "values": { "accountId": "123456789012" } }),
+            *func.id(),
+            vec![],
+        )
+        .await
+        .is_err());
+    }
+}
+
+#[test]
+async fn select(ctx: &DalContext) {
+    let value = json!({
+        "tags": [{ "key": "Name", "value": "web" }, { "key": "Env", "value": "prod" }],
+    });
+    assert_eq!(
+        Some(json!["prod"]),
+        execute(
+            ctx,
+            IntrinsicFunc::Select,
+            json!({ "value": value, "path": "$.tags[1].value" }),
+        )
+        .await
+    );
+    assert_eq!(
+        Some(json!(["Name", "Env"])),
+        execute(
+            ctx,
+            IntrinsicFunc::Select,
+            json!({ "value": value, "path": "tags[*]['key']" }),
+        )
+        .await
+    );
+    assert_eq!(
+        None,
+        execute(
+            ctx,
+            IntrinsicFunc::Select,
+            json!({ "value": value, "path": "$.tags[5].value" }),
+        )
+        .await
+    );
+}
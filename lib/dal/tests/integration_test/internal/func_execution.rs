@@ -2,8 +2,10 @@ use dal::{
     func::{
         backend::string::FuncBackendStringArgs,
         execution::{FuncExecution, FuncExecutionState},
+        execution_log::FuncExecutionLog,
     },
-    DalContext, FuncBinding, StandardModel,
+    AttributeValueId, ComponentId, DalContext, Func, FuncBackendKind, FuncBackendResponseType,
+    FuncBinding, StandardModel,
 };
 use dal_test::{
     test,
@@ -103,6 +105,73 @@ async fn process_return_value(ctx: &DalContext) {
     );
 }
 
+#[test]
+async fn execution_log_is_recorded(ctx: &DalContext) {
+    let mut func = Func::new(
+        ctx,
+        "chatty",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    func.set_handler(ctx, Some("chatty"))
+        .await
+        .expect("unable to set func handler");
+    func.set_code_plaintext(
+        ctx,
+        Some(
+            "function chatty(input) {
+    console.log(\"to stdout\");
+    console.error(\"to stderr\");
+    return \"done\";
+}",
+        ),
+    )
+    .await
+    .expect("unable to set func code plaintext");
+
+    // Builtin backends do not run in veritech and are not logged.
+    let builtin = create_func(ctx).await;
+    FuncBinding::create_and_execute(
+        ctx,
+        serde_json::to_value(FuncBackendStringArgs::new("slayer".to_string()))
+            .expect("cannot turn args into json"),
+        *builtin.id(),
+        vec![],
+    )
+    .await
+    .expect("failed to execute func binding");
+
+    FuncBinding::create_and_execute_for_component(
+        ctx,
+        serde_json::json!({}),
+        *func.id(),
+        vec![],
+        ComponentId::NONE,
+        AttributeValueId::NONE,
+    )
+    .await
+    .expect("failed to execute func binding");
+
+    let logs = FuncExecutionLog::list(ctx, None, Some(*func.id()))
+        .await
+        .expect("cannot list func execution logs");
+    assert_eq!(logs.len(), 1);
+    let log = &logs[0];
+    assert!(log.success());
+    assert_eq!(log.failure(), None);
+    assert!(log.stdout().iter().any(|line| line.contains("to stdout")));
+    assert!(log.stderr().iter().any(|line| line.contains("to stderr")));
+    assert!(log.finished_at() >= log.started_at());
+    assert!(log.duration_ms() >= 0);
+
+    assert!(FuncExecutionLog::list(ctx, None, Some(*builtin.id()))
+        .await
+        .expect("cannot list func execution logs")
+        .is_empty());
+}
+
 // FIXME(nick,fletcher): re-add test once upsert is added.
 // #[test]
 // async fn execution_upserts_return_value() {
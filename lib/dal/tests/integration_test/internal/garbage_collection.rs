@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+use dal::{
+    edge::{EdgeCreationSource, EdgeId, EdgeKind},
+    garbage_collect,
+    socket::SocketEdgeKind,
+    ChangeSet, Connection, DalContext, Edge, Socket, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+
+async fn edge_row_count(ctx: &DalContext, edge_id: EdgeId) -> i64 {
+    ctx.txns()
+        .await
+        .expect("failed to get txns")
+        .pg()
+        .query_one(
+            "SELECT count(*) AS count FROM edges WHERE id = $1",
+            &[&edge_id],
+        )
+        .await
+        .expect("could not count edge rows")
+        .try_get("count")
+        .expect("could not get count")
+}
+
+#[test]
+async fn garbage_collect_keeps_open_change_sets(ctx: &mut DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let from_fallout = bagger.create_component(ctx, "from", "fallout").await;
+    let to_starfield = bagger.create_component(ctx, "to", "starfield").await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        from_fallout.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        to_starfield.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+
+    let connection = Connection::new(
+        ctx,
+        from_fallout.node_id,
+        *output_socket.id(),
+        to_starfield.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
+    )
+    .await
+    .expect("could not create connection");
+
+    let mut edge = Edge::get_by_id(ctx, &connection.id)
+        .await
+        .expect("could not perform get by id")
+        .expect("could not find edge");
+    edge.delete_and_propagate(ctx)
+        .await
+        .expect("could not delete edge");
+
+    // Rows deleted in a change set that is still open are never collected.
+    let older_than = Utc::now() + Duration::minutes(1);
+    garbage_collect(ctx, older_than)
+        .await
+        .expect("could not garbage collect");
+    assert!(edge_row_count(ctx, connection.id).await > 0);
+
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .abandon(ctx)
+        .await
+        .expect("could not abandon change set");
+
+    // Once the change set is abandoned, its deleted rows are reclaimed.
+    let report = garbage_collect(ctx, older_than)
+        .await
+        .expect("could not garbage collect");
+    assert!(report.reclaimed_for("edges") > 0);
+    assert_eq!(0, edge_row_count(ctx, connection.id).await);
+}
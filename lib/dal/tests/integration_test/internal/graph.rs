@@ -4,7 +4,7 @@
 use dal::component::ComponentKind;
 use dal::node::NodeId;
 use dal::{
-    edge::{EdgeKind, EdgeObjectId, VertexObjectKind},
+    edge::{EdgeCreationSource, EdgeKind, EdgeObjectId, VertexObjectKind},
     Component, DalContext, Edge, ExternalProvider, InternalProvider, Node, Schema, SchemaVariant,
     SchemaVariantId, SocketArity, SocketId, StandardModel,
 };
@@ -251,6 +251,7 @@ impl ConfigurationGraphConstructor {
             VertexObjectKind::Configuration,
             source_node.object_id,
             self.output_socket_id,
+            EdgeCreationSource::Manual,
         )
         .await
         .expect("unable to create edge");
@@ -1,5 +1,10 @@
-use dal::{DalContext, HistoryActor, HistoryEvent};
+use chrono::{Duration, Utc};
+use dal::{
+    ComponentId, DalContext, HistoryActor, HistoryEvent, HistoryEventError, HistoryEventFilters,
+    UserPk,
+};
 use dal_test::test;
+use serde_json::json;
 
 #[test]
 async fn new(ctx: &DalContext) {
@@ -17,3 +22,113 @@ async fn new(ctx: &DalContext) {
     assert_eq!(&history_event.data, &serde_json::json!({}));
     assert_eq!(&history_event.tenancy, ctx.tenancy());
 }
+
+#[test]
+async fn list_for_entity(ctx: &DalContext) {
+    let entity_id = ComponentId::generate();
+    for label in ["component.create", "component.updated", "edge.create"] {
+        HistoryEvent::new(
+            ctx,
+            label,
+            "something happened",
+            &json!({ "id": entity_id }),
+        )
+        .await
+        .expect("cannot create a new history event");
+    }
+    HistoryEvent::new(
+        ctx,
+        "component.create",
+        "something else happened",
+        &json!({ "id": ComponentId::generate() }),
+    )
+    .await
+    .expect("cannot create a new history event");
+
+    let all = HistoryEvent::list_for_entity(ctx, entity_id, &HistoryEventFilters::default())
+        .await
+        .expect("cannot list history events");
+    assert_eq!(3, all.events.len());
+    assert_eq!(None, all.next_cursor);
+
+    let first_page = HistoryEvent::list_for_entity(
+        ctx,
+        entity_id,
+        &HistoryEventFilters {
+            limit: Some(2),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list history events");
+    assert_eq!(2, first_page.events.len());
+    let second_page = HistoryEvent::list_for_entity(
+        ctx,
+        entity_id,
+        &HistoryEventFilters {
+            limit: Some(2),
+            cursor: first_page.next_cursor,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list history events");
+    assert_eq!(1, second_page.events.len());
+    assert_eq!(None, second_page.next_cursor);
+    assert!(!first_page
+        .events
+        .iter()
+        .any(|event| event.pk == second_page.events[0].pk));
+
+    let components = HistoryEvent::list_for_entity(
+        ctx,
+        entity_id,
+        &HistoryEventFilters {
+            label_prefix: Some("component.".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list history events");
+    assert_eq!(2, components.events.len());
+
+    let by_someone_else = HistoryEvent::list_for_entity(
+        ctx,
+        entity_id,
+        &HistoryEventFilters {
+            actor: Some(HistoryActor::User(UserPk::generate())),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list history events");
+    assert!(by_someone_else.events.is_empty());
+
+    let now = Utc::now();
+    let in_the_past = HistoryEvent::list_for_entity(
+        ctx,
+        entity_id,
+        &HistoryEventFilters {
+            until: Some(now - Duration::hours(1)),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list history events");
+    assert!(in_the_past.events.is_empty());
+
+    let result = HistoryEvent::list_for_entity(
+        ctx,
+        entity_id,
+        &HistoryEventFilters {
+            since: Some(now),
+            until: Some(now - Duration::hours(1)),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(HistoryEventError::InvalidTimeRange(_, _))
+    ));
+}
@@ -0,0 +1,71 @@
+use dal::job::definition::DependentValuesUpdate;
+use dal::job::queue::JobQueue;
+use dal::{AccessBuilder, AttributeValueId, DalContext};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn dependent_values_updates_coalesce_in_queue(ctx: &DalContext) {
+    let access_builder = AccessBuilder::from(ctx.clone());
+    let visibility = *ctx.visibility();
+
+    let first = AttributeValueId::generate();
+    let second = AttributeValueId::generate();
+    let third = AttributeValueId::generate();
+
+    let queue = JobQueue::new();
+    queue
+        .enqueue_job(DependentValuesUpdate::new(
+            access_builder,
+            visibility,
+            vec![first, second],
+        ))
+        .await;
+    queue
+        .enqueue_job(DependentValuesUpdate::new(
+            access_builder,
+            visibility,
+            vec![second, third],
+        ))
+        .await;
+
+    assert_eq!(1, queue.size().await);
+    let job = queue.fetch_job().await.expect("job not found in queue");
+    assert_eq!(
+        serde_json::json!({ "attribute_values": [first, second, third] }),
+        job.arg().expect("could not get job args"),
+    );
+}
+
+#[test]
+async fn cancelled_change_set_jobs_are_dropped_from_queue(ctx: &DalContext) {
+    let access_builder = AccessBuilder::from(ctx.clone());
+    let visibility = *ctx.visibility();
+
+    let queue = JobQueue::new();
+    queue
+        .enqueue_job(DependentValuesUpdate::new(
+            access_builder,
+            visibility,
+            vec![AttributeValueId::generate()],
+        ))
+        .await;
+    assert_eq!(1, queue.size().await);
+
+    queue.cancel_change_set_jobs(visibility.change_set_pk).await;
+    assert!(queue.is_empty().await);
+
+    // Jobs enqueued after the cancellation are dropped as well.
+    queue
+        .enqueue_job(DependentValuesUpdate::new(
+            access_builder,
+            visibility,
+            vec![AttributeValueId::generate()],
+        ))
+        .await;
+    assert!(queue.is_empty().await);
+    assert_eq!(
+        vec![visibility.change_set_pk],
+        queue.drain_cancelled_change_sets().await
+    );
+}
@@ -1,27 +1,43 @@
 mod action_prototype;
+mod api_token;
 mod attribute;
+mod builtin_migration_checkpoint;
 mod change_set;
+mod cloudformation;
 mod component;
+mod confirmation;
+mod context;
 mod diagram;
+mod discovery;
 mod edge;
+mod fault_injection;
+mod fix;
+mod frame_variable;
 mod func;
 mod func_execution;
+mod garbage_collection;
 mod graph;
 mod history_event;
+mod job;
 mod key_pair;
 mod node;
 mod node_menu;
 mod pkg;
 mod prop;
+mod prop_permission;
 mod prop_tree;
 mod property_editor;
 mod provider;
+mod resource_identifier;
 mod schema;
 mod secret;
 mod socket;
+mod socket_merge_strategy;
 mod standard_model;
 mod status_update;
 mod tenancy;
 mod user;
 mod visibility;
+mod webhook;
 mod workspace;
+mod ws_event;
@@ -13,9 +13,9 @@ use dal_test::{connection_annotation_string, test, DalContextHeadRef};
 use si_pkg::{
     ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, FuncArgumentSpec, FuncSpec,
     FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, LeafFunctionSpec,
-    LeafInputLocation as PkgLeafInputLocation, LeafKind as PkgLeafKind, PkgSpec, PropSpec,
-    PropSpecKind, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecData, SiPkg,
-    SocketSpec, SocketSpecArity, SocketSpecData, SocketSpecKind,
+    LeafInputLocation as PkgLeafInputLocation, LeafKind as PkgLeafKind, PkgDependencySpec, PkgSpec,
+    PropSpec, PropSpecKind, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecData,
+    SiPkg, SocketSpec, SocketSpecArity, SocketSpecData, SocketSpecKind,
 };
 
 async fn make_stellarfield(ctx: &DalContext) -> BuiltinsResult<()> {
@@ -809,3 +809,283 @@ async fn test_install_pkg(ctx: &DalContext) {
     .expect("able to search for ac input")
     .expect("able to find ac input");
 }
+
+#[test]
+async fn test_upgrade_pkg(ctx: &DalContext) {
+    let scaffold_func = "function createAsset() {
+                return new AssetBuilder().build();
+            }";
+    let scaffold_func_spec = FuncSpec::builder()
+        .name("si:scaffoldFuncGravity")
+        .unique_id("si:scaffoldFuncGravity")
+        .data(
+            FuncSpecData::builder()
+                .name("si:scaffoldFuncGravity")
+                .code_plaintext(scaffold_func)
+                .handler("createAsset")
+                .backend_kind(FuncSpecBackendKind::JsSchemaVariantDefinition)
+                .response_type(FuncSpecBackendResponseType::SchemaVariantDefinition)
+                .build()
+                .expect("build func data"),
+        )
+        .build()
+        .expect("could not build schema variant definition spec");
+
+    let make_schema = |prop_names: &[&str]| {
+        let mut variant_builder = SchemaVariantSpec::builder();
+        variant_builder.name("Rocket 00000").data(
+            SchemaVariantSpecData::builder()
+                .name("Rocket 00000")
+                .color("baddad")
+                .func_unique_id(&scaffold_func_spec.unique_id)
+                .build()
+                .expect("rocket data"),
+        );
+        for prop_name in prop_names {
+            variant_builder.domain_prop(
+                PropSpec::builder()
+                    .name(*prop_name)
+                    .kind(PropSpecKind::String)
+                    .build()
+                    .expect("able to make prop spec"),
+            );
+        }
+
+        SchemaSpec::builder()
+            .name("Gottfried")
+            .unique_id("gottfried")
+            .data(
+                SchemaSpecData::builder()
+                    .name("Gottfried")
+                    .category("Rockets")
+                    .ui_hidden(false)
+                    .build()
+                    .expect("gottfried data"),
+            )
+            .variant(variant_builder.build().expect("able to make variant spec"))
+            .build()
+            .expect("able to make schema spec")
+    };
+
+    let identity_func_spec = IntrinsicFunc::Identity
+        .to_spec()
+        .expect("create identity func spec");
+
+    let pkg_v1 = SiPkg::load_from_spec(
+        PkgSpec::builder()
+            .name("Gravity's Rainbow")
+            .version("0.1")
+            .created_by("Blicero")
+            .schema(make_schema(&["Imipolex"]))
+            .func(identity_func_spec.clone())
+            .func(scaffold_func_spec.clone())
+            .build()
+            .expect("able to build package spec"),
+    )
+    .expect("able to load from spec");
+
+    let pkg_v2 = SiPkg::load_from_spec(
+        PkgSpec::builder()
+            .name("Gravity's Rainbow")
+            .version("0.2")
+            .created_by("Blicero")
+            .schema(make_schema(&["Imipolex", "Schwarzgerat"]))
+            .func(identity_func_spec)
+            .func(scaffold_func_spec)
+            .build()
+            .expect("able to build package spec"),
+    )
+    .expect("able to load from spec");
+
+    let (installed_pkg_id, _, _) = import_pkg_from_pkg(ctx, &pkg_v1, None, false)
+        .await
+        .expect("able to install pkg");
+    let installed_pkg =
+        InstalledPkg::get_by_id(ctx, &installed_pkg_id.expect("install should be recorded"))
+            .await
+            .expect("able to get installed pkg")
+            .expect("installed pkg exists");
+    assert_eq!(Some("0.1"), installed_pkg.version());
+
+    let upgrade = installed_pkg
+        .upgrade(ctx, &pkg_v2)
+        .await
+        .expect("able to upgrade pkg");
+
+    assert_eq!(Some("0.1".to_string()), upgrade.previous_version);
+    assert_eq!("0.2", upgrade.version);
+    assert_eq!(vec!["Gottfried".to_string()], upgrade.upgraded_schemas);
+    assert!(upgrade.added_schemas.is_empty());
+    assert!(upgrade.unchanged_schemas.is_empty());
+
+    // The upgrade should add a variant to the existing schema rather than creating a new one
+    let schemas = Schema::find_by_attr(ctx, "name", &"Gottfried".to_string())
+        .await
+        .expect("find schemas");
+    assert_eq!(1, schemas.len());
+    let variants = schemas
+        .get(0)
+        .expect("schema exists")
+        .variants(ctx)
+        .await
+        .expect("list variants");
+    assert_eq!(2, variants.len());
+
+    assert!(InstalledPkg::find_by_hash(ctx, installed_pkg.root_hash())
+        .await
+        .expect("find by hash")
+        .is_none());
+    let upgraded_pkg = InstalledPkg::get_by_id(ctx, &upgrade.installed_pkg_id)
+        .await
+        .expect("able to get upgraded pkg")
+        .expect("upgraded pkg exists");
+    assert_eq!(Some("0.2"), upgraded_pkg.version());
+}
+
+#[test]
+async fn test_uninstall_pkg(ctx: &DalContext) {
+    let identity_func_spec = IntrinsicFunc::Identity
+        .to_spec()
+        .expect("create identity func spec");
+    let schema_spec = SchemaSpec::builder()
+        .name("Slothrop")
+        .data(
+            SchemaSpecData::builder()
+                .name("Slothrop")
+                .category("Rockets")
+                .ui_hidden(false)
+                .build()
+                .expect("slothrop data"),
+        )
+        .variant(
+            SchemaVariantSpec::builder()
+                .name("v0")
+                .data(
+                    SchemaVariantSpecData::builder()
+                        .name("v0")
+                        .color("baddad")
+                        .build()
+                        .expect("v0 data"),
+                )
+                .build()
+                .expect("able to make variant spec"),
+        )
+        .build()
+        .expect("able to make schema spec");
+
+    let pkg = SiPkg::load_from_spec(
+        PkgSpec::builder()
+            .name("Mindless Pleasures")
+            .version("0.1")
+            .created_by("Pointsman")
+            .schema(schema_spec)
+            .func(identity_func_spec)
+            .build()
+            .expect("able to build package spec"),
+    )
+    .expect("able to load from spec");
+
+    let (installed_pkg_id, _, _) = import_pkg_from_pkg(ctx, &pkg, None, false)
+        .await
+        .expect("able to install pkg");
+    let installed_pkg =
+        InstalledPkg::get_by_id(ctx, &installed_pkg_id.expect("install should be recorded"))
+            .await
+            .expect("able to get installed pkg")
+            .expect("installed pkg exists");
+
+    let uninstall = installed_pkg
+        .uninstall(ctx)
+        .await
+        .expect("able to uninstall pkg");
+
+    assert_eq!("Mindless Pleasures", uninstall.name);
+    assert!(uninstall
+        .removed_assets
+        .iter()
+        .any(|asset| matches!(asset, InstalledPkgAssetTyped::Schema { .. })));
+    // The identity func is intrinsic, so it must survive the uninstall
+    assert!(Func::find_by_attr(ctx, "name", &"si:identity".to_string())
+        .await
+        .expect("find identity func")
+        .pop()
+        .is_some());
+
+    assert!(Schema::find_by_attr(ctx, "name", &"Slothrop".to_string())
+        .await
+        .expect("find schemas")
+        .is_empty());
+    assert!(InstalledPkg::get_by_id(ctx, installed_pkg.id())
+        .await
+        .expect("able to get installed pkg")
+        .is_none());
+    assert!(
+        InstalledPkgAsset::list_for_installed_pkg_id(ctx, *installed_pkg.id())
+            .await
+            .expect("list assets")
+            .is_empty()
+    );
+}
+
+#[test]
+async fn test_install_pkg_with_dependencies(ctx: &DalContext) {
+    let make_pkg = |name: &str, dependencies: Vec<PkgDependencySpec>| {
+        let mut builder = PkgSpec::builder();
+        builder.name(name).version("0.1").created_by("Ren").func(
+            IntrinsicFunc::Identity
+                .to_spec()
+                .expect("create identity func spec"),
+        );
+        for dependency in dependencies {
+            builder.dependency(dependency);
+        }
+
+        SiPkg::load_from_spec(builder.build().expect("able to build package spec"))
+            .expect("able to load package")
+    };
+
+    let mothra = make_pkg("mothra", vec![]);
+    let mothra_hash = mothra.hash().expect("get hash").to_string();
+
+    let godzilla = make_pkg(
+        "godzilla",
+        vec![
+            PkgDependencySpec::builder()
+                .name("mothra")
+                .build()
+                .expect("able to build dependency spec"),
+            PkgDependencySpec::builder()
+                .name("mothra")
+                .root_hash(&mothra_hash)
+                .build()
+                .expect("able to build dependency spec"),
+        ],
+    );
+
+    match import_pkg_from_pkg(ctx, &godzilla, None, true).await {
+        Err(PkgError::InstalledPkg(InstalledPkgError::MissingDependency(name, unmet))) => {
+            assert_eq!("godzilla", name);
+            assert_eq!(
+                vec!["mothra".to_string(), format!("mothra@{mothra_hash}")],
+                unmet
+            );
+        }
+        other => panic!("expected a missing dependency error, got {other:?}"),
+    }
+
+    import_pkg_from_pkg(ctx, &mothra, None, true)
+        .await
+        .expect("able to install dependency");
+    import_pkg_from_pkg(ctx, &godzilla, None, true)
+        .await
+        .expect("able to install package once its dependencies are met");
+
+    let metadata = godzilla.metadata().expect("get metadata");
+    let resolved = InstalledPkg::resolve_dependencies(ctx, &metadata)
+        .await
+        .expect("dependencies resolve");
+    assert_eq!(2, resolved.len());
+    assert!(resolved
+        .iter()
+        .all(|installed| installed.root_hash() == mothra_hash));
+}
@@ -1,6 +1,11 @@
-use dal::{DalContext, Prop, PropKind, Schema, SchemaVariant, StandardModel};
+use dal::prop::PropPath;
+use dal::{
+    AttributeContext, AttributeValue, DalContext, Prop, PropError, PropKind, Schema, SchemaVariant,
+    StandardModel,
+};
 use dal_test::helpers::generate_fake_name;
 use dal_test::test;
+use dal_test::test_harness::create_schema;
 use pretty_assertions_sorted::assert_eq;
 
 #[test]
@@ -103,3 +108,259 @@ async fn parent_props_wrong_prop_kinds(ctx: &DalContext) {
 
     result.expect_err("should have errored, and it did not");
 }
+
+#[test]
+async fn set_name(ctx: &DalContext) {
+    let schema = create_schema(ctx).await;
+    let (schema_variant, root_prop) = SchemaVariant::new(ctx, *schema.id(), "v0")
+        .await
+        .expect("cannot create schema variant");
+
+    let mut parent_prop = Prop::new_without_ui_optionals(
+        ctx,
+        "poop",
+        PropKind::Object,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    let child_prop = Prop::new_without_ui_optionals(
+        ctx,
+        "canoe",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(*parent_prop.id()),
+    )
+    .await
+    .expect("could not create prop");
+    Prop::new_without_ui_optionals(
+        ctx,
+        "toilet",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+
+    // Siblings cannot share a name.
+    let result = parent_prop.set_name(ctx, "toilet").await;
+    assert!(matches!(result, Err(PropError::DuplicateChildName(..))));
+
+    parent_prop
+        .set_name(ctx, "boat")
+        .await
+        .expect("could not rename prop");
+    assert_eq!(parent_prop.name(), "boat");
+    assert_eq!(
+        parent_prop.path(),
+        PropPath::new(["root", "domain", "boat"])
+    );
+
+    let child_prop = Prop::get_by_id(ctx, child_prop.id())
+        .await
+        .expect("could not get prop")
+        .expect("prop not found");
+    assert_eq!(
+        child_prop.path(),
+        PropPath::new(["root", "domain", "boat", "canoe"])
+    );
+    let found_child_prop = Prop::find_prop_by_path_opt(
+        ctx,
+        *schema_variant.id(),
+        &PropPath::new(["root", "domain", "boat", "canoe"]),
+    )
+    .await
+    .expect("could not find prop by path");
+    assert_eq!(found_child_prop, Some(child_prop));
+}
+
+#[test]
+async fn delete_recursive(ctx: &DalContext) {
+    let schema = create_schema(ctx).await;
+    let (schema_variant, root_prop) = SchemaVariant::new(ctx, *schema.id(), "v0")
+        .await
+        .expect("cannot create schema variant");
+
+    let parent_prop = Prop::new_without_ui_optionals(
+        ctx,
+        "poop",
+        PropKind::Object,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    let child_prop = Prop::new_without_ui_optionals(
+        ctx,
+        "canoe",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(*parent_prop.id()),
+    )
+    .await
+    .expect("could not create prop");
+    SchemaVariant::create_default_prototypes_and_values(ctx, *schema_variant.id())
+        .await
+        .expect("could not create default prototypes and values");
+
+    let child_prop_context = AttributeContext::builder()
+        .set_prop_id(*child_prop.id())
+        .to_context()
+        .expect("could not create attribute context");
+    assert!(
+        AttributeValue::find_for_context(ctx, child_prop_context.into())
+            .await
+            .expect("could not find attribute value")
+            .is_some()
+    );
+
+    // The root prop cannot go away.
+    let root = Prop::get_by_id(ctx, &root_prop.prop_id)
+        .await
+        .expect("could not get prop")
+        .expect("prop not found");
+    let result = root.delete_recursive(ctx).await;
+    assert!(matches!(result, Err(PropError::CannotDeleteRootProp(_))));
+
+    let parent_prop_id = *parent_prop.id();
+    parent_prop
+        .delete_recursive(ctx)
+        .await
+        .expect("could not delete prop");
+
+    for prop_id in [parent_prop_id, *child_prop.id()] {
+        assert!(Prop::get_by_id(ctx, &prop_id)
+            .await
+            .expect("could not get prop")
+            .is_none());
+    }
+    assert!(
+        AttributeValue::find_for_context(ctx, child_prop_context.into())
+            .await
+            .expect("could not find attribute value")
+            .is_none()
+    );
+    let domain_prop = Prop::get_by_id(ctx, &root_prop.domain_prop_id)
+        .await
+        .expect("could not get prop")
+        .expect("prop not found");
+    assert!(domain_prop
+        .child_props(ctx)
+        .await
+        .expect("could not get child props")
+        .is_empty());
+}
+
+#[test]
+async fn delete_recursive_finalized(ctx: &DalContext) {
+    let schema = Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema");
+    let schema_variant_id = *schema
+        .default_schema_variant_id()
+        .expect("could not get default variant id");
+    let prop =
+        SchemaVariant::find_prop_in_tree(ctx, schema_variant_id, &["root", "domain", "name"])
+            .await
+            .expect("could not find prop");
+
+    let result = prop.delete_recursive(ctx).await;
+    assert!(matches!(result, Err(PropError::SchemaVariantFinalized(_))));
+}
+
+#[test]
+async fn new_with_default_value(ctx: &DalContext) {
+    let schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = SchemaVariant::new(ctx, *schema.id(), "v0")
+        .await
+        .expect("cannot create schema variant");
+
+    let prop = Prop::new_with_default_value(
+        ctx,
+        "flavor",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+        Some("the flavor of the soda".to_owned()),
+        Some(serde_json::json!["orange"]),
+    )
+    .await
+    .expect("could not create prop");
+    assert_eq!(prop.documentation(), Some("the flavor of the soda"));
+    assert_eq!(prop.default_value(), Some(&serde_json::json!["orange"]));
+
+    // Only scalar props can have default values.
+    let result = Prop::new_with_default_value(
+        ctx,
+        "cans",
+        PropKind::Object,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+        None,
+        Some(serde_json::json![{}]),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(PropError::SetDefaultForNonScalar(PropKind::Object))
+    ));
+
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize schema variant");
+
+    let attribute_value =
+        AttributeValue::find_for_context(ctx, AttributeReadContext::default_with_prop(*prop.id()))
+            .await
+            .expect("could not find attribute value")
+            .expect("attribute value not found");
+    assert_eq!(
+        Some(serde_json::json!["orange"]),
+        attribute_value
+            .get_value(ctx)
+            .await
+            .expect("could not get value")
+    );
+}
+
+#[test]
+async fn get_by_id_reads_through_attribute_context_cache(ctx: &DalContext) {
+    let schema = Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema");
+    let schema_variant_id = *schema
+        .default_schema_variant_id()
+        .expect("could not get default variant id");
+    let domain_prop = SchemaVariant::find_prop_in_tree(ctx, schema_variant_id, &["root", "domain"])
+        .await
+        .expect("could not find prop");
+
+    let mut cached_ctx = ctx.clone();
+    cached_ctx.enable_attribute_context_cache();
+
+    let mut cached_prop = Prop::get_by_id(&cached_ctx, domain_prop.id())
+        .await
+        .expect("could not get prop")
+        .expect("prop not found");
+    assert_eq!(domain_prop, cached_prop);
+    assert_eq!(
+        Some(cached_prop.clone()),
+        Prop::get_by_id(&cached_ctx, domain_prop.id())
+            .await
+            .expect("could not get prop"),
+    );
+
+    // Writes through the standard model must not leave stale props behind.
+    cached_prop
+        .set_hidden(&cached_ctx, !domain_prop.hidden())
+        .await
+        .expect("could not set hidden");
+    let updated_prop = Prop::get_by_id(&cached_ctx, domain_prop.id())
+        .await
+        .expect("could not get prop")
+        .expect("prop not found");
+    assert_eq!(!domain_prop.hidden(), updated_prop.hidden());
+}
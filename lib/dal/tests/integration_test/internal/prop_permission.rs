@@ -0,0 +1,70 @@
+use dal::prop::PropPath;
+use dal::{DalContext, HistoryActor, PropAccess, PropPermission, User, UserPk, WorkspaceRole};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn restricted_props_follow_workspace_role(ctx: &DalContext) {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+    let user = User::new(
+        ctx,
+        UserPk::generate(),
+        "ozzy",
+        "ozzy@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    user.associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("cannot associate workspace");
+    User::set_workspace_role(ctx, user.pk(), workspace_pk, WorkspaceRole::Editor)
+        .await
+        .expect("cannot set workspace role");
+
+    let permission = PropPermission::upsert(
+        ctx,
+        workspace_pk,
+        "root/secrets/",
+        vec![WorkspaceRole::Admin],
+    )
+    .await
+    .expect("cannot upsert prop permission");
+    assert_eq!("/root/secrets", permission.path());
+
+    let secret_path = PropPath::new(["root", "secrets", "aws_credential"]);
+    let domain_path = PropPath::new(["root", "domain", "region"]);
+
+    let user_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(user.pk()));
+    let access = PropAccess::for_context(&user_ctx)
+        .await
+        .expect("cannot gather prop access");
+    assert_eq!(Some(WorkspaceRole::Editor), access.role());
+    assert!(!access.can_access(&secret_path));
+    assert!(access.can_access(&domain_path));
+
+    // The system is never restricted.
+    let access = PropAccess::for_context(ctx)
+        .await
+        .expect("cannot gather prop access");
+    assert!(access.can_access(&secret_path));
+
+    User::set_workspace_role(ctx, user.pk(), workspace_pk, WorkspaceRole::Admin)
+        .await
+        .expect("cannot set workspace role");
+    let access = PropAccess::for_context(&user_ctx)
+        .await
+        .expect("cannot gather prop access");
+    assert!(access.can_access(&secret_path));
+
+    PropPermission::remove(ctx, workspace_pk, "/root/secrets")
+        .await
+        .expect("cannot remove prop permission");
+    assert!(PropPermission::list(ctx, workspace_pk)
+        .await
+        .expect("cannot list prop permissions")
+        .is_empty());
+}
@@ -1,9 +1,12 @@
 use dal::func::argument::FuncArgumentKind;
 use dal::{
     generate_name,
-    property_editor::{schema::PropertyEditorSchema, values::PropertyEditorValues},
+    property_editor::{
+        schema::PropertyEditorSchema, tree::PropertyEditorTreeNode, values::PropertyEditorValues,
+        PropertyEditorPropId,
+    },
     DalContext, Func, FuncArgument, FuncBackendKind, FuncBackendResponseType, LeafInput,
-    LeafInputLocation, LeafKind, PropKind, SchemaVariant, StandardModel,
+    LeafInputLocation, LeafKind, PropKind, Schema, SchemaVariant, StandardModel,
 };
 use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::test;
@@ -148,3 +151,91 @@ async fn property_editor_value(ctx: &DalContext) {
     assert_eq!(found_name.replace('"', ""), name);
     assert_eq!(si_name_value, domain_name_value);
 }
+
+#[test]
+async fn property_editor_schema_marks_socket_driven_props(ctx: &DalContext) {
+    let schema = Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema");
+    let schema_variant_id = *schema
+        .default_schema_variant_id()
+        .expect("could not get default variant id");
+
+    let property_editor_schema = PropertyEditorSchema::for_schema_variant(ctx, schema_variant_id)
+        .await
+        .expect("could not create property editor schema");
+
+    for (path, expected) in [
+        (["root", "domain", "attributes"], true),
+        (["root", "domain", "name"], false),
+    ] {
+        let prop = SchemaVariant::find_prop_in_tree(ctx, schema_variant_id, &path)
+            .await
+            .expect("could not find prop");
+        let property_editor_prop_id: PropertyEditorPropId = (*prop.id()).into();
+        let property_editor_prop = property_editor_schema
+            .props
+            .get(&property_editor_prop_id)
+            .expect("prop missing from property editor schema");
+        assert_eq!(expected, property_editor_prop.is_socket_driven);
+    }
+}
+
+#[test]
+async fn property_editor_tree(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let name = generate_name();
+    let component_bag = bagger.create_component(ctx, &name, "starfield").await;
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let tree = PropertyEditorTreeNode::for_component(ctx, component_bag.component_id)
+        .await
+        .expect("cannot fetch property editor tree for component");
+    assert_eq!("root", tree.name);
+    assert_eq!(PropKind::Object, tree.kind);
+
+    let si_name = tree
+        .child("si")
+        .and_then(|si| si.child("name"))
+        .expect("did not find \"/root/si/name\" in the tree");
+    let domain_name = tree
+        .child("domain")
+        .and_then(|domain| domain.child("name"))
+        .expect("did not find \"/root/domain/name\" in the tree");
+    assert_eq!(serde_json::json![name], si_name.value);
+    assert_eq!(si_name.value, domain_name.value);
+    assert!(si_name.children.is_empty());
+
+    // The tree holds the same values as the ones assembled value by value.
+    let property_editor_values =
+        PropertyEditorValues::for_component(ctx, component_bag.component_id)
+            .await
+            .expect("cannot create property editor values from context");
+    assert_eq!(property_editor_values.root_value_id, tree.id);
+    let mut work_queue = vec![&tree];
+    let mut node_count = 0;
+    while let Some(node) = work_queue.pop() {
+        node_count += 1;
+        let value = property_editor_values
+            .values
+            .get(&node.id)
+            .expect("tree node not found in property editor values");
+        assert_eq!(value.value(), node.value);
+        assert_eq!(
+            property_editor_values
+                .child_values
+                .get(&node.id)
+                .cloned()
+                .unwrap_or_default(),
+            node.children
+                .iter()
+                .map(|child| child.id)
+                .collect::<Vec<_>>(),
+        );
+        work_queue.extend(node.children.iter());
+    }
+    assert_eq!(property_editor_values.values.len(), node_count);
+}
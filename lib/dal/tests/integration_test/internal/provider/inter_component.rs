@@ -1,5 +1,6 @@
 use pretty_assertions_sorted::assert_eq;
 
+use dal::provider::history::ProviderValueHistoryEntry;
 use dal::{
     socket::SocketArity, AttributeContext, AttributePrototypeArgument, AttributeReadContext,
     AttributeValue, Component, ComponentView, DalContext, Edge, ExternalProvider,
@@ -234,6 +235,36 @@ async fn inter_component_identity_update(ctx: &DalContext) {
         }], // expected
         swings_bag.component_view_properties_raw(ctx).await // actual
     );
+
+    // The values that flowed through both providers were recorded, most recent first.
+    let esp_history = ProviderValueHistoryEntry::list_for_component_and_provider(
+        ctx,
+        esp_bag.component_id,
+        InternalProviderId::NONE,
+        esp_external_provider_id,
+    )
+    .await
+    .expect("could not list provider value history");
+    let esp_values: Vec<Option<&serde_json::Value>> =
+        esp_history.iter().map(|entry| entry.value()).collect();
+    assert_eq!(
+        Some(&serde_json::json!["two"]),       // expected
+        esp_values.first().copied().flatten(), // actual
+    );
+    assert!(esp_values.contains(&Some(&serde_json::json!["one"])));
+
+    let swings_history = ProviderValueHistoryEntry::list_for_component_and_provider(
+        ctx,
+        swings_bag.component_id,
+        swings_explicit_internal_provider_id,
+        ExternalProviderId::NONE,
+    )
+    .await
+    .expect("could not list provider value history");
+    assert_eq!(
+        Some(&serde_json::json!["two"]),                        // expected
+        swings_history.first().and_then(|entry| entry.value()), // actual
+    );
 }
 
 // 38.805354552534816, -77.05091482877533
@@ -0,0 +1,98 @@
+use dal::func::backend::js_action::ActionRunResult;
+use dal::{DalContext, ResourceIdentifier, ResourceIdentifierError};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use veritech_client::ResourceStatus;
+
+fn resource(payload: serde_json::Value) -> ActionRunResult {
+    ActionRunResult {
+        status: Some(ResourceStatus::Ok),
+        payload: Some(payload),
+        message: None,
+        logs: vec![],
+        last_synced: Default::default(),
+        plan: Vec::new(),
+    }
+}
+
+#[test]
+async fn register_from_resource(mut octx: DalContext) {
+    let ctx = &mut octx;
+
+    let mut bagger = ComponentBagger::new();
+    let first_bag = bagger.create_component(ctx, "first", "fallout").await;
+    let second_bag = bagger.create_component(ctx, "second", "fallout").await;
+
+    first_bag
+        .schema(ctx)
+        .await
+        .set_resource_identifier_path(ctx, Some("/GroupId"))
+        .await
+        .expect("could not set resource identifier path");
+
+    let first = first_bag.component(ctx).await;
+    first
+        .set_resource_raw(
+            ctx,
+            resource(serde_json::json![{ "GroupId": "sg-1" }]),
+            false,
+        )
+        .await
+        .expect("could not set resource");
+    let registered = ResourceIdentifier::find_by_identifier(ctx, "sg-1")
+        .await
+        .expect("could not find resource identifier")
+        .expect("resource identifier was not registered");
+    assert_eq!(first_bag.component_id, registered.component_id());
+
+    // The second component modeling the same resource does not take the identifier over.
+    let second = second_bag.component(ctx).await;
+    assert_eq!(
+        Some(("sg-1".to_owned(), first_bag.component_id)),
+        second
+            .resource_identifier_conflict(ctx, &resource(serde_json::json![{ "GroupId": "sg-1" }]))
+            .await
+            .expect("could not check for conflicts"),
+    );
+    second
+        .set_resource_raw(
+            ctx,
+            resource(serde_json::json![{ "GroupId": "sg-1" }]),
+            false,
+        )
+        .await
+        .expect("could not set resource");
+    let registered = ResourceIdentifier::find_by_identifier(ctx, "sg-1")
+        .await
+        .expect("could not find resource identifier")
+        .expect("resource identifier was not registered");
+    assert_eq!(first_bag.component_id, registered.component_id());
+    assert!(matches!(
+        ResourceIdentifier::register(ctx, second_bag.component_id, "sg-1").await,
+        Err(ResourceIdentifierError::AlreadyRegistered(_, component_id))
+            if component_id == first_bag.component_id
+    ));
+
+    // Once the first resource is gone, the identifier is free again.
+    first
+        .set_resource_raw(
+            ctx,
+            ActionRunResult {
+                payload: None,
+                ..resource(serde_json::Value::Null)
+            },
+            false,
+        )
+        .await
+        .expect("could not set resource");
+    assert!(
+        ResourceIdentifier::list_for_component(ctx, first_bag.component_id)
+            .await
+            .expect("could not list resource identifiers")
+            .is_empty()
+    );
+    ResourceIdentifier::register(ctx, second_bag.component_id, "sg-1")
+        .await
+        .expect("could not register resource identifier");
+}
@@ -248,3 +248,92 @@ async fn list_implicit_internal_providers_for_root_children(ctx: &DalContext) {
         );
     }
 }
+
+#[test]
+async fn duplicate(ctx: &DalContext) {
+    let schema = Schema::find_by_name(ctx, "starfield")
+        .await
+        .expect("could not find schema");
+    let original = schema
+        .default_variant(ctx)
+        .await
+        .expect("could not get default variant");
+
+    let mut duplicate = SchemaVariant::duplicate(ctx, *original.id(), "v1")
+        .await
+        .expect("could not duplicate schema variant");
+    assert_eq!(duplicate.name(), "v1");
+    assert_ne!(original.id(), duplicate.id());
+    assert_eq!(
+        *schema.id(),
+        *duplicate
+            .schema(ctx)
+            .await
+            .expect("could not get schema")
+            .expect("schema not found")
+            .id()
+    );
+
+    // The duplicate must be editable and finalizable like any other variant.
+    duplicate
+        .finalize(ctx, None)
+        .await
+        .expect("could not finalize duplicate");
+
+    let original_prop_paths: Vec<String> = SchemaVariant::all_props(ctx, *original.id())
+        .await
+        .expect("could not list original props")
+        .iter()
+        .map(|p| p.path().as_str().to_string())
+        .collect();
+    let duplicate_prop_ids: Vec<PropId> = SchemaVariant::all_props(ctx, *duplicate.id())
+        .await
+        .expect("could not list duplicate props")
+        .iter()
+        .map(|p| *p.id())
+        .collect();
+    assert_eq!(original_prop_paths.len(), duplicate_prop_ids.len());
+    let galaxy_prop = duplicate
+        .find_prop(ctx, &["root", "domain", "universe", "galaxies", "galaxy"])
+        .await
+        .expect("could not find duplicated prop");
+    assert!(duplicate_prop_ids.contains(galaxy_prop.id()));
+
+    let mut original_socket_names: Vec<String> = original
+        .sockets(ctx)
+        .await
+        .expect("could not list original sockets")
+        .iter()
+        .map(|s| format!("{}:{:?}", s.name(), s.edge_kind()))
+        .collect();
+    original_socket_names.sort();
+    let mut duplicate_socket_names: Vec<String> = duplicate
+        .sockets(ctx)
+        .await
+        .expect("could not list duplicate sockets")
+        .iter()
+        .map(|s| format!("{}:{:?}", s.name(), s.edge_kind()))
+        .collect();
+    duplicate_socket_names.sort();
+    assert_eq!(original_socket_names, duplicate_socket_names);
+
+    for leaf_kind in [LeafKind::CodeGeneration, LeafKind::Qualification] {
+        let mut original_funcs: Vec<String> =
+            SchemaVariant::find_leaf_item_functions(ctx, *original.id(), leaf_kind)
+                .await
+                .expect("could not find original leaf functions")
+                .iter()
+                .map(|(_, f)| f.name().to_string())
+                .collect();
+        original_funcs.sort();
+        let mut duplicate_funcs: Vec<String> =
+            SchemaVariant::find_leaf_item_functions(ctx, *duplicate.id(), leaf_kind)
+                .await
+                .expect("could not find duplicate leaf functions")
+                .iter()
+                .map(|(_, f)| f.name().to_string())
+                .collect();
+        duplicate_funcs.sort();
+        assert_eq!(original_funcs, duplicate_funcs);
+    }
+}
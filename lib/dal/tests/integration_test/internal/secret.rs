@@ -1,6 +1,6 @@
 use dal::{
-    DalContext, EncryptedSecret, Secret, SecretAlgorithm, SecretVersion, StandardModel,
-    WorkspaceSignup,
+    DalContext, EncryptedSecret, NewSecretValue, Secret, SecretAlgorithm, SecretVersion,
+    StandardModel, WorkspaceSignup,
 };
 use dal_test::{
     test,
@@ -119,3 +119,68 @@ async fn encrypt_decrypt_round_trip(ctx: &DalContext, nw: &WorkspaceSignup) {
         serde_json::to_value(&decrypted).expect("failed to serial decrypted into Value");
     assert_eq!(decrypted_value["message"], message);
 }
+
+#[test]
+async fn rotate(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let pkey = nw.key_pair.public_key();
+    let crypt = |message: &serde_json::Value| {
+        sodiumoxide::crypto::sealedbox::seal(
+            &serde_json::to_vec(message).expect("failed to serialize message"),
+            pkey,
+        )
+    };
+
+    let mut secret = EncryptedSecret::new(
+        ctx,
+        generate_fake_name(),
+        "imasecret".to_owned(),
+        None,
+        &crypt(&serde_json::json!({"song": "Bar Round Here"})),
+        nw.key_pair.pk(),
+        Default::default(),
+        Default::default(),
+    )
+    .await
+    .expect("failed to create encrypted secret");
+    let original_id = *secret.id();
+    assert_eq!(1, secret.revision());
+
+    let message = serde_json::json!({"song": "Omaha"});
+    secret
+        .rotate(
+            ctx,
+            NewSecretValue {
+                crypted: crypt(&message),
+                key_pair_pk: nw.key_pair.pk(),
+                version: SecretVersion::V1,
+                algorithm: SecretAlgorithm::Sealedbox,
+            },
+        )
+        .await
+        .expect("failed to rotate secret");
+    assert_eq!(original_id, *secret.id());
+    assert_eq!(2, secret.revision());
+
+    let rotated = Secret::get_by_id(ctx, &original_id)
+        .await
+        .expect("failed to fetch secret")
+        .expect("failed to find secret");
+    assert_eq!(2, rotated.revision());
+
+    let decrypted = EncryptedSecret::get_by_id(ctx, &original_id)
+        .await
+        .expect("failed to fetch encrypted secret")
+        .expect("failed to find encrypted secret")
+        .decrypt(ctx)
+        .await
+        .expect("failed to decrypt encrypted secret");
+    let decrypted_value =
+        serde_json::to_value(&decrypted).expect("failed to serial decrypted into Value");
+    assert_eq!(decrypted_value["message"], message);
+
+    assert!(secret
+        .list_consuming_components(ctx)
+        .await
+        .expect("failed to list consuming components")
+        .is_empty());
+}
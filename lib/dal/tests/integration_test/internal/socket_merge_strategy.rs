@@ -0,0 +1,50 @@
+use dal::{ComponentId, DalContext, MergeStrategy, SocketId, SocketMergeStrategy, StandardModel};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn strategies_are_unique_per_socket(ctx: &DalContext) {
+    let component_id = ComponentId::generate();
+    let (first_socket_id, second_socket_id) = (SocketId::generate(), SocketId::generate());
+
+    SocketMergeStrategy::upsert(
+        ctx,
+        component_id,
+        first_socket_id,
+        MergeStrategy::ArrayAppend,
+    )
+    .await
+    .expect("cannot upsert strategy");
+    SocketMergeStrategy::upsert(ctx, component_id, second_socket_id, MergeStrategy::KeyedMap)
+        .await
+        .expect("cannot upsert strategy");
+    let mut replaced =
+        SocketMergeStrategy::upsert(ctx, component_id, first_socket_id, MergeStrategy::FirstWins)
+            .await
+            .expect("cannot upsert strategy");
+
+    let strategies = SocketMergeStrategy::list_for_component(ctx, component_id)
+        .await
+        .expect("cannot list strategies");
+    assert_eq!(2, strategies.len());
+    let strategy = strategies
+        .iter()
+        .find(|strategy| strategy.socket_id() == first_socket_id)
+        .expect("strategy not found");
+    assert_eq!(MergeStrategy::FirstWins, *strategy.strategy());
+
+    replaced
+        .delete_by_id(ctx)
+        .await
+        .expect("cannot delete strategy");
+    let strategies = SocketMergeStrategy::list_for_component(ctx, component_id)
+        .await
+        .expect("cannot list strategies");
+    assert_eq!(
+        vec![second_socket_id],
+        strategies
+            .iter()
+            .map(SocketMergeStrategy::socket_id)
+            .collect::<Vec<_>>()
+    );
+}
@@ -1,4 +1,4 @@
-use dal::{DalContext, User, UserPk, WorkspaceSignup};
+use dal::{DalContext, Permission, User, UserPk, WorkspacePk, WorkspaceRole, WorkspaceSignup};
 use dal_test::test;
 
 #[test]
@@ -14,6 +14,64 @@ async fn new(ctx: &DalContext) {
     .expect("cannot create user");
 }
 
+#[test]
+async fn has_permission(ctx: &DalContext) {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+    let user = User::new(
+        ctx,
+        UserPk::generate(),
+        "lemmy",
+        "lemmy@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+
+    // Not a member yet, so no permissions at all.
+    assert!(
+        !User::has_permission(ctx, user.pk(), workspace_pk, Permission::DiagramRead)
+            .await
+            .expect("cannot check permission")
+    );
+
+    user.associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("cannot associate workspace");
+    User::set_workspace_role(ctx, user.pk(), workspace_pk, WorkspaceRole::Viewer)
+        .await
+        .expect("cannot set workspace role");
+    assert!(
+        User::has_permission(ctx, user.pk(), workspace_pk, Permission::DiagramRead)
+            .await
+            .expect("cannot check permission")
+    );
+    assert!(
+        !User::has_permission(ctx, user.pk(), workspace_pk, Permission::DiagramWrite)
+            .await
+            .expect("cannot check permission")
+    );
+
+    User::set_workspace_role(ctx, user.pk(), workspace_pk, WorkspaceRole::Editor)
+        .await
+        .expect("cannot set workspace role");
+    assert!(
+        User::has_permission(ctx, user.pk(), workspace_pk, Permission::DiagramWrite)
+            .await
+            .expect("cannot check permission")
+    );
+    assert!(!User::has_permission(
+        ctx,
+        user.pk(),
+        WorkspacePk::generate(),
+        Permission::DiagramRead
+    )
+    .await
+    .expect("cannot check permission"));
+}
+
 #[test]
 async fn authorize(_ctx: &DalContext, _nw: &WorkspaceSignup) {
     // let worked = User::authorize(ctx, &nw.user.pk(), &nw.workspace.pk())
@@ -0,0 +1,235 @@
+use chrono::{Duration, Utc};
+use dal::webhook::{webhook_backoff, WEBHOOK_MAX_ATTEMPTS};
+use dal::{
+    DalContext, Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookError, WebhookEventKind,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn deliveries_are_enqueued_for_subscribed_webhooks(ctx: &DalContext) {
+    let result = Webhook::new(ctx, "ftp://hooks.example.com", vec![], true).await;
+    assert!(matches!(result, Err(WebhookError::InvalidUrl(_))));
+    let result = Webhook::new(ctx, "https://hooks.example.com", vec![], true).await;
+    assert!(matches!(result, Err(WebhookError::NoEvents)));
+
+    let applied = Webhook::new(
+        ctx,
+        "https://hooks.example.com/applied",
+        vec![WebhookEventKind::ChangeSetApplied],
+        true,
+    )
+    .await
+    .expect("cannot create webhook");
+    let everything = Webhook::new(
+        ctx,
+        "https://hooks.example.com/everything",
+        vec![
+            WebhookEventKind::ActionCompleted,
+            WebhookEventKind::ChangeSetApplied,
+            WebhookEventKind::QualificationFailed,
+        ],
+        true,
+    )
+    .await
+    .expect("cannot create webhook");
+    let disabled = Webhook::new(
+        ctx,
+        "https://hooks.example.com/disabled",
+        vec![WebhookEventKind::ChangeSetApplied],
+        false,
+    )
+    .await
+    .expect("cannot create webhook");
+    assert_eq!(
+        vec![applied.pk(), everything.pk(), disabled.pk()],
+        Webhook::list(ctx)
+            .await
+            .expect("cannot list webhooks")
+            .iter()
+            .map(Webhook::pk)
+            .collect::<Vec<_>>()
+    );
+
+    let deliveries = Webhook::enqueue(
+        ctx,
+        WebhookEventKind::ChangeSetApplied,
+        serde_json::json!({ "name": "poop" }),
+    )
+    .await
+    .expect("cannot enqueue deliveries");
+    let mut webhook_pks: Vec<_> = deliveries.iter().map(WebhookDelivery::webhook_pk).collect();
+    webhook_pks.sort();
+    let mut expected = vec![applied.pk(), everything.pk()];
+    expected.sort();
+    assert_eq!(expected, webhook_pks);
+    let delivery = &deliveries[0];
+    assert_eq!(WebhookDeliveryStatus::Pending, delivery.status());
+    assert_eq!(0, delivery.attempts());
+    assert_eq!(
+        Some("changeSetApplied"),
+        delivery.payload()["event"].as_str()
+    );
+    assert_eq!(Some("poop"), delivery.payload()["data"]["name"].as_str());
+
+    let deliveries = Webhook::enqueue(
+        ctx,
+        WebhookEventKind::QualificationFailed,
+        serde_json::json!({}),
+    )
+    .await
+    .expect("cannot enqueue deliveries");
+    assert_eq!(1, deliveries.len());
+    assert_eq!(everything.pk(), deliveries[0].webhook_pk());
+
+    // Enabling a webhook lets it receive events again, and deleting one drops its deliveries.
+    Webhook::update(
+        ctx,
+        disabled.pk(),
+        disabled.url(),
+        disabled.events().to_vec(),
+        true,
+    )
+    .await
+    .expect("cannot update webhook");
+    Webhook::delete(ctx, everything.pk())
+        .await
+        .expect("cannot delete webhook");
+    let deliveries = Webhook::enqueue(
+        ctx,
+        WebhookEventKind::ChangeSetApplied,
+        serde_json::json!({}),
+    )
+    .await
+    .expect("cannot enqueue deliveries");
+    assert_eq!(2, deliveries.len());
+    assert!(WebhookDelivery::list_for_webhook(ctx, everything.pk(), 50)
+        .await
+        .expect("cannot list deliveries")
+        .is_empty());
+    assert!(matches!(
+        Webhook::delete(ctx, everything.pk()).await,
+        Err(WebhookError::NotFound(_))
+    ));
+}
+
+#[test]
+async fn failed_deliveries_are_retried_with_backoff(ctx: &DalContext) {
+    let webhook = Webhook::new(
+        ctx,
+        "https://hooks.example.com",
+        vec![WebhookEventKind::ActionCompleted],
+        true,
+    )
+    .await
+    .expect("cannot create webhook");
+    let mut delivery = Webhook::enqueue(
+        ctx,
+        WebhookEventKind::ActionCompleted,
+        serde_json::json!({}),
+    )
+    .await
+    .expect("cannot enqueue deliveries")
+    .pop()
+    .expect("no delivery enqueued");
+
+    // A claimed delivery is not due again until its lease runs out.
+    let now = Utc::now() + Duration::seconds(1);
+    assert!(WebhookDelivery::list_due(ctx, now, 1000)
+        .await
+        .expect("cannot list due deliveries")
+        .iter()
+        .any(|due| due.pk() == delivery.pk()));
+    WebhookDelivery::claim(ctx, delivery.pk(), now)
+        .await
+        .expect("cannot claim delivery")
+        .expect("delivery not claimed");
+    assert!(WebhookDelivery::claim(ctx, delivery.pk(), now)
+        .await
+        .expect("cannot claim delivery")
+        .is_none());
+
+    let attempted_at = Utc::now();
+    delivery
+        .record_attempt(ctx, attempted_at, Some(503), Some("unavailable".to_owned()))
+        .await
+        .expect("cannot record attempt");
+    assert_eq!(WebhookDeliveryStatus::Pending, delivery.status());
+    assert_eq!(1, delivery.attempts());
+    assert_eq!(Some(503), delivery.last_response_status());
+    assert_eq!(Some("unavailable"), delivery.last_error());
+    assert_eq!(
+        (attempted_at + webhook_backoff(1)).timestamp_millis(),
+        delivery.next_attempt_at().timestamp_millis()
+    );
+
+    while delivery.attempts() < WEBHOOK_MAX_ATTEMPTS {
+        delivery
+            .record_attempt(ctx, Utc::now(), None, Some("unreachable".to_owned()))
+            .await
+            .expect("cannot record attempt");
+    }
+    assert_eq!(WebhookDeliveryStatus::Failed, delivery.status());
+    assert!(delivery.delivered_at().is_none());
+    assert!(
+        !WebhookDelivery::list_due(ctx, Utc::now() + Duration::days(1), 1000)
+            .await
+            .expect("cannot list due deliveries")
+            .iter()
+            .any(|due| due.pk() == delivery.pk())
+    );
+
+    let mut delivery = Webhook::enqueue(
+        ctx,
+        WebhookEventKind::ActionCompleted,
+        serde_json::json!({}),
+    )
+    .await
+    .expect("cannot enqueue deliveries")
+    .pop()
+    .expect("no delivery enqueued");
+    delivery
+        .record_attempt(ctx, Utc::now(), Some(200), None)
+        .await
+        .expect("cannot record attempt");
+    assert_eq!(WebhookDeliveryStatus::Succeeded, delivery.status());
+    assert!(delivery.delivered_at().is_some());
+
+    let deliveries = WebhookDelivery::list_for_webhook(ctx, webhook.pk(), 50)
+        .await
+        .expect("cannot list deliveries");
+    assert_eq!(2, deliveries.len());
+}
+
+#[test]
+async fn unreachable_receivers_fail_the_attempt(ctx: &DalContext) {
+    // Nothing listens on the port once the listener is dropped.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("cannot bind listener");
+    let url = format!(
+        "http://{}/hook",
+        listener.local_addr().expect("no local addr")
+    );
+    drop(listener);
+
+    let webhook = Webhook::new(ctx, url, vec![WebhookEventKind::ChangeSetApplied], true)
+        .await
+        .expect("cannot create webhook");
+    let mut delivery = Webhook::enqueue(
+        ctx,
+        WebhookEventKind::ChangeSetApplied,
+        serde_json::json!({}),
+    )
+    .await
+    .expect("cannot enqueue deliveries")
+    .pop()
+    .expect("no delivery enqueued");
+
+    delivery
+        .deliver(ctx, &webhook)
+        .await
+        .expect("cannot deliver");
+    assert_eq!(WebhookDeliveryStatus::Pending, delivery.status());
+    assert_eq!(1, delivery.attempts());
+    assert!(delivery.last_error().is_some());
+    assert!(delivery.last_response_status().is_none());
+}
@@ -1,5 +1,25 @@
-use dal::{DalContext, Workspace, WorkspacePk};
+use chrono::{Duration, Utc};
+use dal::component::view::ComponentView;
+use dal::func::binding::FuncBindingId;
+use dal::func::execution::FuncExecutionPk;
+use dal::workspace::digest::{
+    WorkspaceDigest, WorkspaceDigestConfig, RESOURCE_DRIFTED_HISTORY_EVENT_LABEL,
+};
+use dal::workspace::retention::{
+    WorkspaceRetentionPolicy, DEFAULT_FUNC_BINDING_RETURN_VALUE_HISTORY_HOURS,
+};
+use dal::workspace::script::{MutationScript, MutationScriptOperation, MutationScriptParameter};
+use dal::workspace::template::WorkspaceTemplate;
+use dal::{
+    ChangeSet, Component, ComponentId, DalContext, FuncBindingReturnValue, FuncId, HistoryEvent,
+    PropKind, RootPropChild, StandardModel, Visibility, Workspace, WorkspacePk,
+};
 use dal_test::test;
+use dal_test::test_harness::{
+    create_prop_without_ui_optionals, create_schema, create_schema_variant_with_root,
+};
+use pretty_assertions_sorted::assert_eq;
+use std::collections::{HashMap, HashSet};
 
 #[test]
 async fn new(ctx: &mut DalContext) {
@@ -7,3 +27,349 @@ async fn new(ctx: &mut DalContext) {
         .await
         .expect("cannot create workspace");
 }
+
+#[test]
+async fn digest_summarizes_recent_activity(ctx: &mut DalContext) {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.update_visibility(Visibility::new_head(false));
+
+    let component_id = ComponentId::generate();
+    HistoryEvent::new(
+        ctx,
+        RESOURCE_DRIFTED_HISTORY_EVENT_LABEL,
+        "Resource drifted",
+        &serde_json::json![{
+            "componentId": component_id,
+            "componentName": "mastodon",
+        }],
+    )
+    .await
+    .expect("could not create history event");
+
+    let until = Utc::now() + Duration::minutes(1);
+    let digest = WorkspaceDigest::build(ctx, workspace_pk, 24, until)
+        .await
+        .expect("could not build digest");
+
+    assert!(!digest.is_empty());
+    assert_eq!(
+        vec![change_set.name.clone()], // expected
+        digest
+            .applied_change_sets
+            .iter()
+            .map(|change_set| change_set.name.clone())
+            .collect::<Vec<String>>(), // actual
+    );
+    assert_eq!(1, digest.drifted_resources.len());
+    assert_eq!(component_id, digest.drifted_resources[0].component_id);
+    assert!(digest.text.contains("mastodon"));
+
+    // Nothing happened in the window that closed before the activity above.
+    let digest = WorkspaceDigest::build(ctx, workspace_pk, 1, until - Duration::hours(2))
+        .await
+        .expect("could not build digest");
+    assert!(digest.is_empty());
+}
+
+#[test]
+async fn digest_config_is_due_once_per_window(ctx: &DalContext) {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+    assert!(WorkspaceDigestConfig::get(ctx, workspace_pk)
+        .await
+        .expect("could not get digest config")
+        .is_none());
+
+    let mut config = WorkspaceDigestConfig::upsert(
+        ctx,
+        workspace_pk,
+        true,
+        6,
+        Some("http://localhost/digest".to_owned()),
+    )
+    .await
+    .expect("could not upsert digest config");
+    assert_eq!(6, config.window_hours());
+
+    let now = Utc::now();
+    let is_due = |configs: Vec<WorkspaceDigestConfig>| {
+        configs
+            .iter()
+            .any(|config| config.workspace_pk() == workspace_pk)
+    };
+    assert!(is_due(
+        WorkspaceDigestConfig::list_due(ctx, now)
+            .await
+            .expect("could not list due configs")
+    ));
+
+    config
+        .mark_sent(ctx, now)
+        .await
+        .expect("could not mark digest sent");
+    assert!(!is_due(
+        WorkspaceDigestConfig::list_due(ctx, now + Duration::hours(1))
+            .await
+            .expect("could not list due configs")
+    ));
+    assert!(is_due(
+        WorkspaceDigestConfig::list_due(ctx, now + Duration::hours(6))
+            .await
+            .expect("could not list due configs")
+    ));
+
+    WorkspaceDigestConfig::upsert(ctx, workspace_pk, false, 6, None)
+        .await
+        .expect("could not upsert digest config");
+    assert!(!is_due(
+        WorkspaceDigestConfig::list_due(ctx, now + Duration::hours(6))
+            .await
+            .expect("could not list due configs")
+    ));
+    assert!(
+        WorkspaceDigestConfig::upsert(ctx, workspace_pk, true, 0, None)
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+async fn builtin_templates_refer_to_their_own_components(_ctx: &DalContext) {
+    let templates = WorkspaceTemplate::list().expect("could not list workspace templates");
+    assert!(!templates.is_empty());
+
+    for template in templates {
+        assert_eq!(
+            Some(&template),
+            WorkspaceTemplate::find_by_name(&template.name)
+                .expect("could not find workspace template")
+                .as_ref()
+        );
+        assert!(!template.packages.is_empty());
+
+        let keys: HashSet<&str> = template
+            .components
+            .iter()
+            .map(|component| component.key.as_str())
+            .collect();
+        assert_eq!(template.components.len(), keys.len());
+        for component in &template.components {
+            if let Some(parent) = &component.parent {
+                assert!(keys.contains(parent.as_str()));
+            }
+        }
+        for connection in &template.connections {
+            assert!(keys.contains(connection.from.as_str()));
+            assert!(keys.contains(connection.to.as_str()));
+        }
+    }
+}
+
+#[test]
+async fn mutation_scripts_are_versioned_and_run_in_new_change_sets(ctx: &mut DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let _rule_prop = create_prop_without_ui_optionals(
+        ctx,
+        "rule",
+        PropKind::String,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await;
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize schema variant");
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "security group", *schema.id())
+            .await
+            .expect("could not create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Scripts run in change sets created from head, so the component has to be there.
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.update_visibility(Visibility::new_head(false));
+
+    let parameters = vec![
+        MutationScriptParameter {
+            name: "target".to_owned(),
+            description: None,
+            default: None,
+        },
+        MutationScriptParameter {
+            name: "port".to_owned(),
+            description: None,
+            default: Some(serde_json::json!(443)),
+        },
+    ];
+    let operations = vec![MutationScriptOperation::SetDomain {
+        component: "{{target}}".to_owned(),
+        domain: serde_json::json!({ "rule": "allow tcp/{{port}}" }),
+    }];
+
+    assert!(
+        MutationScript::save(ctx, "ingress", None, Vec::new(), operations.clone())
+            .await
+            .is_err()
+    );
+    let first = MutationScript::save(ctx, "ingress", None, parameters.clone(), Vec::new())
+        .await
+        .expect("could not save mutation script");
+    let second = MutationScript::save(ctx, "ingress", None, parameters, operations)
+        .await
+        .expect("could not save mutation script");
+    assert_eq!(1, first.version());
+    assert_eq!(2, second.version());
+    assert_eq!(
+        Some(second.clone()),
+        MutationScript::get(ctx, "ingress", None)
+            .await
+            .expect("could not get mutation script")
+    );
+    assert_eq!(
+        vec![1, 2],
+        MutationScript::list_versions(ctx, "ingress")
+            .await
+            .expect("could not list versions")
+            .iter()
+            .map(MutationScript::version)
+            .collect::<Vec<i32>>()
+    );
+
+    assert!(second.run(ctx, HashMap::new()).await.is_err());
+    let change_set_pk = second
+        .run(
+            ctx,
+            HashMap::from([(
+                "target".to_owned(),
+                serde_json::json!(component.id().to_string()),
+            )]),
+        )
+        .await
+        .expect("could not run mutation script");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let script_ctx = ctx.clone_with_new_visibility(Visibility::new(change_set_pk, None));
+    assert_eq!(
+        serde_json::json!("allow tcp/443"),
+        ComponentView::new(&script_ctx, *component.id())
+            .await
+            .expect("could not get component view")
+            .properties["domain"]["rule"]
+    );
+}
+
+#[test]
+async fn retention_prunes_superseded_func_binding_return_values(ctx: &DalContext) {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .expect("no workspace in tenancy");
+    let policy = WorkspaceRetentionPolicy::get(ctx, workspace_pk)
+        .await
+        .expect("could not get retention policy")
+        .expect("retention policy not found");
+    assert_eq!(
+        DEFAULT_FUNC_BINDING_RETURN_VALUE_HISTORY_HOURS,
+        policy.func_binding_return_value_history_hours()
+    );
+    let policy = WorkspaceRetentionPolicy::upsert(ctx, workspace_pk, 0)
+        .await
+        .expect("could not upsert retention policy");
+    assert_eq!(0, policy.func_binding_return_value_history_hours());
+    assert!(WorkspaceRetentionPolicy::upsert(ctx, workspace_pk, -1)
+        .await
+        .is_err());
+
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, _) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize schema variant");
+    let (component, _) = Component::new_for_default_variant_from_schema(ctx, "kept", *schema.id())
+        .await
+        .expect("could not create component");
+    let domain_attribute_value = Component::root_prop_child_attribute_value_for_component(
+        ctx,
+        *component.id(),
+        RootPropChild::Domain,
+    )
+    .await
+    .expect("could not get domain attribute value");
+
+    let superseded = FuncBindingReturnValue::new(
+        ctx,
+        Some(serde_json::json!("stale")),
+        Some(serde_json::json!("stale")),
+        FuncId::NONE,
+        FuncBindingId::generate(),
+        FuncExecutionPk::NONE,
+    )
+    .await
+    .expect("could not create func binding return value");
+
+    // Nothing is old enough yet.
+    WorkspaceRetentionPolicy::prune_func_binding_return_values(
+        ctx,
+        workspace_pk,
+        Utc::now() - Duration::hours(1),
+    )
+    .await
+    .expect("could not prune");
+    assert!(FuncBindingReturnValue::get_by_id(ctx, superseded.id())
+        .await
+        .expect("could not get func binding return value")
+        .is_some());
+
+    let deleted = policy
+        .prune(ctx, Utc::now() + Duration::hours(1))
+        .await
+        .expect("could not prune");
+    assert!(deleted >= 1);
+    assert!(FuncBindingReturnValue::get_by_id(ctx, superseded.id())
+        .await
+        .expect("could not get func binding return value")
+        .is_none());
+    assert!(FuncBindingReturnValue::get_by_id(
+        ctx,
+        &domain_attribute_value.func_binding_return_value_id()
+    )
+    .await
+    .expect("could not get func binding return value")
+    .is_some());
+}
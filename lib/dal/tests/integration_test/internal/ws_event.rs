@@ -0,0 +1,50 @@
+use dal::{ChangeSetPk, DalContext, WsEvent};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn replayable_events_are_replayed_in_cursor_order(ctx: &DalContext) {
+    let mut first = WsEvent::change_set_written(ctx, ChangeSetPk::generate())
+        .await
+        .expect("cannot create event");
+    first.record(ctx).await.expect("cannot record event");
+    let mut created = WsEvent::change_set_created(ctx, ChangeSetPk::generate())
+        .await
+        .expect("cannot create event");
+    created.record(ctx).await.expect("cannot record event");
+    let mut second = WsEvent::change_set_written(ctx, ChangeSetPk::generate())
+        .await
+        .expect("cannot create event");
+    second.record(ctx).await.expect("cannot record event");
+
+    // Only replayable events get a cursor, and cursors only go up.
+    assert!(created.cursor().is_none());
+    let first_cursor = first.cursor().expect("no cursor recorded");
+    let second_cursor = second.cursor().expect("no cursor recorded");
+    assert!(first_cursor < second_cursor);
+
+    let replay = WsEvent::replay(ctx, 0, 100)
+        .await
+        .expect("cannot replay events");
+    assert!(replay.complete);
+    assert_eq!(vec![first.clone(), second.clone()], replay.events);
+
+    let replay = WsEvent::replay(ctx, first_cursor, 100)
+        .await
+        .expect("cannot replay events");
+    assert!(replay.complete);
+    assert_eq!(vec![second.clone()], replay.events);
+
+    // A client that missed more than the limit is told so.
+    let replay = WsEvent::replay(ctx, 0, 1)
+        .await
+        .expect("cannot replay events");
+    assert!(!replay.complete);
+    assert_eq!(vec![first], replay.events);
+
+    let replay = WsEvent::replay(ctx, second_cursor, 100)
+        .await
+        .expect("cannot replay events");
+    assert!(replay.complete);
+    assert!(replay.events.is_empty());
+}
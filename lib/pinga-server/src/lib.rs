@@ -11,11 +11,16 @@ pub use crate::{
 
 const NATS_JOBS_DEFAULT_SUBJECT: &str = "pinga-jobs";
 const NATS_JOBS_DEFAULT_QUEUE: &str = "pinga";
+const NATS_JOBS_CANCELLATION_SUBJECT: &str = "pinga-jobs-cancel";
 
 pub fn nats_jobs_subject(prefix: Option<&str>) -> String {
     nats_subject(prefix, NATS_JOBS_DEFAULT_SUBJECT)
 }
 
+pub fn nats_jobs_cancellation_subject(prefix: Option<&str>) -> String {
+    nats_subject(prefix, NATS_JOBS_CANCELLATION_SUBJECT)
+}
+
 fn nats_subject(prefix: Option<&str>, suffix: impl AsRef<str>) -> String {
     let suffix = suffix.as_ref();
     match prefix {
@@ -1,4 +1,8 @@
-use std::{io, sync::Arc};
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, Mutex},
+};
 
 use dal::{
     job::{
@@ -6,8 +10,9 @@ use dal::{
         definition::{FixesJob, RefreshJob},
         producer::BlockingJobError,
     },
-    DalContext, DalContextBuilder, DependentValuesUpdate, InitializationError, JobFailure,
-    JobFailureError, JobQueueProcessor, NatsProcessor, ServicesContext, TransactionsError,
+    ChangeSetPk, DalContext, DalContextBuilder, DependentValuesUpdate, InitializationError,
+    JobFailure, JobFailureError, JobQueueProcessor, NatsProcessor, ServicesContext,
+    TransactionsError,
 };
 use futures::{FutureExt, Stream, StreamExt};
 use nats_subscriber::{Request, SubscriberError};
@@ -25,12 +30,12 @@ use tokio::{
         mpsc::{self, UnboundedReceiver, UnboundedSender},
         oneshot, watch,
     },
-    task,
+    task::{self, AbortHandle},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use veritech_client::{Client as VeritechClient, CycloneEncryptionKey, CycloneEncryptionKeyError};
 
-use crate::{nats_jobs_subject, Config, NATS_JOBS_DEFAULT_QUEUE};
+use crate::{nats_jobs_cancellation_subject, nats_jobs_subject, Config, NATS_JOBS_DEFAULT_QUEUE};
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -171,11 +176,20 @@ impl Server {
             .map_err(|e| ServerError::UnableToConnectToDatabase(Box::new(e)))?;
 
         let (tx, rx) = mpsc::unbounded_channel();
+        let running_jobs = RunningJobs::default();
 
         // Span a task to receive and process jobs from the unbounded channel
         drop(task::spawn(process_job_requests_task(
             rx,
             self.concurrency_limit,
+            running_jobs.clone(),
+        )));
+
+        // Span a task to abort the running jobs of change sets that were abandoned or applied
+        drop(task::spawn(receive_cancellations_task(
+            self.services_context.clone(),
+            running_jobs,
+            self.shutdown_watch_rx.clone(),
         )));
 
         // Run "the main loop" which pulls message from a subscription off NATS and forwards each
@@ -343,7 +357,98 @@ async fn receive_job_requests(
     Ok(())
 }
 
-async fn process_job_requests_task(rx: UnboundedReceiver<JobItem>, concurrency_limit: usize) {
+/// The jobs currently executing, keyed by the change set they were enqueued for, so they can be
+/// aborted when that change set is abandoned or applied.
+#[derive(Clone, Debug, Default)]
+struct RunningJobs(Arc<Mutex<HashMap<ChangeSetPk, HashMap<String, AbortHandle>>>>);
+
+impl RunningJobs {
+    fn insert(&self, change_set_pk: ChangeSetPk, job_id: String, abort_handle: AbortHandle) {
+        if change_set_pk == ChangeSetPk::NONE {
+            return;
+        }
+        if let Ok(mut running_jobs) = self.0.lock() {
+            running_jobs
+                .entry(change_set_pk)
+                .or_default()
+                .insert(job_id, abort_handle);
+        }
+    }
+
+    fn remove(&self, change_set_pk: ChangeSetPk, job_id: &str) {
+        if let Ok(mut running_jobs) = self.0.lock() {
+            if let Some(jobs) = running_jobs.get_mut(&change_set_pk) {
+                jobs.remove(job_id);
+                if jobs.is_empty() {
+                    running_jobs.remove(&change_set_pk);
+                }
+            }
+        }
+    }
+
+    fn abort_change_set(&self, change_set_pk: ChangeSetPk) -> usize {
+        let jobs = match self.0.lock() {
+            Ok(mut running_jobs) => running_jobs.remove(&change_set_pk).unwrap_or_default(),
+            Err(_) => return 0,
+        };
+        for abort_handle in jobs.values() {
+            abort_handle.abort();
+        }
+        jobs.len()
+    }
+}
+
+async fn receive_cancellations_task(
+    services_context: ServicesContext,
+    running_jobs: RunningJobs,
+    shutdown_watch_rx: watch::Receiver<()>,
+) {
+    if let Err(err) = receive_cancellations(services_context, running_jobs, shutdown_watch_rx).await
+    {
+        warn!(error = ?err, "processing job cancellations failed");
+    }
+}
+
+async fn receive_cancellations(
+    services_context: ServicesContext,
+    running_jobs: RunningJobs,
+    mut shutdown_watch_rx: watch::Receiver<()>,
+) -> Result<()> {
+    let nats = services_context.nats_conn().clone();
+    let subject = nats_jobs_cancellation_subject(nats.metadata().subject_prefix());
+    debug!(
+        messaging.destination.name = subject.as_str(),
+        "subscribing for job cancellations"
+    );
+
+    // Every instance needs to hear about cancellations, so there is no queue group here.
+    let mut requests = nats_subscriber::Subscriber::create(subject)
+        .start(&nats)
+        .await?
+        .take_until_if(Box::pin(shutdown_watch_rx.changed().map(|_| true)));
+
+    while let Some(request) = requests.next().await {
+        match request {
+            Ok(request) => {
+                let change_set_pk: ChangeSetPk = request.payload;
+                let aborted = running_jobs.abort_change_set(change_set_pk);
+                info!(%change_set_pk, aborted, "cancelled jobs for change set");
+            }
+            Err(err) => {
+                warn!(error = ?err, "next job cancellation had an error");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn process_job_requests_task(
+    rx: UnboundedReceiver<JobItem>,
+    concurrency_limit: usize,
+    running_jobs: RunningJobs,
+) {
+    let running_jobs = &running_jobs;
     UnboundedReceiverStream::new(rx)
         .for_each_concurrent(concurrency_limit, |job| async move {
             // Got the next message from the subscriber
@@ -351,10 +456,38 @@ async fn process_job_requests_task(rx: UnboundedReceiver<JobItem>, concurrency_l
 
             match job.request {
                 Ok(request) => {
+                    let change_set_pk = request.payload.visibility.change_set_pk;
+                    let job_id = request.payload.id.clone();
+                    let maybe_reply_channel = request.reply.clone();
+                    let nats = job.ctx_builder.nats_conn().clone();
+
                     // Spawn a task and process the request
                     let join_handle =
                         task::spawn(execute_job_task(job.metadata, job.ctx_builder, request));
-                    if let Err(err) = join_handle.await {
+                    running_jobs.insert(change_set_pk, job_id.clone(), join_handle.abort_handle());
+                    let result = join_handle.await;
+                    running_jobs.remove(change_set_pk, &job_id);
+
+                    if let Err(err) = result {
+                        if err.is_cancelled() {
+                            info!(job.id = job_id, %change_set_pk, "job cancelled");
+                            // A blocking caller is still waiting on a reply.
+                            if let Some(reply_channel) = maybe_reply_channel {
+                                let reply_message: std::result::Result<(), BlockingJobError> =
+                                    Err(BlockingJobError::JobExecution(format!(
+                                        "job cancelled: change set {change_set_pk} was closed"
+                                    )));
+                                if let Ok(message) = serde_json::to_vec(&reply_message) {
+                                    if let Err(err) =
+                                        nats.publish(reply_channel, message.into()).await
+                                    {
+                                        error!(error = ?err, "Unable to notify spawning job of cancellation");
+                                    }
+                                }
+                            }
+                            return;
+                        }
+
                         // NOTE(fnichol): This likely happens when there is contention or
                         // an error in the Tokio runtime so we will be loud and log an
                         // error under the assumptions that 1) this event rarely
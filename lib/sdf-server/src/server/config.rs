@@ -17,12 +17,15 @@ use si_std::{CanonicalFile, CanonicalFileError, SensitiveString};
 use telemetry::prelude::*;
 use thiserror::Error;
 
-pub use dal::MigrationMode;
+pub use dal::{BuiltinSchemaMigrationMode, MigrationMode};
 pub use si_crypto::CycloneKeyPair;
 pub use si_settings::{StandardConfig, StandardConfigFile};
 
 const DEFAULT_SIGNUP_SECRET: &str = "cool-steam";
 const DEFAULT_MODULE_INDEX_URL: &str = "https://module-index.systeminit.com";
+/// Matches the default body limit of axum.
+const DEFAULT_BODY_LIMIT_BYTES: usize = 2 * 1024 * 1024;
+const DEFAULT_LARGE_BODY_LIMIT_BYTES: usize = 256 * 1024 * 1024;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -72,6 +75,12 @@ pub struct Config {
     #[builder(default = "MigrationMode::default()")]
     migration_mode: MigrationMode,
 
+    #[builder(default = "BuiltinSchemaMigrationMode::default()")]
+    builtin_schema_migration_mode: BuiltinSchemaMigrationMode,
+
+    #[builder(default = "BodyLimits::default()")]
+    body_limits: BodyLimits,
+
     #[builder(default = "CryptoConfig::default()")]
     crypto: CryptoConfig,
 
@@ -105,6 +114,12 @@ impl Config {
         &self.migration_mode
     }
 
+    /// Gets a reference to the config's builtin schema migration mode.
+    #[must_use]
+    pub fn builtin_schema_migration_mode(&self) -> &BuiltinSchemaMigrationMode {
+        &self.builtin_schema_migration_mode
+    }
+
     /// Gets a reference to the config's nats.
     #[must_use]
     pub fn nats(&self) -> &NatsConfig {
@@ -150,6 +165,12 @@ impl Config {
     pub fn module_index_url(&self) -> &str {
         &self.module_index_url
     }
+
+    /// Gets the config's request body size limits.
+    #[must_use]
+    pub fn body_limits(&self) -> BodyLimits {
+        self.body_limits
+    }
 }
 
 impl ConfigBuilder {
@@ -171,6 +192,8 @@ pub struct ConfigFile {
     #[serde(default)]
     pub migration_mode: MigrationMode,
     #[serde(default)]
+    pub builtin_schema_migration_mode: BuiltinSchemaMigrationMode,
+    #[serde(default)]
     pub jwt_signing_public_key: JwtConfig,
     #[serde(default)]
     pub crypto: CryptoConfig,
@@ -184,6 +207,8 @@ pub struct ConfigFile {
     pub module_index_url: String,
     #[serde(default = "default_symmetric_crypto_config")]
     symmetric_crypto_service: SymmetricCryptoServiceConfigFile,
+    #[serde(default)]
+    pub body_limits: BodyLimits,
 }
 
 impl Default for ConfigFile {
@@ -192,6 +217,7 @@ impl Default for ConfigFile {
             pg: Default::default(),
             nats: Default::default(),
             migration_mode: Default::default(),
+            builtin_schema_migration_mode: Default::default(),
             jwt_signing_public_key: Default::default(),
             crypto: Default::default(),
             signup_secret: default_signup_secret(),
@@ -199,6 +225,7 @@ impl Default for ConfigFile {
             posthog: Default::default(),
             module_index_url: default_module_index_url(),
             symmetric_crypto_service: default_symmetric_crypto_config(),
+            body_limits: Default::default(),
         }
     }
 }
@@ -217,6 +244,7 @@ impl TryFrom<ConfigFile> for Config {
         config.pg_pool(value.pg);
         config.nats(value.nats);
         config.migration_mode(value.migration_mode);
+        config.builtin_schema_migration_mode(value.builtin_schema_migration_mode);
         config.jwt_signing_public_key(value.jwt_signing_public_key);
         config.crypto(value.crypto);
         config.signup_secret(value.signup_secret);
@@ -224,6 +252,7 @@ impl TryFrom<ConfigFile> for Config {
         config.posthog(value.posthog);
         config.module_index_url(value.module_index_url);
         config.symmetric_crypto_service(value.symmetric_crypto_service.try_into()?);
+        config.body_limits(value.body_limits);
         config.build().map_err(Into::into)
     }
 }
@@ -257,6 +286,27 @@ impl IncomingStream {
     }
 }
 
+/// The maximum sizes of request bodies, in bytes. Requests with larger bodies are rejected with a
+/// "413 Payload Too Large" error.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct BodyLimits {
+    /// The limit of most routes.
+    pub default_bytes: usize,
+    /// The limit of the routes receiving large payloads, such as package uploads and asset
+    /// definitions.
+    pub large_bytes: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            default_bytes: DEFAULT_BODY_LIMIT_BYTES,
+            large_bytes: DEFAULT_LARGE_BODY_LIMIT_BYTES,
+        }
+    }
+}
+
 fn default_signup_secret() -> SensitiveString {
     DEFAULT_SIGNUP_SECRET.into()
 }
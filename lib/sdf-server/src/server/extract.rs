@@ -1,14 +1,14 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, marker::PhantomData};
 
 use axum::{
     async_trait,
     extract::{FromRequestParts, Query},
-    http::request::Parts,
+    http::{request::Parts, Method},
     Json,
 };
 use dal::{
     context::{self, DalContextBuilder},
-    User, UserClaim,
+    ApiToken, Permission, User, UserClaim,
 };
 use hyper::StatusCode;
 
@@ -24,6 +24,15 @@ impl FromRequestParts<AppState> for AccessBuilder {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        if bearer_token(parts).is_some_and(ApiToken::is_api_token) {
+            let ApiTokenAuthorization(token) =
+                ApiTokenAuthorization::from_request_parts(parts, state).await?;
+            return Ok(Self(context::AccessBuilder::new(
+                dal::Tenancy::new(token.workspace_pk()),
+                dal::HistoryActor::from(token.created_by_user_pk()),
+            )));
+        }
+
         let Authorization(claim) = Authorization::from_request_parts(parts, state).await?;
         let Tenancy(tenancy) = tenancy_from_claim(&claim).await?;
 
@@ -149,6 +158,107 @@ impl FromRequestParts<AppState> for Authorization {
     }
 }
 
+/// Authorizes a request made with a workspace [`ApiToken`] instead of a user's JWT. Read-only
+/// tokens are only accepted on requests that don't change anything.
+pub struct ApiTokenAuthorization(pub ApiToken);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ApiTokenAuthorization {
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let raw_token = bearer_token(parts).ok_or_else(unauthorized_error)?;
+
+        let HandlerContext(builder) = HandlerContext::from_request_parts(parts, state).await?;
+        let mut ctx = builder.build_default().await.map_err(internal_error)?;
+
+        let token = ApiToken::authenticate(&ctx, raw_token)
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(unauthorized_error)?;
+
+        // The token can't outlive its creator's membership of the workspace.
+        ctx.update_tenancy(dal::Tenancy::new(token.workspace_pk()));
+        let is_authorized =
+            User::authorize(&ctx, &token.created_by_user_pk(), &token.workspace_pk())
+                .await
+                .map_err(|_| unauthorized_error())?;
+        if !is_authorized {
+            return Err(unauthorized_error());
+        }
+
+        if !token.scope().allows_writes() && !matches!(parts.method, Method::GET | Method::HEAD) {
+            return Err(forbidden_error());
+        }
+
+        // Persist when the token was last used.
+        ctx.commit().await.map_err(internal_error)?;
+
+        Ok(Self(token))
+    }
+}
+
+/// A [`Permission`] a route can require with [`RequirePermission`].
+pub trait RequiredPermission: Send + Sync {
+    const PERMISSION: Permission;
+}
+
+/// Requires [`Permission::DiagramRead`].
+pub struct DiagramRead;
+
+impl RequiredPermission for DiagramRead {
+    const PERMISSION: Permission = Permission::DiagramRead;
+}
+
+/// Requires [`Permission::DiagramWrite`].
+pub struct DiagramWrite;
+
+impl RequiredPermission for DiagramWrite {
+    const PERMISSION: Permission = Permission::DiagramWrite;
+}
+
+/// Rejects the request unless whoever made it, a user or the creator of an [`ApiToken`], holds
+/// the permission `P` through their role in the workspace.
+pub struct RequirePermission<P>(PhantomData<P>);
+
+#[async_trait]
+impl<P: RequiredPermission> FromRequestParts<AppState> for RequirePermission<P> {
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let (user_pk, workspace_pk) = if bearer_token(parts).is_some_and(ApiToken::is_api_token) {
+            let ApiTokenAuthorization(token) =
+                ApiTokenAuthorization::from_request_parts(parts, state).await?;
+            if P::PERMISSION.is_write() && !token.scope().allows_writes() {
+                return Err(forbidden_error());
+            }
+            (token.created_by_user_pk(), token.workspace_pk())
+        } else {
+            let Authorization(claim) = Authorization::from_request_parts(parts, state).await?;
+            (claim.user_pk, claim.workspace_pk)
+        };
+
+        let HandlerContext(builder) = HandlerContext::from_request_parts(parts, state).await?;
+        let mut ctx = builder.build_default().await.map_err(internal_error)?;
+        ctx.update_tenancy(dal::Tenancy::new(workspace_pk));
+
+        let has_permission = User::has_permission(&ctx, user_pk, workspace_pk, P::PERMISSION)
+            .await
+            .map_err(internal_error)?;
+        if !has_permission {
+            return Err(forbidden_error());
+        }
+
+        Ok(Self(PhantomData))
+    }
+}
+
 pub struct WsAuthorization(pub UserClaim);
 
 #[async_trait]
@@ -206,6 +316,17 @@ async fn tenancy_from_claim(
     Ok(Tenancy(dal::Tenancy::new(claim.workspace_pk)))
 }
 
+/// The token of a "Bearer <token>" authorization header, if any.
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get("Authorization")?
+        .to_str()
+        .ok()?
+        .split(' ')
+        .last()
+}
+
 fn internal_error(message: impl fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
     let status_code = StatusCode::INTERNAL_SERVER_ERROR;
     (
@@ -233,3 +354,17 @@ fn unauthorized_error() -> (StatusCode, Json<serde_json::Value>) {
         })),
     )
 }
+
+fn forbidden_error() -> (StatusCode, Json<serde_json::Value>) {
+    let status_code = StatusCode::FORBIDDEN;
+    (
+        status_code,
+        Json(serde_json::json!({
+            "error": {
+                "message": "forbidden",
+                "statusCode": status_code.as_u16(),
+                "code": 42,
+            },
+        })),
+    )
+}
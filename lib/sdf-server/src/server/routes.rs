@@ -1,8 +1,11 @@
 use axum::{
+    extract::DefaultBodyLimit,
+    http::header,
+    middleware,
     response::Json,
     response::{IntoResponse, Response},
     routing::get,
-    Router,
+    Extension, Router,
 };
 use hyper::StatusCode;
 use serde_json::{json, Value};
@@ -12,10 +15,10 @@ use thiserror::Error;
 use tower_http::compression::CompressionLayer;
 use tower_http::cors::CorsLayer;
 
-use super::{server::ServerError, state::AppState};
+use super::{config::BodyLimits, server::ServerError, state::AppState};
 
 #[allow(clippy::too_many_arguments)]
-pub fn routes(state: AppState) -> Router {
+pub fn routes(state: AppState, body_limits: BodyLimits) -> Router {
     let mut router: Router<AppState> = Router::new();
     router = router
         // root health route is currently pinged by auth portal to check if backend is up and running so we need permissive CORS headers
@@ -31,9 +34,15 @@ pub fn routes(state: AppState) -> Router {
             "/api/component",
             crate::server::service::component::routes(),
         )
+        .nest("/api/export", crate::server::service::export::routes())
         .nest("/api/fix", crate::server::service::fix::routes())
         .nest("/api/func", crate::server::service::func::routes())
-        .nest("/api/pkg", crate::server::service::pkg::routes())
+        .nest("/api/history", crate::server::service::history::routes())
+        .nest(
+            "/api/pkg",
+            crate::server::service::pkg::routes()
+                .layer(DefaultBodyLimit::max(body_limits.large_bytes)),
+        )
         .nest("/api/provider", crate::server::service::provider::routes())
         .nest(
             "/api/qualification",
@@ -46,13 +55,22 @@ pub fn routes(state: AppState) -> Router {
         .nest("/api/status", crate::server::service::status::routes())
         .nest(
             "/api/variant_def",
-            crate::server::service::variant_definition::routes(),
+            crate::server::service::variant_definition::routes()
+                .layer(DefaultBodyLimit::max(body_limits.large_bytes)),
+        )
+        .nest(
+            "/api/workspace",
+            crate::server::service::workspace::routes(),
         )
         .nest("/api/ws", crate::server::service::ws::routes())
-        .layer(CompressionLayer::new());
+        .layer(CompressionLayer::new())
+        .layer(DefaultBodyLimit::max(body_limits.default_bytes))
+        .layer(Extension(body_limits))
+        .layer(middleware::map_response(payload_too_large_as_json));
 
     // Load dev routes if we are in dev mode (decided by "opt-level" at the moment).
     router = dev_routes(router);
+    router = graphql_routes(router);
 
     router.with_state(state)
 }
@@ -61,6 +79,31 @@ async fn system_status_route() -> Json<Value> {
     Json(json!({ "ok": true }))
 }
 
+/// Bodies over the limit are rejected by axum with a plain text error, give them the same shape as
+/// our other errors.
+async fn payload_too_large_as_json(response: Response) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map_or(false, |content_type| {
+            content_type.as_bytes().starts_with(b"application/json")
+        });
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || is_json {
+        return response;
+    }
+
+    let status = StatusCode::PAYLOAD_TOO_LARGE;
+    let body = Json(json!({
+        "error": {
+            "message": "request body is too large",
+            "code": 42,
+            "statusCode": status.as_u16(),
+        },
+    }));
+
+    (status, body).into_response()
+}
+
 #[cfg(debug_assertions)]
 pub fn dev_routes(mut router: Router<AppState>) -> Router<AppState> {
     router = router.nest("/api/dev", crate::server::service::dev::routes());
@@ -73,6 +116,18 @@ pub fn dev_routes(router: Router<AppState>) -> Router<AppState> {
     router
 }
 
+#[cfg(feature = "graphql")]
+pub fn graphql_routes(mut router: Router<AppState>) -> Router<AppState> {
+    router = router.nest("/api/graphql", crate::server::service::graphql::routes());
+    router
+}
+
+#[cfg(not(feature = "graphql"))]
+pub fn graphql_routes(router: Router<AppState>) -> Router<AppState> {
+    telemetry::prelude::debug!("skipping graphql routes...");
+    router
+}
+
 #[allow(clippy::large_enum_variant)]
 #[remain::sorted]
 #[derive(Debug, Error)]
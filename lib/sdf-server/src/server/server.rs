@@ -9,9 +9,13 @@ use std::{
 use axum::{routing::IntoMakeService, Router};
 use dal::{
     builtins,
+    builtins::BuiltinSchemaMigrationMode,
     jwt_key::JwtConfig,
     pkg::{import_pkg_from_pkg, ImportOptions, PkgError},
-    tasks::{ResourceScheduler, StatusReceiver, StatusReceiverError},
+    tasks::{
+        DigestScheduler, GarbageCollector, ResourceScheduler, RetentionScheduler, StatusReceiver,
+        StatusReceiverError, WebhookDispatcher,
+    },
     BuiltinsError, DalContext, JwtPublicSigningKey, ServicesContext, Tenancy, TransactionsError,
     Workspace, WorkspaceError,
 };
@@ -40,7 +44,7 @@ use tower_http::trace::TraceLayer;
 use ulid::Ulid;
 use veritech_client::{Client as VeritechClient, CycloneEncryptionKey, CycloneEncryptionKeyError};
 
-use crate::server::config::CycloneKeyPair;
+use crate::server::config::{BodyLimits, CycloneKeyPair};
 
 use super::{
     routes, state::AppState, Config, IncomingStream, UdsIncomingStream, UdsIncomingStreamError,
@@ -135,6 +139,7 @@ impl Server<(), ()> {
                     jwt_public_signing_key,
                     config.signup_secret().clone(),
                     posthog_client,
+                    config.body_limits(),
                 )?;
 
                 info!("binding to HTTP socket; socket_addr={}", &socket_addr);
@@ -171,6 +176,7 @@ impl Server<(), ()> {
                     jwt_public_signing_key,
                     config.signup_secret().clone(),
                     posthog_client,
+                    config.body_limits(),
                 )?;
 
                 info!("binding to Unix domain socket; path={}", path.display());
@@ -236,9 +242,12 @@ impl Server<(), ()> {
     }
 
     #[instrument(name = "sdf.init.migrate_database", skip_all)]
-    pub async fn migrate_database(services_context: &ServicesContext) -> Result<()> {
+    pub async fn migrate_database(
+        services_context: &ServicesContext,
+        builtin_schema_migration_mode: &BuiltinSchemaMigrationMode,
+    ) -> Result<()> {
         dal::migrate_all_with_progress(services_context).await?;
-        migrate_builtins_from_module_index(services_context).await?;
+        migrate_builtins_from_module_index(services_context, builtin_schema_migration_mode).await?;
         Ok(())
     }
 
@@ -250,6 +259,38 @@ impl Server<(), ()> {
         ResourceScheduler::new(services_context).start(shutdown_broadcast_rx);
     }
 
+    /// Start the workspace activity digest scheduler
+    pub async fn start_digest_scheduler(
+        services_context: ServicesContext,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) {
+        DigestScheduler::new(services_context).start(shutdown_broadcast_rx);
+    }
+
+    /// Start the scheduler pruning superseded data according to workspace retention policies
+    pub async fn start_retention_scheduler(
+        services_context: ServicesContext,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) {
+        RetentionScheduler::new(services_context).start(shutdown_broadcast_rx);
+    }
+
+    /// Start the collector hard-deleting rows soft-deleted beyond the retention window
+    pub async fn start_garbage_collector(
+        services_context: ServicesContext,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) {
+        GarbageCollector::new(services_context).start(shutdown_broadcast_rx);
+    }
+
+    /// Start the dispatcher sending pending webhook deliveries
+    pub async fn start_webhook_dispatcher(
+        services_context: ServicesContext,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) {
+        WebhookDispatcher::new(services_context).start(shutdown_broadcast_rx);
+    }
+
     pub async fn start_status_updater(
         services_context: ServicesContext,
         shutdown_broadcast_rx: broadcast::Receiver<()>,
@@ -316,7 +357,10 @@ where
     }
 }
 
-pub async fn migrate_builtins_from_module_index(services_context: &ServicesContext) -> Result<()> {
+pub async fn migrate_builtins_from_module_index(
+    services_context: &ServicesContext,
+    builtin_schema_migration_mode: &BuiltinSchemaMigrationMode,
+) -> Result<()> {
     let mut interval = time::interval(Duration::from_secs(5));
     let instant = Instant::now();
 
@@ -340,7 +384,10 @@ pub async fn migrate_builtins_from_module_index(services_context: &ServicesConte
 
     let module_index_client =
         IndexClient::unauthenticated_client(module_index_url.clone().as_str().try_into()?);
-    let module_list = module_index_client.list_builtins().await?;
+    let mut module_list = module_index_client.list_builtins().await?;
+    module_list
+        .modules
+        .retain(|module| builtin_schema_migration_mode.includes_pkg(&module.name));
     let install_builtins = install_builtins(ctx, module_list, module_index_client);
     tokio::pin!(install_builtins);
     loop {
@@ -396,6 +443,7 @@ async fn install_builtins(
                         skip_import_funcs: None,
                         no_record: false,
                         is_builtin: true,
+                        upgrade_schemas: None,
                     }),
                     true,
                 )
@@ -444,6 +492,7 @@ pub fn build_service_for_tests(
         jwt_public_signing_key,
         signup_secret,
         posthog_client,
+        BodyLimits::default(),
         true,
     )
 }
@@ -453,12 +502,14 @@ pub fn build_service(
     jwt_public_signing_key: JwtPublicSigningKey,
     signup_secret: SensitiveString,
     posthog_client: PosthogClient,
+    body_limits: BodyLimits,
 ) -> Result<(Router, oneshot::Receiver<()>, broadcast::Receiver<()>)> {
     build_service_inner(
         services_context,
         jwt_public_signing_key,
         signup_secret,
         posthog_client,
+        body_limits,
         false,
     )
 }
@@ -468,6 +519,7 @@ fn build_service_inner(
     jwt_public_signing_key: JwtPublicSigningKey,
     signup_secret: SensitiveString,
     posthog_client: PosthogClient,
+    body_limits: BodyLimits,
     for_tests: bool,
 ) -> Result<(Router, oneshot::Receiver<()>, broadcast::Receiver<()>)> {
     let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
@@ -483,7 +535,7 @@ fn build_service_inner(
         for_tests,
     );
 
-    let routes = routes(state).layer(
+    let routes = routes(state, body_limits).layer(
         TraceLayer::new_for_http()
             .make_span_with(HttpMakeSpan::new().level(Level::INFO))
             .on_response(HttpOnResponse::new().level(Level::DEBUG)),
@@ -5,9 +5,9 @@ use axum::{
     Json, Router,
 };
 use dal::{
-    change_status::ChangeStatusError, ActionError, ActionId, ChangeSetError as DalChangeSetError,
-    ComponentError as DalComponentError, FixError, StandardModelError, TransactionsError,
-    UserError, UserPk, WsEventError,
+    change_status::ChangeStatusError, ActionError, ActionId, ChangeSetConflict,
+    ChangeSetError as DalChangeSetError, ChangeSetPk, ComponentError as DalComponentError,
+    FixError, StandardModelError, TransactionsError, UserError, UserPk, WsEventError,
 };
 use module_index_client::IndexClientError;
 use telemetry::prelude::*;
@@ -18,15 +18,18 @@ use crate::{server::state::AppState, service::pkg::PkgError};
 pub mod abandon_change_set;
 mod abandon_vote;
 pub mod add_action;
+pub mod add_recommended_actions;
 pub mod apply_change_set;
 mod begin_abandon_approval_process;
 mod begin_approval_process;
+pub mod compare_change_sets;
 pub mod create_change_set;
 pub mod get_change_set;
 pub mod get_stats;
 pub mod list_open_change_sets;
 pub mod list_queued_actions;
 mod merge_vote;
+pub mod preview_actions;
 pub mod remove_action;
 pub mod update_selected_change_set;
 
@@ -41,10 +44,14 @@ pub enum ChangeSetError {
     ChangeSet(#[from] DalChangeSetError),
     #[error("change set not found")]
     ChangeSetNotFound,
+    #[error("change set {0} is not open")]
+    ChangeSetNotOpen(ChangeSetPk),
     #[error(transparent)]
     ChangeStatusError(#[from] ChangeStatusError),
     #[error(transparent)]
     Component(#[from] DalComponentError),
+    #[error("change set conflicts with head on {} values", .0.len())]
+    Conflicts(Vec<ChangeSetConflict>),
     #[error(transparent)]
     ContextError(#[from] TransactionsError),
     #[error(transparent)]
@@ -66,6 +73,8 @@ pub enum ChangeSetError {
     #[error(transparent)]
     PkgService(#[from] PkgError),
     #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
     StandardModel(#[from] StandardModelError),
     #[error(transparent)]
     UrlParse(#[from] url::ParseError),
@@ -79,8 +88,18 @@ pub type ChangeSetResult<T> = std::result::Result<T, ChangeSetError>;
 
 impl IntoResponse for ChangeSetError {
     fn into_response(self) -> Response {
+        if let ChangeSetError::Conflicts(conflicts) = &self {
+            let status = StatusCode::CONFLICT;
+            let body = Json(serde_json::json!({
+                "error": { "message": self.to_string(), "code": 42, "statusCode": status.as_u16() },
+                "conflicts": conflicts,
+            }));
+            return (status, body).into_response();
+        }
+
         let (status, error_message) = match self {
             ChangeSetError::ChangeSetNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            ChangeSetError::ChangeSetNotOpen(_) => (StatusCode::BAD_REQUEST, self.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -103,12 +122,21 @@ pub fn routes() -> Router<AppState> {
             get(list_queued_actions::list_queued_actions),
         )
         .route("/remove_action", post(remove_action::remove_action))
+        .route("/preview_actions", get(preview_actions::preview_actions))
         .route("/add_action", post(add_action::add_action))
+        .route(
+            "/add_recommended_actions",
+            post(add_recommended_actions::add_recommended_actions),
+        )
         .route(
             "/create_change_set",
             post(create_change_set::create_change_set),
         )
         .route("/get_change_set", get(get_change_set::get_change_set))
+        .route(
+            "/compare_change_sets",
+            get(compare_change_sets::compare_change_sets),
+        )
         .route("/get_stats", get(get_stats::get_stats))
         .route(
             "/apply_change_set",
@@ -4,7 +4,7 @@ use crate::server::service::change_set::ChangeSetError;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
-use dal::{ChangeSet, ChangeSetPk, HistoryActor, User, WsEvent};
+use dal::{ChangeSet, ChangeSetPk};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -43,21 +43,6 @@ pub async fn abandon_change_set(
         }),
     );
 
-    ctx.blocking_commit().await?;
-
-    let user = match ctx.history_actor() {
-        HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk)
-            .await?
-            .ok_or(ChangeSetError::InvalidUser(*user_pk))?,
-
-        HistoryActor::SystemInit => return Err(ChangeSetError::InvalidUserSystemInit),
-    };
-
-    WsEvent::change_set_abandoned(&ctx, change_set.pk, Some(user.pk()))
-        .await?
-        .publish_on_commit(&ctx)
-        .await?;
-
     ctx.commit().await?;
 
     Ok(Json(AbandonChangeSetResponse { change_set }))
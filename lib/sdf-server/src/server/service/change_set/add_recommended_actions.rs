@@ -0,0 +1,78 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+use axum::extract::{Json, OriginalUri};
+use axum::response::IntoResponse;
+use dal::{Action, ActionId, ChangeSet, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AddRecommendedActionsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AddRecommendedActionsResponse {
+    pub action_ids: Vec<ActionId>,
+}
+
+/// Queue the actions recommended by the latest confirmations of every component, so they run in
+/// dependency order when the change set is applied.
+pub async fn add_recommended_actions(
+    OriginalUri(original_uri): OriginalUri,
+    PosthogClient(posthog_client): PosthogClient,
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<AddRecommendedActionsRequest>,
+) -> ChangeSetResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let action_ids: Vec<ActionId> = Action::queue_recommended(&ctx)
+        .await?
+        .iter()
+        .map(|action| *action.id())
+        .collect();
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "create_action",
+        serde_json::json!({
+            "how": "/change_set/add_recommended_actions",
+            "action_ids": &action_ids,
+            "change_set_pk": ctx.visibility().change_set_pk,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&AddRecommendedActionsResponse {
+            action_ids,
+        })?)?)
+}
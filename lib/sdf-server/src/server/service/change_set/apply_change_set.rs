@@ -7,7 +7,7 @@ use axum::Json;
 use dal::job::definition::{FixItem, FixesJob};
 use dal::{
     action::ActionBag, ActionId, ChangeSet, ChangeSetPk, Component, ComponentError, Fix, FixBatch,
-    FixId, HistoryActor, StandardModel, User,
+    FixBatchTargetFilter, FixId, HistoryActor, StandardModel, User,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -17,6 +17,12 @@ use std::collections::{HashMap, VecDeque};
 #[serde(rename_all = "camelCase")]
 pub struct ApplyChangeSetRequest {
     pub change_set_pk: ChangeSetPk,
+    /// When provided, only the actions of components matching the filter are run.
+    #[serde(default)]
+    pub fix_target_filter: Option<FixBatchTargetFilter>,
+    /// Apply even if values set in the change set were changed differently on head since.
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -37,7 +43,13 @@ pub async fn apply_change_set(
     let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
         .await?
         .ok_or(ChangeSetError::ChangeSetNotFound)?;
-    let actions = change_set.actions(&ctx).await?;
+    if !request.force {
+        let conflicts = change_set.conflicts(&ctx).await?;
+        if !conflicts.is_empty() {
+            return Err(ChangeSetError::Conflicts(conflicts));
+        }
+    }
+    let mut actions = change_set.actions(&ctx).await?;
     let actors = change_set.actors(&ctx).await?;
     change_set.apply(&mut ctx).await?;
 
@@ -61,9 +73,25 @@ pub async fn apply_change_set(
         HistoryActor::SystemInit => return Err(ChangeSetError::InvalidUserSystemInit),
     };
 
+    if let Some(filter) = &request.fix_target_filter {
+        let mut filtered_actions = HashMap::new();
+        for (action_id, bag) in actions {
+            if filter.matches(&ctx, *bag.action.component_id()).await? {
+                filtered_actions.insert(action_id, bag);
+            }
+        }
+        actions = filtered_actions;
+    }
+
     if !actions.is_empty() {
         let actors_delimited_string = actors.join(",");
-        let batch = FixBatch::new(&ctx, user.email(), &actors_delimited_string).await?;
+        let batch = FixBatch::new(
+            &ctx,
+            user.email(),
+            &actors_delimited_string,
+            request.fix_target_filter.as_ref(),
+        )
+        .await?;
         let mut fixes: HashMap<FixId, FixItem> = HashMap::new();
         let mut fixes_by_action: HashMap<ActionId, FixId> = HashMap::new();
 
@@ -77,6 +105,10 @@ pub async fn apply_change_set(
         'outer: while let Some(bag) = values.pop_front() {
             let mut parents = Vec::new();
             for parent_id in bag.parents.clone() {
+                // Parents excluded by the target filter will not run, so they are not waited on.
+                if !actions.contains_key(&parent_id) {
+                    continue;
+                }
                 if let Some(parent_id) = fixes_by_action.get(&parent_id) {
                     parents.push(*parent_id);
                 } else {
@@ -0,0 +1,51 @@
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use axum::extract::Query;
+use axum::Json;
+use dal::change_set::comparison::ChangeSetComparison;
+use dal::{ChangeSet, ChangeSetPk, ChangeSetStatus};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareChangeSetsRequest {
+    pub left_change_set_pk: ChangeSetPk,
+    pub right_change_set_pk: ChangeSetPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareChangeSetsResponse {
+    /// Whether applying either change set would overwrite changes made by the other.
+    pub collides: bool,
+    #[serde(flatten)]
+    pub comparison: ChangeSetComparison,
+}
+
+/// Compare two open change sets with each other, returning the components, values and edges they
+/// disagree on.
+pub async fn compare_change_sets(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<CompareChangeSetsRequest>,
+) -> ChangeSetResult<Json<CompareChangeSetsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut change_sets = Vec::with_capacity(2);
+    for pk in [request.left_change_set_pk, request.right_change_set_pk] {
+        let change_set = ChangeSet::get_by_pk(&ctx, &pk)
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound)?;
+        if change_set.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::ChangeSetNotOpen(pk));
+        }
+        change_sets.push(change_set);
+    }
+
+    let comparison = change_sets[0].compare(&ctx, &change_sets[1]).await?;
+
+    Ok(Json(CompareChangeSetsResponse {
+        collides: comparison.collides(),
+        comparison,
+    }))
+}
@@ -0,0 +1,78 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{
+    action::ActionBag, func::backend::js_action::ActionRunResult, Action, ActionId, ActionKind,
+    ComponentId, Visibility,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewActionsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPreview {
+    pub id: ActionId,
+    pub kind: ActionKind,
+    pub component_id: ComponentId,
+    pub parents: Vec<ActionId>,
+    /// The rendered CLI commands and HTTP requests are in the result's `plan`.
+    pub result: Option<ActionRunResult>,
+    /// Set when the action func could not be run in plan-only mode.
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewActionsResponse {
+    pub actions: HashMap<ActionId, ActionPreview>,
+}
+
+/// Run every action queued in the change set in plan-only mode, returning what applying the
+/// change set would do without touching any resource.
+pub async fn preview_actions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<PreviewActionsRequest>,
+) -> ChangeSetResult<Json<PreviewActionsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut actions = HashMap::new();
+    for (
+        id,
+        ActionBag {
+            action,
+            kind,
+            parents,
+        },
+    ) in Action::order(&ctx).await?
+    {
+        let prototype = action.prototype(&ctx).await?;
+        let (result, error) = match prototype.preview(&ctx, *action.component_id()).await {
+            Ok(result) => (result, None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+
+        actions.insert(
+            id,
+            ActionPreview {
+                id,
+                kind,
+                component_id: *action.component_id(),
+                parents,
+                result,
+                error,
+            },
+        );
+    }
+
+    Ok(Json(PreviewActionsResponse { actions }))
+}
@@ -12,8 +12,9 @@ use dal::{
     component::view::debug::ComponentDebugViewError, node::NodeError,
     property_editor::PropertyEditorError, AttributeContextBuilderError,
     AttributePrototypeArgumentError, AttributePrototypeError, AttributeValueError, ChangeSetError,
-    ComponentError as DalComponentError, ComponentId, DiagramError, ExternalProviderError,
-    FuncBindingError, FuncError, InternalProviderError, PropId, ReconciliationPrototypeError,
+    ComponentError as DalComponentError, ComponentId, ConfirmationResolverError, DiagramError,
+    ExternalProviderError, FuncBindingError, FuncError, InternalProviderError, PropId,
+    PropPermissionError, ReconciliationPrototypeError, ResourceIdentifierError,
     SchemaError as DalSchemaError, StandardModelError, TransactionsError, WsEventError,
 };
 use thiserror::Error;
@@ -21,22 +22,37 @@ use thiserror::Error;
 use crate::{server::state::AppState, service::schema::SchemaError};
 
 pub mod alter_simulation;
+pub mod archive;
+pub mod confirmations;
 pub mod debug;
 pub mod delete_property_editor_value;
+pub mod export_snippet;
+pub mod find_by_resource_identifier;
 pub mod get_actions;
 pub mod get_code;
+pub mod get_code_diff;
 pub mod get_components_metadata;
 pub mod get_diff;
 pub mod get_property_editor_schema;
+pub mod get_property_editor_tree;
 pub mod get_property_editor_values;
 pub mod get_resource;
+pub mod import_snippet;
 pub mod insert_property_editor_value;
 pub mod json;
+pub mod list_code_artifacts;
+pub mod list_code_diffs;
 pub mod list_qualifications;
+pub mod notes;
 pub mod refresh;
 pub mod resource_domain_diff;
+pub mod revalidate;
+pub mod search_components;
+pub mod set_owner;
 pub mod set_type;
+pub mod stale_values;
 pub mod update_property_editor_value;
+pub mod validate_value;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -71,6 +87,8 @@ pub enum ComponentError {
     ComponentNotFound(ComponentId),
     #[error("component view error: {0}")]
     ComponentView(#[from] ComponentViewError),
+    #[error("confirmation resolver error: {0}")]
+    ConfirmationResolver(#[from] ConfirmationResolverError),
     #[error("dal schema error: {0}")]
     DalSchema(#[from] DalSchemaError),
     #[error("diagram error: {0}")]
@@ -105,8 +123,12 @@ pub enum ComponentError {
     PropertyEditor(#[from] PropertyEditorError),
     #[error("prop not found for id: {0}")]
     PropNotFound(PropId),
+    #[error(transparent)]
+    PropPermission(#[from] PropPermissionError),
     #[error("reconciliation prototype: {0}")]
     ReconciliationPrototype(#[from] ReconciliationPrototypeError),
+    #[error("resource identifier error: {0}")]
+    ResourceIdentifier(#[from] ResourceIdentifierError),
     #[error("can't delete attribute value for root prop")]
     RootPropAttributeValue,
     #[error("schema error: {0}")]
@@ -134,6 +156,16 @@ impl IntoResponse for ComponentError {
         let (status, error_message) = match self {
             ComponentError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
             ComponentError::InvalidVisibility => (StatusCode::NOT_FOUND, self.to_string()),
+            ComponentError::PropPermission(PropPermissionError::Forbidden(_, _)) => {
+                (StatusCode::FORBIDDEN, self.to_string())
+            }
+            ComponentError::Component(
+                DalComponentError::PropNotDefinedForStrictVariant(_, _)
+                | DalComponentError::UndefinedPropPath(_, _),
+            ) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            ComponentError::Component(DalComponentError::InvalidPropValueFilter(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -156,13 +188,31 @@ pub fn routes() -> Router<AppState> {
             get(list_qualifications::list_qualifications),
         )
         .route("/get_code", get(get_code::get_code))
+        .route("/get_code_diff", get(get_code_diff::get_code_diff))
         .route("/get_resource", get(get_resource::get_resource))
+        .route(
+            "/search_components",
+            post(search_components::search_components),
+        )
+        .route(
+            "/find_by_resource_identifier",
+            get(find_by_resource_identifier::find_by_resource_identifier),
+        )
         .route("/get_actions", get(get_actions::get_actions))
         .route("/get_diff", get(get_diff::get_diff))
+        .route(
+            "/list_code_artifacts",
+            get(list_code_artifacts::list_code_artifacts),
+        )
+        .route("/list_code_diffs", get(list_code_diffs::list_code_diffs))
         .route(
             "/get_property_editor_schema",
             get(get_property_editor_schema::get_property_editor_schema),
         )
+        .route(
+            "/get_property_editor_tree",
+            get(get_property_editor_tree::get_property_editor_tree),
+        )
         .route(
             "/get_property_editor_values",
             get(get_property_editor_values::get_property_editor_values),
@@ -175,12 +225,30 @@ pub fn routes() -> Router<AppState> {
             "/insert_property_editor_value",
             post(insert_property_editor_value::insert_property_editor_value),
         )
+        .route("/validate_value", post(validate_value::validate_value))
         .route(
             "/delete_property_editor_value",
             post(delete_property_editor_value::delete_property_editor_value),
         )
+        .route("/archive", post(archive::archive_component))
+        .route("/unarchive", post(archive::unarchive_component))
+        .route("/list_archived", get(archive::list_archived_components))
+        .route("/set_owner", post(set_owner::set_owner))
         .route("/set_type", post(set_type::set_type))
+        .route("/get_notes", get(notes::get_notes))
+        .route("/set_notes", post(notes::set_notes))
+        .route("/list_stale_values", get(stale_values::list_stale_values))
+        .route(
+            "/repair_stale_values",
+            post(stale_values::repair_stale_values),
+        )
         .route("/refresh", post(refresh::refresh))
+        .route(
+            "/list_confirmations",
+            get(confirmations::list_confirmations),
+        )
+        .route("/run_confirmations", post(confirmations::run_confirmations))
+        .route("/revalidate", post(revalidate::revalidate))
         .route("/resource_domain_diff", get(resource_domain_diff::get_diff))
         .route(
             "/alter_simulation",
@@ -188,4 +256,6 @@ pub fn routes() -> Router<AppState> {
         )
         .route("/debug", get(debug::debug_component))
         .route("/json", get(json::json))
+        .route("/export_snippet", get(export_snippet::export_snippet))
+        .route("/import_snippet", post(import_snippet::import_snippet))
 }
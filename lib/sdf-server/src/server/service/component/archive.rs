@@ -0,0 +1,114 @@
+use axum::extract::{OriginalUri, Query};
+use axum::{response::IntoResponse, Json};
+use dal::{ChangeSet, Component, ComponentId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveComponentRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn archive_component(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ArchiveComponentRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    Component::archive(&ctx, request.component_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "archive_component",
+        serde_json::json!({
+                    "component_id": request.component_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
+
+pub async fn unarchive_component(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ArchiveComponentRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    Component::unarchive(&ctx, request.component_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "unarchive_component",
+        serde_json::json!({
+                    "component_id": request.component_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListArchivedComponentsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedComponentView {
+    pub component_id: ComponentId,
+    pub name: String,
+}
+
+pub type ListArchivedComponentsResponse = Vec<ArchivedComponentView>;
+
+pub async fn list_archived_components(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListArchivedComponentsRequest>,
+) -> ComponentResult<Json<ListArchivedComponentsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut response = Vec::new();
+    for component in Component::list_archived(&ctx).await? {
+        response.push(ArchivedComponentView {
+            component_id: *component.id(),
+            name: component.name(&ctx).await?,
+        });
+    }
+
+    Ok(Json(response))
+}
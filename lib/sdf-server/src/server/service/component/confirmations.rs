@@ -0,0 +1,126 @@
+use axum::extract::{OriginalUri, Query};
+use axum::Json;
+use dal::{
+    confirmation::prototype::ConfirmationPrototypeId, Component, ComponentId, ConfirmationResolver,
+    FuncId, RecommendedAction, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationView {
+    pub confirmation_prototype_id: ConfirmationPrototypeId,
+    pub component_id: ComponentId,
+    pub func_id: FuncId,
+    pub success: bool,
+    pub message: Option<String>,
+    pub recommended_actions: Vec<RecommendedAction>,
+}
+
+impl From<ConfirmationResolver> for ConfirmationView {
+    fn from(resolver: ConfirmationResolver) -> Self {
+        Self {
+            confirmation_prototype_id: resolver.confirmation_prototype_id(),
+            component_id: resolver.component_id(),
+            func_id: resolver.func_id(),
+            success: resolver.success(),
+            message: resolver.message().map(ToOwned::to_owned),
+            recommended_actions: resolver.recommended_actions().to_vec(),
+        }
+    }
+}
+
+pub type ConfirmationsResponse = Vec<ConfirmationView>;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListConfirmationsRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// List the latest confirmation outcomes recorded for a [`Component`](dal::Component).
+pub async fn list_confirmations(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListConfirmationsRequest>,
+) -> ComponentResult<Json<ConfirmationsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let confirmations = ConfirmationResolver::list_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(confirmations))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunConfirmationsRequest {
+    /// Runs the confirmations of every [`Component`](dal::Component) when unset.
+    pub component_id: Option<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Compare the desired attribute values of [`Components`](dal::Component) with their last known
+/// resource state and record the recommended actions.
+pub async fn run_confirmations(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RunConfirmationsRequest>,
+) -> ComponentResult<Json<ConfirmationsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let component_ids = if let Some(component_id) = request.component_id {
+        Component::get_by_id(&ctx.clone_with_delete_visibility(), &component_id)
+            .await?
+            .ok_or(ComponentError::ComponentNotFound(component_id))?;
+        vec![component_id]
+    } else {
+        ctx.run_with_deleted_visibility(|ctx| async move {
+            let component_ids = Component::list(&ctx)
+                .await?
+                .into_iter()
+                .filter(|c| c.visibility().deleted_at.is_none() || c.needs_destroy())
+                .map(|c| *c.id())
+                .collect::<Vec<_>>();
+            Ok::<_, ComponentError>(component_ids)
+        })
+        .await?
+    };
+
+    let mut confirmations = Vec::new();
+    for component_id in &component_ids {
+        for resolver in ConfirmationResolver::run_for_component(&ctx, *component_id).await? {
+            confirmations.push(ConfirmationView::from(resolver));
+        }
+    }
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "run_confirmations",
+        serde_json::json!({
+            "component_ids": &component_ids,
+            "recommended_action_count": confirmations
+                .iter()
+                .map(|c| c.recommended_actions.len())
+                .sum::<usize>(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(confirmations))
+}
@@ -4,7 +4,7 @@ use axum::response::IntoResponse;
 use axum::Json;
 use dal::{
     AttributeReadContext, AttributeValue, AttributeValueId, ChangeSet, ComponentId,
-    DependentValuesUpdate, Prop, PropId, PropKind, StandardModel, Visibility, WsEvent,
+    DependentValuesUpdate, Prop, PropAccess, PropId, PropKind, StandardModel, Visibility, WsEvent,
 };
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +26,11 @@ pub async fn delete_property_editor_value(
 ) -> ComponentResult<impl IntoResponse> {
     let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
+    PropAccess::for_context(&ctx)
+        .await?
+        .ensure_can_write(&ctx, request.prop_id)
+        .await?;
+
     let mut force_changeset_pk = None;
     if ctx.visibility().is_head() {
         let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
@@ -0,0 +1,28 @@
+use axum::{extract::Query, Json};
+use dal::{component::snippet::ComponentSnippet, Component, ComponentId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSnippetRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ExportSnippetResponse = ComponentSnippet;
+
+pub async fn export_snippet(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ExportSnippetRequest>,
+) -> ComponentResult<Json<ExportSnippetResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let snippet = Component::export_snippet(&ctx, request.component_id).await?;
+
+    Ok(Json(snippet))
+}
@@ -0,0 +1,36 @@
+use axum::{extract::Query, Json};
+use dal::{ComponentId, ResourceIdentifier, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FindByResourceIdentifierRequest {
+    pub identifier: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FindByResourceIdentifierResponse {
+    /// Unset when no component models the resource yet.
+    pub component_id: Option<ComponentId>,
+}
+
+/// Find the component already modeling the resource with the given canonical identifier (e.g.
+/// "sg-0123"), so that it is not modeled twice.
+pub async fn find_by_resource_identifier(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<FindByResourceIdentifierRequest>,
+) -> ComponentResult<Json<FindByResourceIdentifierResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let component_id =
+        ResourceIdentifier::find_conflict(&ctx, ComponentId::NONE, &request.identifier).await?;
+
+    Ok(Json(FindByResourceIdentifierResponse { component_id }))
+}
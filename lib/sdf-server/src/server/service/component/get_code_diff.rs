@@ -0,0 +1,33 @@
+use axum::{extract::Query, Json};
+use dal::component::diff::ComponentCodeGenerationDiff;
+use dal::{ComponentId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCodeDiffRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCodeDiffResponse {
+    pub code_diff: ComponentCodeGenerationDiff,
+}
+
+pub async fn get_code_diff(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetCodeDiffRequest>,
+) -> ComponentResult<Json<GetCodeDiffResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let code_diff = ComponentCodeGenerationDiff::new(&ctx, request.component_id).await?;
+
+    Ok(Json(GetCodeDiffResponse { code_diff }))
+}
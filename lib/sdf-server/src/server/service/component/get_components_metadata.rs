@@ -11,6 +11,11 @@ use crate::server::extract::{AccessBuilder, HandlerContext};
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GetComponentsMetadataRequest {
+    /// The "nextCursor" of the previous page.
+    pub cursor: Option<ComponentId>,
+    /// The number of components in a page. Every component is returned when neither this nor
+    /// the cursor is set.
+    pub limit: Option<usize>,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -28,8 +33,13 @@ pub struct ComponentMetadata {
 #[serde(rename_all = "camelCase")]
 pub struct GetComponentsMetadataResponse {
     pub data: Vec<ComponentMetadata>,
+    /// The cursor to request the next page with, unset if this is the last page.
+    pub next_cursor: Option<ComponentId>,
 }
 
+/// The number of components in a page when only the cursor is set.
+const DEFAULT_PAGE_LIMIT: usize = 100;
+
 pub async fn get_components_metadata(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
@@ -37,7 +47,19 @@ pub async fn get_components_metadata(
 ) -> ComponentResult<Json<GetComponentsMetadataResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
-    let components = Component::list(&ctx).await?;
+    let (components, next_cursor) = match (request.cursor, request.limit) {
+        (None, None) => (Component::list(&ctx).await?, None),
+        (cursor, limit) => {
+            let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).max(1);
+            let components = Component::list_page(&ctx, cursor, limit).await?;
+            let next_cursor = if components.len() == limit {
+                components.last().map(|component| *component.id())
+            } else {
+                None
+            };
+            (components, next_cursor)
+        }
+    };
     let mut metadata = Vec::with_capacity(components.len());
 
     // Note: this is slow, we should have a better way of doing this
@@ -68,5 +90,8 @@ pub async fn get_components_metadata(
             component_id: *component.id(),
         });
     }
-    Ok(Json(GetComponentsMetadataResponse { data: metadata }))
+    Ok(Json(GetComponentsMetadataResponse {
+        data: metadata,
+        next_cursor,
+    }))
 }
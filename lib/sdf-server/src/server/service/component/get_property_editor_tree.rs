@@ -0,0 +1,44 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::property_editor::tree::PropertyEditorTreeNode;
+use dal::{Component, ComponentId, PropAccess, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPropertyEditorTreeRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type GetPropertyEditorTreeResponse = PropertyEditorTreeNode;
+
+/// Returns the whole tree of props of the component with their current values, as fetched in a
+/// single query.
+pub async fn get_property_editor_tree(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetPropertyEditorTreeRequest>,
+) -> ComponentResult<Json<GetPropertyEditorTreeResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let is_component_in_tenancy = Component::is_in_tenancy(&ctx, request.component_id).await?;
+    let is_component_in_visibility = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .is_some();
+    if is_component_in_tenancy && !is_component_in_visibility {
+        return Err(ComponentError::InvalidVisibility);
+    }
+
+    let mut tree = PropertyEditorTreeNode::for_component(&ctx, request.component_id).await?;
+    PropAccess::for_context(&ctx)
+        .await?
+        .redact_tree(&ctx, &mut tree)
+        .await?;
+
+    Ok(Json(tree))
+}
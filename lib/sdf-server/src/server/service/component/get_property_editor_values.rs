@@ -1,7 +1,7 @@
 use axum::extract::Query;
 use axum::Json;
 use dal::property_editor::values::PropertyEditorValues;
-use dal::{Component, ComponentId, StandardModel, Visibility};
+use dal::{Component, ComponentId, PropAccess, StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 
 use super::{ComponentError, ComponentResult};
@@ -32,7 +32,12 @@ pub async fn get_property_editor_values(
         return Err(ComponentError::InvalidVisibility);
     }
 
-    let prop_edit_values = PropertyEditorValues::for_component(&ctx, request.component_id).await?;
+    let mut prop_edit_values =
+        PropertyEditorValues::for_component(&ctx, request.component_id).await?;
+    PropAccess::for_context(&ctx)
+        .await?
+        .redact(&ctx, &mut prop_edit_values)
+        .await?;
 
     Ok(Json(prop_edit_values))
 }
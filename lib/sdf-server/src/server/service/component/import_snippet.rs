@@ -0,0 +1,91 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use dal::{
+    component::snippet::ComponentSnippet, ChangeSet, Component, ComponentId, StandardModel,
+    Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSnippetRequest {
+    pub snippet: ComponentSnippet,
+    pub x: String,
+    pub y: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSnippetResponse {
+    pub component_id: ComponentId,
+}
+
+pub async fn import_snippet(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ImportSnippetRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let (component, mut node) = Component::import_snippet(&ctx, &request.snippet).await?;
+    node.set_geometry(
+        &ctx,
+        &request.x,
+        &request.y,
+        Option::<&str>::None,
+        Option::<&str>::None,
+    )
+    .await?;
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "import_component_snippet",
+        serde_json::json!({
+                    "component_id": component.id(),
+                    "component_schema_name": request.snippet.schema_name,
+                    "component_schema_variant_name": request.snippet.schema_variant_name,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    response = response.header("Content-Type", "application/json");
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(serde_json::to_string(&ImportSnippetResponse {
+        component_id: *component.id(),
+    })?)?)
+}
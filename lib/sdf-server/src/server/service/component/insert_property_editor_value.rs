@@ -1,7 +1,7 @@
 use axum::{response::IntoResponse, Json};
 use dal::{
-    AttributeContext, AttributeValue, AttributeValueId, ChangeSet, ComponentId, PropId, Visibility,
-    WsEvent,
+    AttributeContext, AttributeValue, AttributeValueId, ChangeSet, Component, ComponentId,
+    PropAccess, PropId, Visibility, WsEvent,
 };
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +27,12 @@ pub async fn insert_property_editor_value(
 ) -> ComponentResult<impl IntoResponse> {
     let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
+    PropAccess::for_context(&ctx)
+        .await?
+        .ensure_can_write(&ctx, request.prop_id)
+        .await?;
+    Component::ensure_prop_defined(&ctx, request.component_id, request.prop_id).await?;
+
     let mut force_changeset_pk = None;
     if ctx.visibility().is_head() {
         let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
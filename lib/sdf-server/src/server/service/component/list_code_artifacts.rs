@@ -0,0 +1,33 @@
+use axum::{extract::Query, Json};
+use dal::component::code::CodeGenerationArtifact;
+use dal::{Component, ComponentId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCodeArtifactsRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCodeArtifactsResponse {
+    pub artifacts: Vec<CodeGenerationArtifact>,
+}
+
+pub async fn list_code_artifacts(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListCodeArtifactsRequest>,
+) -> ComponentResult<Json<ListCodeArtifactsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let artifacts = Component::list_code_generation_artifacts(&ctx, request.component_id).await?;
+
+    Ok(Json(ListCodeArtifactsResponse { artifacts }))
+}
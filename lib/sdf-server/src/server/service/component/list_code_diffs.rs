@@ -0,0 +1,37 @@
+use axum::{extract::Query, Json};
+use dal::component::diff::ComponentCodeDiff;
+use dal::{ComponentId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCodeDiffsRequest {
+    /// Restricts the diffs to a single component when provided.
+    pub component_id: Option<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCodeDiffsResponse {
+    pub code_diffs: Vec<ComponentCodeDiff>,
+}
+
+pub async fn list_code_diffs(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListCodeDiffsRequest>,
+) -> ComponentResult<Json<ListCodeDiffsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let code_diffs = match request.component_id {
+        Some(component_id) => ComponentCodeDiff::for_component(&ctx, component_id).await?,
+        None => ComponentCodeDiff::list(&ctx).await?,
+    };
+
+    Ok(Json(ListCodeDiffsResponse { code_diffs }))
+}
@@ -0,0 +1,96 @@
+use axum::extract::{OriginalUri, Query};
+use axum::{response::IntoResponse, Json};
+use dal::component::notes::render_notes;
+use dal::{ChangeSet, Component, ComponentId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNotesRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NotesResponse {
+    /// The notes as written, in markdown.
+    pub notes: Option<String>,
+    /// The notes rendered to HTML, safe to insert into the page.
+    pub html: Option<String>,
+}
+
+impl NotesResponse {
+    fn new(notes: Option<String>) -> Self {
+        let html = notes.as_deref().map(render_notes);
+        Self { notes, html }
+    }
+}
+
+pub async fn get_notes(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetNotesRequest>,
+) -> ComponentResult<Json<NotesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(ComponentError::ComponentNotFound(request.component_id))?;
+
+    Ok(Json(NotesResponse::new(component.notes(&ctx).await?)))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetNotesRequest {
+    pub component_id: ComponentId,
+    pub notes: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn set_notes(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetNotesRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(ComponentError::ComponentNotFound(request.component_id))?;
+
+    component.set_notes(&ctx, request.notes).await?;
+    let notes = component.notes(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_component_notes",
+        serde_json::json!({
+            "component_id": component.id(),
+            "has_notes": notes.is_some(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&NotesResponse::new(notes))?)?)
+}
@@ -69,20 +69,33 @@ pub async fn get_diff(
 
         let mut diff = HashMap::new();
 
+        let mut diffed_props = Vec::new();
+        let mut contexts = Vec::new();
         for prop in props {
             let (domain_prop_id, resource_prop_id) = match prop.refers_to_prop_id() {
                 None => continue,
                 Some(prop_id) => (*prop_id, *prop.id()),
             };
 
-            let context = AttributeReadContext {
+            let resource_context = AttributeReadContext {
                 prop_id: Some(resource_prop_id),
                 internal_provider_id: Some(InternalProviderId::NONE),
                 external_provider_id: Some(ExternalProviderId::NONE),
                 component_id: Some(*component.id()),
             };
-            let resource_prop_av = AttributeValue::find_for_context(ctx, context)
-                .await?
+            let domain_context = AttributeReadContext {
+                prop_id: Some(domain_prop_id),
+                ..resource_context
+            };
+            contexts.push(resource_context);
+            contexts.push(domain_context);
+            diffed_props.push((prop, domain_prop_id, resource_context, domain_context));
+        }
+        let attribute_values = AttributeValue::find_many_for_contexts(ctx, &contexts).await?;
+
+        for (prop, domain_prop_id, resource_context, domain_context) in diffed_props {
+            let resource_prop_av = attribute_values
+                .get(&resource_context)
                 .ok_or(ComponentError::AttributeValueNotFound)?;
 
             let view_context = AttributeReadContext {
@@ -95,15 +108,8 @@ pub async fn get_diff(
             let resource_prop_view =
                 AttributeView::new(ctx, view_context, Some(*resource_prop_av.id())).await?;
 
-            let context = AttributeReadContext {
-                prop_id: Some(domain_prop_id),
-                internal_provider_id: Some(InternalProviderId::NONE),
-                external_provider_id: Some(ExternalProviderId::NONE),
-                component_id: Some(*component.id()),
-            };
-
-            let domain_prop_av = AttributeValue::find_for_context(ctx, context)
-                .await?
+            let domain_prop_av = attribute_values
+                .get(&domain_context)
                 .ok_or(ComponentError::AttributeValueNotFound)?;
 
             let domain_prop_view =
@@ -0,0 +1,57 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Component, ComponentId, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevalidateRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevalidateResponse {
+    pub success: bool,
+}
+
+/// Ask every client looking at a [`Component`](dal::Component) to drop its cached validation
+/// results and validate all of its values again.
+pub async fn revalidate(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RevalidateRequest>,
+) -> ComponentResult<Json<RevalidateResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(ComponentError::ComponentNotFound(request.component_id))?;
+
+    WsEvent::component_revalidation_requested(&ctx, *component.id())
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "revalidate_component",
+        serde_json::json!({
+            "component_id": component.id(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RevalidateResponse { success: true }))
+}
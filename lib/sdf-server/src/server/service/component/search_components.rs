@@ -0,0 +1,52 @@
+use axum::Json;
+use dal::component::search::{ComponentSearch, ComponentSearchResults, PropValueFilter};
+use dal::qualification::QualificationSubCheckStatus;
+use dal::{Component, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchComponentsRequest {
+    pub schema_name: Option<String>,
+    /// A case-insensitive substring of the component name.
+    pub name: Option<String>,
+    pub qualification_status: Option<QualificationSubCheckStatus>,
+    /// Conditions like "region == us-east-1" that must all be met.
+    #[serde(default)]
+    pub prop_filters: Vec<String>,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type SearchComponentsResponse = ComponentSearchResults;
+
+/// Find the components meeting every filter of the request, a page at a time.
+pub async fn search_components(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<SearchComponentsRequest>,
+) -> ComponentResult<Json<SearchComponentsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let prop_filters = request
+        .prop_filters
+        .iter()
+        .map(|filter| filter.parse::<PropValueFilter>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let search = ComponentSearch {
+        schema_name: request.schema_name,
+        name: request.name,
+        qualification_status: request.qualification_status,
+        prop_filters,
+        offset: request.offset,
+        limit: request.limit,
+    };
+
+    Ok(Json(Component::search(&ctx, &search).await?))
+}
@@ -0,0 +1,84 @@
+use axum::extract::{OriginalUri, Query};
+use axum::Json;
+use dal::attribute::value::stale::StaleAttributeValue;
+use dal::{AttributeValue, AttributeValueId, ComponentId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStaleValuesRequest {
+    /// Restricts the check to a single component when provided.
+    pub component_id: Option<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStaleValuesResponse {
+    pub stale_values: Vec<StaleAttributeValue>,
+}
+
+pub async fn list_stale_values(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListStaleValuesRequest>,
+) -> ComponentResult<Json<ListStaleValuesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let stale_values = AttributeValue::list_stale(&ctx, request.component_id).await?;
+
+    Ok(Json(ListStaleValuesResponse { stale_values }))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairStaleValuesRequest {
+    /// Restricts the repair to a single component when provided.
+    pub component_id: Option<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairStaleValuesResponse {
+    pub stale_values: Vec<StaleAttributeValue>,
+    /// The values the enqueued dependent values update was seeded with.
+    pub enqueued_attribute_value_ids: Vec<AttributeValueId>,
+}
+
+pub async fn repair_stale_values(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RepairStaleValuesRequest>,
+) -> ComponentResult<Json<RepairStaleValuesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let stale_values = AttributeValue::list_stale(&ctx, request.component_id).await?;
+    let enqueued_attribute_value_ids = AttributeValue::repair_stale(&ctx, &stale_values).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "repair_stale_values",
+        serde_json::json!({
+            "component_id": request.component_id,
+            "stale_value_count": stale_values.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RepairStaleValuesResponse {
+        stale_values,
+        enqueued_attribute_value_ids,
+    }))
+}
@@ -2,7 +2,7 @@ use axum::extract::OriginalUri;
 use axum::{response::IntoResponse, Json};
 use dal::{
     AttributeContext, AttributeValue, AttributeValueId, ChangeSet, Component, ComponentId, Prop,
-    PropId, StandardModel, Visibility,
+    PropAccess, PropId, StandardModel, Visibility,
 };
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +33,12 @@ pub async fn update_property_editor_value(
 ) -> ComponentResult<impl IntoResponse> {
     let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
+    PropAccess::for_context(&ctx)
+        .await?
+        .ensure_can_write(&ctx, request.prop_id)
+        .await?;
+    Component::ensure_prop_defined(&ctx, request.component_id, request.prop_id).await?;
+
     let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
 
     let attribute_context = AttributeContext::builder()
@@ -0,0 +1,34 @@
+use axum::Json;
+use dal::prop::validation::PropValueValidation;
+use dal::{Prop, PropId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateValueRequest {
+    pub prop_id: PropId,
+    pub value: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ValidateValueResponse = PropValueValidation;
+
+/// Run the validations of a [`Prop`](dal::Prop) against a candidate value without persisting it,
+/// so that errors can be shown while the value is being typed.
+pub async fn validate_value(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<ValidateValueRequest>,
+) -> ComponentResult<Json<ValidateValueResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let prop = Prop::get_by_id(&ctx, &request.prop_id)
+        .await?
+        .ok_or(ComponentError::PropNotFound(request.prop_id))?;
+
+    Ok(Json(prop.validate_value(&ctx, request.value).await?))
+}
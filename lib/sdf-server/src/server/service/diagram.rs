@@ -3,14 +3,15 @@ use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::Json;
 use axum::Router;
+use dal::discovery::DiscoveryError;
 use dal::provider::external::ExternalProviderError as DalExternalProviderError;
 use dal::socket::{SocketError, SocketId};
 use dal::{
     component::ComponentViewError, node::NodeId, schema::variant::SchemaVariantError, ActionError,
     ActionPrototypeError, AttributeContextBuilderError, AttributeValueError, ChangeSetError,
-    ComponentError, ComponentType, DiagramError as DalDiagramError, EdgeError,
+    ComponentError, ComponentType, DiagramError as DalDiagramError, EdgeError, FrameVariableError,
     InternalProviderError, NodeError, NodeKind, NodeMenuError, SchemaError as DalSchemaError,
-    SchemaVariantId, StandardModelError, TransactionsError,
+    SchemaVariantId, SecretId, SocketMergeStrategyError, StandardModelError, TransactionsError,
 };
 use dal::{AttributeReadContext, WsEventError};
 use std::num::ParseFloatError;
@@ -20,18 +21,28 @@ use crate::server::state::AppState;
 use crate::service::schema::SchemaError;
 
 mod connect_component_to_frame;
+pub mod create_components_from_dataset;
+pub mod create_components_from_template;
 pub mod create_connection;
 pub mod create_node;
 pub mod delete_component;
 pub mod delete_connection;
 mod detach_component_from_frame;
+mod disconnect_component_from_frame;
+pub mod duplicate_component;
+mod frame_membership;
+pub mod frame_variables;
 pub mod get_diagram;
 pub mod get_node_add_menu;
+pub mod get_socket_value;
+pub mod import_aws_resources;
 pub mod list_schema_variants;
+pub mod list_socket_suggestions;
 pub mod paste_component;
 mod restore_component;
 pub mod restore_connection;
 pub mod set_node_position;
+pub mod socket_merge_strategies;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -62,6 +73,8 @@ pub enum DiagramError {
     DalSchema(#[from] DalSchemaError),
     #[error("dal diagram error: {0}")]
     DiagramError(#[from] DalDiagramError),
+    #[error("discovery error: {0}")]
+    Discovery(#[from] DiscoveryError),
     #[error(transparent)]
     Edge(#[from] EdgeError),
     #[error("edge not found")]
@@ -74,6 +87,10 @@ pub enum DiagramError {
     FrameInternalProviderNotFoundForSchemaVariant(SchemaVariantId),
     #[error("frame socket not found for schema variant id: {0}")]
     FrameSocketNotFound(SchemaVariantId),
+    #[error("frame variable error: {0}")]
+    FrameVariable(#[from] FrameVariableError),
+    #[error("frame variable not found: {0}")]
+    FrameVariableNotFound(String),
     #[error("invalid header name {0}")]
     Hyper(#[from] hyper::http::Error),
     #[error(transparent)]
@@ -116,10 +133,14 @@ pub enum DiagramError {
     SchemaVariant(#[from] SchemaVariantError),
     #[error("schema variant not found")]
     SchemaVariantNotFound,
+    #[error("secret not found: {0}")]
+    SecretNotFound(SecretId),
     #[error("serde error: {0}")]
     Serde(#[from] serde_json::Error),
     #[error("socket error: {0}")]
     Socket(#[from] SocketError),
+    #[error("socket merge strategy error: {0}")]
+    SocketMergeStrategy(#[from] SocketMergeStrategyError),
     #[error("socket not found")]
     SocketNotFound,
     #[error(transparent)]
@@ -134,6 +155,9 @@ impl IntoResponse for DiagramError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
             DiagramError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            DiagramError::Component(ComponentError::DatasetCsvUnterminatedQuote(_)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -153,6 +177,18 @@ pub fn routes() -> Router<AppState> {
             post(get_node_add_menu::get_node_add_menu),
         )
         .route("/create_node", post(create_node::create_node))
+        .route(
+            "/create_components_from_dataset",
+            post(create_components_from_dataset::create_components_from_dataset),
+        )
+        .route(
+            "/create_components_from_template",
+            post(create_components_from_template::create_components_from_template),
+        )
+        .route(
+            "/import_aws_resources",
+            post(import_aws_resources::import_aws_resources),
+        )
         .route(
             "/set_node_position",
             post(set_node_position::set_node_position),
@@ -182,6 +218,10 @@ pub fn routes() -> Router<AppState> {
             post(detach_component_from_frame::detach_component_from_frame),
         )
         .route("/paste_components", post(paste_component::paste_components))
+        .route(
+            "/duplicate_component",
+            post(duplicate_component::duplicate_component),
+        )
         .route(
             "/restore_component",
             post(restore_component::restore_component),
@@ -194,8 +234,37 @@ pub fn routes() -> Router<AppState> {
             "/connect_component_to_frame",
             post(connect_component_to_frame::connect_component_to_frame),
         )
+        .route(
+            "/disconnect_component_from_frame",
+            post(disconnect_component_from_frame::disconnect_component_from_frame),
+        )
+        .route(
+            "/list_frame_variables",
+            get(frame_variables::list_frame_variables),
+        )
+        .route(
+            "/set_frame_variable",
+            post(frame_variables::set_frame_variable),
+        )
+        .route(
+            "/remove_frame_variable",
+            post(frame_variables::remove_frame_variable),
+        )
         .route(
             "/list_schema_variants",
             get(list_schema_variants::list_schema_variants),
         )
+        .route(
+            "/list_socket_suggestions",
+            get(list_socket_suggestions::list_socket_suggestions),
+        )
+        .route("/get_socket_value", get(get_socket_value::get_socket_value))
+        .route(
+            "/list_socket_merge_strategies",
+            get(socket_merge_strategies::list_socket_merge_strategies),
+        )
+        .route(
+            "/set_socket_merge_strategy",
+            post(socket_merge_strategies::set_socket_merge_strategy),
+        )
 }
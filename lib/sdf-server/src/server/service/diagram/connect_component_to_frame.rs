@@ -6,17 +6,19 @@ use axum::{response::IntoResponse, Json};
 use hyper::http::Uri;
 use serde::{Deserialize, Serialize};
 
-use dal::edge::{EdgeKind, EdgeObjectId, VertexObjectKind};
+use dal::edge::{EdgeCreationSource, EdgeKind, EdgeObjectId, VertexObjectKind};
 use dal::job::definition::DependentValuesUpdate;
 use dal::socket::{SocketEdgeKind, SocketKind};
 use dal::{
     node::NodeId, AttributeReadContext, AttributeValue, ChangeSet, Component, ComponentError,
-    Connection, DalContext, Edge, EdgeError, ExternalProvider, InternalProvider, SocketId,
-    StandardModel, Visibility,
+    ComponentId, Connection, DalContext, Edge, EdgeError, ExternalProvider, FrameVariable,
+    InternalProvider, SocketId, StandardModel, Visibility,
 };
 use dal::{ComponentType, Socket};
 
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 
 use super::{DiagramError, DiagramResult};
@@ -36,7 +38,11 @@ pub struct CreateFrameConnectionResponse {
     pub connection: Connection,
 }
 
-// Create all valid connections between parent and child sockets
+// Create all valid connections between parent and child sockets, and between the child and every
+// configuration frame above the parent. When frames are nested, the child inherits the sockets of all
+// of its ancestors and the nearest one wins on conflicts. The child's own descendants are re-evaluated
+// as well, since moving a frame (re-parenting it) gives them new ancestors too. Subsystems behave
+// like configuration frames pushing down, and also namespace the generated names inside them.
 pub async fn connect_component_sockets_to_frame(
     ctx: &DalContext,
     parent_node_id: NodeId,
@@ -47,9 +53,10 @@ pub async fn connect_component_sockets_to_frame(
     // We stored connected sockets to ensure we connect children's sockets only to the nearest valid ancestor socket
     let mut connected_sockets_for_node_id: HashMap<NodeId, Vec<SocketId>> = HashMap::new();
 
-    connected_sockets_for_node_id
-        .entry(child_node_id)
-        .or_default();
+    connected_sockets_for_node_id.insert(
+        child_node_id,
+        frame_connected_socket_ids(ctx, child_node_id).await?,
+    );
     connected_sockets_for_node_id
         .entry(parent_node_id)
         .or_default();
@@ -74,15 +81,20 @@ pub async fn connect_component_sockets_to_frame(
             .map(|grandchild_node_id| (child_node_id, grandchild_node_id))
             .collect();
 
-    // Goes down new child's children list, trying to connect them to their new ancestors' Configuration Sockets
+    // Goes down new child's children list, trying to connect them to their new ancestors' Configuration Sockets.
+    // The sockets their nearer frames already provide are left alone, since the nearest ancestor wins.
     while let Some((parent_node_id, child_node_id)) = sorted_children.pop_front() {
+        let mut connected_sockets_for_node_id = HashMap::from([(
+            child_node_id,
+            frame_connected_socket_ids(ctx, child_node_id).await?,
+        )]);
         connect_component_sockets_to_frame_inner(
             ctx,
             parent_node_id,
             child_node_id,
             original_uri,
             posthog_client,
-            &mut HashMap::new(),
+            &mut connected_sockets_for_node_id,
         )
         .await?;
 
@@ -94,9 +106,57 @@ pub async fn connect_component_sockets_to_frame(
         );
     }
 
+    // Entering a subsystem (or a frame inside one) namespaces the generated names of the child
+    // and of everything inside it.
+    let child_component = Component::find_for_node(ctx, child_node_id)
+        .await?
+        .ok_or(DiagramError::NodeNotFound(child_node_id))?;
+    Component::apply_subsystem_namespace(ctx, *child_component.id()).await?;
+
     Ok(())
 }
 
+/// The input sockets of the [`Node`](dal::Node) that are already connected to one of the frames
+/// it is in.
+async fn frame_connected_socket_ids(
+    ctx: &DalContext,
+    node_id: NodeId,
+) -> DiagramResult<Vec<SocketId>> {
+    let component = Component::find_for_node(ctx, node_id)
+        .await?
+        .ok_or(DiagramError::NodeNotFound(node_id))?;
+    Ok(Edge::list_for_component(ctx, *component.id())
+        .await?
+        .into_iter()
+        .filter(|edge| {
+            *edge.kind() == EdgeKind::Configuration
+                && edge.auto_created()
+                && edge.head_node_id() == node_id
+        })
+        .map(|edge| edge.head_socket_id())
+        .collect())
+}
+
+/// Whether the aggregation frame edge between the two [`Nodes`](dal::Node) through the socket of
+/// the frame already exists, which is the case when the frame the child is in gets re-parented.
+async fn aggregation_edge_exists(
+    ctx: &DalContext,
+    child_component_id: ComponentId,
+    tail_node_id: NodeId,
+    head_node_id: NodeId,
+    socket_id: SocketId,
+) -> DiagramResult<bool> {
+    Ok(Edge::list_for_component(ctx, child_component_id)
+        .await?
+        .iter()
+        .any(|edge| {
+            edge.tail_node_id() == tail_node_id
+                && edge.head_node_id() == head_node_id
+                && edge.tail_socket_id() == socket_id
+                && edge.head_socket_id() == socket_id
+        }))
+}
+
 #[async_recursion]
 async fn connect_component_sockets_to_frame_inner(
     ctx: &DalContext,
@@ -128,6 +188,18 @@ async fn connect_component_sockets_to_frame_inner(
             ComponentType::AggregationFrame => {
                 match *parent_socket.edge_kind() {
                     SocketEdgeKind::ConfigurationInput => {
+                        if aggregation_edge_exists(
+                            ctx,
+                            *child_component.id(),
+                            child_node_id,
+                            parent_node_id,
+                            *parent_socket.id(),
+                        )
+                        .await?
+                        {
+                            continue;
+                        }
+
                         let provider =
                             InternalProvider::find_explicit_for_socket(ctx, *parent_socket.id())
                                 .await?
@@ -155,6 +227,7 @@ async fn connect_component_sockets_to_frame_inner(
                             VertexObjectKind::Configuration,
                             EdgeObjectId::from(*parent_component.id()),
                             *parent_socket.id(),
+                            EdgeCreationSource::FrameAutoConnection,
                         )
                         .await?;
 
@@ -171,14 +244,27 @@ async fn connect_component_sockets_to_frame_inner(
                                     attribute_value_context,
                                 ))?;
 
-                        ctx.enqueue_job(DependentValuesUpdate::new(
+                        let job = DependentValuesUpdate::new(
                             ctx.access_builder(),
                             *ctx.visibility(),
                             vec![*attribute_value.id()],
-                        ))
-                        .await?;
+                        );
+                        ctx.on_commit(move |ctx| async move { ctx.enqueue_job(job).await })
+                            .await?;
                     }
                     SocketEdgeKind::ConfigurationOutput => {
+                        if aggregation_edge_exists(
+                            ctx,
+                            *child_component.id(),
+                            parent_node_id,
+                            child_node_id,
+                            *parent_socket.id(),
+                        )
+                        .await?
+                        {
+                            continue;
+                        }
+
                         let provider = ExternalProvider::find_for_socket(ctx, *parent_socket.id())
                             .await?
                             .ok_or(EdgeError::ExternalProviderNotFoundForSocket(
@@ -204,6 +290,7 @@ async fn connect_component_sockets_to_frame_inner(
                             VertexObjectKind::Configuration,
                             EdgeObjectId::from(*child_component.id()),
                             *parent_socket.id(),
+                            EdgeCreationSource::FrameAutoConnection,
                         )
                         .await?;
 
@@ -220,17 +307,19 @@ async fn connect_component_sockets_to_frame_inner(
                                     attribute_value_context,
                                 ))?;
 
-                        ctx.enqueue_job(DependentValuesUpdate::new(
+                        let job = DependentValuesUpdate::new(
                             ctx.access_builder(),
                             *ctx.visibility(),
                             vec![*attribute_value.id()],
-                        ))
-                        .await?;
+                        );
+                        ctx.on_commit(move |ctx| async move { ctx.enqueue_job(job).await })
+                            .await?;
                     }
                 }
             }
             component_type @ (ComponentType::ConfigurationFrameDown
-            | ComponentType::ConfigurationFrameUp) => {
+            | ComponentType::ConfigurationFrameUp
+            | ComponentType::Subsystem) => {
                 let child_sockets = Socket::list_for_component(ctx, *child_component.id()).await?;
 
                 for child_socket in &child_sockets {
@@ -247,7 +336,7 @@ async fn connect_component_sockets_to_frame_inner(
                         dest_node_id,
                         destination_component_id,
                         dest_socket,
-                    ) = if component_type == ComponentType::ConfigurationFrameDown {
+                    ) = if component_type.is_configuration_frame_down() {
                         let used_socket = connected_sockets_for_node_id
                             .get(&child_node_id)
                             .into_iter()
@@ -292,6 +381,7 @@ async fn connect_component_sockets_to_frame_inner(
                                 dest_node_id,
                                 *dest_socket.id(),
                                 EdgeKind::Configuration,
+                                EdgeCreationSource::FrameAutoConnection,
                             )
                             .await?;
 
@@ -318,12 +408,13 @@ async fn connect_component_sockets_to_frame_inner(
                                 .update_from_prototype_function(ctx)
                                 .await?;
 
-                            ctx.enqueue_job(DependentValuesUpdate::new(
+                            let job = DependentValuesUpdate::new(
                                 ctx.access_builder(),
                                 *ctx.visibility(),
                                 vec![*dest_attribute_value.id()],
-                            ))
-                            .await?;
+                            );
+                            ctx.on_commit(move |ctx| async move { ctx.enqueue_job(job).await })
+                                .await?;
                         }
                     }
                 }
@@ -331,6 +422,30 @@ async fn connect_component_sockets_to_frame_inner(
         };
     }
 
+    // Variables fill the sockets left unconnected by the frame's own sockets, before the ancestors
+    // get a chance to, so that the nearest frame defining a value wins.
+    if parent_component
+        .get_type(ctx)
+        .await?
+        .is_configuration_frame_down()
+    {
+        let used_socket_ids = connected_sockets_for_node_id
+            .get(&child_node_id)
+            .cloned()
+            .unwrap_or_default();
+        let exposed_socket_ids = FrameVariable::expose_to_component(
+            ctx,
+            *parent_component.id(),
+            *child_component.id(),
+            &used_socket_ids,
+        )
+        .await?;
+        connected_sockets_for_node_id
+            .entry(child_node_id)
+            .or_default()
+            .extend(exposed_socket_ids);
+    }
+
     if let Some(grandparent_id) =
         Edge::get_parent_for_component(ctx, *parent_component.id()).await?
     {
@@ -345,8 +460,12 @@ async fn connect_component_sockets_to_frame_inner(
             .pop()
             .ok_or(ComponentError::NodeNotFoundForComponent(grandparent_id))?;
         match ty {
-            ComponentType::Component => {}
-            ComponentType::ConfigurationFrameDown | ComponentType::ConfigurationFrameUp => {
+            // Aggregation frames only ever aggregate their direct children, so nothing is
+            // inherited from them (or past them).
+            ComponentType::Component | ComponentType::AggregationFrame => {}
+            ComponentType::ConfigurationFrameDown
+            | ComponentType::ConfigurationFrameUp
+            | ComponentType::Subsystem => {
                 connect_component_sockets_to_frame_inner(
                     ctx,
                     *grandparent.id(),
@@ -357,7 +476,6 @@ async fn connect_component_sockets_to_frame_inner(
                 )
                 .await?
             }
-            ComponentType::AggregationFrame => unimplemented!(),
         }
     }
 
@@ -412,6 +530,7 @@ async fn connect_component_sockets_to_frame_inner(
 pub async fn connect_component_to_frame(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateFrameConnectionRequest>,
@@ -420,6 +539,9 @@ pub async fn connect_component_to_frame(
 
     let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
 
+    // Walking the frame hierarchy looks up the same props and providers over and over.
+    ctx.enable_attribute_context_cache();
+
     // Connect children to parent through frame edge
     connect_component_sockets_to_frame(
         &ctx,
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::component::dataset::{ComponentDataset, ComponentDatasetProblem};
+use dal::node::NodeId;
+use dal::{
+    action_prototype::ActionPrototypeContextField, generate_name_from_schema_name, Action,
+    ActionKind, ActionPrototype, ActionPrototypeContext, ChangeSet, Component, ComponentId, Schema,
+    SchemaId, StandardModel, Visibility, WsEvent,
+};
+
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+/// How many components are laid out per row of the grid, starting at the requested position.
+const GRID_COLUMNS: usize = 5;
+/// The distance between two components of the grid.
+const GRID_SPACING: f64 = 300.0;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateComponentsFromDatasetRequest {
+    pub schema_id: SchemaId,
+    pub parent_id: Option<NodeId>,
+    /// A CSV document whose first record holds the column names. Takes precedence over
+    /// `dataset` when set.
+    pub csv: Option<String>,
+    #[serde(default)]
+    pub dataset: ComponentDataset,
+    /// Maps column names to the JSON pointer of a prop (e.g. "/root/domain/GroupName").
+    pub mapping: HashMap<String, String>,
+    /// The column holding component names. Names are generated when unset.
+    pub name_column: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    /// Only validate the dataset, without creating anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateComponentsFromDatasetResponse {
+    /// How many components the dataset describes, whether they were created or not.
+    pub row_count: usize,
+    pub component_ids: Vec<ComponentId>,
+    pub node_ids: Vec<NodeId>,
+    /// Nothing is created when there are problems.
+    pub problems: Vec<ComponentDatasetProblem>,
+}
+
+/// Create one component of a schema per row of a dataset, optionally inside of a frame, mapping
+/// the columns of the dataset to props.
+pub async fn create_components_from_dataset(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateComponentsFromDatasetRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let schema = Schema::get_by_id(&ctx, &request.schema_id)
+        .await?
+        .ok_or(DiagramError::SchemaNotFound)?;
+    let schema_variant_id = *schema
+        .default_schema_variant_id()
+        .ok_or(DiagramError::SchemaVariantNotFound)?;
+
+    let dataset = match &request.csv {
+        Some(csv) => ComponentDataset::from_csv(csv)?,
+        None => request.dataset,
+    };
+    let (entries, problems) = dataset
+        .prepare(
+            &ctx,
+            schema_variant_id,
+            &request.mapping,
+            request.name_column.as_deref(),
+        )
+        .await?;
+
+    let mut response = CreateComponentsFromDatasetResponse {
+        row_count: entries.len(),
+        component_ids: Vec::new(),
+        node_ids: Vec::new(),
+        problems,
+    };
+    if request.dry_run || !response.problems.is_empty() {
+        return Ok(axum::response::Response::builder()
+            .header("content-type", "application/json")
+            .body(serde_json::to_string(&response)?)?);
+    }
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let create_prototypes = ActionPrototype::find_for_context_and_kind(
+        &ctx,
+        ActionKind::Create,
+        ActionPrototypeContext::new_for_context_field(ActionPrototypeContextField::SchemaVariant(
+            schema_variant_id,
+        )),
+    )
+    .await?;
+
+    for (index, entry) in entries.iter().enumerate() {
+        let name = match &entry.name {
+            Some(name) => name.clone(),
+            None => generate_name_from_schema_name(schema.name()),
+        };
+        let (component, mut node) = Component::new(&ctx, &name, schema_variant_id).await?;
+        Component::apply_domain_overrides(&ctx, *component.id(), &entry.domain).await?;
+
+        for prototype in &create_prototypes {
+            Action::new(&ctx, *prototype.id(), *component.id()).await?;
+        }
+
+        let x = request.x + (index % GRID_COLUMNS) as f64 * GRID_SPACING;
+        let y = request.y + (index / GRID_COLUMNS) as f64 * GRID_SPACING;
+        node.set_geometry(
+            &ctx,
+            x.to_string(),
+            y.to_string(),
+            Option::<&str>::None,
+            Option::<&str>::None,
+        )
+        .await?;
+
+        if let Some(frame_id) = request.parent_id {
+            connect_component_sockets_to_frame(
+                &ctx,
+                frame_id,
+                *node.id(),
+                &original_uri,
+                &posthog_client,
+            )
+            .await?;
+        }
+
+        response.component_ids.push(*component.id());
+        response.node_ids.push(*node.id());
+    }
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_after_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "components_created_from_dataset",
+        serde_json::json!({
+                    "schema_id": schema.id(),
+                    "schema_name": schema.name(),
+                    "schema_variant_id": &schema_variant_id,
+                    "component_count": response.component_ids.len(),
+                    "parent_id": request.parent_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut builder = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        builder = builder.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(builder
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&response)?)?)
+}
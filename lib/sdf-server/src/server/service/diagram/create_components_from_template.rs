@@ -0,0 +1,214 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::edge::EdgeKind;
+use dal::node::NodeId;
+use dal::{
+    action_prototype::ActionPrototypeContextField, Action, ActionKind, ActionPrototype,
+    ActionPrototypeContext, ChangeSet, Component, ComponentError, ComponentId, Edge, StandardModel,
+    Visibility, WsEvent,
+};
+
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+/// The most copies created by a single request.
+const MAX_TEMPLATE_COPIES: usize = 100;
+/// How many copies are laid out per row of the grid, to the right of the template.
+const GRID_COLUMNS: usize = 5;
+/// The distance between two copies of the grid, on top of the size of the template.
+const GRID_SPACING: f64 = 300.0;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateComponentsFromTemplateRequest {
+    /// The node of the component (or frame) to copy.
+    pub node_id: NodeId,
+    pub count: usize,
+    /// The name of the copies, where "{{index}}" is replaced by the number of the copy, starting
+    /// at 1 (e.g. "web-{{index}}").
+    pub name_pattern: String,
+    /// Domain overrides applied to every copy, shaped like "/root/domain".
+    #[serde(default)]
+    pub overrides: serde_json::Value,
+    /// Domain overrides applied to a single copy on top of the shared ones, by position.
+    #[serde(default)]
+    pub per_copy_overrides: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateComponentsFromTemplateResponse {
+    /// The copies of the template itself, in order.
+    pub component_ids: Vec<ComponentId>,
+    pub node_ids: Vec<NodeId>,
+    /// Every component created, including the copies of the children of a frame.
+    pub created_component_ids: Vec<ComponentId>,
+}
+
+/// Create many copies of a [`Component`](dal::Component), or of a frame along with everything
+/// inside of it, named after a pattern and each with its own overrides. The copies are placed in
+/// the frame of the template, if any. Creates change-set if on head
+pub async fn create_components_from_template(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateComponentsFromTemplateRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    if request.count == 0
+        || request.count > MAX_TEMPLATE_COPIES
+        || request.per_copy_overrides.len() > request.count
+    {
+        return Err(DiagramError::InvalidRequest);
+    }
+
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let template = Component::find_for_node(&ctx, request.node_id)
+        .await?
+        .ok_or(ComponentError::NotFoundForNode(request.node_id))?;
+    let template_node = template
+        .node(&ctx)
+        .await?
+        .pop()
+        .ok_or(DiagramError::NodeNotFound(request.node_id))?;
+    let step_x = template_node
+        .width()
+        .and_then(|width| width.parse::<f64>().ok())
+        .unwrap_or_default()
+        + GRID_SPACING;
+    let step_y = template_node
+        .height()
+        .and_then(|height| height.parse::<f64>().ok())
+        .unwrap_or_default()
+        + GRID_SPACING;
+
+    // The template's frame is the head of the symbolic edge it is the tail of
+    let parent_node_id = Edge::list_for_component(&ctx, *template.id())
+        .await?
+        .into_iter()
+        .find(|edge| {
+            *edge.kind() == EdgeKind::Symbolic && edge.tail_component_id() == *template.id()
+        })
+        .map(|edge| edge.head_node_id());
+
+    let mut response = CreateComponentsFromTemplateResponse {
+        component_ids: Vec::with_capacity(request.count),
+        node_ids: Vec::with_capacity(request.count),
+        created_component_ids: Vec::new(),
+    };
+    for position in 0..request.count {
+        let mut overrides = request.overrides.clone();
+        if let Some(copy_overrides) = request.per_copy_overrides.get(position) {
+            merge_overrides(&mut overrides, copy_overrides);
+        }
+
+        let offset_x = (position % GRID_COLUMNS + 1) as f64 * step_x;
+        let offset_y = (position / GRID_COLUMNS) as f64 * step_y;
+        let (component, node, created_component_ids) = Component::instantiate_template(
+            &ctx,
+            *template.id(),
+            position + 1,
+            &request.name_pattern,
+            &overrides,
+            offset_x,
+            offset_y,
+        )
+        .await?;
+
+        if let Some(parent_node_id) = parent_node_id {
+            connect_component_sockets_to_frame(
+                &ctx,
+                parent_node_id,
+                *node.id(),
+                &original_uri,
+                &posthog_client,
+            )
+            .await?;
+        }
+
+        for component_id in &created_component_ids {
+            let schema_variant_id = Component::schema_variant_id(&ctx, *component_id).await?;
+            for prototype in ActionPrototype::find_for_context_and_kind(
+                &ctx,
+                ActionKind::Create,
+                ActionPrototypeContext::new_for_context_field(
+                    ActionPrototypeContextField::SchemaVariant(schema_variant_id),
+                ),
+            )
+            .await?
+            {
+                Action::new(&ctx, *prototype.id(), *component_id).await?;
+            }
+        }
+
+        response.component_ids.push(*component.id());
+        response.node_ids.push(*node.id());
+        response.created_component_ids.extend(created_component_ids);
+    }
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_after_commit(&ctx)
+        .await?;
+
+    let schema = template
+        .schema(&ctx)
+        .await?
+        .ok_or(DiagramError::SchemaNotFound)?;
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "components_created_from_template",
+        serde_json::json!({
+                    "template_component_id": template.id(),
+                    "template_schema_name": schema.name(),
+                    "copy_count": response.component_ids.len(),
+                    "component_count": response.created_component_ids.len(),
+                    "parent_node_id": parent_node_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut builder = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        builder = builder.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(builder
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&response)?)?)
+}
+
+/// Merges the overrides of a single copy into the shared ones, objects field by field.
+fn merge_overrides(overrides: &mut serde_json::Value, copy_overrides: &serde_json::Value) {
+    match (overrides, copy_overrides) {
+        (serde_json::Value::Object(fields), serde_json::Value::Object(copy_fields)) => {
+            for (key, copy_value) in copy_fields {
+                match fields.get_mut(key) {
+                    Some(value) => merge_overrides(value, copy_value),
+                    None => {
+                        fields.insert(key.clone(), copy_value.clone());
+                    }
+                }
+            }
+        }
+        (overrides, copy_overrides) => {
+            if !copy_overrides.is_null() {
+                *overrides = copy_overrides.clone();
+            }
+        }
+    }
+}
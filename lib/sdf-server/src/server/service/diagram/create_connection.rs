@@ -1,6 +1,6 @@
 use axum::extract::OriginalUri;
 use axum::{response::IntoResponse, Json};
-use dal::edge::EdgeKind;
+use dal::edge::{EdgeCreationSource, EdgeKind};
 use dal::{
     job::definition::DependentValuesUpdate, node::NodeId, socket::SocketId, AttributeReadContext,
     AttributeValue, ChangeSet, Connection, InternalProvider, Node, Socket, StandardModel,
@@ -9,7 +9,9 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -35,6 +37,7 @@ pub struct CreateConnectionResponse {
 pub async fn create_connection(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateConnectionRequest>,
@@ -53,7 +56,7 @@ pub async fn create_connection(
 
         WsEvent::change_set_created(&ctx, change_set.pk)
             .await?
-            .publish_on_commit(&ctx)
+            .publish_after_commit(&ctx)
             .await?;
     };
 
@@ -64,6 +67,7 @@ pub async fn create_connection(
         request.to_node_id,
         request.to_socket_id,
         EdgeKind::Configuration,
+        EdgeCreationSource::Manual,
     )
     .await?;
 
@@ -121,12 +125,13 @@ pub async fn create_connection(
         .update_from_prototype_function(&ctx)
         .await?;
 
-    ctx.enqueue_job(DependentValuesUpdate::new(
+    let job = DependentValuesUpdate::new(
         ctx.access_builder(),
         *ctx.visibility(),
         vec![*to_attribute_value.id()],
-    ))
-    .await?;
+    );
+    ctx.on_commit(move |ctx| async move { ctx.enqueue_job(job).await })
+        .await?;
 
     track(
         &posthog_client,
@@ -9,7 +9,9 @@ use dal::{
     SchemaId, StandardModel, Visibility, WsEvent,
 };
 
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
 use crate::service::diagram::{DiagramError, DiagramResult};
@@ -35,6 +37,7 @@ pub struct CreateNodeResponse {
 pub async fn create_node(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<CreateNodeRequest>,
@@ -53,7 +56,7 @@ pub async fn create_node(
 
         WsEvent::change_set_created(&ctx, change_set.pk)
             .await?
-            .publish_on_commit(&ctx)
+            .publish_after_commit(&ctx)
             .await?;
     };
 
@@ -119,7 +122,7 @@ pub async fn create_node(
 
     WsEvent::component_created(&ctx)
         .await?
-        .publish_on_commit(&ctx)
+        .publish_after_commit(&ctx)
         .await?;
 
     track(
@@ -1,14 +1,15 @@
 use axum::{extract::OriginalUri, http::uri::Uri};
 use axum::{response::IntoResponse, Json};
+use dal::component::delete::DeleteConfirmation;
 use dal::{
-    action_prototype::ActionPrototypeContextField, Action, ActionKind, ActionPrototype,
-    ActionPrototypeContext, ChangeSet, Component, ComponentId, DalContext, StandardModel,
-    Visibility, WsEvent,
+    Action, ChangeSet, Component, ComponentId, DalContext, StandardModel, Visibility, WsEvent,
 };
 use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -24,7 +25,7 @@ async fn delete_single_component(
     component_id: ComponentId,
     original_uri: &Uri,
     PosthogClient(posthog_client): &PosthogClient,
-) -> DiagramResult<()> {
+) -> DiagramResult<DeleteConfirmation> {
     let mut comp = Component::get_by_id(ctx, &component_id)
         .await?
         .ok_or(DiagramError::ComponentNotFound)?;
@@ -33,33 +34,12 @@ async fn delete_single_component(
         .schema(ctx)
         .await?
         .ok_or(DiagramError::SchemaNotFound)?;
-    let comp_schema_variant = comp
-        .schema_variant(ctx)
-        .await?
-        .ok_or(DiagramError::SchemaNotFound)?;
+    let comp_name = comp.name(ctx).await?;
 
-    let resource = comp.resource(ctx).await?;
-
-    // TODO: this is tricky, we don't want to delete all actions, but we don't need to be perfect
-    // right now, let's see how the usage plays with users
-    let actions = Action::find_for_change_set(ctx).await?;
-    for mut action in actions {
-        if action.component_id() == comp.id() {
-            action.delete_by_id(ctx).await?;
-        }
-    }
+    let confirmation = comp.delete(ctx).await?;
 
-    if resource.payload.is_some() {
-        for prototype in ActionPrototype::find_for_context_and_kind(
-            ctx,
-            ActionKind::Delete,
-            ActionPrototypeContext::new_for_context_field(
-                ActionPrototypeContextField::SchemaVariant(*comp_schema_variant.id()),
-            ),
-        )
-        .await?
-        {
-            let action = Action::new(ctx, *prototype.id(), *comp.id()).await?;
+    for action_id in &confirmation.resource_teardown_action_ids {
+        if let Some(action) = Action::get_by_id(ctx, action_id).await? {
             let prototype = action.prototype(ctx).await?;
 
             track(
@@ -72,15 +52,13 @@ async fn delete_single_component(
                     "prototype_id": prototype.id(),
                     "prototype_kind": prototype.kind(),
                     "component_id": comp.id(),
-                    "component_name": comp.name(ctx).await?,
+                    "component_name": &comp_name,
                     "change_set_pk": ctx.visibility().change_set_pk,
                 }),
             );
         }
     }
 
-    comp.delete_and_propagate(ctx).await?;
-
     track(
         posthog_client,
         ctx,
@@ -89,16 +67,21 @@ async fn delete_single_component(
         serde_json::json!({
             "component_id": comp.id(),
             "component_schema_name": comp_schema.name(),
+            "tears_down_resource": confirmation.tears_down_resource(),
         }),
     );
 
-    Ok(())
+    Ok(confirmation)
 }
 
-/// Delete a [`Component`](dal::Component) via its componentId. Creates change-set if on head
+pub type DeleteComponentResponse = DeleteConfirmation;
+
+/// Delete a [`Component`](dal::Component) via its componentId, returning what applying the change
+/// set will tear down. Creates change-set if on head
 pub async fn delete_component(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DeleteComponentRequest>,
@@ -107,7 +90,8 @@ pub async fn delete_component(
 
     let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
 
-    delete_single_component(&ctx, request.component_id, &original_uri, &posthog_client).await?;
+    let confirmation =
+        delete_single_component(&ctx, request.component_id, &original_uri, &posthog_client).await?;
 
     ctx.commit().await?;
 
@@ -115,7 +99,9 @@ pub async fn delete_component(
     if let Some(force_changeset_pk) = force_changeset_pk {
         response = response.header("force_changeset_pk", force_changeset_pk.to_string());
     }
-    Ok(response.body(axum::body::Empty::new())?)
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&confirmation)?)?)
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -130,6 +116,7 @@ pub struct DeleteComponentsRequest {
 pub async fn delete_components(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DeleteComponentsRequest>,
@@ -148,7 +135,7 @@ pub async fn delete_components(
 
         WsEvent::change_set_created(&ctx, change_set.pk)
             .await?
-            .publish_on_commit(&ctx)
+            .publish_after_commit(&ctx)
             .await?;
     };
 
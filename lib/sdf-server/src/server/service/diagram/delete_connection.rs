@@ -5,7 +5,9 @@ use dal::{ChangeSet, Connection, Edge, Node, Socket, Visibility};
 use serde::{Deserialize, Serialize};
 
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 use crate::service::diagram::DiagramError;
 use dal::standard_model::StandardModel;
@@ -18,10 +20,12 @@ pub struct DeleteConnectionRequest {
     pub visibility: Visibility,
 }
 
-/// Delete a [`Connection`](dal::Connection) via its EdgeId. Creating change-set if on head.
+/// Delete a [`Connection`](dal::Connection) via its EdgeId, resetting the values that depended on
+/// it. Creating change-set if on head.
 pub async fn delete_connection(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DeleteConnectionRequest>,
@@ -62,7 +66,7 @@ pub async fn delete_connection(
         .await?
         .ok_or(DiagramError::SocketNotFound)?;
 
-    Connection::delete_for_edge(&ctx, request.edge_id).await?;
+    let affected_attribute_value_ids = Connection::delete(&ctx, request.edge_id).await?;
 
     track(
         &posthog_client,
@@ -78,6 +82,7 @@ pub async fn delete_connection(
             "to_node_schema_name": to_component_schema.name(),
             "to_socket_id": conn.destination.socket_id,
             "to_socket_name":  &to_socket.name(),
+            "affected_attribute_value_ids": &affected_attribute_value_ids,
         }),
     );
 
@@ -1,9 +1,12 @@
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 use crate::service::diagram::{DiagramError, DiagramResult};
 use axum::extract::OriginalUri;
 use axum::response::IntoResponse;
 use axum::Json;
+use dal::edge::EdgeKind;
 use dal::{ChangeSet, Component, ComponentId, Edge, StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +22,7 @@ pub struct DetachComponentRequest {
 pub async fn detach_component_from_frame(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<DetachComponentRequest>,
@@ -32,6 +36,10 @@ pub async fn detach_component_from_frame(
 
     let child_comp_edges = Edge::list_for_component(&ctx, *child_comp.id()).await?;
     for mut child_comp_edge in child_comp_edges {
+        // Connections drawn by the user survive leaving the frame.
+        if *child_comp_edge.kind() != EdgeKind::Symbolic && !child_comp_edge.auto_created() {
+            continue;
+        }
         if request
             .parent_component_ids
             .contains(&child_comp_edge.head_component_id())
@@ -0,0 +1,136 @@
+use std::collections::{HashSet, VecDeque};
+
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::edge::EdgeKind;
+use dal::{
+    node::NodeId, ChangeSet, Component, ComponentId, DalContext, Edge, StandardModel, Visibility,
+};
+
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+
+use super::{DiagramError, DiagramResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DisconnectFrameConnectionRequest {
+    pub child_node_id: NodeId,
+    pub parent_node_id: NodeId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Undo [`connect_component_sockets_to_frame`](super::connect_component_to_frame::connect_component_sockets_to_frame).
+///
+/// Connecting a child to a frame creates the symbolic edge to the parent, but also configuration
+/// edges from the child (and all of its descendants) to the sockets of the parent and of any
+/// configuration frame above it. All of those edges are deleted here, while connections a user drew
+/// between the same components are kept. Deleting a configuration edge recomputes the destination
+/// value and enqueues a dependent values update for it. Generated names lose the namespace of
+/// any subsystem left behind.
+pub async fn disconnect_component_sockets_from_frame(
+    ctx: &DalContext,
+    parent_node_id: NodeId,
+    child_node_id: NodeId,
+) -> DiagramResult<Vec<ComponentId>> {
+    let parent_component = Component::find_for_node(ctx, parent_node_id)
+        .await?
+        .ok_or(DiagramError::NodeNotFound(parent_node_id))?;
+    let child_component = Component::find_for_node(ctx, child_node_id)
+        .await?
+        .ok_or(DiagramError::NodeNotFound(child_node_id))?;
+
+    // The frame chain the child is leaving: the parent and all of its ancestors.
+    let mut ancestors = HashSet::from([*parent_component.id()]);
+    let mut current = *parent_component.id();
+    while let Some(ancestor_id) = Edge::get_parent_for_component(ctx, current).await? {
+        if !ancestors.insert(ancestor_id) {
+            break;
+        }
+        current = ancestor_id;
+    }
+
+    // The child and everything inside it move out together.
+    let mut subtree = HashSet::new();
+    let mut queue = VecDeque::from([*child_component.id()]);
+    while let Some(component_id) = queue.pop_front() {
+        if subtree.insert(component_id) {
+            queue.extend(Edge::list_children_for_component(ctx, component_id).await?);
+        }
+    }
+
+    let mut disconnected = Vec::new();
+    for component_id in &subtree {
+        for mut edge in Edge::list_for_component(ctx, *component_id).await? {
+            if *edge.kind() != EdgeKind::Symbolic && !edge.auto_created() {
+                continue;
+            }
+            let other_id = if edge.head_component_id() == *component_id {
+                edge.tail_component_id()
+            } else {
+                edge.head_component_id()
+            };
+            if ancestors.contains(&other_id) {
+                edge.delete_and_propagate(ctx).await?;
+                if !disconnected.contains(component_id) {
+                    disconnected.push(*component_id);
+                }
+            }
+        }
+    }
+
+    // Leaving a subsystem drops its namespace from the generated names inside the child.
+    Component::apply_subsystem_namespace(ctx, *child_component.id()).await?;
+
+    Ok(disconnected)
+}
+
+/// Detach a child [`Node`](dal::Node) from its parent frame, tearing down the symbolic edge and
+/// every configuration edge that was created implicitly when it was connected. Creating a change
+/// set if on head.
+pub async fn disconnect_component_from_frame(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<DisconnectFrameConnectionRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let disconnected_component_ids = disconnect_component_sockets_from_frame(
+        &ctx,
+        request.parent_node_id,
+        request.child_node_id,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "component_disconnected_from_frame",
+        serde_json::json!({
+            "parent_node_id": &request.parent_node_id,
+            "child_node_id": &request.child_node_id,
+            "disconnected_component_ids": &disconnected_component_ids,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body("{}".to_owned())?)
+}
@@ -0,0 +1,142 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use dal::edge::EdgeKind;
+use dal::{
+    action_prototype::ActionPrototypeContextField, Action, ActionKind, ActionPrototype,
+    ActionPrototypeContext, ChangeSet, Component, ComponentError, ComponentId, Edge, Node, NodeId,
+    StandardModel, Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{DiagramError, DiagramResult};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
+
+/// How far from the original the duplicated node is placed, on both axes.
+const DUPLICATE_OFFSET: f64 = 50.0;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateComponentRequest {
+    pub node_id: NodeId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateComponentResponse {
+    pub component_id: ComponentId,
+    pub node: Node,
+}
+
+/// Duplicate the [`Component`](dal::Component) of a [`Node`](dal::Node), keeping it in the same
+/// frame as the original. Creates change-set if on head
+pub async fn duplicate_component(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<DuplicateComponentRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let original = Component::find_for_node(&ctx, request.node_id)
+        .await?
+        .ok_or(ComponentError::NotFoundForNode(request.node_id))?;
+
+    let (component, node) =
+        Component::duplicate(&ctx, *original.id(), DUPLICATE_OFFSET, DUPLICATE_OFFSET).await?;
+
+    let schema_variant = component
+        .schema_variant(&ctx)
+        .await?
+        .ok_or(DiagramError::SchemaVariantNotFound)?;
+    for prototype in ActionPrototype::find_for_context_and_kind(
+        &ctx,
+        ActionKind::Create,
+        ActionPrototypeContext::new_for_context_field(ActionPrototypeContextField::SchemaVariant(
+            *schema_variant.id(),
+        )),
+    )
+    .await?
+    {
+        let action = Action::new(&ctx, *prototype.id(), *component.id()).await?;
+        let prototype = action.prototype(&ctx).await?;
+
+        track(
+            &posthog_client,
+            &ctx,
+            &original_uri,
+            "create_action",
+            serde_json::json!({
+                "how": "/diagram/duplicate_component",
+                "prototype_id": prototype.id(),
+                "prototype_kind": prototype.kind(),
+                "component_id": component.id(),
+                "component_name": component.name(&ctx).await?,
+                "change_set_pk": ctx.visibility().change_set_pk,
+            }),
+        );
+    }
+
+    // The original's frame is the head of the symbolic edge it is the tail of
+    let parent_node_id = Edge::list_for_component(&ctx, *original.id())
+        .await?
+        .into_iter()
+        .find(|edge| {
+            *edge.kind() == EdgeKind::Symbolic && edge.tail_component_id() == *original.id()
+        })
+        .map(|edge| edge.head_node_id());
+    if let Some(parent_node_id) = parent_node_id {
+        connect_component_sockets_to_frame(
+            &ctx,
+            parent_node_id,
+            *node.id(),
+            &original_uri,
+            &posthog_client,
+        )
+        .await?;
+    }
+
+    let schema = component
+        .schema(&ctx)
+        .await?
+        .ok_or(DiagramError::SchemaNotFound)?;
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "duplicate_component",
+        serde_json::json!({
+            "original_component_id": original.id(),
+            "component_id": component.id(),
+            "component_schema_name": schema.name(),
+        }),
+    );
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_after_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    response = response.header("content-type", "application/json");
+    Ok(
+        response.body(serde_json::to_string(&DuplicateComponentResponse {
+            component_id: *component.id(),
+            node,
+        })?)?,
+    )
+}
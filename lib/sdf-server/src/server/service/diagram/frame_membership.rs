@@ -0,0 +1,144 @@
+use std::collections::{HashSet, VecDeque};
+
+use hyper::http::Uri;
+use serde::{Deserialize, Serialize};
+
+use dal::node::{NodeId, NodeKind};
+use dal::{Component, ComponentId, ComponentType, DalContext, Edge, Node, StandardModel};
+
+use super::connect_component_to_frame::connect_component_sockets_to_frame;
+use super::disconnect_component_from_frame::disconnect_component_sockets_from_frame;
+use super::{DiagramError, DiagramResult};
+
+/// The changes made to the frame membership of a [`Node`] after it was moved.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameMembershipUpdate {
+    /// The frame the node was connected to, if it moved into one.
+    pub attached_to: Option<NodeId>,
+    /// The frame the node was disconnected from, if it moved out of one.
+    pub detached_from: Option<NodeId>,
+}
+
+/// The area a frame covers on the diagram. The position of a node is the center of its top edge.
+#[derive(Debug, Clone, Copy)]
+struct FrameBounds {
+    node_id: NodeId,
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+}
+
+impl FrameBounds {
+    fn for_node(node: &Node) -> Option<Self> {
+        let x: f64 = node.x().parse().ok()?;
+        let y: f64 = node.y().parse().ok()?;
+        let width: f64 = node.width()?.parse().ok()?;
+        let height: f64 = node.height()?.parse().ok()?;
+        Some(Self {
+            node_id: *node.id(),
+            left: x - width / 2.0,
+            top: y,
+            width,
+            height,
+        })
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.left
+            && x <= self.left + self.width
+            && y >= self.top
+            && y <= self.top + self.height
+    }
+
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+/// Makes the frame containing the position of the [`Node`] its parent, replacing the frame it was
+/// in (if any). When frames are nested, the innermost one (i.e. the smallest) wins. Frames inside
+/// the node itself are never candidates, since that would make the node its own ancestor.
+///
+/// This is what [`connect_component_to_frame`](super::connect_component_to_frame) and
+/// [`disconnect_component_from_frame`](super::disconnect_component_from_frame) do, driven by the
+/// geometry of the diagram rather than by explicit requests.
+pub async fn update_frame_membership(
+    ctx: &DalContext,
+    node: &Node,
+    original_uri: &Uri,
+    posthog_client: &crate::server::state::PosthogClient,
+) -> DiagramResult<FrameMembershipUpdate> {
+    let (x, y) = match (node.x().parse::<f64>(), node.y().parse::<f64>()) {
+        (Ok(x), Ok(y)) => (x, y),
+        _ => return Ok(FrameMembershipUpdate::default()),
+    };
+    let component = Component::find_for_node(ctx, *node.id())
+        .await?
+        .ok_or(DiagramError::ComponentNotFound)?;
+
+    // The node and everything inside it.
+    let mut subtree: HashSet<ComponentId> = HashSet::new();
+    let mut queue = VecDeque::from([*component.id()]);
+    while let Some(component_id) = queue.pop_front() {
+        if subtree.insert(component_id) {
+            queue.extend(Edge::list_children_for_component(ctx, component_id).await?);
+        }
+    }
+
+    let mut target: Option<FrameBounds> = None;
+    for candidate in Node::list_live(ctx, NodeKind::Configuration).await? {
+        let bounds = match FrameBounds::for_node(&candidate) {
+            Some(bounds) if bounds.contains(x, y) => bounds,
+            _ => continue,
+        };
+        if target.map_or(false, |target| target.area() <= bounds.area()) {
+            continue;
+        }
+        let candidate_component = match Component::find_for_node(ctx, *candidate.id()).await? {
+            Some(candidate_component) => candidate_component,
+            None => continue,
+        };
+        if subtree.contains(candidate_component.id())
+            || candidate_component.get_type(ctx).await? == ComponentType::Component
+        {
+            continue;
+        }
+        target = Some(bounds);
+    }
+    let target = target.map(|bounds| bounds.node_id);
+
+    let current = match Edge::get_parent_for_component(ctx, *component.id()).await? {
+        Some(parent_id) => Component::get_by_id(ctx, &parent_id)
+            .await?
+            .ok_or(DiagramError::ComponentNotFound)?
+            .node(ctx)
+            .await?
+            .pop()
+            .map(|parent_node| *parent_node.id()),
+        None => None,
+    };
+
+    let mut update = FrameMembershipUpdate::default();
+    if current == target {
+        return Ok(update);
+    }
+    if let Some(parent_node_id) = current {
+        disconnect_component_sockets_from_frame(ctx, parent_node_id, *node.id()).await?;
+        update.detached_from = Some(parent_node_id);
+    }
+    if let Some(parent_node_id) = target {
+        connect_component_sockets_to_frame(
+            ctx,
+            parent_node_id,
+            *node.id(),
+            original_uri,
+            posthog_client,
+        )
+        .await?;
+        update.attached_to = Some(parent_node_id);
+    }
+
+    Ok(update)
+}
@@ -0,0 +1,189 @@
+use axum::extract::{OriginalUri, Query};
+use axum::{response::IntoResponse, Json};
+use dal::{
+    ChangeSet, Component, ComponentId, ComponentType, FrameVariable, FrameVariableId, Secret,
+    SecretId, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{DiagramError, DiagramResult};
+use crate::server::extract::{
+    AccessBuilder, DiagramRead, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameVariableView {
+    pub id: FrameVariableId,
+    pub name: String,
+    pub value: Option<serde_json::Value>,
+    pub secret: bool,
+}
+
+impl From<FrameVariable> for FrameVariableView {
+    fn from(variable: FrameVariable) -> Self {
+        Self {
+            id: *variable.id(),
+            name: variable.name().to_owned(),
+            value: variable.value().cloned(),
+            secret: variable.secret(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFrameVariablesRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListFrameVariablesResponse = Vec<FrameVariableView>;
+
+/// List the variables defined on a configuration frame.
+pub async fn list_frame_variables(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
+    Query(request): Query<ListFrameVariablesRequest>,
+) -> DiagramResult<Json<ListFrameVariablesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let variables = FrameVariable::list_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(variables))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFrameVariableRequest {
+    pub component_id: ComponentId,
+    pub name: String,
+    /// The id of a [`Secret`](dal::Secret) when `secret` is set.
+    pub value: Option<serde_json::Value>,
+    #[serde(default)]
+    pub secret: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Define (or redefine) a variable on a configuration frame and expose it to every component
+/// inside of it.
+pub async fn set_frame_variable(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetFrameVariableRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(DiagramError::ComponentNotFound)?;
+    let component_type = component.get_type(&ctx).await?;
+    if !component_type.is_configuration_frame_down() {
+        return Err(DiagramError::InvalidComponentTypeForFrame(component_type));
+    }
+
+    if request.secret {
+        let secret_id: SecretId =
+            serde_json::from_value(request.value.clone().ok_or(DiagramError::InvalidRequest)?)?;
+        Secret::get_by_id(&ctx, &secret_id)
+            .await?
+            .ok_or(DiagramError::SecretNotFound(secret_id))?;
+    }
+
+    let variable = FrameVariable::upsert(
+        &ctx,
+        request.component_id,
+        &request.name,
+        request.value,
+        request.secret,
+    )
+    .await?;
+    variable.expose_to_descendants(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_frame_variable",
+        serde_json::json!({
+            "component_id": request.component_id,
+            "frame_variable_name": variable.name(),
+            "frame_variable_secret": variable.secret(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&FrameVariableView::from(variable))?)?)
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveFrameVariableRequest {
+    pub component_id: ComponentId,
+    pub name: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Remove a variable from a configuration frame. Values already exposed to the components inside
+/// of the frame are kept until they are set again.
+pub async fn remove_frame_variable(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RemoveFrameVariableRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut variable = FrameVariable::list_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .find(|variable| variable.name() == request.name)
+        .ok_or_else(|| DiagramError::FrameVariableNotFound(request.name.clone()))?;
+    variable.delete_by_id(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "remove_frame_variable",
+        serde_json::json!({
+            "component_id": request.component_id,
+            "frame_variable_name": request.name,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body("{}".to_owned())?)
+}
@@ -1,27 +1,44 @@
 use axum::{extract::Query, Json};
-use dal::{Diagram, Visibility};
+use dal::{ComponentId, Diagram, DiagramPage, Visibility};
 use serde::{Deserialize, Serialize};
 
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{AccessBuilder, DiagramRead, HandlerContext, RequirePermission};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDiagramRequest {
+    /// The "nextCursor" of the previous page.
+    pub cursor: Option<ComponentId>,
+    /// The number of components in a page. The whole diagram is returned when neither this nor
+    /// the cursor is set.
+    pub limit: Option<usize>,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-pub type GetDiagramResponse = Diagram;
+pub type GetDiagramResponse = DiagramPage;
+
+/// The number of components in a page when only the cursor is set.
+const DEFAULT_PAGE_LIMIT: usize = 500;
 
 pub async fn get_diagram(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
     Query(request): Query<GetDiagramRequest>,
 ) -> DiagramResult<Json<GetDiagramResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
-    let response = Diagram::assemble(&ctx).await?;
+    let response = match (request.cursor, request.limit) {
+        (None, None) => DiagramPage {
+            diagram: Diagram::assemble(&ctx).await?,
+            next_cursor: None,
+        },
+        (cursor, limit) => {
+            Diagram::assemble_page(&ctx, cursor, limit.unwrap_or(DEFAULT_PAGE_LIMIT)).await?
+        }
+    };
 
     Ok(Json(response))
 }
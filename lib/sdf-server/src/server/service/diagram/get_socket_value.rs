@@ -0,0 +1,52 @@
+use axum::{extract::Query, Json};
+use dal::{
+    AttributeReadContext, AttributeValue, ComponentId, ExternalProvider, SocketId, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{DiagramError, DiagramResult};
+use crate::server::extract::{AccessBuilder, DiagramRead, HandlerContext, RequirePermission};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSocketValueRequest {
+    pub component_id: ComponentId,
+    pub socket_id: SocketId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSocketValueResponse {
+    pub value: Option<serde_json::Value>,
+}
+
+/// Returns the value an output socket of a component currently emits, i.e. the latest resolved
+/// value of the socket's external provider for that component.
+pub async fn get_socket_value(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
+    Query(request): Query<GetSocketValueRequest>,
+) -> DiagramResult<Json<GetSocketValueResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let external_provider = ExternalProvider::find_for_socket(&ctx, request.socket_id)
+        .await?
+        .ok_or(DiagramError::ExternalProviderNotFoundForSocket(
+            request.socket_id,
+        ))?;
+
+    let read_context = AttributeReadContext {
+        external_provider_id: Some(*external_provider.id()),
+        component_id: Some(request.component_id),
+        ..AttributeReadContext::default()
+    };
+    let attribute_value = AttributeValue::find_for_context(&ctx, read_context)
+        .await?
+        .ok_or(DiagramError::AttributeValueNotFoundForContext(read_context))?;
+    let value = attribute_value.get_value(&ctx).await?;
+
+    Ok(Json(GetSocketValueResponse { value }))
+}
@@ -0,0 +1,111 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::discovery::{import_aws_resources as import_resources, DiscoveryReport};
+use dal::node::NodeId;
+use dal::{ChangeSet, Node, SecretId, StandardModel, Visibility, WsEvent};
+
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+/// How many components are laid out per row of the grid, starting at the requested position.
+const GRID_COLUMNS: usize = 5;
+/// The distance between two components of the grid.
+const GRID_SPACING: f64 = 300.0;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAwsResourcesRequest {
+    /// The "AWS Credential" secret to discover resources with.
+    pub secret_id: SecretId,
+    pub region: String,
+    pub parent_id: Option<NodeId>,
+    pub x: f64,
+    pub y: f64,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ImportAwsResourcesResponse = DiscoveryReport;
+
+/// Import the existing security groups and rules of an AWS region as components, optionally
+/// inside of a frame, with their attribute values and resources set from the live resources.
+/// Resources already modeled by a component are skipped. Creates change-set if on head
+pub async fn import_aws_resources(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ImportAwsResourcesRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    if request.region.is_empty() {
+        return Err(DiagramError::InvalidRequest);
+    }
+
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let report = import_resources(&ctx, request.secret_id, &request.region).await?;
+
+    for (index, imported) in report.imported.iter().enumerate() {
+        let mut node = Node::get_by_id(&ctx, &imported.node_id)
+            .await?
+            .ok_or(DiagramError::NodeNotFound(imported.node_id))?;
+        let x = request.x + (index % GRID_COLUMNS) as f64 * GRID_SPACING;
+        let y = request.y + (index / GRID_COLUMNS) as f64 * GRID_SPACING;
+        node.set_geometry(
+            &ctx,
+            x.to_string(),
+            y.to_string(),
+            Option::<&str>::None,
+            Option::<&str>::None,
+        )
+        .await?;
+
+        if let Some(frame_id) = request.parent_id {
+            connect_component_sockets_to_frame(
+                &ctx,
+                frame_id,
+                *node.id(),
+                &original_uri,
+                &posthog_client,
+            )
+            .await?;
+        }
+    }
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_after_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "aws_resources_imported",
+        serde_json::json!({
+                    "region": request.region,
+                    "imported_count": report.imported.len(),
+                    "skipped_count": report.skipped.len(),
+                    "parent_id": request.parent_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut builder = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        builder = builder.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(builder
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&report)?)?)
+}
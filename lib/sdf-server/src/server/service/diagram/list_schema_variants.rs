@@ -7,7 +7,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{AccessBuilder, DiagramRead, HandlerContext, RequirePermission};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -67,6 +67,7 @@ pub type ListSchemaVariantsResponse = Vec<SchemaVariantView>;
 pub async fn list_schema_variants(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
     Query(request): Query<ListSchemaVariantsRequest>,
 ) -> DiagramResult<Json<ListSchemaVariantsResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
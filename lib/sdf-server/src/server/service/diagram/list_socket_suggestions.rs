@@ -0,0 +1,31 @@
+use axum::{extract::Query, Json};
+use dal::diagram::socket_suggestion::SocketSuggestion;
+use dal::{NodeId, SocketId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::DiagramResult;
+use crate::server::extract::{AccessBuilder, DiagramRead, HandlerContext, RequirePermission};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSocketSuggestionsRequest {
+    pub node_id: NodeId,
+    pub socket_id: SocketId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListSocketSuggestionsResponse = Vec<SocketSuggestion>;
+
+pub async fn list_socket_suggestions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
+    Query(request): Query<ListSocketSuggestionsRequest>,
+) -> DiagramResult<Json<ListSocketSuggestionsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let response = SocketSuggestion::list(&ctx, request.node_id, request.socket_id).await?;
+
+    Ok(Json(response))
+}
@@ -4,7 +4,9 @@ use dal::{ChangeSet, Component, ComponentId, DalContext, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 use crate::service::diagram::DiagramError;
 use dal::standard_model::StandardModel;
@@ -23,7 +25,7 @@ async fn restore_single_component(
     original_uri: &Uri,
     PosthogClient(posthog_client): &PosthogClient,
 ) -> DiagramResult<()> {
-    Component::restore_and_propagate(ctx, component_id).await?;
+    Component::restore(ctx, component_id).await?;
 
     // Track
     {
@@ -53,10 +55,12 @@ async fn restore_single_component(
     Ok(())
 }
 
-/// Restore a [`Component`](dal::Component) via its componentId. Creating change set if on head.
+/// Restore a [`Component`](dal::Component) deleted in the change set via its componentId, dropping
+/// the actions queued to tear down its resource. Creating change set if on head.
 pub async fn restore_component(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<RestoreComponentRequest>,
@@ -88,6 +92,7 @@ pub struct RestoreComponentsRequest {
 pub async fn restore_components(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     posthog_client: PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<RestoreComponentsRequest>,
@@ -106,7 +111,7 @@ pub async fn restore_components(
 
         WsEvent::change_set_created(&ctx, change_set.pk)
             .await?
-            .publish_on_commit(&ctx)
+            .publish_after_commit(&ctx)
             .await?;
     };
 
@@ -5,7 +5,9 @@ use dal::{ChangeSet, Connection, Edge, Node, Socket, Visibility};
 use serde::{Deserialize, Serialize};
 
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::server::tracking::track;
 use crate::service::diagram::DiagramError;
 use dal::standard_model::StandardModel;
@@ -22,6 +24,7 @@ pub struct UndeleteConnectionRequest {
 pub async fn restore_connection(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
     PosthogClient(posthog_client): PosthogClient,
     OriginalUri(original_uri): OriginalUri,
     Json(request): Json<UndeleteConnectionRequest>,
@@ -1,6 +1,10 @@
+use super::frame_membership::{update_frame_membership, FrameMembershipUpdate};
 use super::DiagramResult;
-use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
 use crate::service::diagram::DiagramError;
+use axum::extract::OriginalUri;
 use axum::Json;
 use dal::node::NodeId;
 use dal::socket::SocketEdgeKind;
@@ -17,17 +21,29 @@ pub struct SetNodePositionRequest {
     pub y: String,
     pub width: Option<String>,
     pub height: Option<String>,
+    /// Whether moving the node in or out of a frame connects it to or disconnects it from that
+    /// frame. Clients that manage frame connections themselves opt out.
+    #[serde(default = "auto_frame_membership_default")]
+    pub auto_frame_membership: bool,
+}
+
+fn auto_frame_membership_default() -> bool {
+    true
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SetNodePositionResponse {
     pub node: Node,
+    pub frame_membership: FrameMembershipUpdate,
 }
 
 pub async fn set_node_position(
     HandlerContext(builder): HandlerContext,
     AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
     Json(request): Json<SetNodePositionRequest>,
 ) -> DiagramResult<Json<SetNodePositionResponse>> {
     let visibility = Visibility::new_change_set(request.visibility.change_set_pk, true);
@@ -70,6 +86,7 @@ pub async fn set_node_position(
         size
     };
 
+    let mut frame_membership = FrameMembershipUpdate::default();
     {
         if node.visibility().deleted_at.is_some() {
             node.set_geometry(&ctx, &request.x, &request.y, width, height)
@@ -82,10 +99,24 @@ pub async fn set_node_position(
 
             node.set_geometry(ctx_without_deleted, &request.x, &request.y, width, height)
                 .await?;
+
+            // Frame connections are only ever made in a change set.
+            if request.auto_frame_membership && !ctx.visibility().is_head() {
+                frame_membership = update_frame_membership(
+                    ctx_without_deleted,
+                    &node,
+                    &original_uri,
+                    &posthog_client,
+                )
+                .await?;
+            }
         };
     }
 
     ctx.commit().await?;
 
-    Ok(Json(SetNodePositionResponse { node }))
+    Ok(Json(SetNodePositionResponse {
+        node,
+        frame_membership,
+    }))
 }
@@ -0,0 +1,167 @@
+use axum::extract::{OriginalUri, Query};
+use axum::{response::IntoResponse, Json};
+use dal::job::definition::DependentValuesUpdate;
+use dal::socket::SocketEdgeKind;
+use dal::{
+    AttributeReadContext, AttributeValue, ChangeSet, Component, ComponentId, ComponentType,
+    ExternalProvider, MergeStrategy, Socket, SocketId, SocketMergeStrategy, StandardModel,
+    Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{DiagramError, DiagramResult};
+use crate::server::extract::{
+    AccessBuilder, DiagramRead, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketMergeStrategyView {
+    pub socket_id: SocketId,
+    pub strategy: MergeStrategy,
+}
+
+impl From<SocketMergeStrategy> for SocketMergeStrategyView {
+    fn from(strategy: SocketMergeStrategy) -> Self {
+        Self {
+            socket_id: strategy.socket_id(),
+            strategy: *strategy.strategy(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSocketMergeStrategiesRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListSocketMergeStrategiesResponse = Vec<SocketMergeStrategyView>;
+
+/// List the merge strategies chosen for the output sockets of an aggregation frame.
+pub async fn list_socket_merge_strategies(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
+    Query(request): Query<ListSocketMergeStrategiesRequest>,
+) -> DiagramResult<Json<ListSocketMergeStrategiesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let strategies = SocketMergeStrategy::list_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(strategies))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSocketMergeStrategyRequest {
+    pub component_id: ComponentId,
+    pub socket_id: SocketId,
+    /// No strategy goes back to handing the values over as they come.
+    pub strategy: Option<MergeStrategy>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Choose how an aggregation frame merges the values of its children for one of its output
+/// sockets, and merge them again right away.
+pub async fn set_socket_merge_strategy(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetSocketMergeStrategyRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(DiagramError::ComponentNotFound)?;
+    let component_type = component.get_type(&ctx).await?;
+    if component_type != ComponentType::AggregationFrame {
+        return Err(DiagramError::InvalidComponentTypeForFrame(component_type));
+    }
+
+    let socket = Socket::list_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .find(|socket| *socket.id() == request.socket_id)
+        .ok_or(DiagramError::SocketNotFound)?;
+    if *socket.edge_kind() != SocketEdgeKind::ConfigurationOutput {
+        return Err(DiagramError::InvalidRequest);
+    }
+
+    match request.strategy {
+        Some(strategy) => {
+            SocketMergeStrategy::upsert(&ctx, request.component_id, request.socket_id, strategy)
+                .await?;
+        }
+        None => {
+            let existing = SocketMergeStrategy::list_for_component(&ctx, request.component_id)
+                .await?
+                .into_iter()
+                .find(|strategy| strategy.socket_id() == request.socket_id);
+            if let Some(mut existing) = existing {
+                existing.delete_by_id(&ctx).await?;
+            }
+        }
+    }
+
+    let provider = ExternalProvider::find_for_socket(&ctx, request.socket_id)
+        .await?
+        .ok_or(DiagramError::ExternalProviderNotFoundForSocket(
+            request.socket_id,
+        ))?;
+    let attribute_value_context = AttributeReadContext {
+        component_id: Some(request.component_id),
+        external_provider_id: Some(*provider.id()),
+        ..Default::default()
+    };
+    let mut attribute_value = AttributeValue::find_for_context(&ctx, attribute_value_context)
+        .await?
+        .ok_or(DiagramError::AttributeValueNotFoundForContext(
+            attribute_value_context,
+        ))?;
+    attribute_value.update_from_prototype_function(&ctx).await?;
+
+    let job = DependentValuesUpdate::new(
+        ctx.access_builder(),
+        *ctx.visibility(),
+        vec![*attribute_value.id()],
+    );
+    ctx.on_commit(move |ctx| async move { ctx.enqueue_job(job).await })
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_socket_merge_strategy",
+        serde_json::json!({
+            "component_id": request.component_id,
+            "socket_id": request.socket_id,
+            "socket_name": socket.name(),
+            "strategy": request.strategy,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body("{}".to_owned())?)
+}
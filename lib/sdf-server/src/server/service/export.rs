@@ -0,0 +1,43 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use dal::cloudformation::CloudFormationError;
+use dal::TransactionsError;
+use hyper::StatusCode;
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod export_cloudformation;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error(transparent)]
+    CloudFormation(#[from] CloudFormationError),
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+}
+
+pub type ExportResult<T> = std::result::Result<T, ExportError>;
+
+impl IntoResponse for ExportError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/export_cloudformation",
+        get(export_cloudformation::export_cloudformation),
+    )
+}
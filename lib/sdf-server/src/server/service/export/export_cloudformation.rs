@@ -0,0 +1,28 @@
+use axum::{extract::Query, Json};
+use dal::cloudformation::CloudFormationExport;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::ExportResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportCloudFormationRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ExportCloudFormationResponse = CloudFormationExport;
+
+pub async fn export_cloudformation(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ExportCloudFormationRequest>,
+) -> ExportResult<Json<ExportCloudFormationResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let response = CloudFormationExport::assemble(&ctx).await?;
+
+    Ok(Json(response))
+}
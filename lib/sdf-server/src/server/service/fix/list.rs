@@ -1,7 +1,7 @@
 use axum::{extract::Query, Json};
 use chrono::Utc;
 use dal::fix::FixHistoryView;
-use dal::{FixBatch, FixBatchId, FixCompletionStatus};
+use dal::{FixBatch, FixBatchId, FixBatchTargetFilter, FixCompletionStatus};
 use dal::{StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 
@@ -25,6 +25,7 @@ pub struct BatchHistoryView {
     fixes: Vec<FixHistoryView>,
     started_at: Option<String>,
     finished_at: Option<String>,
+    target_filter: Option<FixBatchTargetFilter>,
 }
 
 pub type ListFixesResponse = Vec<BatchHistoryView>;
@@ -82,6 +83,7 @@ pub async fn list(
             actors: fix_actors,
             started_at: batch.started_at().map(|s| s.to_string()),
             finished_at: batch.finished_at().map(|s| s.to_string()),
+            target_filter: batch.target_filter().cloned(),
         })
     }
 
@@ -11,6 +11,7 @@ use tokio::task::JoinError;
 
 use dal::authentication_prototype::{AuthenticationPrototype, AuthenticationPrototypeError};
 use dal::func::execution::FuncExecutionError;
+use dal::func::execution_log::FuncExecutionLogError;
 use dal::{
     attribute::context::{AttributeContextBuilder, AttributeContextBuilderError},
     func::{
@@ -33,10 +34,12 @@ use dal::{
 use crate::server::{impl_default_error_into_response, state::AppState};
 use crate::service::func::get_func::GetFuncResponse;
 
+pub mod check_func;
 pub mod create_func;
 pub mod delete_func;
 pub mod execute;
 pub mod get_func;
+pub mod get_func_executions;
 pub mod list_funcs;
 pub mod list_input_sources;
 pub mod revert_func;
@@ -125,6 +128,8 @@ pub enum FuncError {
     FuncExecutionFailed(String),
     #[error("Function execution failed: this function is not connected to any assets, and was not executed")]
     FuncExecutionFailedNoPrototypes,
+    #[error("Function execution log: {0}")]
+    FuncExecutionLog(#[from] FuncExecutionLogError),
     #[error("Function still has associations: {0}")]
     FuncHasAssociations(FuncId),
     #[error("Function named \"{0}\" already exists in this changeset")]
@@ -246,12 +251,16 @@ impl TryFrom<&Func> for FuncVariant {
             | (FuncBackendKind::Diff, _)
             | (FuncBackendKind::Identity, _)
             | (FuncBackendKind::Integer, _)
+            | (FuncBackendKind::Join, _)
             | (FuncBackendKind::JsSchemaVariantDefinition, _)
             | (FuncBackendKind::Map, _)
             | (FuncBackendKind::Object, _)
+            | (FuncBackendKind::Select, _)
             | (FuncBackendKind::String, _)
+            | (FuncBackendKind::Template, _)
             | (FuncBackendKind::Unset, _)
-            | (FuncBackendKind::Validation, _) => {
+            | (FuncBackendKind::Validation, _)
+            | (FuncBackendKind::WasmAttribute, _) => {
                 Err(FuncError::FuncCannotBeTurnedIntoVariant(*func.id()))
             }
         }
@@ -927,6 +936,11 @@ pub fn routes() -> Router<AppState> {
             "/get_func_last_execution",
             get(get_func::get_latest_func_execution),
         )
+        .route(
+            "/get_func_executions",
+            get(get_func_executions::get_func_executions),
+        )
+        .route("/check_func", post(check_func::check_func))
         .route("/create_func", post(create_func::create_func))
         .route("/save_func", post(save_func::save_func))
         .route("/delete_func", post(delete_func::delete_func))
@@ -0,0 +1,39 @@
+use axum::Json;
+use dal::func::lint::FuncCodeProblem;
+use dal::{Func, FuncId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{FuncError, FuncResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckFuncRequest {
+    pub id: FuncId,
+    pub code: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckFuncResponse {
+    pub problems: Vec<FuncCodeProblem>,
+}
+
+/// Compile the code being authored for a [`Func`] with veritech and report its problems, without
+/// saving it.
+pub async fn check_func(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<CheckFuncRequest>,
+) -> FuncResult<Json<CheckFuncResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let func = Func::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(FuncError::FuncNotFound)?;
+    let problems = func.check_code(&ctx, &request.code).await?;
+
+    Ok(Json(CheckFuncResponse { problems }))
+}
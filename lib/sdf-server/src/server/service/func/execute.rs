@@ -101,8 +101,6 @@ pub async fn execute(
 /// This method requires an owned [`WsEvent`](crate::WsEvent), despite it not needing to,
 ///  because [`events`](crate::WsEvent) should likely not be reused.
 async fn publish_immediately(ctx: &DalContext, ws_event: WsEvent) -> FuncBindingResult<()> {
-    let subject = format!("si.workspace_pk.{}.event", ws_event.workspace_pk());
-    let msg_bytes = serde_json::to_vec(&ws_event)?;
-    ctx.nats_conn().publish(subject, msg_bytes.into()).await?;
+    ws_event.publish_immediately(ctx).await?;
     Ok(())
 }
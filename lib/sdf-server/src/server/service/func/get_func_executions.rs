@@ -0,0 +1,76 @@
+use super::FuncResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use axum::{extract::Query, Json};
+use chrono::{DateTime, Utc};
+use dal::func::execution::FuncExecutionPk;
+use dal::func::execution_log::FuncExecutionLog;
+use dal::{AttributeValueId, ComponentId, FuncBackendKind, FuncId, Visibility};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFuncExecutionsRequest {
+    pub component_id: Option<ComponentId>,
+    pub func_id: Option<FuncId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncExecutionView {
+    pub func_execution_pk: FuncExecutionPk,
+    pub func_id: FuncId,
+    pub component_id: Option<ComponentId>,
+    pub attribute_value_id: Option<AttributeValueId>,
+    pub backend_kind: FuncBackendKind,
+    pub success: bool,
+    pub failure: Option<String>,
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: i64,
+}
+
+impl From<FuncExecutionLog> for FuncExecutionView {
+    fn from(log: FuncExecutionLog) -> Self {
+        Self {
+            func_execution_pk: log.func_execution_pk(),
+            func_id: log.func_id(),
+            component_id: (log.component_id() != ComponentId::NONE).then_some(log.component_id()),
+            attribute_value_id: (log.attribute_value_id() != AttributeValueId::NONE)
+                .then_some(log.attribute_value_id()),
+            backend_kind: log.backend_kind(),
+            success: log.success(),
+            failure: log.failure().map(ToOwned::to_owned),
+            stdout: log.stdout().to_vec(),
+            stderr: log.stderr().to_vec(),
+            started_at: log.started_at(),
+            finished_at: log.finished_at(),
+            duration_ms: log.duration_ms(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFuncExecutionsResponse {
+    pub executions: Vec<FuncExecutionView>,
+}
+
+pub async fn get_func_executions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetFuncExecutionsRequest>,
+) -> FuncResult<Json<GetFuncExecutionsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let executions = FuncExecutionLog::list(&ctx, request.component_id, request.func_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(GetFuncExecutionsResponse { executions }))
+}
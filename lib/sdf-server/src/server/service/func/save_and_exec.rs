@@ -143,7 +143,7 @@ pub async fn save_and_exec(
     let (save_func_response, func) = do_save_func(&ctx, request).await?;
 
     match func.backend_kind() {
-        FuncBackendKind::JsAttribute => {
+        FuncBackendKind::JsAttribute | FuncBackendKind::WasmAttribute => {
             update_values_for_func(&ctx, &func).await?;
         }
         FuncBackendKind::JsAction => {
@@ -154,12 +154,15 @@ pub async fn save_and_exec(
         | FuncBackendKind::Diff
         | FuncBackendKind::Identity
         | FuncBackendKind::Integer
+        | FuncBackendKind::Join
         | FuncBackendKind::JsAuthentication
         | FuncBackendKind::JsReconciliation
         | FuncBackendKind::JsSchemaVariantDefinition
         | FuncBackendKind::Map
         | FuncBackendKind::Object
+        | FuncBackendKind::Select
         | FuncBackendKind::String
+        | FuncBackendKind::Template
         | FuncBackendKind::Unset
         | FuncBackendKind::Validation
         | FuncBackendKind::JsValidation => Err(FuncError::FuncNotRunnable)?,
@@ -638,14 +638,18 @@ pub async fn do_save_func(
         | FuncBackendKind::Diff
         | FuncBackendKind::Identity
         | FuncBackendKind::Integer
+        | FuncBackendKind::Join
         | FuncBackendKind::JsReconciliation
         | FuncBackendKind::JsSchemaVariantDefinition
         | FuncBackendKind::Map
         | FuncBackendKind::Object
+        | FuncBackendKind::Select
         | FuncBackendKind::String
+        | FuncBackendKind::Template
         | FuncBackendKind::Unset
         | FuncBackendKind::Validation
-        | FuncBackendKind::JsValidation => return Err(FuncError::NotWritable),
+        | FuncBackendKind::JsValidation
+        | FuncBackendKind::WasmAttribute => return Err(FuncError::NotWritable),
     }
 
     let is_revertible = super::is_func_revertible(ctx, &func).await?;
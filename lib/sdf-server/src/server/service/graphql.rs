@@ -0,0 +1,92 @@
+//! An optional, read-only GraphQL endpoint exposing [`Components`](dal::Component), their prop
+//! trees, sockets and edges as a graph, so that external tooling can query exactly the fields it
+//! needs instead of stitching REST calls together. It is only built with the "graphql" feature.
+//!
+//! Queries are answered with the visibility given in the query string of the request, like any
+//! other route: `POST /api/graphql?visibility_change_set_pk=...`.
+
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    extract::Query,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use dal::{TransactionsError, Visibility};
+use hyper::StatusCode;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::state::AppState;
+
+pub mod query;
+
+use query::QueryRoot;
+
+/// How deeply queries may nest, since edges lead back to components.
+const MAX_QUERY_DEPTH: usize = 16;
+
+pub type SdfSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+static SCHEMA: Lazy<SdfSchema> = Lazy::new(|| {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+});
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum GraphqlError {
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+}
+
+pub type GraphqlResult<T> = std::result::Result<T, GraphqlError>;
+
+impl IntoResponse for GraphqlError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphqlRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Answer a GraphQL query. Errors of the resolvers are part of the GraphQL response.
+pub async fn graphql(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GraphqlRequest>,
+    graphql_request: GraphQLRequest,
+) -> GraphqlResult<GraphQLResponse> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    Ok(SCHEMA
+        .execute(graphql_request.into_inner().data(ctx))
+        .await
+        .into())
+}
+
+/// The schema of the endpoint, in the GraphQL schema definition language.
+pub async fn schema() -> String {
+    SCHEMA.sdl()
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(graphql))
+        .route("/schema", get(schema))
+}
@@ -0,0 +1,190 @@
+//! The read-only graph served by the GraphQL endpoint: [`Components`](Component) lead to their
+//! prop trees, sockets and edges, and edges lead back to [`Components`](Component).
+
+use async_graphql::{Context, Json, Object, Result, ID};
+use dal::component::view::ComponentView;
+use dal::{Component, ComponentId, DalContext, Edge, Prop, Socket, StandardModel};
+
+/// The [`DalContext`] the query is answered with.
+fn dal_ctx<'a>(ctx: &Context<'a>) -> Result<&'a DalContext> {
+    ctx.data::<DalContext>()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Every component of the workspace.
+    async fn components(&self, ctx: &Context<'_>) -> Result<Vec<ComponentNode>> {
+        let ctx = dal_ctx(ctx)?;
+        Ok(Component::list(ctx)
+            .await?
+            .into_iter()
+            .map(ComponentNode)
+            .collect())
+    }
+
+    /// The component with the given id, if there is one.
+    async fn component(&self, ctx: &Context<'_>, id: ID) -> Result<Option<ComponentNode>> {
+        let ctx = dal_ctx(ctx)?;
+        let component_id: ComponentId = id.parse()?;
+        Ok(Component::get_by_id(ctx, &component_id)
+            .await?
+            .map(ComponentNode))
+    }
+}
+
+pub struct ComponentNode(Component);
+
+#[Object(name = "Component")]
+impl ComponentNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id().to_string())
+    }
+
+    async fn name(&self, ctx: &Context<'_>) -> Result<String> {
+        Ok(self.0.name(dal_ctx(ctx)?).await?)
+    }
+
+    async fn schema_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let schema = self.0.schema(dal_ctx(ctx)?).await?;
+        Ok(schema.map(|schema| schema.name().to_owned()))
+    }
+
+    async fn schema_variant_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let schema_variant = self.0.schema_variant(dal_ctx(ctx)?).await?;
+        Ok(schema_variant.map(|schema_variant| schema_variant.name().to_owned()))
+    }
+
+    /// The values of the whole prop tree, as JSON.
+    async fn properties(&self, ctx: &Context<'_>) -> Result<Json<serde_json::Value>> {
+        let view = ComponentView::new(dal_ctx(ctx)?, *self.0.id()).await?;
+        Ok(Json(view.properties))
+    }
+
+    /// The root of the prop tree of the schema variant of the component.
+    async fn root_prop(&self, ctx: &Context<'_>) -> Result<Option<PropNode>> {
+        let ctx = dal_ctx(ctx)?;
+        let root_prop_id = match self.0.schema_variant(ctx).await? {
+            Some(schema_variant) => match schema_variant.root_prop_id() {
+                Some(root_prop_id) => *root_prop_id,
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+        Ok(Prop::get_by_id(ctx, &root_prop_id).await?.map(PropNode))
+    }
+
+    async fn sockets(&self, ctx: &Context<'_>) -> Result<Vec<SocketNode>> {
+        let sockets = Socket::list_for_component(dal_ctx(ctx)?, *self.0.id()).await?;
+        Ok(sockets.into_iter().map(SocketNode).collect())
+    }
+
+    /// The edges coming in and going out of the component.
+    async fn edges(&self, ctx: &Context<'_>) -> Result<Vec<EdgeNode>> {
+        let edges = Edge::list_for_component(dal_ctx(ctx)?, *self.0.id()).await?;
+        Ok(edges.into_iter().map(EdgeNode).collect())
+    }
+}
+
+pub struct PropNode(Prop);
+
+#[Object(name = "Prop")]
+impl PropNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id().to_string())
+    }
+
+    async fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn kind(&self) -> String {
+        self.0.kind().to_string()
+    }
+
+    /// The path of the prop, e.g. "/root/domain/region".
+    async fn path(&self) -> String {
+        self.0.json_pointer()
+    }
+
+    async fn documentation(&self) -> Option<&str> {
+        self.0.documentation()
+    }
+
+    async fn hidden(&self) -> bool {
+        self.0.hidden()
+    }
+
+    async fn children(&self, ctx: &Context<'_>) -> Result<Vec<PropNode>> {
+        let children = self.0.child_props(dal_ctx(ctx)?).await?;
+        Ok(children.into_iter().map(PropNode).collect())
+    }
+}
+
+pub struct SocketNode(Socket);
+
+#[Object(name = "Socket")]
+impl SocketNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id().to_string())
+    }
+
+    async fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn kind(&self) -> String {
+        self.0.kind().to_string()
+    }
+
+    /// Whether the socket is an input or an output, and of what.
+    async fn edge_kind(&self) -> String {
+        self.0.edge_kind().to_string()
+    }
+
+    async fn arity(&self) -> String {
+        self.0.arity().to_string()
+    }
+
+    async fn required(&self) -> bool {
+        self.0.required()
+    }
+}
+
+pub struct EdgeNode(Edge);
+
+#[Object(name = "Edge")]
+impl EdgeNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id().to_string())
+    }
+
+    async fn kind(&self) -> String {
+        self.0.kind().to_string()
+    }
+
+    async fn from_socket_id(&self) -> ID {
+        ID(self.0.tail_socket_id().to_string())
+    }
+
+    async fn to_socket_id(&self) -> ID {
+        ID(self.0.head_socket_id().to_string())
+    }
+
+    /// The component the edge goes out of.
+    async fn from(&self, ctx: &Context<'_>) -> Result<Option<ComponentNode>> {
+        let component_id = self.0.tail_component_id();
+        Ok(Component::get_by_id(dal_ctx(ctx)?, &component_id)
+            .await?
+            .map(ComponentNode))
+    }
+
+    /// The component the edge comes into.
+    async fn to(&self, ctx: &Context<'_>) -> Result<Option<ComponentNode>> {
+        let component_id = self.0.head_component_id();
+        Ok(Component::get_by_id(dal_ctx(ctx)?, &component_id)
+            .await?
+            .map(ComponentNode))
+    }
+}
@@ -0,0 +1,44 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use dal::{HistoryEventError, TransactionsError};
+use hyper::StatusCode;
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod list_for_entity;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+    #[error(transparent)]
+    HistoryEvent(#[from] HistoryEventError),
+}
+
+pub type HistoryResult<T> = std::result::Result<T, HistoryError>;
+
+impl IntoResponse for HistoryError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            HistoryError::HistoryEvent(HistoryEventError::InvalidTimeRange(_, _)) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/list_for_entity", get(list_for_entity::list_for_entity))
+}
@@ -0,0 +1,50 @@
+use axum::{extract::Query, Json};
+use chrono::{DateTime, Utc};
+use dal::{
+    HistoryActor, HistoryEvent, HistoryEventFilters, HistoryEventPage, HistoryEventPk, UserPk,
+    Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::HistoryResult;
+use crate::server::extract::{AccessBuilder, DiagramRead, HandlerContext, RequirePermission};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListForEntityRequest {
+    /// The id (or pk) of the entity to audit, such as a component id.
+    pub entity_id: String,
+    /// Only list the events of this user.
+    pub user_pk: Option<UserPk>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub label_prefix: Option<String>,
+    /// The "nextCursor" of the previous page.
+    pub cursor: Option<HistoryEventPk>,
+    pub limit: Option<usize>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListForEntityResponse = HistoryEventPage;
+
+pub async fn list_for_entity(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramRead>,
+    Query(request): Query<ListForEntityRequest>,
+) -> HistoryResult<Json<ListForEntityResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let filters = HistoryEventFilters {
+        actor: request.user_pk.map(HistoryActor::User),
+        since: request.since,
+        until: request.until,
+        label_prefix: request.label_prefix,
+        cursor: request.cursor,
+        limit: request.limit,
+    };
+    let page = HistoryEvent::list_for_entity(&ctx, &request.entity_id, &filters).await?;
+
+    Ok(Json(page))
+}
@@ -1,14 +1,16 @@
-use crate::server::{impl_default_error_into_response, state::AppState};
+use crate::server::state::AppState;
 use axum::{
-    response::Response,
+    http::StatusCode,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
 use convert_case::{Case, Casing};
 use dal::{
-    installed_pkg::InstalledPkgError, pkg::PkgError as DalPkgError, ChangeSetError,
-    DalContextBuilder, SchemaVariantError, SchemaVariantId, StandardModelError, TenancyError,
-    TransactionsError, UserError, UserPk, WorkspaceError, WorkspacePk, WsEventError,
+    installed_pkg::{InstalledPkgError, InstalledPkgId},
+    pkg::PkgError as DalPkgError,
+    ChangeSetError, DalContextBuilder, SchemaVariantError, SchemaVariantId, StandardModelError,
+    TenancyError, TransactionsError, UserError, UserPk, WorkspaceError, WorkspacePk, WsEventError,
 };
 use serde::{Deserialize, Serialize};
 use si_pkg::{SiPkg, SiPkgError};
@@ -22,18 +24,25 @@ const MAX_NAME_SEARCH_ATTEMPTS: usize = 100;
 
 mod approval_process;
 pub mod builtin_module_spec;
+pub mod download_pkg;
 pub mod export_pkg;
 pub mod export_workspace;
 pub mod get_pkg;
 pub mod import_workspace_vote;
 pub mod install_pkg;
+pub mod list_installed_pkg_assets;
 pub mod list_pkgs;
 mod reject_pkg;
 pub mod remote_module_spec;
+pub mod uninstall_pkg;
+pub mod upgrade_pkg;
+pub mod upload_pkg;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum PkgError {
+    #[error("error reading request body: {0}")]
+    BodyStream(#[from] axum::Error),
     #[error("Could not canononicalize path: {0}")]
     Canononicalize(#[from] CanonicalFileError),
     #[error(transparent)]
@@ -47,6 +56,8 @@ pub enum PkgError {
     // add error for matching hash
     #[error(transparent)]
     InstalledPkg(#[from] InstalledPkgError),
+    #[error("installed package not found: {0}")]
+    InstalledPkgNotFound(InstalledPkgId),
     #[error("Invalid pacakge file name: {0}")]
     InvalidPackageFileName(String),
     #[error("invalid user {0}")]
@@ -75,6 +86,8 @@ pub enum PkgError {
     PackageNotFound(String),
     #[error("Package version required")]
     PackageVersionEmpty,
+    #[error("package is larger than the limit of {0} bytes")]
+    PayloadTooLarge(usize),
     #[error(transparent)]
     Pg(#[from] si_data_pg::PgError),
     #[error(transparent)]
@@ -111,7 +124,21 @@ pub enum PkgError {
 
 pub type PkgResult<T> = Result<T, PkgError>;
 
-impl_default_error_into_response!(PkgError);
+impl IntoResponse for PkgError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            PkgError::PackageNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            PkgError::PayloadTooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -203,6 +230,7 @@ pub async fn pkg_open(builder: &DalContextBuilder, file_name: &str) -> PkgResult
 
 pub fn routes() -> Router<AppState> {
     Router::new()
+        .route("/download_pkg", get(download_pkg::download_pkg))
         .route("/export_pkg", post(export_pkg::export_pkg))
         .route(
             "/export_workspace",
@@ -211,6 +239,13 @@ pub fn routes() -> Router<AppState> {
         .route("/get_module_by_hash", get(get_pkg::get_module_by_hash))
         .route("/install_pkg", post(install_pkg::install_pkg))
         .route("/list_pkgs", get(list_pkgs::list_pkgs))
+        .route(
+            "/list_installed_pkg_assets",
+            get(list_installed_pkg_assets::list_installed_pkg_assets),
+        )
+        .route("/uninstall_pkg", post(uninstall_pkg::uninstall_pkg))
+        .route("/upgrade_pkg", post(upgrade_pkg::upgrade_pkg))
+        .route("/upload_pkg", post(upload_pkg::upload_pkg))
         .route(
             "/remote_module_spec",
             get(remote_module_spec::remote_module_spec),
@@ -0,0 +1,40 @@
+use axum::body::StreamBody;
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+
+use super::{get_pkgs_path, pkg_lookup, PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadPkgRequest {
+    /// The file name of the package in the packages path.
+    pub name: String,
+}
+
+/// Download a package archive from the packages path. The file is streamed in chunks rather than
+/// read into memory.
+pub async fn download_pkg(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(_request_ctx): AccessBuilder,
+    Query(request): Query<DownloadPkgRequest>,
+) -> PkgResult<impl IntoResponse> {
+    let (real_pkg_path, file_name) =
+        pkg_lookup(get_pkgs_path(&builder).await?, &request.name).await?;
+    let real_pkg_path = real_pkg_path.ok_or(PkgError::PackageNotFound(request.name))?;
+    let file_name = file_name.unwrap_or_default();
+
+    let file = File::open(&real_pkg_path).await?;
+
+    Ok(axum::response::Response::builder()
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{file_name}\""),
+        )
+        .body(StreamBody::new(ReaderStream::new(file)))?)
+}
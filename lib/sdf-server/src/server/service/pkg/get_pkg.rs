@@ -1,6 +1,9 @@
 use axum::{extract::Query, Json};
 use chrono::{DateTime, Utc};
-use dal::{installed_pkg::InstalledPkg, StandardModel, Visibility};
+use dal::{
+    installed_pkg::{InstalledPkg, InstalledPkgAsset, InstalledPkgAssetTyped},
+    StandardModel, Visibility,
+};
 use serde::{Deserialize, Serialize};
 use std::cmp::{Ord, PartialOrd};
 
@@ -50,6 +53,7 @@ pub struct PkgGetResponse {
     pub funcs: Vec<PkgFuncView>,
     pub spec: serde_json::Value,
     pub installed: bool,
+    pub installed_assets: Vec<InstalledPkgAssetTyped>,
 }
 
 pub async fn get_module_by_hash(
@@ -92,6 +96,13 @@ pub async fn get_module_by_hash(
         .await?
         .is_empty();
 
+    let installed_assets: Vec<InstalledPkgAssetTyped> =
+        InstalledPkgAsset::list_for_installed_pkg_id(&ctx, *installed_pkg.id())
+            .await?
+            .iter()
+            .map(Into::into)
+            .collect();
+
     // This type can be serialized to json with serde_json::to_string/to_string_pretty
     let pkg_spec = pkg.to_spec().await?;
 
@@ -118,6 +129,7 @@ pub async fn get_module_by_hash(
         created_by: metadata.created_by().to_string(),
         spec: serde_json::to_value(&pkg_spec)?,
         installed,
+        installed_assets,
         schemas,
         funcs,
     }))
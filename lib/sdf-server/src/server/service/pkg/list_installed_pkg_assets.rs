@@ -0,0 +1,51 @@
+use axum::{extract::Query, Json};
+use dal::{
+    installed_pkg::{InstalledPkg, InstalledPkgAsset, InstalledPkgAssetTyped, InstalledPkgId},
+    StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListInstalledPkgAssetsRequest {
+    pub installed_pkg_id: InstalledPkgId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListInstalledPkgAssetsResponse {
+    pub name: String,
+    pub version: Option<String>,
+    pub hash: String,
+    pub assets: Vec<InstalledPkgAssetTyped>,
+}
+
+pub async fn list_installed_pkg_assets(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListInstalledPkgAssetsRequest>,
+) -> PkgResult<Json<ListInstalledPkgAssetsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let installed_pkg = InstalledPkg::get_by_id(&ctx, &request.installed_pkg_id)
+        .await?
+        .ok_or(PkgError::InstalledPkgNotFound(request.installed_pkg_id))?;
+
+    let assets = InstalledPkgAsset::list_for_installed_pkg_id(&ctx, *installed_pkg.id())
+        .await?
+        .iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListInstalledPkgAssetsResponse {
+        name: installed_pkg.name().to_owned(),
+        version: installed_pkg.version().map(ToOwned::to_owned),
+        hash: installed_pkg.root_hash().to_owned(),
+        assets,
+    }))
+}
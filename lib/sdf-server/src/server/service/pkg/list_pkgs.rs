@@ -3,7 +3,10 @@ use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::{extract::Query, Json};
-use dal::{installed_pkg::InstalledPkg, StandardModel, Visibility};
+use dal::{
+    installed_pkg::{InstalledPkg, InstalledPkgAsset, InstalledPkgId},
+    StandardModel, Visibility,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -22,8 +25,11 @@ pub struct PkgListResponse {
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PkgView {
+    id: InstalledPkgId,
     name: String,
+    version: Option<String>,
     hash: String,
+    asset_count: usize,
 }
 
 pub async fn list_pkgs(
@@ -37,13 +43,19 @@ pub async fn list_pkgs(
 
     let installed_pkgs = InstalledPkg::list(&ctx).await?;
 
-    let pkgs: Vec<PkgView> = installed_pkgs
-        .iter()
-        .map(|pkg| PkgView {
+    let mut pkgs = Vec::with_capacity(installed_pkgs.len());
+    for pkg in installed_pkgs {
+        let asset_count = InstalledPkgAsset::list_for_installed_pkg_id(&ctx, *pkg.id())
+            .await?
+            .len();
+        pkgs.push(PkgView {
+            id: *pkg.id(),
             name: pkg.name().to_owned(),
+            version: pkg.version().map(ToOwned::to_owned),
             hash: pkg.root_hash().to_string(),
-        })
-        .collect();
+            asset_count,
+        });
+    }
 
     track(
         &posthog_client,
@@ -0,0 +1,58 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use dal::{
+    installed_pkg::{InstalledPkg, InstalledPkgId},
+    ChangeSet, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallPkgRequest {
+    pub installed_pkg_id: InstalledPkgId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn uninstall_pkg(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<UninstallPkgRequest>,
+) -> PkgResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let installed_pkg = InstalledPkg::get_by_id(&ctx, &request.installed_pkg_id)
+        .await?
+        .ok_or(PkgError::InstalledPkgNotFound(request.installed_pkg_id))?;
+
+    let uninstall = installed_pkg.uninstall(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "uninstall_pkg",
+        serde_json::json!({
+            "pkg_name": uninstall.name,
+            "removed_assets": uninstall.removed_assets.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&uninstall)?)?)
+}
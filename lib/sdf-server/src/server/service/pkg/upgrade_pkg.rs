@@ -0,0 +1,79 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use dal::{
+    installed_pkg::{InstalledPkg, InstalledPkgId},
+    ChangeSet, StandardModel, Visibility, WsEvent,
+};
+use module_index_client::IndexClient;
+use serde::{Deserialize, Serialize};
+use si_pkg::SiPkg;
+use ulid::Ulid;
+
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAccessToken};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradePkgRequest {
+    pub installed_pkg_id: InstalledPkgId,
+    /// The module index id of the version to upgrade to.
+    pub id: Ulid,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn upgrade_pkg(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    RawAccessToken(raw_access_token): RawAccessToken,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<UpgradePkgRequest>,
+) -> PkgResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let module_index_url = match ctx.module_index_url() {
+        Some(url) => url,
+        None => return Err(PkgError::ModuleIndexNotConfigured),
+    };
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let installed_pkg = InstalledPkg::get_by_id(&ctx, &request.installed_pkg_id)
+        .await?
+        .ok_or(PkgError::InstalledPkgNotFound(request.installed_pkg_id))?;
+
+    let module_index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
+    let pkg_data = module_index_client.download_module(request.id).await?;
+    let pkg = SiPkg::load_from_bytes(pkg_data)?;
+
+    let upgrade = installed_pkg.upgrade(&ctx, &pkg).await?;
+
+    WsEvent::module_imported(&ctx, upgrade.schema_variant_ids.clone())
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "upgrade_pkg",
+        serde_json::json!({
+            "pkg_name": upgrade.name,
+            "previous_version": upgrade.previous_version,
+            "version": upgrade.version,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&upgrade)?)?)
+}
@@ -0,0 +1,70 @@
+use axum::extract::BodyStream;
+use axum::{Extension, Json};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use si_pkg::SiPkg;
+use std::path::Path;
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+use ulid::Ulid;
+
+use super::{get_new_pkg_path, get_pkgs_path, PkgError, PkgResult};
+use crate::server::config::BodyLimits;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadPkgResponse {
+    /// The file name of the package in the packages path.
+    pub name: String,
+}
+
+/// Write a streamed body to a file, chunk by chunk, failing once it goes over `limit` bytes, then
+/// load the package it holds.
+async fn load_pkg_from_body(body: &mut BodyStream, path: &Path, limit: usize) -> PkgResult<SiPkg> {
+    let mut file = File::create(path).await?;
+    let mut written = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        written += chunk.len();
+        if written > limit {
+            return Err(PkgError::PayloadTooLarge(limit));
+        }
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    Ok(SiPkg::load_from_file(path).await?)
+}
+
+/// Upload a package archive (a ".sipkg" file) into the packages path. The body is streamed to
+/// disk rather than buffered in memory, and the package is named after its own metadata.
+pub async fn upload_pkg(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(_request_ctx): AccessBuilder,
+    Extension(body_limits): Extension<BodyLimits>,
+    mut body: BodyStream,
+) -> PkgResult<Json<UploadPkgResponse>> {
+    let pkgs_path = get_pkgs_path(&builder).await?;
+    // Not a package file name until the upload is complete, so that it does not get listed.
+    let upload_path = pkgs_path.join(format!(".{}.upload", Ulid::new()));
+
+    let pkg = match load_pkg_from_body(&mut body, &upload_path, body_limits.large_bytes).await {
+        Ok(pkg) => pkg,
+        Err(err) => {
+            let _ = fs::remove_file(&upload_path).await;
+            return Err(err);
+        }
+    };
+
+    let metadata = pkg.metadata()?;
+    let pkg_path = get_new_pkg_path(&builder, metadata.name(), metadata.version()).await?;
+    fs::rename(&upload_path, &pkg_path).await?;
+
+    let name = pkg_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Ok(Json(UploadPkgResponse { name }))
+}
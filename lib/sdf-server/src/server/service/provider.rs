@@ -1,30 +1,76 @@
 use axum::response::Response;
-use axum::{http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use axum::{
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use dal::func::argument::{FuncArgumentError, FuncArgumentId};
 use dal::provider::external::ExternalProviderError;
+use dal::provider::history::ProviderValueHistoryError;
 use dal::provider::internal::InternalProviderError;
-use dal::{StandardModelError, TransactionsError};
+use dal::{
+    AttributePrototypeArgumentError, AttributePrototypeArgumentId, AttributePrototypeError,
+    AttributePrototypeId, AttributeValueError, ChangeSetError, ComponentError, ComponentId,
+    ExternalProviderId, InternalProviderId, StandardModelError, TransactionsError,
+};
 
 use thiserror::Error;
 
 use crate::server::state::AppState;
 
 pub mod list_all_providers;
+pub mod list_attribute_prototype_arguments;
+pub mod list_provider_value_history;
+pub mod rebind_attribute_prototype_argument;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum ProviderError {
+    #[error("attribute prototype error: {0}")]
+    AttributePrototype(#[from] AttributePrototypeError),
+    #[error("attribute prototype argument error: {0}")]
+    AttributePrototypeArgument(#[from] AttributePrototypeArgumentError),
+    #[error("attribute prototype argument not found: {0}")]
+    AttributePrototypeArgumentNotFound(AttributePrototypeArgumentId),
+    #[error("attribute prototype not found: {0}")]
+    AttributePrototypeNotFound(AttributePrototypeId),
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error(transparent)]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
     #[error(transparent)]
     ContextError(#[from] TransactionsError),
     #[error("external provider error: {0}")]
     ExternalProvider(#[from] ExternalProviderError),
+    #[error("external provider not found: {0}")]
+    ExternalProviderNotFound(ExternalProviderId),
+    #[error("func argument error: {0}")]
+    FuncArgument(#[from] FuncArgumentError),
+    #[error("func argument not found: {0}")]
+    FuncArgumentNotFound(FuncArgumentId),
+    #[error(transparent)]
+    Hyper(#[from] hyper::http::Error),
     #[error("internal provider error: {0}")]
     InternalProvider(#[from] InternalProviderError),
+    #[error("internal provider not found: {0}")]
+    InternalProviderNotFound(InternalProviderId),
+    #[error("exactly one of an internal or an external provider must be selected")]
+    InvalidProviderSelection,
     #[error(transparent)]
     Nats(#[from] si_data_nats::NatsError),
     #[error(transparent)]
     Pg(#[from] si_data_pg::PgError),
     #[error(transparent)]
     PgPool(#[from] si_data_pg::PgPoolError),
+    #[error("provider value history error: {0}")]
+    ProviderValueHistory(#[from] ProviderValueHistoryError),
+    #[error("json serialization error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
     StandardModel(#[from] StandardModelError),
 }
@@ -33,7 +79,10 @@ pub type ProviderResult<T> = std::result::Result<T, ProviderError>;
 
 impl IntoResponse for ProviderError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let (status, error_message) = match self {
+            ProviderError::InvalidProviderSelection => (StatusCode::BAD_REQUEST, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
 
         let body = Json(serde_json::json!({
             "error": {
@@ -48,8 +97,21 @@ impl IntoResponse for ProviderError {
 }
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route(
-        "/list_all_providers",
-        get(list_all_providers::list_all_providers),
-    )
+    Router::new()
+        .route(
+            "/list_all_providers",
+            get(list_all_providers::list_all_providers),
+        )
+        .route(
+            "/list_attribute_prototype_arguments",
+            get(list_attribute_prototype_arguments::list_attribute_prototype_arguments),
+        )
+        .route(
+            "/list_provider_value_history",
+            get(list_provider_value_history::list_provider_value_history),
+        )
+        .route(
+            "/rebind_attribute_prototype_argument",
+            post(rebind_attribute_prototype_argument::rebind_attribute_prototype_argument),
+        )
 }
@@ -0,0 +1,140 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::func::argument::FuncArgumentId;
+use dal::{
+    AttributePrototypeArgument, AttributePrototypeArgumentId, AttributePrototypeId, Component,
+    ComponentId, DalContext, ExternalProvider, ExternalProviderId, FuncArgument, InternalProvider,
+    InternalProviderId, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::service::provider::{ProviderError, ProviderResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAttributePrototypeArgumentsRequest {
+    pub attribute_prototype_id: AttributePrototypeId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Describes where an [`AttributePrototypeArgument`] gets its value from. _Intra_
+/// [`Component`] arguments only have an internal provider set, while _inter_ [`Component`]
+/// arguments have an external provider and the tail and head [`Components`](Component) set.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributePrototypeArgumentView {
+    pub id: AttributePrototypeArgumentId,
+    pub func_argument_id: FuncArgumentId,
+    pub func_argument_name: String,
+    pub internal_provider_id: Option<InternalProviderId>,
+    pub internal_provider_name: Option<String>,
+    pub external_provider_id: Option<ExternalProviderId>,
+    pub external_provider_name: Option<String>,
+    pub tail_component_id: Option<ComponentId>,
+    pub tail_component_name: Option<String>,
+    pub head_component_id: Option<ComponentId>,
+    pub head_component_name: Option<String>,
+}
+
+impl AttributePrototypeArgumentView {
+    pub async fn new(
+        ctx: &DalContext,
+        argument: &AttributePrototypeArgument,
+    ) -> ProviderResult<Self> {
+        let func_argument = FuncArgument::get_by_id(ctx, &argument.func_argument_id())
+            .await?
+            .ok_or(ProviderError::FuncArgumentNotFound(
+                argument.func_argument_id(),
+            ))?;
+
+        let internal_provider_id =
+            Some(argument.internal_provider_id()).filter(|id| *id != InternalProviderId::NONE);
+        let internal_provider_name = match internal_provider_id {
+            Some(id) => Some(
+                InternalProvider::get_by_id(ctx, &id)
+                    .await?
+                    .ok_or(ProviderError::InternalProviderNotFound(id))?
+                    .name()
+                    .to_owned(),
+            ),
+            None => None,
+        };
+
+        let external_provider_id =
+            Some(argument.external_provider_id()).filter(|id| *id != ExternalProviderId::NONE);
+        let external_provider_name = match external_provider_id {
+            Some(id) => Some(
+                ExternalProvider::get_by_id(ctx, &id)
+                    .await?
+                    .ok_or(ProviderError::ExternalProviderNotFound(id))?
+                    .name()
+                    .to_owned(),
+            ),
+            None => None,
+        };
+
+        let tail_component_id =
+            Some(argument.tail_component_id()).filter(|id| *id != ComponentId::NONE);
+        let tail_component_name = component_name(ctx, tail_component_id).await?;
+        let head_component_id =
+            Some(argument.head_component_id()).filter(|id| *id != ComponentId::NONE);
+        let head_component_name = component_name(ctx, head_component_id).await?;
+
+        Ok(Self {
+            id: *argument.id(),
+            func_argument_id: *func_argument.id(),
+            func_argument_name: func_argument.name().to_owned(),
+            internal_provider_id,
+            internal_provider_name,
+            external_provider_id,
+            external_provider_name,
+            tail_component_id,
+            tail_component_name,
+            head_component_id,
+            head_component_name,
+        })
+    }
+}
+
+async fn component_name(
+    ctx: &DalContext,
+    component_id: Option<ComponentId>,
+) -> ProviderResult<Option<String>> {
+    match component_id {
+        Some(id) => {
+            let component = Component::get_by_id(ctx, &id)
+                .await?
+                .ok_or(ProviderError::ComponentNotFound(id))?;
+            Ok(Some(component.name(ctx).await?))
+        }
+        None => Ok(None),
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAttributePrototypeArgumentsResponse {
+    pub arguments: Vec<AttributePrototypeArgumentView>,
+}
+
+pub async fn list_attribute_prototype_arguments(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListAttributePrototypeArgumentsRequest>,
+) -> ProviderResult<Json<ListAttributePrototypeArgumentsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut arguments = Vec::new();
+    for argument in AttributePrototypeArgument::list_for_attribute_prototype(
+        &ctx,
+        request.attribute_prototype_id,
+    )
+    .await?
+    {
+        arguments.push(AttributePrototypeArgumentView::new(&ctx, &argument).await?);
+    }
+
+    Ok(Json(ListAttributePrototypeArgumentsResponse { arguments }))
+}
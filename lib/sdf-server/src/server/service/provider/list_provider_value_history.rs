@@ -0,0 +1,67 @@
+use axum::extract::Query;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use dal::provider::history::ProviderValueHistoryEntry;
+use dal::{AttributeValueId, ComponentId, ExternalProviderId, InternalProviderId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::service::provider::{ProviderError, ProviderResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListProviderValueHistoryRequest {
+    pub component_id: ComponentId,
+    /// Either an explicit internal provider (input socket) or an external provider (output
+    /// socket) must be set.
+    pub internal_provider_id: Option<InternalProviderId>,
+    pub external_provider_id: Option<ExternalProviderId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderValueHistoryEntryView {
+    pub attribute_value_id: AttributeValueId,
+    pub value: Option<serde_json::Value>,
+    pub job_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type ListProviderValueHistoryResponse = Vec<ProviderValueHistoryEntryView>;
+
+/// List the values a provider of a component emitted, most recent first, along with the jobs that
+/// computed them.
+pub async fn list_provider_value_history(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListProviderValueHistoryRequest>,
+) -> ProviderResult<Json<ListProviderValueHistoryResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let (internal_provider_id, external_provider_id) =
+        match (request.internal_provider_id, request.external_provider_id) {
+            (Some(internal_provider_id), None) => (internal_provider_id, ExternalProviderId::NONE),
+            (None, Some(external_provider_id)) => (InternalProviderId::NONE, external_provider_id),
+            _ => return Err(ProviderError::InvalidProviderSelection),
+        };
+
+    let timeline = ProviderValueHistoryEntry::list_for_component_and_provider(
+        &ctx,
+        request.component_id,
+        internal_provider_id,
+        external_provider_id,
+    )
+    .await?
+    .into_iter()
+    .map(|entry| ProviderValueHistoryEntryView {
+        attribute_value_id: entry.attribute_value_id(),
+        value: entry.value().cloned(),
+        job_id: entry.job_id().map(ToOwned::to_owned),
+        created_at: entry.created_at(),
+    })
+    .collect();
+
+    Ok(Json(timeline))
+}
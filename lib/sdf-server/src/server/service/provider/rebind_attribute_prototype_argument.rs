@@ -0,0 +1,149 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use dal::{
+    job::definition::DependentValuesUpdate, AttributePrototype, AttributePrototypeArgument,
+    AttributePrototypeArgumentId, AttributeReadContext, ChangeSet, Component, ComponentId,
+    ExternalProvider, ExternalProviderId, InternalProvider, InternalProviderId, StandardModel,
+    Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::list_attribute_prototype_arguments::AttributePrototypeArgumentView;
+use crate::server::extract::{
+    AccessBuilder, DiagramWrite, HandlerContext, PosthogClient, RequirePermission,
+};
+use crate::server::tracking::track;
+use crate::service::provider::{ProviderError, ProviderResult};
+
+/// Fields left unset keep their current value.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RebindAttributePrototypeArgumentRequest {
+    pub attribute_prototype_argument_id: AttributePrototypeArgumentId,
+    pub internal_provider_id: Option<InternalProviderId>,
+    pub external_provider_id: Option<ExternalProviderId>,
+    pub tail_component_id: Option<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RebindAttributePrototypeArgumentResponse {
+    pub argument: AttributePrototypeArgumentView,
+}
+
+/// Rebind an [`AttributePrototypeArgument`] to a different internal or external provider (or
+/// tail [`Component`]) and re-run the affected values, rewiring value flow without having to
+/// delete and recreate connections.
+pub async fn rebind_attribute_prototype_argument(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    _: RequirePermission<DiagramWrite>,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RebindAttributePrototypeArgumentRequest>,
+) -> ProviderResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut argument =
+        AttributePrototypeArgument::get_by_id(&ctx, &request.attribute_prototype_argument_id)
+            .await?
+            .ok_or(ProviderError::AttributePrototypeArgumentNotFound(
+                request.attribute_prototype_argument_id,
+            ))?;
+
+    if let Some(internal_provider_id) = request.internal_provider_id {
+        InternalProvider::get_by_id(&ctx, &internal_provider_id)
+            .await?
+            .ok_or(ProviderError::InternalProviderNotFound(
+                internal_provider_id,
+            ))?;
+    }
+    if let Some(external_provider_id) = request.external_provider_id {
+        ExternalProvider::get_by_id(&ctx, &external_provider_id)
+            .await?
+            .ok_or(ProviderError::ExternalProviderNotFound(
+                external_provider_id,
+            ))?;
+    }
+    if let Some(tail_component_id) = request.tail_component_id {
+        Component::get_by_id(&ctx, &tail_component_id)
+            .await?
+            .ok_or(ProviderError::ComponentNotFound(tail_component_id))?;
+    }
+
+    argument
+        .rebind(
+            &ctx,
+            request.internal_provider_id,
+            request.external_provider_id,
+            request.tail_component_id,
+        )
+        .await?;
+
+    // Re-run the values using the prototype. Inter component arguments only feed the values of
+    // their head component.
+    let prototype = AttributePrototype::get_by_id(&ctx, &argument.attribute_prototype_id())
+        .await?
+        .ok_or(ProviderError::AttributePrototypeNotFound(
+            argument.attribute_prototype_id(),
+        ))?;
+    let head_component_id = argument.head_component_id();
+    let mut attribute_value_ids = Vec::new();
+    for mut attribute_value in AttributePrototype::attribute_values_in_context_or_greater(
+        &ctx,
+        *prototype.id(),
+        AttributeReadContext::from(prototype.context),
+    )
+    .await?
+    {
+        if head_component_id != ComponentId::NONE
+            && attribute_value.context.component_id() != head_component_id
+        {
+            continue;
+        }
+        attribute_value.update_from_prototype_function(&ctx).await?;
+        attribute_value_ids.push(*attribute_value.id());
+    }
+
+    if !attribute_value_ids.is_empty() {
+        ctx.enqueue_job(DependentValuesUpdate::new(
+            ctx.access_builder(),
+            *ctx.visibility(),
+            attribute_value_ids,
+        ))
+        .await?;
+    }
+
+    let argument = AttributePrototypeArgumentView::new(&ctx, &argument).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "rebind_attribute_prototype_argument",
+        serde_json::json!({
+            "attribute_prototype_argument_id": argument.id,
+            "func_argument_name": argument.func_argument_name,
+            "internal_provider_id": argument.internal_provider_id,
+            "external_provider_id": argument.external_provider_id,
+            "tail_component_id": argument.tail_component_id,
+            "head_component_id": argument.head_component_id,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(
+            &RebindAttributePrototypeArgumentResponse { argument },
+        )?)?)
+}
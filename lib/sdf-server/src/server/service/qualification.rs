@@ -18,6 +18,7 @@ use dal::{
 use crate::server::state::AppState;
 
 pub mod get_summary;
+pub mod qualification_summary;
 
 // code endpoints here are deprecated, removing them from the module tree
 // moved to the func service - this probably means we can pair down the
@@ -86,5 +87,10 @@ impl IntoResponse for QualificationError {
 }
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/get_summary", get(get_summary::get_summary))
+    Router::new()
+        .route("/get_summary", get(get_summary::get_summary))
+        .route(
+            "/qualification_summary",
+            get(qualification_summary::qualification_summary),
+        )
 }
@@ -0,0 +1,34 @@
+use axum::extract::Query;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::qualification::QualificationSummaryForComponent;
+use dal::{ComponentId, Visibility};
+
+use super::get_summary::QualificationSummaryForComponentResponse;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::service::qualification::QualificationResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QualificationSummaryRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// The qualification counts of a single component, or `null` if its qualifications haven't run
+/// yet. Use it to refresh one entry of the [`summary`](super::get_summary::get_summary) without
+/// fetching all of it; `QualificationStatusChanged` events carry the same counts as they change.
+pub async fn qualification_summary(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<QualificationSummaryRequest>,
+) -> QualificationResult<Json<Option<QualificationSummaryForComponentResponse>>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let summary =
+        QualificationSummaryForComponent::get_for_component(&ctx, request.component_id).await?;
+
+    Ok(Json(summary.map(Into::into)))
+}
@@ -3,28 +3,51 @@ use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::Json;
 use axum::Router;
-use dal::{SchemaError as DalSchemaError, StandardModelError, TransactionsError, WsEventError};
+use dal::property_editor::PropertyEditorError;
+use dal::schema::variant::SchemaVariantError;
+use dal::{
+    ChangeSetError, SchemaError as DalSchemaError, SchemaVariantId, StandardModelError,
+    TransactionsError, WsEventError,
+};
 use thiserror::Error;
 
 use crate::server::state::AppState;
 
 pub mod create_schema;
+pub mod get_property_editor_schema;
 pub mod get_schema;
 pub mod list_schemas;
+pub mod set_schema_refresh_interval;
+pub mod set_schema_resource_identifier_path;
+pub mod set_schema_variant_strict;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum SchemaError {
+    #[error("change set error: {0}")]
+    ChangeSet(#[from] ChangeSetError),
     #[error(transparent)]
     ContextTransaction(#[from] TransactionsError),
     #[error(transparent)]
+    Hyper(#[from] hyper::http::Error),
+    #[error("refresh interval must be a positive number of seconds")]
+    InvalidRefreshInterval,
+    #[error("resource identifier path must be a json pointer")]
+    InvalidResourceIdentifierPath,
+    #[error(transparent)]
     Nats(#[from] si_data_nats::NatsError),
     #[error(transparent)]
     Pg(#[from] si_data_pg::PgError),
+    #[error("property editor error: {0}")]
+    PropertyEditor(#[from] PropertyEditorError),
     #[error("schema error: {0}")]
     Schema(#[from] DalSchemaError),
     #[error("schema not found")]
     SchemaNotFound,
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] SchemaVariantError),
+    #[error("schema variant not found: {0}")]
+    SchemaVariantNotFound(SchemaVariantId),
     #[error(transparent)]
     StandardModel(#[from] StandardModelError),
     #[error("wsevent error: {0}")]
@@ -36,7 +59,14 @@ pub type SchemaResult<T> = std::result::Result<T, SchemaError>;
 impl IntoResponse for SchemaError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
-            SchemaError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            SchemaError::SchemaNotFound
+            | SchemaError::SchemaVariantNotFound(_)
+            | SchemaError::PropertyEditor(PropertyEditorError::SchemaVariantNotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            SchemaError::InvalidRefreshInterval | SchemaError::InvalidResourceIdentifierPath => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -53,4 +83,20 @@ pub fn routes() -> Router<AppState> {
         .route("/create_schema", post(create_schema::create_schema))
         .route("/list_schemas", get(list_schemas::list_schemas))
         .route("/get_schema", get(get_schema::get_schema))
+        .route(
+            "/get_property_editor_schema",
+            get(get_property_editor_schema::get_property_editor_schema),
+        )
+        .route(
+            "/set_schema_refresh_interval",
+            post(set_schema_refresh_interval::set_schema_refresh_interval),
+        )
+        .route(
+            "/set_schema_resource_identifier_path",
+            post(set_schema_resource_identifier_path::set_schema_resource_identifier_path),
+        )
+        .route(
+            "/set_schema_variant_strict",
+            post(set_schema_variant_strict::set_schema_variant_strict),
+        )
 }
@@ -0,0 +1,33 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::property_editor::schema::PropertyEditorSchema;
+use dal::{SchemaVariantId, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::SchemaResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPropertyEditorSchemaRequest {
+    pub schema_variant_id: SchemaVariantId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type GetPropertyEditorSchemaResponse = PropertyEditorSchema;
+
+/// Returns the complete prop tree of a schema variant (kinds, widgets, validation formats, doc
+/// links, inline documentation and socket-driven markers) in a single payload.
+pub async fn get_property_editor_schema(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetPropertyEditorSchemaRequest>,
+) -> SchemaResult<Json<GetPropertyEditorSchemaResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let prop_edit_schema =
+        PropertyEditorSchema::for_schema_variant(&ctx, request.schema_variant_id).await?;
+
+    Ok(Json(prop_edit_schema))
+}
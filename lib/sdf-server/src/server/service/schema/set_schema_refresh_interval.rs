@@ -0,0 +1,45 @@
+use axum::{response::IntoResponse, Json};
+use dal::{ChangeSet, Schema, SchemaId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{SchemaError, SchemaResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSchemaRefreshIntervalRequest {
+    pub schema_id: SchemaId,
+    /// Resources of the schema's components are no longer refreshed on a schedule when unset.
+    pub refresh_interval_seconds: Option<i64>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn set_schema_refresh_interval(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<SetSchemaRefreshIntervalRequest>,
+) -> SchemaResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    if matches!(request.refresh_interval_seconds, Some(seconds) if seconds <= 0) {
+        return Err(SchemaError::InvalidRefreshInterval);
+    }
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut schema = Schema::get_by_id(&ctx, &request.schema_id)
+        .await?
+        .ok_or(SchemaError::SchemaNotFound)?;
+    schema
+        .set_refresh_interval_seconds(&ctx, request.refresh_interval_seconds)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
@@ -0,0 +1,46 @@
+use axum::{response::IntoResponse, Json};
+use dal::{ChangeSet, Schema, SchemaId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{SchemaError, SchemaResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSchemaResourceIdentifierPathRequest {
+    pub schema_id: SchemaId,
+    /// A JSON pointer into the resource payload (e.g. "/GroupId"). Resource identifiers are no
+    /// longer recorded for the schema's components when unset.
+    pub resource_identifier_path: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn set_schema_resource_identifier_path(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<SetSchemaResourceIdentifierPathRequest>,
+) -> SchemaResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    if matches!(&request.resource_identifier_path, Some(path) if !path.starts_with('/')) {
+        return Err(SchemaError::InvalidResourceIdentifierPath);
+    }
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut schema = Schema::get_by_id(&ctx, &request.schema_id)
+        .await?
+        .ok_or(SchemaError::SchemaNotFound)?;
+    schema
+        .set_resource_identifier_path(&ctx, request.resource_identifier_path)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
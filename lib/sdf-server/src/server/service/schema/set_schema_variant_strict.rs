@@ -0,0 +1,40 @@
+use axum::{response::IntoResponse, Json};
+use dal::{ChangeSet, SchemaVariant, SchemaVariantId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{SchemaError, SchemaResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSchemaVariantStrictRequest {
+    pub schema_variant_id: SchemaVariantId,
+    pub strict: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn set_schema_variant_strict(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<SetSchemaVariantStrictRequest>,
+) -> SchemaResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut schema_variant = SchemaVariant::get_by_id(&ctx, &request.schema_variant_id)
+        .await?
+        .ok_or(SchemaError::SchemaVariantNotFound(
+            request.schema_variant_id,
+        ))?;
+    schema_variant.set_strict(&ctx, request.strict).await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
@@ -15,6 +15,7 @@ use crate::server::state::AppState;
 pub mod create_secret;
 pub mod get_public_key;
 pub mod list_secrets;
+pub mod rotate_secret;
 pub mod update_secret;
 
 #[remain::sorted]
@@ -75,4 +76,5 @@ pub fn routes() -> Router<AppState> {
         .route("/", post(create_secret::create_secret))
         .route("/", get(list_secrets::list_secrets))
         .route("/", patch(update_secret::update_secret))
+        .route("/rotate", post(rotate_secret::rotate_secret))
 }
@@ -0,0 +1,47 @@
+use axum::response::IntoResponse;
+use axum::Json;
+use dal::secret::SecretView;
+use dal::{ChangeSet, NewSecretValue, Secret, Visibility};
+use dal::{SecretError, SecretId, StandardModel};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+use super::SecretResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateSecretRequest {
+    pub id: SecretId,
+    pub new_secret_data: NewSecretValue,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type RotateSecretResponse = SecretView;
+
+pub async fn rotate_secret(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_tx): AccessBuilder,
+    Json(request): Json<RotateSecretRequest>,
+) -> SecretResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_tx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let mut secret = Secret::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(SecretError::SecretNotFound(request.id))?;
+    secret.rotate(&ctx, request.new_secret_data).await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+
+    Ok(response.body(serde_json::to_string(
+        &SecretView::from_secret(&ctx, secret).await?,
+    )?)?)
+}
@@ -1,16 +1,18 @@
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::post,
+    routing::{get, post},
     Json, Router,
 };
 use thiserror::Error;
 
+use dal::workspace::template::WorkspaceTemplateError;
 use dal::{
     ComponentError, NodeError, SchemaError, StandardModelError, TransactionsError, WorkspaceError,
 };
 
 pub mod create_account;
+pub mod list_workspace_templates;
 
 #[allow(clippy::large_enum_variant)]
 #[remain::sorted]
@@ -34,6 +36,10 @@ pub enum SignupError {
     StandardModel(#[from] StandardModelError),
     #[error(transparent)]
     Workspace(#[from] WorkspaceError),
+    #[error("workspace template error: {0}")]
+    WorkspaceTemplate(#[from] WorkspaceTemplateError),
+    #[error("workspace template not found: {0}")]
+    WorkspaceTemplateNotFound(String),
 }
 
 pub type SignupResult<T> = std::result::Result<T, SignupError>;
@@ -44,6 +50,9 @@ impl IntoResponse for SignupError {
             SignupError::InvalidSignupSecret => {
                 (StatusCode::BAD_REQUEST, "signup failed".to_string())
             }
+            err @ SignupError::WorkspaceTemplateNotFound(_) => {
+                (StatusCode::NOT_FOUND, err.to_string())
+            }
             err => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
         };
 
@@ -60,5 +69,10 @@ impl IntoResponse for SignupError {
 }
 
 pub fn routes() -> Router {
-    Router::new().route("/create_account", post(create_account::create_account))
+    Router::new()
+        .route("/create_account", post(create_account::create_account))
+        .route(
+            "/list_workspace_templates",
+            get(list_workspace_templates::list_workspace_templates),
+        )
 }
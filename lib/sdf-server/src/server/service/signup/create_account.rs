@@ -1,7 +1,8 @@
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
-use dal::Workspace;
+use dal::workspace::template::WorkspaceTemplate;
+use dal::{ChangeSetPk, Workspace};
 use telemetry::prelude::*;
 
 use crate::{
@@ -19,12 +20,17 @@ pub struct CreateAccountRequest {
     pub user_email: String,
     pub user_password: String,
     pub signup_secret: String,
+    /// The name of the [`WorkspaceTemplate`] to bootstrap the new workspace from, if any.
+    #[serde(default)]
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateAccountResponse {
     pub success: bool,
+    /// The change set holding the template's diagram, when a template was requested.
+    pub template_change_set_pk: Option<ChangeSetPk>,
 }
 
 pub async fn create_account(
@@ -37,6 +43,14 @@ pub async fn create_account(
         return Err(SignupError::InvalidSignupSecret);
     }
 
+    let template = match &request.template {
+        Some(name) => Some(
+            WorkspaceTemplate::find_by_name(name)?
+                .ok_or_else(|| SignupError::WorkspaceTemplateNotFound(name.clone()))?,
+        ),
+        None => None,
+    };
+
     let mut ctx = builder.build_default().await?;
 
     let _nw = Workspace::signup(
@@ -47,7 +61,15 @@ pub async fn create_account(
     )
     .await?;
 
+    let template_change_set_pk = match template {
+        Some(template) => Some(template.bootstrap(&ctx).await?),
+        None => None,
+    };
+
     ctx.commit().await?;
 
-    Ok(Json(CreateAccountResponse { success: true }))
+    Ok(Json(CreateAccountResponse {
+        success: true,
+        template_change_set_pk,
+    }))
 }
@@ -0,0 +1,33 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::workspace::template::WorkspaceTemplate;
+
+use super::SignupResult;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTemplateView {
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceTemplatesResponse {
+    pub templates: Vec<WorkspaceTemplateView>,
+}
+
+pub async fn list_workspace_templates() -> SignupResult<Json<ListWorkspaceTemplatesResponse>> {
+    let templates = WorkspaceTemplate::list()?
+        .into_iter()
+        .map(|template| WorkspaceTemplateView {
+            name: template.name,
+            display_name: template.display_name,
+            description: template.description,
+        })
+        .collect();
+
+    Ok(Json(ListWorkspaceTemplatesResponse { templates }))
+}
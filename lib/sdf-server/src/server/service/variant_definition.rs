@@ -40,7 +40,9 @@ use self::save_variant_def::SaveVariantDefRequest;
 use super::func::get_leaf_function_inputs;
 
 pub mod clone_variant_def;
+pub mod code_generation;
 pub mod create_variant_def;
+pub mod create_variant_def_from_spec;
 pub mod exec_variant_def;
 pub mod get_variant_def;
 pub mod list_variant_defs;
@@ -103,6 +105,8 @@ pub enum SchemaVariantDefinitionError {
     InvalidState(String),
     #[error("No new asset was created")]
     NoAssetCreated,
+    #[error("func {0} is not a code generation func")]
+    NotCodeGenerationFunc(FuncId),
     #[error(transparent)]
     Pg(#[from] si_data_pg::PgError),
     #[error(transparent)]
@@ -123,6 +127,8 @@ pub enum SchemaVariantDefinitionError {
     SchemaVariantDefinition(#[from] DalSchemaVariantDefinitionError),
     #[error("could not find schema variant {0} connected to variant definition {1}")]
     SchemaVariantNotFound(SchemaVariantId, SchemaVariantDefinitionId),
+    #[error("schema variant {0} was not authored in this workspace")]
+    SchemaVariantNotUserOwned(SchemaVariantId),
     #[error(transparent)]
     SdfFunc(#[from] SdfFuncError),
     #[error("json serialization error: {0}")]
@@ -745,6 +751,10 @@ pub fn routes() -> Router<AppState> {
             "/create_variant_def",
             post(create_variant_def::create_variant_def),
         )
+        .route(
+            "/create_variant_def_from_spec",
+            post(create_variant_def_from_spec::create_variant_def_from_spec),
+        )
         .route(
             "/exec_variant_def",
             post(exec_variant_def::exec_variant_def),
@@ -753,4 +763,16 @@ pub fn routes() -> Router<AppState> {
             "/clone_variant_def",
             post(clone_variant_def::clone_variant_def),
         )
+        .route(
+            "/list_code_generation",
+            get(code_generation::list_code_generation),
+        )
+        .route(
+            "/add_code_generation",
+            post(code_generation::add_code_generation),
+        )
+        .route(
+            "/remove_code_generation",
+            post(code_generation::remove_code_generation),
+        )
 }
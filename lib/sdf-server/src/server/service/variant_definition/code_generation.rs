@@ -0,0 +1,262 @@
+use axum::extract::{OriginalUri, Query};
+use axum::{response::IntoResponse, Json};
+use dal::schema::variant::definition::SchemaVariantDefinition;
+use dal::{
+    AttributePrototypeId, ChangeSet, DalContext, Func, FuncBackendKind, FuncBackendResponseType,
+    FuncId, LeafInputLocation, LeafKind, SchemaVariant, SchemaVariantId, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{SchemaVariantDefinitionError, SchemaVariantDefinitionResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+use crate::service::func::get_leaf_function_inputs;
+use crate::service::func::list_funcs::ListedFuncView;
+
+/// A code generation [`Func`] populating an entry of "/root/code" on a schema variant.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeGenerationPrototypeView {
+    pub attribute_prototype_id: AttributePrototypeId,
+    pub func_id: FuncId,
+    pub func_name: String,
+    pub func_display_name: Option<String>,
+    pub inputs: Vec<LeafInputLocation>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCodeGenerationRequest {
+    pub schema_variant_id: SchemaVariantId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCodeGenerationResponse {
+    pub prototypes: Vec<CodeGenerationPrototypeView>,
+    /// The code generation funcs that could be added to the schema variant.
+    pub available_funcs: Vec<ListedFuncView>,
+}
+
+pub async fn list_code_generation(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListCodeGenerationRequest>,
+) -> SchemaVariantDefinitionResult<Json<ListCodeGenerationResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let prototypes = list_prototypes(&ctx, request.schema_variant_id).await?;
+
+    let mut available_funcs = Vec::new();
+    for func in Func::find_by_attr(
+        &ctx,
+        "backend_kind",
+        &FuncBackendKind::JsAttribute.as_ref().to_string(),
+    )
+    .await?
+    {
+        if func.hidden()
+            || func.backend_response_type() != &FuncBackendResponseType::CodeGeneration
+            || prototypes
+                .iter()
+                .any(|prototype| prototype.func_id == *func.id())
+        {
+            continue;
+        }
+        available_funcs.push(ListedFuncView {
+            id: *func.id(),
+            handler: func.handler().map(|handler| handler.to_owned()),
+            variant: (&func).try_into()?,
+            name: func.name().to_owned(),
+            display_name: func.display_name().map(Into::into),
+            is_builtin: func.builtin(),
+        });
+    }
+
+    Ok(Json(ListCodeGenerationResponse {
+        prototypes,
+        available_funcs,
+    }))
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AddCodeGenerationRequest {
+    pub schema_variant_id: SchemaVariantId,
+    pub func_id: FuncId,
+    /// Defaults to the inputs the func already uses elsewhere, or "/root/domain" if it isn't
+    /// used anywhere yet.
+    pub inputs: Option<Vec<LeafInputLocation>>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeGenerationResponse {
+    pub prototypes: Vec<CodeGenerationPrototypeView>,
+}
+
+pub async fn add_code_generation(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<AddCodeGenerationRequest>,
+) -> SchemaVariantDefinitionResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    ensure_user_owned(&ctx, request.schema_variant_id).await?;
+    let func = Func::get_by_id(&ctx, &request.func_id)
+        .await?
+        .ok_or(SchemaVariantDefinitionError::FuncNotFound(request.func_id))?;
+    if func.backend_response_type() != &FuncBackendResponseType::CodeGeneration {
+        return Err(SchemaVariantDefinitionError::NotCodeGenerationFunc(
+            request.func_id,
+        ));
+    }
+
+    let inputs = match request.inputs {
+        Some(inputs) => inputs,
+        None => {
+            let inputs = get_leaf_function_inputs(&ctx, *func.id()).await?;
+            if inputs.is_empty() {
+                vec![LeafInputLocation::Domain]
+            } else {
+                inputs
+            }
+        }
+    };
+
+    SchemaVariant::upsert_leaf_function(
+        &ctx,
+        request.schema_variant_id,
+        None,
+        LeafKind::CodeGeneration,
+        &inputs,
+        &func,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "add_code_generation_prototype",
+        serde_json::json!({
+            "schema_variant_id": request.schema_variant_id,
+            "func_id": func.id(),
+            "func_name": func.name(),
+        }),
+    );
+
+    let prototypes = list_prototypes(&ctx, request.schema_variant_id).await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&CodeGenerationResponse {
+            prototypes,
+        })?)?)
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveCodeGenerationRequest {
+    pub schema_variant_id: SchemaVariantId,
+    pub func_id: FuncId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn remove_code_generation(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RemoveCodeGenerationRequest>,
+) -> SchemaVariantDefinitionResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    ensure_user_owned(&ctx, request.schema_variant_id).await?;
+    SchemaVariant::remove_leaf(
+        &ctx,
+        request.schema_variant_id,
+        LeafKind::CodeGeneration,
+        request.func_id,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "remove_code_generation_prototype",
+        serde_json::json!({
+            "schema_variant_id": request.schema_variant_id,
+            "func_id": request.func_id,
+        }),
+    );
+
+    let prototypes = list_prototypes(&ctx, request.schema_variant_id).await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&CodeGenerationResponse {
+            prototypes,
+        })?)?)
+}
+
+async fn list_prototypes(
+    ctx: &DalContext,
+    schema_variant_id: SchemaVariantId,
+) -> SchemaVariantDefinitionResult<Vec<CodeGenerationPrototypeView>> {
+    let mut views = Vec::new();
+    for (attribute_prototype, func) in
+        SchemaVariant::find_leaf_item_functions(ctx, schema_variant_id, LeafKind::CodeGeneration)
+            .await?
+    {
+        views.push(CodeGenerationPrototypeView {
+            attribute_prototype_id: *attribute_prototype.id(),
+            func_id: *func.id(),
+            func_name: func.name().to_owned(),
+            func_display_name: func.display_name().map(Into::into),
+            inputs: get_leaf_function_inputs(ctx, *func.id()).await?,
+        });
+    }
+    Ok(views)
+}
+
+/// Only schema variants authored in the workspace, i.e. backed by a
+/// [`SchemaVariantDefinition`], can have their generators changed.
+async fn ensure_user_owned(
+    ctx: &DalContext,
+    schema_variant_id: SchemaVariantId,
+) -> SchemaVariantDefinitionResult<()> {
+    if SchemaVariantDefinition::get_by_schema_variant_id(ctx, &schema_variant_id)
+        .await?
+        .is_none()
+    {
+        return Err(SchemaVariantDefinitionError::SchemaVariantNotUserOwned(
+            schema_variant_id,
+        ));
+    }
+    Ok(())
+}
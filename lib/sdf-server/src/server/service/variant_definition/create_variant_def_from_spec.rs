@@ -0,0 +1,131 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use dal::{
+    pkg::attach_resource_payload_to_value,
+    schema::variant::definition::{
+        SchemaVariantDefinition, SchemaVariantDefinitionId, SchemaVariantDefinitionJson,
+        SchemaVariantDefinitionMetadataJson,
+    },
+    ChangeSet, Func, FuncBackendKind, FuncBackendResponseType, SchemaVariantId, StandardModel,
+    Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use super::exec_variant_def::{generate_scaffold_func_name, import_variant_definition, user_email};
+use super::SchemaVariantDefinitionResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVariantDefFromSpecRequest {
+    pub metadata: SchemaVariantDefinitionMetadataJson,
+    /// The same definition an asset function returns from `new AssetBuilder().build()`.
+    pub definition: SchemaVariantDefinitionJson,
+    #[serde(default)]
+    pub override_builtin_schema_feature_flag: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVariantDefFromSpecResponse {
+    pub id: SchemaVariantDefinitionId,
+    pub schema_variant_id: SchemaVariantId,
+    pub success: bool,
+}
+
+/// Create an asset from a declarative spec of its props, sockets and bindings and materialize its
+/// schema variant right away, without running an asset function.
+///
+/// The asset function of the new variant definition returns the spec as is, so the asset can be
+/// edited and executed again like any other.
+pub async fn create_variant_def_from_spec(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateVariantDefFromSpecRequest>,
+) -> SchemaVariantDefinitionResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let force_changeset_pk = ChangeSet::force_new(&mut ctx).await?;
+
+    let metadata = request.metadata;
+    let code = format!(
+        "function main() {{\n  return {};\n}}",
+        serde_json::to_string_pretty(&request.definition)?
+    );
+
+    let mut asset_func = Func::new(
+        &ctx,
+        generate_scaffold_func_name(metadata.name.clone()),
+        FuncBackendKind::JsSchemaVariantDefinition,
+        FuncBackendResponseType::SchemaVariantDefinition,
+    )
+    .await?;
+    asset_func.set_handler(&ctx, Some("main")).await?;
+    asset_func.set_code_plaintext(&ctx, Some(&code)).await?;
+
+    let mut variant_def = SchemaVariantDefinition::new(
+        &ctx,
+        metadata.name.clone(),
+        metadata.menu_name.clone(),
+        metadata.category.clone(),
+        metadata.link.clone(),
+        metadata.color.clone(),
+        metadata.component_kind,
+        metadata.description.clone(),
+        *asset_func.id(),
+    )
+    .await?;
+    variant_def
+        .set_component_type(&ctx, metadata.component_type)
+        .await?;
+
+    let user_email = user_email(&ctx).await?;
+    let (schema_variant_id, pkg_spec) = import_variant_definition(
+        &ctx,
+        &asset_func,
+        metadata.clone(),
+        request.definition,
+        &user_email,
+        request.override_builtin_schema_feature_flag,
+    )
+    .await?;
+    attach_resource_payload_to_value(&ctx, schema_variant_id).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "create_variant_def_from_spec",
+        serde_json::json!({
+                    "variant_def_category": metadata.category,
+                    "variant_def_name": metadata.name,
+                    "variant_def_id": variant_def.id(),
+                    "variant_def_schema_count":  pkg_spec.schemas.len(),
+        }),
+    );
+
+    WsEvent::schema_variant_definition_created(&ctx, *variant_def.id())
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    response = response.header("Content-Type", "application/json");
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(
+        response.body(serde_json::to_string(&CreateVariantDefFromSpecResponse {
+            id: *variant_def.id(),
+            schema_variant_id,
+            success: true,
+        })?)?,
+    )
+}
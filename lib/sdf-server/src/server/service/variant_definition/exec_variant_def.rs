@@ -12,8 +12,8 @@ use dal::{
     schema::variant::definition::{
         SchemaVariantDefinition, SchemaVariantDefinitionJson, SchemaVariantDefinitionMetadataJson,
     },
-    AttributePrototypeId, ChangeSet, Func, FuncBinding, FuncId, HistoryActor, SchemaVariant,
-    SchemaVariantError, SchemaVariantId, StandardModel, User,
+    AttributePrototypeId, ChangeSet, DalContext, Func, FuncBinding, FuncId, HistoryActor,
+    SchemaVariant, SchemaVariantError, SchemaVariantId, StandardModel, User,
 };
 use si_pkg::{
     FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType, FuncSpecData, PkgSpec, SiPkg,
@@ -68,13 +68,7 @@ pub async fn exec_variant_def(
     // Ensure we save all details before "exec"
     super::save_variant_def(&ctx, &request, Some(scaffold_func_name)).await?;
 
-    let user = match ctx.history_actor() {
-        HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk).await?,
-        _ => None,
-    };
-    let user_email = user
-        .map(|user| user.email().to_owned())
-        .unwrap_or("unauthenticated user email".into());
+    let user_email = user_email(&ctx).await?;
 
     let mut variant_def = SchemaVariantDefinition::get_by_id(&ctx, &request.id)
         .await?
@@ -138,76 +132,16 @@ pub async fn exec_variant_def(
         )
     };
 
-    let asset_func_built = {
-        let mut schema_variant_func_spec = FuncSpec::builder();
-        schema_variant_func_spec.name(asset_func.name());
-        schema_variant_func_spec.unique_id(asset_func.id().to_string());
-        let mut func_spec_data_builder = FuncSpecData::builder();
-        func_spec_data_builder
-            .name(asset_func.name())
-            .backend_kind(FuncSpecBackendKind::JsSchemaVariantDefinition)
-            .response_type(FuncSpecBackendResponseType::SchemaVariantDefinition)
-            .hidden(asset_func.hidden());
-        if let Some(code) = asset_func.code_plaintext()? {
-            func_spec_data_builder.code_plaintext(code);
-        }
-        if let Some(handler) = asset_func.handler() {
-            func_spec_data_builder.handler(handler.to_string());
-        }
-        if let Some(description) = asset_func.description() {
-            func_spec_data_builder.description(description.to_string());
-        }
-        if let Some(display_name) = asset_func.display_name() {
-            func_spec_data_builder.display_name(display_name.to_string());
-        }
-        schema_variant_func_spec
-            .data(func_spec_data_builder.build()?)
-            .build()?
-    };
-
-    let pkg_spec = {
-        // we need to change this to use the PkgImport
-        let identity_func_spec = IntrinsicFunc::Identity.to_spec()?;
-
-        let variant_spec = definition.to_spec(
-            metadata.clone(),
-            &identity_func_spec.unique_id,
-            &asset_func_built.unique_id,
-        )?;
-        let schema_spec = metadata.to_spec(variant_spec)?;
-        PkgSpec::builder()
-            .name(metadata.clone().name)
-            .created_by(&user_email)
-            .func(identity_func_spec)
-            .func(asset_func_built.clone())
-            .schema(schema_spec)
-            .version("0.0.1")
-            .build()?
-    };
-
-    let pkg = SiPkg::load_from_spec(pkg_spec.clone())?;
-
-    let (_, schema_variant_ids, _) = import_pkg_from_pkg(
+    let (schema_variant_id, pkg_spec) = import_variant_definition(
         &ctx,
-        &pkg,
-        Some(dal::pkg::ImportOptions {
-            schemas: None,
-            skip_import_funcs: Some(HashMap::from_iter([(
-                asset_func_built.unique_id.to_owned(),
-                asset_func.clone(),
-            )])),
-            no_record: true,
-            is_builtin: false,
-        }),
+        &asset_func,
+        metadata.clone(),
+        definition,
+        &user_email,
         request.override_builtin_schema_feature_flag,
     )
     .await?;
 
-    let schema_variant_id = schema_variant_ids
-        .get(0)
-        .copied()
-        .ok_or(SchemaVariantDefinitionError::NoAssetCreated)?;
-
     let detached_attribute_prototypes = match maybe_previous_variant_id {
         Some(previous_schema_variant_id) => {
             migrate_leaf_functions_to_new_schema_variant(
@@ -297,7 +231,102 @@ pub async fn exec_variant_def(
     )
 }
 
-fn generate_scaffold_func_name(name: String) -> String {
+/// The email of the user behind the request, recorded as the author of the generated package.
+pub(super) async fn user_email(ctx: &DalContext) -> SchemaVariantDefinitionResult<String> {
+    let user = match ctx.history_actor() {
+        HistoryActor::User(user_pk) => User::get_by_pk(ctx, *user_pk).await?,
+        _ => None,
+    };
+    Ok(user
+        .map(|user| user.email().to_owned())
+        .unwrap_or("unauthenticated user email".into()))
+}
+
+/// Materialize a [`SchemaVariantDefinitionJson`] as a new [`SchemaVariant`] by importing it as a
+/// package, alongside the asset [`Func`] it came from.
+pub(super) async fn import_variant_definition(
+    ctx: &DalContext,
+    asset_func: &Func,
+    metadata: SchemaVariantDefinitionMetadataJson,
+    definition: SchemaVariantDefinitionJson,
+    user_email: &str,
+    override_builtin_schema_feature_flag: bool,
+) -> SchemaVariantDefinitionResult<(SchemaVariantId, PkgSpec)> {
+    let asset_func_built = {
+        let mut schema_variant_func_spec = FuncSpec::builder();
+        schema_variant_func_spec.name(asset_func.name());
+        schema_variant_func_spec.unique_id(asset_func.id().to_string());
+        let mut func_spec_data_builder = FuncSpecData::builder();
+        func_spec_data_builder
+            .name(asset_func.name())
+            .backend_kind(FuncSpecBackendKind::JsSchemaVariantDefinition)
+            .response_type(FuncSpecBackendResponseType::SchemaVariantDefinition)
+            .hidden(asset_func.hidden());
+        if let Some(code) = asset_func.code_plaintext()? {
+            func_spec_data_builder.code_plaintext(code);
+        }
+        if let Some(handler) = asset_func.handler() {
+            func_spec_data_builder.handler(handler.to_string());
+        }
+        if let Some(description) = asset_func.description() {
+            func_spec_data_builder.description(description.to_string());
+        }
+        if let Some(display_name) = asset_func.display_name() {
+            func_spec_data_builder.display_name(display_name.to_string());
+        }
+        schema_variant_func_spec
+            .data(func_spec_data_builder.build()?)
+            .build()?
+    };
+
+    let pkg_spec = {
+        // we need to change this to use the PkgImport
+        let identity_func_spec = IntrinsicFunc::Identity.to_spec()?;
+
+        let variant_spec = definition.to_spec(
+            metadata.clone(),
+            &identity_func_spec.unique_id,
+            &asset_func_built.unique_id,
+        )?;
+        let schema_spec = metadata.to_spec(variant_spec)?;
+        PkgSpec::builder()
+            .name(metadata.name)
+            .created_by(user_email)
+            .func(identity_func_spec)
+            .func(asset_func_built.clone())
+            .schema(schema_spec)
+            .version("0.0.1")
+            .build()?
+    };
+
+    let pkg = SiPkg::load_from_spec(pkg_spec.clone())?;
+
+    let (_, schema_variant_ids, _) = import_pkg_from_pkg(
+        ctx,
+        &pkg,
+        Some(dal::pkg::ImportOptions {
+            schemas: None,
+            skip_import_funcs: Some(HashMap::from_iter([(
+                asset_func_built.unique_id.to_owned(),
+                asset_func.clone(),
+            )])),
+            no_record: true,
+            is_builtin: false,
+            upgrade_schemas: None,
+        }),
+        override_builtin_schema_feature_flag,
+    )
+    .await?;
+
+    let schema_variant_id = schema_variant_ids
+        .get(0)
+        .copied()
+        .ok_or(SchemaVariantDefinitionError::NoAssetCreated)?;
+
+    Ok((schema_variant_id, pkg_spec))
+}
+
+pub(super) fn generate_scaffold_func_name(name: String) -> String {
     let version = Utc::now().format("%Y%m%d%H%M").to_string();
     let generated_name = format!("{}Scaffold_{}", name.to_case(Case::Camel), version);
     generated_name
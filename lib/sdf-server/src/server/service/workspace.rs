@@ -0,0 +1,178 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use dal::workspace::digest::WorkspaceDigestError;
+use dal::workspace::script::MutationScriptError;
+use dal::{
+    ApiTokenError, DalContext, HistoryActor, PropPermissionError, TransactionsError, User,
+    UserError, WebhookError, WorkspacePk, WorkspaceRole,
+};
+use hyper::StatusCode;
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod create_api_token;
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod get_digest_config;
+pub mod get_mutation_script;
+pub mod list_api_tokens;
+pub mod list_mutation_scripts;
+pub mod list_prop_permissions;
+pub mod list_webhook_deliveries;
+pub mod list_webhooks;
+pub mod preview_digest;
+pub mod remove_prop_permission;
+pub mod revoke_api_token;
+pub mod run_mutation_script;
+pub mod save_mutation_script;
+pub mod set_digest_config;
+pub mod set_member_role;
+pub mod set_prop_permission;
+pub mod update_webhook;
+
+#[allow(clippy::large_enum_variant)]
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WorkspaceError {
+    #[error(transparent)]
+    ApiToken(#[from] ApiTokenError),
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+    #[error(transparent)]
+    MutationScript(#[from] MutationScriptError),
+    #[error("mutation script not found: {0}")]
+    MutationScriptNotFound(String),
+    #[error("only workspace admins can do this")]
+    NotAdmin,
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error(transparent)]
+    PropPermission(#[from] PropPermissionError),
+    #[error(transparent)]
+    User(#[from] UserError),
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+    #[error(transparent)]
+    WorkspaceDigest(#[from] WorkspaceDigestError),
+}
+
+pub type WorkspaceResult<T> = std::result::Result<T, WorkspaceError>;
+
+impl IntoResponse for WorkspaceError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            WorkspaceError::NoWorkspaceInTenancy => (StatusCode::BAD_REQUEST, self.to_string()),
+            WorkspaceError::NotAdmin => (StatusCode::FORBIDDEN, self.to_string()),
+            WorkspaceError::ApiToken(ApiTokenError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            WorkspaceError::MutationScriptNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            WorkspaceError::MutationScript(
+                MutationScriptError::MissingArgument(_, _)
+                | MutationScriptError::UndeclaredParameter(_, _)
+                | MutationScriptError::UnknownArgument(_, _),
+            ) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            WorkspaceError::PropPermission(PropPermissionError::InvalidPath(_)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            WorkspaceError::User(UserError::NotWorkspaceMember(_, _)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            WorkspaceError::Webhook(WebhookError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            WorkspaceError::Webhook(WebhookError::InvalidUrl(_) | WebhookError::NoEvents) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            WorkspaceError::WorkspaceDigest(WorkspaceDigestError::InvalidWindow(_)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+fn workspace_pk(ctx: &DalContext) -> WorkspaceResult<WorkspacePk> {
+    ctx.tenancy()
+        .workspace_pk()
+        .ok_or(WorkspaceError::NoWorkspaceInTenancy)
+}
+
+async fn ensure_admin(ctx: &DalContext) -> WorkspaceResult<()> {
+    if let HistoryActor::User(user_pk) = ctx.history_actor() {
+        let role = User::workspace_role(ctx, *user_pk, workspace_pk(ctx)?).await?;
+        if role != Some(WorkspaceRole::Admin) {
+            return Err(WorkspaceError::NotAdmin);
+        }
+    }
+    Ok(())
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/get_digest_config",
+            get(get_digest_config::get_digest_config),
+        )
+        .route(
+            "/set_digest_config",
+            post(set_digest_config::set_digest_config),
+        )
+        .route("/preview_digest", get(preview_digest::preview_digest))
+        .route(
+            "/list_prop_permissions",
+            get(list_prop_permissions::list_prop_permissions),
+        )
+        .route(
+            "/set_prop_permission",
+            post(set_prop_permission::set_prop_permission),
+        )
+        .route(
+            "/remove_prop_permission",
+            post(remove_prop_permission::remove_prop_permission),
+        )
+        .route("/set_member_role", post(set_member_role::set_member_role))
+        .route(
+            "/list_mutation_scripts",
+            get(list_mutation_scripts::list_mutation_scripts),
+        )
+        .route(
+            "/get_mutation_script",
+            get(get_mutation_script::get_mutation_script),
+        )
+        .route(
+            "/save_mutation_script",
+            post(save_mutation_script::save_mutation_script),
+        )
+        .route(
+            "/run_mutation_script",
+            post(run_mutation_script::run_mutation_script),
+        )
+        .route("/list_api_tokens", get(list_api_tokens::list_api_tokens))
+        .route(
+            "/create_api_token",
+            post(create_api_token::create_api_token),
+        )
+        .route(
+            "/revoke_api_token",
+            post(revoke_api_token::revoke_api_token),
+        )
+        .route("/list_webhooks", get(list_webhooks::list_webhooks))
+        .route("/create_webhook", post(create_webhook::create_webhook))
+        .route("/update_webhook", post(update_webhook::update_webhook))
+        .route("/delete_webhook", post(delete_webhook::delete_webhook))
+        .route(
+            "/list_webhook_deliveries",
+            get(list_webhook_deliveries::list_webhook_deliveries),
+        )
+}
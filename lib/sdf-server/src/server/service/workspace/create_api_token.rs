@@ -0,0 +1,58 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use dal::{ApiToken, ApiTokenScope, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, Authorization, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scope: ApiTokenScope,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiTokenResponse {
+    pub api_token: ApiToken,
+    /// Only ever returned here.
+    pub token: String,
+}
+
+pub async fn create_api_token(
+    HandlerContext(builder): HandlerContext,
+    // Tokens are managed with a user session, never with another token.
+    Authorization(_claim): Authorization,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateApiTokenRequest>,
+) -> WorkspaceResult<Json<CreateApiTokenResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    let (api_token, token) =
+        ApiToken::new(&ctx, &request.name, request.scope, request.expires_at).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "create_api_token",
+        serde_json::json!({
+            "api_token_pk": api_token.pk(),
+            "scope": api_token.scope(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateApiTokenResponse { api_token, token }))
+}
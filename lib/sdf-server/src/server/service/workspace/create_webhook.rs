@@ -0,0 +1,53 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Visibility, Webhook, WebhookEventKind};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<WebhookEventKind>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+pub type CreateWebhookResponse = Webhook;
+
+pub async fn create_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateWebhookRequest>,
+) -> WorkspaceResult<Json<CreateWebhookResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    let webhook = Webhook::new(&ctx, &request.url, request.events, request.enabled).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "create_webhook",
+        serde_json::json!({
+            "webhook_pk": webhook.pk(),
+            "events": webhook.events(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(webhook))
+}
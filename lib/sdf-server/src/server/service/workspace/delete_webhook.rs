@@ -0,0 +1,45 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Visibility, Webhook, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWebhookRequest {
+    pub pk: WebhookPk,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type DeleteWebhookResponse = Webhook;
+
+pub async fn delete_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<DeleteWebhookRequest>,
+) -> WorkspaceResult<Json<DeleteWebhookResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    let webhook = Webhook::delete(&ctx, request.pk).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "delete_webhook",
+        serde_json::json!({
+            "webhook_pk": webhook.pk(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(webhook))
+}
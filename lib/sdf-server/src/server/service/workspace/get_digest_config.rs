@@ -0,0 +1,28 @@
+use axum::{extract::Query, Json};
+use dal::workspace::digest::WorkspaceDigestConfig;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::{workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDigestConfigRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type GetDigestConfigResponse = Option<WorkspaceDigestConfig>;
+
+pub async fn get_digest_config(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetDigestConfigRequest>,
+) -> WorkspaceResult<Json<GetDigestConfigResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let config = WorkspaceDigestConfig::get(&ctx, workspace_pk(&ctx)?).await?;
+
+    Ok(Json(config))
+}
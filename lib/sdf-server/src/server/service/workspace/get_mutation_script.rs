@@ -0,0 +1,43 @@
+use axum::{extract::Query, Json};
+use dal::workspace::script::MutationScript;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMutationScriptRequest {
+    pub name: String,
+    /// The latest version is returned if not provided.
+    pub version: Option<i32>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMutationScriptResponse {
+    pub script: MutationScript,
+    pub versions: Vec<i32>,
+}
+
+pub async fn get_mutation_script(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetMutationScriptRequest>,
+) -> WorkspaceResult<Json<GetMutationScriptResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let script = MutationScript::get(&ctx, &request.name, request.version)
+        .await?
+        .ok_or_else(|| WorkspaceError::MutationScriptNotFound(request.name.clone()))?;
+    let versions = MutationScript::list_versions(&ctx, &request.name)
+        .await?
+        .iter()
+        .map(MutationScript::version)
+        .collect();
+
+    Ok(Json(GetMutationScriptResponse { script, versions }))
+}
@@ -0,0 +1,28 @@
+use axum::{extract::Query, Json};
+use dal::{ApiToken, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, Authorization, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApiTokensRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListApiTokensResponse = Vec<ApiToken>;
+
+pub async fn list_api_tokens(
+    HandlerContext(builder): HandlerContext,
+    Authorization(_claim): Authorization,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListApiTokensRequest>,
+) -> WorkspaceResult<Json<ListApiTokensResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let api_tokens = ApiToken::list(&ctx).await?;
+
+    Ok(Json(api_tokens))
+}
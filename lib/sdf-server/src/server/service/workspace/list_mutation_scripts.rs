@@ -0,0 +1,28 @@
+use axum::{extract::Query, Json};
+use dal::workspace::script::MutationScript;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMutationScriptsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListMutationScriptsResponse = Vec<MutationScript>;
+
+pub async fn list_mutation_scripts(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListMutationScriptsRequest>,
+) -> WorkspaceResult<Json<ListMutationScriptsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let scripts = MutationScript::list(&ctx).await?;
+
+    Ok(Json(scripts))
+}
@@ -0,0 +1,27 @@
+use axum::{extract::Query, Json};
+use dal::{PropPermission, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPropPermissionsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListPropPermissionsResponse = Vec<PropPermission>;
+
+pub async fn list_prop_permissions(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListPropPermissionsRequest>,
+) -> WorkspaceResult<Json<ListPropPermissionsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let permissions = PropPermission::list(&ctx, workspace_pk(&ctx)?).await?;
+
+    Ok(Json(permissions))
+}
@@ -0,0 +1,38 @@
+use axum::{extract::Query, Json};
+use dal::{Visibility, Webhook, WebhookDelivery, WebhookError, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+/// The number of deliveries returned when the request doesn't say.
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookDeliveriesRequest {
+    pub pk: WebhookPk,
+    pub limit: Option<i64>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListWebhookDeliveriesResponse = Vec<WebhookDelivery>;
+
+/// List the most recent deliveries of a webhook, most recent first.
+pub async fn list_webhook_deliveries(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListWebhookDeliveriesRequest>,
+) -> WorkspaceResult<Json<ListWebhookDeliveriesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    if Webhook::get_by_pk(&ctx, request.pk).await?.is_none() {
+        return Err(WebhookError::NotFound(request.pk).into());
+    }
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let deliveries = WebhookDelivery::list_for_webhook(&ctx, request.pk, limit).await?;
+
+    Ok(Json(deliveries))
+}
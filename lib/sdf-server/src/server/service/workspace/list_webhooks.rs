@@ -0,0 +1,27 @@
+use axum::{extract::Query, Json};
+use dal::{Visibility, Webhook};
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type ListWebhooksResponse = Vec<Webhook>;
+
+pub async fn list_webhooks(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListWebhooksRequest>,
+) -> WorkspaceResult<Json<ListWebhooksResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let webhooks = Webhook::list(&ctx).await?;
+
+    Ok(Json(webhooks))
+}
@@ -0,0 +1,42 @@
+use axum::{extract::Query, Json};
+use chrono::Utc;
+use dal::workspace::digest::{WorkspaceDigest, WorkspaceDigestConfig};
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::{workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+const DEFAULT_WINDOW_HOURS: i32 = 24;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewDigestRequest {
+    /// Defaults to the configured window, or a day if the workspace has no configuration.
+    pub window_hours: Option<i32>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type PreviewDigestResponse = WorkspaceDigest;
+
+/// Render the digest the workspace would receive now, without delivering it.
+pub async fn preview_digest(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<PreviewDigestRequest>,
+) -> WorkspaceResult<Json<PreviewDigestResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    let workspace_pk = workspace_pk(&ctx)?;
+
+    let window_hours = match request.window_hours {
+        Some(window_hours) => window_hours,
+        None => WorkspaceDigestConfig::get(&ctx, workspace_pk)
+            .await?
+            .map(|config| config.window_hours())
+            .unwrap_or(DEFAULT_WINDOW_HOURS),
+    };
+    let digest = WorkspaceDigest::build(&ctx, workspace_pk, window_hours, Utc::now()).await?;
+
+    Ok(Json(digest))
+}
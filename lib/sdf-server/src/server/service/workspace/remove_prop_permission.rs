@@ -0,0 +1,43 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{PropPermission, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovePropPermissionRequest {
+    pub path: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn remove_prop_permission(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RemovePropPermissionRequest>,
+) -> WorkspaceResult<Json<()>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    PropPermission::remove(&ctx, workspace_pk(&ctx)?, &request.path).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "remove_prop_permission",
+        serde_json::json!({
+            "path": request.path,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
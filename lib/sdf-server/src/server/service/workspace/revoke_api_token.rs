@@ -0,0 +1,46 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{ApiToken, ApiTokenPk, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, Authorization, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeApiTokenRequest {
+    pub pk: ApiTokenPk,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type RevokeApiTokenResponse = ApiToken;
+
+pub async fn revoke_api_token(
+    HandlerContext(builder): HandlerContext,
+    Authorization(_claim): Authorization,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RevokeApiTokenRequest>,
+) -> WorkspaceResult<Json<RevokeApiTokenResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    let api_token = ApiToken::revoke(&ctx, request.pk).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "revoke_api_token",
+        serde_json::json!({
+            "api_token_pk": api_token.pk(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(api_token))
+}
@@ -0,0 +1,59 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::workspace::script::MutationScript;
+use dal::{ChangeSetPk, Visibility};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunMutationScriptRequest {
+    pub name: String,
+    /// The latest version is run if not provided.
+    pub version: Option<i32>,
+    #[serde(default)]
+    pub arguments: HashMap<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunMutationScriptResponse {
+    pub change_set_pk: ChangeSetPk,
+}
+
+pub async fn run_mutation_script(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RunMutationScriptRequest>,
+) -> WorkspaceResult<Json<RunMutationScriptResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let script = MutationScript::get(&ctx, &request.name, request.version)
+        .await?
+        .ok_or_else(|| WorkspaceError::MutationScriptNotFound(request.name.clone()))?;
+    let change_set_pk = script.run(&ctx, request.arguments).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "run_mutation_script",
+        serde_json::json!({
+            "name": script.name(),
+            "version": script.version(),
+            "change_set_pk": change_set_pk,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RunMutationScriptResponse { change_set_pk }))
+}
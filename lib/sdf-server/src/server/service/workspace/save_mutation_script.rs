@@ -0,0 +1,58 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::workspace::script::{MutationScript, MutationScriptOperation, MutationScriptParameter};
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveMutationScriptRequest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<MutationScriptParameter>,
+    pub operations: Vec<MutationScriptOperation>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type SaveMutationScriptResponse = MutationScript;
+
+pub async fn save_mutation_script(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SaveMutationScriptRequest>,
+) -> WorkspaceResult<Json<SaveMutationScriptResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let script = MutationScript::save(
+        &ctx,
+        &request.name,
+        request.description,
+        request.parameters,
+        request.operations,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "save_mutation_script",
+        serde_json::json!({
+            "name": script.name(),
+            "version": script.version(),
+            "operations": script.operations().len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(script))
+}
@@ -0,0 +1,55 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::workspace::digest::WorkspaceDigestConfig;
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::{workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetDigestConfigRequest {
+    pub enabled: bool,
+    pub window_hours: i32,
+    pub webhook_url: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type SetDigestConfigResponse = WorkspaceDigestConfig;
+
+pub async fn set_digest_config(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetDigestConfigRequest>,
+) -> WorkspaceResult<Json<SetDigestConfigResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let config = WorkspaceDigestConfig::upsert(
+        &ctx,
+        workspace_pk(&ctx)?,
+        request.enabled,
+        request.window_hours,
+        request.webhook_url,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_workspace_digest_config",
+        serde_json::json!({
+            "enabled": config.enabled(),
+            "window_hours": config.window_hours(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(config))
+}
@@ -0,0 +1,45 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{User, UserPk, Visibility, WorkspaceRole};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMemberRoleRequest {
+    pub user_pk: UserPk,
+    pub role: WorkspaceRole,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn set_member_role(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetMemberRoleRequest>,
+) -> WorkspaceResult<Json<()>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    User::set_workspace_role(&ctx, request.user_pk, workspace_pk(&ctx)?, request.role).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_workspace_member_role",
+        serde_json::json!({
+            "user_pk": request.user_pk,
+            "role": request.role,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
@@ -0,0 +1,54 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{PropPermission, Visibility, WorkspaceRole};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, workspace_pk, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPropPermissionRequest {
+    /// The path of the restricted prop, e.g. "/root/secrets".
+    pub path: String,
+    pub allowed_roles: Vec<WorkspaceRole>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type SetPropPermissionResponse = PropPermission;
+
+pub async fn set_prop_permission(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetPropPermissionRequest>,
+) -> WorkspaceResult<Json<SetPropPermissionResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    let permission = PropPermission::upsert(
+        &ctx,
+        workspace_pk(&ctx)?,
+        &request.path,
+        request.allowed_roles,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_prop_permission",
+        serde_json::json!({
+            "path": permission.path(),
+            "allowed_roles": permission.allowed_roles(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(permission))
+}
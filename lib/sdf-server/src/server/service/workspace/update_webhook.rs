@@ -0,0 +1,57 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Visibility, Webhook, WebhookEventKind, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_admin, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookRequest {
+    pub pk: WebhookPk,
+    pub url: String,
+    pub events: Vec<WebhookEventKind>,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type UpdateWebhookResponse = Webhook;
+
+pub async fn update_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> WorkspaceResult<Json<UpdateWebhookResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+    ensure_admin(&ctx).await?;
+
+    let webhook = Webhook::update(
+        &ctx,
+        request.pk,
+        &request.url,
+        request.events,
+        request.enabled,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "update_webhook",
+        serde_json::json!({
+            "webhook_pk": webhook.pk(),
+            "events": webhook.events(),
+            "enabled": webhook.enabled(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(webhook))
+}
@@ -3,32 +3,35 @@ use axum::{
     extract::{ws::WebSocket, State, WebSocketUpgrade},
     response::IntoResponse,
 };
-use dal::WorkspacePk;
+use dal::{DalContextBuilder, UserPk, WorkspacePk};
 use si_data_nats::NatsClient;
 use telemetry::prelude::*;
 use tokio::sync::broadcast;
 
 use crate::server::{
-    extract::{Nats, WsAuthorization},
+    extract::{HandlerContext, Nats, WsAuthorization},
     state::ShutdownBroadcast,
 };
 
-#[instrument(skip(wsu, nats))]
+#[instrument(skip(wsu, nats, builder))]
 #[allow(clippy::unused_async)]
 pub async fn workspace_updates(
     wsu: WebSocketUpgrade,
     Nats(nats): Nats,
+    HandlerContext(builder): HandlerContext,
     WsAuthorization(claim): WsAuthorization,
     State(shutdown_broadcast): State<ShutdownBroadcast>,
 ) -> Result<impl IntoResponse, WsError> {
     async fn handle_socket(
         socket: WebSocket,
         nats: NatsClient,
+        builder: DalContextBuilder,
         mut shutdown: broadcast::Receiver<()>,
         workspace_pk: WorkspacePk,
+        user_pk: UserPk,
     ) {
         tokio::select! {
-            _ = run_workspace_updates_proto(socket, nats, workspace_pk) => {
+            _ = run_workspace_updates_proto(socket, nats, builder, workspace_pk, user_pk) => {
                 trace!("finished workspace_updates proto");
             }
             _ = shutdown.recv() => {
@@ -41,15 +44,29 @@ pub async fn workspace_updates(
     }
 
     let shutdown = shutdown_broadcast.subscribe();
-    Ok(wsu.on_upgrade(move |socket| handle_socket(socket, nats, shutdown, claim.workspace_pk)))
+    Ok(wsu.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            nats,
+            builder,
+            shutdown,
+            claim.workspace_pk,
+            claim.user_pk,
+        )
+    }))
 }
 
 async fn run_workspace_updates_proto(
     mut socket: WebSocket,
     nats: NatsClient,
+    builder: DalContextBuilder,
     workspace_pk: WorkspacePk,
+    user_pk: UserPk,
 ) {
-    let proto = match workspace_updates::run(nats, workspace_pk).start().await {
+    let proto = match workspace_updates::run(nats, builder, workspace_pk, user_pk)
+        .start()
+        .await
+    {
         Ok(started) => started,
         Err(err) => {
             // This is likely due to nats failing to subscribe to the required topic, which is
@@ -77,9 +94,10 @@ mod workspace_updates {
     use std::error::Error;
 
     use axum::extract::ws::{self, WebSocket};
+    use dal::ws_event::WS_EVENT_REPLAY_LIMIT;
     use dal::{
-        user::CursorPayload, user::OnlinePayload, ChangeSetPk, UserPk, WorkspacePk, WsEvent,
-        WsEventError,
+        user::CursorPayload, user::OnlinePayload, ChangeSetPk, DalContextBuilder, Tenancy,
+        TransactionsError, UserPk, WorkspacePk, WsEvent, WsEventError,
     };
     use futures::StreamExt;
     use serde::{Deserialize, Serialize};
@@ -110,10 +128,31 @@ mod workspace_updates {
             change_set_pk: Option<ChangeSetPk>,
             idle: bool,
         },
+        /// Sent by a reconnecting client with the cursor of the last event it received: the
+        /// replayable events it missed are sent down the WebSocket, followed by a
+        /// "ReplayFinished" event.
+        #[serde(rename_all = "camelCase")]
+        SubscribeFrom { cursor: i64 },
     }
 
-    pub fn run(nats: NatsClient, workspace_pk: WorkspacePk) -> WorkspaceUpdates {
-        WorkspaceUpdates { nats, workspace_pk }
+    /// The part of a published event the replay needs to skip duplicates.
+    #[derive(Deserialize)]
+    struct EventCursor {
+        cursor: Option<i64>,
+    }
+
+    pub fn run(
+        nats: NatsClient,
+        builder: DalContextBuilder,
+        workspace_pk: WorkspacePk,
+        user_pk: UserPk,
+    ) -> WorkspaceUpdates {
+        WorkspaceUpdates {
+            nats,
+            builder,
+            workspace_pk,
+            user_pk,
+        }
     }
 
     #[remain::sorted]
@@ -127,6 +166,8 @@ mod workspace_updates {
         Serde(#[from] serde_json::Error),
         #[error("failed to subscribe to subject {1}")]
         Subscribe(#[source] NatsError, String),
+        #[error("transactions error: {0}")]
+        Transactions(#[from] TransactionsError),
         #[error("error when closing websocket")]
         WsClose(#[source] axum::Error),
         #[error("wsevent error: {0}")]
@@ -140,7 +181,9 @@ mod workspace_updates {
     #[derive(Debug)]
     pub struct WorkspaceUpdates {
         nats: NatsClient,
+        builder: DalContextBuilder,
         workspace_pk: WorkspacePk,
+        user_pk: UserPk,
     }
 
     impl WorkspaceUpdates {
@@ -154,8 +197,11 @@ mod workspace_updates {
 
             Ok(WorkspaceUpdatesStarted {
                 nats: self.nats.clone(),
+                builder: self.builder,
                 workspace_pk: self.workspace_pk,
+                user_pk: self.user_pk,
                 subscriber,
+                replayed_through: None,
             })
         }
     }
@@ -163,8 +209,13 @@ mod workspace_updates {
     #[derive(Debug)]
     pub struct WorkspaceUpdatesStarted {
         workspace_pk: WorkspacePk,
+        user_pk: UserPk,
         nats: NatsClient,
+        builder: DalContextBuilder,
         subscriber: Subscriber,
+        /// The cursor of the last event replayed, if the client asked for a replay. Live events
+        /// up to it were already sent.
+        replayed_through: Option<i64>,
     }
 
     impl WorkspaceUpdatesStarted {
@@ -185,7 +236,6 @@ mod workspace_updates {
                                 };
                                 match event {
                                     WebsocketEventRequest::Cursor { user_pk, user_name, change_set_pk, container, container_key, x, y } => {
-                                        let subject = format!("si.workspace_pk.{}.event", self.workspace_pk);
                                         let event = WsEvent::cursor(self.workspace_pk, change_set_pk.unwrap_or(ChangeSetPk::NONE), CursorPayload {
                                             user_pk,
                                             user_name,
@@ -194,10 +244,9 @@ mod workspace_updates {
                                             container,
                                             container_key
                                         }).await?;
-                                        self.nats.publish(subject, serde_json::to_vec(&event)?.into()).await?;
+                                        event.publish_ephemeral(&self.nats).await?;
                                     }
                                     WebsocketEventRequest::Online { user_pk, name, picture_url, change_set_pk, idle } => {
-                                        let subject = format!("si.workspace_pk.{}.event", self.workspace_pk);
                                         let event = WsEvent::online(self.workspace_pk, OnlinePayload {
                                             user_pk,
                                             name,
@@ -205,7 +254,12 @@ mod workspace_updates {
                                             change_set_pk,
                                             idle,
                                         }).await?;
-                                        self.nats.publish(subject, serde_json::to_vec(&event)?.into()).await?;
+                                        event.publish_ephemeral(&self.nats).await?;
+                                    }
+                                    WebsocketEventRequest::SubscribeFrom { cursor } => {
+                                        if let Some(closing) = self.replay(ws, cursor).await? {
+                                            return Ok(closing);
+                                        }
                                     }
                                 }
                             },
@@ -214,25 +268,16 @@ mod workspace_updates {
                         }
                     }
                     Some(nats_msg) = self.subscriber.next() => {
+                        if !self.is_addressed_to_user(nats_msg.subject().as_str()) {
+                            continue;
+                        }
+                        if self.was_replayed(nats_msg.payload()) {
+                            continue;
+                        }
                         let msg = ws::Message::Text(String::from_utf8_lossy(nats_msg.payload()).to_string());
 
-                        if let Err(err) = ws.send(msg).await {
-                            match err
-                                .source()
-                                .and_then(|err| err.downcast_ref::<tungstenite::Error>())
-                            {
-                                Some(ws_err) => match ws_err {
-                                    // If the websocket has cleanly closed, we should cleanly finish as
-                                    // well--this is not an error condition
-                                    tungstenite::Error::ConnectionClosed
-                                    | tungstenite::Error::AlreadyClosed => {
-                                        trace!("websocket has cleanly closed, ending");
-                                        return Ok(WorkspaceUpdatesClosing { ws_is_closed: true });
-                                    }
-                                    _ => return Err(WorkspaceUpdatesError::WsSendIo(err)),
-                                },
-                                None => return Err(WorkspaceUpdatesError::WsSendIo(err)),
-                            }
+                        if let Some(closing) = send(ws, msg).await? {
+                            return Ok(closing);
                         }
                     }
                     else => break,
@@ -243,6 +288,74 @@ mod workspace_updates {
                 ws_is_closed: false,
             })
         }
+
+        /// Sends the events recorded after the cursor down the WebSocket, then a "ReplayFinished"
+        /// event telling the client whether that was everything it missed.
+        async fn replay(
+            &mut self,
+            ws: &mut WebSocket,
+            cursor: i64,
+        ) -> Result<Option<WorkspaceUpdatesClosing>> {
+            let mut ctx = self.builder.build_default().await?;
+            ctx.update_tenancy(Tenancy::new(self.workspace_pk));
+            let replay = WsEvent::replay(&ctx, cursor, WS_EVENT_REPLAY_LIMIT).await?;
+            ctx.rollback().await?;
+
+            for event in &replay.events {
+                let msg = ws::Message::Text(serde_json::to_string(event)?);
+                if let Some(closing) = send(ws, msg).await? {
+                    return Ok(Some(closing));
+                }
+            }
+            let finished = WsEvent::replay_finished(self.workspace_pk, cursor, &replay);
+            self.replayed_through = replay.events.last().and_then(WsEvent::cursor);
+            send(ws, ws::Message::Text(serde_json::to_string(&finished)?)).await
+        }
+
+        /// Events published between the subscription and the replay are both replayed and
+        /// waiting on the subscriber, so the ones that were already replayed are skipped.
+        fn was_replayed(&self, payload: &[u8]) -> bool {
+            match self.replayed_through {
+                Some(replayed_through) => matches!(
+                    serde_json::from_slice::<EventCursor>(payload),
+                    Ok(EventCursor { cursor: Some(cursor) }) if cursor <= replayed_through
+                ),
+                None => false,
+            }
+        }
+
+        /// Owner notifications are published on "si.workspace_pk.<pk>.owner.user.<user_pk>.event"
+        /// and must only reach that user. Team-owned notifications, like everything else, go to
+        /// the whole workspace.
+        fn is_addressed_to_user(&self, subject: &str) -> bool {
+            let owner_prefix = format!("si.workspace_pk.{}.owner.user.", self.workspace_pk);
+            match subject.strip_prefix(&owner_prefix) {
+                Some(rest) => rest.starts_with(&format!("{}.", self.user_pk)),
+                None => true,
+            }
+        }
+    }
+
+    /// Sends a message down the WebSocket, returning how to close if the client went away.
+    async fn send(ws: &mut WebSocket, msg: ws::Message) -> Result<Option<WorkspaceUpdatesClosing>> {
+        if let Err(err) = ws.send(msg).await {
+            match err
+                .source()
+                .and_then(|err| err.downcast_ref::<tungstenite::Error>())
+            {
+                Some(ws_err) => match ws_err {
+                    // If the websocket has cleanly closed, we should cleanly finish as
+                    // well--this is not an error condition
+                    tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+                        trace!("websocket has cleanly closed, ending");
+                        return Ok(Some(WorkspaceUpdatesClosing { ws_is_closed: true }));
+                    }
+                    _ => return Err(WorkspaceUpdatesError::WsSendIo(err)),
+                },
+                None => return Err(WorkspaceUpdatesError::WsSendIo(err)),
+            }
+        }
+        Ok(None)
     }
 
     #[derive(Debug)]
@@ -33,7 +33,11 @@ async fn get_components_metadata(
         .await
         .expect("cannot commit transaction");
 
-    let request = GetComponentsMetadataRequest { visibility };
+    let request = GetComponentsMetadataRequest {
+        cursor: None,
+        limit: None,
+        visibility,
+    };
 
     let response: GetComponentsMetadataResponse = api_request_auth_query(
         app,
@@ -359,6 +359,8 @@ impl ScenarioHarness {
     /// Get the latest [`Diagram`] for the workspace.
     async fn get_diagram(&self, visibility: &Visibility) -> Diagram {
         let request = GetDiagramRequest {
+            cursor: None,
+            limit: None,
             visibility: *visibility,
         };
         let response: Diagram = self.query_get("/api/diagram/get_diagram", &request).await;
@@ -560,6 +562,7 @@ impl ScenarioHarness {
         assert!(!ctx.visibility().is_head());
         let request = ApplyChangeSetRequest {
             change_set_pk: ctx.visibility().change_set_pk,
+            fix_target_filter: None,
         };
         let _response: ApplyChangeSetResponse = self
             .query_post("/api/change_set/apply_change_set", &request)
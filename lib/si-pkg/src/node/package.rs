@@ -9,13 +9,14 @@ use object_tree::{
     NodeChild, NodeKind, NodeWithChildren, ReadBytes, WriteBytes,
 };
 
-use crate::{PkgSpec, SiPkgKind};
+use crate::{PkgDependencySpec, PkgSpec, SiPkgKind};
 
 use super::{category::PackageCategory, PkgNode};
 
 const KEY_CREATED_AT_STR: &str = "created_at";
 const KEY_CREATED_BY_STR: &str = "created_by";
 const KEY_DEFAULT_CHANGE_SET: &str = "default_change_set";
+const KEY_DEPENDENCIES_STR: &str = "dependencies";
 const KEY_DESCRIPTION_STR: &str = "description";
 const KEY_KIND_STR: &str = "kind";
 const KEY_NAME_STR: &str = "name";
@@ -35,6 +36,7 @@ pub struct PackageNode {
     pub default_change_set: Option<String>,
     pub workspace_pk: Option<String>,
     pub workspace_name: Option<String>,
+    pub dependencies: Vec<PkgDependencySpec>,
 }
 
 impl NameStr for PackageNode {
@@ -60,6 +62,13 @@ impl WriteBytes for PackageNode {
         if let Some(workspace_name) = &self.workspace_name {
             write_key_value_line(writer, KEY_WORKSPACE_NAME_STR, workspace_name.as_str())?;
         }
+        if !self.dependencies.is_empty() {
+            write_key_value_line(
+                writer,
+                KEY_DEPENDENCIES_STR,
+                serde_json::to_string(&self.dependencies).map_err(GraphError::parse)?,
+            )?;
+        }
         Ok(())
     }
 }
@@ -84,6 +93,12 @@ impl ReadBytes for PackageNode {
         let default_change_set = read_key_value_line_opt(reader, KEY_DEFAULT_CHANGE_SET)?;
         let workspace_pk = read_key_value_line_opt(reader, KEY_WORKSPACE_PK_STR)?;
         let workspace_name = read_key_value_line_opt(reader, KEY_WORKSPACE_NAME_STR)?;
+        let dependencies = match read_key_value_line_opt(reader, KEY_DEPENDENCIES_STR)? {
+            None => vec![],
+            Some(dependencies_str) => {
+                serde_json::from_str(&dependencies_str).map_err(GraphError::parse)?
+            }
+        };
 
         Ok(Some(Self {
             kind,
@@ -95,6 +110,7 @@ impl ReadBytes for PackageNode {
             default_change_set,
             workspace_pk,
             workspace_name,
+            dependencies,
         }))
     }
 }
@@ -115,6 +131,7 @@ impl NodeChild for PkgSpec {
                 default_change_set: self.default_change_set.to_owned(),
                 workspace_pk: self.workspace_pk.to_owned(),
                 workspace_name: self.workspace_name.to_owned(),
+                dependencies: self.dependencies.to_owned(),
             }),
             match self.kind {
                 SiPkgKind::Module => vec![
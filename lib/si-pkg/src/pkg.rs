@@ -37,7 +37,7 @@ pub use {
 
 use crate::{
     node::{CategoryNode, PkgNode},
-    spec::{FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SpecError},
+    spec::{FuncSpec, PkgDependencySpec, PkgSpec, SchemaVariantSpecPropRoot, SpecError},
 };
 
 #[remain::sorted]
@@ -233,6 +233,10 @@ impl SiPkg {
             builder.workspace_name(workspace_name);
         }
 
+        for dependency in metadata.dependencies() {
+            builder.dependency(dependency.clone());
+        }
+
         for func in self.funcs()? {
             builder.func(FuncSpec::try_from(func)?);
         }
@@ -364,6 +368,7 @@ pub struct SiPkgMetadata {
     default_change_set: Option<String>,
     workspace_pk: Option<String>,
     workspace_name: Option<String>,
+    dependencies: Vec<PkgDependencySpec>,
     hash: Hash,
 }
 
@@ -390,6 +395,7 @@ impl SiPkgMetadata {
             default_change_set: metadata_node.default_change_set,
             workspace_pk: metadata_node.workspace_pk,
             workspace_name: metadata_node.workspace_name,
+            dependencies: metadata_node.dependencies,
             hash: metadata_hashed_node.hash(),
         })
     }
@@ -430,6 +436,10 @@ impl SiPkgMetadata {
         self.workspace_name.as_deref()
     }
 
+    pub fn dependencies(&self) -> &[PkgDependencySpec] {
+        &self.dependencies
+    }
+
     pub fn hash(&self) -> Hash {
         self.hash
     }
@@ -9,6 +9,7 @@ mod attribute_value;
 mod authentication_func;
 mod change_set;
 mod component;
+mod dependency;
 mod edge;
 mod func;
 mod leaf_function;
@@ -23,8 +24,8 @@ mod variant;
 
 pub use {
     action_func::*, attr_func_input::*, attribute_value::*, authentication_func::*, change_set::*,
-    component::*, edge::*, func::*, leaf_function::*, map_key_func::*, position::*, prop::*,
-    root_prop_func::*, schema::*, si_prop_func::*, socket::*, variant::*,
+    component::*, dependency::*, edge::*, func::*, leaf_function::*, map_key_func::*, position::*,
+    prop::*, root_prop_func::*, schema::*, si_prop_func::*, socket::*, variant::*,
 };
 
 use super::SiPkgKind;
@@ -54,6 +55,10 @@ pub struct PkgSpec {
     #[builder(setter(into, strip_option), default)]
     pub workspace_name: Option<String>,
 
+    #[builder(setter(each(name = "dependency", into)), default)]
+    #[serde(default)]
+    pub dependencies: Vec<PkgDependencySpec>,
+
     #[builder(setter(each(name = "schema", into)), default)]
     #[serde(default)]
     pub schemas: Vec<SchemaSpec>,
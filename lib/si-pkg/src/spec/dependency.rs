@@ -0,0 +1,24 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::SpecError;
+
+/// A requirement on another package that must be installed before the declaring package can be
+/// installed. When `root_hash` is set, only that exact build of the package satisfies it.
+#[derive(Builder, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct PkgDependencySpec {
+    #[builder(setter(into))]
+    pub name: String,
+
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default)]
+    pub root_hash: Option<String>,
+}
+
+impl PkgDependencySpec {
+    pub fn builder() -> PkgDependencySpecBuilder {
+        PkgDependencySpecBuilder::default()
+    }
+}
@@ -65,6 +65,7 @@ pub enum FuncSpecBackendKind {
     Diff,
     Identity,
     Integer,
+    Join,
     JsAction,
     JsAttribute,
     JsAuthentication,
@@ -73,9 +74,12 @@ pub enum FuncSpecBackendKind {
     JsValidation,
     Map,
     Object,
+    Select,
     String,
+    Template,
     Unset,
     Validation,
+    WasmAttribute,
 }
 
 #[remain::sorted]
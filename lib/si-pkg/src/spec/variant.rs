@@ -44,6 +44,9 @@ pub enum SchemaVariantSpecComponentType {
     ConfigurationFrameDown,
     #[strum(serialize = "ConfigurationFrameUp", serialize = "configurationFrameUp")]
     ConfigurationFrameUp,
+    #[serde(alias = "Subsystem")]
+    #[strum(serialize = "Subsystem", serialize = "subsystem")]
+    Subsystem,
 }
 
 #[remain::sorted]
@@ -16,19 +16,24 @@ use proc_macro2::{Ident, TokenStream};
 use syn::{parse_quote, punctuated::Punctuated, token::Comma, Expr, FnArg, ItemFn, Type};
 
 use crate::{
-    expand::{expand_test, FnSetup, FnSetupExpander},
+    expand::{expand_test, is_fault_injector_arg, FnSetup, FnSetupExpander},
     path_as_string, Args,
 };
 
 pub(crate) fn expand(item: ItemFn, args: Args) -> TokenStream {
-    let fn_setup = fn_setup(item.sig.inputs.iter());
+    let fn_setup = fn_setup(item.sig.inputs.iter(), &args);
 
     expand_test(item, args, fn_setup)
 }
 
-fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
+fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg> + Clone, args: &Args) -> DalTestFnSetup {
     let mut expander = DalTestFnSetupExpander::new();
 
+    // Faults need the test context to be built with them, before any other setup
+    if args.faults.is_enabled() || params.clone().any(is_fault_injector_arg) {
+        expander.set_with_faults(Some(()));
+    }
+
     for param in params {
         match param {
             FnArg::Typed(pat_type) => match &*pat_type.ty {
@@ -74,6 +79,11 @@ fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
                                 let var = var.as_ref();
                                 expander.push_arg(parse_quote! {#var});
                             }
+                            "FaultInjector" => {
+                                let var = expander.setup_fault_injector();
+                                let var = var.as_ref();
+                                expander.push_arg(parse_quote! {#var});
+                            }
                             "PingaShutdownHandle" => {
                                 let var = expander.setup_pinga_shutdown_handle();
                                 let var = var.as_ref();
@@ -173,6 +183,11 @@ fn fn_setup<'a>(params: impl Iterator<Item = &'a FnArg>) -> DalTestFnSetup {
         expander.setup_start_veritech_server();
         expander.setup_start_pinga_server();
         expander.setup_start_council_server();
+        if args.faults.is_enabled() {
+            expander.setup_apply_faults(&args.faults);
+        }
+    } else if args.faults.is_enabled() {
+        panic!("fault injection options require the test function to take arguments");
     }
 
     expander.finish()
@@ -193,7 +208,9 @@ struct DalTestFnSetupExpander {
     code: TokenStream,
     args: Punctuated<Expr, Comma>,
 
+    with_faults: Option<()>,
     test_context: Option<Rc<Ident>>,
+    fault_injector: Option<Rc<Ident>>,
     nats_subject_prefix: Option<Rc<Ident>>,
     council_server: Option<Rc<Ident>>,
     start_council_server: Option<()>,
@@ -219,7 +236,9 @@ impl DalTestFnSetupExpander {
         Self {
             code: TokenStream::new(),
             args: Punctuated::new(),
+            with_faults: None,
             test_context: None,
+            fault_injector: None,
             nats_subject_prefix: None,
             council_server: None,
             start_council_server: None,
@@ -262,6 +281,14 @@ impl FnSetupExpander for DalTestFnSetupExpander {
         self.args.push(arg);
     }
 
+    fn with_faults(&self) -> Option<()> {
+        self.with_faults
+    }
+
+    fn set_with_faults(&mut self, value: Option<()>) {
+        self.with_faults = value;
+    }
+
     fn test_context(&self) -> Option<&Rc<Ident>> {
         self.test_context.as_ref()
     }
@@ -270,6 +297,14 @@ impl FnSetupExpander for DalTestFnSetupExpander {
         self.test_context = value;
     }
 
+    fn fault_injector(&self) -> Option<&Rc<Ident>> {
+        self.fault_injector.as_ref()
+    }
+
+    fn set_fault_injector(&mut self, value: Option<Rc<Ident>>) {
+        self.fault_injector = value;
+    }
+
     fn nats_subject_prefix(&self) -> Option<&Rc<Ident>> {
         self.nats_subject_prefix.as_ref()
     }
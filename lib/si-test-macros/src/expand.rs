@@ -2,10 +2,11 @@ use std::rc::Rc;
 
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::quote;
-use syn::{punctuated::Punctuated, token::Comma, Expr, ItemFn, ReturnType};
+use syn::{punctuated::Punctuated, token::Comma, Expr, FnArg, ItemFn, ReturnType, Type};
 
 use crate::{
-    Args, LOG_ENV_VAR, RT_DEFAULT_THREAD_STACK_SIZE, RT_DEFAULT_WORKER_THREADS, SPAN_EVENTS_ENV_VAR,
+    path_as_string, Args, Faults, LOG_ENV_VAR, RT_DEFAULT_THREAD_STACK_SIZE,
+    RT_DEFAULT_WORKER_THREADS, SPAN_EVENTS_ENV_VAR,
 };
 
 pub(crate) trait FnSetup {
@@ -199,13 +200,33 @@ fn expand_runtime(worker_threads: usize, thread_stack_size: usize) -> TokenStrea
     }
 }
 
+/// Whether the test function takes a `FaultInjector`, which needs the test context to be built
+/// with faults.
+pub(crate) fn is_fault_injector_arg(param: &FnArg) -> bool {
+    match param {
+        FnArg::Typed(pat_type) => match &*pat_type.ty {
+            Type::Path(type_path) => {
+                path_as_string(&type_path.path).split("::").last() == Some("FaultInjector")
+            }
+            _ => false,
+        },
+        FnArg::Receiver(_) => false,
+    }
+}
+
 pub(crate) trait FnSetupExpander {
     fn code_extend<I: IntoIterator<Item = TokenTree>>(&mut self, stream: I);
     fn push_arg(&mut self, arg: Expr);
 
+    fn with_faults(&self) -> Option<()>;
+    fn set_with_faults(&mut self, value: Option<()>);
+
     fn test_context(&self) -> Option<&Rc<Ident>>;
     fn set_test_context(&mut self, value: Option<Rc<Ident>>);
 
+    fn fault_injector(&self) -> Option<&Rc<Ident>>;
+    fn set_fault_injector(&mut self, value: Option<Rc<Ident>>);
+
     fn nats_subject_prefix(&self) -> Option<&Rc<Ident>>;
     fn set_nats_subject_prefix(&mut self, value: Option<Rc<Ident>>);
 
@@ -266,14 +287,50 @@ pub(crate) trait FnSetupExpander {
         }
 
         let var = Ident::new("test_context", Span::call_site());
-        self.code_extend(quote! {
-            let test_context = ::dal_test::TestContext::global(crate::TEST_PG_DBNAME).await?;
-        });
+        if self.with_faults().is_some() {
+            self.code_extend(quote! {
+                let test_context =
+                    ::dal_test::TestContext::global_with_faults(crate::TEST_PG_DBNAME).await?;
+            });
+        } else {
+            self.code_extend(quote! {
+                let test_context = ::dal_test::TestContext::global(crate::TEST_PG_DBNAME).await?;
+            });
+        }
         self.set_test_context(Some(Rc::new(var)));
 
         self.test_context().unwrap().clone()
     }
 
+    fn setup_fault_injector(&mut self) -> Rc<Ident> {
+        if let Some(ident) = self.fault_injector() {
+            return ident.clone();
+        }
+
+        let test_context = self.setup_test_context();
+        let test_context = test_context.as_ref();
+
+        let var = Ident::new("fault_injector", Span::call_site());
+        self.code_extend(quote! {
+            let #var = #test_context.fault_injector()?;
+        });
+        self.set_fault_injector(Some(Rc::new(var)));
+
+        self.fault_injector().unwrap().clone()
+    }
+
+    /// Starts injecting the faults set with the macro options. This must come last in the setup so
+    /// that the setup itself is not faulted.
+    fn setup_apply_faults(&mut self, faults: &Faults) {
+        let fault_injector = self.setup_fault_injector();
+        let fault_injector = fault_injector.as_ref();
+        let config = faults.expand_config();
+
+        self.code_extend(quote! {
+            #fault_injector.apply(#config);
+        });
+    }
+
     fn setup_nats_subject_prefix(&mut self) -> Rc<Ident> {
         if let Some(ident) = self.nats_subject_prefix() {
             return ident.clone();
@@ -7,11 +7,12 @@ mod sdf_test;
 use std::collections::HashSet;
 
 use proc_macro::TokenStream;
+use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
-    Ident, ItemFn, Path, Token,
+    Ident, ItemFn, LitInt, Path, Token,
 };
 
 const LOG_ENV_VAR: &str = "SI_TEST_LOG";
@@ -20,18 +21,94 @@ const SPAN_EVENTS_ENV_VAR: &str = "SI_TEST_LOG_SPAN_EVENTS";
 const RT_DEFAULT_WORKER_THREADS: usize = 2;
 const RT_DEFAULT_THREAD_STACK_SIZE: usize = 2 * 1024 * 1024 * 3;
 
-#[allow(dead_code)] // We aren't current using args on the macro, but when we do we can drop this
+#[allow(dead_code)] // We aren't current using vars on the macro, but when we do we can drop this
                     // line
 struct Args {
     pub(crate) vars: HashSet<Ident>,
+    pub(crate) faults: Faults,
 }
 
 impl Parse for Args {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let vars = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
-        Ok(Self {
-            vars: vars.into_iter().collect(),
-        })
+        let mut vars = HashSet::new();
+        let mut faults = Faults::default();
+        for arg in Punctuated::<Arg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                Arg::Var(var) => {
+                    vars.insert(var);
+                }
+                Arg::Option(name, value) => faults.set(&name, &value)?,
+            }
+        }
+        Ok(Self { vars, faults })
+    }
+}
+
+enum Arg {
+    Var(Ident),
+    Option(Ident, LitInt),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Ok(Self::Option(name, input.parse()?))
+        } else {
+            Ok(Self::Var(name))
+        }
+    }
+}
+
+/// The faults to inject into a test's connections once its setup is done, set with macro options
+/// such as `#[test(pg_fail_every = 3)]`.
+#[derive(Default)]
+struct Faults {
+    nats_drop_messages: Option<u64>,
+    nats_latency_ms: Option<u64>,
+    pg_fail_every: Option<u64>,
+    pg_latency_ms: Option<u64>,
+}
+
+impl Faults {
+    fn set(&mut self, name: &Ident, value: &LitInt) -> syn::Result<()> {
+        let field = match name.to_string().as_str() {
+            "nats_drop_messages" => &mut self.nats_drop_messages,
+            "nats_latency_ms" => &mut self.nats_latency_ms,
+            "pg_fail_every" => &mut self.pg_fail_every,
+            "pg_latency_ms" => &mut self.pg_latency_ms,
+            _ => return Err(syn::Error::new(name.span(), "unknown test option")),
+        };
+        *field = Some(value.base10_parse()?);
+        Ok(())
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.nats_drop_messages.is_some()
+            || self.nats_latency_ms.is_some()
+            || self.pg_fail_every.is_some()
+            || self.pg_latency_ms.is_some()
+    }
+
+    /// Expands to the `::dal_test::FaultConfig` to apply.
+    pub(crate) fn expand_config(&self) -> proc_macro2::TokenStream {
+        let nats_drop_messages = self.nats_drop_messages.unwrap_or_default();
+        let nats_latency_ms = self.nats_latency_ms.unwrap_or_default();
+        let pg_fail_every = match self.pg_fail_every {
+            Some(every) => quote! {Some(#every)},
+            None => quote! {None},
+        };
+        let pg_latency_ms = self.pg_latency_ms.unwrap_or_default();
+
+        quote! {
+            ::dal_test::FaultConfig {
+                nats_drop_messages: #nats_drop_messages,
+                nats_latency: ::std::time::Duration::from_millis(#nats_latency_ms),
+                pg_fail_every: #pg_fail_every,
+                pg_latency: ::std::time::Duration::from_millis(#pg_latency_ms),
+            }
+        }
     }
 }
 
@@ -147,6 +224,8 @@ fn path_as_string(path: &Path) -> String {
 ///    for a visibility which is not in a change set
 /// * `DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>`: a mutable reference to a DAL context
 ///    for a workspace for a visibility which is not in a change set
+/// * `faults: FaultInjector`: controls the faults injected into the test's connections (see
+///    below)
 /// * `pinga_handle: PingaShutdownHandle`: the shutdown handle for the Pinga server running
 ///    alongside each test
 /// * `services_ctx: ServicesContext`: a services context object, used to create DAL contexts
@@ -169,6 +248,28 @@ fn path_as_string(path: &Path) -> String {
 /// * `nw: &WorkspaceSignup`: a reference to the full "new-workspace" data structure,
 ///    created for this test
 ///
+/// # Fault Injection
+///
+/// Options on the attribute run the test's PostgreSQL and NATS connections through fault
+/// injecting proxies, which start injecting once the setup is done:
+///
+/// ```ignore
+/// #[test(pg_fail_every = 3, nats_drop_messages = 1)]
+/// async fn recovers(ctx: &DalContext) {
+///     // ...
+/// }
+/// ```
+///
+/// The following options are valid:
+///
+/// * `nats_drop_messages`: the number of messages published to NATS to drop
+/// * `nats_latency_ms`: how long to hold every message published to NATS
+/// * `pg_fail_every`: fail every kth PostgreSQL query, by severing its connection
+/// * `pg_latency_ms`: how long to hold every PostgreSQL query
+///
+/// Taking a `FaultInjector` argument also runs the connections through the proxies, and lets the
+/// test change the faults as it goes.
+///
 /// # Customized Tokio Runtime
 ///
 /// The attribute uses a similar strategy to the stock `#[tokio::test]` attribute, except that this
@@ -323,6 +424,8 @@ pub fn dal_test(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///    for a visibility which is not in a change set
 /// * `DalContextHeadMutRef(ctx): DalContextHeadMutRef<'_>`: a mutable reference to a DAL context
 ///    for a workspace for a visibility which is not in a change set
+/// * `faults: FaultInjector`: controls the faults injected into the test's connections (see
+///    below)
 /// * `pinga_handle: PingaShutdownHandle`: the shutdown handle for the Pinga server running
 ///    alongside each test
 /// * `services_ctx: ServicesContext`: a services context object, used to create DAL contexts
@@ -345,6 +448,28 @@ pub fn dal_test(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// * `nw: &WorkspaceSignup`: a reference to the full "new-workspace" data structure,
 ///    created for this test
 ///
+/// # Fault Injection
+///
+/// Options on the attribute run the test's PostgreSQL and NATS connections through fault
+/// injecting proxies, which start injecting once the setup is done:
+///
+/// ```ignore
+/// #[test(pg_fail_every = 3, nats_drop_messages = 1)]
+/// async fn recovers(ctx: &DalContext) {
+///     // ...
+/// }
+/// ```
+///
+/// The following options are valid:
+///
+/// * `nats_drop_messages`: the number of messages published to NATS to drop
+/// * `nats_latency_ms`: how long to hold every message published to NATS
+/// * `pg_fail_every`: fail every kth PostgreSQL query, by severing its connection
+/// * `pg_latency_ms`: how long to hold every PostgreSQL query
+///
+/// Taking a `FaultInjector` argument also runs the connections through the proxies, and lets the
+/// test change the faults as it goes.
+///
 /// # Customized Tokio Runtime
 ///
 /// The attribute uses a similar strategy to the stock `#[tokio::test]` attribute, except that this
@@ -3,6 +3,7 @@ use nats_subscriber::{Subscriber, SubscriberError};
 use serde::{de::DeserializeOwned, Serialize};
 use si_data_nats::NatsClient;
 use telemetry::prelude::*;
+use telemetry_nats::empty_injected_headers;
 use thiserror::Error;
 use tokio::sync::mpsc;
 use veritech_core::{
@@ -249,8 +250,14 @@ impl Client {
         // Root reply mailbox will receive a reply if nobody is listening to the channel `subject`
         let mut root_subscriber = self.nats.subscribe(reply_mailbox_root.clone()).await?;
 
+        // Propagate the current trace so the function execution in veritech joins it
         self.nats
-            .publish_with_reply(subject, reply_mailbox_root.clone(), msg.into())
+            .publish_with_reply_and_headers(
+                subject,
+                reply_mailbox_root.clone(),
+                empty_injected_headers(),
+                msg.into(),
+            )
             .await?;
 
         tokio::select! {
@@ -271,6 +271,17 @@ async fn process_resolver_function_requests(
     Ok(())
 }
 
+#[instrument(
+    name = "veritech.resolver_function_request",
+    parent = &request.process_span,
+    skip_all,
+    level = "info",
+    fields(
+        si.func.execution_id = request.payload.execution_id.as_str(),
+        messaging.operation = "process",
+        otel.kind = SpanKind::Consumer.as_str(),
+    )
+)]
 async fn resolver_function_request_task(
     nats: NatsClient,
     cyclone_pool: Pool<LocalUdsInstanceSpec>,
@@ -427,6 +438,17 @@ async fn process_validation_requests(
     Ok(())
 }
 
+#[instrument(
+    name = "veritech.validation_request",
+    parent = &request.process_span,
+    skip_all,
+    level = "info",
+    fields(
+        si.func.execution_id = request.payload.execution_id.as_str(),
+        messaging.operation = "process",
+        otel.kind = SpanKind::Consumer.as_str(),
+    )
+)]
 async fn validation_request_task(
     nats: NatsClient,
     cyclone_pool: Pool<LocalUdsInstanceSpec>,
@@ -547,6 +569,17 @@ async fn process_schema_variant_definition_requests(
     Ok(())
 }
 
+#[instrument(
+    name = "veritech.schema_variant_definition_request",
+    parent = &request.process_span,
+    skip_all,
+    level = "info",
+    fields(
+        si.func.execution_id = request.payload.execution_id.as_str(),
+        messaging.operation = "process",
+        otel.kind = SpanKind::Consumer.as_str(),
+    )
+)]
 async fn schema_variant_definition_request_task(
     nats: NatsClient,
     cyclone_pool: Pool<LocalUdsInstanceSpec>,
@@ -661,6 +694,17 @@ async fn process_action_run_requests(
     Ok(())
 }
 
+#[instrument(
+    name = "veritech.action_run_request",
+    parent = &request.process_span,
+    skip_all,
+    level = "info",
+    fields(
+        si.func.execution_id = request.payload.execution_id.as_str(),
+        messaging.operation = "process",
+        otel.kind = SpanKind::Consumer.as_str(),
+    )
+)]
 async fn action_run_request_task(
     nats: NatsClient,
     cyclone_pool: Pool<LocalUdsInstanceSpec>,
@@ -776,6 +820,17 @@ async fn process_reconciliation_requests(
     Ok(())
 }
 
+#[instrument(
+    name = "veritech.reconciliation_request",
+    parent = &request.process_span,
+    skip_all,
+    level = "info",
+    fields(
+        si.func.execution_id = request.payload.execution_id.as_str(),
+        messaging.operation = "process",
+        otel.kind = SpanKind::Consumer.as_str(),
+    )
+)]
 async fn reconciliation_request_task(
     nats: NatsClient,
     cyclone_pool: Pool<LocalUdsInstanceSpec>,